@@ -1,31 +1,52 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coin, ensure, to_json_binary, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Reply, Response, StdError, StdResult, SubMsg, SubMsgResult, Uint128, WasmMsg,
+    coin, ensure, from_json, to_json_binary, to_json_string, Addr, Attribute, BankMsg, Binary,
+    Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Isqrt, MessageInfo, Reply, Response, StdError,
+    StdResult, Storage, SubMsg, SubMsgResult, Uint128, Uint256, WasmMsg,
 };
+use covenant_oracle::query_price;
 use covenant_utils::{
-    astroport::query_astro_pool_token,
+    astroport::{
+        query_astro_pool_token, query_liquidity_token_address, query_liquidity_token_balance,
+    },
+    clock::increment_tick_nonce,
+    multi_asset::get_proportional_amounts,
     op_mode::{verify_caller, ContractOperationMode},
-    withdraw_lp_helper::WithdrawLPMsgs,
+    pool_ownership::{validate_max_pool_ownership, PoolOwnership},
+    withdraw_lp_helper::{WithdrawLPMsgs, EMERGENCY_COMMITTEE_ADDR},
 };
 use cw2::set_contract_version;
 
 use astroport::{
-    asset::{Asset, PairInfo},
+    asset::{Asset, AssetInfo, PairInfo},
     factory::PairType,
-    pair::{Cw20HookMsg, ExecuteMsg::ProvideLiquidity, PoolResponse, SimulationResponse},
+    generator::{
+        Cw20HookMsg as GeneratorCw20HookMsg, ExecuteMsg as GeneratorExecuteMsg,
+        QueryMsg as GeneratorQueryMsg,
+    },
+    pair::{
+        ConfigResponse, CumulativePricesResponse, Cw20HookMsg, ExecuteMsg::ProvideLiquidity,
+        PoolResponse, SimulationResponse,
+    },
+    pair_concentrated::{ConcentratedPoolParams, QueryMsg as PclQueryMsg},
     DecimalCheckedOps,
 };
-use cw20::Cw20ExecuteMsg;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
 use crate::{
     error::ContractError,
     msg::{
-        ContractState, DecimalRange, ExecuteMsg, InstantiateMsg, LpConfig, MigrateMsg,
-        ProvidedLiquidityInfo, QueryMsg,
+        ContractState, CumulativeRatioSnapshot, DecimalRange, ExecuteMsg, ExpectedLpOut,
+        InstantiateMsg, LpConfig, MigrateMsg, PairTypeMigrationPolicy, ProvideSimulation,
+        ProvidedLiquidityInfo, ProvisionAction, ProvisionFailure, ProvisionSimulation, QueryMsg,
+    },
+    state::{
+        EXPECTED_LP_OUT, FUNDING_EXPIRATION, HOLDER_ADDRESS, LAST_FAILURE, LP_CONFIG,
+        MAX_POOL_OWNERSHIP, ORACLE_PRICE_GUARD, PAIR_TYPE_MIGRATION_POLICY, PENDING_PROVISION,
+        PRE_WITHDRAW_BALANCES, PROVIDED_LIQUIDITY_INFO, PROVISIONING_PAUSED, TWAP_SNAPSHOTS,
+        WITHDRAWN_LIQUIDITY,
     },
-    state::{HOLDER_ADDRESS, LP_CONFIG, PROVIDED_LIQUIDITY_INFO},
 };
 
 use neutron_sdk::NeutronResult;
@@ -38,11 +59,15 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const DOUBLE_SIDED_REPLY_ID: u64 = 321u64;
 const SINGLE_SIDED_REPLY_ID: u64 = 322u64;
 const SWAP_REPLY_ID: u64 = 323u64;
+const WITHDRAW_REPLY_ID: u64 = 324u64;
+
+/// precision the astroport pairs accumulate their cumulative prices with
+const TWAP_PRECISION: u32 = 6;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -64,6 +89,14 @@ pub fn instantiate(
         ContractError::PairTypeMismatch {}
     );
 
+    // validate the assets we plan to provide
+    msg.assets.validate()?;
+    msg.single_side_lp_limits.validate()?;
+    ensure!(
+        !(msg.assets.is_multi_asset() && msg.pair_type == PairType::Xyk {}),
+        ContractError::XykMultiAssetError {}
+    );
+
     // contract starts at Instantiated state
     CONTRACT_STATE.save(deps.storage, &ContractState::Instantiated)?;
 
@@ -71,11 +104,63 @@ pub fn instantiate(
     CONTRACT_OP_MODE.save(deps.storage, &op_mode)?;
     HOLDER_ADDRESS.save(deps.storage, &holder_addr)?;
 
+    let pair_type_migration_policy = msg.pair_type_migration_policy.unwrap_or_default();
+    PAIR_TYPE_MIGRATION_POLICY.save(deps.storage, &pair_type_migration_policy)?;
+
+    if let Some(addr) = &msg.emergency_committee {
+        let committee_addr = deps.api.addr_validate(addr)?;
+        EMERGENCY_COMMITTEE_ADDR.save(deps.storage, &committee_addr)?;
+    }
+
+    if let Some(guard) = &msg.oracle_price_guard {
+        guard.validate(deps.api)?;
+        ORACLE_PRICE_GUARD.save(deps.storage, guard)?;
+    }
+
+    validate_max_pool_ownership(msg.max_pool_ownership)?;
+    let generator_address = msg
+        .generator_address
+        .as_ref()
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    if let Some(max_pool_ownership) = msg.max_pool_ownership {
+        MAX_POOL_OWNERSHIP.save(deps.storage, &max_pool_ownership)?;
+    }
+
+    let funding_expiration = match &msg.funding_deadline {
+        Some(deadline) => {
+            deadline.validate(&env.block)?;
+            let expiration = deadline.clone().into_expiration(&env.block);
+            FUNDING_EXPIRATION.save(deps.storage, &expiration)?;
+            Some(expiration)
+        }
+        None => None,
+    };
+
     let decimal_range = DecimalRange::try_from(
         msg.pool_price_config.expected_spot_price,
         msg.pool_price_config.acceptable_price_spread,
     )?;
 
+    // cw20 pool assets are configured by their contract address
+    let cw20_assets = pool_response
+        .asset_infos
+        .iter()
+        .filter_map(|asset_info| match asset_info {
+            AssetInfo::Token { contract_addr } => Some(contract_addr.clone()),
+            AssetInfo::NativeToken { .. } => None,
+        })
+        .collect();
+
+    ensure!(
+        msg.twap_window != Some(0),
+        ContractError::InvalidTwapWindow {}
+    );
+    if let Some(rebalance) = &msg.single_side_rebalance {
+        rebalance.validate()?;
+    }
+    validate_min_lp_out_tolerance(msg.min_lp_out_tolerance)?;
+
     let lp_config = LpConfig {
         pool_address: pool_addr,
         single_side_lp_limits: msg.single_side_lp_limits,
@@ -83,8 +168,18 @@ pub fn instantiate(
         expected_pool_ratio_range: decimal_range,
         pair_type: msg.pair_type,
         asset_data: msg.assets,
+        generator_address,
+        cw20_assets,
+        twap_window: msg.twap_window,
+        single_side_rebalance: msg.single_side_rebalance,
+        min_lp_out_tolerance: msg.min_lp_out_tolerance,
+        max_provision_per_tick: msg.max_provision_per_tick,
     };
+    validate_pcl_config(&lp_config)?;
+    validate_max_provision_per_tick(&lp_config)?;
     LP_CONFIG.save(deps.storage, &lp_config)?;
+    // the time-weighted ratio becomes available one window from now
+    record_twap_snapshot(deps.branch(), &env, &lp_config)?;
 
     // we begin with no liquidity provided
     PROVIDED_LIQUIDITY_INFO.save(
@@ -92,12 +187,31 @@ pub fn instantiate(
         &ProvidedLiquidityInfo {
             provided_coin_a: coin(0, lp_config.asset_data.asset_a_denom.as_str()),
             provided_coin_b: coin(0, lp_config.asset_data.asset_b_denom.as_str()),
+            provided_additional_coins: vec![],
         },
     )?;
 
     Ok(Response::default()
         .add_attribute("method", "lp_instantiate")
         .add_attribute("op_mode", format!("{:?}", op_mode))
+        .add_attribute(
+            "pair_type_migration_policy",
+            format!("{:?}", pair_type_migration_policy),
+        )
+        .add_attribute(
+            "max_pool_ownership",
+            match msg.max_pool_ownership {
+                Some(max_pool_ownership) => max_pool_ownership.to_string(),
+                None => "None".to_string(),
+            },
+        )
+        .add_attribute(
+            "funding_expiration",
+            match funding_expiration {
+                Some(expiration) => expiration.to_string(),
+                None => "None".to_string(),
+            },
+        )
         .add_attributes(lp_config.to_response_attributes()))
 }
 
@@ -109,9 +223,318 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Tick {} => try_tick(deps, env, info),
+        ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
+            Ok(try_tick(deps, env, info)?.add_attribute("tick_nonce", tick_nonce.to_string()))
+        }
         ExecuteMsg::Withdraw { percentage } => try_withdraw(deps, env, info, percentage),
+        ExecuteMsg::UpdatePairTypeMigrationPolicy { policy } => {
+            try_update_pair_type_migration_policy(deps, info, policy)
+        }
+        ExecuteMsg::AcceptPairTypeMigration {} => try_accept_pair_type_migration(deps, info),
+        ExecuteMsg::SetProvisioningPaused { paused } => {
+            try_set_provisioning_paused(deps, info, paused)
+        }
+        ExecuteMsg::DonateLpTokens { receiver } => try_donate_lp_tokens(deps, env, info, receiver),
+        ExecuteMsg::StakeLpTokens { generator } => try_stake_lp_tokens(deps, env, info, generator),
+        ExecuteMsg::ReturnIdleFunds {} => try_return_idle_funds(deps, env, info),
+        ExecuteMsg::Receive(cw20_msg) => try_receive_cw20(deps, info, cw20_msg),
+    }
+}
+
+/// accepts cw20 pool assets sent with a hook. like native funds, they
+/// are provided as liquidity on the following ticks.
+fn try_receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+    ensure!(
+        lp_config.is_cw20_asset(info.sender.as_str()),
+        ContractError::UnsupportedCw20 {
+            token: info.sender.to_string()
+        }
+    );
+
+    Ok(Response::default()
+        .add_attribute("method", "try_receive_cw20")
+        .add_attribute("token", info.sender.to_string())
+        .add_attribute("sender", cw20_msg.sender)
+        .add_attribute("amount", cw20_msg.amount))
+}
+
+/// stakes all LP tokens into the generator on behalf of the holder,
+/// which accrues the rewards and unstakes them before withdrawing.
+fn try_stake_lp_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    generator: String,
+) -> Result<Response, ContractError> {
+    let holder_addr = HOLDER_ADDRESS.load(deps.storage)?;
+    ensure!(info.sender == holder_addr, ContractError::NotHolder {});
+    let generator = deps.api.addr_validate(&generator)?;
+
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+    let lp_token_info = query_astro_pool_token(
+        deps.querier,
+        lp_config.pool_address.to_string(),
+        env.contract.address.to_string(),
+    )?;
+    let lp_token_balance = lp_token_info.balance_response.balance;
+
+    let mut resp = Response::default()
+        .add_attribute("method", "try_stake_lp_tokens")
+        .add_attribute("generator", generator.to_string())
+        .add_attribute("amount", lp_token_balance);
+
+    // liquidity is provided over several ticks, so there may be
+    // nothing new to stake
+    if !lp_token_balance.is_zero() {
+        resp = resp.add_message(WasmMsg::Execute {
+            contract_addr: lp_token_info.pair_info.liquidity_token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: generator.to_string(),
+                amount: lp_token_balance,
+                msg: to_json_binary(&GeneratorCw20HookMsg::DepositFor(holder_addr.to_string()))?,
+            })?,
+            funds: vec![],
+        });
+    }
+
+    Ok(resp)
+}
+
+/// gives up the LP position by transferring all LP tokens to the receiver.
+/// used by the holder to get rid of positions that remain unclaimed.
+fn try_donate_lp_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: String,
+) -> Result<Response, ContractError> {
+    let holder_addr = HOLDER_ADDRESS.load(deps.storage)?;
+    ensure!(info.sender == holder_addr, ContractError::NotHolder {});
+    let receiver = deps.api.addr_validate(&receiver)?;
+
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+    let lp_token_info = query_astro_pool_token(
+        deps.querier,
+        lp_config.pool_address.to_string(),
+        env.contract.address.to_string(),
+    )?;
+    let lp_token = lp_token_info.pair_info.liquidity_token.to_string();
+    let staked_lp_amount = query_staked_lp_amount(deps.as_ref(), &env, &lp_config, &lp_token)?;
+    let lp_token_balance = lp_token_info
+        .balance_response
+        .balance
+        .checked_add(staked_lp_amount)?;
+
+    let mut resp = Response::default()
+        .add_attribute("method", "try_donate_lp_tokens")
+        .add_attribute("receiver", receiver.to_string())
+        .add_attribute("amount", lp_token_balance);
+
+    // auto-staked LP tokens are donated as well
+    if let Some(unstake_msg) = get_unstake_msg(&lp_config, &lp_token, staked_lp_amount)? {
+        resp = resp.add_message(unstake_msg);
+    }
+
+    // nothing to donate should not block the holder from completing
+    if !lp_token_balance.is_zero() {
+        resp = resp.add_message(WasmMsg::Execute {
+            contract_addr: lp_token_info.pair_info.liquidity_token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: receiver.to_string(),
+                amount: lp_token_balance,
+            })?,
+            funds: vec![],
+        });
+    }
+
+    Ok(resp)
+}
+
+/// sends the pool asset balances that were not provided as liquidity
+/// (e.g. single side leftovers) back to the holder
+fn try_return_idle_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let holder_addr = HOLDER_ADDRESS.load(deps.storage)?;
+    ensure!(info.sender == holder_addr, ContractError::NotHolder {});
+
+    let idle_balances = query_idle_balances(deps.as_ref(), &env)?;
+    ensure!(!idle_balances.is_empty(), ContractError::NoIdleFunds {});
+
+    Ok(Response::default()
+        .add_attribute("method", "try_return_idle_funds")
+        .add_attribute("idle_balances", to_json_string(&idle_balances)?)
+        .add_messages(get_return_idle_funds_msgs(
+            deps.as_ref(),
+            idle_balances,
+            &holder_addr,
+        )?))
+}
+
+/// messages transferring the given idle balances to the holder
+fn get_return_idle_funds_msgs(
+    deps: Deps,
+    idle_balances: Vec<Coin>,
+    holder_addr: &Addr,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+    let (native_balances, mut msgs) = split_cw20_coins(&lp_config, idle_balances, holder_addr)?;
+    if !native_balances.is_empty() {
+        msgs.push(
+            BankMsg::Send {
+                to_address: holder_addr.to_string(),
+                amount: native_balances,
+            }
+            .into(),
+        );
+    }
+    Ok(msgs)
+}
+
+/// past the funding deadline, the balances that were not provided
+/// as liquidity are returned to the holder instead of being provided
+fn try_return_leftover_funds(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let holder_addr = HOLDER_ADDRESS.load(deps.storage)?;
+    let idle_balances = query_idle_balances(deps.as_ref(), &env)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_return_leftover_funds")
+        .add_attribute("idle_balances", to_json_string(&idle_balances)?)
+        .add_messages(get_return_idle_funds_msgs(
+            deps.as_ref(),
+            idle_balances,
+            &holder_addr,
+        )?))
+}
+
+/// non-zero balances of the pool assets held by the pooler
+fn query_idle_balances(deps: Deps, env: &Env) -> StdResult<Vec<Coin>> {
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+    let mut idle_balances = vec![];
+    for denom in lp_config.asset_data.get_denoms() {
+        let balance = query_asset_balance(deps, env, &lp_config, &denom)?;
+        if !balance.amount.is_zero() {
+            idle_balances.push(balance);
+        }
+    }
+    Ok(idle_balances)
+}
+
+/// validates the lp config against the constraints of concentrated
+/// pools, if the pool is one. concentrated pools hold exactly two assets
+/// and bound the slippage tolerance of provided liquidity.
+fn validate_pcl_config(lp_config: &LpConfig) -> Result<(), ContractError> {
+    if !lp_config.is_pcl() {
+        return Ok(());
+    }
+
+    ensure!(
+        !lp_config.asset_data.is_multi_asset(),
+        ContractError::PclMultiAssetError {}
+    );
+    // concentrated pools reject slippage tolerances above 50%
+    let max = Decimal::percent(50);
+    ensure!(
+        lp_config.slippage_tolerance.unwrap_or_default() <= max,
+        ContractError::PclSlippageToleranceError { max }
+    );
+    Ok(())
+}
+
+fn validate_max_provision_per_tick(lp_config: &LpConfig) -> Result<(), ContractError> {
+    if let Some(cap) = &lp_config.max_provision_per_tick {
+        ensure!(
+            !cap.amount.is_zero()
+                && (cap.denom == lp_config.asset_data.asset_a_denom
+                    || cap.denom == lp_config.asset_data.asset_b_denom),
+            ContractError::InvalidMaxProvisionPerTick {}
+        );
     }
+    Ok(())
+}
+
+fn validate_min_lp_out_tolerance(tolerance: Option<Decimal>) -> Result<(), ContractError> {
+    ensure!(
+        tolerance.unwrap_or_default() <= Decimal::one(),
+        ContractError::InvalidMinLpOutTolerance {}
+    );
+    Ok(())
+}
+
+/// pair type migrations can be resolved by the holder or the emergency
+/// committee, as the holders do not forward these messages themselves
+fn verify_pair_type_migration_authority(
+    storage: &dyn Storage,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    let holder_addr = HOLDER_ADDRESS.load(storage)?;
+    let committee_addr = EMERGENCY_COMMITTEE_ADDR.may_load(storage)?;
+    ensure!(
+        sender == holder_addr || Some(sender) == committee_addr.as_ref(),
+        ContractError::NotPairTypeMigrationAuthority {}
+    );
+    Ok(())
+}
+
+fn try_update_pair_type_migration_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: PairTypeMigrationPolicy,
+) -> Result<Response, ContractError> {
+    verify_pair_type_migration_authority(deps.storage, &info.sender)?;
+
+    PAIR_TYPE_MIGRATION_POLICY.save(deps.storage, &policy)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "update_pair_type_migration_policy")
+        .add_attribute("pair_type_migration_policy", format!("{:?}", policy)))
+}
+
+fn try_accept_pair_type_migration(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    verify_pair_type_migration_authority(deps.storage, &info.sender)?;
+
+    let mut lp_config = LP_CONFIG.load(deps.storage)?;
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&lp_config.pool_address, &astroport::pair::QueryMsg::Pair {})?;
+    ensure!(
+        pair_info.pair_type != lp_config.pair_type,
+        ContractError::PairTypeNotMigrated {}
+    );
+
+    lp_config.pair_type = pair_info.pair_type;
+    validate_pcl_config(&lp_config)?;
+    LP_CONFIG.save(deps.storage, &lp_config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "accept_pair_type_migration")
+        .add_attribute("pair_type", format!("{:?}", lp_config.pair_type)))
+}
+
+fn try_set_provisioning_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let holder_addr = HOLDER_ADDRESS.load(deps.storage)?;
+    ensure!(info.sender == holder_addr, ContractError::NotHolder {});
+
+    PROVISIONING_PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "set_provisioning_paused")
+        .add_attribute("provisioning_paused", paused.to_string()))
 }
 
 fn try_withdraw(
@@ -136,64 +559,66 @@ fn try_withdraw(
         lp_config.pool_address.to_string(),
         env.contract.address.to_string(),
     )?;
+    let lp_token = lp_token_info.pair_info.liquidity_token.to_string();
+    let staked_lp_amount = query_staked_lp_amount(deps.as_ref(), &env, &lp_config, &lp_token)?;
+    let lp_position = lp_token_info
+        .balance_response
+        .balance
+        .checked_add(staked_lp_amount)?;
 
     // if no lp tokens are available, we attempt to withdraw any available denoms
-    if lp_token_info.balance_response.balance.is_zero() {
-        let asset_a_bal = deps.querier.query_balance(
-            env.contract.address.to_string(),
-            lp_config.asset_data.asset_a_denom.as_str(),
-        )?;
-        let asset_b_bal = deps.querier.query_balance(
-            env.contract.address.to_string(),
-            lp_config.asset_data.asset_b_denom.as_str(),
-        )?;
-
+    if lp_position.is_zero() {
         let mut funds = vec![];
 
-        if !asset_a_bal.amount.is_zero() {
-            funds.push(asset_a_bal);
-        }
-
-        if !asset_b_bal.amount.is_zero() {
-            funds.push(asset_b_bal);
+        for denom in lp_config.asset_data.get_denoms() {
+            let bal = query_asset_balance(deps.as_ref(), &env, &lp_config, &denom)?;
+            if !bal.amount.is_zero() {
+                funds.push(bal);
+            }
         }
 
         ensure!(!funds.is_empty(), ContractError::NothingToWithdraw {});
+        record_withdrawn_coins(deps.storage, &funds)?;
 
-        return Ok(Response::default().add_message(WasmMsg::Execute {
-            contract_addr: holder_addr.to_string(),
-            msg: to_json_binary(&WithdrawLPMsgs::Distribute {})?,
+        return Ok(Response::default().add_messages(get_distribute_msgs(
+            &lp_config,
+            &holder_addr,
             funds,
-        }));
+        )?));
     }
 
     // If percentage is 100%, use the whole balance
     // If percentage is less than 100%, calculate the percentage of share we want to withdraw
     let withdraw_shares_amount = if percent == Decimal::one() {
-        lp_token_info.balance_response.balance
+        lp_position
     } else {
-        Decimal::from_atomics(lp_token_info.balance_response.balance, 0)?
+        Decimal::from_atomics(lp_position, 0)?
             .checked_mul(percent)?
             .to_uint_floor()
     };
 
-    // Clculate the withdrawn amount of A and B tokens from the shares we have
-    let withdrawn_coins = deps
-        .querier
-        .query_wasm_smart::<Vec<Asset>>(
-            lp_config.pool_address.to_string(),
-            &astroport::pair::QueryMsg::Share {
-                amount: withdraw_shares_amount,
-            },
-        )?
-        .iter()
-        .map(|asset| asset.to_coin())
-        .collect::<Result<Vec<Coin>, _>>()?;
+    // auto-staked LP tokens are unstaked to cover whatever the
+    // LP token balance falls short of the withdrawn shares
+    let unstake_msg = get_unstake_msg(
+        &lp_config,
+        &lp_token,
+        withdraw_shares_amount.saturating_sub(lp_token_info.balance_response.balance),
+    )?;
+
+    // the pool asset balances prior to the withdrawal, so that the
+    // reply can tell the amounts returned by the pool
+    let pre_withdraw_balances = lp_config
+        .asset_data
+        .get_denoms()
+        .into_iter()
+        .map(|denom| query_asset_balance(deps.as_ref(), &env, &lp_config, &denom))
+        .collect::<StdResult<Vec<Coin>>>()?;
+    PRE_WITHDRAW_BALANCES.save(deps.storage, &pre_withdraw_balances)?;
 
     // exit pool and withdraw funds with the shares calculated
     let withdraw_liquidity_hook = &Cw20HookMsg::WithdrawLiquidity { assets: vec![] };
     let withdraw_msg = WasmMsg::Execute {
-        contract_addr: lp_token_info.pair_info.liquidity_token.to_string(),
+        contract_addr: lp_token,
         msg: to_json_binary(&Cw20ExecuteMsg::Send {
             contract: lp_config.pool_address.to_string(),
             amount: withdraw_shares_amount,
@@ -202,17 +627,76 @@ fn try_withdraw(
         funds: vec![],
     };
 
-    // send message to holder that we finished with the withdrawal
-    // with the funds we withdrew from the pool
-    let to_holder_msg = WasmMsg::Execute {
-        contract_addr: holder_addr.to_string(),
-        msg: to_json_binary(&WithdrawLPMsgs::Distribute {})?,
-        funds: withdrawn_coins,
+    // the withdrawn funds are sent to the holder once the reply
+    // recorded the amounts returned
+    Ok(Response::default()
+        .add_attribute("method", "try_withdraw")
+        .add_attribute("withdrawn_shares", withdraw_shares_amount)
+        .add_messages(unstake_msg)
+        .add_submessage(SubMsg::reply_on_success(withdraw_msg, WITHDRAW_REPLY_ID)))
+}
+
+/// amount of LP tokens auto-staked in the generator by the pooler.
+/// zero if no generator is configured.
+fn query_staked_lp_amount(
+    deps: Deps,
+    env: &Env,
+    lp_config: &LpConfig,
+    lp_token: &str,
+) -> StdResult<Uint128> {
+    match &lp_config.generator_address {
+        Some(generator) => deps.querier.query_wasm_smart(
+            generator.to_string(),
+            &GeneratorQueryMsg::Deposit {
+                lp_token: lp_token.to_string(),
+                user: env.contract.address.to_string(),
+            },
+        ),
+        None => Ok(Uint128::zero()),
+    }
+}
+
+/// returns the msg unstaking the given amount of LP tokens from the
+/// generator back to the pooler, if there is anything to unstake
+fn get_unstake_msg(
+    lp_config: &LpConfig,
+    lp_token: &str,
+    amount: Uint128,
+) -> StdResult<Option<WasmMsg>> {
+    match &lp_config.generator_address {
+        Some(generator) if !amount.is_zero() => Ok(Some(WasmMsg::Execute {
+            contract_addr: generator.to_string(),
+            msg: to_json_binary(&GeneratorExecuteMsg::Withdraw {
+                lp_token: lp_token.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// claims the generator rewards accrued by the auto-staked LP tokens.
+/// the rewards remain on the pooler.
+fn get_claim_rewards_msgs(deps: Deps, env: &Env) -> StdResult<Vec<WasmMsg>> {
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+    let Some(generator) = &lp_config.generator_address else {
+        return Ok(vec![]);
     };
+    let lp_token = query_liquidity_token_address(deps.querier, lp_config.pool_address.to_string())?;
 
-    Ok(Response::default()
-        .add_message(withdraw_msg)
-        .add_message(to_holder_msg))
+    // nothing is staked until liquidity got provided
+    if query_staked_lp_amount(deps, env, &lp_config, &lp_token)?.is_zero() {
+        return Ok(vec![]);
+    }
+
+    Ok(vec![WasmMsg::Execute {
+        contract_addr: generator.to_string(),
+        msg: to_json_binary(&GeneratorExecuteMsg::ClaimRewards {
+            lp_tokens: vec![lp_token],
+        })?,
+        funds: vec![],
+    }])
 }
 
 /// attempts to advance the state machine. performs `info.sender` validation.
@@ -221,117 +705,558 @@ fn try_tick(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Cont
 
     let current_state = CONTRACT_STATE.load(deps.storage)?;
     match current_state {
-        ContractState::Instantiated => try_lp(deps, env),
+        ContractState::Instantiated => {
+            let claim_rewards_msgs = get_claim_rewards_msgs(deps.as_ref(), &env)?;
+            let funding_expired = FUNDING_EXPIRATION
+                .may_load(deps.storage)?
+                .map_or(false, |expiration| expiration.is_expired(&env.block));
+            let resp = if funding_expired {
+                try_return_leftover_funds(deps, env)?
+            } else {
+                try_lp(deps, env)?
+            };
+            Ok(resp.add_messages(claim_rewards_msgs))
+        }
     }
 }
 
+/// liquidity provision the next tick performs, determined
+/// without modifying the state
+struct ProvisionPlan {
+    /// lp config the provision is performed with
+    lp_config: LpConfig,
+    /// whether the lp config got re-derived after a pair type migration
+    lp_config_updated: bool,
+    /// current a to b ratio of the pool
+    pool_ratio: Decimal,
+    /// time-weighted a to b ratio of the pool, if available
+    twap_ratio: Option<Decimal>,
+    /// outcome of validating the pool ratio
+    pool_ratio_check: Result<(), ContractError>,
+    action: ProvisionAction,
+    /// attributes describing why no liquidity is provided
+    attributes: Vec<Attribute>,
+}
+
 /// method which attempts to provision liquidity to the pool.
 /// if both desired asset balances are non-zero, double sided liquidity
 /// is provided.
 /// otherwise, single-sided liquidity provision is attempted.
 fn try_lp(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
-    let lp_config = LP_CONFIG.load(deps.storage)?;
+    let plan = get_provision_plan(deps.as_ref(), &env, None)?;
+    plan.pool_ratio_check?;
+    record_twap_snapshot(deps.branch(), &env, &plan.lp_config)?;
+
+    if plan.lp_config_updated {
+        LP_CONFIG.save(deps.storage, &plan.lp_config)?;
+    }
+
+    match plan.action {
+        ProvisionAction::Nothing { reason } => Ok(Response::default()
+            .add_attribute("method", "try_lp")
+            .add_attribute("status", reason)
+            .add_attributes(plan.attributes)),
+        ProvisionAction::SingleSided { coin } => {
+            let single_sided_submsgs =
+                try_get_single_side_lp_submsg(deps, env, coin, plan.lp_config)?;
+            Ok(Response::default()
+                .add_submessages(single_sided_submsgs)
+                .add_attribute("method", "single_side_lp"))
+        }
+        ProvisionAction::Rebalance { swap, coins } => {
+            let max_spread = plan
+                .lp_config
+                .single_side_rebalance
+                .as_ref()
+                .map(|rebalance| rebalance.max_swap_slippage);
+            // the ask coin provided is the one received for the swap
+            let ask_coin = coins
+                .iter()
+                .find(|c| c.denom != swap.denom)
+                .cloned()
+                .unwrap_or_default();
+            record_expected_lp_out(
+                deps.branch(),
+                &env,
+                &plan.lp_config,
+                &coins,
+                Some((&swap, &ask_coin)),
+            )?;
+            let mut submsgs = vec![SubMsg::reply_on_success(
+                get_swap_msg(&plan.lp_config, &swap, max_spread)?,
+                SWAP_REPLY_ID,
+            )];
+            submsgs.extend(get_provide_liquidity_submsgs(
+                deps,
+                &env,
+                coins,
+                &plan.lp_config,
+                DOUBLE_SIDED_REPLY_ID,
+            )?);
+            Ok(Response::default()
+                .add_submessages(submsgs)
+                .add_attribute("method", "rebalance_lp")
+                .add_attribute("swap", swap.to_string()))
+        }
+        ProvisionAction::DoubleSided { coins } => {
+            let method = if plan.lp_config.asset_data.is_multi_asset() {
+                "multi_asset_lp"
+            } else {
+                "double_side_lp"
+            };
+            record_expected_lp_out(deps.branch(), &env, &plan.lp_config, &coins, None)?;
+            let double_sided_submsgs = get_provide_liquidity_submsgs(
+                deps,
+                &env,
+                coins,
+                &plan.lp_config,
+                DOUBLE_SIDED_REPLY_ID,
+            )?;
+            Ok(Response::default()
+                .add_submessages(double_sided_submsgs)
+                .add_attribute("method", method))
+        }
+    }
+}
+
+/// determines the liquidity provision of the next tick, given the
+/// current pool state and our balances
+/// `balances` overrides the pooler balances of asset a and b to provide.
+fn get_provision_plan(
+    deps: Deps,
+    env: &Env,
+    balances: Option<(Coin, Coin)>,
+) -> Result<ProvisionPlan, ContractError> {
+    let mut lp_config = LP_CONFIG.load(deps.storage)?;
 
     let pool_response: PoolResponse = deps
         .querier
         .query_wasm_smart(&lp_config.pool_address, &astroport::pair::QueryMsg::Pool {})?;
 
     let (pool_token_a_bal, pool_token_b_bal) = get_pool_asset_amounts(
-        pool_response.assets,
+        pool_response.assets.clone(),
         lp_config.asset_data.asset_a_denom.as_str(),
         lp_config.asset_data.asset_b_denom.as_str(),
     )?;
 
-    // `get_pool_asset_amounts` ensures that both a and b balances are non-zero so this is safe
-    let a_to_b_ratio = Decimal::from_ratio(pool_token_a_bal, pool_token_b_bal);
+    // the holder may pause the provision, e.g. while its depeg guard is triggered
+    if PROVISIONING_PAUSED
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        // `get_pool_asset_amounts` ensures that both a and b balances are non-zero
+        let a_to_b_ratio = Decimal::from_ratio(pool_token_a_bal, pool_token_b_bal);
+        return Ok(ProvisionPlan {
+            lp_config,
+            lp_config_updated: false,
+            pool_ratio: a_to_b_ratio,
+            twap_ratio: None,
+            pool_ratio_check: Ok(()),
+            action: ProvisionAction::Nothing {
+                reason: "paused".to_string(),
+            },
+            attributes: vec![Attribute::new("provisioning_paused", "true")],
+        });
+    }
 
-    // validate the current pool ratio against our expectations
-    lp_config
-        .expected_pool_ratio_range
-        .is_within_range(a_to_b_ratio)?;
+    // validate that the pool did not migrate to a new pair type
+    let mut lp_config_updated = false;
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&lp_config.pool_address, &astroport::pair::QueryMsg::Pair {})?;
+    if pair_info.pair_type != lp_config.pair_type {
+        match PAIR_TYPE_MIGRATION_POLICY
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+        {
+            PairTypeMigrationPolicy::Reject => return Err(ContractError::PairTypeMismatch {}),
+            PairTypeMigrationPolicy::PauseForCommittee => {
+                // `get_pool_asset_amounts` ensures that both a and b balances are non-zero
+                let a_to_b_ratio = Decimal::from_ratio(pool_token_a_bal, pool_token_b_bal);
+                return Ok(ProvisionPlan {
+                    lp_config,
+                    lp_config_updated,
+                    pool_ratio: a_to_b_ratio,
+                    twap_ratio: None,
+                    pool_ratio_check: Ok(()),
+                    action: ProvisionAction::Nothing {
+                        reason: "paused".to_string(),
+                    },
+                    attributes: vec![Attribute::new(
+                        "pool_pair_type",
+                        format!("{:?}", pair_info.pair_type),
+                    )],
+                });
+            }
+            PairTypeMigrationPolicy::AcceptWithReratio => {
+                // adopt the new pair type. the pool ratio can be moved
+                // within a single block, so our price expectations are
+                // only re-derived from the oracle, if configured.
+                lp_config.pair_type = pair_info.pair_type.clone();
+                validate_pcl_config(&lp_config)?;
+                if let Some(guard) = ORACLE_PRICE_GUARD.may_load(deps.storage)? {
+                    let oracle_price = query_price(deps, &guard.source, &guard.base, &guard.quote)?;
+                    lp_config.expected_pool_ratio_range =
+                        lp_config.expected_pool_ratio_range.recenter(oracle_price)?;
+                }
+                lp_config_updated = true;
+            }
+        }
+    }
 
-    // first we query our own balances
-    let coin_a = deps.querier.query_balance(
-        env.contract.address.to_string(),
-        lp_config.asset_data.asset_a_denom.as_str(),
+    let a_to_b_ratio = get_pool_ratio(
+        deps,
+        &lp_config,
+        &pair_info,
+        (pool_token_a_bal, pool_token_b_bal),
     )?;
-    let coin_b = deps.querier.query_balance(
-        env.contract.address.to_string(),
-        lp_config.asset_data.asset_b_denom.as_str(),
+
+    let twap_ratio = query_twap_ratio(deps, env, &lp_config);
+    let pool_ratio_check = validate_pool_ratio(deps, &lp_config, a_to_b_ratio, twap_ratio);
+    let (action, attributes) = if pool_ratio_check.is_ok() {
+        get_provision_action(
+            deps,
+            env,
+            &lp_config,
+            pool_response,
+            (pool_token_a_bal, pool_token_b_bal),
+            balances,
+        )?
+    } else {
+        let action = ProvisionAction::Nothing {
+            reason: "invalid pool ratio".to_string(),
+        };
+        (action, vec![])
+    };
+
+    Ok(ProvisionPlan {
+        lp_config,
+        lp_config_updated,
+        pool_ratio: a_to_b_ratio,
+        twap_ratio,
+        pool_ratio_check,
+        action,
+        attributes,
+    })
+}
+
+/// returns the current a to b ratio of the pool. classic pools hold their
+/// reserves in the ratio of the price, whereas concentrated pools hold them
+/// around the price scale of the pool, which is therefore used instead.
+fn get_pool_ratio(
+    deps: Deps,
+    lp_config: &LpConfig,
+    pair_info: &PairInfo,
+    (pool_token_a_bal, pool_token_b_bal): (Uint128, Uint128),
+) -> Result<Decimal, ContractError> {
+    if !lp_config.is_pcl() {
+        // `get_pool_asset_amounts` ensures that both a and b balances are non-zero so this is safe
+        return Ok(Decimal::from_ratio(pool_token_a_bal, pool_token_b_bal));
+    }
+
+    let config: ConfigResponse = deps
+        .querier
+        .query_wasm_smart(&lp_config.pool_address, &PclQueryMsg::Config {})?;
+    let params: ConcentratedPoolParams = from_json(
+        config
+            .params
+            .ok_or_else(|| StdError::generic_err("concentrated pool params not found"))?,
     )?;
-    let assets = lp_config
-        .asset_data
-        .to_asset_vec(coin_a.amount, coin_b.amount);
+
+    // the price scale is the price of the second pool asset
+    // denominated in the first one
+    if pair_info.asset_infos.first().map(|info| info.to_string())
+        == Some(lp_config.asset_data.asset_a_denom.to_string())
+    {
+        Ok(params.price_scale)
+    } else {
+        params
+            .price_scale
+            .inv()
+            .ok_or_else(|| ContractError::from(StdError::generic_err("zero price scale")))
+    }
+}
+
+/// validates the current pool ratio against our expectations. the
+/// time-weighted ratio is validated in place of the spot ratio if
+/// available, as the spot ratio can be moved within a single block.
+fn validate_pool_ratio(
+    deps: Deps,
+    lp_config: &LpConfig,
+    a_to_b_ratio: Decimal,
+    twap_ratio: Option<Decimal>,
+) -> Result<(), ContractError> {
+    lp_config
+        .expected_pool_ratio_range
+        .is_within_range(twap_ratio.unwrap_or(a_to_b_ratio))?;
+
+    // if configured, the pool ratio must also be in line with the oracle
+    if let Some(guard) = ORACLE_PRICE_GUARD.may_load(deps.storage)? {
+        guard.check_price(deps, a_to_b_ratio)?;
+    }
+
+    Ok(())
+}
+
+/// determines the liquidity to provide with our balances
+fn get_provision_action(
+    deps: Deps,
+    env: &Env,
+    lp_config: &LpConfig,
+    pool_response: PoolResponse,
+    (pool_token_a_bal, pool_token_b_bal): (Uint128, Uint128),
+    balances: Option<(Coin, Coin)>,
+) -> Result<(ProvisionAction, Vec<Attribute>), ContractError> {
+    // if configured, the pooler must not own more than the max share of
+    // the pool. balances that would exceed it are kept as idle balance.
+    let pool_ownership = get_pool_ownership(deps, env, lp_config, pool_response.total_share)?;
+    let max_mintable_shares = pool_ownership.get_max_mintable_shares()?;
+    if max_mintable_shares == Some(Uint128::zero()) {
+        let action = ProvisionAction::Nothing {
+            reason: "max pool ownership reached".to_string(),
+        };
+        let attributes = vec![Attribute::new(
+            "pool_ownership",
+            pool_ownership.ownership.to_string(),
+        )];
+        return Ok((action, attributes));
+    }
+
+    // pools of three or more assets are entered proportionally
+    if lp_config.asset_data.is_multi_asset() {
+        let action = get_multi_asset_provision_action(
+            deps,
+            env,
+            pool_response.assets,
+            lp_config,
+            (pool_ownership, max_mintable_shares),
+        )?;
+        return Ok((action, vec![]));
+    }
+
+    // first we query our own balances, unless given
+    let (mut coin_a, mut coin_b) = match balances {
+        Some(balances) => balances,
+        None => (
+            query_asset_balance(
+                deps,
+                env,
+                lp_config,
+                lp_config.asset_data.asset_a_denom.as_str(),
+            )?,
+            query_asset_balance(
+                deps,
+                env,
+                lp_config,
+                lp_config.asset_data.asset_b_denom.as_str(),
+            )?,
+        ),
+    };
+    coin_a.amount =
+        pool_ownership.cap_provided_amount(coin_a.amount, pool_token_a_bal, max_mintable_shares)?;
+    coin_b.amount =
+        pool_ownership.cap_provided_amount(coin_b.amount, pool_token_b_bal, max_mintable_shares)?;
+    // large balances are deployed gradually over multiple ticks
+    (coin_a.amount, coin_b.amount) = lp_config.cap_provision_per_tick(
+        (coin_a.amount, coin_b.amount),
+        (pool_token_a_bal, pool_token_b_bal),
+    );
 
     // depending on available balances we attempt a different action:
-    match (coin_a.amount.is_zero(), coin_b.amount.is_zero()) {
+    let action = match (coin_a.amount.is_zero(), coin_b.amount.is_zero()) {
         // asset_b balance is non-zero, we attempt single-side
         (true, false) => {
-            ensure!(
-                coin_b.amount <= lp_config.single_side_lp_limits.asset_b_limit,
-                ContractError::SingleSideLpLimitError {}
-            );
-
-            let single_sided_submsgs =
-                try_get_single_side_lp_submsg(deps.branch(), env, coin_b, assets, lp_config)?;
-            if !single_sided_submsgs.is_empty() {
-                return Ok(Response::default()
-                    .add_submessages(single_sided_submsgs)
-                    .add_attribute("method", "single_side_lp"));
+            if coin_b.amount
+                <= lp_config
+                    .single_side_lp_limits
+                    .get_asset_b_limit(pool_token_b_bal)
+            {
+                ProvisionAction::SingleSided { coin: coin_b }
+            } else {
+                get_rebalance_action(
+                    deps,
+                    lp_config,
+                    coin_b,
+                    &lp_config.asset_data.asset_a_denom,
+                    (pool_token_b_bal, pool_token_a_bal),
+                )?
             }
         }
         // asset_a balance is non-zero, we attempt single-side
         (false, true) => {
-            ensure!(
-                coin_a.amount <= lp_config.single_side_lp_limits.asset_a_limit,
-                ContractError::SingleSideLpLimitError {}
-            );
-            let single_sided_submsgs =
-                try_get_single_side_lp_submsg(deps.branch(), env, coin_a, assets, lp_config)?;
-            if !single_sided_submsgs.is_empty() {
-                return Ok(Response::default()
-                    .add_submessages(single_sided_submsgs)
-                    .add_attribute("method", "single_side_lp"));
+            if coin_a.amount
+                <= lp_config
+                    .single_side_lp_limits
+                    .get_asset_a_limit(pool_token_a_bal)
+            {
+                ProvisionAction::SingleSided { coin: coin_a }
+            } else {
+                get_rebalance_action(
+                    deps,
+                    lp_config,
+                    coin_a,
+                    &lp_config.asset_data.asset_b_denom,
+                    (pool_token_a_bal, pool_token_b_bal),
+                )?
             }
         }
         // both balances are non-zero, we attempt double-side
-        (false, false) => {
-            let double_sided_submsg = try_get_double_side_lp_submsg(
-                deps.branch(),
-                env,
+        (false, false) => ProvisionAction::DoubleSided {
+            coins: get_double_side_lp_coins(
                 (coin_a, coin_b),
-                a_to_b_ratio,
                 (pool_token_a_bal, pool_token_b_bal),
                 lp_config,
-            )?;
-            if let Some(msg) = double_sided_submsg {
-                return Ok(Response::default()
-                    .add_submessage(msg)
-                    .add_attribute("method", "double_side_lp"));
-            }
-        }
+            )?,
+        },
         // both balances zero, no liquidity can be provisioned
-        _ => (),
+        (true, true) => ProvisionAction::Nothing {
+            reason: "not enough funds".to_string(),
+        },
+    };
+
+    Ok((action, vec![]))
+}
+
+/// determines the swap that restores the balance of a single-sided coin
+/// exceeding its single-side limit, along with the double-sided liquidity
+/// provided with the proceeds. errors unless rebalancing is configured.
+fn get_rebalance_action(
+    deps: Deps,
+    lp_config: &LpConfig,
+    offer_balance: Coin,
+    ask_denom: &str,
+    (offer_reserve, ask_reserve): (Uint128, Uint128),
+) -> Result<ProvisionAction, ContractError> {
+    ensure!(
+        lp_config.single_side_rebalance.is_some(),
+        ContractError::SingleSideLpLimitError {}
+    );
+
+    // swapping s out of the x held, our holdings end up in the ratio
+    // of the pool reserves for s = sqrt(r * (r + x)) - r, ignoring fees
+    let reserve = Uint256::from(offer_reserve);
+    let swap_amount: Uint128 = reserve
+        .checked_mul(reserve.checked_add(offer_balance.amount.into())?)?
+        .isqrt()
+        .checked_sub(reserve)?
+        .try_into()
+        .map_err(StdError::from)?;
+    if swap_amount.is_zero() {
+        return Ok(ProvisionAction::Nothing {
+            reason: "not enough funds".to_string(),
+        });
     }
+    let swap = coin(swap_amount.u128(), &offer_balance.denom);
 
-    // if no message could be constructed, we keep waiting for funds
-    Ok(Response::default()
-        .add_attribute("method", "try_lp")
-        .add_attribute("status", "not enough funds"))
+    let simulation: SimulationResponse = deps.querier.query_wasm_smart(
+        &lp_config.pool_address,
+        &astroport::pair::QueryMsg::Simulation {
+            offer_asset: lp_config.to_asset(&swap),
+            ask_asset_info: None,
+        },
+    )?;
+
+    // the remaining offer balance is provided in the ratio of the pool
+    // reserves after the swap. anything beyond it is kept for upcoming ticks.
+    let provided_offer_amount = simulation
+        .return_amount
+        .multiply_ratio(
+            offer_reserve.checked_add(swap_amount)?,
+            ask_reserve.checked_sub(simulation.return_amount)?,
+        )
+        .min(offer_balance.amount.checked_sub(swap_amount)?);
+    let offer_coin = coin(provided_offer_amount.u128(), &offer_balance.denom);
+    let ask_coin = coin(simulation.return_amount.u128(), ask_denom);
+
+    let coins = if offer_coin.denom == lp_config.asset_data.asset_a_denom {
+        vec![offer_coin, ask_coin]
+    } else {
+        vec![ask_coin, offer_coin]
+    };
+    Ok(ProvisionAction::Rebalance { swap, coins })
+}
+
+/// determines the liquidity to provide to a pool of three or more assets.
+/// if all of our balances are non-zero, we provide the highest amounts
+/// that maintain the existing pool composition. leftovers are kept for
+/// upcoming ticks.
+/// if only one of our balances is non-zero, it is provided single-sided
+/// as long as it does not exceed its single-side limit.
+fn get_multi_asset_provision_action(
+    deps: Deps,
+    env: &Env,
+    pool_assets: Vec<Asset>,
+    lp_config: &LpConfig,
+    (pool_ownership, max_mintable_shares): (PoolOwnership, Option<Uint128>),
+) -> Result<ProvisionAction, ContractError> {
+    let denoms = lp_config.asset_data.get_denoms();
+    let pool_balances = get_multi_pool_asset_amounts(pool_assets, &denoms)?;
+
+    let balances = denoms
+        .iter()
+        .zip(pool_balances.iter())
+        .map(|(denom, pool_balance)| {
+            let mut balance = query_asset_balance(deps, env, lp_config, denom)?;
+            balance.amount = pool_ownership.cap_provided_amount(
+                balance.amount,
+                *pool_balance,
+                max_mintable_shares,
+            )?;
+            Ok(balance)
+        })
+        .collect::<StdResult<Vec<Coin>>>()?;
+
+    let non_zero_balances: Vec<&Coin> = balances
+        .iter()
+        .filter(|bal| !bal.amount.is_zero())
+        .collect();
+
+    if non_zero_balances.len() == balances.len() {
+        let available: Vec<Uint128> = balances.iter().map(|bal| bal.amount).collect();
+        let amounts = get_proportional_amounts(&pool_balances, &available)?;
+        if amounts.iter().all(Uint128::is_zero) {
+            return Ok(ProvisionAction::Nothing {
+                reason: "not enough funds".to_string(),
+            });
+        }
+        let coins: Vec<Coin> = denoms
+            .iter()
+            .zip(amounts)
+            .map(|(denom, amount)| coin(amount.u128(), denom))
+            .collect();
+        Ok(ProvisionAction::DoubleSided { coins })
+    } else if non_zero_balances.len() == 1 {
+        let single_coin = non_zero_balances[0];
+        let pool_reserve = denoms
+            .iter()
+            .position(|denom| denom == &single_coin.denom)
+            .map(|i| pool_balances[i])
+            .unwrap_or_default();
+        ensure!(
+            single_coin.amount <= lp_config.get_single_side_limit(&single_coin.denom, pool_reserve),
+            ContractError::SingleSideLpLimitError {}
+        );
+        Ok(ProvisionAction::SingleSided {
+            coin: single_coin.clone(),
+        })
+    } else {
+        // we wait for all assets to arrive before entering the pool
+        Ok(ProvisionAction::Nothing {
+            reason: "not enough funds".to_string(),
+        })
+    }
 }
 
-/// attempts to get a double sided ProvideLiquidity submessage.
+/// determines the double sided liquidity to provide.
 /// amounts here do not matter. as long as we have non-zero balances of both
 /// a and b tokens, the maximum amount of liquidity is provided to maintain
 /// the existing pool ratio.
-fn try_get_double_side_lp_submsg(
-    deps: DepsMut,
-    env: Env,
+fn get_double_side_lp_coins(
     (token_a, token_b): (Coin, Coin),
-    pool_token_ratio: Decimal,
     (pool_token_a_bal, pool_token_b_bal): (Uint128, Uint128),
-    lp_config: LpConfig,
-) -> Result<Option<SubMsg>, ContractError> {
+    lp_config: &LpConfig,
+) -> Result<Vec<Coin>, ContractError> {
+    let pool_token_ratio = Decimal::from_ratio(pool_token_a_bal, pool_token_b_bal);
     // we thus find the required token amount to enter into the position using all available b tokens:
     let required_token_a_amount = pool_token_ratio.checked_mul_uint128(token_b.amount)?;
 
@@ -355,45 +1280,97 @@ fn try_get_double_side_lp_submsg(
             .to_tuple(token_a.amount, ratio.checked_mul_uint128(token_a.amount)?)
     };
 
-    let a_coin = asset_a_double_sided.to_coin()?;
-    let b_coin = asset_b_double_sided.to_coin()?;
+    Ok(vec![
+        asset_a_double_sided.to_coin()?,
+        asset_b_double_sided.to_coin()?,
+    ])
+}
+
+/// builds the `ProvideLiquidity` submessages with the given coins and
+/// updates the provided liquidity info accordingly. any cw20 coins
+/// are approved to the pool ahead of providing them.
+fn get_provide_liquidity_submsgs(
+    deps: DepsMut,
+    env: &Env,
+    coins: Vec<Coin>,
+    lp_config: &LpConfig,
+    reply_id: u64,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let provided_coins: Vec<Coin> = coins
+        .iter()
+        .filter(|c| !c.amount.is_zero())
+        .cloned()
+        .collect();
+    let mut submsgs = get_cw20_allowance_submsgs(lp_config, &provided_coins)?;
 
-    // craft a ProvideLiquidity message with the determined assets
-    let double_sided_liq_msg = ProvideLiquidity {
-        assets: vec![asset_a_double_sided, asset_b_double_sided],
+    let provide_liquidity_msg = ProvideLiquidity {
+        assets: coins.iter().map(|c| lp_config.to_asset(c)).collect(),
         slippage_tolerance: lp_config.slippage_tolerance,
-        auto_stake: Some(false),
+        auto_stake: Some(lp_config.generator_address.is_some()),
         receiver: Some(env.contract.address.to_string()),
     };
 
-    // update the provided amounts and leftover assets
-    PROVIDED_LIQUIDITY_INFO.update(
-        deps.storage,
-        |mut info: ProvidedLiquidityInfo| -> StdResult<_> {
-            info.provided_coin_b.amount = info.provided_coin_b.amount.checked_add(b_coin.amount)?;
-            info.provided_coin_a.amount = info.provided_coin_a.amount.checked_add(a_coin.amount)?;
-            Ok(info)
-        },
-    )?;
+    record_pending_provision(deps.storage, &provided_coins)?;
 
-    Ok(Some(SubMsg::reply_on_success(
+    submsgs.push(SubMsg::reply_always(
         CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: lp_config.pool_address.to_string(),
-            msg: to_json_binary(&double_sided_liq_msg)?,
-            funds: vec![a_coin, b_coin],
+            msg: to_json_binary(&provide_liquidity_msg)?,
+            funds: get_native_funds(lp_config, &provided_coins),
         }),
-        DOUBLE_SIDED_REPLY_ID,
-    )))
+        reply_id,
+    ));
+    Ok(submsgs)
+}
+
+/// optimistically adds the coins to the provided liquidity info. they
+/// are kept as pending until the reply of the provision settles them.
+fn record_pending_provision(storage: &mut dyn Storage, coins: &[Coin]) -> StdResult<()> {
+    PROVIDED_LIQUIDITY_INFO.update(storage, |mut info| -> StdResult<_> {
+        for c in coins.iter() {
+            info.add_provided_coin(c)?;
+        }
+        Ok(info)
+    })?;
+    PENDING_PROVISION.save(storage, &coins.to_vec())
+}
+
+/// native coins among the given ones, to be attached as funds
+fn get_native_funds(lp_config: &LpConfig, coins: &[Coin]) -> Vec<Coin> {
+    coins
+        .iter()
+        .filter(|c| !c.amount.is_zero() && !lp_config.is_cw20_asset(&c.denom))
+        .cloned()
+        .collect()
+}
+
+/// allowances of the given cw20 coins to the pool, so that it
+/// can collect them when liquidity is provided
+fn get_cw20_allowance_submsgs(lp_config: &LpConfig, coins: &[Coin]) -> StdResult<Vec<SubMsg>> {
+    coins
+        .iter()
+        .filter(|c| !c.amount.is_zero() && lp_config.is_cw20_asset(&c.denom))
+        .map(|c| {
+            Ok(SubMsg::new(WasmMsg::Execute {
+                contract_addr: c.denom.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: lp_config.pool_address.to_string(),
+                    amount: c.amount,
+                    expires: None,
+                })?,
+                funds: vec![],
+            }))
+        })
+        .collect()
 }
 
 /// attempts to build a single sided `ProvideLiquidity` message.
 /// pool ratio and single-side limit validations are performed by
 /// the calling method.
 fn try_get_single_side_lp_submsg(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     coin: Coin,
-    mut assets: Vec<Asset>,
     lp_config: LpConfig,
 ) -> Result<Vec<SubMsg>, ContractError> {
     match lp_config.pair_type {
@@ -401,114 +1378,267 @@ fn try_get_single_side_lp_submsg(
         // we therefore perform a manual swap with 1/2 of the available denom, and execute
         // two-sided lp provision with the resulting assets.
         PairType::Xyk {} => {
-            // we halve the non-zero coin we have in order to swap it for the other denom.
-            // the halved coin amount here is the floor of the division result,
-            // so it is safe to assume that after the swap we will have at least
-            // the same amount of the offer asset left.
-            let halved_coin = Coin {
-                denom: coin.denom.clone(),
-                amount: coin.amount / Uint128::from(2u128),
-            };
+            let (offer_coin, ask_coin) =
+                simulate_single_side_swap(deps.as_ref(), &lp_config, &coin)?;
+            let offer_asset = lp_config.to_asset(&offer_coin);
+            let ask_asset = lp_config.to_asset(&ask_coin);
 
-            let (offer_asset, offer_coin, mut ask_asset) = {
-                if assets[0].to_coin()?.denom == halved_coin.denom {
-                    assets[0].amount = halved_coin.amount;
-                    (assets[0].clone(), halved_coin, assets[1].clone())
-                } else {
-                    assets[1].amount = halved_coin.amount;
-                    (assets[1].clone(), halved_coin, assets[0].clone())
-                }
-            };
+            let swap_wasm_msg =
+                get_swap_msg(&lp_config, &offer_coin, lp_config.slippage_tolerance)?;
 
-            // we simulate a swap with 1/2 of the offer asset
-            let simulation: SimulationResponse = deps.querier.query_wasm_smart(
-                &lp_config.pool_address,
-                &astroport::pair::QueryMsg::Simulation {
-                    offer_asset: offer_asset.clone(),
-                    ask_asset_info: None,
-                },
+            let provided_coins = vec![offer_coin, ask_coin];
+            record_pending_provision(deps.storage, &provided_coins)?;
+            record_expected_lp_out(
+                deps.branch(),
+                &env,
+                &lp_config,
+                &provided_coins,
+                Some((&provided_coins[0], &provided_coins[1])),
             )?;
-            ask_asset.amount = simulation.return_amount;
-            let ask_coin = ask_asset.to_coin()?;
-
-            let swap_wasm_msg: CosmosMsg = WasmMsg::Execute {
-                contract_addr: lp_config.pool_address.to_string(),
-                msg: to_json_binary(&astroport::pair::ExecuteMsg::Swap {
-                    offer_asset: offer_asset.clone(),
-                    max_spread: lp_config.slippage_tolerance,
-                    belief_price: None,
-                    to: None,
-                    ask_asset_info: None,
-                })?,
-                funds: vec![offer_coin.clone()],
-            }
-            .into();
-
-            PROVIDED_LIQUIDITY_INFO.update(deps.storage, |mut info| -> StdResult<_> {
-                if offer_coin.denom == info.provided_coin_a.denom {
-                    info.provided_coin_a.amount =
-                        info.provided_coin_a.amount.checked_add(offer_coin.amount)?;
-                    info.provided_coin_b.amount =
-                        info.provided_coin_b.amount.checked_add(ask_coin.amount)?;
-                } else {
-                    info.provided_coin_b.amount =
-                        info.provided_coin_b.amount.checked_add(offer_coin.amount)?;
-                    info.provided_coin_a.amount =
-                        info.provided_coin_a.amount.checked_add(ask_coin.amount)?;
-                }
-                Ok(info)
-            })?;
-
             let provide_liquidity_msg: CosmosMsg = WasmMsg::Execute {
                 contract_addr: lp_config.pool_address.to_string(),
                 msg: to_json_binary(&ProvideLiquidity {
                     assets: vec![offer_asset, ask_asset],
                     slippage_tolerance: lp_config.slippage_tolerance,
-                    auto_stake: Some(false),
+                    auto_stake: Some(lp_config.generator_address.is_some()),
                     receiver: Some(env.contract.address.to_string()),
                 })?,
-                funds: vec![offer_coin, ask_coin],
+                funds: get_native_funds(&lp_config, &provided_coins),
             }
             .into();
-            let swap_submsg = SubMsg::reply_on_success(swap_wasm_msg, SWAP_REPLY_ID);
-            let provide_liquidity_submsg =
-                SubMsg::reply_on_success(provide_liquidity_msg, DOUBLE_SIDED_REPLY_ID);
 
-            Ok(vec![swap_submsg, provide_liquidity_submsg])
+            let mut submsgs = vec![SubMsg::reply_on_success(swap_wasm_msg, SWAP_REPLY_ID)];
+            submsgs.extend(get_cw20_allowance_submsgs(&lp_config, &provided_coins)?);
+            submsgs.push(SubMsg::reply_always(
+                provide_liquidity_msg,
+                DOUBLE_SIDED_REPLY_ID,
+            ));
+            Ok(submsgs)
         }
         PairType::Stable {} | PairType::Custom(_) => {
-            // given one non-zero asset, we build the ProvideLiquidity message
-            let single_sided_liq_msg = ProvideLiquidity {
-                assets,
-                slippage_tolerance: lp_config.slippage_tolerance,
-                auto_stake: Some(false),
-                receiver: Some(env.contract.address.to_string()),
+            let coins = get_single_side_coins(&lp_config, &coin);
+            record_expected_lp_out(deps.branch(), &env, &lp_config, &coins, None)?;
+            get_provide_liquidity_submsgs(deps, &env, coins, &lp_config, SINGLE_SIDED_REPLY_ID)
+        }
+    }
+}
+
+/// simulates swapping half of the single-sided coin for the other asset
+/// of an xyk pool. returns the offer coin and the simulated ask coin.
+fn simulate_single_side_swap(
+    deps: Deps,
+    lp_config: &LpConfig,
+    coin: &Coin,
+) -> Result<(Coin, Coin), ContractError> {
+    // we halve the non-zero coin we have in order to swap it for the other denom.
+    // the halved coin amount here is the floor of the division result,
+    // so it is safe to assume that after the swap we will have at least
+    // the same amount of the offer asset left.
+    let offer_coin = Coin {
+        denom: coin.denom.clone(),
+        amount: coin.amount / Uint128::from(2u128),
+    };
+    let ask_denom = if offer_coin.denom == lp_config.asset_data.asset_a_denom {
+        lp_config.asset_data.asset_b_denom.to_string()
+    } else {
+        lp_config.asset_data.asset_a_denom.to_string()
+    };
+
+    // we simulate a swap with 1/2 of the offer asset
+    let simulation: SimulationResponse = deps.querier.query_wasm_smart(
+        &lp_config.pool_address,
+        &astroport::pair::QueryMsg::Simulation {
+            offer_asset: lp_config.to_asset(&offer_coin),
+            ask_asset_info: None,
+        },
+    )?;
+    let ask_coin = Coin {
+        denom: ask_denom,
+        amount: simulation.return_amount,
+    };
+    Ok((offer_coin, ask_coin))
+}
+
+/// given one non-zero asset, it is provided along with
+/// zero amounts of the other pool assets
+fn get_single_side_coins(lp_config: &LpConfig, coin: &Coin) -> Vec<Coin> {
+    lp_config
+        .asset_data
+        .get_denoms()
+        .into_iter()
+        .map(|denom| {
+            let amount = if denom == coin.denom {
+                coin.amount
+            } else {
+                Uint128::zero()
             };
+            Coin { denom, amount }
+        })
+        .collect()
+}
 
-            // update the provided liquidity info
-            PROVIDED_LIQUIDITY_INFO.update(deps.storage, |mut info| -> StdResult<_> {
-                if coin.denom == info.provided_coin_a.denom {
-                    info.provided_coin_a.amount =
-                        info.provided_coin_a.amount.checked_add(coin.amount)?;
-                } else {
-                    info.provided_coin_b.amount =
-                        info.provided_coin_b.amount.checked_add(coin.amount)?;
-                }
-                Ok(info)
-            })?;
-
-            let submsg = SubMsg::reply_on_success(
-                CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: lp_config.pool_address.to_string(),
-                    msg: to_json_binary(&single_sided_liq_msg)?,
-                    funds: vec![coin],
-                }),
-                SINGLE_SIDED_REPLY_ID,
-            );
+/// coins the provision action submits to the pool, along with the
+/// offer and ask coins of the swap preceding the provision, if any
+fn get_provided_coins(
+    deps: Deps,
+    lp_config: &LpConfig,
+    action: &ProvisionAction,
+) -> Result<(Vec<Coin>, Option<(Coin, Coin)>), ContractError> {
+    match action {
+        ProvisionAction::Nothing { .. } => Ok((vec![], None)),
+        ProvisionAction::SingleSided { coin } => match lp_config.pair_type {
+            PairType::Xyk {} => {
+                let (offer_coin, ask_coin) = simulate_single_side_swap(deps, lp_config, coin)?;
+                Ok((
+                    vec![offer_coin.clone(), ask_coin.clone()],
+                    Some((offer_coin, ask_coin)),
+                ))
+            }
+            PairType::Stable {} | PairType::Custom(_) => {
+                Ok((get_single_side_coins(lp_config, coin), None))
+            }
+        },
+        ProvisionAction::DoubleSided { coins } => Ok((coins.clone(), None)),
+        ProvisionAction::Rebalance { swap, coins } => {
+            // the ask coin provided is the one received for the swap
+            let ask_coin = coins
+                .iter()
+                .find(|c| c.denom != swap.denom)
+                .cloned()
+                .unwrap_or_default();
+            Ok((coins.clone(), Some((swap.clone(), ask_coin))))
+        }
+    }
+}
 
-            Ok(vec![submsg])
+/// builds the msg swapping the offer coin through the pool for the other
+/// asset. cw20 offer assets are swapped through the cw20 hook of the pool.
+fn get_swap_msg(
+    lp_config: &LpConfig,
+    offer_coin: &Coin,
+    max_spread: Option<Decimal>,
+) -> StdResult<CosmosMsg> {
+    let swap_msg = if lp_config.is_cw20_asset(&offer_coin.denom) {
+        WasmMsg::Execute {
+            contract_addr: offer_coin.denom.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: lp_config.pool_address.to_string(),
+                amount: offer_coin.amount,
+                msg: to_json_binary(&Cw20HookMsg::Swap {
+                    ask_asset_info: None,
+                    belief_price: None,
+                    max_spread,
+                    to: None,
+                })?,
+            })?,
+            funds: vec![],
+        }
+    } else {
+        WasmMsg::Execute {
+            contract_addr: lp_config.pool_address.to_string(),
+            msg: to_json_binary(&astroport::pair::ExecuteMsg::Swap {
+                offer_asset: lp_config.to_asset(offer_coin),
+                max_spread,
+                belief_price: None,
+                to: None,
+                ask_asset_info: None,
+            })?,
+            funds: vec![offer_coin.clone()],
         }
+    };
+    Ok(swap_msg.into())
+}
+
+/// if a min lp out tolerance is configured, records the LP tokens expected
+/// from providing the given coins, to be validated in the provision reply.
+/// `swap` holds the offer and ask coins of a swap preceding the provision.
+fn record_expected_lp_out(
+    deps: DepsMut,
+    env: &Env,
+    lp_config: &LpConfig,
+    coins: &[Coin],
+    swap: Option<(&Coin, &Coin)>,
+) -> Result<(), ContractError> {
+    let Some(tolerance) = lp_config.min_lp_out_tolerance else {
+        return Ok(());
+    };
+
+    let expected_lp_out = simulate_lp_out(deps.as_ref(), lp_config, coins, swap)?;
+    let min_lp_out = expected_lp_out
+        .checked_sub(tolerance.checked_mul_uint128(expected_lp_out)?)
+        .unwrap_or_default();
+    EXPECTED_LP_OUT.save(
+        deps.storage,
+        &ExpectedLpOut {
+            pre_provision_lp_balance: query_lp_position(deps.as_ref(), env, lp_config)?,
+            expected_lp_out,
+            min_lp_out,
+        },
+    )?;
+    Ok(())
+}
+
+/// LP tokens expected from providing the given coins at the current
+/// pool state. `swap` holds the offer and ask coins of a swap
+/// preceding the provision.
+fn simulate_lp_out(
+    deps: Deps,
+    lp_config: &LpConfig,
+    coins: &[Coin],
+    swap: Option<(&Coin, &Coin)>,
+) -> Result<Uint128, ContractError> {
+    let pool_response: PoolResponse = deps
+        .querier
+        .query_wasm_smart(&lp_config.pool_address, &astroport::pair::QueryMsg::Pool {})?;
+    let pool_asset_count = Uint128::from(pool_response.assets.len() as u128);
+
+    // every coin mints its share of the pool reserves of its denom,
+    // evenly weighted across the pool assets. a preceding swap moves
+    // the reserves before the provision.
+    let mut expected_lp_out = Uint128::zero();
+    for c in coins.iter().filter(|c| !c.amount.is_zero()) {
+        let mut reserve = pool_response
+            .assets
+            .iter()
+            .find(|asset| asset.info.to_string() == c.denom)
+            .map(|asset| asset.amount)
+            .unwrap_or_default();
+        if let Some((offer_coin, ask_coin)) = swap {
+            if offer_coin.denom == c.denom {
+                reserve = reserve.checked_add(offer_coin.amount)?;
+            } else if ask_coin.denom == c.denom {
+                reserve = reserve.checked_sub(ask_coin.amount)?;
+            }
+        }
+        if reserve.is_zero() {
+            continue;
+        }
+        expected_lp_out = expected_lp_out.checked_add(c.amount.multiply_ratio(
+            pool_response.total_share,
+            reserve.checked_mul(pool_asset_count)?,
+        ))?;
     }
+    Ok(expected_lp_out)
+}
+
+/// LP tokens held by the pooler, including any auto-staked ones
+fn query_lp_position(deps: Deps, env: &Env, lp_config: &LpConfig) -> StdResult<Uint128> {
+    let lp_token_info = query_astro_pool_token(
+        deps.querier,
+        lp_config.pool_address.to_string(),
+        env.contract.address.to_string(),
+    )?;
+    let staked_lp_amount = query_staked_lp_amount(
+        deps,
+        env,
+        lp_config,
+        lp_token_info.pair_info.liquidity_token.as_str(),
+    )?;
+    lp_token_info
+        .balance_response
+        .balance
+        .checked_add(staked_lp_amount)
+        .map_err(StdError::from)
 }
 
 /// filters out irrelevant balances and returns a and b token amounts
@@ -520,13 +1650,14 @@ fn get_pool_asset_amounts(
     let (mut a_bal, mut b_bal) = (Uint128::zero(), Uint128::zero());
 
     for asset in assets {
-        let coin = asset.to_coin()?;
-        if coin.denom == b_denom {
+        // cw20 assets are identified by their contract address
+        let denom = asset.info.to_string();
+        if denom == b_denom {
             // found b balance
-            b_bal = coin.amount;
-        } else if coin.denom == a_denom {
+            b_bal = asset.amount;
+        } else if denom == a_denom {
             // found a token balance
-            a_bal = coin.amount;
+            a_bal = asset.amount;
         }
     }
 
@@ -537,6 +1668,35 @@ fn get_pool_asset_amounts(
     Ok((a_bal, b_bal))
 }
 
+/// returns the pool balances of the given denoms, in the same order.
+/// errors if any of them is not present in the pool.
+fn get_multi_pool_asset_amounts(
+    assets: Vec<Asset>,
+    denoms: &[String],
+) -> Result<Vec<Uint128>, StdError> {
+    // cw20 assets are identified by their contract address
+    let pool_coins: Vec<Coin> = assets
+        .iter()
+        .map(|asset| Coin {
+            denom: asset.info.to_string(),
+            amount: asset.amount,
+        })
+        .collect();
+
+    denoms
+        .iter()
+        .map(|denom| {
+            match pool_coins
+                .iter()
+                .find(|c| &c.denom == denom && !c.amount.is_zero())
+            {
+                Some(c) => Ok(c.amount),
+                None => Err(StdError::generic_err("all pool assets must be non-zero")),
+            }
+        })
+        .collect()
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -553,7 +1713,207 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::OperationMode {} => {
             Ok(to_json_binary(&CONTRACT_OP_MODE.may_load(deps.storage)?)?)
         }
+        QueryMsg::PairTypeMigrationPolicy {} => Ok(to_json_binary(
+            &PAIR_TYPE_MIGRATION_POLICY
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::EmergencyCommittee {} => Ok(to_json_binary(
+            &EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?,
+        )?),
+        QueryMsg::LastProvisionFailure {} => {
+            Ok(to_json_binary(&LAST_FAILURE.may_load(deps.storage)?)?)
+        }
+        QueryMsg::ProvisioningPaused {} => Ok(to_json_binary(
+            &PROVISIONING_PAUSED
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::OraclePriceGuard {} => {
+            Ok(to_json_binary(&ORACLE_PRICE_GUARD.may_load(deps.storage)?)?)
+        }
+        QueryMsg::PoolOwnership {} => {
+            let lp_config = LP_CONFIG.load(deps.storage)?;
+            let pool_response: PoolResponse = deps
+                .querier
+                .query_wasm_smart(&lp_config.pool_address, &astroport::pair::QueryMsg::Pool {})?;
+            Ok(to_json_binary(&get_pool_ownership(
+                deps,
+                &env,
+                &lp_config,
+                pool_response.total_share,
+            )?)?)
+        }
+        QueryMsg::WithdrawnLiquidity {} => Ok(to_json_binary(
+            &WITHDRAWN_LIQUIDITY
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::IdleBalances {} => Ok(to_json_binary(&query_idle_balances(deps, &env)?)?),
+        QueryMsg::FundingExpiration {} => {
+            Ok(to_json_binary(&FUNDING_EXPIRATION.may_load(deps.storage)?)?)
+        }
+        QueryMsg::SimulateProvision {} => {
+            let plan = get_provision_plan(deps, &env, None)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            Ok(to_json_binary(&ProvisionSimulation {
+                pool_ratio: plan.pool_ratio,
+                twap_ratio: plan.twap_ratio,
+                pool_ratio_error: plan.pool_ratio_check.err().map(|e| e.to_string()),
+                action: plan.action,
+            })?)
+        }
+        QueryMsg::SimulateProvide { coin_a, coin_b } => Ok(to_json_binary(
+            &query_simulate_provide(deps, &env, coin_a, coin_b)
+                .map_err(|e| StdError::generic_err(e.to_string()))?,
+        )?),
+    }
+}
+
+/// simulates the liquidity provision of the given balances of asset a
+/// and b at the current pool state, in place of the pooler balances
+fn query_simulate_provide(
+    deps: Deps,
+    env: &Env,
+    coin_a: Coin,
+    coin_b: Coin,
+) -> Result<ProvideSimulation, ContractError> {
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+    ensure!(
+        !lp_config.asset_data.is_multi_asset(),
+        ContractError::MultiAssetProvideSimulation {}
+    );
+    ensure!(
+        coin_a.denom == lp_config.asset_data.asset_a_denom
+            && coin_b.denom == lp_config.asset_data.asset_b_denom,
+        ContractError::ProvideSimulationDenomMismatch {}
+    );
+
+    let plan = get_provision_plan(deps, env, Some((coin_a, coin_b)))?;
+    let (provided_assets, swap) = get_provided_coins(deps, &plan.lp_config, &plan.action)?;
+    let expected_lp_out = simulate_lp_out(
+        deps,
+        &plan.lp_config,
+        &provided_assets,
+        swap.as_ref()
+            .map(|(offer_coin, ask_coin)| (offer_coin, ask_coin)),
+    )?;
+
+    Ok(ProvideSimulation {
+        pool_ratio: plan.pool_ratio,
+        twap_ratio: plan.twap_ratio,
+        pool_ratio_error: plan.pool_ratio_check.err().map(|e| e.to_string()),
+        action: plan.action,
+        provided_assets,
+        expected_lp_out,
+    })
+}
+
+/// queries the cumulative a to b ratio accumulated by the pool,
+/// i.e. the cumulative price of asset b denominated in asset a
+fn query_cumulative_ratio(deps: Deps, lp_config: &LpConfig) -> StdResult<Uint128> {
+    let response: CumulativePricesResponse = deps.querier.query_wasm_smart(
+        &lp_config.pool_address,
+        &astroport::pair::QueryMsg::CumulativePrices {},
+    )?;
+    response
+        .cumulative_prices
+        .into_iter()
+        .find(|(from, to, _)| {
+            from.to_string() == lp_config.asset_data.asset_b_denom
+                && to.to_string() == lp_config.asset_data.asset_a_denom
+        })
+        .map(|(_, _, cumulative_ratio)| cumulative_ratio)
+        .ok_or_else(|| StdError::generic_err("pool does not accumulate the pool ratio"))
+}
+
+/// returns the time-weighted a to b ratio of the pool since the newest
+/// snapshot at least a twap window old. `None` if twap validation is not
+/// configured, no snapshot is old enough yet, or the pool does not
+/// accumulate prices (e.g. concentrated pools).
+fn query_twap_ratio(deps: Deps, env: &Env, lp_config: &LpConfig) -> Option<Decimal> {
+    let window = lp_config.twap_window?;
+    let now = env.block.time.seconds();
+    let snapshots = TWAP_SNAPSHOTS.may_load(deps.storage).ok()??;
+    let snapshot = snapshots
+        .iter()
+        .rev()
+        .find(|snapshot| now.saturating_sub(snapshot.timestamp) >= window)?;
+
+    let cumulative_ratio = query_cumulative_ratio(deps, lp_config).ok()?;
+    // cumulative prices accumulate the price scaled by the twap precision
+    // for every second elapsed, wrapping around on overflow
+    let elapsed = Uint128::from(now - snapshot.timestamp)
+        .checked_mul(Uint128::from(10u128.pow(TWAP_PRECISION)))
+        .ok()?;
+    Decimal::checked_from_ratio(
+        cumulative_ratio.wrapping_sub(snapshot.cumulative_ratio),
+        elapsed,
+    )
+    .ok()
+}
+
+/// records the current cumulative ratio of the pool, at most every half
+/// twap window. snapshots no longer needed for the twap are pruned.
+fn record_twap_snapshot(deps: DepsMut, env: &Env, lp_config: &LpConfig) -> StdResult<()> {
+    let Some(window) = lp_config.twap_window else {
+        return Ok(());
+    };
+    // pools that do not accumulate prices are validated on the spot ratio
+    let Ok(cumulative_ratio) = query_cumulative_ratio(deps.as_ref(), lp_config) else {
+        return Ok(());
+    };
+
+    let now = env.block.time.seconds();
+    let mut snapshots = TWAP_SNAPSHOTS.may_load(deps.storage)?.unwrap_or_default();
+    if let Some(newest) = snapshots.last() {
+        if now.saturating_sub(newest.timestamp) < (window / 2).max(1) {
+            return Ok(());
+        }
+    }
+    snapshots.push(CumulativeRatioSnapshot {
+        timestamp: now,
+        cumulative_ratio,
+    });
+
+    // only the newest snapshot at least a window old is needed
+    if let Some(i) = snapshots
+        .iter()
+        .rposition(|snapshot| now.saturating_sub(snapshot.timestamp) >= window)
+    {
+        snapshots.drain(..i);
     }
+    TWAP_SNAPSHOTS.save(deps.storage, &snapshots)
+}
+
+/// returns the share of the pool owned by the pooler (including any
+/// auto-staked LP tokens), given the total share supply of the pool
+fn get_pool_ownership(
+    deps: Deps,
+    env: &Env,
+    lp_config: &LpConfig,
+    total_share: Uint128,
+) -> StdResult<PoolOwnership> {
+    let lp_token_info = query_astro_pool_token(
+        deps.querier,
+        lp_config.pool_address.to_string(),
+        env.contract.address.to_string(),
+    )?;
+    let staked_lp_amount = query_staked_lp_amount(
+        deps,
+        env,
+        lp_config,
+        lp_token_info.pair_info.liquidity_token.as_str(),
+    )?;
+
+    Ok(PoolOwnership::new(
+        lp_token_info
+            .balance_response
+            .balance
+            .checked_add(staked_lp_amount)?,
+        total_share,
+        MAX_POOL_OWNERSHIP.may_load(deps.storage)?,
+    ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -563,6 +1923,7 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> NeutronResult<Respo
             op_mode,
             holder_address,
             lp_config,
+            emergency_committee,
         } => {
             let mut response = Response::default().add_attribute("method", "update_config");
 
@@ -579,9 +1940,30 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> NeutronResult<Respo
                 response = response.add_attribute("holder_address", holder_address);
             }
 
+            if let Some(addr) = emergency_committee {
+                let committee_addr = deps.api.addr_validate(&addr)?;
+                EMERGENCY_COMMITTEE_ADDR.save(deps.storage, &committee_addr)?;
+                response = response.add_attribute("emergency_committee", committee_addr);
+            }
+
             if let Some(config) = lp_config {
                 // validate the address before storing it
                 deps.api.addr_validate(config.pool_address.as_str())?;
+                if let Some(generator) = &config.generator_address {
+                    deps.api.addr_validate(generator.as_str())?;
+                }
+                config.single_side_lp_limits.validate()?;
+                validate_min_lp_out_tolerance(config.min_lp_out_tolerance)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?;
+                if let Some(rebalance) = &config.single_side_rebalance {
+                    rebalance
+                        .validate()
+                        .map_err(|err| StdError::generic_err(err.to_string()))?;
+                }
+                validate_pcl_config(&config)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?;
+                validate_max_provision_per_tick(&config)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?;
                 LP_CONFIG.save(deps.storage, &config)?;
                 response = response.add_attributes(config.to_response_attributes());
             }
@@ -598,33 +1980,232 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> NeutronResult<Respo
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.result {
         SubMsgResult::Ok(_) => {
             let response = Response::default().add_attribute("reply_id", msg.id.to_string());
 
             match msg.id {
-                DOUBLE_SIDED_REPLY_ID => handle_double_sided_reply_id(response),
-                SINGLE_SIDED_REPLY_ID => handle_single_sided_reply_id(response),
+                DOUBLE_SIDED_REPLY_ID => handle_double_sided_reply_id(deps, env, response),
+                SINGLE_SIDED_REPLY_ID => handle_single_sided_reply_id(deps, env, response),
                 SWAP_REPLY_ID => handle_swap_reply_id(response),
+                WITHDRAW_REPLY_ID => handle_withdraw_reply_id(deps, env, response),
                 _ => Err(ContractError::from(StdError::generic_err(format!(
                     "unknown reply id: {}",
                     msg.id
                 )))),
             }
         }
-        SubMsgResult::Err(e) => Err(ContractError::from(StdError::generic_err(e))),
+        // failed provisions are recorded rather than failing the tick.
+        // the swaps they depend on still abort it.
+        SubMsgResult::Err(e) => match msg.id {
+            DOUBLE_SIDED_REPLY_ID | SINGLE_SIDED_REPLY_ID => {
+                handle_provision_failure(deps, env, msg.id, e)
+            }
+            _ => Err(ContractError::from(StdError::generic_err(e))),
+        },
     }
 }
 
+/// records the failed provision and reverts its optimistic
+/// update of the provided liquidity info
+fn handle_provision_failure(
+    deps: DepsMut,
+    env: Env,
+    reply_id: u64,
+    reason: String,
+) -> Result<Response, ContractError> {
+    let coins = PENDING_PROVISION
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    PENDING_PROVISION.remove(deps.storage);
+    EXPECTED_LP_OUT.remove(deps.storage);
+
+    PROVIDED_LIQUIDITY_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+        for c in coins.iter() {
+            info.sub_provided_coin(c)?;
+        }
+        Ok(info)
+    })?;
+    LAST_FAILURE.save(
+        deps.storage,
+        &ProvisionFailure {
+            coins,
+            reason: reason.clone(),
+            failed_at: env.block.time,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "handle_provision_failure")
+        .add_attribute("reply_id", reply_id.to_string())
+        .add_attribute("reason", reason))
+}
+
 fn handle_swap_reply_id(response: Response) -> Result<Response, ContractError> {
     Ok(response.add_attribute("method", "handle_swap_reply_id"))
 }
 
-fn handle_double_sided_reply_id(response: Response) -> Result<Response, ContractError> {
+fn handle_double_sided_reply_id(
+    deps: DepsMut,
+    env: Env,
+    response: Response,
+) -> Result<Response, ContractError> {
+    PENDING_PROVISION.remove(deps.storage);
+    let response = validate_lp_out(deps, &env, response)?;
     Ok(response.add_attribute("method", "handle_double_sided_reply_id"))
 }
 
-fn handle_single_sided_reply_id(response: Response) -> Result<Response, ContractError> {
+fn handle_single_sided_reply_id(
+    deps: DepsMut,
+    env: Env,
+    response: Response,
+) -> Result<Response, ContractError> {
+    PENDING_PROVISION.remove(deps.storage);
+    let response = validate_lp_out(deps, &env, response)?;
     Ok(response.add_attribute("method", "handle_single_sided_reply_id"))
 }
+
+/// validates the LP tokens minted by the provision against the expected
+/// ones, if recorded. erroring reverts the provision.
+fn validate_lp_out(
+    deps: DepsMut,
+    env: &Env,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let Some(expected_lp_out) = EXPECTED_LP_OUT.may_load(deps.storage)? else {
+        return Ok(response);
+    };
+    EXPECTED_LP_OUT.remove(deps.storage);
+
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+    let received = query_lp_position(deps.as_ref(), env, &lp_config)?
+        .checked_sub(expected_lp_out.pre_provision_lp_balance)?;
+    ensure!(
+        received >= expected_lp_out.min_lp_out,
+        ContractError::MinLpOutError {
+            received,
+            min_lp_out: expected_lp_out.min_lp_out,
+        }
+    );
+
+    Ok(response
+        .add_attribute("lp_out", received)
+        .add_attribute("expected_lp_out", expected_lp_out.expected_lp_out))
+}
+
+/// records the amounts returned by the pool for the withdrawn shares
+/// and forwards them to the holder
+fn handle_withdraw_reply_id(
+    deps: DepsMut,
+    env: Env,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let pre_withdraw_balances = PRE_WITHDRAW_BALANCES.load(deps.storage)?;
+    PRE_WITHDRAW_BALANCES.remove(deps.storage);
+    let lp_config = LP_CONFIG.load(deps.storage)?;
+
+    let mut withdrawn_coins = vec![];
+    for pre_withdraw_balance in pre_withdraw_balances {
+        let balance =
+            query_asset_balance(deps.as_ref(), &env, &lp_config, &pre_withdraw_balance.denom)?;
+        let amount = balance.amount.checked_sub(pre_withdraw_balance.amount)?;
+        if !amount.is_zero() {
+            withdrawn_coins.push(Coin {
+                denom: balance.denom,
+                amount,
+            });
+        }
+    }
+    record_withdrawn_coins(deps.storage, &withdrawn_coins)?;
+
+    let holder_addr = HOLDER_ADDRESS.load(deps.storage)?;
+    Ok(response
+        .add_attribute("method", "handle_withdraw_reply_id")
+        .add_attribute("withdrawn_coins", to_json_string(&withdrawn_coins)?)
+        .add_messages(get_distribute_msgs(
+            &lp_config,
+            &holder_addr,
+            withdrawn_coins,
+        )?))
+}
+
+/// queries the pooler balance of the given pool asset, native or cw20
+fn query_asset_balance(
+    deps: Deps,
+    env: &Env,
+    lp_config: &LpConfig,
+    denom: &str,
+) -> StdResult<Coin> {
+    if lp_config.is_cw20_asset(denom) {
+        let amount =
+            query_liquidity_token_balance(deps.querier, denom, env.contract.address.to_string())?;
+        Ok(Coin {
+            denom: denom.to_string(),
+            amount,
+        })
+    } else {
+        deps.querier
+            .query_balance(env.contract.address.to_string(), denom)
+    }
+}
+
+/// splits the coins into the native ones and the msgs
+/// transferring the cw20 ones to the recipient
+fn split_cw20_coins(
+    lp_config: &LpConfig,
+    coins: Vec<Coin>,
+    recipient: &Addr,
+) -> StdResult<(Vec<Coin>, Vec<CosmosMsg>)> {
+    let mut native_coins = vec![];
+    let mut cw20_transfer_msgs = vec![];
+    for coin in coins {
+        if lp_config.is_cw20_asset(&coin.denom) {
+            cw20_transfer_msgs.push(
+                WasmMsg::Execute {
+                    contract_addr: coin.denom,
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.to_string(),
+                        amount: coin.amount,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        } else {
+            native_coins.push(coin);
+        }
+    }
+    Ok((native_coins, cw20_transfer_msgs))
+}
+
+/// msgs handing the withdrawn coins over to the holder. cw20 coins are
+/// transferred ahead of the `Distribute` carrying the native ones.
+fn get_distribute_msgs(
+    lp_config: &LpConfig,
+    holder_addr: &Addr,
+    coins: Vec<Coin>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let (native_coins, mut msgs) = split_cw20_coins(lp_config, coins, holder_addr)?;
+    msgs.push(
+        WasmMsg::Execute {
+            contract_addr: holder_addr.to_string(),
+            msg: to_json_binary(&WithdrawLPMsgs::Distribute {})?,
+            funds: native_coins,
+        }
+        .into(),
+    );
+    Ok(msgs)
+}
+
+/// adds the coins to the lifetime amounts withdrawn to the holder
+fn record_withdrawn_coins(storage: &mut dyn Storage, coins: &[Coin]) -> StdResult<()> {
+    let mut withdrawn = WITHDRAWN_LIQUIDITY.may_load(storage)?.unwrap_or_default();
+    for coin in coins {
+        match withdrawn.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(total) => total.amount = total.amount.checked_add(coin.amount)?,
+            None => withdrawn.push(coin.clone()),
+        }
+    }
+    WITHDRAWN_LIQUIDITY.save(storage, &withdrawn)
+}
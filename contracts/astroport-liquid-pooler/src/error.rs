@@ -1,4 +1,4 @@
-use cosmwasm_std::{DecimalRangeExceeded, OverflowError, StdError};
+use cosmwasm_std::{Decimal, DecimalRangeExceeded, OverflowError, StdError, Uint128};
 use covenant_utils::op_mode::ContractOperationError;
 use neutron_sdk::NeutronError;
 use thiserror::Error;
@@ -44,12 +44,60 @@ pub enum ContractError {
     #[error("Pair type mismatch")]
     PairTypeMismatch {},
 
+    #[error("Pool did not migrate to a different pair type")]
+    PairTypeNotMigrated {},
+
+    #[error("Only the holder or the emergency committee can resolve pair type migrations")]
+    NotPairTypeMigrationAuthority {},
+
+    #[error("Pool asset {denom} is configured more than once")]
+    DuplicatePoolAsset { denom: String },
+
+    #[error("Xyk pools do not support more than two assets")]
+    XykMultiAssetError {},
+
+    #[error("Concentrated pools do not support more than two assets")]
+    PclMultiAssetError {},
+
+    #[error("Concentrated pools do not support a slippage tolerance above {max}")]
+    PclSlippageToleranceError { max: Decimal },
+
     #[error("Only holder can withdraw the position")]
     NotHolder {},
 
     #[error("no covenant denom or lp tokens available")]
     NothingToWithdraw {},
 
+    #[error("no idle funds to return")]
+    NoIdleFunds {},
+
+    #[error("twap window must be non-zero")]
+    InvalidTwapWindow {},
+
+    #[error("max swap slippage must be within (0, 0.5]")]
+    InvalidMaxSwapSlippage {},
+
+    #[error("min lp out tolerance must be within [0, 1]")]
+    InvalidMinLpOutTolerance {},
+
+    #[error("Received {received} LP tokens, below the minimum of {min_lp_out}")]
+    MinLpOutError {
+        received: Uint128,
+        min_lp_out: Uint128,
+    },
+
+    #[error("max provision per tick must be a non-zero amount of asset a or b")]
+    InvalidMaxProvisionPerTick {},
+
+    #[error("provide simulation expects coins of asset a and b")]
+    ProvideSimulationDenomMismatch {},
+
+    #[error("provide simulation is not supported for pools of three or more assets")]
+    MultiAssetProvideSimulation {},
+
+    #[error("cw20 token {token} is not a pool asset")]
+    UnsupportedCw20 { token: String },
+
     #[error("Withdraw percentage range must belong to range (0.0, 1.0]")]
     WithdrawPercentageRangeError {},
 }
@@ -4,17 +4,27 @@ use astroport::{
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    ensure, to_json_binary, Addr, Attribute, Binary, Coin, Decimal, StdResult, Uint128, WasmMsg,
+    ensure, to_json_binary, Addr, Attribute, Binary, Coin, Decimal, StdResult, Timestamp, Uint128,
+    WasmMsg,
 };
 use covenant_macros::{clocked, covenant_deposit_address, covenant_lper_withdraw};
+use covenant_oracle::PriceGuard;
 use covenant_utils::{
+    deadline::Deadline,
     instantiate2_helper::Instantiate2HelperConfig,
+    multi_asset::PoolAssetConfig,
     op_mode::{ContractOperationMode, ContractOperationModeConfig},
+    pool_ownership::PoolOwnership,
     PoolPriceConfig, SingleSideLpLimits,
 };
+use cw20::Cw20ReceiveMsg;
+use cw_utils::Expiration;
 
 use crate::error::ContractError;
 
+/// name of the astroport pair type of concentrated liquidity (PCL) pools
+pub const PCL_PAIR_TYPE: &str = "concentrated";
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub pool_address: String,
@@ -29,6 +39,45 @@ pub struct InstantiateMsg {
     // (aka non-privileged) operation, or a permissioned operation, that is,
     // restricted to being executed by one of the configured privileged accounts.
     pub op_mode_cfg: ContractOperationModeConfig,
+    /// policy applied if the pool migrates to a different pair type.
+    /// defaults to `Reject`.
+    pub pair_type_migration_policy: Option<PairTypeMigrationPolicy>,
+    /// address authorized to resolve pair type migrations along with the holder
+    #[serde(default)]
+    pub emergency_committee: Option<String>,
+    /// optional guard of the pool ratio against an external price source.
+    /// the guarded price is the price of asset b denominated in asset a.
+    #[serde(default)]
+    pub oracle_price_guard: Option<PriceGuard>,
+    /// optional upper bound of the pool share the pooler may own.
+    /// liquidity that would exceed it is kept as idle balance.
+    #[serde(default)]
+    pub max_pool_ownership: Option<Decimal>,
+    /// optional astroport generator to auto-stake the LP tokens into.
+    /// staking rewards are claimed on every tick.
+    #[serde(default)]
+    pub generator_address: Option<String>,
+    /// optional window (in seconds) of the time-weighted pool ratio
+    /// validated in place of the spot ratio, once available.
+    #[serde(default)]
+    pub twap_window: Option<u64>,
+    /// optional swap-then-join provisioning of single-sided balances
+    /// exceeding their single-side limit
+    #[serde(default)]
+    pub single_side_rebalance: Option<SingleSideRebalanceConfig>,
+    /// optional tolerance of the LP tokens minted falling short of the
+    /// amount expected at the current pool state. liquidity provisions
+    /// minting less are reverted.
+    #[serde(default)]
+    pub min_lp_out_tolerance: Option<Decimal>,
+    /// optional cap of the liquidity provided per tick, denominated in
+    /// asset a or b. balances beyond it are provided on the following ticks.
+    #[serde(default)]
+    pub max_provision_per_tick: Option<Coin>,
+    /// optional deadline after which the balances not provided as
+    /// liquidity are returned to the holder on tick
+    #[serde(default)]
+    pub funding_deadline: Option<Deadline>,
 }
 
 impl InstantiateMsg {
@@ -56,6 +105,34 @@ pub struct AstroportLiquidPoolerConfig {
     pub asset_a_denom: String,
     pub asset_b_denom: String,
     pub single_side_lp_limits: SingleSideLpLimits,
+    pub pair_type_migration_policy: Option<PairTypeMigrationPolicy>,
+    /// assets beyond a and b, for pools of three or more tokens
+    #[serde(default)]
+    pub additional_assets: Vec<PoolAssetConfig>,
+    /// optional guard of the pool ratio against an external price source
+    #[serde(default)]
+    pub oracle_price_guard: Option<PriceGuard>,
+    /// optional upper bound of the pool share the pooler may own
+    #[serde(default)]
+    pub max_pool_ownership: Option<Decimal>,
+    /// optional astroport generator to auto-stake the LP tokens into
+    #[serde(default)]
+    pub generator_address: Option<String>,
+    /// optional window (in seconds) of the time-weighted pool ratio validation
+    #[serde(default)]
+    pub twap_window: Option<u64>,
+    /// optional swap-then-join provisioning of excess single-sided balances
+    #[serde(default)]
+    pub single_side_rebalance: Option<SingleSideRebalanceConfig>,
+    /// optional tolerance of the LP tokens minted falling short of expectations
+    #[serde(default)]
+    pub min_lp_out_tolerance: Option<Decimal>,
+    /// optional cap of the liquidity provided per tick
+    #[serde(default)]
+    pub max_provision_per_tick: Option<Coin>,
+    /// optional deadline after which idle balances are returned to the holder
+    #[serde(default)]
+    pub funding_deadline: Option<Deadline>,
 }
 
 impl AstroportLiquidPoolerConfig {
@@ -64,6 +141,7 @@ impl AstroportLiquidPoolerConfig {
         holder_address: String,
         pool_price_config: PoolPriceConfig,
         op_mode_cfg: ContractOperationModeConfig,
+        emergency_committee: Option<String>,
     ) -> InstantiateMsg {
         InstantiateMsg {
             pool_address: self.pool_address.to_string(),
@@ -75,12 +153,60 @@ impl AstroportLiquidPoolerConfig {
             assets: AssetData {
                 asset_a_denom: self.asset_a_denom.to_string(),
                 asset_b_denom: self.asset_b_denom.to_string(),
+                additional_assets: self.additional_assets.clone(),
             },
             op_mode_cfg,
+            pair_type_migration_policy: self.pair_type_migration_policy.clone(),
+            emergency_committee,
+            oracle_price_guard: self.oracle_price_guard.clone(),
+            max_pool_ownership: self.max_pool_ownership,
+            generator_address: self.generator_address.clone(),
+            twap_window: self.twap_window,
+            single_side_rebalance: self.single_side_rebalance.clone(),
+            min_lp_out_tolerance: self.min_lp_out_tolerance,
+            max_provision_per_tick: self.max_provision_per_tick.clone(),
+            funding_deadline: self.funding_deadline.clone(),
         }
     }
 }
 
+/// describes how single-sided balances that exceed their single-side
+/// limit are provided. a portion of the balance is swapped through the
+/// pool for the other asset, and both are then provided double-sided.
+#[cw_serde]
+pub struct SingleSideRebalanceConfig {
+    /// highest spread accepted for the rebalancing swap
+    pub max_swap_slippage: Decimal,
+}
+
+impl SingleSideRebalanceConfig {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        // astroport pairs reject spreads above 50%
+        ensure!(
+            !self.max_swap_slippage.is_zero() && self.max_swap_slippage <= Decimal::percent(50),
+            ContractError::InvalidMaxSwapSlippage {}
+        );
+        Ok(())
+    }
+}
+
+/// describes how the liquid pooler reacts to the pool
+/// migrating to a pair type other than the configured one
+#[cw_serde]
+#[derive(Default)]
+pub enum PairTypeMigrationPolicy {
+    /// liquidity provision errors out
+    #[default]
+    Reject,
+    /// the new pair type is adopted. if an oracle price guard is
+    /// configured, the expected price range is re-centered around
+    /// the oracle price. otherwise the configured range is kept.
+    AcceptWithReratio,
+    /// liquidity provision is paused until the holder or the emergency
+    /// committee accepts the migration or switches to a different policy
+    PauseForCommittee,
+}
+
 #[cw_serde]
 pub struct DecimalRange {
     min: Decimal,
@@ -95,6 +221,12 @@ impl DecimalRange {
         })
     }
 
+    /// returns a range of the same width, centered around `mid`
+    pub fn recenter(&self, mid: Decimal) -> Result<DecimalRange, ContractError> {
+        let delta = self.max.checked_sub(self.min)? / Uint128::new(2);
+        DecimalRange::try_from(mid, delta)
+    }
+
     pub fn is_within_range(&self, value: Decimal) -> Result<(), ContractError> {
         ensure!(
             value >= self.min && value <= self.max,
@@ -108,19 +240,120 @@ impl DecimalRange {
 pub struct LpConfig {
     /// address of the liquidity pool we plan to enter
     pub pool_address: Addr,
-    /// denoms of both parties
+    /// denoms of both parties and any additional pool assets
     pub asset_data: AssetData,
     /// amounts of both tokens we consider ok to single-side lp
     pub single_side_lp_limits: SingleSideLpLimits,
-    /// slippage tolerance parameter for liquidity provisioning
+    /// slippage tolerance parameter for liquidity provisioning.
+    /// concentrated pools apply it to the LP tokens minted and
+    /// accept at most 50%.
     pub slippage_tolerance: Option<Decimal>,
     /// expected price range
     pub expected_pool_ratio_range: DecimalRange,
     /// pair type specified in the covenant
     pub pair_type: PairType,
+    /// generator the LP tokens are auto-staked into, if any
+    #[serde(default)]
+    pub generator_address: Option<Addr>,
+    /// cw20 tokens among the pool assets. they are configured in the
+    /// asset data by their contract address in place of a denom.
+    #[serde(default)]
+    pub cw20_assets: Vec<Addr>,
+    /// window (in seconds) of the time-weighted pool ratio that is
+    /// validated in place of the spot ratio, if configured
+    #[serde(default)]
+    pub twap_window: Option<u64>,
+    /// swap-then-join provisioning of single-sided balances exceeding
+    /// their single-side limit, if configured
+    #[serde(default)]
+    pub single_side_rebalance: Option<SingleSideRebalanceConfig>,
+    /// tolerance of the LP tokens minted falling short of the amount
+    /// expected at the pool state prior to providing, if configured
+    #[serde(default)]
+    pub min_lp_out_tolerance: Option<Decimal>,
+    /// cap of the liquidity provided per tick, denominated in asset a
+    /// or b. the other asset is capped at its equivalent in the pool
+    /// ratio, so that large positions are deployed over multiple ticks.
+    #[serde(default)]
+    pub max_provision_per_tick: Option<Coin>,
 }
 
 impl LpConfig {
+    /// whether the pool is a concentrated liquidity (PCL) pool
+    pub fn is_pcl(&self) -> bool {
+        self.pair_type == PairType::Custom(PCL_PAIR_TYPE.to_string())
+    }
+
+    /// caps the a and b amounts provided on a single tick. the asset the
+    /// cap is not denominated in is capped at its equivalent in the pool
+    /// reserves ratio. pool reserves are expected to be non-zero.
+    pub fn cap_provision_per_tick(
+        &self,
+        (amount_a, amount_b): (Uint128, Uint128),
+        (pool_token_a_bal, pool_token_b_bal): (Uint128, Uint128),
+    ) -> (Uint128, Uint128) {
+        let Some(cap) = &self.max_provision_per_tick else {
+            return (amount_a, amount_b);
+        };
+        let (cap_a, cap_b) = if cap.denom == self.asset_data.asset_a_denom {
+            (
+                cap.amount,
+                cap.amount
+                    .multiply_ratio(pool_token_b_bal, pool_token_a_bal),
+            )
+        } else {
+            (
+                cap.amount
+                    .multiply_ratio(pool_token_a_bal, pool_token_b_bal),
+                cap.amount,
+            )
+        };
+        (amount_a.min(cap_a), amount_b.min(cap_b))
+    }
+
+    pub fn is_cw20_asset(&self, denom: &str) -> bool {
+        self.cw20_assets.iter().any(|addr| addr.as_str() == denom)
+    }
+
+    /// returns the astroport asset info of the given denom
+    pub fn get_asset_info(&self, denom: &str) -> AssetInfo {
+        if self.is_cw20_asset(denom) {
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked(denom),
+            }
+        } else {
+            AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            }
+        }
+    }
+
+    pub fn to_asset(&self, coin: &Coin) -> Asset {
+        Asset {
+            info: self.get_asset_info(&coin.denom),
+            amount: coin.amount,
+        }
+    }
+
+    /// returns the effective single-side limit of the given denom,
+    /// provided the current pool reserves of that denom
+    pub fn get_single_side_limit(&self, denom: &str, pool_reserve: Uint128) -> Uint128 {
+        let absolute_limit = if denom == self.asset_data.asset_a_denom {
+            self.single_side_lp_limits.asset_a_limit
+        } else if denom == self.asset_data.asset_b_denom {
+            self.single_side_lp_limits.asset_b_limit
+        } else {
+            self.asset_data
+                .additional_assets
+                .iter()
+                .find(|asset| asset.denom == denom)
+                .map(|asset| asset.single_side_limit)
+                .unwrap_or_default()
+        };
+        self.single_side_lp_limits
+            .get_effective_limit(absolute_limit, pool_reserve)
+    }
+
     pub fn to_response_attributes(self) -> Vec<Attribute> {
         let slippage_tolerance = match self.slippage_tolerance {
             Some(val) => val.to_string(),
@@ -136,21 +369,87 @@ impl LpConfig {
                 "single_side_asset_b_limit",
                 self.single_side_lp_limits.asset_b_limit.to_string(),
             ),
+            Attribute::new(
+                "single_side_pool_reserve_share",
+                match self.single_side_lp_limits.pool_reserve_share {
+                    Some(share) => share.to_string(),
+                    None => "None".to_string(),
+                },
+            ),
             Attribute::new("slippage_tolerance", slippage_tolerance),
             Attribute::new("party_a_denom", self.asset_data.asset_a_denom),
             Attribute::new("party_b_denom", self.asset_data.asset_b_denom),
+            Attribute::new(
+                "additional_asset_denoms",
+                self.asset_data
+                    .additional_assets
+                    .iter()
+                    .map(|asset| asset.denom.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+            ),
+            Attribute::new(
+                "generator_address",
+                match self.generator_address {
+                    Some(addr) => addr.to_string(),
+                    None => "None".to_string(),
+                },
+            ),
+            Attribute::new(
+                "max_provision_per_tick",
+                match self.max_provision_per_tick {
+                    Some(cap) => cap.to_string(),
+                    None => "None".to_string(),
+                },
+            ),
         ]
     }
 }
 
-/// holds the both asset denoms relevant for providing liquidity
+/// holds the asset denoms relevant for providing liquidity
 #[cw_serde]
 pub struct AssetData {
     pub asset_a_denom: String,
     pub asset_b_denom: String,
+    /// assets beyond a and b, for pools of three or more tokens.
+    /// if non-empty, liquidity is provided with all assets proportionally.
+    #[serde(default)]
+    pub additional_assets: Vec<PoolAssetConfig>,
 }
 
 impl AssetData {
+    /// returns all asset denoms, starting with a and b
+    pub fn get_denoms(&self) -> Vec<String> {
+        let mut denoms = vec![
+            self.asset_a_denom.to_string(),
+            self.asset_b_denom.to_string(),
+        ];
+        denoms.extend(
+            self.additional_assets
+                .iter()
+                .map(|asset| asset.denom.to_string()),
+        );
+        denoms
+    }
+
+    /// validates that no denom is configured more than once
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let denoms = self.get_denoms();
+        for (i, denom) in denoms.iter().enumerate() {
+            ensure!(
+                !denoms[i + 1..].contains(denom),
+                ContractError::DuplicatePoolAsset {
+                    denom: denom.to_string()
+                }
+            );
+        }
+        Ok(())
+    }
+
+    pub fn is_multi_asset(&self) -> bool {
+        !self.additional_assets.is_empty()
+    }
+
     pub fn to_asset_vec(&self, a_bal: Uint128, b_bal: Uint128) -> Vec<Asset> {
         vec![
             Asset {
@@ -190,7 +489,28 @@ impl AssetData {
 #[clocked]
 #[covenant_lper_withdraw]
 #[cw_serde]
-pub enum ExecuteMsg {}
+pub enum ExecuteMsg {
+    /// switches the pair type migration policy. only callable
+    /// by the holder or the emergency committee.
+    UpdatePairTypeMigrationPolicy { policy: PairTypeMigrationPolicy },
+    /// adopts the pair type the pool migrated to, keeping the expected
+    /// price range. resumes the liquidity provision paused for the
+    /// committee. only callable by the holder or the emergency committee.
+    AcceptPairTypeMigration {},
+    /// pauses or resumes the liquidity provision. only callable by the holder.
+    SetProvisioningPaused { paused: bool },
+    /// sends the entire LP token balance to the receiver.
+    /// only callable by the holder.
+    DonateLpTokens { receiver: String },
+    /// stakes the entire LP token balance into the astroport generator
+    /// on behalf of the holder. only callable by the holder.
+    StakeLpTokens { generator: String },
+    /// sends the pool asset balances that were not provided as
+    /// liquidity back to the holder. only callable by the holder.
+    ReturnIdleFunds {},
+    /// accepts deposits of the cw20 pool assets
+    Receive(Cw20ReceiveMsg),
+}
 
 #[covenant_deposit_address]
 #[cw_serde]
@@ -206,6 +526,87 @@ pub enum QueryMsg {
     ProvidedLiquidityInfo {},
     #[returns(ContractOperationMode)]
     OperationMode {},
+    #[returns(PairTypeMigrationPolicy)]
+    PairTypeMigrationPolicy {},
+    #[returns(Option<Addr>)]
+    EmergencyCommittee {},
+    /// whether the liquidity provision is paused by the holder
+    #[returns(bool)]
+    ProvisioningPaused {},
+    /// most recent liquidity provision that failed, if any
+    #[returns(Option<ProvisionFailure>)]
+    LastProvisionFailure {},
+    #[returns(Option<PriceGuard>)]
+    OraclePriceGuard {},
+    /// returns the share of the pool owned by the pooler
+    #[returns(PoolOwnership)]
+    PoolOwnership {},
+    /// simulates the liquidity provision the next tick would perform,
+    /// without modifying the state. errors if the tick would fail for
+    /// reasons other than the pool ratio check.
+    #[returns(ProvisionSimulation)]
+    SimulateProvision {},
+    /// simulates providing the given balances of asset a and b at the
+    /// current pool state, as if they were held by the pooler
+    #[returns(ProvideSimulation)]
+    SimulateProvide { coin_a: Coin, coin_b: Coin },
+    /// lifetime amounts of the pool assets withdrawn to the holder
+    #[returns(Vec<Coin>)]
+    WithdrawnLiquidity {},
+    /// returns the pool asset balances held by the pooler
+    /// that were not provided as liquidity
+    #[returns(Vec<Coin>)]
+    IdleBalances {},
+    /// expiration of the funding deadline, if configured. past it,
+    /// idle balances are returned to the holder on tick.
+    #[returns(Option<Expiration>)]
+    FundingExpiration {},
+}
+
+/// liquidity provision performed on tick
+#[cw_serde]
+pub enum ProvisionAction {
+    /// no liquidity is provided
+    Nothing { reason: String },
+    /// the coin is provided single-sided. for xyk pools,
+    /// half of it is swapped before providing liquidity.
+    SingleSided { coin: Coin },
+    /// the coins are provided in the current pool ratio
+    DoubleSided { coins: Vec<Coin> },
+    /// the single-sided balance exceeds its single-side limit. the swap
+    /// coin is swapped for the other asset, and the coins are then
+    /// provided double-sided.
+    Rebalance { swap: Coin, coins: Vec<Coin> },
+}
+
+#[cw_serde]
+pub struct ProvisionSimulation {
+    /// current a to b ratio of the pool
+    pub pool_ratio: Decimal,
+    /// time-weighted a to b ratio of the pool over the twap window.
+    /// validated in place of the spot ratio, if available.
+    #[serde(default)]
+    pub twap_ratio: Option<Decimal>,
+    /// error the pool ratio validation fails with, if any.
+    /// no liquidity is provided unless the pool ratio is valid.
+    pub pool_ratio_error: Option<String>,
+    pub action: ProvisionAction,
+}
+
+#[cw_serde]
+pub struct ProvideSimulation {
+    /// current a to b ratio of the pool
+    pub pool_ratio: Decimal,
+    /// time-weighted a to b ratio of the pool, if available
+    pub twap_ratio: Option<Decimal>,
+    /// error the pool ratio validation fails with, if any.
+    /// no liquidity is provided unless the pool ratio is valid.
+    pub pool_ratio_error: Option<String>,
+    pub action: ProvisionAction,
+    /// assets submitted to the pool, following any preceding swap
+    pub provided_assets: Vec<Coin>,
+    /// LP tokens expected to be minted for the provided assets
+    pub expected_lp_out: Uint128,
 }
 
 #[cw_serde]
@@ -214,6 +615,8 @@ pub enum MigrateMsg {
         op_mode: Option<ContractOperationModeConfig>,
         holder_address: Option<String>,
         lp_config: Option<Box<LpConfig>>,
+        #[serde(default)]
+        emergency_committee: Option<String>,
     },
     UpdateCodeId {
         data: Option<Binary>,
@@ -225,6 +628,74 @@ pub enum MigrateMsg {
 pub struct ProvidedLiquidityInfo {
     pub provided_coin_a: Coin,
     pub provided_coin_b: Coin,
+    /// provided amounts of any additional pool assets
+    #[serde(default)]
+    pub provided_additional_coins: Vec<Coin>,
+}
+
+impl ProvidedLiquidityInfo {
+    /// adds the coin to the provided amount of its denom
+    pub fn add_provided_coin(&mut self, coin: &Coin) -> StdResult<()> {
+        if coin.denom == self.provided_coin_a.denom {
+            self.provided_coin_a.amount = self.provided_coin_a.amount.checked_add(coin.amount)?;
+        } else if coin.denom == self.provided_coin_b.denom {
+            self.provided_coin_b.amount = self.provided_coin_b.amount.checked_add(coin.amount)?;
+        } else {
+            match self
+                .provided_additional_coins
+                .iter_mut()
+                .find(|c| c.denom == coin.denom)
+            {
+                Some(provided) => provided.amount = provided.amount.checked_add(coin.amount)?,
+                None => self.provided_additional_coins.push(coin.clone()),
+            }
+        }
+        Ok(())
+    }
+
+    /// subtracts the coin from the provided amount of its denom
+    pub fn sub_provided_coin(&mut self, coin: &Coin) -> StdResult<()> {
+        if coin.denom == self.provided_coin_a.denom {
+            self.provided_coin_a.amount = self.provided_coin_a.amount.checked_sub(coin.amount)?;
+        } else if coin.denom == self.provided_coin_b.denom {
+            self.provided_coin_b.amount = self.provided_coin_b.amount.checked_sub(coin.amount)?;
+        } else if let Some(provided) = self
+            .provided_additional_coins
+            .iter_mut()
+            .find(|c| c.denom == coin.denom)
+        {
+            provided.amount = provided.amount.checked_sub(coin.amount)?;
+        }
+        Ok(())
+    }
+}
+
+/// liquidity provision that failed, as recorded by its reply
+#[cw_serde]
+pub struct ProvisionFailure {
+    /// coins the failed provision attempted to provide
+    pub coins: Vec<Coin>,
+    /// error returned by the pool
+    pub reason: String,
+    pub failed_at: Timestamp,
+}
+
+/// LP tokens expected from the pending liquidity provision
+#[cw_serde]
+pub struct ExpectedLpOut {
+    /// LP tokens held (and staked) prior to providing
+    pub pre_provision_lp_balance: Uint128,
+    /// LP tokens minted at the pool state prior to providing
+    pub expected_lp_out: Uint128,
+    /// least LP tokens the provision must mint
+    pub min_lp_out: Uint128,
+}
+
+/// cumulative a to b ratio of the pool, as accumulated by the pair
+#[cw_serde]
+pub struct CumulativeRatioSnapshot {
+    pub timestamp: u64,
+    pub cumulative_ratio: Uint128,
 }
 
 /// state of the LP state machine
@@ -1,8 +1,13 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin, Decimal};
+use covenant_oracle::PriceGuard;
 use covenant_utils::op_mode::ContractOperationMode;
 use cw_storage_plus::Item;
+use cw_utils::Expiration;
 
-use crate::msg::{ContractState, LpConfig, ProvidedLiquidityInfo};
+use crate::msg::{
+    ContractState, CumulativeRatioSnapshot, ExpectedLpOut, LpConfig, PairTypeMigrationPolicy,
+    ProvidedLiquidityInfo, ProvisionFailure,
+};
 
 /// contract state tracks the state machine progress
 pub const CONTRACT_STATE: Item<ContractState> = Item::new("contract_state");
@@ -16,5 +21,43 @@ pub const HOLDER_ADDRESS: Item<Addr> = Item::new("holder_address");
 pub const PROVIDED_LIQUIDITY_INFO: Item<ProvidedLiquidityInfo> =
     Item::new("provided_liquidity_info");
 
+/// lifetime amounts of the pool assets withdrawn to the holder
+pub const WITHDRAWN_LIQUIDITY: Item<Vec<Coin>> = Item::new("withdrawn_liquidity");
+
+/// pool asset balances prior to the pending withdrawal
+pub const PRE_WITHDRAW_BALANCES: Item<Vec<Coin>> = Item::new("pre_withdraw_balances");
+
+/// LP tokens expected from the pending liquidity provision,
+/// validated in its reply
+pub const EXPECTED_LP_OUT: Item<ExpectedLpOut> = Item::new("expected_lp_out");
+
+/// coins optimistically added to the provided liquidity info by the
+/// pending provision, subtracted again should the provision fail
+pub const PENDING_PROVISION: Item<Vec<Coin>> = Item::new("pending_provision");
+
+/// most recent liquidity provision that failed
+pub const LAST_FAILURE: Item<ProvisionFailure> = Item::new("last_failure");
+
 /// configuration relevant to entering into an LP position
 pub const LP_CONFIG: Item<LpConfig> = Item::new("lp_config");
+
+/// policy applied in case the pool migrates to a different pair type
+pub const PAIR_TYPE_MIGRATION_POLICY: Item<PairTypeMigrationPolicy> =
+    Item::new("pair_type_migration_policy");
+
+/// whether the liquidity provision is paused by the holder
+pub const PROVISIONING_PAUSED: Item<bool> = Item::new("provisioning_paused");
+
+/// guard of the pool ratio against an external price source
+pub const ORACLE_PRICE_GUARD: Item<PriceGuard> = Item::new("oracle_price_guard");
+
+/// highest share of the pool the pooler may own
+pub const MAX_POOL_OWNERSHIP: Item<Decimal> = Item::new("max_pool_ownership");
+
+/// snapshots of the cumulative pool ratio, oldest first, from which
+/// the time-weighted pool ratio is derived
+pub const TWAP_SNAPSHOTS: Item<Vec<CumulativeRatioSnapshot>> = Item::new("twap_snapshots");
+
+/// expiration of the funding deadline, past which idle balances
+/// are returned to the holder on tick
+pub const FUNDING_EXPIRATION: Item<Expiration> = Item::new("funding_expiration");
@@ -1,14 +1,18 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
-    SubMsg, Uint64, WasmMsg,
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
+    StdResult, Storage, SubMsg, Timestamp, Uint64, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{PAUSED, QUEUE, TICK_MAX_GAS, WHITELIST};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TickEstimate, TickOutcome};
+use crate::state::{
+    LAST_TICK_OUTCOMES, LEGACY_QUEUE, PAUSED, SCHEDULE, SCHEDULE_KEYS, SCHEDULE_SEQUENCE,
+    TICK_INTERVALS, TICK_MAX_GAS, TICK_RECEIVER, WHITELIST,
+};
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -17,10 +21,13 @@ pub const MIN_TICK_MAX_GAS: Uint64 = Uint64::new(200_000);
 pub const DEFAULT_TICK_MAX_GAS: Uint64 = Uint64::new(2_900_000);
 pub const MAX_TICK_MAX_GAS: Uint64 = Uint64::new(3_000_000);
 
+pub const DEFAULT_TICK_ESTIMATES_LIMIT: u32 = 10;
+pub const MAX_TICK_ESTIMATES_LIMIT: u32 = 50;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -51,8 +58,19 @@ pub fn instantiate(
         .collect::<StdResult<Vec<Addr>>>()?;
 
     initial_queue
-        .into_iter()
-        .try_for_each(|addr| QUEUE.enqueue(deps.storage, addr))?;
+        .iter()
+        .try_for_each(|addr| schedule(deps.storage, addr.clone(), env.block.time.seconds()))?;
+
+    // intervals may only be set for the receivers the clock may tick
+    for (addr, tick_interval) in msg.tick_intervals {
+        let addr = deps.api.addr_validate(&addr)?;
+        if !whitelist.contains(&addr) && !initial_queue.contains(&addr) {
+            return Err(ContractError::NotWhitelisted);
+        }
+        if !tick_interval.is_zero() {
+            TICK_INTERVALS.save(deps.storage, addr, &tick_interval.u64())?;
+        }
+    }
 
     Ok(Response::default()
         .add_attribute("method", "instantiate")
@@ -62,7 +80,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -73,11 +91,27 @@ pub fn execute(
 
     match msg {
         ExecuteMsg::Tick {} => {
-            if let Some(receiver) = QUEUE.dequeue(deps.storage)? {
-                QUEUE.enqueue(deps.storage, receiver.clone())?;
+            if let Some(receiver) = pop_due(deps.storage, env.block.time)? {
+                let tick_interval = TICK_INTERVALS
+                    .may_load(deps.storage, receiver.clone())?
+                    .unwrap_or_default();
+                let next_due = env.block.time.seconds() + tick_interval;
+                schedule(deps.storage, receiver.clone(), next_due)?;
+                // the outcome is assumed to be successful unless
+                // the reply reports an error
+                LAST_TICK_OUTCOMES.save(
+                    deps.storage,
+                    receiver.clone(),
+                    &TickOutcome {
+                        height: env.block.height,
+                        error: None,
+                    },
+                )?;
+                TICK_RECEIVER.save(deps.storage, &receiver)?;
                 Ok(Response::default()
                     .add_attribute("method", "execute_tick")
                     .add_attribute("dequeued", receiver.as_str())
+                    .add_attribute("next_due", next_due.to_string())
                     .add_submessage(
                         SubMsg::reply_on_error(
                             WasmMsg::Execute {
@@ -96,7 +130,7 @@ pub fn execute(
             }
         }
         ExecuteMsg::Enqueue {} => {
-            if QUEUE.has(deps.storage, info.sender.clone()) {
+            if SCHEDULE_KEYS.has(deps.storage, info.sender.clone()) {
                 return Err(ContractError::AlreadyEnqueued);
             }
             // Make sure the caller is whitelisted
@@ -112,17 +146,30 @@ pub fn execute(
                 .query_wasm_contract_info(info.sender.as_str())
                 .map_err(|e| ContractError::NotContract(e.to_string()))?;
 
-            QUEUE.enqueue(deps.storage, info.sender.clone())?;
+            schedule(deps.storage, info.sender.clone(), env.block.time.seconds())?;
             Ok(Response::default()
                 .add_attribute("method", "execute_enqueue")
                 .add_attribute("sender", info.sender))
         }
         ExecuteMsg::Dequeue {} => {
-            QUEUE.remove(deps.storage, info.sender.clone())?;
+            unschedule(deps.storage, info.sender.clone())?;
             Ok(Response::default()
                 .add_attribute("method", "execute_dequeue")
                 .add_attribute("sender", info.sender))
         }
+        ExecuteMsg::SetTickInterval { tick_interval } => {
+            // only queued receivers may declare an interval
+            SCHEDULE_KEYS.load(deps.storage, info.sender.clone())?;
+            if tick_interval.is_zero() {
+                TICK_INTERVALS.remove(deps.storage, info.sender.clone());
+            } else {
+                TICK_INTERVALS.save(deps.storage, info.sender.clone(), &tick_interval.u64())?;
+            }
+            Ok(Response::default()
+                .add_attribute("method", "execute_set_tick_interval")
+                .add_attribute("sender", info.sender)
+                .add_attribute("tick_interval", tick_interval))
+        }
     }
 }
 
@@ -130,41 +177,144 @@ pub fn execute(
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::IsQueued { address } => {
-            to_json_binary(&QUEUE.has(deps.storage, Addr::unchecked(address)))
+            to_json_binary(&SCHEDULE_KEYS.has(deps.storage, Addr::unchecked(address)))
+        }
+        QueryMsg::Queue { start_after, limit } => {
+            to_json_binary(&query_queue(deps, start_after, limit)?)
         }
-        QueryMsg::Queue { start_after, limit } => to_json_binary(
-            &QUEUE.query_queue(
-                deps.storage,
-                start_after
-                    .map(|a| deps.api.addr_validate(&a))
-                    .transpose()?,
-                limit,
-            )?,
-        ),
         QueryMsg::TickMaxGas {} => to_json_binary(&TICK_MAX_GAS.load(deps.storage)?),
         QueryMsg::Paused {} => to_json_binary(&PAUSED.load(deps.storage)?),
         QueryMsg::Whitelist {} => to_json_binary(&WHITELIST.load(deps.storage)?),
+        QueryMsg::EstimateNextTick { limit } => to_json_binary(&query_tick_estimates(deps, limit)?),
+        QueryMsg::TickInterval { address } => to_json_binary(&Uint64::new(
+            TICK_INTERVALS
+                .may_load(deps.storage, Addr::unchecked(address))?
+                .unwrap_or_default(),
+        )),
+    }
+}
+
+fn query_queue(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, u64)>> {
+    let start_after = start_after
+        .map(|a| deps.api.addr_validate(&a))
+        .transpose()?;
+    let range = SCHEDULE_KEYS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .map(|item| item.map(|(addr, (next_due, _))| (addr, next_due)));
+    match limit {
+        None => range.collect(),
+        Some(limit) => range.take(limit as usize).collect(),
     }
 }
 
+fn query_tick_estimates(deps: Deps, limit: Option<u32>) -> StdResult<Vec<TickEstimate>> {
+    let gas_limit = TICK_MAX_GAS.load(deps.storage)?;
+    let limit = limit
+        .unwrap_or(DEFAULT_TICK_ESTIMATES_LIMIT)
+        .min(MAX_TICK_ESTIMATES_LIMIT);
+    SCHEDULE
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let ((next_due, _), receiver) = item?;
+            Ok(TickEstimate {
+                last_tick: LAST_TICK_OUTCOMES.may_load(deps.storage, receiver.clone())?,
+                receiver,
+                gas_limit,
+                next_due: Timestamp::from_seconds(next_due),
+            })
+        })
+        .collect()
+}
+
+/// Adds the receiver to the schedule, due at `next_due` seconds.
+fn schedule(storage: &mut dyn Storage, receiver: Addr, next_due: u64) -> StdResult<()> {
+    let sequence = SCHEDULE_SEQUENCE.may_load(storage)?.unwrap_or_default();
+    SCHEDULE_SEQUENCE.save(storage, &(sequence + 1))?;
+    SCHEDULE.save(storage, (next_due, sequence), &receiver)?;
+    SCHEDULE_KEYS.save(storage, receiver, &(next_due, sequence))
+}
+
+/// Removes the receiver and its tick state from the schedule. Errors
+/// if the receiver is not scheduled.
+fn unschedule(storage: &mut dyn Storage, receiver: Addr) -> StdResult<()> {
+    let key = SCHEDULE_KEYS.load(storage, receiver.clone())?;
+    SCHEDULE.remove(storage, key);
+    SCHEDULE_KEYS.remove(storage, receiver.clone());
+    TICK_INTERVALS.remove(storage, receiver.clone());
+    LAST_TICK_OUTCOMES.remove(storage, receiver);
+    Ok(())
+}
+
+/// Pops the receiver due the earliest off the schedule, if it is due
+/// by `now`. The caller is responsible for rescheduling it.
+fn pop_due(storage: &mut dyn Storage, now: Timestamp) -> StdResult<Option<Addr>> {
+    let Some(((next_due, sequence), receiver)) = SCHEDULE
+        .range(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?
+    else {
+        return Ok(None);
+    };
+    if next_due > now.seconds() {
+        return Ok(None);
+    }
+    SCHEDULE.remove(storage, (next_due, sequence));
+    SCHEDULE_KEYS.remove(storage, receiver.clone());
+    Ok(Some(receiver))
+}
+
+/// Moves the receivers of the flat queue used by previous versions
+/// into the schedule, preserving their order. They are all due
+/// immediately. A no-op once the legacy queue is empty.
+fn migrate_legacy_queue(storage: &mut dyn Storage, now: Timestamp) -> StdResult<()> {
+    while let Some(receiver) = LEGACY_QUEUE.dequeue(storage)? {
+        if !SCHEDULE_KEYS.has(storage, receiver.clone()) {
+            schedule(storage, receiver, now.seconds())?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     // Tick messages are dispatched with reply ID 0 and reply on
     // error. If an error occurs, we ignore it but stop the parent
     // message from failing, so the state change which moved the tick
-    // receiver to the end of the message queue gets committed. This
+    // receiver to its next due time gets committed. This
     // prevents an erroring tick receiver from locking the clock.
     if msg.id != 0 {
         Err(ContractError::UnexpectedReplyId(msg.id))
     } else {
+        let error = msg.result.unwrap_err();
+        let receiver = TICK_RECEIVER.load(deps.storage)?;
+        LAST_TICK_OUTCOMES.save(
+            deps.storage,
+            receiver,
+            &TickOutcome {
+                height: env.block.height,
+                error: Some(error.clone()),
+            },
+        )?;
         Ok(Response::default()
             .add_attribute("method", "reply_on_error")
-            .add_attribute("error", msg.result.unwrap_err()))
+            .add_attribute("error", error))
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    migrate_legacy_queue(deps.storage, env.block.time)?;
+
     match msg {
         MigrateMsg::Pause {} => {
             let is_paused = PAUSED.load(deps.storage)?;
@@ -206,7 +356,9 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
             if let Some(addrs) = remove {
                 for addr in addrs {
                     if let Some(index) = whitelist.iter().position(|x| x == &addr) {
-                        QUEUE.remove(deps.storage, whitelist[index].clone())?;
+                        if SCHEDULE_KEYS.has(deps.storage, whitelist[index].clone()) {
+                            unschedule(deps.storage, whitelist[index].clone())?;
+                        }
                         whitelist.swap_remove(index);
                     }
                 }
@@ -222,7 +374,9 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                             .query_wasm_contract_info(addr.as_str())
                             .map_err(|e| ContractError::NotContract(e.to_string()))?;
 
-                        QUEUE.enqueue(deps.storage, addr.clone())?;
+                        if !SCHEDULE_KEYS.has(deps.storage, addr.clone()) {
+                            schedule(deps.storage, addr.clone(), env.block.time.seconds())?;
+                        }
                         whitelist.push(addr);
                     }
                 }
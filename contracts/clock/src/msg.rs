@@ -4,6 +4,7 @@ use cosmwasm_std::Addr;
 use cosmwasm_std::to_json_binary;
 use cosmwasm_std::Binary;
 use cosmwasm_std::StdResult;
+use cosmwasm_std::Timestamp;
 use cosmwasm_std::Uint64;
 use cosmwasm_std::WasmMsg;
 use covenant_macros::clocked;
@@ -12,7 +13,7 @@ use covenant_macros::clocked;
 pub struct InstantiateMsg {
     /// The max gas that may be used by a tick receiver. If more than
     /// this amount of gas is used, the tick will be treated as if it
-    /// errored, and it will be sent to the back of the queue.
+    /// errored, and it will be rescheduled after its tick interval.
     ///
     /// At the most, this should be no larger than 100_000 gas less
     /// the chain's block max gas. This overhead is needed so the
@@ -24,8 +25,14 @@ pub struct InstantiateMsg {
     /// Whitelist of contracts that are allowed to be queued and ticked
     pub whitelist: Vec<String>,
     /// Initial list of contracts to be enqueued
-    /// (so they don't need to call `Enqueue` themselves)
+    /// (so they don't need to call `Enqueue` themselves). They are
+    /// due immediately and ticked as often as possible.
     pub initial_queue: Vec<String>,
+    /// Tick intervals in seconds of whitelisted receivers, as if
+    /// they declared them via `SetTickInterval` once enqueued.
+    /// Lets deployers throttle receivers that are mostly idle.
+    #[serde(default)]
+    pub tick_intervals: Vec<(String, Uint64)>,
 }
 
 impl InstantiateMsg {
@@ -51,15 +58,21 @@ impl InstantiateMsg {
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Enqueues the message sender for ticks (serialized as messages
-    /// in the form `{"tick": {}}`). The sender will continue to
-    /// receive ticks until sending a `Dequeue {}` message. Only
-    /// callable if the message sender is not currently enqueued and
-    /// is a contract.
+    /// in the form `{"tick": {}}`). The sender is due immediately
+    /// and will continue to receive ticks until sending a `Dequeue
+    /// {}` message. Only callable if the message sender is not
+    /// currently enqueued and is a contract.
     Enqueue {},
     /// Dequeues the message sender stopping them from receiving
     /// ticks. Only callable if the message sender is currently
     /// enqueued.
     Dequeue {},
+    /// Declares the minimum number of seconds between two ticks of
+    /// the message sender. Zero, the default, ticks the sender as
+    /// often as possible. Takes effect from the next tick of the
+    /// sender. Only callable if the message sender is currently
+    /// enqueued.
+    SetTickInterval { tick_interval: Uint64 },
 }
 
 #[cw_serde]
@@ -71,8 +84,8 @@ pub enum QueryMsg {
     IsQueued { address: String },
     /// Paginated query for all the elements in the queue. Returns
     /// elements in asending order by address in the form (address,
-    /// timestamp) where timestamp is the nanosecond unix timestamp at
-    /// which address was added to the queue.
+    /// next_due) where next_due is the unix timestamp in seconds
+    /// from which address is due for its next tick.
     #[returns(Vec<(Addr, u64)>)]
     Queue {
         start_after: Option<String>,
@@ -89,6 +102,44 @@ pub enum QueryMsg {
     /// Queries if the contract is paused.
     #[returns(Vec<Addr>)]
     Whitelist {},
+    /// Returns the receivers in the order in which they will be
+    /// ticked, along with the gas limit their ticks are dispatched
+    /// with, the time from which they are due, and the outcome of
+    /// their last tick. Keepers batching N ticks into a single
+    /// transaction should size its gas limit after the first N
+    /// elements that are due. Returns at most `limit` elements,
+    /// defaulting to `DEFAULT_TICK_ESTIMATES_LIMIT`.
+    #[returns(Vec<TickEstimate>)]
+    EstimateNextTick { limit: Option<u32> },
+    /// Returns the tick interval in seconds declared by `address`.
+    #[returns(Uint64)]
+    TickInterval { address: String },
+}
+
+/// Outcome of the last tick dispatched to a receiver.
+#[cw_serde]
+pub struct TickOutcome {
+    /// Block height at which the tick was dispatched.
+    pub height: u64,
+    /// The error returned by the receiver, if the tick failed.
+    pub error: Option<String>,
+}
+
+/// Gas estimate of an upcoming tick.
+#[cw_serde]
+pub struct TickEstimate {
+    /// The contract receiving the tick.
+    pub receiver: Addr,
+    /// The max gas the tick may consume. Replies do not report the
+    /// gas used by a submessage, so this is the tightest bound known
+    /// to the clock. Ticks exceeding it are treated as errors.
+    pub gas_limit: Uint64,
+    /// Time from which the receiver is due. Ticks sent before then
+    /// do not reach it.
+    pub next_due: Timestamp,
+    /// Outcome of the last tick of the receiver, if it was ever
+    /// ticked.
+    pub last_tick: Option<TickOutcome>,
 }
 
 #[cw_serde]
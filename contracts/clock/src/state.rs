@@ -1,8 +1,27 @@
 use cosmwasm_std::{Addr, Uint64};
 use cw_fifo::FIFOQueue;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
-pub(crate) const QUEUE: FIFOQueue<Addr> = FIFOQueue::new("front", "back", "count");
+use crate::msg::TickOutcome;
+
+/// The flat queue of receivers used before ticks were scheduled.
+/// Only read to move its entries into the schedule on migration.
+pub(crate) const LEGACY_QUEUE: FIFOQueue<Addr> = FIFOQueue::new("front", "back", "count");
 pub(crate) const PAUSED: Item<bool> = Item::new("paused");
 pub(crate) const TICK_MAX_GAS: Item<Uint64> = Item::new("tmg");
 pub(crate) const WHITELIST: Item<Vec<Addr>> = Item::new("whitelist");
+pub(crate) const LAST_TICK_OUTCOMES: Map<Addr, TickOutcome> = Map::new("lto");
+/// The receiver of the tick currently being dispatched. Used to
+/// attribute errors in the reply.
+pub(crate) const TICK_RECEIVER: Item<Addr> = Item::new("tr");
+/// Min-heap of the scheduled receivers keyed by (next due time in
+/// seconds, sequence number). The sequence number keeps receivers
+/// due at the same time in the order in which they were scheduled.
+pub(crate) const SCHEDULE: Map<(u64, u64), Addr> = Map::new("schedule");
+/// The key of every scheduled receiver in `SCHEDULE`.
+pub(crate) const SCHEDULE_KEYS: Map<Addr, (u64, u64)> = Map::new("schedule_keys");
+/// Sequence number of the next scheduled entry.
+pub(crate) const SCHEDULE_SEQUENCE: Item<u64> = Item::new("schedule_seq");
+/// Tick interval in seconds declared by the receivers. Receivers
+/// without an interval are ticked as often as possible.
+pub(crate) const TICK_INTERVALS: Map<Addr, u64> = Map::new("tick_intervals");
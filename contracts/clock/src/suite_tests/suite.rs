@@ -4,7 +4,7 @@ use valence_clock_tester::msg::Mode;
 
 use crate::{
     contract::DEFAULT_TICK_MAX_GAS,
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TickEstimate},
 };
 
 use super::{clock_contract, clock_tester_contract};
@@ -33,6 +33,7 @@ impl Default for SuiteBuilder {
                 tick_max_gas: Some(DEFAULT_TICK_MAX_GAS),
                 whitelist: vec![],
                 initial_queue: vec![],
+                tick_intervals: vec![],
             },
         }
     }
@@ -54,6 +55,14 @@ impl SuiteBuilder {
         self
     }
 
+    pub fn with_tick_intervals(mut self, tick_intervals: Vec<(Addr, u64)>) -> Self {
+        self.instantiate.tick_intervals = tick_intervals
+            .into_iter()
+            .map(|(addr, interval)| (addr.to_string(), Uint64::new(interval)))
+            .collect();
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let clock_code = self.app.store_code(clock_contract());
         let clock = self
@@ -121,6 +130,31 @@ impl Suite {
         Ok(self.query_queue_in_order_of_output())
     }
 
+    // sends a message on WHO's behalf which declares their tick
+    // interval.
+    pub fn set_tick_interval(
+        &mut self,
+        who: &str,
+        tick_interval: u64,
+    ) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(who),
+            self.clock.clone(),
+            &ExecuteMsg::SetTickInterval {
+                tick_interval: Uint64::new(tick_interval),
+            },
+            &[],
+        )
+    }
+
+    // advances the block time by `seconds`.
+    pub fn advance_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(seconds);
+        });
+    }
+
     // sends a tick to the clock.
     pub fn tick(&mut self) -> anyhow::Result<AppResponse> {
         self.app.execute_contract(
@@ -204,13 +238,13 @@ impl Suite {
             .unwrap()
     }
 
-    // queries the queue for all elements and returns addresses in the
-    // order that they will leave the queue (smallest timestamp
-    // first).
+    // queries the schedule for all elements and returns addresses in
+    // the order that they will be ticked (earliest due first).
     pub fn query_queue_in_order_of_output(&self) -> Vec<Addr> {
-        let mut queue = self.query_full_queue();
-        queue.sort_by_key(|(_, time)| *time);
-        queue.into_iter().map(|(addr, _)| addr).collect()
+        self.query_tick_estimates()
+            .into_iter()
+            .map(|estimate| estimate.receiver)
+            .collect()
     }
 
     pub fn query_tester_tick_count(&self, tester: &Addr) -> u64 {
@@ -225,6 +259,25 @@ impl Suite {
         res.u64()
     }
 
+    pub fn query_tick_estimates(&self, limit: Option<u32>) -> Vec<TickEstimate> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.clock, &QueryMsg::EstimateNextTick { limit })
+            .unwrap()
+    }
+
+    pub fn query_tick_interval(&self, who: &Addr) -> Uint64 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.clock,
+                &QueryMsg::TickInterval {
+                    address: who.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_whitelist(&self) -> Vec<Addr> {
         self.app
             .wrap()
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, StdError, Uint64};
+use cosmwasm_std::{Addr, Event, StdError, Uint64};
 use valence_clock_tester::msg::Mode;
 
 use crate::contract::DEFAULT_TICK_MAX_GAS;
@@ -21,7 +21,8 @@ fn test_instantiate_with_initial_queue() {
     let suite = builder
         .with_initial_queue(vec![receiver.to_string()])
         .build();
-    assert_eq!(suite.query_full_queue(), vec![(receiver, 0)]);
+    let now = suite.app.block_info().time.seconds();
+    assert_eq!(suite.query_full_queue(), vec![(receiver, now)]);
 }
 
 #[test]
@@ -74,12 +75,6 @@ fn test_queue() {
     // member.
     suite.tick().unwrap();
 
-    // This fails because when the element is immediately enqueued, it
-    // looks at the block's timestamp and notices that there is
-    // nothing there (as the other one is stored at timestamp + 1). to
-    // get around this, the queue likely needs to be modified to
-    // follow the logic of cw-storage-plus. or perhaps that queue can
-    // be used with a secondary index for reverse mappings.
     let queue = suite.query_queue_in_order_of_output();
     assert_eq!(queue[0], erroring);
     assert_eq!(queue[1], non_erroring);
@@ -109,6 +104,55 @@ fn test_queue() {
     assert_eq!(erroring_tick_count, 0);
 }
 
+// checks that tick estimates follow the queue order and record the
+// outcome of the last tick of every receiver.
+#[test]
+fn test_estimate_next_tick() {
+    let mut suite_builder = SuiteBuilder::default();
+
+    let non_erroring = suite_builder.generate_tester(Mode::Accept);
+    let erroring = suite_builder.generate_tester(Mode::Error);
+
+    let mut suite = suite_builder
+        .with_whitelist(vec![non_erroring.clone(), erroring.clone()])
+        .with_initial_queue(vec![non_erroring.to_string(), erroring.to_string()])
+        .build();
+
+    // Nothing was ticked yet.
+    let estimates = suite.query_tick_estimates(None);
+    assert_eq!(estimates.len(), 2);
+    assert_eq!(estimates[0].receiver, non_erroring);
+    assert_eq!(estimates[1].receiver, erroring);
+    assert!(estimates
+        .iter()
+        .all(|e| e.gas_limit == DEFAULT_TICK_MAX_GAS && e.last_tick.is_none()));
+
+    // The successful tick moves its receiver to the back of the
+    // queue.
+    suite.tick().unwrap();
+    let estimates = suite.query_tick_estimates(None);
+    assert_eq!(estimates[0].receiver, erroring);
+    assert_eq!(estimates[1].receiver, non_erroring);
+    assert_eq!(estimates[1].last_tick.as_ref().unwrap().error, None);
+
+    // Errors returned by the receiver are recorded.
+    suite.tick().unwrap();
+    let estimates = suite.query_tick_estimates(None);
+    assert_eq!(estimates[1].receiver, erroring);
+    assert!(estimates[1].last_tick.as_ref().unwrap().error.is_some());
+
+    // Estimates are limited to the first receivers to be ticked.
+    let estimates = suite.query_tick_estimates(Some(1));
+    assert_eq!(estimates.len(), 1);
+    assert_eq!(estimates[0].receiver, non_erroring);
+
+    // Dequeued receivers are no longer estimated.
+    suite.dequeue(erroring.as_str()).unwrap();
+    let estimates = suite.query_tick_estimates(None);
+    assert_eq!(estimates.len(), 1);
+    assert_eq!(estimates[0].receiver, non_erroring);
+}
+
 // checks that no execute messages can be called while the contract is
 // paused, and that they may be called once the contract is unpaused.
 #[test]
@@ -257,3 +301,99 @@ fn test_manage_whitelisted() {
     assert_eq!(queue.len(), 1);
     assert_eq!(queue[0].0, receiver_two);
 }
+
+// receivers are skipped by ticks until their declared tick interval
+// passed, and ticks do nothing while no receiver is due.
+#[test]
+fn test_tick_interval() {
+    let mut suite_builder = SuiteBuilder::default();
+    let frequent = suite_builder.generate_tester(Mode::Accept);
+    let idle = suite_builder.generate_tester(Mode::Accept);
+
+    let mut suite = suite_builder
+        .with_whitelist(vec![frequent.clone(), idle.clone()])
+        .build();
+
+    suite.enqueue(idle.as_str()).unwrap();
+    suite.enqueue(frequent.as_str()).unwrap();
+    suite.set_tick_interval(idle.as_str(), 100).unwrap();
+    suite.set_tick_interval(frequent.as_str(), 10).unwrap();
+    assert_eq!(suite.query_tick_interval(&idle), Uint64::new(100));
+    assert_eq!(suite.query_tick_interval(&frequent), Uint64::new(10));
+
+    // both are due, so both get ticked once and rescheduled after
+    // their intervals.
+    suite.tick().unwrap();
+    suite.tick().unwrap();
+    let now = suite.app.block_info().time.seconds();
+    let estimates = suite.query_tick_estimates(None);
+    assert_eq!(estimates[0].receiver, frequent);
+    assert_eq!(estimates[0].next_due.seconds(), now + 10);
+    assert_eq!(estimates[1].receiver, idle);
+    assert_eq!(estimates[1].next_due.seconds(), now + 100);
+
+    // nobody is due.
+    let res = suite.tick().unwrap();
+    res.assert_event(&Event::new("wasm").add_attribute("dequeued", "none"));
+
+    // only the frequent receiver is due.
+    suite.advance_time(50);
+    suite.tick().unwrap();
+    let res = suite.tick().unwrap();
+    res.assert_event(&Event::new("wasm").add_attribute("dequeued", "none"));
+    assert_eq!(suite.query_tester_tick_count(&frequent), 2);
+    assert_eq!(suite.query_tester_tick_count(&idle), 1);
+
+    suite.advance_time(50);
+    suite.tick().unwrap();
+    suite.tick().unwrap();
+    assert_eq!(suite.query_tester_tick_count(&frequent), 3);
+    assert_eq!(suite.query_tester_tick_count(&idle), 2);
+
+    // dequeueing clears the declared interval.
+    suite.dequeue(idle.as_str()).unwrap();
+    assert_eq!(suite.query_tick_interval(&idle), Uint64::zero());
+}
+
+// tick intervals can be set for whitelisted receivers at instantiation,
+// before they enqueue themselves.
+#[test]
+fn test_instantiate_with_tick_intervals() {
+    let mut suite_builder = SuiteBuilder::default();
+    let idle = suite_builder.generate_tester(Mode::Accept);
+
+    let mut suite = suite_builder
+        .with_whitelist(vec![idle.clone()])
+        .with_tick_intervals(vec![(idle.clone(), 100)])
+        .build();
+    assert_eq!(suite.query_tick_interval(&idle), Uint64::new(100));
+
+    suite.enqueue(idle.as_str()).unwrap();
+    suite.tick().unwrap();
+    let now = suite.app.block_info().time.seconds();
+    let estimates = suite.query_tick_estimates(None);
+    assert_eq!(estimates[0].next_due.seconds(), now + 100);
+}
+
+#[test]
+#[should_panic(expected = "Caller is not whitelisted, can't enqueue")]
+fn test_instantiate_with_tick_intervals_not_whitelisted() {
+    let mut suite_builder = SuiteBuilder::default();
+    let idle = suite_builder.generate_tester(Mode::Accept);
+    suite_builder.with_tick_intervals(vec![(idle, 100)]).build();
+}
+
+// only queued receivers may declare a tick interval.
+#[test]
+fn test_set_tick_interval_not_queued() {
+    let mut suite = SuiteBuilder::default().build();
+    let err: ContractError = suite
+        .set_tick_interval("nobody", 100)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::Std(StdError::NotFound { kind: _ })
+    ));
+}
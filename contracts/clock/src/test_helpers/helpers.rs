@@ -41,5 +41,6 @@ pub fn mock_neutron_clock_query(_deps: QueryDeps, _env: Env, msg: QueryMsg) -> S
         QueryMsg::TickMaxGas {} => Ok(Binary::default()),
         QueryMsg::Paused {} => Ok(Binary::default()),
         QueryMsg::Whitelist {} => Ok(Binary::default()),
+        QueryMsg::EstimateNextTick { .. } => Ok(Binary::default()),
     }
 }
@@ -0,0 +1,81 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw2::set_contract_version;
+
+use crate::{
+    error::ContractError,
+    invariants::check_covenant_invariants,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+};
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default().add_attribute("method", "covenant_invariant_checker_instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::AssertInvariants { covenant } => try_assert_invariants(deps, covenant),
+    }
+}
+
+fn try_assert_invariants(deps: DepsMut, covenant: String) -> Result<Response, ContractError> {
+    let covenant = deps.api.addr_validate(&covenant)?;
+    let report = check_covenant_invariants(deps.querier, &covenant)?;
+
+    if !report.is_ok() {
+        return Err(ContractError::InvariantsViolated {
+            count: report.violations.len(),
+            violations: report
+                .violations
+                .iter()
+                .map(|violation| format!("{:?}: {}", violation.invariant, violation.reason))
+                .collect::<Vec<String>>()
+                .join("; "),
+        });
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "try_assert_invariants")
+        .add_attribute("covenant", covenant))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::CheckInvariants { covenant } => {
+            let covenant = deps.api.addr_validate(&covenant)?;
+            to_json_binary(&check_covenant_invariants(deps.querier, &covenant)?)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {
+        MigrateMsg::UpdateCodeId { data: _ } => {
+            // This is a migrate message to update code id,
+            // Data is optional base64 that we can parse to any data we would like in the future
+            // let data: SomeStruct = from_binary(&data)?;
+            set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+            Ok(Response::default().add_attribute("method", "update_covenant_invariant_checker"))
+        }
+    }
+}
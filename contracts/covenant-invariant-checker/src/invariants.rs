@@ -0,0 +1,244 @@
+use std::cmp::Ordering;
+
+use cosmwasm_std::{Addr, Decimal, QuerierWrapper, StdResult};
+use covenant_utils::split::SplitConfig;
+use cw_utils::Expiration;
+use valence_covenant_two_party_pol::msg::QueryMsg as CovenantQueryMsg;
+use valence_two_party_pol_holder::msg::{
+    ContractState, DenomSplits, QueryMsg as HolderQueryMsg, TwoPartyPolCovenantConfig,
+};
+
+use crate::msg::{Invariant, InvariantReport, InvariantViolation};
+
+const PARTIES: [&str; 2] = ["party_a", "party_b"];
+
+/// modules of a two party POL covenant, as reported by the covenant
+pub struct CovenantModules {
+    pub clock: Option<Addr>,
+    pub holder: Option<Addr>,
+    pub liquid_pooler: Option<Addr>,
+    /// routers of party a and party b
+    pub routers: [Option<Addr>; 2],
+    /// forwarders of party a and party b, only set for interchain parties
+    pub forwarders: [Option<Addr>; 2],
+}
+
+impl CovenantModules {
+    pub fn query(querier: QuerierWrapper, covenant: &Addr) -> StdResult<Self> {
+        let query_party_module =
+            |query: fn(String) -> CovenantQueryMsg| -> StdResult<[Option<Addr>; 2]> {
+                Ok([
+                    querier.query_wasm_smart(covenant, &query(PARTIES[0].to_string()))?,
+                    querier.query_wasm_smart(covenant, &query(PARTIES[1].to_string()))?,
+                ])
+            };
+
+        Ok(CovenantModules {
+            clock: querier.query_wasm_smart(covenant, &CovenantQueryMsg::ClockAddress {})?,
+            holder: querier.query_wasm_smart(covenant, &CovenantQueryMsg::HolderAddress {})?,
+            liquid_pooler: querier
+                .query_wasm_smart(covenant, &CovenantQueryMsg::LiquidPoolerAddress {})?,
+            routers: query_party_module(|party| CovenantQueryMsg::InterchainRouterAddress {
+                party,
+            })?,
+            forwarders: query_party_module(|party| CovenantQueryMsg::IbcForwarderAddress {
+                party,
+            })?,
+        })
+    }
+
+    /// modules that are expected to be ticked by the clock
+    fn clocked_modules(&self) -> Vec<&Addr> {
+        [&self.holder, &self.liquid_pooler]
+            .into_iter()
+            .chain(self.routers.iter())
+            .chain(self.forwarders.iter())
+            .flatten()
+            .collect()
+    }
+}
+
+/// queries all modules of the covenant and collects the invariants they violate
+pub fn check_covenant_invariants(
+    querier: QuerierWrapper,
+    covenant: &Addr,
+) -> StdResult<InvariantReport> {
+    let modules = CovenantModules::query(querier, covenant)?;
+    let mut violations = vec![];
+
+    let (Some(holder), Some(clock)) = (&modules.holder, &modules.clock) else {
+        violations.push(InvariantViolation {
+            invariant: Invariant::ModuleWiring,
+            reason: "covenant has no holder or clock".to_string(),
+        });
+        return Ok(InvariantReport {
+            covenant: covenant.to_string(),
+            violations,
+        });
+    };
+
+    let state: ContractState =
+        querier.query_wasm_smart(holder, &HolderQueryMsg::ContractState {})?;
+    let denom_splits: DenomSplits =
+        querier.query_wasm_smart(holder, &HolderQueryMsg::DenomSplits {})?;
+    let config: TwoPartyPolCovenantConfig =
+        querier.query_wasm_smart(holder, &HolderQueryMsg::Config {})?;
+    let deposit_deadline: Expiration =
+        querier.query_wasm_smart(holder, &HolderQueryMsg::DepositDeadline {})?;
+    let lockup: Expiration = querier.query_wasm_smart(holder, &HolderQueryMsg::LockupConfig {})?;
+
+    violations.extend(check_split_shares(&denom_splits));
+    violations.extend(check_party_allocations(&config, &state));
+    violations.extend(check_module_wiring(
+        querier, holder, clock, &modules, &config,
+    )?);
+    if state != ContractState::Complete {
+        violations.extend(check_clock_coverage(querier, clock, &modules)?);
+    }
+    violations.extend(check_deadline_order(&deposit_deadline, &lockup));
+
+    Ok(InvariantReport {
+        covenant: covenant.to_string(),
+        violations,
+    })
+}
+
+/// every split must distribute exactly the entire amount of its denom
+pub fn check_split_shares(denom_splits: &DenomSplits) -> Vec<InvariantViolation> {
+    let validate_split = |name: String, split: &SplitConfig| {
+        let total_shares: Decimal = split.receivers.values().sum();
+        if total_shares != Decimal::one() {
+            Some(InvariantViolation {
+                invariant: Invariant::SplitShares,
+                reason: format!("{name} shares add up to {total_shares}"),
+            })
+        } else {
+            None
+        }
+    };
+
+    denom_splits
+        .explicit_splits
+        .iter()
+        .filter_map(|(denom, split)| validate_split(format!("{denom} split"), split))
+        .chain(
+            denom_splits
+                .fallback_split
+                .as_ref()
+                .and_then(|split| validate_split("fallback split".to_string(), split)),
+        )
+        .collect()
+}
+
+/// until one of the parties exits, the parties own the entire position.
+/// afterwards, the allocations may only decrease.
+pub fn check_party_allocations(
+    config: &TwoPartyPolCovenantConfig,
+    state: &ContractState,
+) -> Vec<InvariantViolation> {
+    let total_allocation = config.party_a.allocation + config.party_b.allocation;
+    let consistent = match state {
+        ContractState::Instantiated | ContractState::Active => total_allocation == Decimal::one(),
+        _ => total_allocation <= Decimal::one(),
+    };
+
+    if consistent {
+        vec![]
+    } else {
+        vec![InvariantViolation {
+            invariant: Invariant::PartyAllocations,
+            reason: format!("party allocations add up to {total_allocation} in {state} state"),
+        }]
+    }
+}
+
+/// the holder must be wired to the clock, liquid pooler, and routers of the covenant
+pub fn check_module_wiring(
+    querier: QuerierWrapper,
+    holder: &Addr,
+    clock: &Addr,
+    modules: &CovenantModules,
+    config: &TwoPartyPolCovenantConfig,
+) -> StdResult<Vec<InvariantViolation>> {
+    let mut violations = vec![];
+    let mut validate_address = |module: &str, expected: Option<&Addr>, actual: &str| {
+        if expected.map_or(true, |addr| addr.as_str() != actual) {
+            violations.push(InvariantViolation {
+                invariant: Invariant::ModuleWiring,
+                reason: format!(
+                    "holder {module} {actual} does not match the covenant {module} {expected:?}"
+                ),
+            });
+        }
+    };
+
+    let holder_clock: Addr = querier.query_wasm_smart(holder, &HolderQueryMsg::ClockAddress {})?;
+    validate_address("clock", Some(clock), holder_clock.as_str());
+
+    let holder_liquid_pooler: Addr =
+        querier.query_wasm_smart(holder, &HolderQueryMsg::NextContract {})?;
+    validate_address(
+        "liquid pooler",
+        modules.liquid_pooler.as_ref(),
+        holder_liquid_pooler.as_str(),
+    );
+
+    validate_address(
+        "party_a router",
+        modules.routers[0].as_ref(),
+        &config.party_a.router,
+    );
+    validate_address(
+        "party_b router",
+        modules.routers[1].as_ref(),
+        &config.party_b.router,
+    );
+
+    Ok(violations)
+}
+
+/// every module must be either queued or able to enqueue itself on the clock
+pub fn check_clock_coverage(
+    querier: QuerierWrapper,
+    clock: &Addr,
+    modules: &CovenantModules,
+) -> StdResult<Vec<InvariantViolation>> {
+    let whitelist: Vec<Addr> =
+        querier.query_wasm_smart(clock, &valence_clock::msg::QueryMsg::Whitelist {})?;
+
+    let mut violations = vec![];
+    for module in modules.clocked_modules() {
+        if whitelist.contains(module) {
+            continue;
+        }
+        let queued: bool = querier.query_wasm_smart(
+            clock,
+            &valence_clock::msg::QueryMsg::IsQueued {
+                address: module.to_string(),
+            },
+        )?;
+        if !queued {
+            violations.push(InvariantViolation {
+                invariant: Invariant::ClockCoverage,
+                reason: format!("{module} is neither queued nor whitelisted on the clock"),
+            });
+        }
+    }
+
+    Ok(violations)
+}
+
+/// deposits must be due before the lockup expires.
+/// expirations of different kinds (height and time) can not be compared and are skipped.
+pub fn check_deadline_order(
+    deposit_deadline: &Expiration,
+    lockup: &Expiration,
+) -> Vec<InvariantViolation> {
+    match deposit_deadline.partial_cmp(lockup) {
+        Some(Ordering::Greater) | Some(Ordering::Equal) => vec![InvariantViolation {
+            invariant: Invariant::DeadlineOrder,
+            reason: format!("deposit deadline {deposit_deadline} does not precede lockup {lockup}"),
+        }],
+        _ => vec![],
+    }
+}
@@ -0,0 +1,60 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Binary;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// errors if any invariant of the covenant is violated.
+    /// meant to be included ahead of covenant migrations, so that
+    /// the migration is reverted unless the covenant is consistent.
+    AssertInvariants { covenant: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// queries all modules of the (two party POL) covenant and
+    /// reports the invariants they violate
+    #[returns(InvariantReport)]
+    CheckInvariants { covenant: String },
+}
+
+#[cw_serde]
+pub enum MigrateMsg {
+    UpdateCodeId { data: Option<Binary> },
+}
+
+/// cross-module invariants of a covenant
+#[cw_serde]
+pub enum Invariant {
+    /// every denom split distributes exactly the entire amount
+    SplitShares,
+    /// party allocations are consistent with the holder state
+    PartyAllocations,
+    /// the holder routes the parties to the routers of the covenant
+    ModuleWiring,
+    /// the clock ticks every module until the covenant is complete
+    ClockCoverage,
+    /// the deposit deadline expires before the lockup
+    DeadlineOrder,
+}
+
+#[cw_serde]
+pub struct InvariantViolation {
+    pub invariant: Invariant,
+    pub reason: String,
+}
+
+#[cw_serde]
+pub struct InvariantReport {
+    pub covenant: String,
+    pub violations: Vec<InvariantViolation>,
+}
+
+impl InvariantReport {
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
@@ -5,16 +5,25 @@ use cosmos_sdk_proto::cosmos::bank::v1beta1::{Input, MsgMultiSend, Output};
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     ensure, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
-    StdResult, Uint128,
+    StdResult, Storage, SubMsg, Uint128,
 };
-use covenant_utils::{
-    ica::{
-        get_ica, msg_with_sudo_callback, prepare_sudo_payload, query_ica_registration_fee,
-        sudo_error, sudo_open_ack, sudo_response, sudo_timeout, INTERCHAIN_ACCOUNT_ID,
+use covenant_ica::{
+    registration::register_ica,
+    state::{get_ica, load_ica_state, IcaState},
+    sudo::{
+        handle_sudo, prepare_sudo_payload, read_sudo_payload, submit_ica_tx, sudo_open_ack,
+        sudo_response,
     },
+    INTERCHAIN_ACCOUNT_ID,
+};
+use covenant_utils::mock_ibc::get_contract_version;
+use covenant_utils::{
+    clock::{ensure_single_trigger_per_block, increment_tick_nonce},
+    decommission::{verify_decommission_authority, DecommissionError},
+    forwarder_milestone::{generate_milestone_callback_msg, ForwarderMilestone},
     neutron::{
-        assert_ibc_fee_coverage, get_proto_coin, query_ibc_fee, to_proto_msg_transfer,
-        RemoteChainInfo, SudoPayload,
+        assert_ibc_fee_coverage, get_proto_coin, query_ibc_fee_with_override,
+        to_proto_msg_transfer, validate_ibc_fee_override, RemoteChainInfo, SudoPayload,
     },
     op_mode::{verify_caller, ContractOperationMode},
 };
@@ -22,25 +31,25 @@ use cw2::set_contract_version;
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery, types::ProtobufAny},
     interchain_txs::helpers::get_port_id,
-    sudo::msg::SudoMsg,
+    sudo::msg::{RequestPacket, SudoMsg},
     NeutronError, NeutronResult,
 };
 use prost::Message;
 
-use crate::state::{IbcForwarderIcaStateHelper, FALLBACK_ADDRESS};
+use crate::state::{
+    IbcForwarderIcaHooks, FALLBACK_ADDRESS, FIRST_FORWARD, IBC_FEE_OVERRIDE, MILESTONE_CALLBACK,
+};
 use crate::{error::ContractError, msg::FallbackAddressUpdateConfig};
 use crate::{
     helpers::{get_next_memo, MsgTransfer},
     msg::{ContractState, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    state::{
-        CONTRACT_OP_MODE, CONTRACT_STATE, INTERCHAIN_ACCOUNTS, NEXT_CONTRACT, REMOTE_CHAIN_INFO,
-        TRANSFER_AMOUNT,
-    },
+    state::{CONTRACT_OP_MODE, CONTRACT_STATE, NEXT_CONTRACT, REMOTE_CHAIN_INFO, TRANSFER_AMOUNT},
 };
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const SUDO_PAYLOAD_REPLY_ID: u64 = 1;
+pub const MILESTONE_CALLBACK_REPLY_ID: u64 = 2;
 
 type QueryDeps<'a> = Deps<'a, NeutronQuery>;
 type ExecuteDeps<'a> = DepsMut<'a, NeutronQuery>;
@@ -52,7 +61,11 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_contract_version(
+        deps.storage,
+        CONTRACT_NAME,
+        get_contract_version(CONTRACT_VERSION),
+    )?;
 
     let next_contract = deps.api.addr_validate(&msg.next_contract)?;
     let op_mode = ContractOperationMode::try_init(deps.api, msg.op_mode_cfg.clone())?;
@@ -72,6 +85,13 @@ pub fn instantiate(
     if let Some(addr) = &msg.fallback_address {
         FALLBACK_ADDRESS.save(deps.storage, addr)?;
     }
+    if let Some(addr) = &msg.milestone_callback {
+        MILESTONE_CALLBACK.save(deps.storage, &deps.api.addr_validate(addr)?)?;
+    }
+    if let Some(ibc_fee) = &msg.ibc_fee_override {
+        validate_ibc_fee_override(ibc_fee)?;
+        IBC_FEE_OVERRIDE.save(deps.storage, ibc_fee)?;
+    }
 
     Ok(Response::default()
         .add_attribute("method", "ibc_forwarder_instantiate")
@@ -89,24 +109,41 @@ pub fn execute(
 ) -> NeutronResult<Response<NeutronMsg>> {
     match msg {
         ExecuteMsg::DistributeFallback { coins } => try_distribute_fallback(deps, env, info, coins),
-        ExecuteMsg::Tick {} => try_tick(deps, env, info),
+        ExecuteMsg::Decommission {} => try_decommission(deps, env, info),
+        ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
+            Ok(try_tick(deps, env, info)?.add_attribute("tick_nonce", tick_nonce.to_string()))
+        }
     }
 }
 
 fn try_distribute_fallback(
-    mut deps: ExecuteDeps,
+    deps: ExecuteDeps,
     env: Env,
     info: MessageInfo,
     coins: Vec<cosmwasm_std::Coin>,
 ) -> NeutronResult<Response<NeutronMsg>> {
+    // fallback distributions submit an ica tx, which must not be repeated by
+    // retries landing in the same block. fallback distributions are
+    // permissionless, so the guard is kept per sender for others not to be
+    // locked out.
+    ensure_single_trigger_per_block(
+        deps.storage,
+        &env.block,
+        &format!("distribute_fallback_{}", info.sender),
+    )?;
+
     // load the fallback address or error out if its not set
     let destination = match FALLBACK_ADDRESS.may_load(deps.storage)? {
         Some(addr) => addr,
         None => return Err(ContractError::MissingFallbackAddress {}.into()),
     };
     let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
+    // once decommissioned, the residual target denom can be returned as well
+    let decommissioned = CONTRACT_STATE.load(deps.storage)? == ContractState::Decommissioned;
 
-    let min_ibc_fee_config = query_ibc_fee(deps.querier)?;
+    let min_ibc_fee_config =
+        query_ibc_fee_with_override(deps.querier, IBC_FEE_OVERRIDE.may_load(deps.storage)?)?;
     assert_ibc_fee_coverage(info, min_ibc_fee_config.total_ntrn_fee, Uint128::one())?;
 
     // we iterate over coins to be distributed, validate them, and generate the proto coins to be sent
@@ -116,7 +153,7 @@ fn try_distribute_fallback(
     for coin in coins {
         // validate that target denom is not passed for fallback distribution
         ensure!(
-            coin.denom != remote_chain_info.denom,
+            decommissioned || coin.denom != remote_chain_info.denom,
             Into::<NeutronError>::into(ContractError::UnauthorizedDenomDistribution {})
         );
 
@@ -130,8 +167,11 @@ fn try_distribute_fallback(
     }
 
     let port_id = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
-    let interchain_account = INTERCHAIN_ACCOUNTS.may_load(deps.storage, port_id.clone())?;
-    if let Some(Some((address, controller_conn_id))) = interchain_account {
+    if let IcaState::Active {
+        address,
+        controller_connection_id: controller_conn_id,
+    } = load_ica_state(deps.storage)?
+    {
         let multi_send_msg = MsgMultiSend {
             inputs: vec![Input {
                 address,
@@ -156,19 +196,12 @@ fn try_distribute_fallback(
             type_url: "/cosmos.bank.v1beta1.MsgMultiSend".to_string(),
             value: Binary::from(buf),
         };
-        let submit_msg = NeutronMsg::submit_tx(
+        let sudo_msgs = submit_ica_tx(
+            deps.storage,
             controller_conn_id,
-            INTERCHAIN_ACCOUNT_ID.to_string(),
             vec![any_msg],
-            "".to_string(),
             remote_chain_info.ica_timeout.u64(),
             min_ibc_fee_config.ibc_fee,
-        );
-        let state_helper = IbcForwarderIcaStateHelper;
-        let sudo_msg = msg_with_sudo_callback(
-            &state_helper,
-            deps.branch(),
-            submit_msg,
             SudoPayload {
                 port_id,
                 message: "distribute_fallback_multisend".to_string(),
@@ -178,12 +211,33 @@ fn try_distribute_fallback(
 
         Ok(Response::default()
             .add_attribute("method", "try_forward_fallback")
-            .add_submessages(vec![sudo_msg]))
+            .add_submessages(sudo_msgs))
     } else {
         Err(NeutronError::Std(StdError::generic_err("no ica found")))
     }
 }
 
+/// winds the forwarder down after the covenant completed. the forwarder stops
+/// forwarding funds, and the residual ICA balances (including the target denom)
+/// can then be returned to the fallback address via `DistributeFallback`.
+fn try_decommission(
+    deps: ExecuteDeps,
+    env: Env,
+    info: MessageInfo,
+) -> NeutronResult<Response<NeutronMsg>> {
+    verify_decommission_authority(&deps.querier, &env, &info.sender)?;
+    ensure!(
+        CONTRACT_STATE.load(deps.storage)? != ContractState::Decommissioned,
+        Into::<NeutronError>::into(DecommissionError::Decommissioned)
+    );
+
+    CONTRACT_STATE.save(deps.storage, &ContractState::Decommissioned)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_decommission")
+        .add_attribute("contract_state", "decommissioned"))
+}
+
 /// attempts to advance the state machine. validates the caller to be the clock.
 fn try_tick(deps: ExecuteDeps, env: Env, info: MessageInfo) -> NeutronResult<Response<NeutronMsg>> {
     verify_caller(&info.sender, &CONTRACT_OP_MODE.load(deps.storage)?)?;
@@ -192,31 +246,31 @@ fn try_tick(deps: ExecuteDeps, env: Env, info: MessageInfo) -> NeutronResult<Res
     match current_state {
         ContractState::Instantiated => try_register_ica(deps, env),
         ContractState::IcaCreated => try_forward_funds(env, deps),
+        ContractState::Decommissioned => Ok(Response::default()
+            .add_attribute("method", "try_tick")
+            .add_attribute("contract_state", "decommissioned")),
     }
 }
 
 /// tries to register an ICA on the remote chain
 fn try_register_ica(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<NeutronMsg>> {
     let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
-    let ica_registration_fee = query_ica_registration_fee(deps.querier)?;
 
-    let register_msg = NeutronMsg::register_interchain_account(
+    // the account gets activated in the sudo_open_ack callback
+    let register_msgs = register_ica(
+        &IbcForwarderIcaHooks,
+        deps.storage,
+        deps.querier,
+        &env,
         remote_chain_info.connection_id,
-        INTERCHAIN_ACCOUNT_ID.to_string(),
-        Some(ica_registration_fee),
-    );
-
-    let key = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
-
-    // we are saving empty data here because we handle response of registering ICA in sudo_open_ack method
-    INTERCHAIN_ACCOUNTS.save(deps.storage, key, &None)?;
+    )?;
 
     Ok(Response::new()
         .add_attribute("method", "try_register_ica")
-        .add_message(register_msg))
+        .add_messages(register_msgs))
 }
 
-fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<NeutronMsg>> {
+fn try_forward_funds(env: Env, deps: ExecuteDeps) -> NeutronResult<Response<NeutronMsg>> {
     // first we verify whether the next contract is ready for receiving the funds
     let next_contract = NEXT_CONTRACT.load(deps.storage)?;
     let deposit_address_query: Option<String> = deps.querier.query_wasm_smart(
@@ -231,13 +285,16 @@ fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<
         )));
     };
 
-    let min_fee_query_response = query_ibc_fee(deps.querier)?;
+    let min_fee_query_response =
+        query_ibc_fee_with_override(deps.querier, IBC_FEE_OVERRIDE.may_load(deps.storage)?)?;
 
     let port_id = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
-    let interchain_account = INTERCHAIN_ACCOUNTS.load(deps.storage, port_id.clone())?;
 
-    match interchain_account {
-        Some((address, controller_conn_id)) => {
+    match load_ica_state(deps.storage)? {
+        IcaState::Active {
+            address,
+            controller_connection_id: controller_conn_id,
+        } => {
             let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
             let amount = TRANSFER_AMOUNT.load(deps.storage)?;
 
@@ -261,22 +318,14 @@ fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<
 
             let protobuf_msg = to_proto_msg_transfer(transfer_msg)?;
 
-            // tx to our ICA that wraps the transfer message defined above
-            let submit_msg = NeutronMsg::submit_tx(
+            // tx to our ICA that wraps the transfer message defined above,
+            // with a sudo callback
+            let submsgs = submit_ica_tx(
+                deps.storage,
                 controller_conn_id,
-                INTERCHAIN_ACCOUNT_ID.to_string(),
                 vec![protobuf_msg],
-                "".to_string(),
                 remote_chain_info.ica_timeout.u64(),
                 min_fee_query_response.ibc_fee,
-            );
-
-            // sudo callback msg
-            // let state_helper = IbcForwarderIcaStateHelper;
-            let submsg = msg_with_sudo_callback(
-                &IbcForwarderIcaStateHelper,
-                deps.branch(),
-                submit_msg,
                 SudoPayload {
                     port_id,
                     message: "try_forward_funds".to_string(),
@@ -286,9 +335,9 @@ fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<
 
             Ok(Response::default()
                 .add_attribute("method", "try_forward_funds")
-                .add_submessage(submsg))
+                .add_submessages(submsgs))
         }
-        None => {
+        _ => {
             // I can't think of a case of how we could end up here as `sudo_open_ack`
             // callback advances the state to `ICACreated` and stores the ICA.
             // just in case, we revert the state to `Instantiated` to restart the flow.
@@ -301,37 +350,19 @@ fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> NeutronResult<Binary> {
+pub fn query(deps: QueryDeps, _env: Env, msg: QueryMsg) -> NeutronResult<Binary> {
     match msg {
         // we expect to receive funds into our ICA account on the remote chain.
         // if the ICA had not been opened yet, we return `None` so that the
         // contract querying this will be instructed to wait and retry.
         QueryMsg::DepositAddress {} => {
-            let key = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
             // here we want to return None instead of any errors in case no ICA
             // is registered yet
-            let ica = match INTERCHAIN_ACCOUNTS.may_load(deps.storage, key)? {
-                Some(entry) => {
-                    if let Some((addr, _)) = entry {
-                        Some(addr)
-                    } else {
-                        None
-                    }
-                }
-                None => None,
-            };
+            let ica = load_ica_state(deps.storage)?.address();
 
             Ok(to_json_binary(&ica)?)
         }
-        QueryMsg::IcaAddress {} => Ok(to_json_binary(
-            &get_ica(
-                &IbcForwarderIcaStateHelper,
-                deps.storage,
-                env.contract.address.as_str(),
-                INTERCHAIN_ACCOUNT_ID,
-            )?
-            .0,
-        )?),
+        QueryMsg::IcaAddress {} => Ok(to_json_binary(&get_ica(deps.storage)?.0)?),
         QueryMsg::RemoteChainInfo {} => {
             Ok(to_json_binary(&REMOTE_CHAIN_INFO.may_load(deps.storage)?)?)
         }
@@ -342,21 +373,19 @@ pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> NeutronResult<Binary>
         QueryMsg::OperationMode {} => {
             Ok(to_json_binary(&CONTRACT_OP_MODE.may_load(deps.storage)?)?)
         }
+        QueryMsg::IbcFeeOverride {} => {
+            Ok(to_json_binary(&IBC_FEE_OVERRIDE.may_load(deps.storage)?)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
+pub fn sudo(mut deps: ExecuteDeps, _env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
     match msg {
         // For handling successful (non-error) acknowledgements.
-        SudoMsg::Response { request, data } => sudo_response(request, data),
-
-        // For handling error acknowledgements.
-        SudoMsg::Error { request, details } => sudo_error(request, details),
-
-        // For handling error timeouts.
-        SudoMsg::Timeout { request } => {
-            sudo_timeout(&IbcForwarderIcaStateHelper, deps, env, request)
+        SudoMsg::Response { request, data } => {
+            let response = sudo_response(request.clone(), data)?;
+            try_record_first_forward(deps, request, response)
         }
 
         // For handling successful registering of ICA
@@ -365,23 +394,83 @@ pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response<Neu
             channel_id,
             counterparty_channel_id,
             counterparty_version,
-        } => sudo_open_ack(
-            &IbcForwarderIcaStateHelper,
-            deps,
-            env,
-            port_id,
-            channel_id,
-            counterparty_channel_id,
-            counterparty_version,
-        ),
-        _ => Ok(Response::default()),
+        } => {
+            let response = sudo_open_ack(
+                &IbcForwarderIcaHooks,
+                deps.branch(),
+                port_id,
+                channel_id.to_string(),
+                counterparty_channel_id,
+                counterparty_version,
+            )?;
+            let (address, _) = get_ica(deps.storage)?;
+            notify_milestone(
+                deps.storage,
+                response,
+                ForwarderMilestone::IcaRegistered {
+                    address,
+                    channel: channel_id,
+                },
+            )
+        }
+        _ => handle_sudo(&IbcForwarderIcaHooks, deps, msg),
+    }
+}
+
+/// records the first acknowledged transfer of funds out of the ICA
+fn try_record_first_forward(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+    response: Response<NeutronMsg>,
+) -> StdResult<Response<NeutronMsg>> {
+    if FIRST_FORWARD.exists(deps.storage) {
+        return Ok(response);
+    }
+    let (Some(channel_id), Some(seq_id)) = (request.source_channel, request.sequence) else {
+        return Ok(response);
+    };
+    let Some(payload) = read_sudo_payload(deps.storage, channel_id, seq_id)? else {
+        return Ok(response);
+    };
+    if payload.message != "try_forward_funds" {
+        return Ok(response);
+    }
+
+    let amount = TRANSFER_AMOUNT.load(deps.storage)?;
+    FIRST_FORWARD.save(deps.storage, &amount)?;
+
+    notify_milestone(
+        deps.storage,
+        response,
+        ForwarderMilestone::FirstForward { amount },
+    )
+}
+
+/// emits the milestone event and notifies the milestone callback, if any.
+/// a failing callback does not revert the milestone.
+fn notify_milestone(
+    storage: &dyn Storage,
+    response: Response<NeutronMsg>,
+    milestone: ForwarderMilestone,
+) -> StdResult<Response<NeutronMsg>> {
+    let response = response.add_event(milestone.to_event());
+
+    match MILESTONE_CALLBACK.may_load(storage)? {
+        Some(callback) => Ok(response.add_submessage(SubMsg::reply_on_error(
+            generate_milestone_callback_msg(callback.to_string(), milestone)?,
+            MILESTONE_CALLBACK_REPLY_ID,
+        ))),
+        None => Ok(response),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: ExecuteDeps, env: Env, msg: Reply) -> StdResult<Response<NeutronMsg>> {
+pub fn reply(deps: ExecuteDeps, _env: Env, msg: Reply) -> StdResult<Response<NeutronMsg>> {
     match msg.id {
-        SUDO_PAYLOAD_REPLY_ID => prepare_sudo_payload(&IbcForwarderIcaStateHelper, deps, env, msg),
+        SUDO_PAYLOAD_REPLY_ID => prepare_sudo_payload(deps, msg),
+        MILESTONE_CALLBACK_REPLY_ID => Ok(Response::default()
+            .add_attribute("method", "milestone_callback_failed")
+            .add_attribute("error", msg.result.into_result().err().unwrap_or_default())),
         _ => Err(StdError::generic_err(format!(
             "unsupported reply message id {}",
             msg.id
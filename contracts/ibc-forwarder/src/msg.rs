@@ -1,13 +1,15 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{to_json_binary, Attribute, Binary, Coin, StdResult, Uint128, Uint64, WasmMsg};
 use covenant_macros::{
-    clocked, covenant_deposit_address, covenant_ica_address, covenant_remote_chain,
+    clocked, covenant_decommission, covenant_deposit_address, covenant_ica_address,
+    covenant_remote_chain,
 };
 use covenant_utils::{
     instantiate2_helper::Instantiate2HelperConfig,
     neutron::RemoteChainInfo,
     op_mode::{ContractOperationMode, ContractOperationModeConfig},
 };
+use neutron_sdk::bindings::msg::IbcFee;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -40,6 +42,13 @@ pub struct InstantiateMsg {
     pub ica_timeout: Uint64,
     // fallback address on the remote chain
     pub fallback_address: Option<String>,
+    /// contract to notify as the forwarder reaches its milestones
+    /// (ICA registration and first forward)
+    #[serde(default)]
+    pub milestone_callback: Option<String>,
+    /// ibc fee to pay instead of the chain minimum, where it exceeds it
+    #[serde(default)]
+    pub ibc_fee_override: Option<IbcFee>,
 }
 
 impl InstantiateMsg {
@@ -77,11 +86,17 @@ impl InstantiateMsg {
             ),
             Attribute::new("ica_timeout", self.ica_timeout.to_string()),
             Attribute::new("fallback_address", format!("{:?}", self.fallback_address)),
+            Attribute::new(
+                "milestone_callback",
+                format!("{:?}", self.milestone_callback),
+            ),
+            Attribute::new("ibc_fee_override", format!("{:?}", self.ibc_fee_override)),
         ]
     }
 }
 
 #[clocked]
+#[covenant_decommission]
 #[cw_serde]
 pub enum ExecuteMsg {
     DistributeFallback { coins: Vec<Coin> },
@@ -119,6 +134,8 @@ pub enum QueryMsg {
     FallbackAddress {},
     #[returns(ContractOperationMode)]
     OperationMode {},
+    #[returns(Option<IbcFee>)]
+    IbcFeeOverride {},
 }
 
 #[cw_serde]
@@ -127,4 +144,6 @@ pub enum ContractState {
     Instantiated,
     /// ICA was created, funds are ready to be forwarded
     IcaCreated,
+    /// covenant completed and the forwarder was wound down
+    Decommissioned,
 }
@@ -1,10 +1,8 @@
-use cosmwasm_std::{from_json, to_json_vec, Addr, Binary, StdError, StdResult, Storage, Uint128};
-use covenant_utils::{
-    ica::IcaStateHelper,
-    neutron::{RemoteChainInfo, SudoPayload},
-    op_mode::ContractOperationMode,
-};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use covenant_ica::state::IcaStateHooks;
+use covenant_utils::{neutron::RemoteChainInfo, op_mode::ContractOperationMode};
+use cw_storage_plus::Item;
+use neutron_sdk::bindings::msg::IbcFee;
 
 use crate::msg::ContractState;
 
@@ -20,66 +18,32 @@ pub const NEXT_CONTRACT: Item<Addr> = Item::new("next_contract");
 /// information needed for an ibc transfer to the remote chain
 pub const REMOTE_CHAIN_INFO: Item<RemoteChainInfo> = Item::new("r_c_info");
 
-/// interchain accounts storage in form of (port_id) -> (address, controller_connection_id)
-pub const INTERCHAIN_ACCOUNTS: Map<String, Option<(String, String)>> =
-    Map::new("interchain_accounts");
-
-pub const REPLY_ID_STORAGE: Item<Vec<u8>> = Item::new("reply_queue_id");
-pub const SUDO_PAYLOAD: Map<(String, u64), Vec<u8>> = Map::new("sudo_payload");
 pub const FALLBACK_ADDRESS: Item<String> = Item::new("fallback_address");
 
-pub(crate) struct IbcForwarderIcaStateHelper;
+/// contract notified of the forwarder milestones
+pub const MILESTONE_CALLBACK: Item<Addr> = Item::new("milestone_callback");
 
-impl IcaStateHelper for IbcForwarderIcaStateHelper {
-    fn reset_state(&self, storage: &mut dyn Storage) -> StdResult<()> {
-        CONTRACT_STATE.save(storage, &ContractState::Instantiated)?;
-        Ok(())
-    }
+/// ibc fee paid instead of the chain minimum, where it exceeds it
+pub const IBC_FEE_OVERRIDE: Item<IbcFee> = Item::new("ibc_fee_override");
 
-    fn clear_ica(&self, storage: &mut dyn Storage) -> StdResult<()> {
-        INTERCHAIN_ACCOUNTS.clear(storage);
-        Ok(())
-    }
+/// amount forwarded by the first acknowledged transfer
+pub const FIRST_FORWARD: Item<Uint128> = Item::new("first_forward");
 
-    fn save_ica(
-        &self,
-        storage: &mut dyn Storage,
-        port_id: String,
-        address: String,
-        controller_connection_id: String,
-    ) -> StdResult<()> {
-        INTERCHAIN_ACCOUNTS.save(storage, port_id, &Some((address, controller_connection_id)))?;
-        Ok(())
-    }
+pub(crate) struct IbcForwarderIcaHooks;
 
-    fn save_state_ica_created(&self, storage: &mut dyn Storage) -> StdResult<()> {
-        CONTRACT_STATE.save(storage, &ContractState::IcaCreated)?;
+impl IcaStateHooks for IbcForwarderIcaHooks {
+    fn on_active(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        if CONTRACT_STATE.load(storage)? != ContractState::Decommissioned {
+            CONTRACT_STATE.save(storage, &ContractState::IcaCreated)?;
+        }
         Ok(())
     }
 
-    fn save_reply_payload(&self, storage: &mut dyn Storage, payload: SudoPayload) -> StdResult<()> {
-        REPLY_ID_STORAGE.save(storage, &to_json_vec(&payload)?)?;
+    fn on_closed(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        // decommissioned forwarders do not restart the flow
+        if CONTRACT_STATE.load(storage)? != ContractState::Decommissioned {
+            CONTRACT_STATE.save(storage, &ContractState::Instantiated)?;
+        }
         Ok(())
     }
-
-    fn read_reply_payload(&self, storage: &mut dyn Storage) -> StdResult<SudoPayload> {
-        let data = REPLY_ID_STORAGE.load(storage)?;
-        from_json(Binary(data))
-    }
-
-    fn save_sudo_payload(
-        &self,
-        storage: &mut dyn Storage,
-        channel_id: String,
-        seq_id: u64,
-        payload: SudoPayload,
-    ) -> StdResult<()> {
-        SUDO_PAYLOAD.save(storage, (channel_id, seq_id), &to_json_vec(&payload)?)
-    }
-
-    fn get_ica(&self, storage: &dyn Storage, key: String) -> StdResult<(String, String)> {
-        INTERCHAIN_ACCOUNTS
-            .load(storage, key)?
-            .ok_or_else(|| StdError::generic_err("Interchain account is not created yet"))
-    }
 }
@@ -1,26 +1,49 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, Uint128,
+    ensure, from_json, to_json_binary, to_json_string, Addr, Api, Attribute, Binary, Coin,
+    CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, Uint128,
 };
+use covenant_utils::mock_ibc::get_contract_version;
 use covenant_utils::{
-    clock::{enqueue_msg, verify_clock},
-    neutron::{assert_ibc_fee_coverage, query_ibc_fee},
-    soft_validate_remote_chain_addr,
+    clock::{enqueue_msg, increment_tick_nonce, verify_clock},
+    distribution_receipt::{
+        generate_distribution_failure_msg, mark_distributions_routed, query_pending_distributions,
+        register_distribution, take_distribution_failures,
+        take_routed_distribution_acknowledgements, verify_distribution_holder,
+        ACKNOWLEDGE_DISTRIBUTION_REPLY_ID,
+    },
+    neutron::{
+        assert_ibc_fee_coverage, query_ibc_fee_with_override, validate_ibc_fee_override,
+        MinIbcFeeConfig,
+    },
+    router_dust::{
+        get_dust_attribute, get_min_route_amount_attributes, query_dust_balances,
+        split_dust_balances, MIN_ROUTE_AMOUNTS,
+    },
+    router_halt::{query_holder_complete, RouterHaltConfig, RouterHaltError, ROUTER_HALTED},
+    soft_validate_remote_chain_addr, DestinationConfig, PacketForwardMiddlewareConfig,
 };
 use cw2::set_contract_version;
 use neutron_sdk::{
-    bindings::{msg::NeutronMsg, query::NeutronQuery},
-    query::min_ibc_fee::MinIbcFeeResponse,
+    bindings::{
+        msg::{MsgIbcTransferResponse, NeutronMsg},
+        query::NeutronQuery,
+    },
+    sudo::msg::{RequestPacket, SudoMsg},
     NeutronError, NeutronResult,
 };
 
-use crate::state::{DESTINATION_CONFIG, TARGET_DENOMS};
+use crate::state::{
+    DENOM_DESTINATIONS, DESTINATION_CONFIG, HALT_CONFIG, HOLDER_ADDRESS, IBC_FEE_OVERRIDE,
+    IN_FLIGHT_TRANSFERS, PENDING_TRANSFERS, ROUTING_STATS, TARGET_DENOMS, TIMED_OUT_TRANSFERS,
+};
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    error::ContractError,
+    msg::{ExecuteMsg, InFlightTransfer, InstantiateMsg, MigrateMsg, QueryMsg, RoutingStats},
     state::CLOCK_ADDRESS,
 };
 
@@ -29,6 +52,7 @@ type QueryDeps<'a> = Deps<'a, NeutronQuery>;
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const IBC_TRANSFER_REPLY_ID: u64 = 2;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -37,20 +61,53 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_contract_version(
+        deps.storage,
+        CONTRACT_NAME,
+        get_contract_version(CONTRACT_VERSION),
+    )?;
 
     let clock_address = deps.api.addr_validate(&msg.clock_address)?;
-    soft_validate_remote_chain_addr(deps.api, &msg.destination_config.destination_receiver_addr)?;
+    validate_destination_config(deps.api, &msg.destination_config)?;
 
     CLOCK_ADDRESS.save(deps.storage, &clock_address)?;
     DESTINATION_CONFIG.save(deps.storage, &msg.destination_config)?;
     TARGET_DENOMS.save(deps.storage, &msg.denoms)?;
+    MIN_ROUTE_AMOUNTS.save(deps.storage, &msg.min_route_amounts)?;
 
-    Ok(Response::default()
+    let mut response = Response::default()
         .add_message(enqueue_msg(msg.clock_address.as_str())?)
         .add_attribute("method", "interchain_router_instantiate")
         .add_attribute("clock_address", clock_address.to_string())
-        .add_attributes(msg.destination_config.get_response_attributes()))
+        .add_attributes(msg.destination_config.get_response_attributes())
+        .add_attributes(get_min_route_amount_attributes(&msg.min_route_amounts));
+    response = save_denom_destinations(
+        deps.storage,
+        deps.api,
+        msg.denom_to_destination_map,
+        response,
+    )?;
+
+    if let Some(halt_config) = msg.halt_config {
+        halt_config.validate(deps.api)?;
+        HALT_CONFIG.save(deps.storage, &halt_config)?;
+        response = response.add_attributes(halt_config.get_response_attributes());
+    }
+
+    if let Some(addr) = msg.holder_address {
+        HOLDER_ADDRESS.save(deps.storage, &deps.api.addr_validate(&addr)?)?;
+        response = response.add_attribute("holder_address", addr);
+    }
+
+    if let Some(ibc_fee) = msg.ibc_fee_override {
+        validate_ibc_fee_override(&ibc_fee)?;
+        IBC_FEE_OVERRIDE.save(deps.storage, &ibc_fee)?;
+        response = response.add_attribute("ibc_fee_override", format!("{ibc_fee:?}"));
+    }
+
+    let ownership = cw_ownable::initialize_owner(deps.storage, deps.api, msg.owner.as_deref())?;
+
+    Ok(response.add_attributes(ownership.into_attributes()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -62,13 +119,246 @@ pub fn execute(
 ) -> NeutronResult<Response<NeutronMsg>> {
     match msg {
         ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
             // Verify caller is the clock
             verify_clock(&info.sender, &CLOCK_ADDRESS.load(deps.storage)?)?;
-            try_route_balances(deps, env)
+            let response = match HALT_CONFIG.may_load(deps.storage)? {
+                // a halted router keeps returning whatever it receives to the holder
+                Some(halt_config) if is_halted(deps.storage)? => {
+                    let response =
+                        Response::default().add_attribute("method", "try_return_balances");
+                    return_balances(deps, env, halt_config, response)
+                }
+                _ => try_route_balances(deps, env),
+            }?;
+            Ok(response.add_attribute("tick_nonce", tick_nonce.to_string()))
         }
         ExecuteMsg::DistributeFallback { denoms } => {
+            ensure!(!is_halted(deps.storage)?, RouterHaltError::Halted);
             try_distribute_fallback(deps, env, info, denoms)
         }
+        ExecuteMsg::RegisterDistribution { id, coins } => {
+            try_register_distribution(deps, info, id, coins)
+        }
+        ExecuteMsg::Halt {} => try_halt(deps, env, info),
+        ExecuteMsg::Sweep { denoms } => {
+            ensure!(!is_halted(deps.storage)?, RouterHaltError::Halted);
+            try_sweep(deps, env, info, denoms)
+        }
+        ExecuteMsg::UpdatePfmConfig { denom, pfm_config } => {
+            cw_ownable::assert_owner(deps.storage, &info.sender)
+                .map_err(|e| ContractError::OwnershipError(e).to_neutron_std())?;
+            try_update_pfm_config(deps, denom, pfm_config)
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            let ownership =
+                cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)
+                    .map_err(|e| ContractError::OwnershipError(e).to_neutron_std())?;
+            Ok(Response::default()
+                .add_attribute("method", "update_ownership")
+                .add_attributes(ownership.into_attributes()))
+        }
+    }
+}
+
+/// replaces the pfm config of the denom, e.g. after a chain on the
+/// unwinding path migrated its channels
+fn try_update_pfm_config(
+    deps: ExecuteDeps,
+    denom: String,
+    pfm_config: Option<PacketForwardMiddlewareConfig>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let mut destination_config = DESTINATION_CONFIG.load(deps.storage)?;
+    let response = Response::default()
+        .add_attribute("method", "try_update_pfm_config")
+        .add_attribute("denom", denom.to_string());
+
+    let response = match pfm_config {
+        Some(pfm_config) => {
+            let response = response
+                .add_attribute(
+                    "local_to_hop_chain_channel_id",
+                    pfm_config.local_to_hop_chain_channel_id.to_string(),
+                )
+                .add_attribute(
+                    "hop_to_destination_chain_channel_id",
+                    pfm_config.hop_to_destination_chain_channel_id.to_string(),
+                )
+                .add_attribute(
+                    "hop_chain_receiver_address",
+                    pfm_config.hop_chain_receiver_address.to_string(),
+                )
+                .add_attribute("next_hops", pfm_config.next_hops.len().to_string());
+            destination_config
+                .denom_to_pfm_map
+                .insert(denom, pfm_config);
+            response
+        }
+        None => {
+            destination_config.denom_to_pfm_map.remove(&denom);
+            response.add_attribute("pfm_config", "removed")
+        }
+    };
+    DESTINATION_CONFIG.save(deps.storage, &destination_config)?;
+
+    Ok(response)
+}
+
+fn validate_destination_config(
+    api: &dyn Api,
+    destination_config: &DestinationConfig,
+) -> StdResult<()> {
+    soft_validate_remote_chain_addr(api, &destination_config.destination_receiver_addr)?;
+    destination_config.validate_memo_template()?;
+    destination_config.validate_wasm_hook()
+}
+
+/// validates and stores the destination overrides, replacing the existing ones
+fn save_denom_destinations(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    denom_to_destination_map: BTreeMap<String, DestinationConfig>,
+    mut response: Response<NeutronMsg>,
+) -> StdResult<Response<NeutronMsg>> {
+    DENOM_DESTINATIONS.clear(storage);
+    for (denom, destination_config) in denom_to_destination_map {
+        validate_destination_config(api, &destination_config)?;
+        DENOM_DESTINATIONS.save(storage, denom.to_string(), &destination_config)?;
+        response = response.add_attribute(
+            format!("{denom}_destination_receiver_addr"),
+            destination_config.destination_receiver_addr,
+        );
+    }
+    Ok(response)
+}
+
+/// builds the ibc transfer messages of the coins, sending each of them
+/// to its denom destination if one is set, or the default one otherwise
+fn get_ibc_transfer_messages(
+    storage: &dyn Storage,
+    env: &Env,
+    coins: Vec<Coin>,
+    min_ibc_fee: &MinIbcFeeConfig,
+) -> StdResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let mut coins_by_destination: BTreeMap<Option<String>, Vec<Coin>> = BTreeMap::new();
+    for coin in coins {
+        let destination = match DENOM_DESTINATIONS.has(storage, coin.denom.to_string()) {
+            true => Some(coin.denom.to_string()),
+            false => None,
+        };
+        coins_by_destination
+            .entry(destination)
+            .or_default()
+            .push(coin);
+    }
+
+    let mut messages = vec![];
+    for (destination, coins) in coins_by_destination {
+        let destination_config = match destination {
+            Some(denom) => DENOM_DESTINATIONS.load(storage, denom)?,
+            None => DESTINATION_CONFIG.load(storage)?,
+        };
+        messages.extend(destination_config.get_ibc_transfer_messages_for_coins(
+            coins,
+            env.block.time,
+            env.contract.address.to_string(),
+            min_ibc_fee,
+        )?);
+    }
+    Ok(messages)
+}
+
+fn is_halted(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(ROUTER_HALTED.may_load(storage)?.unwrap_or_default())
+}
+
+/// halts the router, stopping any further routing
+fn try_halt(deps: ExecuteDeps, env: Env, info: MessageInfo) -> NeutronResult<Response<NeutronMsg>> {
+    let halt_config = HALT_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(RouterHaltError::NotConfigured)?;
+    halt_config.verify_halt_authority(&deps.querier, &env, &info.sender)?;
+
+    ROUTER_HALTED.save(deps.storage, &true)?;
+
+    let response = Response::default()
+        .add_attribute("method", "try_halt")
+        .add_attribute("halted_by", info.sender);
+    return_balances(deps, env, halt_config, response)
+}
+
+/// returns all target denom balances to the holder and
+/// reports any pending distributions as failed
+fn return_balances(
+    deps: ExecuteDeps,
+    env: Env,
+    halt_config: RouterHaltConfig,
+    response: Response<NeutronMsg>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let mut balances = vec![];
+    for denom in TARGET_DENOMS.load(deps.storage)? {
+        balances.push(
+            deps.querier
+                .query_balance(env.contract.address.to_string(), denom)?,
+        );
+    }
+
+    let failures: Vec<SubMsg<NeutronMsg>> =
+        take_distribution_failures(deps.storage, "router halted")?
+            .into_iter()
+            .map(|msg| SubMsg::reply_on_error(msg, ACKNOWLEDGE_DISTRIBUTION_REPLY_ID))
+            .collect();
+
+    Ok(response
+        .add_attribute("holder", halt_config.holder_address.to_string())
+        .add_messages(halt_config.get_return_msgs(balances))
+        .add_submessages(failures))
+}
+
+/// returns the holder distributing to the router, falling
+/// back to the holder of the halt config if none is set
+fn load_holder_address(storage: &dyn Storage) -> StdResult<Option<Addr>> {
+    match HOLDER_ADDRESS.may_load(storage)? {
+        Some(addr) => Ok(Some(addr)),
+        None => Ok(HALT_CONFIG
+            .may_load(storage)?
+            .map(|config| Addr::unchecked(config.holder_address))),
+    }
+}
+
+fn try_register_distribution(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    id: u64,
+    coins: Vec<Coin>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    // every registration costs an acknowledgement later on,
+    // so only the holder can register distributions
+    verify_distribution_holder(load_holder_address(deps.storage)?, &info.sender)?;
+
+    let target_denoms = TARGET_DENOMS.load(deps.storage)?;
+    let response = Response::default()
+        .add_attribute("method", "try_register_distribution")
+        .add_attribute("holder", info.sender.to_string())
+        .add_attribute("id", id.to_string());
+
+    // distributions received after halting are returned along with the
+    // remaining balances, so they can not be delivered
+    if is_halted(deps.storage)? {
+        return Ok(response.add_attribute("status", "failed").add_message(
+            generate_distribution_failure_msg(
+                info.sender.to_string(),
+                id,
+                "router halted".to_string(),
+            )?,
+        ));
+    }
+
+    match register_distribution(deps.storage, info.sender, id, coins, &target_denoms)? {
+        Some(failure_msg) => Ok(response
+            .add_attribute("status", "failed")
+            .add_message(failure_msg)),
+        None => Ok(response.add_attribute("status", "pending")),
     }
 }
 
@@ -79,9 +369,9 @@ fn try_distribute_fallback(
     denoms: Vec<String>,
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut available_balances = Vec::with_capacity(denoms.len());
-    let destination_config = DESTINATION_CONFIG.load(deps.storage)?;
     let explicit_denoms = TARGET_DENOMS.load(deps.storage)?;
-    let min_ibc_fee_config = query_ibc_fee(deps.querier)?;
+    let min_ibc_fee_config =
+        query_ibc_fee_with_override(deps.querier, IBC_FEE_OVERRIDE.may_load(deps.storage)?)?;
 
     assert_ibc_fee_coverage(
         info,
@@ -103,24 +393,87 @@ fn try_distribute_fallback(
         available_balances.push(queried_coin);
     }
 
-    let fallback_distribution_messages = destination_config.get_ibc_transfer_messages_for_coins(
-        available_balances,
-        env.block.time,
-        env.contract.address.to_string(),
-        min_ibc_fee_config.ibc_fee,
-    )?;
+    let fallback_distribution_messages =
+        get_ibc_transfer_messages(deps.storage, &env, available_balances, &min_ibc_fee_config)?;
 
     Ok(Response::default()
         .add_attribute("method", "try_distribute_fallback")
-        .add_messages(fallback_distribution_messages))
+        .add_submessages(track_transfers(
+            deps.storage,
+            fallback_distribution_messages,
+        )?))
+}
+
+/// routes whatever is left on the router after the covenant completed, as it
+/// is no longer being ticked by then. like fallback distributions, the caller
+/// covers the ibc fees of the sweep.
+fn try_sweep(
+    deps: ExecuteDeps,
+    env: Env,
+    info: MessageInfo,
+    denoms: Vec<String>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let holder = load_holder_address(deps.storage)?.ok_or(RouterHaltError::HolderNotConfigured)?;
+    ensure!(
+        query_holder_complete(&deps.querier, &holder)?,
+        RouterHaltError::HolderNotComplete
+    );
+
+    let min_ibc_fee_config =
+        query_ibc_fee_with_override(deps.querier, IBC_FEE_OVERRIDE.may_load(deps.storage)?)?;
+    assert_ibc_fee_coverage(
+        info,
+        min_ibc_fee_config.total_ntrn_fee,
+        Uint128::from(denoms.len() as u128),
+    )?;
+
+    let mut balances = Vec::with_capacity(denoms.len());
+    for denom in denoms {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), denom)?;
+        if !balance.amount.is_zero() {
+            balances.push(balance);
+        }
+    }
+
+    let swept_attribute = to_json_string(&balances)?;
+    let messages = get_ibc_transfer_messages(deps.storage, &env, balances, &min_ibc_fee_config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_sweep")
+        .add_attribute("swept", swept_attribute)
+        .add_submessages(track_transfers(deps.storage, messages)?))
 }
 
-/// method that attempts to transfer out all available balances to the receiver
+/// method that attempts to transfer out all available balances to the receiver.
+/// pending distributions get acknowledged once their transfers are acknowledged.
 fn try_route_balances(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<NeutronMsg>> {
-    let destination_config = DESTINATION_CONFIG.load(deps.storage)?;
     let denoms_to_route = TARGET_DENOMS.load(deps.storage)?;
     let mut denom_balances = Vec::with_capacity(denoms_to_route.len());
 
+    // timed out transfers got refunded. target denoms are routed along
+    // with the rest of their balance, while other denoms are retried here.
+    let mut retried_balances = vec![];
+    let timed_out_transfers: Vec<(String, Uint128)> = TIMED_OUT_TRANSFERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (denom, amount) in timed_out_transfers {
+        TIMED_OUT_TRANSFERS.remove(deps.storage, denom.to_string());
+        if denoms_to_route.contains(&denom) {
+            continue;
+        }
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), denom)?;
+        if !balance.amount.is_zero() {
+            retried_balances.push(Coin {
+                amount: balance.amount.min(amount),
+                denom: balance.denom,
+            });
+        }
+    }
+
     for denom in denoms_to_route {
         let coin_to_route = deps
             .querier
@@ -130,14 +483,24 @@ fn try_route_balances(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Neu
         }
     }
 
+    // balances below their minimum route amount are not worth the ibc fees
+    let min_route_amounts = MIN_ROUTE_AMOUNTS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let (mut denom_balances, dust_balances) =
+        split_dust_balances(denom_balances, &min_route_amounts);
+    let dust_attribute = get_dust_attribute(&dust_balances)?;
+
     // if there are no balances, we return early;
     // otherwise build up the response attributes
     let balance_attributes: Vec<Attribute> = match denom_balances.len() {
-        0 => {
+        0 if retried_balances.is_empty() => {
             return Ok(Response::default()
                 .add_attribute("method", "try_route_balances")
-                .add_attribute("balances", "[]"))
+                .add_attribute("balances", "[]")
+                .add_attributes(dust_attribute))
         }
+        0 => vec![],
         1 => vec![Attribute::new(
             denom_balances[0].denom.to_string(),
             denom_balances[0].amount,
@@ -148,30 +511,313 @@ fn try_route_balances(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Neu
             .collect(),
     };
 
-    let min_ibc_fee: MinIbcFeeResponse = deps.querier.query(&NeutronQuery::MinIbcFee {}.into())?;
+    let min_ibc_fee_config =
+        query_ibc_fee_with_override(deps.querier, IBC_FEE_OVERRIDE.may_load(deps.storage)?)?;
+
+    // every routed balance is transferred out in full, carrying
+    // the funds of the pending distributions along with it
+    record_transfers(deps.storage, &denom_balances)?;
+    if !denom_balances.is_empty() {
+        mark_distributions_routed(deps.storage)?;
+    }
+
+    let retried_attributes: Vec<Attribute> = retried_balances
+        .iter()
+        .map(|c| Attribute::new("retried", c.to_string()))
+        .collect();
+    denom_balances.extend(retried_balances);
 
     // get transfer messages for each denom
-    let messages = destination_config.get_ibc_transfer_messages_for_coins(
-        denom_balances,
-        env.block.time,
-        env.contract.address.to_string(),
-        min_ibc_fee.min_fee,
-    )?;
+    let messages =
+        get_ibc_transfer_messages(deps.storage, &env, denom_balances, &min_ibc_fee_config)?;
 
     Ok(Response::default()
         .add_attribute("method", "try_route_balances")
         .add_attributes(balance_attributes)
-        .add_messages(messages))
+        .add_attributes(retried_attributes)
+        .add_attributes(dust_attribute)
+        .add_submessages(track_transfers(deps.storage, messages)?))
+}
+
+/// submits the ibc transfers with a reply, so that they can be tracked
+/// by their sequence until acknowledged. other messages are sent as is.
+fn track_transfers(
+    storage: &mut dyn Storage,
+    messages: Vec<CosmosMsg<NeutronMsg>>,
+) -> StdResult<Vec<SubMsg<NeutronMsg>>> {
+    let mut pending_transfers = PENDING_TRANSFERS.may_load(storage)?.unwrap_or_default();
+    let mut submessages = Vec::with_capacity(messages.len());
+
+    for msg in messages {
+        let token = match &msg {
+            CosmosMsg::Custom(NeutronMsg::IbcTransfer { token, .. }) => Some(token.clone()),
+            _ => None,
+        };
+        match token {
+            Some(token) => {
+                pending_transfers.push(token);
+                submessages.push(SubMsg::reply_on_success(msg, IBC_TRANSFER_REPLY_ID));
+            }
+            None => submessages.push(SubMsg::new(msg)),
+        }
+    }
+
+    PENDING_TRANSFERS.save(storage, &pending_transfers)?;
+    Ok(submessages)
+}
+
+/// records the coins as received and transferred out by the router
+fn record_transfers(storage: &mut dyn Storage, coins: &[Coin]) -> StdResult<()> {
+    for coin in coins {
+        ROUTING_STATS.update(storage, coin.denom.to_string(), |stats| -> StdResult<_> {
+            let mut stats = stats.unwrap_or_default();
+            stats.received = stats.received.checked_add(coin.amount)?;
+            stats.transferred = stats.transferred.checked_add(coin.amount)?;
+            Ok(stats)
+        })?;
+    }
+    Ok(())
+}
+
+/// returns the routing stats of every target denom along with any
+/// denoms routed in the past. balances currently held by the router
+/// are counted as received, but not yet transferred.
+fn query_routing_stats(deps: QueryDeps, env: Env) -> StdResult<BTreeMap<String, RoutingStats>> {
+    let mut routing_stats: BTreeMap<String, RoutingStats> = ROUTING_STATS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    for denom in TARGET_DENOMS.load(deps.storage)? {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), &denom)?;
+        let stats = routing_stats.entry(denom).or_default();
+        stats.received = stats.received.checked_add(balance.amount)?;
+    }
+
+    Ok(routing_stats)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: ExecuteDeps, _env: Env, msg: Reply) -> NeutronResult<Response<NeutronMsg>> {
+    match msg.id {
+        IBC_TRANSFER_REPLY_ID => Ok(handle_ibc_transfer_reply(deps, msg)?),
+        // failed acknowledgements are not retried
+        ACKNOWLEDGE_DISTRIBUTION_REPLY_ID => Ok(Response::default()
+            .add_attribute("method", "reply")
+            .add_attribute("acknowledgement", "failed")),
+        _ => Err(NeutronError::Std(StdError::generic_err(format!(
+            "unknown reply id: {}",
+            msg.id
+        )))),
+    }
+}
+
+/// records the submitted transfer as in flight under its sequence.
+/// submessages reply in order, so the earliest pending transfer is the one.
+fn handle_ibc_transfer_reply(deps: ExecuteDeps, msg: Reply) -> StdResult<Response<NeutronMsg>> {
+    let data = msg
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data
+        .ok_or_else(|| StdError::generic_err("ibc transfer response is missing"))?;
+    let transfer_response: MsgIbcTransferResponse = from_json(data)?;
+
+    let mut pending_transfers = PENDING_TRANSFERS.load(deps.storage)?;
+    ensure!(
+        !pending_transfers.is_empty(),
+        StdError::generic_err("no pending ibc transfer")
+    );
+    let coin = pending_transfers.remove(0);
+    PENDING_TRANSFERS.save(deps.storage, &pending_transfers)?;
+
+    IN_FLIGHT_TRANSFERS.save(
+        deps.storage,
+        (
+            transfer_response.channel.to_string(),
+            transfer_response.sequence,
+        ),
+        &coin,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "handle_ibc_transfer_reply")
+        .add_attribute("channel_id", transfer_response.channel)
+        .add_attribute("sequence", transfer_response.sequence.to_string())
+        .add_attribute("coin", coin.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: QueryDeps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn sudo(deps: ExecuteDeps, _env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
+    match msg {
+        SudoMsg::Response { request, .. } => sudo_transfer_acknowledged(deps, request),
+        SudoMsg::Error { request, details } => sudo_transfer_failed(deps, request, details),
+        SudoMsg::Timeout { request } => sudo_transfer_timed_out(deps, request),
+        _ => Ok(Response::default().add_attribute("method", "sudo")),
+    }
+}
+
+/// removes the transfer of the packet from the in flight transfers
+fn take_in_flight_transfer(
+    storage: &mut dyn Storage,
+    request: RequestPacket,
+) -> StdResult<Option<Coin>> {
+    let (Some(channel_id), Some(sequence)) = (request.source_channel, request.sequence) else {
+        return Ok(None);
+    };
+    let key = (channel_id, sequence);
+    let coin = IN_FLIGHT_TRANSFERS.may_load(storage, key.clone())?;
+    IN_FLIGHT_TRANSFERS.remove(storage, key);
+    Ok(coin)
+}
+
+fn sudo_transfer_acknowledged(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+) -> StdResult<Response<NeutronMsg>> {
+    let response = Response::default().add_attribute("method", "sudo_response");
+    let Some(coin) = take_in_flight_transfer(deps.storage, request)? else {
+        return Ok(response);
+    };
+
+    // only the routed denoms are tracked
+    if let Some(mut stats) = ROUTING_STATS.may_load(deps.storage, coin.denom.to_string())? {
+        stats.acknowledged = stats.acknowledged.checked_add(coin.amount)?;
+        ROUTING_STATS.save(deps.storage, coin.denom.to_string(), &stats)?;
+    }
+
+    Ok(response
+        .add_attribute("acknowledged", coin.to_string())
+        .add_submessages(take_delivered_acknowledgements(deps.storage)?))
+}
+
+/// once no transfers are left pending, in flight, or timed out, the funds of
+/// the routed distributions were delivered and they can be acknowledged
+fn take_delivered_acknowledgements(
+    storage: &mut dyn Storage,
+) -> StdResult<Vec<SubMsg<NeutronMsg>>> {
+    let transfers_outstanding = !PENDING_TRANSFERS
+        .may_load(storage)?
+        .unwrap_or_default()
+        .is_empty()
+        || IN_FLIGHT_TRANSFERS
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+        || TIMED_OUT_TRANSFERS
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+    if transfers_outstanding {
+        return Ok(vec![]);
+    }
+
+    Ok(take_routed_distribution_acknowledgements(storage)?
+        .into_iter()
+        .map(|msg| SubMsg::reply_on_error(msg, ACKNOWLEDGE_DISTRIBUTION_REPLY_ID))
+        .collect())
+}
+
+/// failed transfers are refunded. target denoms get routed again on the
+/// next tick, while the rest remain available for fallback distribution.
+fn sudo_transfer_failed(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+    details: String,
+) -> StdResult<Response<NeutronMsg>> {
+    let response = Response::default()
+        .add_attribute("method", "sudo_error")
+        .add_attribute("error", details);
+    let Some(coin) = take_in_flight_transfer(deps.storage, request)? else {
+        return Ok(response);
+    };
+
+    // refunded target denoms still carry the funds of the routed distributions
+    let acknowledgements = if TARGET_DENOMS.load(deps.storage)?.contains(&coin.denom) {
+        vec![]
+    } else {
+        take_delivered_acknowledgements(deps.storage)?
+    };
+
+    Ok(response
+        .add_attribute("failed", coin.to_string())
+        .add_submessages(acknowledgements))
+}
+
+/// timed out transfers are refunded and requeued to be retried on the next tick
+fn sudo_transfer_timed_out(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+) -> StdResult<Response<NeutronMsg>> {
+    let response = Response::default().add_attribute("method", "sudo_timeout");
+    let Some(coin) = take_in_flight_transfer(deps.storage, request)? else {
+        return Ok(response);
+    };
+
+    TIMED_OUT_TRANSFERS.update(
+        deps.storage,
+        coin.denom.to_string(),
+        |amount| -> StdResult<_> { Ok(amount.unwrap_or_default().checked_add(coin.amount)?) },
+    )?;
+
+    Ok(response.add_attribute("timed_out", coin.to_string()))
+}
+
+fn query_in_flight_transfers(deps: QueryDeps) -> StdResult<Vec<InFlightTransfer>> {
+    IN_FLIGHT_TRANSFERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            let ((channel_id, sequence), coin) = entry?;
+            Ok(InFlightTransfer {
+                channel_id,
+                sequence,
+                coin,
+            })
+        })
+        .collect()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::ReceiverConfig {} => {
             Ok(to_json_binary(&DESTINATION_CONFIG.may_load(deps.storage)?)?)
         }
         QueryMsg::ClockAddress {} => Ok(to_json_binary(&CLOCK_ADDRESS.may_load(deps.storage)?)?),
         QueryMsg::TargetDenoms {} => Ok(to_json_binary(&TARGET_DENOMS.may_load(deps.storage)?)?),
+        QueryMsg::PendingDistributions {} => {
+            Ok(to_json_binary(&query_pending_distributions(deps.storage)?)?)
+        }
+        QueryMsg::RoutingStats {} => Ok(to_json_binary(&query_routing_stats(deps, env)?)?),
+        QueryMsg::HaltConfig {} => Ok(to_json_binary(&HALT_CONFIG.may_load(deps.storage)?)?),
+        QueryMsg::HolderAddress {} => Ok(to_json_binary(&load_holder_address(deps.storage)?)?),
+        QueryMsg::Halted {} => Ok(to_json_binary(&is_halted(deps.storage)?)?),
+        QueryMsg::PfmConfig { denom } => Ok(to_json_binary(
+            &DESTINATION_CONFIG
+                .load(deps.storage)?
+                .denom_to_pfm_map
+                .get(&denom),
+        )?),
+        QueryMsg::AllPfmConfigs {} => Ok(to_json_binary(
+            &DESTINATION_CONFIG.load(deps.storage)?.denom_to_pfm_map,
+        )?),
+        QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
+        QueryMsg::InFlightTransfers {} => Ok(to_json_binary(&query_in_flight_transfers(deps)?)?),
+        QueryMsg::DenomDestinations {} => Ok(to_json_binary(
+            &DENOM_DESTINATIONS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<BTreeMap<_, _>>>()?,
+        )?),
+        QueryMsg::DustBalances {} => Ok(to_json_binary(&query_dust_balances(
+            deps.querier,
+            deps.storage,
+            env.contract.address.as_str(),
+            &TARGET_DENOMS.load(deps.storage)?,
+        )?)?),
+        QueryMsg::IbcFeeOverride {} => {
+            Ok(to_json_binary(&IBC_FEE_OVERRIDE.may_load(deps.storage)?)?)
+        }
     }
 }
 
@@ -186,6 +832,8 @@ pub fn migrate(
             clock_addr,
             destination_config,
             target_denoms,
+            denom_to_destination_map,
+            holder_address,
         } => {
             let mut response =
                 Response::default().add_attribute("method", "update_interchain_router");
@@ -203,10 +851,26 @@ pub fn migrate(
             }
 
             if let Some(config) = destination_config {
+                config.validate_memo_template()?;
+                config.validate_wasm_hook()?;
                 DESTINATION_CONFIG.save(deps.storage, &config)?;
                 response = response.add_attributes(config.get_response_attributes());
             }
 
+            if let Some(denom_to_destination_map) = denom_to_destination_map {
+                response = save_denom_destinations(
+                    deps.storage,
+                    deps.api,
+                    denom_to_destination_map,
+                    response,
+                )?;
+            }
+
+            if let Some(addr) = holder_address {
+                HOLDER_ADDRESS.save(deps.storage, &deps.api.addr_validate(&addr)?)?;
+                response = response.add_attribute("holder_address", addr);
+            }
+
             Ok(response)
         }
         MigrateMsg::UpdateCodeId { data: _ } => {
@@ -16,6 +16,9 @@ pub enum ContractError {
 
     #[error("caller must cover ibc fees: {0}")]
     IbcFeeError(PaymentError),
+
+    #[error(transparent)]
+    OwnershipError(#[from] cw_ownable::OwnershipError),
 }
 
 impl ContractError {
@@ -1,9 +1,16 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{to_json_binary, Addr, Binary, StdResult, WasmMsg};
-use covenant_macros::{clocked, covenant_clock_address};
-use covenant_utils::{instantiate2_helper::Instantiate2HelperConfig, DestinationConfig};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, StdResult, Uint128, WasmMsg};
+use covenant_macros::{
+    clocked, covenant_clock_address, covenant_router_halt, covenant_router_register_distribution,
+};
+use covenant_utils::{
+    distribution_receipt::PendingDistribution, instantiate2_helper::Instantiate2HelperConfig,
+    router_halt::RouterHaltConfig, DestinationConfig, PacketForwardMiddlewareConfig,
+};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use neutron_sdk::bindings::msg::IbcFee;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -14,6 +21,28 @@ pub struct InstantiateMsg {
     pub destination_config: DestinationConfig,
     /// specified denoms to route
     pub denoms: BTreeSet<String>,
+    /// optional config enabling the router to be halted
+    #[serde(default)]
+    pub halt_config: Option<RouterHaltConfig>,
+    /// holder distributing to the router. only it can register distributions.
+    /// defaults to the holder of the halt config.
+    #[serde(default)]
+    pub holder_address: Option<String>,
+    /// address authorized to update the pfm configs. without an
+    /// owner, they can only be updated by migrating the contract.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// destinations of specific denoms, overriding the
+    /// default destination config for those denoms
+    #[serde(default)]
+    pub denom_to_destination_map: BTreeMap<String, DestinationConfig>,
+    /// minimum amounts to route per denom. smaller
+    /// balances are left on the router as dust
+    #[serde(default)]
+    pub min_route_amounts: BTreeMap<String, Uint128>,
+    /// ibc fee to pay instead of the chain minimum, where it exceeds it
+    #[serde(default)]
+    pub ibc_fee_override: Option<IbcFee>,
 }
 
 impl InstantiateMsg {
@@ -35,12 +64,30 @@ impl InstantiateMsg {
 }
 
 #[clocked]
+#[covenant_router_register_distribution]
+#[covenant_router_halt]
+#[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
-    DistributeFallback { denoms: Vec<String> },
+    DistributeFallback {
+        denoms: Vec<String>,
+    },
+    /// set the pfm config used to unwind the denom, or remove it
+    /// if none is passed. only the owner may submit updates.
+    UpdatePfmConfig {
+        denom: String,
+        pfm_config: Option<PacketForwardMiddlewareConfig>,
+    },
+    /// routes the residual balances of the denoms to the receiver.
+    /// permissionless, but only enabled once the holder is complete.
+    /// the caller covers the ibc fees of every denom.
+    Sweep {
+        denoms: Vec<String>,
+    },
 }
 
 #[covenant_clock_address]
+#[cw_ownable_query]
 #[derive(QueryResponses)]
 #[cw_serde]
 pub enum QueryMsg {
@@ -48,6 +95,54 @@ pub enum QueryMsg {
     ReceiverConfig {},
     #[returns(BTreeSet<String>)]
     TargetDenoms {},
+    #[returns(Vec<PendingDistribution>)]
+    PendingDistributions {},
+    /// cumulative amounts of every denom at each stage of routing
+    #[returns(BTreeMap<String, RoutingStats>)]
+    RoutingStats {},
+    #[returns(Option<RouterHaltConfig>)]
+    HaltConfig {},
+    #[returns(Option<Addr>)]
+    HolderAddress {},
+    #[returns(bool)]
+    Halted {},
+    #[returns(Option<PacketForwardMiddlewareConfig>)]
+    PfmConfig { denom: String },
+    #[returns(BTreeMap<String, PacketForwardMiddlewareConfig>)]
+    AllPfmConfigs {},
+    /// ibc transfers that were submitted, but not yet acknowledged
+    #[returns(Vec<InFlightTransfer>)]
+    InFlightTransfers {},
+    /// destinations overriding the default destination config
+    #[returns(BTreeMap<String, DestinationConfig>)]
+    DenomDestinations {},
+    /// target denom balances below their minimum route amount
+    #[returns(Vec<Coin>)]
+    DustBalances {},
+    #[returns(Option<IbcFee>)]
+    IbcFeeOverride {},
+}
+
+#[cw_serde]
+pub struct InFlightTransfer {
+    pub channel_id: String,
+    pub sequence: u64,
+    pub coin: Coin,
+}
+
+/// cumulative amounts of a single denom at each stage of routing.
+/// the difference between two consecutive stages is the amount
+/// that is currently stuck in the latter.
+#[cw_serde]
+#[derive(Default)]
+pub struct RoutingStats {
+    /// amounts received by the router, including the
+    /// balance that is currently awaiting to be routed
+    pub received: Uint128,
+    /// amounts for which ibc transfers were emitted
+    pub transferred: Uint128,
+    /// amounts for which ibc transfer acknowledgements were received
+    pub acknowledged: Uint128,
 }
 
 #[cw_serde]
@@ -56,6 +151,11 @@ pub enum MigrateMsg {
         clock_addr: Option<String>,
         destination_config: Option<DestinationConfig>,
         target_denoms: Option<Vec<String>>,
+        /// replaces the per-denom destination overrides
+        #[serde(default)]
+        denom_to_destination_map: Option<BTreeMap<String, DestinationConfig>>,
+        #[serde(default)]
+        holder_address: Option<String>,
     },
     UpdateCodeId {
         data: Option<Binary>,
@@ -1,9 +1,32 @@
 use std::collections::BTreeSet;
 
-use cosmwasm_std::Addr;
-use covenant_utils::DestinationConfig;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Coin, Uint128};
+use covenant_utils::{router_halt::RouterHaltConfig, DestinationConfig};
+use cw_storage_plus::{Item, Map};
+use neutron_sdk::bindings::msg::IbcFee;
+
+use crate::msg::RoutingStats;
 
 pub const CLOCK_ADDRESS: Item<Addr> = Item::new("clock_address");
 pub const DESTINATION_CONFIG: Item<DestinationConfig> = Item::new("destination_config");
+/// destination configs overriding the default one, keyed by denom
+pub const DENOM_DESTINATIONS: Map<String, DestinationConfig> = Map::new("denom_destinations");
 pub const TARGET_DENOMS: Item<BTreeSet<String>> = Item::new("denoms");
+pub const HALT_CONFIG: Item<RouterHaltConfig> = Item::new("halt_config");
+/// holder distributing to the router
+pub const HOLDER_ADDRESS: Item<Addr> = Item::new("holder_address");
+
+/// cumulative routing amounts, keyed by denom
+pub const ROUTING_STATS: Map<String, RoutingStats> = Map::new("routing_stats");
+
+/// submitted ibc transfers awaiting their sequence, in submission order
+pub const PENDING_TRANSFERS: Item<Vec<Coin>> = Item::new("pending_transfers");
+
+/// ibc transfers awaiting their acknowledgement, keyed by (channel, sequence)
+pub const IN_FLIGHT_TRANSFERS: Map<(String, u64), Coin> = Map::new("in_flight_transfers");
+
+/// timed out transfer amounts to be retried on the next tick, keyed by denom
+pub const TIMED_OUT_TRANSFERS: Map<String, Uint128> = Map::new("timed_out_transfers");
+
+/// ibc fee paid instead of the chain minimum, where it exceeds it
+pub const IBC_FEE_OVERRIDE: Item<IbcFee> = Item::new("ibc_fee_override");
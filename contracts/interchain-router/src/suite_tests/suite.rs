@@ -70,8 +70,16 @@ impl Default for SuiteBuilder {
                     destination_receiver_addr: destination_address.to_string(),
                     ibc_transfer_timeout: Uint64::new(10),
                     denom_to_pfm_map: BTreeMap::new(),
+                    memo_template: None,
+                    wasm_hook: None,
                 },
                 denoms: BTreeSet::new(),
+                halt_config: None,
+                holder_address: None,
+                owner: None,
+                denom_to_destination_map: BTreeMap::new(),
+                min_route_amounts: BTreeMap::new(),
+                ibc_fee_override: None,
             },
             app: App::default(),
         }
@@ -103,6 +111,7 @@ impl SuiteBuilder {
                     tick_max_gas: None,
                     whitelist: vec![],
                     initial_queue: vec![],
+                    tick_intervals: vec![],
                 },
                 &[],
                 "clock",
@@ -39,6 +39,8 @@ fn test_instantiate_and_query_all() {
             destination_receiver_addr: suite.destination_addr.to_string(),
             ibc_transfer_timeout: Uint64::new(10),
             denom_to_pfm_map: BTreeMap::new(),
+            memo_template: None,
+            wasm_hook: None,
         },
         config
     );
@@ -57,8 +59,12 @@ fn test_migrate_config() {
             destination_receiver_addr: "new_receiver".to_string(),
             ibc_transfer_timeout: Uint64::new(100),
             denom_to_pfm_map: BTreeMap::new(),
+            memo_template: None,
+            wasm_hook: None,
         }),
         target_denoms: Some(target_denom_vec),
+        denom_to_destination_map: None,
+        holder_address: None,
     };
 
     suite.migrate(migrate_msg).unwrap();
@@ -74,6 +80,8 @@ fn test_migrate_config() {
             destination_receiver_addr: "new_receiver".to_string(),
             ibc_transfer_timeout: Uint64::new(100),
             denom_to_pfm_map: BTreeMap::new(),
+            memo_template: None,
+            wasm_hook: None,
         },
         config
     );
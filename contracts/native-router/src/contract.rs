@@ -3,16 +3,36 @@ use std::collections::BTreeSet;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Attribute, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Response, StdError, StdResult,
+    ensure, to_json_binary, to_json_string, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg,
+    Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    WasmMsg,
+};
+use covenant_utils::{
+    clock::increment_tick_nonce,
+    distribution_receipt::{
+        generate_distribution_failure_msg, query_pending_distributions, register_distribution,
+        take_distribution_acknowledgements, take_distribution_failures, verify_distribution_holder,
+        ACKNOWLEDGE_DISTRIBUTION_REPLY_ID,
+    },
+    get_local_delivery_msg,
+    neutron::consolidate_bank_sends,
+    op_mode::{verify_caller, ContractOperationMode},
+    router_dust::{
+        get_dust_attribute, get_min_route_amount_attributes, query_dust_balances,
+        split_dust_balances, MIN_ROUTE_AMOUNTS,
+    },
+    router_halt::{query_holder_complete, RouterHaltConfig, RouterHaltError, ROUTER_HALTED},
 };
-use covenant_utils::op_mode::{verify_caller, ContractOperationMode};
 use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20CoinVerified, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
 
 use crate::{
     error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    state::{CONTRACT_OP_MODE, RECEIVER_ADDRESS, TARGET_DENOMS},
+    state::{
+        CONTRACT_OP_MODE, CW20_TOKENS, HALT_CONFIG, HOLDER_ADDRESS, RECEIVER_ADDRESS, RECEIVER_MSG,
+        TARGET_DENOMS,
+    },
 };
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -33,10 +53,39 @@ pub fn instantiate(
     CONTRACT_OP_MODE.save(deps.storage, &op_mode)?;
     RECEIVER_ADDRESS.save(deps.storage, &receiver_addr)?;
     TARGET_DENOMS.save(deps.storage, &msg.denoms)?;
+    MIN_ROUTE_AMOUNTS.save(deps.storage, &msg.min_route_amounts)?;
 
-    Ok(Response::default()
+    let mut response = Response::default()
         .add_attribute("method", "interchain_router_instantiate")
-        .add_attribute("op_mode", format!("{:?}", op_mode)))
+        .add_attribute("op_mode", format!("{:?}", op_mode))
+        .add_attributes(get_min_route_amount_attributes(&msg.min_route_amounts));
+
+    let mut cw20_tokens = BTreeSet::new();
+    for token in msg.cw20_tokens {
+        cw20_tokens.insert(deps.api.addr_validate(&token)?);
+    }
+    if !cw20_tokens.is_empty() {
+        response = response.add_attribute("cw20_tokens", to_json_string(&cw20_tokens)?);
+    }
+    CW20_TOKENS.save(deps.storage, &cw20_tokens)?;
+
+    if let Some(receiver_msg) = msg.receiver_msg {
+        RECEIVER_MSG.save(deps.storage, &receiver_msg)?;
+        response = response.add_attribute("receiver_msg", receiver_msg.to_base64());
+    }
+
+    if let Some(halt_config) = msg.halt_config {
+        halt_config.validate(deps.api)?;
+        HALT_CONFIG.save(deps.storage, &halt_config)?;
+        response = response.add_attributes(halt_config.get_response_attributes());
+    }
+
+    if let Some(addr) = msg.holder_address {
+        HOLDER_ADDRESS.save(deps.storage, &deps.api.addr_validate(&addr)?)?;
+        response = response.add_attribute("holder_address", addr);
+    }
+
+    Ok(response)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -48,10 +97,131 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
             verify_caller(&info.sender, &CONTRACT_OP_MODE.load(deps.storage)?)?;
-            try_route_balances(deps, env)
+            let response = match HALT_CONFIG.may_load(deps.storage)? {
+                // a halted router keeps returning whatever it receives to the holder
+                Some(halt_config) if is_halted(deps.storage)? => {
+                    let response =
+                        Response::default().add_attribute("method", "try_return_balances");
+                    return_balances(deps, env, halt_config, response)
+                }
+                _ => try_route_balances(deps, env),
+            }?;
+            Ok(response.add_attribute("tick_nonce", tick_nonce.to_string()))
+        }
+        ExecuteMsg::DistributeFallback { denoms } => {
+            ensure!(!is_halted(deps.storage)?, RouterHaltError::Halted);
+            try_distribute_fallback(deps, env, denoms)
+        }
+        ExecuteMsg::RegisterDistribution { id, coins } => {
+            try_register_distribution(deps, info, id, coins)
+        }
+        ExecuteMsg::Halt {} => try_halt(deps, env, info),
+        ExecuteMsg::Receive(cw20_receive_msg) => try_receive_cw20(deps, info, cw20_receive_msg),
+        ExecuteMsg::Sweep { denoms } => {
+            ensure!(!is_halted(deps.storage)?, RouterHaltError::Halted);
+            try_sweep(deps, env, denoms)
         }
-        ExecuteMsg::DistributeFallback { denoms } => try_distribute_fallback(deps, env, denoms),
+    }
+}
+
+fn is_halted(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(ROUTER_HALTED.may_load(storage)?.unwrap_or_default())
+}
+
+/// halts the router, stopping any further routing
+fn try_halt(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let halt_config = HALT_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(RouterHaltError::NotConfigured)?;
+    halt_config.verify_halt_authority(&deps.querier, &env, &info.sender)?;
+
+    ROUTER_HALTED.save(deps.storage, &true)?;
+
+    let response = Response::default()
+        .add_attribute("method", "try_halt")
+        .add_attribute("halted_by", info.sender);
+    return_balances(deps, env, halt_config, response)
+}
+
+/// returns all target denom balances to the holder and
+/// reports any pending distributions as failed
+fn return_balances(
+    deps: DepsMut,
+    env: Env,
+    halt_config: RouterHaltConfig,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let mut balances = vec![];
+    for denom in TARGET_DENOMS.load(deps.storage)? {
+        balances.push(
+            deps.querier
+                .query_balance(env.contract.address.to_string(), denom)?,
+        );
+    }
+    let cw20_balances = query_cw20_balances(deps.as_ref(), &env)?;
+
+    let failures: Vec<SubMsg> = take_distribution_failures(deps.storage, "router halted")?
+        .into_iter()
+        .map(|msg| SubMsg::reply_on_error(msg, ACKNOWLEDGE_DISTRIBUTION_REPLY_ID))
+        .collect();
+
+    Ok(response
+        .add_attribute("holder", halt_config.holder_address.to_string())
+        .add_messages(halt_config.get_return_msgs(balances))
+        .add_messages(get_cw20_delivery_msgs(
+            cw20_balances,
+            &halt_config.holder_address,
+            None,
+        )?)
+        .add_submessages(failures))
+}
+
+/// returns the holder distributing to the router, falling
+/// back to the holder of the halt config if none is set
+fn load_holder_address(storage: &dyn Storage) -> StdResult<Option<Addr>> {
+    match HOLDER_ADDRESS.may_load(storage)? {
+        Some(addr) => Ok(Some(addr)),
+        None => Ok(HALT_CONFIG
+            .may_load(storage)?
+            .map(|config| Addr::unchecked(config.holder_address))),
+    }
+}
+
+fn try_register_distribution(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    coins: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    // every registration costs an acknowledgement later on,
+    // so only the holder can register distributions
+    verify_distribution_holder(load_holder_address(deps.storage)?, &info.sender)?;
+
+    let target_denoms = TARGET_DENOMS.load(deps.storage)?;
+    let response = Response::default()
+        .add_attribute("method", "try_register_distribution")
+        .add_attribute("holder", info.sender.to_string())
+        .add_attribute("id", id.to_string());
+
+    // distributions received after halting are returned along with the
+    // remaining balances, so they can not be delivered
+    if is_halted(deps.storage)? {
+        return Ok(response.add_attribute("status", "failed").add_message(
+            generate_distribution_failure_msg(
+                info.sender.to_string(),
+                id,
+                "router halted".to_string(),
+            )?,
+        ));
+    }
+
+    match register_distribution(deps.storage, info.sender, id, coins, &target_denoms)? {
+        Some(failure_msg) => Ok(response
+            .add_attribute("status", "failed")
+            .add_message(failure_msg)),
+        None => Ok(response.add_attribute("status", "pending")),
     }
 }
 
@@ -78,23 +248,178 @@ fn try_distribute_fallback(
         available_balances.push(queried_coin);
     }
 
-    let bank_sends: Vec<CosmosMsg> = available_balances
+    let delivery_msgs = get_delivery_msgs(
+        deps.storage,
+        &env,
+        receiver_address.as_str(),
+        available_balances,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_distribute_fallback")
+        .add_messages(delivery_msgs))
+}
+
+/// forwards the received cw20 tokens right away, to the holder
+/// if the router is halted or to the receiver otherwise
+fn try_receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let cw20_tokens = CW20_TOKENS.may_load(deps.storage)?.unwrap_or_default();
+    ensure!(
+        cw20_tokens.contains(&info.sender),
+        ContractError::UnauthorizedCw20Token {
+            token: info.sender.to_string()
+        }
+    );
+
+    let (recipient, recipient_msg) = match HALT_CONFIG.may_load(deps.storage)? {
+        Some(halt_config) if is_halted(deps.storage)? => (halt_config.holder_address, None),
+        _ => (
+            RECEIVER_ADDRESS.load(deps.storage)?.to_string(),
+            RECEIVER_MSG.may_load(deps.storage)?,
+        ),
+    };
+    let received = Cw20CoinVerified {
+        address: info.sender,
+        amount: cw20_receive_msg.amount,
+    };
+
+    Ok(Response::default()
+        .add_attribute("method", "try_receive_cw20")
+        .add_attribute("sender", cw20_receive_msg.sender)
+        .add_attribute("recipient", recipient.to_string())
+        .add_attribute(received.address.to_string(), received.amount)
+        .add_messages(get_cw20_delivery_msgs(
+            vec![received],
+            &recipient,
+            recipient_msg,
+        )?))
+}
+
+/// queries the non-zero balances of the allowlisted cw20 tokens
+fn query_cw20_balances(deps: Deps, env: &Env) -> StdResult<Vec<Cw20CoinVerified>> {
+    let mut balances = vec![];
+    for token in CW20_TOKENS.may_load(deps.storage)?.unwrap_or_default() {
+        let balance: BalanceResponse = deps.querier.query_wasm_smart(
+            token.to_string(),
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        if !balance.balance.is_zero() {
+            balances.push(Cw20CoinVerified {
+                address: token,
+                amount: balance.balance,
+            });
+        }
+    }
+    Ok(balances)
+}
+
+/// cw20 balances are transferred to the recipient. if a recipient msg
+/// is passed, the tokens are sent along with it to be executed instead.
+fn get_cw20_delivery_msgs(
+    balances: Vec<Cw20CoinVerified>,
+    recipient: &str,
+    recipient_msg: Option<Binary>,
+) -> StdResult<Vec<WasmMsg>> {
+    balances
+        .into_iter()
+        .map(|balance| {
+            let cw20_msg = match &recipient_msg {
+                Some(msg) => Cw20ExecuteMsg::Send {
+                    contract: recipient.to_string(),
+                    amount: balance.amount,
+                    msg: msg.clone(),
+                },
+                None => Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: balance.amount,
+                },
+            };
+            Ok(WasmMsg::Execute {
+                contract_addr: balance.address.to_string(),
+                msg: to_json_binary(&cw20_msg)?,
+                funds: vec![],
+            })
+        })
+        .collect()
+}
+
+fn get_cw20_balance_attributes(balances: &[Cw20CoinVerified]) -> Vec<Attribute> {
+    balances
+        .iter()
+        .map(|balance| Attribute::new(balance.address.to_string(), balance.amount))
+        .collect()
+}
+
+/// routes whatever is left on the router after the covenant completed,
+/// as it is no longer being ticked by then
+fn try_sweep(deps: DepsMut, env: Env, denoms: Vec<String>) -> Result<Response, ContractError> {
+    let holder = load_holder_address(deps.storage)?.ok_or(RouterHaltError::HolderNotConfigured)?;
+    ensure!(
+        query_holder_complete(&deps.querier, &holder)?,
+        RouterHaltError::HolderNotComplete
+    );
+
+    let mut balances = Vec::with_capacity(denoms.len());
+    for denom in denoms {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), denom)?;
+        if !balance.amount.is_zero() {
+            balances.push(balance);
+        }
+    }
+
+    let receiver_address = RECEIVER_ADDRESS.load(deps.storage)?;
+    let swept_attribute = to_json_string(&balances)?;
+    let delivery_msgs = get_delivery_msgs(deps.storage, &env, receiver_address.as_str(), balances)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_sweep")
+        .add_attribute("swept", swept_attribute)
+        .add_messages(delivery_msgs))
+}
+
+/// all denoms are transferred to the receiver with a single message.
+/// receivers configured with an execute msg get executed with the coins attached.
+fn get_delivery_msgs(
+    storage: &dyn Storage,
+    env: &Env,
+    receiver: &str,
+    coins: Vec<Coin>,
+) -> StdResult<Vec<CosmosMsg>> {
+    if let Some(receiver_msg) = RECEIVER_MSG.may_load(storage)? {
+        let coins: Vec<Coin> = coins.into_iter().filter(|c| !c.amount.is_zero()).collect();
+        if coins.is_empty() {
+            return Ok(vec![]);
+        }
+        return Ok(vec![get_local_delivery_msg(
+            receiver,
+            Some(receiver_msg),
+            coins,
+        )]);
+    }
+
+    let bank_sends: Vec<CosmosMsg> = coins
         .into_iter()
         .map(|c| {
             BankMsg::Send {
-                to_address: receiver_address.to_string(),
+                to_address: receiver.to_string(),
                 amount: vec![c],
             }
             .into()
         })
         .collect();
-
-    Ok(Response::default()
-        .add_attribute("method", "try_distribute_fallback")
-        .add_messages(bank_sends))
+    consolidate_bank_sends(env.contract.address.as_str(), bank_sends)
 }
 
-/// method that attempts to transfer out all available balances to the receiver
+/// method that attempts to transfer out all available balances to the receiver.
+/// pending distributions get acknowledged along with the transfers.
 fn try_route_balances(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
     let receiver_addr = RECEIVER_ADDRESS.load(deps.storage)?;
     let denoms_to_route = TARGET_DENOMS.load(deps.storage)?;
@@ -109,14 +434,30 @@ fn try_route_balances(deps: DepsMut, env: Env) -> Result<Response, ContractError
         }
     }
 
+    // balances below their minimum route amount are left as dust
+    let min_route_amounts = MIN_ROUTE_AMOUNTS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let (denom_balances, dust_balances) = split_dust_balances(denom_balances, &min_route_amounts);
+    let dust_attribute = get_dust_attribute(&dust_balances)?;
+    let cw20_balances = query_cw20_balances(deps.as_ref(), &env)?;
+
+    let acknowledgements: Vec<SubMsg> = take_distribution_acknowledgements(deps.storage)?
+        .into_iter()
+        .map(|msg| SubMsg::reply_on_error(msg, ACKNOWLEDGE_DISTRIBUTION_REPLY_ID))
+        .collect();
+
     // if there are no balances, we return early;
     // otherwise build up the response attributes
     let balance_attributes: Vec<Attribute> = match denom_balances.len() {
-        0 => {
+        0 if cw20_balances.is_empty() => {
             return Ok(Response::default()
                 .add_attribute("method", "try_route_balances")
-                .add_attribute("balances", "[]"))
+                .add_attribute("balances", "[]")
+                .add_attributes(dust_attribute)
+                .add_submessages(acknowledgements))
         }
+        0 => vec![],
         1 => vec![Attribute::new(
             denom_balances[0].denom.to_string(),
             denom_balances[0].amount,
@@ -127,33 +468,63 @@ fn try_route_balances(deps: DepsMut, env: Env) -> Result<Response, ContractError
             .collect(),
     };
 
-    let bank_sends: Vec<CosmosMsg> = denom_balances
-        .into_iter()
-        .map(|c| {
-            BankMsg::Send {
-                to_address: receiver_addr.to_string(),
-                amount: vec![c],
-            }
-            .into()
-        })
-        .collect();
+    let delivery_msgs =
+        get_delivery_msgs(deps.storage, &env, receiver_addr.as_str(), denom_balances)?;
 
     Ok(Response::default()
         .add_attribute("method", "try_route_balances")
         .add_attributes(balance_attributes)
-        .add_messages(bank_sends))
+        .add_attributes(get_cw20_balance_attributes(&cw20_balances))
+        .add_attributes(dust_attribute)
+        .add_messages(delivery_msgs)
+        .add_messages(get_cw20_delivery_msgs(
+            cw20_balances,
+            receiver_addr.as_str(),
+            RECEIVER_MSG.may_load(deps.storage)?,
+        )?)
+        .add_submessages(acknowledgements))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        // failed acknowledgements are not retried
+        ACKNOWLEDGE_DISTRIBUTION_REPLY_ID => Ok(Response::default()
+            .add_attribute("method", "reply")
+            .add_attribute("acknowledgement", "failed")),
+        _ => Err(ContractError::Std(StdError::generic_err(format!(
+            "unknown reply id: {}",
+            msg.id
+        )))),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::ReceiverConfig {} => {
             Ok(to_json_binary(&RECEIVER_ADDRESS.may_load(deps.storage)?)?)
         }
+        QueryMsg::ReceiverMsg {} => Ok(to_json_binary(&RECEIVER_MSG.may_load(deps.storage)?)?),
         QueryMsg::TargetDenoms {} => Ok(to_json_binary(&TARGET_DENOMS.may_load(deps.storage)?)?),
         QueryMsg::OperationMode {} => {
             Ok(to_json_binary(&CONTRACT_OP_MODE.may_load(deps.storage)?)?)
         }
+        QueryMsg::PendingDistributions {} => {
+            Ok(to_json_binary(&query_pending_distributions(deps.storage)?)?)
+        }
+        QueryMsg::HaltConfig {} => Ok(to_json_binary(&HALT_CONFIG.may_load(deps.storage)?)?),
+        QueryMsg::HolderAddress {} => Ok(to_json_binary(&load_holder_address(deps.storage)?)?),
+        QueryMsg::Halted {} => Ok(to_json_binary(&is_halted(deps.storage)?)?),
+        QueryMsg::Cw20Tokens {} => Ok(to_json_binary(
+            &CW20_TOKENS.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::DustBalances {} => Ok(to_json_binary(&query_dust_balances(
+            deps.querier,
+            deps.storage,
+            env.contract.address.as_str(),
+            &TARGET_DENOMS.load(deps.storage)?,
+        )?)?),
     }
 }
 
@@ -164,6 +535,7 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
             op_mode,
             receiver_address,
             target_denoms,
+            holder_address,
         } => {
             let mut response =
                 Response::default().add_attribute("method", "update_interchain_router");
@@ -188,6 +560,11 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                 response = response.add_attribute("receiver_addr", addr);
             }
 
+            if let Some(addr) = holder_address {
+                HOLDER_ADDRESS.save(deps.storage, &deps.api.addr_validate(&addr)?)?;
+                response = response.add_attribute("holder_address", addr);
+            }
+
             Ok(response)
         }
         MigrateMsg::UpdateCodeId { data: _ } => {
@@ -1,5 +1,5 @@
 use cosmwasm_std::StdError;
-use covenant_utils::op_mode::ContractOperationError;
+use covenant_utils::{op_mode::ContractOperationError, router_halt::RouterHaltError};
 use neutron_sdk::NeutronError;
 use thiserror::Error;
 
@@ -14,6 +14,12 @@ pub enum ContractError {
     #[error(transparent)]
     ContractOperationError(#[from] ContractOperationError),
 
+    #[error(transparent)]
+    RouterHaltError(#[from] RouterHaltError),
+
     #[error("unauthorized to distribute explicitly defined denom")]
     UnauthorizedDenomDistribution {},
+
+    #[error("cw20 token {token} is not routed")]
+    UnauthorizedCw20Token { token: String },
 }
@@ -1,13 +1,16 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{to_json_binary, Binary, StdResult, WasmMsg};
-use covenant_macros::clocked;
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, StdResult, Uint128, WasmMsg};
+use covenant_macros::{clocked, covenant_router_halt, covenant_router_register_distribution};
 use covenant_utils::{
+    distribution_receipt::PendingDistribution,
     instantiate2_helper::Instantiate2HelperConfig,
     op_mode::{ContractOperationMode, ContractOperationModeConfig},
+    router_halt::RouterHaltConfig,
     ReceiverConfig,
 };
+use cw20::Cw20ReceiveMsg;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -18,8 +21,26 @@ pub struct InstantiateMsg {
     pub op_mode_cfg: ContractOperationModeConfig,
     /// receiver address on local chain
     pub receiver_address: String,
+    /// optional msg to execute the receiver with, attaching the routed
+    /// funds. enables routing to contracts such as vaults
+    #[serde(default)]
+    pub receiver_msg: Option<Binary>,
     /// specified denoms to route
     pub denoms: BTreeSet<String>,
+    /// optional config enabling the router to be halted
+    #[serde(default)]
+    pub halt_config: Option<RouterHaltConfig>,
+    /// holder distributing to the router. only it can register distributions.
+    /// defaults to the holder of the halt config.
+    #[serde(default)]
+    pub holder_address: Option<String>,
+    /// minimum amounts to route per denom. smaller
+    /// balances are left on the router as dust
+    #[serde(default)]
+    pub min_route_amounts: BTreeMap<String, Uint128>,
+    /// allowlist of cw20 token contracts to route
+    #[serde(default)]
+    pub cw20_tokens: BTreeSet<String>,
 }
 
 impl InstantiateMsg {
@@ -41,9 +62,20 @@ impl InstantiateMsg {
 }
 
 #[clocked]
+#[covenant_router_register_distribution]
+#[covenant_router_halt]
 #[cw_serde]
 pub enum ExecuteMsg {
-    DistributeFallback { denoms: Vec<String> },
+    DistributeFallback {
+        denoms: Vec<String>,
+    },
+    /// forwards the received allowlisted cw20 tokens to the receiver
+    Receive(Cw20ReceiveMsg),
+    /// routes the residual balances of the denoms to the receiver.
+    /// permissionless, but only enabled once the holder is complete.
+    Sweep {
+        denoms: Vec<String>,
+    },
 }
 
 #[derive(QueryResponses)]
@@ -51,10 +83,25 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ReceiverConfig)]
     ReceiverConfig {},
+    #[returns(Option<Binary>)]
+    ReceiverMsg {},
     #[returns(BTreeSet<String>)]
     TargetDenoms {},
     #[returns(ContractOperationMode)]
     OperationMode {},
+    #[returns(Vec<PendingDistribution>)]
+    PendingDistributions {},
+    #[returns(Option<RouterHaltConfig>)]
+    HaltConfig {},
+    #[returns(Option<Addr>)]
+    HolderAddress {},
+    #[returns(bool)]
+    Halted {},
+    /// target denom balances below their minimum route amount
+    #[returns(Vec<Coin>)]
+    DustBalances {},
+    #[returns(BTreeSet<Addr>)]
+    Cw20Tokens {},
 }
 
 #[cw_serde]
@@ -63,6 +110,8 @@ pub enum MigrateMsg {
         op_mode: Option<ContractOperationModeConfig>,
         receiver_address: Option<String>,
         target_denoms: Option<Vec<String>>,
+        #[serde(default)]
+        holder_address: Option<String>,
     },
     UpdateCodeId {
         data: Option<Binary>,
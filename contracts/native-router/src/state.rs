@@ -1,9 +1,16 @@
 use std::collections::BTreeSet;
 
-use cosmwasm_std::Addr;
-use covenant_utils::op_mode::ContractOperationMode;
+use cosmwasm_std::{Addr, Binary};
+use covenant_utils::{op_mode::ContractOperationMode, router_halt::RouterHaltConfig};
 use cw_storage_plus::Item;
 
 pub const CONTRACT_OP_MODE: Item<ContractOperationMode> = Item::new("contract_op_mode");
 pub const RECEIVER_ADDRESS: Item<Addr> = Item::new("receiver_address");
+/// msg the receiver contract gets executed with on every delivery
+pub const RECEIVER_MSG: Item<Binary> = Item::new("receiver_msg");
 pub const TARGET_DENOMS: Item<BTreeSet<String>> = Item::new("denoms");
+pub const HALT_CONFIG: Item<RouterHaltConfig> = Item::new("halt_config");
+/// holder distributing to the router
+pub const HOLDER_ADDRESS: Item<Addr> = Item::new("holder_address");
+/// cw20 token contracts that get routed along with the target denoms
+pub const CW20_TOKENS: Item<BTreeSet<Addr>> = Item::new("cw20_tokens");
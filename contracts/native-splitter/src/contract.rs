@@ -1,31 +1,45 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdError, StdResult,
+    ensure, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Order, Response, StdError, StdResult, Storage, Uint128,
 };
 use covenant_utils::{
-    clock::{enqueue_msg, verify_clock},
-    split::SplitConfig,
+    clock::{dequeue_msg, enqueue_msg, increment_tick_nonce, verify_clock},
+    decommission::{verify_decommission_authority, DecommissionError},
+    denom::ensure_denom_exists,
+    neutron::{consolidate_bank_sends, query_ibc_fee},
+    soft_validate_remote_chain_addr,
+    split::{get_splits_updated_event, SplitConfig},
+    split_receiver_fix::{
+        fix_split_receiver, SplitReceiverFixStatus, PENDING_SPLIT_RECEIVER_FIXES,
+    },
 };
 use cw2::set_contract_version;
+use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{CLOCK_ADDRESS, FALLBACK_SPLIT, SPLIT_CONFIG_MAP};
+use crate::msg::{
+    ExecuteMsg, IbcReceiverConfig, InstantiateMsg, MigrateMsg, QueryMsg, ReceiverType,
+};
+use crate::state::{
+    ACCRUED_DISTRIBUTIONS, CLOCK_ADDRESS, COMPLETED, COMPLETION_CONFIG, DECOMMISSIONED,
+    EMERGENCY_COMMITTEE_ADDR, FALLBACK_SPLIT, IBC_RECEIVERS, OP_ADMIN, RECEIVED_AMOUNTS,
+    SPLIT_CONFIG_MAP, VALIDATE_DENOMS,
+};
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    deps: DepsMut<NeutronQuery>,
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
+) -> Result<Response<NeutronMsg>, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let mut resp = Response::default().add_attribute("method", "native_splitter_instantiate");
@@ -34,21 +48,66 @@ pub fn instantiate(
     CLOCK_ADDRESS.save(deps.storage, &clock_address)?;
     resp = resp.add_attribute("clock_addr", msg.clock_address.to_string());
 
+    // receivers on remote chains get paid out with ibc transfers
+    for (receiver, receiver_type) in msg.receiver_types {
+        if let ReceiverType::Ibc(ibc_receiver_config) = receiver_type {
+            soft_validate_remote_chain_addr(deps.api, &receiver)?;
+            resp = resp.add_attribute(
+                format!("ibc_receiver_{receiver}"),
+                ibc_receiver_config.channel_id.to_string(),
+            );
+            IBC_RECEIVERS.save(deps.storage, receiver, &ibc_receiver_config)?;
+        }
+    }
+    let remote_receivers = query_remote_receivers(deps.storage)?;
+
+    VALIDATE_DENOMS.save(deps.storage, &msg.validate_denoms)?;
+
     // we validate the splits and store them per-denom
     for (denom, split) in msg.splits {
-        split.validate_shares_and_receiver_addresses(deps.api)?;
+        if msg.validate_denoms {
+            ensure_denom_exists(&deps.querier, &denom)?;
+        }
+        split.validate_shares_and_receivers(deps.api, &remote_receivers)?;
         SPLIT_CONFIG_MAP.save(deps.storage, denom.to_string(), &split)?;
     }
 
+    if let Some(addr) = &msg.emergency_committee {
+        let committee_addr = deps.api.addr_validate(addr)?;
+        EMERGENCY_COMMITTEE_ADDR.save(deps.storage, &committee_addr)?;
+        resp = resp.add_attribute("emergency_committee", committee_addr);
+    }
+
+    if let Some(addr) = &msg.op_admin {
+        let op_admin_addr = deps.api.addr_validate(addr)?;
+        OP_ADMIN.save(deps.storage, &op_admin_addr)?;
+        resp = resp.add_attribute("op_admin", op_admin_addr);
+    }
+
+    let ownership = cw_ownable::initialize_owner(deps.storage, deps.api, msg.owner.as_deref())?;
+    resp = resp.add_attributes(ownership.into_attributes());
+
     // if a fallback split is provided we validate and store it
     if let Some(split) = msg.fallback_split {
         resp = resp.add_attributes(vec![split.get_response_attribute("fallback".to_string())]);
-        split.validate_shares_and_receiver_addresses(deps.api)?;
+        split.validate_shares_and_receivers(deps.api, &remote_receivers)?;
         FALLBACK_SPLIT.save(deps.storage, &split)?;
     } else {
         resp = resp.add_attribute("fallback", "None");
     }
 
+    if let Some(completion_config) = msg.completion_config {
+        for coin in &completion_config.expected_amounts {
+            ensure!(
+                !coin.amount.is_zero()
+                    && SPLIT_CONFIG_MAP.has(deps.storage, coin.denom.to_string()),
+                ContractError::CompletionConfigError(coin.denom.to_string())
+            );
+            resp = resp.add_attribute(format!("expected_{}", coin.denom), coin.amount);
+        }
+        COMPLETION_CONFIG.save(deps.storage, &completion_config)?;
+    }
+
     Ok(resp
         .add_message(enqueue_msg(msg.clock_address.as_str())?)
         .add_attribute("clock_address", clock_address))
@@ -56,51 +115,181 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    deps: DepsMut<NeutronQuery>,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
+) -> Result<Response<NeutronMsg>, ContractError> {
     match msg {
         ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
             verify_clock(&info.sender, &CLOCK_ADDRESS.load(deps.storage)?)
                 .map_err(|_| ContractError::NotClock)?;
 
-            try_distribute(deps, env)
+            Ok(try_distribute(deps, env)?.add_attribute("tick_nonce", tick_nonce.to_string()))
         }
         ExecuteMsg::DistributeFallback { denoms } => try_distribute_fallback(deps, env, denoms),
+        ExecuteMsg::Decommission {} => try_decommission(deps, env, info),
+        ExecuteMsg::FixSplitReceiver { denom, old, new } => {
+            try_fix_split_receiver(deps, env, info, denom, old, new)
+        }
+        ExecuteMsg::UpdateSplits { splits, fallback } => {
+            try_update_splits(deps, info, splits, fallback)
+        }
+        ExecuteMsg::UpdateConfig {
+            clock_addr,
+            fallback_split,
+            splits,
+        } => {
+            cw_ownable::assert_owner(deps.storage, &info.sender)?;
+            update_config(deps, clock_addr, splits, fallback_split)
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            let ownership =
+                cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
+            Ok(Response::default()
+                .add_attribute("method", "update_ownership")
+                .add_attributes(ownership.into_attributes()))
+        }
     }
 }
 
-pub fn try_distribute(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+pub fn try_distribute(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    // decommissioned splitters already distributed their balances
+    if DECOMMISSIONED.may_load(deps.storage)?.unwrap_or_default() {
+        return Ok(Response::default()
+            .add_attribute("method", "try_distribute")
+            .add_attribute("status", "decommissioned"));
+    }
+    if is_completed(deps.storage)? {
+        return Ok(Response::default()
+            .add_attribute("method", "try_distribute")
+            .add_attribute("status", "completed"));
+    }
+
+    // once all expected amounts arrived, this distribution is the last one
+    let completing = record_received_amounts(deps.branch(), &env)?;
+    let distribution_messages = get_distribution_messages(deps.branch(), &env, completing)?;
+
+    let resp = Response::default()
+        .add_attribute("method", "try_distribute")
+        .add_messages(distribution_messages);
+    if !completing {
+        return Ok(resp);
+    }
+
+    COMPLETED.save(deps.storage, &true)?;
+    let clock_address = CLOCK_ADDRESS.load(deps.storage)?;
+    Ok(resp
+        .add_attribute("status", "completed")
+        .add_message(dequeue_msg(clock_address.as_str())?))
+}
+
+fn is_completed(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(COMPLETED.may_load(storage)?.unwrap_or_default())
+}
+
+/// adds the amounts of the expected denoms received since the last
+/// distribution, and returns whether all expected amounts were received
+fn record_received_amounts(deps: DepsMut<NeutronQuery>, env: &Env) -> StdResult<bool> {
+    let Some(completion_config) = COMPLETION_CONFIG.may_load(deps.storage)? else {
+        return Ok(false);
+    };
+
+    let mut completing = true;
+    for expected in completion_config.expected_amounts {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), expected.denom.to_string())?;
+        // amounts accrued below the receiver min amounts were already recorded
+        let accrued_total = ACCRUED_DISTRIBUTIONS
+            .may_load(deps.storage, expected.denom.to_string())?
+            .unwrap_or_default()
+            .values()
+            .try_fold(Uint128::zero(), |acc, amt| acc.checked_add(*amt))?;
+
+        let received = RECEIVED_AMOUNTS
+            .may_load(deps.storage, expected.denom.to_string())?
+            .unwrap_or_default()
+            .checked_add(balance.amount.saturating_sub(accrued_total))?;
+        RECEIVED_AMOUNTS.save(deps.storage, expected.denom, &received)?;
+
+        completing &= received >= expected.amount;
+    }
+
+    Ok(completing)
+}
+
+/// distributes the remaining balances of the split denoms, flushing the
+/// amounts accrued below the receiver min amounts, and stops the splitter.
+/// fallback denoms remain distributable via `DistributeFallback`.
+fn try_decommission(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    verify_decommission_authority(&deps.querier, &env, &info.sender)?;
+    ensure!(
+        !DECOMMISSIONED.may_load(deps.storage)?.unwrap_or_default(),
+        DecommissionError::Decommissioned
+    );
+
+    DECOMMISSIONED.save(deps.storage, &true)?;
+    let clock_address = CLOCK_ADDRESS.load(deps.storage)?;
+    let completed = is_completed(deps.storage)?;
+    let distribution_messages = get_distribution_messages(deps, &env, true)?;
+
+    let mut resp = Response::default()
+        .add_attribute("method", "try_decommission")
+        .add_messages(distribution_messages);
+    // completed splitters already dequeued themselves
+    if !completed {
+        resp = resp.add_message(dequeue_msg(clock_address.as_str())?);
+    }
+    Ok(resp)
+}
+
+/// transfer messages splitting the balances of the split denoms
+fn get_distribution_messages(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    flush: bool,
+) -> Result<Vec<CosmosMsg<NeutronMsg>>, ContractError> {
     // first we query the contract balances
-    let mut distribution_messages: Vec<CosmosMsg> = vec![];
+    let mut distributions: Vec<(String, Coin)> = vec![];
+
+    let splits = SPLIT_CONFIG_MAP
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, SplitConfig)>>>()?;
 
     // then we iterate over our split config and try to match the entries to available balances
-    for entry in SPLIT_CONFIG_MAP.range(deps.storage, None, None, Order::Ascending) {
-        let (denom, config) = entry?;
+    for (denom, config) in splits {
         let balance = deps
             .querier
             .query_balance(env.contract.address.clone(), denom.to_string())?;
 
         if !balance.amount.is_zero() {
-            let mut transfer_messages =
-                config.get_transfer_messages(balance.amount, balance.denom.to_string(), None)?;
-            distribution_messages.append(&mut transfer_messages);
+            let mut accrued_distributions =
+                get_accrued_distributions(deps.storage, &config, balance, flush)?;
+            distributions.append(&mut accrued_distributions);
         }
     }
 
-    Ok(Response::default()
-        .add_attribute("method", "try_distribute")
-        .add_messages(distribution_messages))
+    Ok(get_payout_messages(deps.as_ref(), env, distributions)?)
 }
 
 fn try_distribute_fallback(
-    deps: DepsMut,
+    deps: DepsMut<NeutronQuery>,
     env: Env,
     denoms: Vec<String>,
-) -> Result<Response, ContractError> {
-    let mut distribution_messages: Vec<CosmosMsg> = vec![];
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let mut distributions: Vec<(String, Coin)> = vec![];
+    // after decommissioning or completing, nothing accrues anymore
+    let flush =
+        DECOMMISSIONED.may_load(deps.storage)?.unwrap_or_default() || is_completed(deps.storage)?;
 
     if let Some(split) = FALLBACK_SPLIT.may_load(deps.storage)? {
         for denom in denoms {
@@ -115,32 +304,265 @@ fn try_distribute_fallback(
                 .querier
                 .query_balance(env.contract.address.to_string(), denom)?;
             if !balance.amount.is_zero() {
-                let mut fallback_messages =
-                    split.get_transfer_messages(balance.amount, balance.denom, None)?;
-                distribution_messages.append(&mut fallback_messages);
+                let mut fallback_distributions =
+                    get_accrued_distributions(deps.storage, &split, balance, flush)?;
+                distributions.append(&mut fallback_distributions);
             }
         }
     } else {
         return Err(StdError::generic_err("no fallback split defined").into());
     }
 
+    let distribution_messages = get_payout_messages(deps.as_ref(), &env, distributions)?;
+
     Ok(Response::default()
         .add_attribute("method", "try_distribute_fallback")
         .add_messages(distribution_messages))
 }
 
+/// schedules (or applies, once timelocked) the replacement of a
+/// receiver of the split of the denom
+fn try_fix_split_receiver(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    old: String,
+    new: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let committee_addr = EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?;
+    ensure!(
+        Some(&info.sender) == committee_addr.as_ref(),
+        ContractError::Unauthorized {}
+    );
+    if IBC_RECEIVERS.has(deps.storage, new.to_string()) {
+        soft_validate_remote_chain_addr(deps.api, &new)?;
+    } else {
+        deps.api.addr_validate(&new)?;
+    }
+
+    let mut split = SPLIT_CONFIG_MAP
+        .may_load(deps.storage, denom.to_string())?
+        .ok_or_else(|| StdError::not_found(format!("split for denom {denom:?}")))?;
+
+    let status = fix_split_receiver(
+        deps.storage,
+        &mut split,
+        denom.to_string(),
+        old,
+        new,
+        &env.block,
+    )?;
+    if let SplitReceiverFixStatus::Applied(_) = status {
+        SPLIT_CONFIG_MAP.save(deps.storage, denom, &split)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "try_fix_split_receiver")
+        .add_event(status.to_event()))
+}
+
+/// validates splits replacing the current ones like on instantiation.
+/// they also have to keep covering the expected amounts of the completion config.
+fn validate_replacement_splits(
+    deps: Deps<NeutronQuery>,
+    splits: &BTreeMap<String, SplitConfig>,
+    remote_receivers: &BTreeSet<String>,
+) -> Result<(), ContractError> {
+    ensure!(!splits.is_empty(), ContractError::SplitMisconfig {});
+
+    let validate_denoms = VALIDATE_DENOMS.may_load(deps.storage)?.unwrap_or_default();
+    for (denom, split) in splits {
+        if validate_denoms {
+            ensure_denom_exists(&deps.querier, denom)?;
+        }
+        split.validate_shares_and_receivers(deps.api, remote_receivers)?;
+    }
+    if let Some(completion_config) = COMPLETION_CONFIG.may_load(deps.storage)? {
+        for coin in completion_config.expected_amounts {
+            ensure!(
+                splits.contains_key(&coin.denom),
+                ContractError::CompletionConfigError(coin.denom)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// replaces the splits (and the fallback split, if provided) at runtime.
+/// the new splits are validated like on instantiation, and have to keep
+/// covering the expected amounts of the completion config.
+fn try_update_splits(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    splits: BTreeMap<String, SplitConfig>,
+    fallback: Option<SplitConfig>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let op_admin = OP_ADMIN.may_load(deps.storage)?;
+    ensure!(
+        Some(&info.sender) == op_admin.as_ref(),
+        ContractError::Unauthorized {}
+    );
+
+    let remote_receivers = query_remote_receivers(deps.storage)?;
+    validate_replacement_splits(deps.as_ref(), &splits, &remote_receivers)?;
+
+    // fixes scheduled against the previous splits no longer apply
+    SPLIT_CONFIG_MAP.clear(deps.storage);
+    PENDING_SPLIT_RECEIVER_FIXES.clear(deps.storage);
+    for (denom, split) in &splits {
+        SPLIT_CONFIG_MAP.save(deps.storage, denom.to_string(), split)?;
+    }
+
+    let mut event = get_splits_updated_event(&info.sender, &splits);
+    if let Some(split) = fallback {
+        split.validate_shares_and_receivers(deps.api, &remote_receivers)?;
+        FALLBACK_SPLIT.save(deps.storage, &split)?;
+        event = event.add_attributes(vec![split.get_response_attribute("fallback".to_string())]);
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "try_update_splits")
+        .add_event(event))
+}
+
+/// splits the part of the balance that is not yet accrued by any receiver.
+/// entitlements below the min amount of their receiver keep accruing
+/// in the contract until they reach it, unless they get flushed.
+fn get_accrued_distributions(
+    storage: &mut dyn Storage,
+    split: &SplitConfig,
+    balance: Coin,
+    flush: bool,
+) -> StdResult<Vec<(String, Coin)>> {
+    let mut accrued = ACCRUED_DISTRIBUTIONS
+        .may_load(storage, balance.denom.to_string())?
+        .unwrap_or_default();
+    let accrued_total = accrued
+        .values()
+        .try_fold(Uint128::zero(), |acc, amt| acc.checked_add(*amt))?;
+
+    let distributions = split.accrue_split_amounts(
+        balance.amount.saturating_sub(accrued_total),
+        &mut accrued,
+        flush,
+    )?;
+
+    if accrued.is_empty() {
+        ACCRUED_DISTRIBUTIONS.remove(storage, balance.denom);
+    } else {
+        ACCRUED_DISTRIBUTIONS.save(storage, balance.denom.to_string(), &accrued)?;
+    }
+
+    Ok(distributions
+        .into_iter()
+        .map(|(receiver, amount)| {
+            (
+                receiver,
+                Coin {
+                    denom: balance.denom.to_string(),
+                    amount,
+                },
+            )
+        })
+        .collect())
+}
+
+/// pays out the distributions to their receivers. native receivers get
+/// bank sends, batched into a single message, while ibc receivers get
+/// ibc transfers with the fees paid by the splitter.
+fn get_payout_messages(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    distributions: Vec<(String, Coin)>,
+) -> StdResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let mut payout_messages: Vec<CosmosMsg<NeutronMsg>> = vec![];
+    let ibc_receivers = IBC_RECEIVERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<BTreeMap<String, IbcReceiverConfig>>>()?;
+    // the fee is only queried if there are ibc transfers to be made
+    let min_ibc_fee = if distributions
+        .iter()
+        .any(|(receiver, _)| ibc_receivers.contains_key(receiver))
+    {
+        Some(query_ibc_fee(deps.querier)?)
+    } else {
+        None
+    };
+
+    for (receiver, coin) in distributions {
+        match (ibc_receivers.get(&receiver), &min_ibc_fee) {
+            (Some(ibc_receiver_config), Some(min_ibc_fee)) => {
+                let mut transfer_messages = ibc_receiver_config
+                    .to_destination_config(&receiver)
+                    .get_ibc_transfer_messages_for_coins(
+                        vec![coin],
+                        env.block.time,
+                        env.contract.address.to_string(),
+                        min_ibc_fee,
+                    )?;
+                payout_messages.append(&mut transfer_messages);
+            }
+            _ => payout_messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: receiver,
+                amount: vec![coin],
+            })),
+        }
+    }
+
+    // sends to the different receivers are batched into a single message
+    consolidate_bank_sends(env.contract.address.as_str(), payout_messages)
+}
+
+/// addresses of the split receivers on remote chains
+fn query_remote_receivers(storage: &dyn Storage) -> StdResult<BTreeSet<String>> {
+    IBC_RECEIVERS
+        .keys(storage, None, None, Order::Ascending)
+        .collect()
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::ClockAddress {} => Ok(to_json_binary(&CLOCK_ADDRESS.may_load(deps.storage)?)?),
         QueryMsg::DenomSplit { denom } => Ok(to_json_binary(&query_split(deps, denom)?)?),
         QueryMsg::Splits {} => Ok(to_json_binary(&query_all_splits(deps)?)?),
         QueryMsg::FallbackSplit {} => Ok(to_json_binary(&FALLBACK_SPLIT.may_load(deps.storage)?)?),
+        QueryMsg::EmergencyCommittee {} => Ok(to_json_binary(
+            &EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?,
+        )?),
+        QueryMsg::OpAdmin {} => Ok(to_json_binary(&OP_ADMIN.may_load(deps.storage)?)?),
+        QueryMsg::IbcReceivers {} => Ok(to_json_binary(
+            &IBC_RECEIVERS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<BTreeMap<String, IbcReceiverConfig>>>()?,
+        )?),
+        QueryMsg::AccruedDistributions { denom } => Ok(to_json_binary(
+            &ACCRUED_DISTRIBUTIONS
+                .may_load(deps.storage, denom)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::PendingSplitReceiverFix { denom } => Ok(to_json_binary(
+            &PENDING_SPLIT_RECEIVER_FIXES.may_load(deps.storage, denom)?,
+        )?),
+        QueryMsg::Decommissioned {} => Ok(to_json_binary(
+            &DECOMMISSIONED.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::CompletionConfig {} => {
+            Ok(to_json_binary(&COMPLETION_CONFIG.may_load(deps.storage)?)?)
+        }
+        QueryMsg::ReceivedAmounts {} => Ok(to_json_binary(
+            &RECEIVED_AMOUNTS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<BTreeMap<String, Uint128>>>()?,
+        )?),
+        QueryMsg::Completed {} => Ok(to_json_binary(&is_completed(deps.storage)?)?),
         QueryMsg::DepositAddress {} => Ok(to_json_binary(&Some(env.contract.address))?),
+        QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
     }
 }
 
-pub fn query_all_splits(deps: Deps) -> Result<Vec<(String, SplitConfig)>, StdError> {
+pub fn query_all_splits(deps: Deps<NeutronQuery>) -> Result<Vec<(String, SplitConfig)>, StdError> {
     let mut splits: Vec<(String, SplitConfig)> = vec![];
 
     for entry in SPLIT_CONFIG_MAP.range(deps.storage, None, None, Order::Ascending) {
@@ -151,7 +573,7 @@ pub fn query_all_splits(deps: Deps) -> Result<Vec<(String, SplitConfig)>, StdErr
     Ok(splits)
 }
 
-pub fn query_split(deps: Deps, denom: String) -> Result<SplitConfig, StdError> {
+pub fn query_split(deps: Deps<NeutronQuery>, denom: String) -> Result<SplitConfig, StdError> {
     for entry in SPLIT_CONFIG_MAP.range(deps.storage, None, None, Order::Ascending) {
         let (entry_denom, config) = entry?;
         if entry_denom == denom {
@@ -161,41 +583,58 @@ pub fn query_split(deps: Deps, denom: String) -> Result<SplitConfig, StdError> {
 
     Ok(SplitConfig {
         receivers: BTreeMap::new(),
+        rounding: Default::default(),
+        min_amounts: Default::default(),
     })
 }
 
+/// updates the config, either via the owner or via a migration
+fn update_config(
+    deps: DepsMut<NeutronQuery>,
+    clock_addr: Option<String>,
+    splits: Option<BTreeMap<String, SplitConfig>>,
+    fallback_split: Option<SplitConfig>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let mut resp = Response::default().add_attribute("method", "update_config");
+
+    if let Some(clock_addr) = clock_addr {
+        CLOCK_ADDRESS.save(deps.storage, &deps.api.addr_validate(&clock_addr)?)?;
+        resp = resp.add_attribute("clock_addr", clock_addr);
+    }
+
+    let remote_receivers = query_remote_receivers(deps.storage)?;
+    if let Some(splits) = splits {
+        validate_replacement_splits(deps.as_ref(), &splits, &remote_receivers)?;
+        // clear all current split configs (along with the fixes scheduled
+        // against them) before storing new values
+        SPLIT_CONFIG_MAP.clear(deps.storage);
+        PENDING_SPLIT_RECEIVER_FIXES.clear(deps.storage);
+        for (denom, split) in splits {
+            SPLIT_CONFIG_MAP.save(deps.storage, denom.to_string(), &split)?;
+        }
+    }
+
+    if let Some(split) = fallback_split {
+        split.validate_shares_and_receivers(deps.api, &remote_receivers)?;
+        FALLBACK_SPLIT.save(deps.storage, &split)?;
+        resp = resp.add_attributes(vec![split.get_response_attribute("fallback".to_string())]);
+    }
+
+    Ok(resp)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, StdError> {
+pub fn migrate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    msg: MigrateMsg,
+) -> Result<Response<NeutronMsg>, ContractError> {
     match msg {
         MigrateMsg::UpdateConfig {
             clock_addr,
             splits,
             fallback_split,
-        } => {
-            let mut resp = Response::default().add_attribute("method", "update_config");
-
-            if let Some(clock_addr) = clock_addr {
-                CLOCK_ADDRESS.save(deps.storage, &deps.api.addr_validate(&clock_addr)?)?;
-                resp = resp.add_attribute("clock_addr", clock_addr);
-            }
-
-            if let Some(splits) = splits {
-                // clear all current split configs before storing new values
-                SPLIT_CONFIG_MAP.clear(deps.storage);
-                for (denom, split) in splits {
-                    // we validate each split before storing it
-                    SPLIT_CONFIG_MAP.save(deps.storage, denom.to_string(), &split)?;
-                }
-            }
-
-            if let Some(split) = fallback_split {
-                FALLBACK_SPLIT.save(deps.storage, &split)?;
-                resp =
-                    resp.add_attributes(vec![split.get_response_attribute("fallback".to_string())]);
-            }
-
-            Ok(resp)
-        }
+        } => update_config(deps, clock_addr, splits, fallback_split),
         MigrateMsg::UpdateCodeId { data: _ } => {
             // This is a migrate message to update code id,
             // Data is optional base64 that we can parse to any data we would like in the future
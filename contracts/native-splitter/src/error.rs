@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use covenant_utils::decommission::DecommissionError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -14,4 +15,13 @@ pub enum ContractError {
 
     #[error("unauthorized caller")]
     Unauthorized {},
+
+    #[error("expected amount of {0} has to be non-zero and covered by a split")]
+    CompletionConfigError(String),
+
+    #[error(transparent)]
+    DecommissionError(#[from] DecommissionError),
+
+    #[error(transparent)]
+    OwnershipError(#[from] cw_ownable::OwnershipError),
 }
@@ -1,9 +1,15 @@
 use std::collections::BTreeMap;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{to_json_binary, Addr, Binary, StdResult, WasmMsg};
-use covenant_macros::{clocked, covenant_clock_address, covenant_deposit_address};
-use covenant_utils::{instantiate2_helper::Instantiate2HelperConfig, split::SplitConfig};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, StdResult, Uint128, Uint64, WasmMsg};
+use covenant_macros::{
+    clocked, covenant_clock_address, covenant_decommission, covenant_deposit_address,
+};
+use covenant_utils::{
+    instantiate2_helper::Instantiate2HelperConfig, split::SplitConfig,
+    split_receiver_fix::SplitReceiverFix, DestinationConfig, PacketForwardMiddlewareConfig,
+};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -14,6 +20,67 @@ pub struct InstantiateMsg {
     /// a split for all denoms that are not covered in the
     /// regular `splits` list
     pub fallback_split: Option<SplitConfig>,
+    /// address authorized to fix mis-entered split receivers
+    #[serde(default)]
+    pub emergency_committee: Option<String>,
+    /// address authorized to update the config. without an owner,
+    /// the config can only be updated by migrating the contract.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// once the expected amounts got distributed, the splitter completes
+    /// and dequeues from the clock. without it, the splitter keeps ticking.
+    #[serde(default)]
+    pub completion_config: Option<CompletionConfig>,
+    /// maps split receivers to the way they get paid out.
+    /// receivers that are not listed are native receivers.
+    #[serde(default)]
+    pub receiver_types: BTreeMap<String, ReceiverType>,
+    /// address authorized to update the splits at runtime,
+    /// e.g. a dao or the emergency committee
+    #[serde(default)]
+    pub op_admin: Option<String>,
+    /// if enabled, the split denoms must have a supply on this chain.
+    /// leave disabled for ibc denoms that are yet to be bridged.
+    #[serde(default)]
+    pub validate_denoms: bool,
+}
+
+#[cw_serde]
+pub enum ReceiverType {
+    /// receiver on the local chain, paid out with bank sends
+    Native,
+    /// receiver on a remote chain, paid out with ibc transfers
+    Ibc(IbcReceiverConfig),
+}
+
+#[cw_serde]
+pub struct IbcReceiverConfig {
+    /// channel id from the local chain to the receiver chain
+    pub channel_id: String,
+    /// timeout in seconds
+    pub ibc_transfer_timeout: Uint64,
+    /// pfm configurations for denoms that are unwound via a hop chain
+    #[serde(default)]
+    pub denom_to_pfm_map: BTreeMap<String, PacketForwardMiddlewareConfig>,
+}
+
+impl IbcReceiverConfig {
+    pub fn to_destination_config(&self, receiver: &str) -> DestinationConfig {
+        DestinationConfig {
+            local_to_destination_chain_channel_id: self.channel_id.to_string(),
+            destination_receiver_addr: receiver.to_string(),
+            ibc_transfer_timeout: self.ibc_transfer_timeout,
+            denom_to_pfm_map: self.denom_to_pfm_map.clone(),
+            memo_template: None,
+            wasm_hook: None,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct CompletionConfig {
+    /// total amounts of the split denoms the splitter expects to receive
+    pub expected_amounts: Vec<Coin>,
 }
 
 impl InstantiateMsg {
@@ -35,13 +102,39 @@ impl InstantiateMsg {
 }
 
 #[clocked]
+#[covenant_decommission]
+#[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
-    DistributeFallback { denoms: Vec<String> },
+    DistributeFallback {
+        denoms: Vec<String>,
+    },
+    /// update the config without a migration. only the owner may
+    /// submit updates.
+    UpdateConfig {
+        clock_addr: Option<String>,
+        fallback_split: Option<SplitConfig>,
+        splits: Option<BTreeMap<String, SplitConfig>>,
+    },
+    /// replace a mis-entered receiver of the split of the denom.
+    /// only the emergency committee may submit fixes. the first submission
+    /// schedules the fix, and resubmitting it after a 24h timelock applies it.
+    FixSplitReceiver {
+        denom: String,
+        old: String,
+        new: String,
+    },
+    /// replace the splits, and the fallback split if provided.
+    /// only the op admin may submit updates.
+    UpdateSplits {
+        splits: BTreeMap<String, SplitConfig>,
+        fallback: Option<SplitConfig>,
+    },
 }
 
 #[covenant_clock_address]
 #[covenant_deposit_address]
+#[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -51,6 +144,31 @@ pub enum QueryMsg {
     Splits {},
     #[returns(SplitConfig)]
     FallbackSplit {},
+    #[returns(Option<Addr>)]
+    EmergencyCommittee {},
+    #[returns(Option<Addr>)]
+    OpAdmin {},
+    /// receivers paid out with ibc transfers, along with their configs
+    #[returns(BTreeMap<String, IbcReceiverConfig>)]
+    IbcReceivers {},
+    /// amounts of the denom accrued by the receivers below
+    /// their split min amounts
+    #[returns(BTreeMap<String, Uint128>)]
+    AccruedDistributions { denom: String },
+    /// split receiver fix scheduled for the denom
+    #[returns(Option<SplitReceiverFix>)]
+    PendingSplitReceiverFix { denom: String },
+    /// whether the splitter was decommissioned
+    #[returns(bool)]
+    Decommissioned {},
+    #[returns(Option<CompletionConfig>)]
+    CompletionConfig {},
+    /// amounts of the expected denoms received so far
+    #[returns(BTreeMap<String, Uint128>)]
+    ReceivedAmounts {},
+    /// whether the splitter distributed all expected amounts
+    #[returns(bool)]
+    Completed {},
 }
 
 #[cw_serde]
@@ -1,7 +1,11 @@
-use cosmwasm_std::Addr;
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Addr, Uint128};
 use covenant_utils::split::SplitConfig;
 use cw_storage_plus::{Item, Map};
 
+use crate::msg::{CompletionConfig, IbcReceiverConfig};
+
 /// clock module address to verify the sender of incoming ticks
 pub const CLOCK_ADDRESS: Item<Addr> = Item::new("clock_address");
 
@@ -10,3 +14,32 @@ pub const SPLIT_CONFIG_MAP: Map<String, SplitConfig> = Map::new("split_config");
 
 /// split for all denoms that are not explicitly defined in SPLIT_CONFIG_MAP
 pub const FALLBACK_SPLIT: Item<SplitConfig> = Item::new("fallback_split");
+
+/// split receivers paid out with ibc transfers, keyed by receiver address
+pub const IBC_RECEIVERS: Map<String, IbcReceiverConfig> = Map::new("ibc_receivers");
+
+/// address authorized to fix mis-entered split receivers
+pub const EMERGENCY_COMMITTEE_ADDR: Item<Addr> = Item::new("emergency_committee_addr");
+
+/// address authorized to update the splits at runtime
+pub const OP_ADMIN: Item<Addr> = Item::new("op_admin");
+
+/// amounts accrued by the receivers below their split min amounts,
+/// keyed by denom
+pub const ACCRUED_DISTRIBUTIONS: Map<String, BTreeMap<String, Uint128>> =
+    Map::new("accrued_distributions");
+
+/// set if the split denoms have to exist on this chain
+pub const VALIDATE_DENOMS: Item<bool> = Item::new("validate_denoms");
+
+/// set once the splitter got decommissioned
+pub const DECOMMISSIONED: Item<bool> = Item::new("decommissioned");
+
+/// amounts the splitter has to receive before completing
+pub const COMPLETION_CONFIG: Item<CompletionConfig> = Item::new("completion_config");
+
+/// amounts of the expected denoms received so far, keyed by denom
+pub const RECEIVED_AMOUNTS: Map<String, Uint128> = Map::new("received_amounts");
+
+/// set once the splitter distributed all expected amounts
+pub const COMPLETED: Item<bool> = Item::new("completed");
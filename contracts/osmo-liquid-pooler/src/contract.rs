@@ -3,41 +3,52 @@ use std::collections::HashMap;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, to_json_binary, to_json_string, Attribute, Binary, Coin, CosmosMsg, Decimal, Env,
-    Fraction, IbcTimeout, MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+    ensure, to_json_binary, to_json_string, Attribute, BankMsg, Binary, Coin, CosmosMsg, Decimal,
+    Env, Fraction, IbcTimeout, MessageInfo, Order, Response, StdError, StdResult, Uint128, Uint64,
+    WasmMsg,
 };
+use covenant_oracle::query_price;
 use covenant_utils::{
-    clock::{enqueue_msg, verify_clock},
+    clock::{enqueue_msg, increment_tick_nonce, verify_clock},
+    deadline::Deadline,
+    neutron::query_ibc_fee,
     polytone::get_polytone_execute_msg_binary,
-    withdraw_lp_helper::WithdrawLPMsgs,
+    pool_ownership::validate_max_pool_ownership,
+    withdraw_lp_helper::{WithdrawLPMsgs, EMERGENCY_COMMITTEE_ADDR},
     ForwardMetadata, PacketMetadata,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 use neutron_sdk::{
     bindings::{
         msg::{IbcFee, NeutronMsg},
         query::NeutronQuery,
     },
-    query::min_ibc_fee::MinIbcFeeResponse,
     sudo::msg::RequestPacketTimeoutHeight,
     NeutronResult,
 };
+use osmosis_std::types::osmosis::{
+    lockup::{MsgBeginUnlocking, MsgLockTokens},
+    superfluid::{MsgLockAndSuperfluidDelegate, MsgSuperfluidUnbondLock},
+};
 use polytone::callbacks::CallbackRequest;
-use valence_outpost_osmo_liquid_pooler::msg::OutpostWithdrawLiquidityConfig;
+use valence_outpost_osmo_liquid_pooler::{
+    concentrated_liquidity::get_transfer_positions_msg, msg::OutpostWithdrawLiquidityConfig,
+};
 
 use crate::{
     error::ContractError,
     msg::{
-        ContractState, ExecuteMsg, IbcConfig, InstantiateMsg, LiquidityProvisionConfig, MigrateMsg,
-        PartyChainInfo, QueryMsg,
+        ContractState, ExecuteMsg, IbcConfig, InstantiateMsg, LiquidityProvisionConfig,
+        LockupConfig, LpLock, MigrateMsg, PartyChainInfo, PolytoneCallbackRecord, QueryMsg,
     },
     polytone_handlers::{
         get_ibc_pfm_withdraw_coin_message, get_ibc_withdraw_coin_message,
         get_note_execute_neutron_msg, get_proxy_query_balances_message, try_handle_callback,
     },
     state::{
-        HOLDER_ADDRESS, IBC_CONFIG, LIQUIDITY_PROVISIONING_CONFIG, NOTE_ADDRESS,
-        POLYTONE_CALLBACKS, PROXY_ADDRESS,
+        FORCED_WITHDRAWAL, HOLDER_ADDRESS, IBC_CONFIG, LIQUIDITY_PROVISIONING_CONFIG, LP_LOCK,
+        NOTE_ADDRESS, POLYTONE_CALLBACKS, POLYTONE_CALLBACK_HISTORY, POSITION_ID, PROXY_ADDRESS,
     },
 };
 
@@ -50,6 +61,8 @@ pub(crate) const PROVIDE_LIQUIDITY_CALLBACK_ID: u8 = 1;
 pub(crate) const PROXY_BALANCES_QUERY_CALLBACK_ID: u8 = 2;
 pub(crate) const CREATE_PROXY_CALLBACK_ID: u8 = 3;
 pub(crate) const WITHDRAW_LIQUIDITY_CALLBACK_ID: u8 = 4;
+pub(crate) const LOCK_SHARES_CALLBACK_ID: u8 = 5;
+pub(crate) const UNLOCK_SHARES_CALLBACK_ID: u8 = 6;
 
 type ExecuteDeps<'a> = cosmwasm_std::DepsMut<'a, NeutronQuery>;
 type QueryDeps<'a> = cosmwasm_std::Deps<'a, NeutronQuery>;
@@ -57,7 +70,7 @@ type QueryDeps<'a> = cosmwasm_std::Deps<'a, NeutronQuery>;
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: ExecuteDeps,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -67,6 +80,29 @@ pub fn instantiate(
     let clock_addr = deps.api.addr_validate(&msg.clock_address)?;
     let holder_addr = deps.api.addr_validate(&msg.holder_address)?;
     let note_addr = deps.api.addr_validate(&msg.note_address)?;
+    msg.single_side_lp_limits.validate()?;
+    msg.funding_duration.validate(&env.block)?;
+    validate_max_pool_ownership(msg.max_pool_ownership)?;
+    if let Some(lockup_config) = &msg.lockup_config {
+        ensure!(
+            !lockup_config.duration.is_zero(),
+            StdError::generic_err("lock duration must be non-zero")
+        );
+    }
+    if let Some(tick_range) = &msg.tick_range {
+        tick_range
+            .validate()
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+    }
+
+    if let Some(guard) = &msg.oracle_price_guard {
+        guard.validate(deps.api)?;
+    }
+
+    if let Some(addr) = &msg.emergency_committee {
+        let committee_addr = deps.api.addr_validate(addr)?;
+        EMERGENCY_COMMITTEE_ADDR.save(deps.storage, &committee_addr)?;
+    }
 
     // contract starts at Instantiated state
     CONTRACT_STATE.save(deps.storage, &ContractState::Instantiated)?;
@@ -89,6 +125,11 @@ pub fn instantiate(
         pool_price_config: msg.pool_price_config,
         funding_duration: msg.funding_duration,
         single_side_lp_limits: msg.single_side_lp_limits,
+        max_pool_ownership: msg.max_pool_ownership,
+        join_mode: msg.join_mode,
+        tick_range: msg.tick_range,
+        lockup_config: msg.lockup_config,
+        oracle_price_guard: msg.oracle_price_guard,
     };
     LIQUIDITY_PROVISIONING_CONFIG.save(deps.storage, &lp_config)?;
 
@@ -119,9 +160,95 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
     match msg {
-        ExecuteMsg::Tick {} => try_tick(deps, env, info),
+        ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
+            Ok(try_tick(deps, env, info)?.add_attribute("tick_nonce", tick_nonce.to_string()))
+        }
         ExecuteMsg::Callback(callback_msg) => try_handle_callback(env, deps, info, callback_msg),
         ExecuteMsg::Withdraw { percentage } => try_initiate_withdrawal(deps, info, percentage),
+        ExecuteMsg::ForceWithdrawal {} => try_force_withdrawal(deps, info),
+        ExecuteMsg::ReturnIdleFunds {} => try_return_idle_funds(deps, env, info),
+    }
+}
+
+/// sends the party denoms pulled back from the proxy to the holder
+fn try_return_idle_funds(
+    deps: ExecuteDeps,
+    env: Env,
+    info: MessageInfo,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let holder_addr = HOLDER_ADDRESS.load(deps.storage)?;
+    ensure!(
+        info.sender == holder_addr,
+        ContractError::NotHolder {}.to_neutron_std()
+    );
+
+    let idle_balances = query_idle_balances(deps.as_ref(), &env)?;
+    ensure!(
+        !idle_balances.is_empty(),
+        ContractError::NoIdleFunds {}.to_neutron_std()
+    );
+
+    Ok(Response::default()
+        .add_attribute("method", "try_return_idle_funds")
+        .add_attribute("idle_balances", to_json_string(&idle_balances)?)
+        .add_message(BankMsg::Send {
+            to_address: holder_addr.to_string(),
+            amount: idle_balances,
+        }))
+}
+
+/// non-zero balances of the party denoms held by this contract while
+/// `Active`. in any other state they are yet to be delivered to the
+/// proxy, or are being distributed to the holder.
+fn query_idle_balances(deps: QueryDeps, env: &Env) -> StdResult<Vec<Coin>> {
+    if CONTRACT_STATE.load(deps.storage)? != ContractState::Active {
+        return Ok(vec![]);
+    }
+
+    let lp_config = LIQUIDITY_PROVISIONING_CONFIG.load(deps.storage)?;
+    let mut idle_balances = vec![];
+    for denom in [
+        &lp_config.party_1_denom_info.local_denom,
+        &lp_config.party_2_denom_info.local_denom,
+    ] {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), denom)?;
+        if !balance.amount.is_zero() {
+            idle_balances.push(balance);
+        }
+    }
+    Ok(idle_balances)
+}
+
+/// lifts the exit price guard for the pending withdrawal. meant to be used
+/// by the emergency committee in case the pool price stays outside of the
+/// acceptable range and the withdrawal keeps on reverting.
+fn try_force_withdrawal(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+) -> NeutronResult<Response<NeutronMsg>> {
+    match EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)? {
+        Some(committee_addr) => ensure!(
+            info.sender == committee_addr,
+            ContractError::NotEmergencyCommittee {}.to_neutron_std()
+        ),
+        None => return Err(ContractError::NotEmergencyCommittee {}.to_neutron_std()),
+    };
+
+    match CONTRACT_STATE.load(deps.storage)? {
+        ContractState::PendingWithdrawal { .. } => {
+            FORCED_WITHDRAWAL.save(deps.storage, &true)?;
+            Ok(Response::default()
+                .add_attribute("method", "try_force_withdrawal")
+                .add_attribute("forced_withdrawal", "true"))
+        }
+        state => Err(ContractError::StateMachineError(format!(
+            "expected pending withdrawal state, got {:?}",
+            state
+        ))
+        .to_neutron_std()),
     }
 }
 
@@ -147,6 +274,9 @@ fn try_initiate_withdrawal(
         .to_neutron_std()
     );
 
+    // every withdrawal starts out subject to the exit price guard
+    FORCED_WITHDRAWAL.remove(deps.storage);
+
     // we advance the contract state to `PendingWithdrawal` and force latest balances sync
     CONTRACT_STATE.save(
         deps.storage,
@@ -230,6 +360,12 @@ pub fn try_withdraw(
     let note_address = NOTE_ADDRESS.load(deps.storage)?;
     let ibc_config = IBC_CONFIG.load(deps.storage)?;
 
+    // a concentrated liquidity position held by the proxy is handed
+    // over to the outpost, which withdraws the share of its liquidity
+    if let Some(position_id) = POSITION_ID.may_load(deps.storage)? {
+        return try_withdraw_position(deps, env, withdraw_share, position_id, lp_config);
+    }
+
     // if there are 0 available lp token balances, we attempt to
     // withdraw the party denoms directly.
     if lp_bal.amount.is_zero() {
@@ -241,12 +377,30 @@ pub fn try_withdraw(
         .checked_multiply_ratio(withdraw_share.numerator(), withdraw_share.denominator())
         .map_err(|e| ContractError::CheckedMultiplyError(e).to_neutron_std())?;
 
+    // unless the emergency committee forced this withdrawal,
+    // the exit is bound by our pool price expectations
+    let exit_price_guard = if FORCED_WITHDRAWAL
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        None
+    } else {
+        Some(lp_config.get_outpost_exit_price_guard())
+    };
+
     let exit_pool_message: CosmosMsg = WasmMsg::Execute {
         contract_addr: lp_config.outpost.to_string(),
         msg: to_json_binary(
             &valence_outpost_osmo_liquid_pooler::msg::ExecuteMsg::WithdrawLiquidity {
                 config: OutpostWithdrawLiquidityConfig {
                     pool_id: lp_config.pool_id,
+                    exit_price_guard,
+                    // the redeemed amount is paid in full, and the
+                    // exit is bound by the simulated amounts
+                    share_amount: None,
+                    min_tokens_out: None,
+                    position_id: None,
+                    position_share: None,
                 },
             },
         )?,
@@ -282,6 +436,79 @@ pub fn try_withdraw(
     Ok(Response::default().add_messages(vec![exit_pool_note_msg]))
 }
 
+/// withdraws the share of the concentrated liquidity position held by
+/// the proxy. the position is transferred to the outpost, which returns
+/// the withdrawn funds (and the remaining position, if any) to the proxy.
+fn try_withdraw_position(
+    deps: ExecuteDeps,
+    env: Env,
+    withdraw_share: Decimal,
+    position_id: Uint64,
+    lp_config: LiquidityProvisionConfig,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let note_address = NOTE_ADDRESS.load(deps.storage)?;
+    let ibc_config = IBC_CONFIG.load(deps.storage)?;
+    let proxy_address = PROXY_ADDRESS.load(deps.storage)?;
+
+    let exit_price_guard = if FORCED_WITHDRAWAL
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        None
+    } else {
+        Some(lp_config.get_outpost_exit_price_guard())
+    };
+
+    let withdraw_position_message: CosmosMsg = WasmMsg::Execute {
+        contract_addr: lp_config.outpost.to_string(),
+        msg: to_json_binary(
+            &valence_outpost_osmo_liquid_pooler::msg::ExecuteMsg::WithdrawLiquidity {
+                config: OutpostWithdrawLiquidityConfig {
+                    pool_id: lp_config.pool_id,
+                    exit_price_guard,
+                    share_amount: None,
+                    min_tokens_out: None,
+                    position_id: Some(position_id),
+                    position_share: Some(withdraw_share),
+                },
+            },
+        )?,
+        funds: vec![],
+    }
+    .into();
+
+    POLYTONE_CALLBACKS.save(
+        deps.storage,
+        format!(
+            "neutron_try_withdraw_position : {:?}",
+            env.block.height.to_string()
+        ),
+        &to_json_string(&withdraw_position_message)?,
+    )?;
+
+    let withdraw_position_note_msg = get_note_execute_neutron_msg(
+        vec![
+            get_transfer_positions_msg(
+                vec![position_id.u64()],
+                proxy_address.to_string(),
+                lp_config.outpost.to_string(),
+            ),
+            withdraw_position_message,
+        ],
+        ibc_config.osmo_ibc_timeout,
+        note_address,
+        Some(CallbackRequest {
+            receiver: env.contract.address.to_string(),
+            msg: to_json_binary(&WITHDRAW_LIQUIDITY_CALLBACK_ID)?,
+        }),
+    )?;
+
+    Ok(Response::default()
+        .add_message(withdraw_position_note_msg)
+        .add_attribute("method", "try_withdraw_position")
+        .add_attribute("position_id", position_id.to_string()))
+}
+
 /// attempts to advance the state machine. performs `info.sender` validation.
 fn try_tick(deps: ExecuteDeps, env: Env, info: MessageInfo) -> NeutronResult<Response<NeutronMsg>> {
     // Verify caller is the clock
@@ -307,8 +534,41 @@ fn try_tick(deps: ExecuteDeps, env: Env, info: MessageInfo) -> NeutronResult<Res
                 try_provide_liquidity(deps, env)
             }
         }
-        ContractState::Active => try_sync_proxy_balances(deps, env),
+        ContractState::Active => {
+            // if configured, the gamm shares held by the proxy are locked
+            let lp_config = LIQUIDITY_PROVISIONING_CONFIG.load(deps.storage)?;
+            match (
+                &lp_config.lockup_config,
+                lp_config.get_lp_token_proxy_balance(),
+            ) {
+                (Some(lockup_config), Some(lp_bal))
+                    if !lp_bal.amount.is_zero() && LP_LOCK.may_load(deps.storage)?.is_none() =>
+                {
+                    try_lock_shares(deps, env, lockup_config.clone(), lp_bal.clone())
+                }
+                _ => try_sync_proxy_balances(deps, env),
+            }
+        }
         ContractState::PendingWithdrawal { share } => {
+            // locked shares have to be released before withdrawing
+            match LP_LOCK.may_load(deps.storage)? {
+                Some(LpLock::Locked { lock_id }) => return try_begin_unlocking(deps, env, lock_id),
+                Some(LpLock::Unlocking {
+                    unlock_expiration, ..
+                }) => {
+                    if !unlock_expiration.is_expired(&env.block) {
+                        return Ok(Response::default()
+                            .add_attribute("method", "tick")
+                            .add_attribute("unlock_expiration", unlock_expiration.to_string()));
+                    }
+                    // the shares are released to the proxy, so its
+                    // balances are synced before withdrawing
+                    LP_LOCK.remove(deps.storage);
+                    return try_sync_proxy_balances(deps, env);
+                }
+                None => (),
+            }
+
             let lp_config = LIQUIDITY_PROVISIONING_CONFIG.load(deps.storage)?;
             match lp_config.get_proxy_balances() {
                 Some((party_1_bal, party_2_bal, lp_bal)) => try_withdraw(
@@ -325,6 +585,108 @@ fn try_tick(deps: ExecuteDeps, env: Env, info: MessageInfo) -> NeutronResult<Res
     }
 }
 
+/// locks the gamm shares held by the proxy. if a superfluid validator
+/// is configured, the locked shares are also delegated to it.
+/// the lock is recorded on the polytone callback.
+fn try_lock_shares(
+    deps: ExecuteDeps,
+    env: Env,
+    lockup_config: LockupConfig,
+    lp_bal: Coin,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let note_address = NOTE_ADDRESS.load(deps.storage)?;
+    let ibc_config = IBC_CONFIG.load(deps.storage)?;
+    let proxy_address = PROXY_ADDRESS.load(deps.storage)?;
+
+    let lock_msg: CosmosMsg = match lockup_config.superfluid_validator {
+        Some(val_addr) => MsgLockAndSuperfluidDelegate {
+            sender: proxy_address,
+            coins: vec![lp_bal.into()],
+            val_addr,
+        }
+        .into(),
+        None => MsgLockTokens {
+            owner: proxy_address,
+            duration: Some(osmosis_std::shim::Duration {
+                seconds: lockup_config.duration.u64() as i64,
+                nanos: 0,
+            }),
+            coins: vec![lp_bal.into()],
+        }
+        .into(),
+    };
+
+    let note_lock_msg = get_note_execute_neutron_msg(
+        vec![lock_msg],
+        ibc_config.osmo_ibc_timeout,
+        note_address,
+        Some(CallbackRequest {
+            receiver: env.contract.address.to_string(),
+            msg: to_json_binary(&LOCK_SHARES_CALLBACK_ID)?,
+        }),
+    )?;
+
+    // the proxy balances are synced again before
+    // the shares are considered for locking
+    LIQUIDITY_PROVISIONING_CONFIG.update(deps.storage, |mut lp_config| -> StdResult<_> {
+        lp_config.reset_latest_proxy_balances();
+        Ok(lp_config)
+    })?;
+
+    Ok(Response::default()
+        .add_message(note_lock_msg)
+        .add_attribute("method", "try_lock_shares"))
+}
+
+/// begins unlocking the gamm shares lock of the proxy. superfluid
+/// delegated locks are undelegated first. the lock is recorded as
+/// unlocking on the polytone callback.
+fn try_begin_unlocking(
+    deps: ExecuteDeps,
+    env: Env,
+    lock_id: Uint64,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let note_address = NOTE_ADDRESS.load(deps.storage)?;
+    let ibc_config = IBC_CONFIG.load(deps.storage)?;
+    let proxy_address = PROXY_ADDRESS.load(deps.storage)?;
+    let lp_config = LIQUIDITY_PROVISIONING_CONFIG.load(deps.storage)?;
+
+    let superfluid = lp_config
+        .lockup_config
+        .and_then(|config| config.superfluid_validator)
+        .is_some();
+    let unlock_msg: CosmosMsg = if superfluid {
+        MsgSuperfluidUnbondLock {
+            sender: proxy_address,
+            lock_id: lock_id.u64(),
+        }
+        .into()
+    } else {
+        // no coins passed unlocks the whole lock
+        MsgBeginUnlocking {
+            owner: proxy_address,
+            id: lock_id.u64(),
+            coins: vec![],
+        }
+        .into()
+    };
+
+    let note_unlock_msg = get_note_execute_neutron_msg(
+        vec![unlock_msg],
+        ibc_config.osmo_ibc_timeout,
+        note_address,
+        Some(CallbackRequest {
+            receiver: env.contract.address.to_string(),
+            msg: to_json_binary(&UNLOCK_SHARES_CALLBACK_ID)?,
+        }),
+    )?;
+
+    Ok(Response::default()
+        .add_message(note_unlock_msg)
+        .add_attribute("method", "try_begin_unlocking")
+        .add_attribute("lock_id", lock_id.to_string()))
+}
+
 fn try_distribute(
     deps: ExecuteDeps,
     env: Env,
@@ -372,6 +734,7 @@ fn try_distribute(
     if denom_1_withdrawn && denom_2_withdrawn {
         let holder_addr = HOLDER_ADDRESS.load(deps.storage)?;
         CONTRACT_STATE.save(deps.storage, &ContractState::Active)?;
+        FORCED_WITHDRAWAL.remove(deps.storage);
 
         // submit a Distribute message to the holder,
         // which carries the withdrawn coins along.
@@ -422,6 +785,7 @@ fn try_distribute(
                             receiver: env.contract.address.to_string(),
                             port: forward_metadata.port.to_string(),
                             channel: ibc_config.party_1_chain_info.party_chain_to_neutron_channel,
+                            next: None,
                         }),
                     })?,
                 );
@@ -468,6 +832,7 @@ fn try_distribute(
                             receiver: env.contract.address.to_string(),
                             port: forward_metadata.port.to_string(),
                             channel: ibc_config.party_2_chain_info.party_chain_to_neutron_channel,
+                            next: None,
                         }),
                     })?,
                 );
@@ -580,7 +945,10 @@ fn try_deliver_funds(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Neut
             {
                 // otherwise we advance the state machine and store an
                 // expiration time for the funding period
-                let funding_expiration = lp_config.funding_duration.after(&env.block);
+                let funding_expiration = lp_config
+                    .funding_duration
+                    .clone()
+                    .into_expiration(&env.block);
                 CONTRACT_STATE.save(
                     deps.storage,
                     &ContractState::ProxyFunded { funding_expiration },
@@ -636,14 +1004,14 @@ fn try_fund_proxy(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Neutron
     }
 
     let mut transfer_messages = vec![];
-    let min_ibc_fee: MinIbcFeeResponse = deps.querier.query(&NeutronQuery::MinIbcFee {}.into())?;
+    let min_ibc_fee = query_ibc_fee(deps.querier)?;
     if coin_1_bal.amount > Uint128::zero() {
         transfer_messages.push(get_ibc_transfer_message(
             ibc_config.party_1_chain_info,
             env.clone(),
             coin_1_bal,
             proxy_address.to_string(),
-            &min_ibc_fee.min_fee,
+            &min_ibc_fee.ibc_fee,
         )?);
     }
     if coin_2_bal.amount > Uint128::zero() {
@@ -652,7 +1020,7 @@ fn try_fund_proxy(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Neutron
             env,
             coin_2_bal,
             proxy_address,
-            &min_ibc_fee.min_fee,
+            &min_ibc_fee.ibc_fee,
         )?);
     }
 
@@ -669,9 +1037,37 @@ fn try_provide_liquidity(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<
 
     // we generate a provide_liquidity message for the outpost
     // and wrap it in a note message
-    let outpost_msg = lp_config.get_osmo_outpost_provide_liquidity_message()?;
+    let position_id = POSITION_ID.may_load(deps.storage)?;
+    // if configured, the outpost may only join the pool at
+    // spot prices in line with the oracle
+    let oracle_price = match &lp_config.oracle_price_guard {
+        Some(guard) => Some(query_price(
+            deps.as_ref(),
+            &guard.source,
+            &guard.base,
+            &guard.quote,
+        )?),
+        None => None,
+    };
+    let price_config = lp_config.get_provision_price_config(oracle_price)?;
+    let outpost_msg =
+        lp_config.get_osmo_outpost_provide_liquidity_message(position_id, &price_config)?;
+
+    // a concentrated liquidity position held by the proxy is handed
+    // over to the outpost to be added to, and transferred back along
+    // with the replacing position
+    let mut outpost_msgs = vec![];
+    if let Some(position_id) = position_id {
+        outpost_msgs.push(get_transfer_positions_msg(
+            vec![position_id.u64()],
+            proxy_address.to_string(),
+            lp_config.outpost.to_string(),
+        ));
+    }
+    outpost_msgs.push(outpost_msg);
+
     let note_outpost_liquidity_msg = get_note_execute_neutron_msg(
-        vec![outpost_msg],
+        outpost_msgs,
         ibc_config.osmo_ibc_timeout,
         note_address.clone(),
         Some(CallbackRequest {
@@ -752,6 +1148,7 @@ fn get_ibc_transfer_message(
                     receiver: proxy_address.to_string(),
                     port: forward_metadata.port,
                     channel: forward_metadata.channel,
+                    next: None,
                 }),
             })?,
             fee: ibc_fee.clone(),
@@ -802,11 +1199,39 @@ pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
             Ok(to_json_binary(&vals)?)
         }
+        QueryMsg::CallbackHistory { start_after, limit } => {
+            let callbacks: Vec<PolytoneCallbackRecord> = POLYTONE_CALLBACK_HISTORY
+                .range(
+                    deps.storage,
+                    None,
+                    start_after.map(Bound::exclusive),
+                    Order::Descending,
+                )
+                .take(limit.unwrap_or(10) as usize)
+                .map(|entry| entry.map(|(_, record)| record))
+                .collect::<StdResult<_>>()?;
+            Ok(to_json_binary(&callbacks)?)
+        }
+        QueryMsg::EmergencyCommittee {} => Ok(to_json_binary(
+            &EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?,
+        )?),
+        QueryMsg::LpLock {} => Ok(to_json_binary(&LP_LOCK.may_load(deps.storage)?)?),
+        QueryMsg::PositionId {} => Ok(to_json_binary(&POSITION_ID.may_load(deps.storage)?)?),
+        QueryMsg::IdleBalances {} => Ok(to_json_binary(&query_idle_balances(deps, &env)?)?),
+        QueryMsg::TimeToExpiry {} => {
+            let time_to_expiry = match CONTRACT_STATE.may_load(deps.storage)? {
+                Some(ContractState::ProxyFunded { funding_expiration }) => {
+                    Some(Deadline::from(funding_expiration).time_to_expiry(&env.block))
+                }
+                _ => None,
+            };
+            Ok(to_json_binary(&time_to_expiry)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: ExecuteDeps, _env: Env, msg: MigrateMsg) -> NeutronResult<Response> {
+pub fn migrate(deps: ExecuteDeps, env: Env, msg: MigrateMsg) -> NeutronResult<Response> {
     match msg {
         MigrateMsg::UpdateConfig {
             clock_addr,
@@ -814,6 +1239,7 @@ pub fn migrate(deps: ExecuteDeps, _env: Env, msg: MigrateMsg) -> NeutronResult<R
             note_address,
             ibc_config,
             lp_config,
+            emergency_committee,
         } => {
             let mut response = Response::default().add_attribute("method", "update_config");
 
@@ -839,10 +1265,37 @@ pub fn migrate(deps: ExecuteDeps, _env: Env, msg: MigrateMsg) -> NeutronResult<R
             }
 
             if let Some(config) = *lp_config {
+                config.funding_duration.validate(&env.block)?;
+                if let Some(guard) = &config.oracle_price_guard {
+                    guard.validate(deps.api)?;
+                }
+                // an absolute funding deadline replaces the expiration of
+                // an ongoing funding period. durations only apply to the
+                // funding periods started after the migration.
+                if let (
+                    ContractState::ProxyFunded { .. },
+                    Deadline::Expiration(funding_expiration),
+                ) = (CONTRACT_STATE.load(deps.storage)?, &config.funding_duration)
+                {
+                    CONTRACT_STATE.save(
+                        deps.storage,
+                        &ContractState::ProxyFunded {
+                            funding_expiration: *funding_expiration,
+                        },
+                    )?;
+                    response = response
+                        .add_attribute("funding_expiration", funding_expiration.to_string());
+                }
                 LIQUIDITY_PROVISIONING_CONFIG.save(deps.storage, &config)?;
                 response = response.add_attributes(config.to_response_attributes());
             }
 
+            if let Some(addr) = emergency_committee {
+                let committee_addr = deps.api.addr_validate(&addr)?;
+                EMERGENCY_COMMITTEE_ADDR.save(deps.storage, &committee_addr)?;
+                response = response.add_attribute("emergency_committee", committee_addr);
+            }
+
             Ok(response)
         }
         MigrateMsg::UpdateCodeId { data: _ } => {
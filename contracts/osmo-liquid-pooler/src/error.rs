@@ -39,6 +39,12 @@ pub enum ContractError {
 
     #[error("Only holder can withdraw the position")]
     NotHolder {},
+
+    #[error("Only emergency committee can force withdrawals")]
+    NotEmergencyCommittee {},
+
+    #[error("no idle funds to return")]
+    NoIdleFunds {},
 }
 
 impl ContractError {
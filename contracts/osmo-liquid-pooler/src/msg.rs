@@ -2,19 +2,23 @@ use std::collections::HashMap;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    to_json_binary, Addr, Attribute, Binary, Coin, CosmosMsg, Decimal, StdResult, Uint128, Uint64,
-    WasmMsg,
+    ensure, to_json_binary, Addr, Attribute, Binary, Coin, CosmosMsg, Decimal, StdError, StdResult,
+    Timestamp, Uint128, Uint64, WasmMsg,
 };
 use covenant_macros::{
     clocked, covenant_clock_address, covenant_deposit_address, covenant_lper_withdraw,
 };
+use covenant_oracle::PriceGuard;
 use covenant_utils::{
-    instantiate2_helper::Instantiate2HelperConfig, ForwardMetadata, PoolPriceConfig,
-    SingleSideLpLimits,
+    deadline::{Deadline, TimeToExpiry},
+    instantiate2_helper::Instantiate2HelperConfig,
+    ForwardMetadata, PoolPriceConfig, SingleSideLpLimits,
 };
-use cw_utils::{Duration, Expiration};
+use cw_utils::Expiration;
 use polytone::callbacks::CallbackMessage;
-use valence_outpost_osmo_liquid_pooler::msg::OutpostProvideLiquidityConfig;
+use valence_outpost_osmo_liquid_pooler::msg::{
+    JoinMode, OutpostExitPriceGuard, OutpostProvideLiquidityConfig, TickRange,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -32,8 +36,32 @@ pub struct InstantiateMsg {
     pub lp_token_denom: String,
     pub slippage_tolerance: Option<Decimal>,
     pub pool_price_config: PoolPriceConfig,
-    pub funding_duration: Duration,
+    /// funding period of the proxy. either a duration counted from the
+    /// proxy receiving both party contributions, or an absolute deadline.
+    pub funding_duration: Deadline,
     pub single_side_lp_limits: SingleSideLpLimits,
+    /// address authorized to force exits regardless of the pool price
+    pub emergency_committee: Option<String>,
+    /// highest share of the pool the liquid pooler may own.
+    /// balances beyond it are kept idle.
+    #[serde(default)]
+    pub max_pool_ownership: Option<Decimal>,
+    /// how the outpost joins the pool when both assets are provided
+    #[serde(default)]
+    pub join_mode: JoinMode,
+    /// price range of the position, required for concentrated
+    /// liquidity pools
+    #[serde(default)]
+    pub tick_range: Option<TickRange>,
+    /// if passed, the gamm shares are locked (and optionally
+    /// superfluid delegated) once the funding period expires
+    #[serde(default)]
+    pub lockup_config: Option<LockupConfig>,
+    /// optional guard of the pool spot price against an external price source.
+    /// the guarded price is the price of the second pool asset denominated
+    /// in the first one.
+    #[serde(default)]
+    pub oracle_price_guard: Option<PriceGuard>,
 }
 
 impl InstantiateMsg {
@@ -66,8 +94,25 @@ pub struct OsmosisLiquidPoolerConfig {
     pub osmo_to_neutron_channel_id: String,
     pub party_1_denom_info: PartyDenomInfo,
     pub party_2_denom_info: PartyDenomInfo,
-    pub funding_duration: Duration,
+    pub funding_duration: Deadline,
     pub single_side_lp_limits: SingleSideLpLimits,
+    /// highest share of the pool the liquid pooler may own
+    #[serde(default)]
+    pub max_pool_ownership: Option<Decimal>,
+    /// how the outpost joins the pool when both assets are provided
+    #[serde(default)]
+    pub join_mode: JoinMode,
+    /// price range of the position, required for concentrated
+    /// liquidity pools
+    #[serde(default)]
+    pub tick_range: Option<TickRange>,
+    /// if passed, the gamm shares are locked (and optionally
+    /// superfluid delegated) once the funding period expires
+    #[serde(default)]
+    pub lockup_config: Option<LockupConfig>,
+    /// optional guard of the pool spot price against an external price source
+    #[serde(default)]
+    pub oracle_price_guard: Option<PriceGuard>,
 }
 
 impl OsmosisLiquidPoolerConfig {
@@ -76,6 +121,7 @@ impl OsmosisLiquidPoolerConfig {
         clock_address: String,
         holder_address: String,
         pool_price_config: PoolPriceConfig,
+        emergency_committee: Option<String>,
     ) -> InstantiateMsg {
         InstantiateMsg {
             clock_address,
@@ -92,8 +138,14 @@ impl OsmosisLiquidPoolerConfig {
             lp_token_denom: self.lp_token_denom.to_string(),
             slippage_tolerance: None,
             pool_price_config,
-            funding_duration: self.funding_duration,
+            funding_duration: self.funding_duration.clone(),
             single_side_lp_limits: self.single_side_lp_limits.clone(),
+            emergency_committee,
+            max_pool_ownership: self.max_pool_ownership,
+            join_mode: self.join_mode.clone(),
+            tick_range: self.tick_range.clone(),
+            lockup_config: self.lockup_config.clone(),
+            oracle_price_guard: self.oracle_price_guard.clone(),
         }
     }
 }
@@ -108,8 +160,41 @@ pub struct LiquidityProvisionConfig {
     pub lp_token_denom: String,
     pub slippage_tolerance: Option<Decimal>,
     pub pool_price_config: PoolPriceConfig,
-    pub funding_duration: Duration,
+    pub funding_duration: Deadline,
     pub single_side_lp_limits: SingleSideLpLimits,
+    #[serde(default)]
+    pub max_pool_ownership: Option<Decimal>,
+    #[serde(default)]
+    pub join_mode: JoinMode,
+    #[serde(default)]
+    pub tick_range: Option<TickRange>,
+    #[serde(default)]
+    pub lockup_config: Option<LockupConfig>,
+    #[serde(default)]
+    pub oracle_price_guard: Option<PriceGuard>,
+}
+
+/// lockup of the gamm shares held by the proxy
+#[cw_serde]
+pub struct LockupConfig {
+    /// lock duration in seconds. superfluid delegated locks must
+    /// be locked for the unbonding period of osmosis.
+    pub duration: Uint64,
+    /// validator to superfluid delegate the locked shares to
+    pub superfluid_validator: Option<String>,
+}
+
+/// gamm shares lock of the proxy
+#[cw_serde]
+pub enum LpLock {
+    Locked {
+        lock_id: Uint64,
+    },
+    /// the lock is released to the proxy upon its expiration
+    Unlocking {
+        lock_id: Uint64,
+        unlock_expiration: Expiration,
+    },
 }
 
 #[cw_serde]
@@ -157,7 +242,49 @@ impl LiquidityProvisionConfig {
         self.latest_balances.get(&self.lp_token_denom)
     }
 
-    pub fn get_osmo_outpost_provide_liquidity_message(&self) -> StdResult<CosmosMsg> {
+    /// narrows the pool price config down to the prices within the max
+    /// deviation of the oracle price guard, given the price it reported.
+    /// errors if none of the expected pool prices are in line with the oracle.
+    pub fn get_provision_price_config(
+        &self,
+        oracle_price: Option<Decimal>,
+    ) -> StdResult<PoolPriceConfig> {
+        let (Some(guard), Some(oracle_price)) = (&self.oracle_price_guard, oracle_price) else {
+            return Ok(self.pool_price_config.clone());
+        };
+
+        let expected_spot_price = self.pool_price_config.expected_spot_price;
+        let acceptable_price_spread = self.pool_price_config.acceptable_price_spread;
+        let (oracle_min_price, oracle_max_price) = guard.get_price_range(oracle_price)?;
+
+        let min_price = expected_spot_price
+            .saturating_sub(acceptable_price_spread)
+            .max(oracle_min_price);
+        let max_price = expected_spot_price
+            .saturating_add(acceptable_price_spread)
+            .min(oracle_max_price);
+        ensure!(
+            min_price <= max_price,
+            StdError::generic_err(format!(
+                "oracle price {oracle_price} is out of the expected pool price range"
+            ))
+        );
+
+        let half_range = (max_price - min_price) / Uint128::new(2);
+        Ok(PoolPriceConfig {
+            expected_spot_price: min_price + half_range,
+            acceptable_price_spread: half_range,
+        })
+    }
+
+    /// builds the outpost provision message. `position_id` is the
+    /// concentrated liquidity position to add to, if any. the outpost
+    /// only joins the pool if its spot price is within `price_config`.
+    pub fn get_osmo_outpost_provide_liquidity_message(
+        &self,
+        position_id: Option<Uint64>,
+        price_config: &PoolPriceConfig,
+    ) -> StdResult<CosmosMsg> {
         let mut funds = vec![];
         if let Some(c) = self.get_party_1_proxy_balance() {
             funds.push(c.clone());
@@ -168,12 +295,17 @@ impl LiquidityProvisionConfig {
 
         let outpost_config = OutpostProvideLiquidityConfig {
             pool_id: Uint64::new(self.pool_id.u64()),
-            expected_spot_price: self.pool_price_config.expected_spot_price,
-            acceptable_price_spread: self.pool_price_config.acceptable_price_spread,
+            expected_spot_price: price_config.expected_spot_price,
+            acceptable_price_spread: price_config.acceptable_price_spread,
             // if no slippage tolerance is passed, we use 0
             slippage_tolerance: self.slippage_tolerance.unwrap_or_default(),
             asset_1_single_side_lp_limit: self.single_side_lp_limits.asset_a_limit,
             asset_2_single_side_lp_limit: self.single_side_lp_limits.asset_b_limit,
+            single_side_lp_pool_reserve_share: self.single_side_lp_limits.pool_reserve_share,
+            max_pool_ownership: self.max_pool_ownership,
+            join_mode: self.join_mode.clone(),
+            tick_range: self.tick_range.clone(),
+            position_id,
         };
 
         Ok(WasmMsg::Execute {
@@ -188,6 +320,14 @@ impl LiquidityProvisionConfig {
         .into())
     }
 
+    /// exit price expectations derived from the pool price config
+    pub fn get_outpost_exit_price_guard(&self) -> OutpostExitPriceGuard {
+        OutpostExitPriceGuard {
+            expected_spot_price: self.pool_price_config.expected_spot_price,
+            acceptable_price_spread: self.pool_price_config.acceptable_price_spread,
+        }
+    }
+
     pub fn reset_latest_proxy_balances(&mut self) {
         self.latest_balances
             .remove(&self.party_1_denom_info.osmosis_coin.denom);
@@ -218,6 +358,32 @@ impl LiquidityProvisionConfig {
             Some(val) => val.to_string(),
             None => "None".to_string(),
         };
+        let max_pool_ownership = match self.max_pool_ownership {
+            Some(val) => val.to_string(),
+            None => "None".to_string(),
+        };
+        let join_mode = match self.join_mode {
+            JoinMode::MinRatio => "min_ratio",
+            JoinMode::ExactAmountsIn => "exact_amounts_in",
+        };
+        let tick_range = match &self.tick_range {
+            Some(range) => format!("[{}, {}]", range.lower_tick, range.upper_tick),
+            None => "None".to_string(),
+        };
+        let oracle_price_guard = match &self.oracle_price_guard {
+            Some(guard) => format!(
+                "{}/{} within {}",
+                guard.base, guard.quote, guard.max_deviation
+            ),
+            None => "None".to_string(),
+        };
+        let lockup = match &self.lockup_config {
+            Some(config) => match &config.superfluid_validator {
+                Some(validator) => format!("{}s superfluid to {}", config.duration, validator),
+                None => format!("{}s", config.duration),
+            },
+            None => "None".to_string(),
+        };
         let proxy_bals: Vec<Attribute> = self
             .latest_balances
             .iter()
@@ -228,6 +394,11 @@ impl LiquidityProvisionConfig {
             Attribute::new("outpost", self.outpost),
             Attribute::new("lp_token_denom", self.lp_token_denom),
             Attribute::new("slippage_tolerance", slippage_tolerance),
+            Attribute::new("max_pool_ownership", max_pool_ownership),
+            Attribute::new("join_mode", join_mode),
+            Attribute::new("tick_range", tick_range),
+            Attribute::new("lockup", lockup),
+            Attribute::new("oracle_price_guard", oracle_price_guard),
             Attribute::new(
                 "expected_spot_price",
                 self.pool_price_config.expected_spot_price.to_string(),
@@ -284,6 +455,12 @@ impl PartyDenomInfo {
 pub enum ExecuteMsg {
     // polytone callback listener
     Callback(CallbackMessage),
+    /// lifts the exit price guard for the pending withdrawal.
+    /// only callable by the emergency committee.
+    ForceWithdrawal {},
+    /// sends the party denoms pulled back from the proxy to the
+    /// holder. only callable by the holder, in `Active` state.
+    ReturnIdleFunds {},
 }
 
 #[covenant_clock_address]
@@ -303,6 +480,54 @@ pub enum QueryMsg {
     ProxyAddress {},
     #[returns(Vec<String>)]
     Callbacks {},
+    /// most recent polytone callbacks, newest first.
+    /// paginated by the id of the last callback of the previous page.
+    #[returns(Vec<PolytoneCallbackRecord>)]
+    CallbackHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<Addr>)]
+    EmergencyCommittee {},
+    /// time left until the proxy funding expires. `None` outside
+    /// of the `ProxyFunded` state.
+    #[returns(Option<TimeToExpiry>)]
+    TimeToExpiry {},
+    /// party denoms pulled back from the proxy and held by this
+    /// contract. empty outside of the `Active` state, where the
+    /// balances are either yet to be provided or being distributed.
+    #[returns(Vec<Coin>)]
+    IdleBalances {},
+    /// concentrated liquidity position held by the proxy, if any
+    #[returns(Option<Uint64>)]
+    PositionId {},
+    /// lock of the gamm shares held by the proxy, if any
+    #[returns(Option<LpLock>)]
+    LpLock {},
+}
+
+/// kind of a polytone callback
+#[cw_serde]
+pub enum PolytoneCallbackKind {
+    Execute,
+    Query,
+    FatalError,
+}
+
+/// polytone callback received from the note
+#[cw_serde]
+pub struct PolytoneCallbackRecord {
+    /// sequence number of the callback
+    pub id: u64,
+    pub block_height: u64,
+    pub block_time: Timestamp,
+    pub kind: PolytoneCallbackKind,
+    /// request that the callback responds to (e.g. `provide_liquidity`)
+    pub initiator: String,
+    /// false if the remote execution or query failed
+    pub success: bool,
+    /// json encoded result of the execution or query, or the error
+    pub result: String,
 }
 
 /// state of the LP state machine
@@ -374,6 +599,7 @@ pub enum MigrateMsg {
         note_address: Option<String>,
         ibc_config: Box<Option<IbcConfig>>,
         lp_config: Box<Option<LiquidityProvisionConfig>>,
+        emergency_committee: Option<String>,
     },
     UpdateCodeId {
         data: Option<Binary>,
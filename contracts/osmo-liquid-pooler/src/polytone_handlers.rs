@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    coin, ensure, from_json, to_json_binary, Addr, Binary, Coin, CosmosMsg, DepsMut, Empty, Env,
-    IbcMsg, IbcTimeout, MessageInfo, QueryRequest, Response, StdResult, Uint128, Uint64, WasmMsg,
+    coin, ensure, from_json, to_json_binary, to_json_string, Addr, Binary, Coin, CosmosMsg,
+    DepsMut, Empty, Env, IbcMsg, IbcTimeout, MessageInfo, QueryRequest, Response, StdResult,
+    Storage, Uint128, Uint64, WasmMsg,
 };
 use covenant_utils::{
     polytone::{
@@ -11,22 +12,32 @@ use covenant_utils::{
     },
     withdraw_lp_helper::WithdrawLPMsgs,
 };
+use cw_utils::Expiration;
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
 };
-use osmosis_std::types::cosmos::bank::v1beta1::QueryBalanceResponse;
+use osmosis_std::types::{
+    cosmos::bank::v1beta1::QueryBalanceResponse,
+    osmosis::{lockup::MsgLockTokensResponse, superfluid::MsgLockAndSuperfluidDelegateResponse},
+};
+use prost::Message;
 
 use crate::{
     contract::{
-        CREATE_PROXY_CALLBACK_ID, PROVIDE_LIQUIDITY_CALLBACK_ID, PROXY_BALANCES_QUERY_CALLBACK_ID,
+        CREATE_PROXY_CALLBACK_ID, LOCK_SHARES_CALLBACK_ID, PROVIDE_LIQUIDITY_CALLBACK_ID,
+        PROXY_BALANCES_QUERY_CALLBACK_ID, UNLOCK_SHARES_CALLBACK_ID,
         WITHDRAW_LIQUIDITY_CALLBACK_ID,
     },
     error::ContractError,
-    msg::{ContractState, IbcConfig, LiquidityProvisionConfig},
+    msg::{
+        ContractState, IbcConfig, LiquidityProvisionConfig, LpLock, PolytoneCallbackKind,
+        PolytoneCallbackRecord,
+    },
     state::{
-        CONTRACT_STATE, HOLDER_ADDRESS, LIQUIDITY_PROVISIONING_CONFIG, NOTE_ADDRESS,
-        POLYTONE_CALLBACKS, PROXY_ADDRESS,
+        CONTRACT_STATE, HOLDER_ADDRESS, LIQUIDITY_PROVISIONING_CONFIG, LP_LOCK, NOTE_ADDRESS,
+        POLYTONE_CALLBACKS, POLYTONE_CALLBACK_HISTORY, POLYTONE_CALLBACK_HISTORY_LIMIT,
+        POLYTONE_CALLBACK_NONCE, POSITION_ID, PROXY_ADDRESS,
     },
 };
 
@@ -50,6 +61,8 @@ pub fn try_handle_callback(
         ContractError::Unauthorized {}.to_neutron_std()
     );
 
+    record_polytone_callback(deps.storage, &env, &msg)?;
+
     match msg.result {
         PolytoneCallback::Query(resp) => process_query_callback(env, deps, resp, msg.initiator_msg),
         PolytoneCallback::Execute(resp) => {
@@ -59,6 +72,59 @@ pub fn try_handle_callback(
     }
 }
 
+/// appends the callback to the callback history, pruning the oldest
+/// callback once the history exceeds its limit
+fn record_polytone_callback(
+    storage: &mut dyn Storage,
+    env: &Env,
+    msg: &CallbackMessage,
+) -> StdResult<()> {
+    let (kind, success, result) = match &msg.result {
+        PolytoneCallback::Execute(Ok(resp)) => {
+            (PolytoneCallbackKind::Execute, true, to_json_string(resp)?)
+        }
+        PolytoneCallback::Execute(Err(e)) => (PolytoneCallbackKind::Execute, false, e.to_string()),
+        PolytoneCallback::Query(Ok(resp)) => {
+            (PolytoneCallbackKind::Query, true, to_json_string(resp)?)
+        }
+        PolytoneCallback::Query(Err(e)) => (PolytoneCallbackKind::Query, false, to_json_string(e)?),
+        PolytoneCallback::FatalError(e) => (PolytoneCallbackKind::FatalError, false, e.to_string()),
+    };
+    let initiator = match from_json::<u8>(&msg.initiator_msg) {
+        Ok(PROVIDE_LIQUIDITY_CALLBACK_ID) => "provide_liquidity".to_string(),
+        Ok(PROXY_BALANCES_QUERY_CALLBACK_ID) => "proxy_balances_query".to_string(),
+        Ok(CREATE_PROXY_CALLBACK_ID) => "create_proxy".to_string(),
+        Ok(WITHDRAW_LIQUIDITY_CALLBACK_ID) => "withdraw_liquidity".to_string(),
+        Ok(LOCK_SHARES_CALLBACK_ID) => "lock_shares".to_string(),
+        Ok(UNLOCK_SHARES_CALLBACK_ID) => "unlock_shares".to_string(),
+        _ => format!("unknown: {}", msg.initiator_msg),
+    };
+
+    let id = POLYTONE_CALLBACK_NONCE
+        .may_load(storage)?
+        .unwrap_or_default()
+        + 1;
+    POLYTONE_CALLBACK_NONCE.save(storage, &id)?;
+    POLYTONE_CALLBACK_HISTORY.save(
+        storage,
+        id,
+        &PolytoneCallbackRecord {
+            id,
+            block_height: env.block.height,
+            block_time: env.block.time,
+            kind,
+            initiator,
+            success,
+            result,
+        },
+    )?;
+    if id > POLYTONE_CALLBACK_HISTORY_LIMIT {
+        POLYTONE_CALLBACK_HISTORY.remove(storage, id - POLYTONE_CALLBACK_HISTORY_LIMIT);
+    }
+
+    Ok(())
+}
+
 fn process_query_callback(
     env: Env,
     deps: ExecuteDeps,
@@ -89,7 +155,13 @@ fn process_execute_callback(
     let initiator_msg: u8 = from_json(initiator_msg)?;
     let callback_result: ExecutionResponse = match execute_callback_result {
         Ok(val) => val,
-        Err(e) => return Err(ContractError::PolytoneError(e).to_neutron_std()),
+        // erroring out would revert the callback history entry, so we
+        // only report the error and leave the state machine as is
+        Err(e) => {
+            return Ok(Response::default()
+                .add_attribute("method", "process_execute_callback")
+                .add_attribute("polytone_error", e))
+        }
     };
 
     match initiator_msg {
@@ -104,6 +176,15 @@ fn process_execute_callback(
             )?;
 
             for submsg_response in callback_result.result {
+                // concentrated liquidity provisions report the position
+                // transferred to the proxy
+                for event in submsg_response.events.iter().filter(|e| e.ty == "wasm") {
+                    for attr in event.attributes.iter().filter(|a| a.key == "position_id") {
+                        if let Ok(position_id) = Uint64::from_str(&attr.value) {
+                            POSITION_ID.save(deps.storage, &position_id)?;
+                        }
+                    }
+                }
                 if submsg_response.data.is_some() {
                     if let Some(response_binary) = submsg_response.data {
                         POLYTONE_CALLBACKS.save(
@@ -115,6 +196,73 @@ fn process_execute_callback(
                 }
             }
         }
+        LOCK_SHARES_CALLBACK_ID => {
+            POLYTONE_CALLBACKS.save(
+                deps.storage,
+                format!("lock_shares_callback : {:?}", env.block.height.to_string()),
+                &to_json_binary(&callback_result)?.to_string(),
+            )?;
+
+            // both lock responses carry the id of the created lock
+            let lp_config = LIQUIDITY_PROVISIONING_CONFIG.load(deps.storage)?;
+            let superfluid = lp_config
+                .lockup_config
+                .and_then(|config| config.superfluid_validator)
+                .is_some();
+            let lock_data = callback_result
+                .result
+                .first()
+                .and_then(|response| response.data.clone());
+            if let Some(data) = lock_data {
+                let lock_id = if superfluid {
+                    MsgLockAndSuperfluidDelegateResponse::decode(data.as_slice()).map(|r| r.id)
+                } else {
+                    MsgLockTokensResponse::decode(data.as_slice()).map(|r| r.id)
+                };
+                match lock_id {
+                    Ok(lock_id) => LP_LOCK.save(
+                        deps.storage,
+                        &LpLock::Locked {
+                            lock_id: Uint64::new(lock_id),
+                        },
+                    )?,
+                    Err(e) => POLYTONE_CALLBACKS.save(
+                        deps.storage,
+                        format!(
+                            "lock_shares_callback_LOCK_ID_error : {:?}",
+                            env.block.height.to_string()
+                        ),
+                        &e.to_string(),
+                    )?,
+                }
+            }
+        }
+        UNLOCK_SHARES_CALLBACK_ID => {
+            POLYTONE_CALLBACKS.save(
+                deps.storage,
+                format!(
+                    "unlock_shares_callback : {:?}",
+                    env.block.height.to_string()
+                ),
+                &to_json_binary(&callback_result)?.to_string(),
+            )?;
+
+            // the lock is released after the lock duration
+            let lp_config = LIQUIDITY_PROVISIONING_CONFIG.load(deps.storage)?;
+            if let (Some(LpLock::Locked { lock_id }), Some(lockup_config)) =
+                (LP_LOCK.may_load(deps.storage)?, lp_config.lockup_config)
+            {
+                LP_LOCK.save(
+                    deps.storage,
+                    &LpLock::Unlocking {
+                        lock_id,
+                        unlock_expiration: Expiration::AtTime(
+                            env.block.time.plus_seconds(lockup_config.duration.u64()),
+                        ),
+                    },
+                )?;
+            }
+        }
         CREATE_PROXY_CALLBACK_ID => {
             let note_address = NOTE_ADDRESS.load(deps.storage)?;
 
@@ -148,10 +296,16 @@ fn process_execute_callback(
             // query the events for one that has "type" == "wasm"
             // and search its attributes for one where key == "refund_tokens".
             // type is polytone ExecutionResponse
+            // concentrated liquidity withdrawals report the position
+            // returned to the proxy, unless it was withdrawn in full
+            let mut remaining_position_id = None;
             for callback_response in callback_result.clone().result {
                 for event in callback_response.events {
                     if event.ty == "wasm" {
                         for attr in event.attributes {
+                            if attr.key == "position_id" {
+                                remaining_position_id = Uint64::from_str(&attr.value).ok();
+                            }
                             if attr.key == "refund_tokens" {
                                 let refunded_coins: Vec<Coin> = match from_json(&attr.value) {
                                     Ok(coins) => coins,
@@ -188,6 +342,12 @@ fn process_execute_callback(
                     }
                 }
             }
+            if POSITION_ID.exists(deps.storage) {
+                match remaining_position_id {
+                    Some(position_id) => POSITION_ID.save(deps.storage, &position_id)?,
+                    None => POSITION_ID.remove(deps.storage),
+                }
+            }
             POLYTONE_CALLBACKS.save(
                 deps.storage,
                 format!(
@@ -262,7 +422,12 @@ fn handle_proxy_balances_callback(
             }
             val
         }
-        Err(err) => return Err(ContractError::PolytoneError(err.error).to_neutron_std()),
+        // see `process_execute_callback` on why we do not error out
+        Err(err) => {
+            return Ok(Response::default()
+                .add_attribute("method", "handle_proxy_balances_callback")
+                .add_attribute("polytone_error", err.error))
+        }
     };
 
     // store the latest prices in lp config
@@ -324,8 +489,6 @@ pub fn get_ibc_pfm_withdraw_coin_message(
     timeout_timestamp_nanos: u64,
     memo: String,
 ) -> CosmosMsg {
-    use prost::Message;
-
     let ibc_message = osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer {
         source_port: "transfer".to_string(),
         source_channel: channel_id,
@@ -1,7 +1,9 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Uint64};
 use cw_storage_plus::{Item, Map};
 
-use crate::msg::{ContractState, IbcConfig, LiquidityProvisionConfig};
+use crate::msg::{
+    ContractState, IbcConfig, LiquidityProvisionConfig, LpLock, PolytoneCallbackRecord,
+};
 
 /// contract state tracks the state machine progress
 pub const CONTRACT_STATE: Item<ContractState> = Item::new("contract_state");
@@ -20,8 +22,26 @@ pub const PROXY_ADDRESS: Item<String> = Item::new("proxy_address");
 // fields relevant for providing liquidity
 pub const LIQUIDITY_PROVISIONING_CONFIG: Item<LiquidityProvisionConfig> = Item::new("lp_config");
 
+// concentrated liquidity position held by the proxy
+pub const POSITION_ID: Item<Uint64> = Item::new("position_id");
+
+// lock of the gamm shares held by the proxy
+pub const LP_LOCK: Item<LpLock> = Item::new("lp_lock");
+
 // ibc-related fields
 pub const IBC_CONFIG: Item<IbcConfig> = Item::new("ibc_config");
 
+// set by the emergency committee to perform the pending
+// withdrawal without the exit price guard
+pub const FORCED_WITHDRAWAL: Item<bool> = Item::new("forced_withdrawal");
+
 // timestamp to message
 pub const POLYTONE_CALLBACKS: Map<String, String> = Map::new("callbacks");
+
+/// number of most recent polytone callbacks kept in the callback history
+pub const POLYTONE_CALLBACK_HISTORY_LIMIT: u64 = 50;
+// callback id to the polytone callback
+pub const POLYTONE_CALLBACK_HISTORY: Map<u64, PolytoneCallbackRecord> =
+    Map::new("callback_history");
+// id of the latest recorded polytone callback
+pub const POLYTONE_CALLBACK_NONCE: Item<u64> = Item::new("callback_nonce");
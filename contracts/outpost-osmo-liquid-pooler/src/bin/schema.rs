@@ -1,10 +1,11 @@
 use cosmwasm_schema::write_api;
-use valence_outpost_osmo_liquid_pooler::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
+use valence_outpost_osmo_liquid_pooler::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
+        query: QueryMsg,
         migrate: MigrateMsg,
     }
 }
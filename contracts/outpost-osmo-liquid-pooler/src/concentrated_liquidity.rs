@@ -0,0 +1,95 @@
+use std::{fmt::Display, str::FromStr};
+
+use cosmwasm_std::{Binary, CosmosMsg, Decimal, Decimal256, StdError, StdResult};
+use osmosis_std::types::osmosis::concentratedliquidity::v1beta1::Pool as ClPool;
+use prost::Message;
+
+use crate::error::ContractError;
+
+/// precision `sdk.Dec` values are encoded with on osmosis
+const OSMOSIS_DEC_PRECISION: usize = 18;
+
+// manual definition of the concentrated liquidity position transfer,
+// which is not part of the osmosis-std release we depend on
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgTransferPositions {
+    #[prost(uint64, repeated, tag = "1")]
+    pub position_ids: Vec<u64>,
+    #[prost(string, tag = "2")]
+    pub sender: String,
+    #[prost(string, tag = "3")]
+    pub new_owner: String,
+}
+
+/// builds the message transferring the concentrated liquidity
+/// positions owned by `sender` to `new_owner`
+pub fn get_transfer_positions_msg(
+    position_ids: Vec<u64>,
+    sender: String,
+    new_owner: String,
+) -> CosmosMsg {
+    let msg = MsgTransferPositions {
+        position_ids,
+        sender,
+        new_owner,
+    };
+    CosmosMsg::Stargate {
+        type_url: "/osmosis.concentratedliquidity.v1beta1.MsgTransferPositions".to_string(),
+        value: Binary(msg.encode_to_vec()),
+    }
+}
+
+/// spot price of the pool, expressed as the ratio of token0 to token1.
+/// the pool tracks the square root of the price of token0 denominated
+/// in token1.
+pub fn get_cl_spot_price(pool: &ClPool) -> Result<Decimal, ContractError> {
+    let sqrt_price: Decimal = parse_osmosis_dec(&pool.current_sqrt_price)?;
+    let token0_price = sqrt_price.checked_mul(sqrt_price).map_err(StdError::from)?;
+    if token0_price.is_zero() {
+        return Err(ContractError::OsmosisPoolError(
+            "pool has no liquidity".to_string(),
+        ));
+    }
+    Decimal::one()
+        .checked_div(token0_price)
+        .map_err(|e| StdError::generic_err(e.to_string()).into())
+}
+
+/// liquidity amount to withdraw from a position holding `liquidity`,
+/// given the share of it to be withdrawn
+pub fn get_withdraw_liquidity_amount(
+    liquidity: &str,
+    share: Decimal,
+) -> Result<String, ContractError> {
+    // withdrawing all of the liquidity passes it on as is,
+    // so that no dust is left behind due to truncation
+    if share == Decimal::one() {
+        return Ok(liquidity.to_string());
+    }
+    let liquidity: Decimal256 = parse_osmosis_dec(liquidity)?;
+    let amount = liquidity
+        .checked_mul(Decimal256::from(share))
+        .map_err(StdError::from)?;
+    if amount.is_zero() {
+        return Err(ContractError::LiquidityWithdrawalError(
+            "position liquidity to withdraw is zero".to_string(),
+        ));
+    }
+    Ok(amount.to_string())
+}
+
+/// parses an osmosis decimal, truncating the fractional digits
+/// beyond the precision of `Decimal`
+fn parse_osmosis_dec<T>(value: &str) -> StdResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match value.split_once('.') {
+        Some((whole, fractional)) if fractional.len() > OSMOSIS_DEC_PRECISION => {
+            T::from_str(&format!("{whole}.{}", &fractional[..OSMOSIS_DEC_PRECISION]))
+        }
+        _ => T::from_str(value),
+    }
+    .map_err(|e| StdError::generic_err(format!("invalid osmosis decimal {value}: {e}")))
+}
@@ -1,39 +1,65 @@
 use std::str::FromStr;
 
 use crate::{
+    concentrated_liquidity::{
+        get_cl_spot_price, get_transfer_positions_msg, get_withdraw_liquidity_amount,
+    },
     error::ContractError,
     msg::{
-        CallerContext, ExecuteMsg, InstantiateMsg, MigrateMsg, OsmosisPool,
-        OutpostProvideLiquidityConfig, OutpostWithdrawLiquidityConfig, QueryMsg,
+        validate_spot_price_range, CallerContext, ExecuteMsg, InstantiateMsg, JoinMode, MigrateMsg,
+        OsmosisPool, OsmosisPoolKind, OsmosisPoolType, OutpostProvideLiquidityConfig,
+        OutpostWithdrawLiquidityConfig, PoolInfoResponse, PositionCallerContext,
+        PositionWithdrawCallerContext, QueryMsg, RemainderJoin, SwapCallerContext,
+    },
+    state::{
+        PENDING_POSITION_REPLY, PENDING_POSITION_WITHDRAW_REPLY, PENDING_REPLY, PENDING_SWAP_REPLY,
     },
-    state::PENDING_REPLY,
 };
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, to_json_string, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    Fraction, MessageInfo, Reply, Response, StdError, StdResult, SubMsg, Uint128,
+    ensure, to_json_binary, to_json_string, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, Event, Fraction, MessageInfo, Reply, Response, StdError, StdResult, SubMsg,
+    SubMsgResult, Uint128, Uint64,
 };
 use cw2::set_contract_version;
-use cw_utils::must_pay;
+use cw_utils::{must_pay, one_coin};
 use osmosis_std::{
     shim::Any,
     types::{
         cosmos::base::v1beta1::Coin as ProtoCoin,
-        osmosis::gamm::v1beta1::{
-            MsgExitPool, MsgJoinPool, MsgJoinSwapExternAmountIn, Pool,
-            QueryCalcExitPoolCoinsFromSharesRequest, QueryCalcExitPoolCoinsFromSharesResponse,
-            QueryCalcJoinPoolNoSwapSharesRequest, QueryCalcJoinPoolNoSwapSharesResponse,
-            QueryCalcJoinPoolSharesRequest, QueryCalcJoinPoolSharesResponse, QueryPoolRequest,
-            QueryPoolResponse,
+        osmosis::{
+            concentratedliquidity::v1beta1::{
+                MsgAddToPosition, MsgAddToPositionResponse, MsgCreatePosition,
+                MsgCreatePositionResponse, MsgWithdrawPosition, Pool as ClPool,
+                PositionByIdRequest, PositionByIdResponse,
+            },
+            gamm::{
+                poolmodels::stableswap::v1beta1::Pool as StableswapPool,
+                v1beta1::{
+                    MsgExitPool, MsgJoinPool, MsgJoinSwapExternAmountIn, Pool,
+                    QueryCalcExitPoolCoinsFromSharesRequest,
+                    QueryCalcExitPoolCoinsFromSharesResponse, QueryCalcJoinPoolNoSwapSharesRequest,
+                    QueryCalcJoinPoolNoSwapSharesResponse, QueryCalcJoinPoolSharesRequest,
+                    QueryCalcJoinPoolSharesResponse,
+                },
+            },
+            poolmanager::v1beta1::{
+                MsgSwapExactAmountIn, PoolRequest, PoolResponse, SwapAmountInRoute,
+                TotalPoolLiquidityRequest, TotalPoolLiquidityResponse,
+            },
         },
     },
 };
+use prost::Message;
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const OSMO_POOL_REPLY_ID: u64 = 1;
+const CL_POSITION_REPLY_ID: u64 = 2;
+const SWAP_REPLY_ID: u64 = 3;
+const CL_WITHDRAW_REPLY_ID: u64 = 4;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -56,9 +82,55 @@ pub fn execute(
     match msg {
         ExecuteMsg::ProvideLiquidity { config } => try_provide_liquidity(deps, env, info, config),
         ExecuteMsg::WithdrawLiquidity { config } => try_withdraw_liquidity(deps, env, info, config),
+        ExecuteMsg::Swap {
+            pool_id,
+            token_out_denom,
+            min_out,
+        } => try_swap(deps, env, info, pool_id, token_out_denom, min_out),
     }
 }
 
+fn try_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: Uint64,
+    token_out_denom: String,
+    min_out: Uint128,
+) -> Result<Response, ContractError> {
+    // exactly one token is swapped
+    let token_in = one_coin(&info)?;
+    ensure!(
+        token_in.denom != token_out_denom,
+        ContractError::SwapError(format!("cannot swap {} for itself", token_in.denom))
+    );
+
+    let swap_msg: CosmosMsg = MsgSwapExactAmountIn {
+        sender: env.contract.address.to_string(),
+        routes: vec![SwapAmountInRoute {
+            pool_id: pool_id.u64(),
+            token_out_denom: token_out_denom.to_string(),
+        }],
+        token_in: Some(token_in.clone().into()),
+        token_out_min_amount: min_out.to_string(),
+    }
+    .into();
+
+    // store the callback context to be loaded in the callback
+    PENDING_SWAP_REPLY.save(
+        deps.storage,
+        &SwapCallerContext {
+            sender: info.sender.to_string(),
+            token_in_denom: token_in.denom,
+            token_out_denom,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_swap")
+        .add_submessage(SubMsg::reply_always(swap_msg, SWAP_REPLY_ID)))
+}
+
 fn try_withdraw_liquidity(
     deps: DepsMut,
     env: Env,
@@ -66,25 +138,39 @@ fn try_withdraw_liquidity(
     config: OutpostWithdrawLiquidityConfig,
 ) -> Result<Response, ContractError> {
     // first we query the pool for validation and info
-    let query_response: QueryPoolResponse = deps.querier.query(
-        &QueryPoolRequest {
-            pool_id: config.pool_id.u64(),
-        }
-        .into(),
-    )?;
-    let osmo_pool: Pool = decode_osmo_pool_binary(query_response.pool)?;
+    let osmo_pool: Box<dyn OsmosisPool> =
+        match query_osmosis_pool(deps.as_ref(), config.pool_id.u64())? {
+            OsmosisPoolType::Balancer(pool) => Box::new(pool),
+            OsmosisPoolType::Stableswap(pool) => Box::new(pool),
+            OsmosisPoolType::Concentrated(pool) => {
+                return withdraw_concentrated_liquidity(deps, env, info, pool, config)
+            }
+        };
 
-    let pool_shares_coin = match osmo_pool.total_shares {
-        Some(coin) => coin,
-        None => {
-            return Err(ContractError::OsmosisPoolError(
-                "no shares coin in pool".to_string(),
-            ))
-        }
-    };
+    // if the caller passed exit price expectations, we validate the
+    // pool price against them. this way an exit during a price dislocation
+    // reverts instead of accepting an arbitrary ratio of the underlying assets.
+    if let Some(exit_price_guard) = &config.exit_price_guard {
+        osmo_pool.validate_pool_assets_length()?;
+        exit_price_guard.validate_spot_price(osmo_pool.get_spot_price()?)?;
+    }
+
+    let pool_shares_coin = osmo_pool.get_gamm_cw_coin()?;
 
     // we assert that the correct lp token is being redeemed
-    let shares_to_redeem = must_pay(&info, &pool_shares_coin.denom)?;
+    let shares_paid = must_pay(&info, &pool_shares_coin.denom)?;
+    let shares_to_redeem = match config.share_amount {
+        Some(share_amount) => {
+            ensure!(
+                !share_amount.is_zero() && share_amount <= shares_paid,
+                ContractError::LiquidityWithdrawalError(format!(
+                    "share amount must be within (0, {shares_paid}], got {share_amount}"
+                ))
+            );
+            share_amount
+        }
+        None => shares_paid,
+    };
 
     // we now estimate the underlying assets from those shares
     let calc_exit_query_response: QueryCalcExitPoolCoinsFromSharesResponse = deps.querier.query(
@@ -101,12 +187,17 @@ fn try_withdraw_liquidity(
         ContractError::OsmosisPoolError("exit pool simulation must return 2 denoms".to_string())
     );
 
-    // build the exit pool request based on the exit pool simulation
+    // build the exit pool request based on the exit pool simulation,
+    // unless the caller passed its own minimum amounts
+    let token_out_mins: Vec<ProtoCoin> = match config.min_tokens_out {
+        Some(min_tokens_out) => min_tokens_out.into_iter().map(Into::into).collect(),
+        None => calc_exit_query_response.tokens_out.clone(),
+    };
     let exit_pool_request: CosmosMsg = MsgExitPool {
         sender: env.contract.address.to_string(),
         pool_id: config.pool_id.u64(),
         share_in_amount: shares_to_redeem.to_string(),
-        token_out_mins: calc_exit_query_response.tokens_out.clone(),
+        token_out_mins,
     }
     .into();
 
@@ -117,6 +208,7 @@ fn try_withdraw_liquidity(
         gamm_denom: pool_shares_coin.denom.to_string(),
         pool_denom_1: calc_exit_query_response.tokens_out[0].denom.to_string(),
         pool_denom_2: calc_exit_query_response.tokens_out[1].denom.to_string(),
+        remainder_join: None,
     };
 
     // store the callback context to be loaded in the callback
@@ -137,20 +229,29 @@ fn try_provide_liquidity(
         config.slippage_tolerance < Decimal::one(),
         ContractError::SlippageError {}
     );
+    if let Some(max_pool_ownership) = config.max_pool_ownership {
+        ensure!(
+            !max_pool_ownership.is_zero() && max_pool_ownership <= Decimal::one(),
+            ContractError::LiquidityProvisionError(
+                "max pool ownership must be within (0, 1]".to_string()
+            )
+        );
+    }
     // first we query the pool for validation and info
-    let query_response: QueryPoolResponse = deps.querier.query(
-        &QueryPoolRequest {
-            pool_id: config.pool_id.u64(),
-        }
-        .into(),
-    )?;
-    let osmo_pool: Pool = decode_osmo_pool_binary(query_response.pool)?;
+    let osmo_pool: Box<dyn OsmosisPool> =
+        match query_osmosis_pool(deps.as_ref(), config.pool_id.u64())? {
+            OsmosisPoolType::Balancer(pool) => Box::new(pool),
+            OsmosisPoolType::Stableswap(pool) => Box::new(pool),
+            OsmosisPoolType::Concentrated(pool) => {
+                return provide_concentrated_liquidity(deps, env, info, pool, config)
+            }
+        };
 
     // validate that the pool we wish to provide liquidity
     // to is composed of two assets
     osmo_pool.validate_pool_assets_length()?;
 
-    // only gamm 50:50 pools are supported (for now)
+    // validate that none of the pool asset weights are zero
     osmo_pool.validate_pool_asset_weights()?;
 
     // collect the pool assets into cw coins
@@ -158,32 +259,71 @@ fn try_provide_liquidity(
     // get the total gamm shares cw_std coin
     let gamm_shares_coin = osmo_pool.get_gamm_cw_coin()?;
 
-    // validate the price against our expectations
-    let pool_spot_price = Decimal::from_ratio(pool_assets[0].amount, pool_assets[1].amount);
-    let min_acceptable_spot_price = config.expected_spot_price - config.acceptable_price_spread;
-    let max_acceptable_spot_price = config.expected_spot_price + config.acceptable_price_spread;
+    // validate the price against our expectations. if the pool
+    // is out of range, the paid funds are returned to the sender.
+    let pool_spot_price = osmo_pool.get_spot_price()?;
+    if validate_spot_price_range(
+        pool_spot_price,
+        config.expected_spot_price,
+        config.acceptable_price_spread,
+    )
+    .is_err()
+    {
+        return Ok(get_out_of_range_refund_response(info, pool_spot_price));
+    }
 
-    if min_acceptable_spot_price > pool_spot_price || max_acceptable_spot_price < pool_spot_price {
-        return Err(ContractError::PriceRangeError {});
+    // if configured, the sender must not own more than the max share
+    // of the pool. the received amounts are capped accordingly and
+    // the excess is returned to the sender along with the gamm shares.
+    let owned_shares = deps
+        .querier
+        .query_balance(info.sender.to_string(), gamm_shares_coin.denom.to_string())?
+        .amount;
+    let max_mintable_shares = get_max_mintable_shares(
+        owned_shares,
+        gamm_shares_coin.amount,
+        config.max_pool_ownership,
+    )?;
+    if max_mintable_shares == Some(Uint128::zero()) {
+        return Err(ContractError::LiquidityProvisionError(
+            "max pool ownership reached".to_string(),
+        ));
     }
 
     // get the amounts paid of pool denoms
     let asset_1_received = Coin {
         denom: pool_assets[0].denom.to_string(),
-        amount: get_paid_denom_amount(&info, &pool_assets[0].denom).unwrap_or(Uint128::zero()),
+        amount: cap_provided_amount(
+            get_paid_denom_amount(&info, &pool_assets[0].denom).unwrap_or(Uint128::zero()),
+            pool_assets[0].amount,
+            gamm_shares_coin.amount,
+            max_mintable_shares,
+        )?,
     };
     let asset_2_received = Coin {
         denom: pool_assets[1].denom.to_string(),
-        amount: get_paid_denom_amount(&info, &pool_assets[1].denom).unwrap_or(Uint128::zero()),
+        amount: cap_provided_amount(
+            get_paid_denom_amount(&info, &pool_assets[1].denom).unwrap_or(Uint128::zero()),
+            pool_assets[1].amount,
+            gamm_shares_coin.amount,
+            max_mintable_shares,
+        )?,
     };
 
+    let single_side_lp_limits = config.get_single_side_lp_limits();
+    let asset_1_single_side_lp_limit =
+        single_side_lp_limits.get_asset_a_limit(pool_assets[0].amount);
+    let asset_2_single_side_lp_limit =
+        single_side_lp_limits.get_asset_b_limit(pool_assets[1].amount);
+
     // we build a context helper that will be used to
     // return the resulting funds to the sender
-    let callback_context = CallerContext {
+    let mut callback_context = CallerContext {
         sender: info.sender.to_string(),
         gamm_denom: gamm_shares_coin.denom.to_string(),
         pool_denom_1: asset_1_received.denom.to_string(),
         pool_denom_2: asset_2_received.denom.to_string(),
+        remainder_join: None,
     };
 
     // depending on which assets we have available,
@@ -193,32 +333,44 @@ fn try_provide_liquidity(
         !asset_2_received.amount.is_zero(),
     ) {
         // both assets provided, attempt to provide two sided liquidity
-        (true, true) => provide_double_sided_liquidity(
-            deps,
-            env,
-            osmo_pool,
-            vec![asset_1_received, asset_2_received],
-            config.slippage_tolerance,
-            callback_context,
-        ),
+        (true, true) => {
+            // in exact amounts in mode, the remainder is joined
+            // single-sided after the double sided join
+            if config.join_mode == JoinMode::ExactAmountsIn {
+                callback_context.remainder_join = Some(RemainderJoin {
+                    pool_id: osmo_pool.get_pool_id(),
+                    slippage_tolerance: config.slippage_tolerance,
+                    asset_1_single_side_lp_limit,
+                    asset_2_single_side_lp_limit,
+                });
+            }
+            provide_double_sided_liquidity(
+                deps,
+                env,
+                osmo_pool.get_pool_id(),
+                vec![asset_1_received, asset_2_received],
+                config.slippage_tolerance,
+                callback_context,
+            )
+        }
         // only asset 1 is provided, attempt to provide single sided
         (true, false) => provide_single_sided_liquidity(
             deps,
-            osmo_pool,
+            osmo_pool.get_pool_id(),
             asset_1_received,
             env.contract.address.to_string(),
             config.slippage_tolerance,
-            config.asset_1_single_side_lp_limit,
+            asset_1_single_side_lp_limit,
             callback_context,
         ),
         // only asset 2 is provided, attempt to provide single sided
         (false, true) => provide_single_sided_liquidity(
             deps,
-            osmo_pool,
+            osmo_pool.get_pool_id(),
             asset_2_received,
             env.contract.address.to_string(),
             config.slippage_tolerance,
-            config.asset_2_single_side_lp_limit,
+            asset_2_single_side_lp_limit,
             callback_context,
         ),
         // no funds provided, error out
@@ -228,10 +380,268 @@ fn try_provide_liquidity(
     }
 }
 
+/// queries the pool through the pool manager, which serves pools of any type
+fn query_osmosis_pool(deps: Deps, pool_id: u64) -> Result<OsmosisPoolType, ContractError> {
+    let query_response: PoolResponse = deps.querier.query(&PoolRequest { pool_id }.into())?;
+    let pool = query_response.pool.ok_or_else(|| StdError::NotFound {
+        kind: "shim not found".to_string(),
+    })?;
+
+    match pool.type_url.as_str() {
+        "/osmosis.gamm.v1beta1.Pool" => Ok(OsmosisPoolType::Balancer(decode_osmo_pool_binary(
+            Some(pool),
+        )?)),
+        "/osmosis.gamm.poolmodels.stableswap.v1beta1.Pool" => Ok(OsmosisPoolType::Stableswap(
+            StableswapPool::decode(pool.value.as_slice()).map_err(|err| {
+                StdError::InvalidBase64 {
+                    msg: err.to_string(),
+                }
+            })?,
+        )),
+        "/osmosis.concentratedliquidity.v1beta1.Pool" => Ok(OsmosisPoolType::Concentrated(
+            ClPool::decode(pool.value.as_slice()).map_err(|err| StdError::InvalidBase64 {
+                msg: err.to_string(),
+            })?,
+        )),
+        type_url => Err(ContractError::OsmosisPoolError(format!(
+            "unsupported pool type: {type_url}"
+        ))),
+    }
+}
+
+/// provides the paid funds to a concentrated liquidity position. unless
+/// a position of the sender is added to, a new position is created within
+/// the configured tick range. the resulting position is transferred to the
+/// sender on reply, along with any funds left over.
+fn provide_concentrated_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool: ClPool,
+    config: OutpostProvideLiquidityConfig,
+) -> Result<Response, ContractError> {
+    // validate the price against our expectations. if the pool
+    // is out of range, the paid funds are returned to the sender.
+    let pool_spot_price = get_cl_spot_price(&pool)?;
+    if validate_spot_price_range(
+        pool_spot_price,
+        config.expected_spot_price,
+        config.acceptable_price_spread,
+    )
+    .is_err()
+    {
+        return Ok(get_out_of_range_refund_response(info, pool_spot_price));
+    }
+
+    let token0_received = Coin {
+        denom: pool.token0.to_string(),
+        amount: get_paid_denom_amount(&info, &pool.token0).unwrap_or(Uint128::zero()),
+    };
+    let token1_received = Coin {
+        denom: pool.token1.to_string(),
+        amount: get_paid_denom_amount(&info, &pool.token1).unwrap_or(Uint128::zero()),
+    };
+    ensure!(
+        !token0_received.amount.is_zero() || !token1_received.amount.is_zero(),
+        ContractError::LiquidityProvisionError("no funds provided".to_string())
+    );
+
+    // the share of the funds deposited into the position depends on its
+    // tick range, so the position is bound by the validated price range
+    // rather than by minimum amounts
+    let position_msg: CosmosMsg = match config.position_id {
+        Some(position_id) => MsgAddToPosition {
+            position_id: position_id.u64(),
+            sender: env.contract.address.to_string(),
+            amount0: token0_received.amount.to_string(),
+            amount1: token1_received.amount.to_string(),
+            token_min_amount0: "0".to_string(),
+            token_min_amount1: "0".to_string(),
+        }
+        .into(),
+        None => {
+            let tick_range = config.tick_range.ok_or_else(|| {
+                ContractError::LiquidityProvisionError(
+                    "concentrated liquidity pools require a tick range".to_string(),
+                )
+            })?;
+            tick_range.validate()?;
+
+            // provided tokens are expected to be sorted by denom
+            let mut tokens_provided: Vec<Coin> = vec![token0_received, token1_received]
+                .into_iter()
+                .filter(|c| !c.amount.is_zero())
+                .collect();
+            tokens_provided.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+            MsgCreatePosition {
+                pool_id: pool.id,
+                sender: env.contract.address.to_string(),
+                lower_tick: tick_range.lower_tick,
+                upper_tick: tick_range.upper_tick,
+                tokens_provided: tokens_provided.into_iter().map(Into::into).collect(),
+                token_min_amount0: "0".to_string(),
+                token_min_amount1: "0".to_string(),
+            }
+            .into()
+        }
+    };
+
+    // store the callback context to be loaded in the callback
+    PENDING_POSITION_REPLY.save(
+        deps.storage,
+        &PositionCallerContext {
+            sender: info.sender.to_string(),
+            token0_denom: pool.token0,
+            token1_denom: pool.token1,
+            position_id: config.position_id,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_provide_concentrated_liquidity")
+        .add_submessage(SubMsg::reply_always(position_msg, CL_POSITION_REPLY_ID)))
+}
+
+/// withdraws the configured share of the liquidity of a concentrated
+/// liquidity position transferred to the outpost. the withdrawn funds,
+/// along with any remaining position, are returned to the sender on reply.
+fn withdraw_concentrated_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool: ClPool,
+    config: OutpostWithdrawLiquidityConfig,
+) -> Result<Response, ContractError> {
+    let position_id = config.position_id.ok_or_else(|| {
+        ContractError::LiquidityWithdrawalError(
+            "concentrated liquidity pools require a position id".to_string(),
+        )
+    })?;
+    let position_share = config.position_share.unwrap_or(Decimal::one());
+    ensure!(
+        !position_share.is_zero() && position_share <= Decimal::one(),
+        ContractError::LiquidityWithdrawalError(format!(
+            "position share must be within (0, 1], got {position_share}"
+        ))
+    );
+
+    // the exit is bound by our price expectations, if any
+    if let Some(exit_price_guard) = &config.exit_price_guard {
+        exit_price_guard.validate_spot_price(get_cl_spot_price(&pool)?)?;
+    }
+
+    let position_response: PositionByIdResponse = deps.querier.query(
+        &PositionByIdRequest {
+            position_id: position_id.u64(),
+        }
+        .into(),
+    )?;
+    let position = position_response
+        .position
+        .and_then(|breakdown| breakdown.position)
+        .ok_or_else(|| StdError::NotFound {
+            kind: format!("position {position_id}"),
+        })?;
+    ensure!(
+        position.pool_id == pool.id,
+        ContractError::LiquidityWithdrawalError(format!(
+            "position {position_id} does not belong to pool {}",
+            pool.id
+        ))
+    );
+
+    let withdraw_position_msg: CosmosMsg = MsgWithdrawPosition {
+        position_id: position_id.u64(),
+        sender: env.contract.address.to_string(),
+        liquidity_amount: get_withdraw_liquidity_amount(&position.liquidity, position_share)?,
+    }
+    .into();
+
+    // store the callback context to be loaded in the callback
+    PENDING_POSITION_WITHDRAW_REPLY.save(
+        deps.storage,
+        &PositionWithdrawCallerContext {
+            sender: info.sender.to_string(),
+            token0_denom: pool.token0,
+            token1_denom: pool.token1,
+            position_id,
+            partial: position_share < Decimal::one(),
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_withdraw_concentrated_liquidity")
+        .add_submessage(SubMsg::reply_always(
+            withdraw_position_msg,
+            CL_WITHDRAW_REPLY_ID,
+        )))
+}
+
+/// returns the paid funds to the sender instead of providing
+/// liquidity to a pool priced outside of the acceptable range
+fn get_out_of_range_refund_response(info: MessageInfo, pool_spot_price: Decimal) -> Response {
+    let mut response = Response::default()
+        .add_attribute("method", "refund_out_of_range")
+        .add_attribute("pool_spot_price", pool_spot_price.to_string());
+    if !info.funds.is_empty() {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: info.funds,
+        });
+    }
+    response
+}
+
+/// returns the highest amount of gamm shares that can be minted to the
+/// sender without exceeding its max pool ownership.
+/// `None` means that minting is not limited.
+fn get_max_mintable_shares(
+    owned_shares: Uint128,
+    total_shares: Uint128,
+    max_pool_ownership: Option<Decimal>,
+) -> StdResult<Option<Uint128>> {
+    let max_ownership = match max_pool_ownership {
+        Some(max_ownership) if max_ownership < Decimal::one() => max_ownership,
+        _ => return Ok(None),
+    };
+
+    // (owned + minted) / (total + minted) <= max_ownership
+    let max_mintable_shares = total_shares
+        .mul_floor(max_ownership)
+        .saturating_sub(owned_shares)
+        .checked_multiply_ratio(
+            Decimal::one().atomics(),
+            (Decimal::one() - max_ownership).atomics(),
+        )
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Ok(Some(max_mintable_shares))
+}
+
+/// caps the amount of an asset to be provided, so that the gamm
+/// shares it mints stay within `max_mintable_shares`
+fn cap_provided_amount(
+    amount: Uint128,
+    pool_reserve: Uint128,
+    total_shares: Uint128,
+    max_mintable_shares: Option<Uint128>,
+) -> StdResult<Uint128> {
+    match max_mintable_shares {
+        Some(max_shares) if !total_shares.is_zero() => {
+            let max_amount = max_shares
+                .checked_multiply_ratio(pool_reserve, total_shares)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            Ok(amount.min(max_amount))
+        }
+        _ => Ok(amount),
+    }
+}
+
 fn provide_double_sided_liquidity(
     deps: DepsMut,
     env: Env,
-    pool: Pool,
+    pool_id: u64,
     assets_paid: Vec<Coin>,
     slippage_tolerance: Decimal,
     callback_ctx: CallerContext,
@@ -242,7 +652,7 @@ fn provide_double_sided_liquidity(
     // first we query the expected gamm amount
     let query_response: QueryCalcJoinPoolNoSwapSharesResponse = deps.querier.query(
         &QueryCalcJoinPoolNoSwapSharesRequest {
-            pool_id: pool.id,
+            pool_id,
             tokens_in: token_in_maxs.clone(),
         }
         .into(),
@@ -257,7 +667,7 @@ fn provide_double_sided_liquidity(
 
     let osmo_msg: CosmosMsg = MsgJoinPool {
         sender: env.contract.address.to_string(),
-        pool_id: pool.id,
+        pool_id,
         // exact number of shares we wish to receive
         share_out_amount: expected_gamm_coin.amount.to_string(),
         token_in_maxs,
@@ -274,7 +684,7 @@ fn provide_double_sided_liquidity(
 
 fn provide_single_sided_liquidity(
     deps: DepsMut,
-    pool: Pool,
+    pool_id: u64,
     asset_paid: Coin,
     outpost: String,
     slippage_tolerance: Decimal,
@@ -288,48 +698,164 @@ fn provide_single_sided_liquidity(
             asset_paid.amount.to_string(),
         )
     );
+    let join_pool_msg = get_join_swap_extern_amount_in_msg(
+        deps.as_ref(),
+        pool_id,
+        asset_paid,
+        outpost,
+        slippage_tolerance,
+        &callback_ctx.gamm_denom,
+    )?;
+
+    // store the callback context to be loaded in the callback
+    PENDING_REPLY.save(deps.storage, &callback_ctx)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_join_pool")
+        .add_submessage(SubMsg::reply_always(join_pool_msg, OSMO_POOL_REPLY_ID)))
+}
+
+/// builds a single-side join message, expecting the queried
+/// amount of gamm shares (minus the slippage tolerance)
+fn get_join_swap_extern_amount_in_msg(
+    deps: Deps,
+    pool_id: u64,
+    token_in: Coin,
+    sender: String,
+    slippage_tolerance: Decimal,
+    gamm_denom: &str,
+) -> Result<CosmosMsg, ContractError> {
     // first we query the expected gamm amount
     let query_response: QueryCalcJoinPoolSharesResponse = deps.querier.query(
         &QueryCalcJoinPoolSharesRequest {
-            pool_id: pool.id,
-            tokens_in: vec![asset_paid.clone().into()],
+            pool_id,
+            tokens_in: vec![token_in.clone().into()],
         }
         .into(),
     )?;
 
     let response_gamm_coin = Coin {
-        denom: callback_ctx.gamm_denom.to_string(),
+        denom: gamm_denom.to_string(),
         amount: Uint128::from_str(&query_response.share_out_amount)?,
     };
     let expected_gamm_coin = apply_slippage(slippage_tolerance, response_gamm_coin)?;
 
-    let join_pool_msg: CosmosMsg = MsgJoinSwapExternAmountIn {
-        sender: outpost,
-        pool_id: pool.id,
-        token_in: Some(asset_paid.clone().into()),
+    Ok(MsgJoinSwapExternAmountIn {
+        sender,
+        pool_id,
+        token_in: Some(token_in.into()),
         share_out_min_amount: expected_gamm_coin.amount.to_string(),
     }
-    .into();
+    .into())
+}
 
-    // store the callback context to be loaded in the callback
-    PENDING_REPLY.save(deps.storage, &callback_ctx)?;
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::PoolInfo { pool_id } => {
+            to_json_binary(&query_pool_info(deps, pool_id.u64()).map_err(|e| e.to_std())?)
+        }
+        QueryMsg::ExpectedShares { pool_id, tokens_in } => to_json_binary(
+            &query_expected_shares(deps, pool_id.u64(), tokens_in).map_err(|e| e.to_std())?,
+        ),
+        QueryMsg::SpotPrice { pool_id } => to_json_binary(
+            &query_pool_info(deps, pool_id.u64())
+                .map_err(|e| e.to_std())?
+                .spot_price,
+        ),
+    }
+}
 
-    Ok(Response::default()
-        .add_attribute("method", "try_join_pool")
-        .add_submessage(SubMsg::reply_always(join_pool_msg, OSMO_POOL_REPLY_ID)))
+fn query_pool_info(deps: Deps, pool_id: u64) -> Result<PoolInfoResponse, ContractError> {
+    let (pool_type, osmo_pool): (OsmosisPoolKind, Box<dyn OsmosisPool>) =
+        match query_osmosis_pool(deps, pool_id)? {
+            OsmosisPoolType::Balancer(pool) => (OsmosisPoolKind::Balancer, Box::new(pool)),
+            OsmosisPoolType::Stableswap(pool) => (OsmosisPoolKind::Stableswap, Box::new(pool)),
+            OsmosisPoolType::Concentrated(pool) => {
+                // concentrated liquidity reserves are spread across positions,
+                // so we query their total through the pool manager
+                let query_response: TotalPoolLiquidityResponse = deps
+                    .querier
+                    .query(&TotalPoolLiquidityRequest { pool_id }.into())?;
+                let mut assets = vec![];
+                for token in query_response.liquidity {
+                    assets.push(Coin {
+                        denom: token.denom,
+                        amount: Uint128::from_str(&token.amount)?,
+                    });
+                }
+                return Ok(PoolInfoResponse {
+                    pool_id: Uint64::new(pool_id),
+                    pool_type: OsmosisPoolKind::Concentrated,
+                    assets,
+                    total_shares: None,
+                    spot_price: get_cl_spot_price(&pool)?,
+                });
+            }
+        };
+
+    // spot price is only defined for pools of two assets
+    osmo_pool.validate_pool_assets_length()?;
+
+    Ok(PoolInfoResponse {
+        pool_id: Uint64::new(pool_id),
+        pool_type,
+        assets: osmo_pool.get_pool_cw_coins()?,
+        total_shares: Some(osmo_pool.get_gamm_cw_coin()?),
+        spot_price: osmo_pool.get_spot_price()?,
+    })
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, _msg: QueryMsg) -> StdResult<Binary> {
-    Err(StdError::NotFound {
-        kind: "not implemented".to_string(),
+fn query_expected_shares(
+    deps: Deps,
+    pool_id: u64,
+    tokens_in: Vec<Coin>,
+) -> Result<Coin, ContractError> {
+    let osmo_pool: Box<dyn OsmosisPool> = match query_osmosis_pool(deps, pool_id)? {
+        OsmosisPoolType::Balancer(pool) => Box::new(pool),
+        OsmosisPoolType::Stableswap(pool) => Box::new(pool),
+        OsmosisPoolType::Concentrated(_) => {
+            return Err(ContractError::OsmosisPoolError(
+                "concentrated liquidity pools do not issue shares".to_string(),
+            ))
+        }
+    };
+    let gamm_denom = osmo_pool.get_gamm_cw_coin()?.denom;
+    let tokens_in: Vec<ProtoCoin> = tokens_in.into_iter().map(Into::into).collect();
+
+    let shares_out = match tokens_in.len() {
+        1 => {
+            let query_response: QueryCalcJoinPoolSharesResponse = deps
+                .querier
+                .query(&QueryCalcJoinPoolSharesRequest { pool_id, tokens_in }.into())?;
+            query_response.share_out_amount
+        }
+        2 => {
+            let query_response: QueryCalcJoinPoolNoSwapSharesResponse = deps
+                .querier
+                .query(&QueryCalcJoinPoolNoSwapSharesRequest { pool_id, tokens_in }.into())?;
+            query_response.shares_out
+        }
+        _ => {
+            return Err(ContractError::LiquidityProvisionError(
+                "expected shares require one or two tokens in".to_string(),
+            ))
+        }
+    };
+
+    Ok(Coin {
+        denom: gamm_denom,
+        amount: Uint128::from_str(&shares_out)?,
     })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
-        OSMO_POOL_REPLY_ID => handle_pool_interaction_reply(deps, env),
+        OSMO_POOL_REPLY_ID => handle_pool_interaction_reply(deps, env, msg.result),
+        CL_POSITION_REPLY_ID => handle_position_reply(deps, env, msg.result),
+        SWAP_REPLY_ID => handle_swap_reply(deps, env, msg.result),
+        CL_WITHDRAW_REPLY_ID => handle_position_withdraw_reply(deps, env, msg.result),
         _ => Err(ContractError::UnknownReplyId(msg.id)),
     }
 }
@@ -346,34 +872,184 @@ pub fn migrate(_deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response
     }
 }
 
-fn handle_pool_interaction_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+fn handle_pool_interaction_reply(
+    mut deps: DepsMut,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
     // load and clear the pending reply that we are processing
-    let callback_ctx = PENDING_REPLY.load(deps.storage)?;
+    let mut callback_ctx = PENDING_REPLY.load(deps.storage)?;
     PENDING_REPLY.remove(deps.storage);
 
-    // we query the balances of relevant denoms
-    let available_gamm = deps.querier.query_balance(
-        env.contract.address.to_string(),
-        callback_ctx.gamm_denom.to_string(),
-    )?;
-    let leftover_asset_1 = deps
-        .querier
-        .query_balance(env.contract.address.to_string(), callback_ctx.pool_denom_1)?;
-    let leftover_asset_2 = deps
-        .querier
-        .query_balance(env.contract.address.to_string(), callback_ctx.pool_denom_2)?;
+    // if the double sided join went through, we join the remainder
+    if let (true, Some(remainder_join)) = (result.is_ok(), callback_ctx.remainder_join.take()) {
+        if let Some(response) =
+            try_join_remainder(deps.branch(), &env, &callback_ctx, remainder_join)?
+        {
+            return Ok(response);
+        }
+    }
 
-    // and collect them into tokens to be refunded (if any)
-    let refund_tokens: Vec<Coin> = vec![available_gamm, leftover_asset_1, leftover_asset_2]
-        .into_iter()
-        .filter(|c| c.amount > Uint128::zero())
-        .collect();
+    let response = Response::default().add_attribute("method", "handle_pool_interaction_reply");
+    add_refund(
+        deps.as_ref(),
+        &env,
+        response,
+        callback_ctx.sender,
+        vec![
+            callback_ctx.gamm_denom,
+            callback_ctx.pool_denom_1,
+            callback_ctx.pool_denom_2,
+        ],
+        result.into_result().err(),
+    )
+}
 
-    let mut response = Response::default().add_attribute("method", "handle_pool_interaction_reply");
+/// transfers the concentrated liquidity position resulting from the
+/// provision to the sender, and refunds any funds left over
+fn handle_position_reply(
+    deps: DepsMut,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    // load and clear the pending reply that we are processing
+    let callback_ctx = PENDING_POSITION_REPLY.load(deps.storage)?;
+    PENDING_POSITION_REPLY.remove(deps.storage);
 
+    let mut response = Response::default().add_attribute("method", "handle_position_reply");
+    let failure = match result {
+        SubMsgResult::Ok(_) => None,
+        SubMsgResult::Err(ref reason) => Some(reason.to_string()),
+    };
+    if let SubMsgResult::Ok(submsg_response) = result {
+        let data = submsg_response.data.ok_or_else(|| {
+            ContractError::LiquidityProvisionError("position response is missing".to_string())
+        })?;
+        let decode_err = |err: prost::DecodeError| StdError::parse_err("position", err.to_string());
+        // adding to a position replaces it with a new one
+        let position_id = match callback_ctx.position_id {
+            Some(_) => {
+                MsgAddToPositionResponse::decode(data.as_slice())
+                    .map_err(decode_err)?
+                    .position_id
+            }
+            None => {
+                MsgCreatePositionResponse::decode(data.as_slice())
+                    .map_err(decode_err)?
+                    .position_id
+            }
+        };
+        response = response
+            .add_message(get_transfer_positions_msg(
+                vec![position_id],
+                env.contract.address.to_string(),
+                callback_ctx.sender.to_string(),
+            ))
+            .add_attribute("position_id", position_id.to_string());
+    }
+
+    add_refund(
+        deps.as_ref(),
+        &env,
+        response,
+        callback_ctx.sender,
+        vec![callback_ctx.token0_denom, callback_ctx.token1_denom],
+        failure,
+    )
+}
+
+/// returns the funds withdrawn from a concentrated liquidity position
+/// to the sender. unless the position was fully withdrawn, it is
+/// transferred back to the sender as well.
+fn handle_position_withdraw_reply(
+    deps: DepsMut,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    // load and clear the pending reply that we are processing
+    let callback_ctx = PENDING_POSITION_WITHDRAW_REPLY.load(deps.storage)?;
+    PENDING_POSITION_WITHDRAW_REPLY.remove(deps.storage);
+
+    let mut response =
+        Response::default().add_attribute("method", "handle_position_withdraw_reply");
+    // a fully withdrawn position is deleted, otherwise it is returned
+    if result.is_err() || callback_ctx.partial {
+        response = response
+            .add_message(get_transfer_positions_msg(
+                vec![callback_ctx.position_id.u64()],
+                env.contract.address.to_string(),
+                callback_ctx.sender.to_string(),
+            ))
+            .add_attribute("position_id", callback_ctx.position_id.to_string());
+    }
+
+    add_refund(
+        deps.as_ref(),
+        &env,
+        response,
+        callback_ctx.sender,
+        vec![callback_ctx.token0_denom, callback_ctx.token1_denom],
+        result.into_result().err(),
+    )
+}
+
+/// returns the swapped tokens to the sender. if the swap
+/// failed, the paid tokens are refunded instead.
+fn handle_swap_reply(
+    deps: DepsMut,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    // load and clear the pending reply that we are processing
+    let callback_ctx = PENDING_SWAP_REPLY.load(deps.storage)?;
+    PENDING_SWAP_REPLY.remove(deps.storage);
+
+    let response = Response::default().add_attribute("method", "handle_swap_reply");
+    add_refund(
+        deps.as_ref(),
+        &env,
+        response,
+        callback_ctx.sender,
+        vec![callback_ctx.token_in_denom, callback_ctx.token_out_denom],
+        result.into_result().err(),
+    )
+}
+
+/// refunds the balances of the given denoms held by the outpost
+/// (if any) to the sender. if the pool interaction failed, all of
+/// the paid funds are among them, and a `refund` event is emitted.
+fn add_refund(
+    deps: Deps,
+    env: &Env,
+    response: Response,
+    sender: String,
+    denoms: Vec<String>,
+    failure: Option<String>,
+) -> Result<Response, ContractError> {
+    // we query the balances of relevant denoms
+    // and collect them into tokens to be refunded (if any)
+    let mut refund_tokens: Vec<Coin> = vec![];
+    for denom in denoms {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), denom)?;
+        if balance.amount > Uint128::zero() {
+            refund_tokens.push(balance);
+        }
+    }
+
+    let mut response = response;
     if !refund_tokens.is_empty() {
+        if let Some(reason) = failure {
+            response = response.add_event(
+                Event::new("refund")
+                    .add_attribute("recipient", sender.to_string())
+                    .add_attribute("amount", to_json_string(&refund_tokens)?)
+                    .add_attribute("reason", reason),
+            );
+        }
         response = response.add_message(BankMsg::Send {
-            to_address: callback_ctx.sender,
+            to_address: sender,
             amount: refund_tokens.clone(),
         });
     }
@@ -381,6 +1057,60 @@ fn handle_pool_interaction_reply(deps: DepsMut, env: Env) -> Result<Response, Co
     Ok(response.add_attribute("refund_tokens", to_json_string(&refund_tokens)?))
 }
 
+/// single-side joins the remainder left over by a double sided join.
+/// returns `None` if there is no remainder that can be joined, in which
+/// case any leftovers get refunded right away.
+fn try_join_remainder(
+    deps: DepsMut,
+    env: &Env,
+    callback_ctx: &CallerContext,
+    remainder_join: RemainderJoin,
+) -> Result<Option<Response>, ContractError> {
+    let remainders = [
+        (
+            deps.querier.query_balance(
+                env.contract.address.to_string(),
+                callback_ctx.pool_denom_1.to_string(),
+            )?,
+            remainder_join.asset_1_single_side_lp_limit,
+        ),
+        (
+            deps.querier.query_balance(
+                env.contract.address.to_string(),
+                callback_ctx.pool_denom_2.to_string(),
+            )?,
+            remainder_join.asset_2_single_side_lp_limit,
+        ),
+    ];
+
+    // remainders exceeding their single-side limit are refunded
+    let Some((remainder, _)) = remainders
+        .into_iter()
+        .find(|(coin, limit)| !coin.amount.is_zero() && coin.amount <= *limit)
+    else {
+        return Ok(None);
+    };
+
+    let join_pool_msg = get_join_swap_extern_amount_in_msg(
+        deps.as_ref(),
+        remainder_join.pool_id,
+        remainder.clone(),
+        env.contract.address.to_string(),
+        remainder_join.slippage_tolerance,
+        &callback_ctx.gamm_denom,
+    )?;
+
+    // the refund is processed once the remainder join replies
+    PENDING_REPLY.save(deps.storage, callback_ctx)?;
+
+    Ok(Some(
+        Response::default()
+            .add_attribute("method", "try_join_remainder")
+            .add_attribute("remainder", remainder.to_string())
+            .add_submessage(SubMsg::reply_always(join_pool_msg, OSMO_POOL_REPLY_ID)),
+    ))
+}
+
 /// cw-utils must pay requires specifically one coin, this is a helper
 /// for multi-coin inputs
 fn get_paid_denom_amount(info: &MessageInfo, target_denom: &str) -> StdResult<Uint128> {
@@ -10,7 +10,7 @@ pub enum ContractError {
     #[error("{0}")]
     PaymentError(#[from] PaymentError),
 
-    #[error("only 50:50 pools are supported, got {0}")]
+    #[error("pool asset weights must be non-zero, got {0}")]
     PoolRatioError(String),
 
     #[error("Osmosis pool error: {0}")]
@@ -19,6 +19,12 @@ pub enum ContractError {
     #[error("liquidity provision error: {0}")]
     LiquidityProvisionError(String),
 
+    #[error("liquidity withdrawal error: {0}")]
+    LiquidityWithdrawalError(String),
+
+    #[error("swap error: {0}")]
+    SwapError(String),
+
     #[error("Fund deposit error: expected {0} bal {1}, got {2}")]
     FundsDepositError(String, String, String),
 
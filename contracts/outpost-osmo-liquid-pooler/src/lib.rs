@@ -1,5 +1,6 @@
 extern crate core;
 
+pub mod concentrated_liquidity;
 pub mod contract;
 pub mod error;
 pub mod msg;
@@ -1,8 +1,12 @@
 use std::str::FromStr;
 
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Binary, Coin, Decimal, Uint128, Uint64};
-use osmosis_std::types::osmosis::gamm::v1beta1::Pool;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Coin, Decimal, Decimal256, StdError, Uint128, Uint64};
+use covenant_utils::SingleSideLpLimits;
+use osmosis_std::types::osmosis::{
+    concentratedliquidity::v1beta1::Pool as ClPool,
+    gamm::{poolmodels::stableswap::v1beta1::Pool as StableswapPool, v1beta1::Pool},
+};
 
 use crate::error::ContractError;
 
@@ -17,6 +21,14 @@ pub enum ExecuteMsg {
     WithdrawLiquidity {
         config: OutpostWithdrawLiquidityConfig,
     },
+    /// swaps the paid token for `token_out_denom` in the given pool.
+    /// the swapped tokens are returned to the sender.
+    Swap {
+        pool_id: Uint64,
+        token_out_denom: String,
+        /// minimum amount of `token_out_denom` to be received
+        min_out: Uint128,
+    },
 }
 
 #[cw_serde]
@@ -32,12 +44,139 @@ pub struct OutpostProvideLiquidityConfig {
     /// limits for single-side liquidity provision
     pub asset_1_single_side_lp_limit: Uint128,
     pub asset_2_single_side_lp_limit: Uint128,
+    /// if set, single-side limits are derived as this share of
+    /// the current pool reserves and the absolute limits are ignored
+    #[serde(default)]
+    pub single_side_lp_pool_reserve_share: Option<Decimal>,
+    /// if set, the received assets are capped so that the sender
+    /// does not own more than this share of the pool gamm shares
+    #[serde(default)]
+    pub max_pool_ownership: Option<Decimal>,
+    /// how double sided liquidity is joined. defaults to `min_ratio`.
+    #[serde(default)]
+    pub join_mode: JoinMode,
+    /// tick range of the position created in concentrated liquidity
+    /// pools. required for, and only applicable to, such pools.
+    #[serde(default)]
+    pub tick_range: Option<TickRange>,
+    /// concentrated liquidity position of the sender to add the liquidity
+    /// to. the position must be transferred to the outpost beforehand,
+    /// and the resulting position is transferred back to the sender.
+    /// if none is passed, a new position is created.
+    #[serde(default)]
+    pub position_id: Option<Uint64>,
+}
+
+impl OutpostProvideLiquidityConfig {
+    pub fn get_single_side_lp_limits(&self) -> SingleSideLpLimits {
+        SingleSideLpLimits {
+            asset_a_limit: self.asset_1_single_side_lp_limit,
+            asset_b_limit: self.asset_2_single_side_lp_limit,
+            pool_reserve_share: self.single_side_lp_pool_reserve_share,
+        }
+    }
+}
+
+/// range of ticks a concentrated liquidity position is provided within
+#[cw_serde]
+pub struct TickRange {
+    pub lower_tick: i64,
+    pub upper_tick: i64,
+}
+
+impl TickRange {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.lower_tick >= self.upper_tick {
+            return Err(ContractError::LiquidityProvisionError(
+                "lower tick must be below the upper tick".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum JoinMode {
+    /// join with the amounts matching the pool ratio and
+    /// return the unmatched remainder to the sender
+    #[default]
+    MinRatio,
+    /// join with the amounts matching the pool ratio, and then
+    /// single-side join the unmatched remainder (within the
+    /// single-side limit of its denom)
+    ExactAmountsIn,
+}
+
+/// single-side join of the remainder left over by a double sided join
+#[cw_serde]
+pub struct RemainderJoin {
+    pub pool_id: u64,
+    pub slippage_tolerance: Decimal,
+    pub asset_1_single_side_lp_limit: Uint128,
+    pub asset_2_single_side_lp_limit: Uint128,
 }
 
 #[cw_serde]
 pub struct OutpostWithdrawLiquidityConfig {
     /// id of the pool we wish to withdraw liquidity from
     pub pool_id: Uint64,
+    /// price bounds the exit is subject to. if none are
+    /// passed, the exit is performed at any pool price.
+    pub exit_price_guard: Option<OutpostExitPriceGuard>,
+    /// amount of the paid shares to redeem. if none is passed,
+    /// all of them are redeemed. the rest is refunded.
+    #[serde(default)]
+    pub share_amount: Option<Uint128>,
+    /// minimum amounts of the underlying assets to receive. if none
+    /// are passed, the simulated exit amounts are used.
+    #[serde(default)]
+    pub min_tokens_out: Option<Vec<Coin>>,
+    /// concentrated liquidity position to withdraw from. required for,
+    /// and only applicable to, such pools. the position must be
+    /// transferred to the outpost beforehand.
+    #[serde(default)]
+    pub position_id: Option<Uint64>,
+    /// share of the position liquidity to withdraw. if none is passed,
+    /// all of it is withdrawn. a remaining position is transferred
+    /// back to the sender.
+    #[serde(default)]
+    pub position_share: Option<Decimal>,
+}
+
+#[cw_serde]
+pub struct OutpostExitPriceGuard {
+    /// the price which we expect to withdraw liquidity at
+    pub expected_spot_price: Decimal,
+    /// acceptable delta (both ways) of the expected price
+    pub acceptable_price_spread: Decimal,
+}
+
+impl OutpostExitPriceGuard {
+    /// validates that the pool spot price is within the acceptable range
+    pub fn validate_spot_price(&self, pool_spot_price: Decimal) -> Result<(), ContractError> {
+        validate_spot_price_range(
+            pool_spot_price,
+            self.expected_spot_price,
+            self.acceptable_price_spread,
+        )
+    }
+}
+
+/// validates that the pool spot price is within `expected_spot_price +- acceptable_price_spread`
+pub fn validate_spot_price_range(
+    pool_spot_price: Decimal,
+    expected_spot_price: Decimal,
+    acceptable_price_spread: Decimal,
+) -> Result<(), ContractError> {
+    let min_acceptable_spot_price = expected_spot_price.saturating_sub(acceptable_price_spread);
+    let max_acceptable_spot_price = expected_spot_price.saturating_add(acceptable_price_spread);
+
+    if min_acceptable_spot_price > pool_spot_price || max_acceptable_spot_price < pool_spot_price {
+        return Err(ContractError::PriceRangeError {});
+    }
+
+    Ok(())
 }
 
 #[cw_serde]
@@ -46,21 +185,117 @@ pub struct CallerContext {
     pub pool_denom_1: String,
     pub pool_denom_2: String,
     pub gamm_denom: String,
+    /// remainder to be joined once the double sided join succeeds
+    #[serde(default)]
+    pub remainder_join: Option<RemainderJoin>,
+}
+
+/// context of a pending concentrated liquidity position
+/// creation, used to hand the position over to the sender
+#[cw_serde]
+pub struct PositionCallerContext {
+    pub sender: String,
+    pub token0_denom: String,
+    pub token1_denom: String,
+    /// position added to, if any
+    pub position_id: Option<Uint64>,
+}
+
+/// context of a pending concentrated liquidity position
+/// withdrawal, used to return the withdrawn funds (and the
+/// remaining position, if any) to the sender
+#[cw_serde]
+pub struct PositionWithdrawCallerContext {
+    pub sender: String,
+    pub token0_denom: String,
+    pub token1_denom: String,
+    pub position_id: Uint64,
+    /// whether the position is only partially withdrawn
+    pub partial: bool,
 }
 
+/// context of a pending swap, used to return the
+/// swapped tokens (or the refund) to the sender
 #[cw_serde]
-pub enum QueryMsg {}
+pub struct SwapCallerContext {
+    pub sender: String,
+    pub token_in_denom: String,
+    pub token_out_denom: String,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// type, reserves, and total shares of the pool
+    #[returns(PoolInfoResponse)]
+    PoolInfo { pool_id: Uint64 },
+    /// gamm shares expected from joining the pool with the given tokens.
+    /// a single token is joined single-sided, two tokens are joined
+    /// without swapping.
+    #[returns(Coin)]
+    ExpectedShares {
+        pool_id: Uint64,
+        tokens_in: Vec<Coin>,
+    },
+    /// spot price of the pool, expressed the same way as the
+    /// expected spot price of the liquidity provision config
+    #[returns(Decimal)]
+    SpotPrice { pool_id: Uint64 },
+}
+
+/// type of an osmosis pool
+#[cw_serde]
+pub enum OsmosisPoolKind {
+    Balancer,
+    Stableswap,
+    Concentrated,
+}
+
+#[cw_serde]
+pub struct PoolInfoResponse {
+    pub pool_id: Uint64,
+    pub pool_type: OsmosisPoolKind,
+    /// reserves of the pool assets
+    pub assets: Vec<Coin>,
+    /// total gamm shares of the pool. `None` for
+    /// concentrated liquidity pools.
+    pub total_shares: Option<Coin>,
+    pub spot_price: Decimal,
+}
 
 #[cw_serde]
 pub enum MigrateMsg {
     UpdateCodeId { data: Option<Binary> },
 }
 
+/// osmosis pool, by its type
+pub enum OsmosisPoolType {
+    Balancer(Pool),
+    Stableswap(StableswapPool),
+    Concentrated(ClPool),
+}
+
 pub trait OsmosisPool {
     fn validate_pool_assets_length(&self) -> Result<(), ContractError>;
     fn validate_pool_asset_weights(&self) -> Result<(), ContractError>;
     fn get_pool_cw_coins(&self) -> Result<Vec<Coin>, ContractError>;
     fn get_gamm_cw_coin(&self) -> Result<Coin, ContractError>;
+    fn get_spot_price(&self) -> Result<Decimal, ContractError>;
+    fn get_pool_id(&self) -> u64;
+}
+
+trait WeightedPool {
+    fn get_pool_asset_weights(&self) -> Result<Vec<Uint128>, ContractError>;
+}
+
+impl WeightedPool for Pool {
+    fn get_pool_asset_weights(&self) -> Result<Vec<Uint128>, ContractError> {
+        let mut weights = vec![];
+        for pool_asset in &self.pool_assets {
+            weights.push(Uint128::from_str(&pool_asset.weight)?);
+        }
+        Ok(weights)
+    }
 }
 
 impl OsmosisPool for Pool {
@@ -69,22 +304,29 @@ impl OsmosisPool for Pool {
     fn validate_pool_assets_length(&self) -> Result<(), ContractError> {
         match self.pool_assets.len() {
             2 => Ok(()),
+            // multi-asset weighted pools would need the liquid pooler to fund
+            // and withdraw assets beyond the two party denoms
+            len if len > 2 => Err(ContractError::OsmosisPoolError(
+                "pools of three or more assets are not supported".to_string(),
+            )),
             _ => Err(ContractError::OsmosisPoolError(
                 "pool must have 2 assets".to_string(),
             )),
         }
     }
 
-    /// only gamm 50:50 pools are supported (for now)
+    /// pools of any two weights are supported, as long as
+    /// neither of them is zero
     fn validate_pool_asset_weights(&self) -> Result<(), ContractError> {
-        if self.pool_assets[0].weight != self.pool_assets[1].weight {
-            Err(ContractError::PoolRatioError(format!(
-                "{:?}:{:?}",
-                self.pool_assets[0].weight, self.pool_assets[1].weight
-            )))
-        } else {
-            Ok(())
+        for weight in self.get_pool_asset_weights()? {
+            if weight.is_zero() {
+                return Err(ContractError::PoolRatioError(format!(
+                    "{:?}:{:?}",
+                    self.pool_assets[0].weight, self.pool_assets[1].weight
+                )));
+            }
         }
+        Ok(())
     }
 
     /// collect the pool assets into cw coins
@@ -117,4 +359,116 @@ impl OsmosisPool for Pool {
             )),
         }
     }
+
+    /// spot price of the pool, expressed as the ratio of the first
+    /// pool asset to the second one, each divided by its weight
+    fn get_spot_price(&self) -> Result<Decimal, ContractError> {
+        self.validate_pool_asset_weights()?;
+        let pool_assets = self.get_pool_cw_coins()?;
+        let weights = self.get_pool_asset_weights()?;
+
+        let spot_price = Decimal256::checked_from_ratio(
+            pool_assets[0].amount.full_mul(weights[1]),
+            pool_assets[1].amount.full_mul(weights[0]),
+        )
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+        Ok(Decimal::try_from(spot_price).map_err(|e| StdError::generic_err(e.to_string()))?)
+    }
+
+    fn get_pool_id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl OsmosisPool for StableswapPool {
+    /// validate that the pool we wish to provide liquidity
+    /// to is composed of two assets
+    fn validate_pool_assets_length(&self) -> Result<(), ContractError> {
+        match (self.pool_liquidity.len(), self.scaling_factors.len()) {
+            (2, 2) => Ok(()),
+            _ => Err(ContractError::OsmosisPoolError(
+                "pool must have 2 assets".to_string(),
+            )),
+        }
+    }
+
+    /// stableswap pools are not weighted. scaling factors only shape
+    /// the price curve, joins remain proportional to the reserves.
+    fn validate_pool_asset_weights(&self) -> Result<(), ContractError> {
+        if self.scaling_factors.iter().any(|factor| *factor == 0) {
+            Err(ContractError::OsmosisPoolError(
+                "scaling factors must be non-zero".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// collect the pool liquidity into cw coins
+    fn get_pool_cw_coins(&self) -> Result<Vec<Coin>, ContractError> {
+        let mut pool_assets: Vec<Coin> = vec![];
+        for token in &self.pool_liquidity {
+            pool_assets.push(Coin {
+                denom: token.denom.to_string(),
+                amount: Uint128::from_str(&token.amount)?,
+            });
+        }
+        Ok(pool_assets)
+    }
+
+    fn get_gamm_cw_coin(&self) -> Result<Coin, ContractError> {
+        match &self.total_shares {
+            Some(coin) => Ok(Coin {
+                denom: coin.denom.to_string(),
+                amount: Uint128::from_str(&coin.amount)?,
+            }),
+            None => Err(ContractError::OsmosisPoolError(
+                "expected Some(total_shares), found None".to_string(),
+            )),
+        }
+    }
+
+    /// spot price of the pool, expressed as the price of the second
+    /// pool asset in the first one.
+    /// stableswap pools follow the `xy(x^2 + y^2) = k` curve over the
+    /// reserves divided by their scaling factors. its slope is only
+    /// dependent on the ratio `t = x / y`, giving the price of
+    /// `t(t^2 + 3) / (3t^2 + 1)` in scaled units.
+    fn get_spot_price(&self) -> Result<Decimal, ContractError> {
+        self.validate_pool_assets_length()?;
+        self.validate_pool_asset_weights()?;
+        let pool_assets = self.get_pool_cw_coins()?;
+        let scaling_factor_1 = Uint128::from(self.scaling_factors[0]);
+        let scaling_factor_2 = Uint128::from(self.scaling_factors[1]);
+
+        let ratio = Decimal256::checked_from_ratio(
+            pool_assets[0].amount.full_mul(scaling_factor_2),
+            pool_assets[1].amount.full_mul(scaling_factor_1),
+        )
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+        let ratio_squared = ratio.checked_mul(ratio).map_err(StdError::from)?;
+        let three = Decimal256::percent(300);
+
+        let numerator = ratio
+            .checked_mul(ratio_squared.checked_add(three).map_err(StdError::from)?)
+            .map_err(StdError::from)?;
+        let denominator = three
+            .checked_mul(ratio_squared)
+            .map_err(StdError::from)?
+            .checked_add(Decimal256::one())
+            .map_err(StdError::from)?;
+        let scaled_price = numerator
+            .checked_div(denominator)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+        // convert the price back to the unscaled units
+        let spot_price = scaled_price
+            .checked_mul(Decimal256::from_ratio(scaling_factor_1, scaling_factor_2))
+            .map_err(StdError::from)?;
+        Ok(Decimal::try_from(spot_price).map_err(|e| StdError::generic_err(e.to_string()))?)
+    }
+
+    fn get_pool_id(&self) -> u64 {
+        self.id
+    }
 }
@@ -1,4 +1,16 @@
-use crate::msg::CallerContext;
+use crate::msg::{
+    CallerContext, PositionCallerContext, PositionWithdrawCallerContext, SwapCallerContext,
+};
 use cw_storage_plus::Item;
 
 pub const PENDING_REPLY: Item<CallerContext> = Item::new("pending_reply");
+
+/// concentrated liquidity position creation awaiting its reply
+pub const PENDING_POSITION_REPLY: Item<PositionCallerContext> = Item::new("pending_position_reply");
+
+/// concentrated liquidity position withdrawal awaiting its reply
+pub const PENDING_POSITION_WITHDRAW_REPLY: Item<PositionWithdrawCallerContext> =
+    Item::new("pending_position_withdraw_reply");
+
+/// swap awaiting its reply
+pub const PENDING_SWAP_REPLY: Item<SwapCallerContext> = Item::new("pending_swap_reply");
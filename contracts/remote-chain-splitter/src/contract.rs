@@ -1,5 +1,4 @@
-use std::collections::{BTreeSet, HashSet};
-use std::str::FromStr;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use cosmos_sdk_proto::cosmos::bank::v1beta1::{Input, MsgMultiSend, Output};
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
@@ -7,22 +6,32 @@ use cosmos_sdk_proto::traits::Message;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, to_json_binary, Attribute, Binary, Deps, DepsMut, Env, Fraction, MessageInfo, Reply,
-    Response, StdError, StdResult, Uint128,
+    ensure, to_json_binary, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, StdResult, Uint128,
 };
-use covenant_utils::ica::{
-    get_ica, msg_with_sudo_callback, prepare_sudo_payload, query_ica_registration_fee, sudo_error,
-    sudo_open_ack, sudo_response, sudo_timeout, INTERCHAIN_ACCOUNT_ID,
+use covenant_ica::{
+    registration::register_ica,
+    state::{get_ica, load_ica_state, IcaState},
+    sudo::{handle_sudo, prepare_sudo_payload, submit_ica_tx},
+    INTERCHAIN_ACCOUNT_ID,
 };
+use covenant_utils::mock_ibc::get_contract_version;
 use covenant_utils::neutron::{
-    assert_ibc_fee_coverage, get_proto_coin, query_ibc_fee, RemoteChainInfo, SudoPayload,
+    assert_ibc_fee_coverage, get_proto_coin, query_ibc_fee_with_override,
+    validate_ibc_fee_override, RemoteChainInfo, SudoPayload,
+};
+use covenant_utils::op_mode::{verify_caller, ContractOperationMode, ContractOperationModeConfig};
+use covenant_utils::split::{get_splits_updated_event, SplitConfig};
+use covenant_utils::split_receiver_fix::{
+    fix_split_receiver, SplitReceiverFixStatus, PENDING_SPLIT_RECEIVER_FIXES,
+};
+use covenant_utils::{
+    clock::{ensure_single_trigger_per_block, increment_tick_nonce},
+    neutron, soft_validate_remote_chain_addr,
 };
-use covenant_utils::op_mode::{verify_caller, ContractOperationMode};
-use covenant_utils::{neutron, soft_validate_remote_chain_addr};
 use cw2::set_contract_version;
 use neutron_sdk::bindings::types::ProtobufAny;
 use neutron_sdk::interchain_txs::helpers::get_port_id;
-use neutron_sdk::query::min_ibc_fee::MinIbcFeeResponse;
 use neutron_sdk::sudo::msg::SudoMsg;
 use neutron_sdk::NeutronError;
 
@@ -31,8 +40,9 @@ use crate::msg::{
     ContractState, ExecuteMsg, FallbackAddressUpdateConfig, InstantiateMsg, MigrateMsg, QueryMsg,
 };
 use crate::state::{
-    RemoteChainSplitteIcaStateHelper, CONTRACT_OP_MODE, CONTRACT_STATE, FALLBACK_ADDRESS,
-    INTERCHAIN_ACCOUNTS, REMOTE_CHAIN_INFO, SPLIT_CONFIG_MAP, TRANSFER_AMOUNT,
+    RemoteChainSplitterIcaHooks, CONTRACT_OP_MODE, CONTRACT_STATE, EMERGENCY_COMMITTEE_ADDR,
+    FALLBACK_ADDRESS, IBC_FEE_OVERRIDE, OP_ADMIN, REMOTE_CHAIN_INFO, SPLIT_CONFIG_MAP,
+    TRANSFER_AMOUNT,
 };
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
@@ -54,7 +64,11 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_contract_version(
+        deps.storage,
+        CONTRACT_NAME,
+        get_contract_version(CONTRACT_VERSION),
+    )?;
 
     let op_mode = ContractOperationMode::try_init(deps.api, msg.op_mode_cfg.clone())?;
     CONTRACT_OP_MODE.save(deps.storage, &op_mode)?;
@@ -73,6 +87,19 @@ pub fn instantiate(
         soft_validate_remote_chain_addr(deps.api, addr)?;
         FALLBACK_ADDRESS.save(deps.storage, addr)?;
     }
+    if let Some(addr) = &msg.emergency_committee {
+        let committee_addr = deps.api.addr_validate(addr)?;
+        EMERGENCY_COMMITTEE_ADDR.save(deps.storage, &committee_addr)?;
+    }
+    if let Some(addr) = &msg.op_admin {
+        let op_admin_addr = deps.api.addr_validate(addr)?;
+        OP_ADMIN.save(deps.storage, &op_admin_addr)?;
+    }
+    if let Some(ibc_fee) = &msg.ibc_fee_override {
+        validate_ibc_fee_override(ibc_fee)?;
+        IBC_FEE_OVERRIDE.save(deps.storage, ibc_fee)?;
+    }
+    let ownership = cw_ownable::initialize_owner(deps.storage, deps.api, msg.owner.as_deref())?;
 
     // validate each split and store it in a map
     let mut split_resp_attributes: Vec<Attribute> = Vec::with_capacity(msg.splits.len());
@@ -87,7 +114,8 @@ pub fn instantiate(
         .add_attribute("method", "remote_chain_splitter_instantiate")
         .add_attribute("op_mode", format!("{:?}", op_mode))
         .add_attributes(remote_chain_info.get_response_attributes())
-        .add_attributes(split_resp_attributes))
+        .add_attributes(split_resp_attributes)
+        .add_attributes(ownership.into_attributes()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -98,24 +126,159 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
     match msg {
-        ExecuteMsg::Tick {} => try_tick(deps, env, info),
+        ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
+            Ok(try_tick(deps, env, info)?.add_attribute("tick_nonce", tick_nonce.to_string()))
+        }
         ExecuteMsg::DistributeFallback { coins } => try_distribute_fallback(deps, env, info, coins),
+        ExecuteMsg::FixSplitReceiver { denom, old, new } => {
+            try_fix_split_receiver(deps, env, info, denom, old, new)
+        }
+        ExecuteMsg::UpdateSplits { splits, fallback } => {
+            try_update_splits(deps, info, splits, fallback)
+        }
+        ExecuteMsg::UpdateConfig {
+            op_mode,
+            remote_chain_info,
+            splits,
+            fallback_address,
+        } => {
+            cw_ownable::assert_owner(deps.storage, &info.sender)
+                .map_err(ContractError::OwnershipError)?;
+            Ok(update_config(
+                deps,
+                op_mode,
+                remote_chain_info,
+                splits,
+                fallback_address,
+            )?)
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            let ownership =
+                cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)
+                    .map_err(ContractError::OwnershipError)?;
+            Ok(Response::default()
+                .add_attribute("method", "update_ownership")
+                .add_attributes(ownership.into_attributes()))
+        }
+    }
+}
+
+/// schedules (or applies, once timelocked) the replacement of a
+/// receiver of the split of the denom
+fn try_fix_split_receiver(
+    deps: ExecuteDeps,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    old: String,
+    new: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let committee_addr = EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?;
+    ensure!(
+        Some(&info.sender) == committee_addr.as_ref(),
+        Into::<NeutronError>::into(ContractError::Unauthorized {})
+    );
+    deps.api.addr_validate(&new)?;
+
+    let mut split = SPLIT_CONFIG_MAP
+        .may_load(deps.storage, denom.to_string())?
+        .ok_or_else(|| StdError::not_found(format!("split for denom {denom:?}")))?;
+
+    let status = fix_split_receiver(
+        deps.storage,
+        &mut split,
+        denom.to_string(),
+        old,
+        new,
+        &env.block,
+    )?;
+    if let SplitReceiverFixStatus::Applied(_) = status {
+        SPLIT_CONFIG_MAP.save(deps.storage, denom, &split)?;
     }
+
+    Ok(Response::default()
+        .add_attribute("method", "try_fix_split_receiver")
+        .add_event(status.to_event()))
+}
+
+/// replaces the splits (and updates the fallback address, if provided)
+/// at runtime. the new splits are validated like on instantiation, and
+/// have to keep covering the denom transferred by the splitter.
+fn try_update_splits(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    splits: BTreeMap<String, SplitConfig>,
+    fallback: Option<FallbackAddressUpdateConfig>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let op_admin = OP_ADMIN.may_load(deps.storage)?;
+    ensure!(
+        Some(&info.sender) == op_admin.as_ref(),
+        Into::<NeutronError>::into(ContractError::Unauthorized {})
+    );
+
+    let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
+    ensure!(
+        splits.contains_key(&remote_chain_info.denom),
+        StdError::generic_err(format!(
+            "missing split configuration for denom {}",
+            remote_chain_info.denom
+        ))
+    );
+    for split in splits.values() {
+        split.validate_shares_and_receiver_addresses(deps.api)?;
+    }
+
+    // fixes scheduled against the previous splits no longer apply
+    SPLIT_CONFIG_MAP.clear(deps.storage);
+    PENDING_SPLIT_RECEIVER_FIXES.clear(deps.storage);
+    for (denom, split) in &splits {
+        SPLIT_CONFIG_MAP.save(deps.storage, denom.to_string(), split)?;
+    }
+
+    let mut event = get_splits_updated_event(&info.sender, &splits);
+    match fallback {
+        Some(FallbackAddressUpdateConfig::ExplicitAddress(addr)) => {
+            soft_validate_remote_chain_addr(deps.api, &addr)?;
+            FALLBACK_ADDRESS.save(deps.storage, &addr)?;
+            event = event.add_attribute("fallback_address", addr);
+        }
+        Some(FallbackAddressUpdateConfig::Disable {}) => {
+            FALLBACK_ADDRESS.remove(deps.storage);
+            event = event.add_attribute("fallback_address", "removed");
+        }
+        None => (),
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "try_update_splits")
+        .add_event(event))
 }
 
 fn try_distribute_fallback(
-    mut deps: ExecuteDeps,
+    deps: ExecuteDeps,
     env: Env,
     info: MessageInfo,
     coins: Vec<cosmwasm_std::Coin>,
 ) -> NeutronResult<Response<NeutronMsg>> {
+    // fallback distributions submit an ica tx, which must not be repeated by
+    // retries landing in the same block. fallback distributions are
+    // permissionless, so the guard is kept per sender for others not to be
+    // locked out.
+    ensure_single_trigger_per_block(
+        deps.storage,
+        &env.block,
+        &format!("distribute_fallback_{}", info.sender),
+    )?;
+
     // load the fallback address or error out if its not set
     let destination = match FALLBACK_ADDRESS.may_load(deps.storage)? {
         Some(addr) => addr,
         None => return Err(ContractError::MissingFallbackAddress {}.into()),
     };
     let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
-    let ibc_fee_response = query_ibc_fee(deps.querier)?;
+    let ibc_fee_response =
+        query_ibc_fee_with_override(deps.querier, IBC_FEE_OVERRIDE.may_load(deps.storage)?)?;
 
     assert_ibc_fee_coverage(info, ibc_fee_response.total_ntrn_fee, Uint128::one())?;
 
@@ -140,8 +303,11 @@ fn try_distribute_fallback(
     }
 
     let port_id = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
-    let interchain_account = INTERCHAIN_ACCOUNTS.may_load(deps.storage, port_id.clone())?;
-    if let Some(Some((address, controller_conn_id))) = interchain_account {
+    if let IcaState::Active {
+        address,
+        controller_connection_id: controller_conn_id,
+    } = load_ica_state(deps.storage)?
+    {
         let multi_send_msg = MsgMultiSend {
             inputs: vec![Input {
                 address,
@@ -164,18 +330,12 @@ fn try_distribute_fallback(
             type_url: "/cosmos.bank.v1beta1.MsgMultiSend".to_string(),
             value: Binary::from(buf),
         };
-        let submit_msg = NeutronMsg::submit_tx(
+        let sudo_msgs = submit_ica_tx(
+            deps.storage,
             controller_conn_id,
-            INTERCHAIN_ACCOUNT_ID.to_string(),
             vec![any_msg],
-            "".to_string(),
             remote_chain_info.ica_timeout.u64(),
             ibc_fee_response.ibc_fee,
-        );
-        let sudo_msg = msg_with_sudo_callback(
-            &RemoteChainSplitteIcaStateHelper,
-            deps.branch(),
-            submit_msg,
             SudoPayload {
                 port_id,
                 message: "distribute_fallback_multisend".to_string(),
@@ -185,7 +345,7 @@ fn try_distribute_fallback(
 
         Ok(Response::default()
             .add_attribute("method", "try_forward_fallback")
-            .add_submessages(vec![sudo_msg]))
+            .add_submessages(sudo_msgs))
     } else {
         Err(NeutronError::Std(StdError::generic_err("no ica found")))
     }
@@ -203,41 +363,42 @@ fn try_tick(deps: ExecuteDeps, env: Env, info: MessageInfo) -> NeutronResult<Res
 
 fn try_register_ica(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<NeutronMsg>> {
     let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
-    let ica_registration_fee = query_ica_registration_fee(deps.querier)?;
-
-    let register: NeutronMsg = NeutronMsg::register_interchain_account(
+    // the account gets activated in the sudo_open_ack callback
+    let register = register_ica(
+        &RemoteChainSplitterIcaHooks,
+        deps.storage,
+        deps.querier,
+        &env,
         remote_chain_info.connection_id,
-        INTERCHAIN_ACCOUNT_ID.to_string(),
-        Some(ica_registration_fee),
-    );
-    let key = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
-
-    // we are saving empty data here because we handle response of registering ICA in sudo_open_ack method
-    INTERCHAIN_ACCOUNTS.save(deps.storage, key, &None)?;
+    )?;
 
     Ok(Response::new()
         .add_attribute("method", "try_register_ica")
-        .add_message(register))
+        .add_messages(register))
 }
 
-fn try_split_funds(mut deps: ExecuteDeps, env: Env) -> NeutronResult<Response<NeutronMsg>> {
+fn try_split_funds(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<NeutronMsg>> {
     let port_id = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
-    let interchain_account = INTERCHAIN_ACCOUNTS.load(deps.storage, port_id.clone())?;
     let amount = TRANSFER_AMOUNT.load(deps.storage)?;
-    let min_fee_query_response: MinIbcFeeResponse =
-        deps.querier.query(&NeutronQuery::MinIbcFee {}.into())?;
+    let min_ibc_fee_config =
+        query_ibc_fee_with_override(deps.querier, IBC_FEE_OVERRIDE.may_load(deps.storage)?)?;
 
-    match interchain_account {
-        Some((address, controller_conn_id)) => {
+    match load_ica_state(deps.storage)? {
+        IcaState::Active {
+            address,
+            controller_connection_id: controller_conn_id,
+        } => {
             let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
 
-            let splits = SPLIT_CONFIG_MAP
-                .load(deps.storage, remote_chain_info.denom.to_string())?
-                .receivers;
+            let split_config =
+                SPLIT_CONFIG_MAP.load(deps.storage, remote_chain_info.denom.to_string())?;
+
+            // split amounts always add up to the transfer amount,
+            // with the remainder allocated according to the rounding policy
+            let split_amounts = split_config.get_split_amounts(amount)?;
 
-            let mut outputs: Vec<Output> = Vec::with_capacity(splits.len());
-            let mut total_allocated = Uint128::zero();
-            for (split_receiver, share) in splits.iter() {
+            let mut outputs: Vec<Output> = Vec::with_capacity(split_amounts.len());
+            for (split_receiver, amt) in split_amounts {
                 // query the ibc forwarders for their ICA addresses
                 // if either does not exist yet, error out
                 let forwarder_deposit_address: Option<String> = deps.querier.query_wasm_smart(
@@ -254,13 +415,6 @@ fn try_split_funds(mut deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Ne
                     }
                 };
 
-                // get the fraction dedicated to this receiver
-                let amt = amount
-                    .checked_multiply_ratio(share.numerator(), share.denominator())
-                    .map_err(|e: cosmwasm_std::CheckedMultiplyRatioError| {
-                        NeutronError::Std(StdError::GenericErr { msg: e.to_string() })
-                    })?;
-
                 let coin = Coin {
                     denom: remote_chain_info.denom.to_string(),
                     amount: amt.to_string(),
@@ -269,18 +423,9 @@ fn try_split_funds(mut deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Ne
                     address: receiver_ica,
                     coins: vec![coin.clone()],
                 };
-                total_allocated += amt;
                 outputs.push(output);
             }
 
-            // if there is no leftover, nothing happens.
-            // otherwise we add the leftover to the first receiver.
-            if let Some(output) = outputs.first_mut() {
-                output.coins[0].amount = (Uint128::from_str(&output.coins[0].amount)?
-                    + (amount - total_allocated))
-                    .to_string();
-            }
-
             let mut inputs: Vec<Input> = Vec::new();
             let input = Input {
                 address: address.to_string(),
@@ -307,18 +452,12 @@ fn try_split_funds(mut deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Ne
                 type_url: "/cosmos.bank.v1beta1.MsgMultiSend".to_string(),
                 value: Binary::from(buf),
             };
-            let submit_msg = NeutronMsg::submit_tx(
+            let sudo_msgs = submit_ica_tx(
+                deps.storage,
                 controller_conn_id,
-                INTERCHAIN_ACCOUNT_ID.to_string(),
                 vec![any_msg],
-                "".to_string(),
                 remote_chain_info.ica_timeout.u64(),
-                min_fee_query_response.min_fee,
-            );
-            let sudo_msg = msg_with_sudo_callback(
-                &RemoteChainSplitteIcaStateHelper,
-                deps.branch(),
-                submit_msg,
+                min_ibc_fee_config.ibc_fee,
                 SudoPayload {
                     port_id,
                     message: "split_funds_msg".to_string(),
@@ -327,9 +466,9 @@ fn try_split_funds(mut deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Ne
             )?;
             Ok(Response::default()
                 .add_attribute("method", "try_split_funds")
-                .add_submessages(vec![sudo_msg]))
+                .add_submessages(sudo_msgs))
         }
-        None => {
+        _ => {
             // I can't think of a case of how we could end up here as `sudo_open_ack`
             // callback advances the state to `ICACreated` and stores the ICA.
             // just in case, we revert the state to `Instantiated` to restart the flow.
@@ -342,11 +481,11 @@ fn try_split_funds(mut deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Ne
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> NeutronResult<Binary> {
+pub fn query(deps: QueryDeps, _env: Env, msg: QueryMsg) -> NeutronResult<Binary> {
     match msg {
         QueryMsg::ContractState {} => Ok(to_json_binary(&CONTRACT_STATE.may_load(deps.storage)?)?),
         QueryMsg::DepositAddress {} => {
-            let ica = query_deposit_address(deps, env)?;
+            let ica = query_deposit_address(deps)?;
             // up to the querying module to make sense of the response
             Ok(to_json_binary(&ica)?)
         }
@@ -363,66 +502,105 @@ pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> NeutronResult<Binary>
         QueryMsg::TransferAmount {} => {
             Ok(to_json_binary(&TRANSFER_AMOUNT.may_load(deps.storage)?)?)
         }
-        QueryMsg::IcaAddress {} => Ok(to_json_binary(
-            &get_ica(
-                &RemoteChainSplitteIcaStateHelper,
-                deps.storage,
-                env.contract.address.as_str(),
-                INTERCHAIN_ACCOUNT_ID,
-            )?
-            .0,
-        )?),
+        QueryMsg::IcaAddress {} => Ok(to_json_binary(&get_ica(deps.storage)?.0)?),
         QueryMsg::FallbackAddress {} => {
             Ok(to_json_binary(&FALLBACK_ADDRESS.may_load(deps.storage)?)?)
         }
         QueryMsg::OperationMode {} => {
             Ok(to_json_binary(&CONTRACT_OP_MODE.may_load(deps.storage)?)?)
         }
+        QueryMsg::EmergencyCommittee {} => Ok(to_json_binary(
+            &EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?,
+        )?),
+        QueryMsg::OpAdmin {} => Ok(to_json_binary(&OP_ADMIN.may_load(deps.storage)?)?),
+        QueryMsg::PendingSplitReceiverFix { denom } => Ok(to_json_binary(
+            &PENDING_SPLIT_RECEIVER_FIXES.may_load(deps.storage, denom)?,
+        )?),
+        QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
+        QueryMsg::IbcFeeOverride {} => {
+            Ok(to_json_binary(&IBC_FEE_OVERRIDE.may_load(deps.storage)?)?)
+        }
     }
 }
 
-fn query_deposit_address(deps: QueryDeps, env: Env) -> Result<Option<String>, StdError> {
-    let key = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
+fn query_deposit_address(deps: QueryDeps) -> Result<Option<String>, StdError> {
     /*
        here we cover three possible cases:
        - 1. ICA had been created -> nice
        - 2. ICA creation request had been submitted but did not receive
            the channel_open_ack yet -> None
-       - 3. ICA creation request hadn't been submitted yet -> None
+       - 3. ICA creation request hadn't been submitted yet, or its
+           channel got closed -> None
     */
-    INTERCHAIN_ACCOUNTS
-        .may_load(deps.storage, key)
-        .map(|entry| entry.flatten().map(|x| x.0))
+    Ok(load_ica_state(deps.storage)?.address())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
-    match msg {
-        // For handling successful (non-error) acknowledgements.
-        SudoMsg::Response { request, data } => sudo_response(request, data),
-        // For handling error acknowledgements.
-        SudoMsg::Error { request, details } => sudo_error(request, details),
-        // For handling error timeouts.
-        SudoMsg::Timeout { request } => {
-            sudo_timeout(&RemoteChainSplitteIcaStateHelper, deps, env, request)
+pub fn sudo(deps: ExecuteDeps, _env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
+    handle_sudo(&RemoteChainSplitterIcaHooks, deps, msg)
+}
+
+/// updates the config, either via the owner or via a migration
+fn update_config(
+    deps: ExecuteDeps,
+    op_mode: Option<ContractOperationModeConfig>,
+    remote_chain_info: Option<RemoteChainInfo>,
+    splits: Option<BTreeMap<String, SplitConfig>>,
+    fallback_address: Option<FallbackAddressUpdateConfig>,
+) -> StdResult<Response<NeutronMsg>> {
+    let mut resp = Response::default().add_attribute("method", "update_config");
+
+    if let Some(op_mode_cfg) = op_mode {
+        let updated_op_mode = ContractOperationMode::try_init(deps.api, op_mode_cfg)
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+        CONTRACT_OP_MODE.save(deps.storage, &updated_op_mode)?;
+        resp = resp.add_attribute("op_mode", format!("{:?}", updated_op_mode));
+    }
+
+    if let Some(remote_chain_info) = remote_chain_info {
+        REMOTE_CHAIN_INFO.save(deps.storage, &remote_chain_info)?;
+        resp = resp.add_attribute("remote_chain_info", format!("{remote_chain_info:?}"));
+    }
+
+    if let Some(splits) = splits {
+        let mut split_resp_attributes: Vec<Attribute> = Vec::with_capacity(splits.len());
+        let mut encountered_denoms: HashSet<String> = HashSet::with_capacity(splits.len());
+
+        for (denom, split) in splits {
+            // if denom had not yet been encountered we proceed, otherwise error
+            if encountered_denoms.insert(denom.to_string()) {
+                split.validate_shares_and_receiver_addresses(deps.api)?;
+                split_resp_attributes.push(split.get_response_attribute(denom.to_string()));
+                SPLIT_CONFIG_MAP.save(deps.storage, denom.to_string(), &split)?;
+                // a fix scheduled against the replaced split no longer applies
+                PENDING_SPLIT_RECEIVER_FIXES.remove(deps.storage, denom.to_string());
+
+                resp = resp
+                    .add_attribute(format!("split-{}", denom), format!("{:?}", split.receivers));
+            } else {
+                return Err(StdError::generic_err(format!(
+                    "multiple {:?} entries",
+                    denom
+                )));
+            }
+        }
+    }
+
+    if let Some(config) = fallback_address {
+        match config {
+            FallbackAddressUpdateConfig::ExplicitAddress(addr) => {
+                FALLBACK_ADDRESS.save(deps.storage, &addr)?;
+                resp = resp.add_attribute("fallback_address", addr);
+            }
+            FallbackAddressUpdateConfig::Disable {} => {
+                FALLBACK_ADDRESS.remove(deps.storage);
+                resp = resp.add_attribute("fallback_address", "removed");
+            }
         }
-        // For handling successful registering of ICA
-        SudoMsg::OpenAck {
-            port_id,
-            channel_id,
-            counterparty_channel_id,
-            counterparty_version,
-        } => sudo_open_ack(
-            &RemoteChainSplitteIcaStateHelper,
-            deps,
-            env,
-            port_id,
-            channel_id,
-            counterparty_channel_id,
-            counterparty_version,
-        ),
-        _ => Ok(Response::default()),
     }
+
+    Ok(resp)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -433,61 +611,7 @@ pub fn migrate(deps: ExecuteDeps, _env: Env, msg: MigrateMsg) -> StdResult<Respo
             remote_chain_info,
             splits,
             fallback_address,
-        } => {
-            let mut resp = Response::default().add_attribute("method", "update_config");
-
-            if let Some(op_mode_cfg) = op_mode {
-                let updated_op_mode = ContractOperationMode::try_init(deps.api, op_mode_cfg)
-                    .map_err(|err| StdError::generic_err(err.to_string()))?;
-
-                CONTRACT_OP_MODE.save(deps.storage, &updated_op_mode)?;
-                resp = resp.add_attribute("op_mode", format!("{:?}", updated_op_mode));
-            }
-
-            if let Some(remote_chain_info) = remote_chain_info {
-                REMOTE_CHAIN_INFO.save(deps.storage, &remote_chain_info)?;
-                resp = resp.add_attribute("remote_chain_info", format!("{remote_chain_info:?}"));
-            }
-
-            if let Some(splits) = splits {
-                let mut split_resp_attributes: Vec<Attribute> = Vec::with_capacity(splits.len());
-                let mut encountered_denoms: HashSet<String> = HashSet::with_capacity(splits.len());
-
-                for (denom, split) in splits {
-                    // if denom had not yet been encountered we proceed, otherwise error
-                    if encountered_denoms.insert(denom.to_string()) {
-                        split.validate_shares_and_receiver_addresses(deps.api)?;
-                        split_resp_attributes.push(split.get_response_attribute(denom.to_string()));
-                        SPLIT_CONFIG_MAP.save(deps.storage, denom.to_string(), &split)?;
-
-                        resp = resp.add_attribute(
-                            format!("split-{}", denom),
-                            format!("{:?}", split.receivers),
-                        );
-                    } else {
-                        return Err(StdError::generic_err(format!(
-                            "multiple {:?} entries",
-                            denom
-                        )));
-                    }
-                }
-            }
-
-            if let Some(config) = fallback_address {
-                match config {
-                    FallbackAddressUpdateConfig::ExplicitAddress(addr) => {
-                        FALLBACK_ADDRESS.save(deps.storage, &addr)?;
-                        resp = resp.add_attribute("fallback_address", addr);
-                    }
-                    FallbackAddressUpdateConfig::Disable {} => {
-                        FALLBACK_ADDRESS.remove(deps.storage);
-                        resp = resp.add_attribute("fallback_address", "removed");
-                    }
-                }
-            }
-
-            Ok(resp)
-        }
+        } => update_config(deps, op_mode, remote_chain_info, splits, fallback_address),
         MigrateMsg::UpdateCodeId { data: _ } => {
             // This is a migrate message to update code id,
             // Data is optional base64 that we can parse to any data we would like in the future
@@ -498,11 +622,9 @@ pub fn migrate(deps: ExecuteDeps, _env: Env, msg: MigrateMsg) -> StdResult<Respo
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: ExecuteDeps, env: Env, msg: Reply) -> StdResult<Response<NeutronMsg>> {
+pub fn reply(deps: ExecuteDeps, _env: Env, msg: Reply) -> StdResult<Response<NeutronMsg>> {
     match msg.id {
-        SUDO_PAYLOAD_REPLY_ID => {
-            prepare_sudo_payload(&RemoteChainSplitteIcaStateHelper, deps, env, msg)
-        }
+        SUDO_PAYLOAD_REPLY_ID => prepare_sudo_payload(deps, msg),
         _ => Err(StdError::generic_err(format!(
             "unsupported reply message id {}",
             msg.id
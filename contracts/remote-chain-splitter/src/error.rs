@@ -11,6 +11,9 @@ pub enum ContractError {
     #[error(transparent)]
     ContractOperationError(#[from] ContractOperationError),
 
+    #[error(transparent)]
+    OwnershipError(#[from] cw_ownable::OwnershipError),
+
     #[error("Missing fallback address")]
     MissingFallbackAddress {},
 
@@ -19,6 +22,9 @@ pub enum ContractError {
 
     #[error("Attempt to distribute duplicate denoms via fallback distribution")]
     DuplicateDenomDistribution {},
+
+    #[error("Unauthorized caller")]
+    Unauthorized {},
 }
 
 impl From<ContractError> for NeutronError {
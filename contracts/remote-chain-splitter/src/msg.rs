@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{to_json_binary, Binary, Coin, StdResult, Uint128, Uint64, WasmMsg};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, StdResult, Uint128, Uint64, WasmMsg};
 use covenant_macros::{
     clocked, covenant_deposit_address, covenant_ica_address, covenant_remote_chain,
 };
@@ -11,7 +11,10 @@ use covenant_utils::{
     neutron::RemoteChainInfo,
     op_mode::{ContractOperationMode, ContractOperationModeConfig},
     split::SplitConfig,
+    split_receiver_fix::SplitReceiverFix,
 };
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use neutron_sdk::bindings::msg::IbcFee;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -42,6 +45,20 @@ pub struct InstantiateMsg {
     pub ibc_transfer_timeout: Uint64,
     // fallback address on the remote chain
     pub fallback_address: Option<String>,
+    /// address authorized to fix mis-entered split receivers
+    #[serde(default)]
+    pub emergency_committee: Option<String>,
+    /// address authorized to update the config. without an owner,
+    /// the config can only be updated by migrating the contract.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// address authorized to update the splits at runtime,
+    /// e.g. a dao or the emergency committee
+    #[serde(default)]
+    pub op_admin: Option<String>,
+    /// ibc fee to pay instead of the chain minimum, where it exceeds it
+    #[serde(default)]
+    pub ibc_fee_override: Option<IbcFee>,
 }
 
 impl InstantiateMsg {
@@ -63,14 +80,40 @@ impl InstantiateMsg {
 }
 
 #[clocked]
+#[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
-    DistributeFallback { coins: Vec<Coin> },
+    DistributeFallback {
+        coins: Vec<Coin>,
+    },
+    /// update the config without a migration. only the owner may
+    /// submit updates.
+    UpdateConfig {
+        op_mode: Option<ContractOperationModeConfig>,
+        remote_chain_info: Option<RemoteChainInfo>,
+        splits: Option<BTreeMap<String, SplitConfig>>,
+        fallback_address: Option<FallbackAddressUpdateConfig>,
+    },
+    /// replace a mis-entered receiver of the split of the denom.
+    /// only the emergency committee may submit fixes. the first submission
+    /// schedules the fix, and resubmitting it after a 24h timelock applies it.
+    FixSplitReceiver {
+        denom: String,
+        old: String,
+        new: String,
+    },
+    /// replace the splits, and update the fallback address if provided.
+    /// only the op admin may submit updates.
+    UpdateSplits {
+        splits: BTreeMap<String, SplitConfig>,
+        fallback: Option<FallbackAddressUpdateConfig>,
+    },
 }
 
 #[covenant_remote_chain]
 #[covenant_deposit_address]
 #[covenant_ica_address]
+#[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -84,6 +127,15 @@ pub enum QueryMsg {
     FallbackAddress {},
     #[returns(ContractOperationMode)]
     OperationMode {},
+    #[returns(Option<Addr>)]
+    EmergencyCommittee {},
+    #[returns(Option<Addr>)]
+    OpAdmin {},
+    /// split receiver fix scheduled for the denom
+    #[returns(Option<SplitReceiverFix>)]
+    PendingSplitReceiverFix { denom: String },
+    #[returns(Option<IbcFee>)]
+    IbcFeeOverride {},
 }
 
 #[cw_serde]
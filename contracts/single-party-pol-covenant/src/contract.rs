@@ -3,10 +3,14 @@ use std::collections::{BTreeMap, BTreeSet};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    WasmMsg,
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, StdResult, WasmMsg,
 };
 use covenant_utils::op_mode::ContractOperationModeConfig;
+use covenant_utils::plan::{
+    DeploymentPlan, DeploymentStep, ShadowDeployment, SHADOW_DEPLOY_REPLY_ID,
+    SHADOW_MIGRATE_REPLY_ID,
+};
 use covenant_utils::split::SplitConfig;
 use covenant_utils::{instantiate2_helper::get_instantiate2_salt_and_address, DestinationConfig};
 use cw2::set_contract_version;
@@ -19,10 +23,14 @@ use valence_stride_liquid_staker::msg::InstantiateMsg as LiquidStakerInstantiate
 use crate::msg::LiquidPoolerMigrateMsg;
 use crate::{
     error::ContractError,
-    msg::{CovenantPartyConfig, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{
+        CovenantContractCodeIds, CovenantPartyConfig, ExecuteMsg, InstantiateMsg, MigrateMsg,
+        QueryMsg,
+    },
     state::{
-        CONTRACT_CODES, COVENANT_CLOCK_ADDR, HOLDER_ADDR, LIQUID_POOLER_ADDR, LIQUID_STAKER_ADDR,
-        LP_FORWARDER_ADDR, LS_FORWARDER_ADDR, ROUTER_ADDR, SPLITTER_ADDR,
+        CONTRACT_CODES, COVENANT_CLOCK_ADDR, DEPLOYMENT_PLAN, HOLDER_ADDR, LIQUID_POOLER_ADDR,
+        LIQUID_STAKER_ADDR, LP_FORWARDER_ADDR, LS_FORWARDER_ADDR, ROUTER_ADDR, SHADOW_DEPLOYMENT,
+        SPLITTER_ADDR,
     },
 };
 
@@ -48,6 +56,7 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let creator_address = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let memo_template = msg.get_party_memo_template(&env.contract.address);
     let clock_instantiate2_config = get_instantiate2_salt_and_address(
         deps.as_ref(),
         CLOCK_SALT,
@@ -99,7 +108,9 @@ pub fn instantiate(
 
     let mut clock_whitelist = Vec::with_capacity(7);
 
-    clock_whitelist.push(liquid_staker_instantiate2_config.addr.to_string());
+    if !msg.pre_staked_contribution {
+        clock_whitelist.push(liquid_staker_instantiate2_config.addr.to_string());
+    }
     clock_whitelist.push(holder_instantiate2_config.addr.to_string());
     clock_whitelist.push(router_instantiate2_config.addr.to_string());
 
@@ -121,8 +132,16 @@ pub fn instantiate(
             destination_receiver_addr: msg.covenant_party_config.party_receiver_addr.to_string(),
             ibc_transfer_timeout: msg.covenant_party_config.ibc_transfer_timeout,
             denom_to_pfm_map: msg.covenant_party_config.denom_to_pfm_map,
+            memo_template,
+            wasm_hook: None,
         },
         denoms,
+        halt_config: None,
+        holder_address: Some(holder_instantiate2_config.addr.to_string()),
+        owner: None,
+        denom_to_destination_map: BTreeMap::new(),
+        min_route_amounts: BTreeMap::new(),
+        ibc_fee_override: msg.ibc_fee_overrides.router.clone(),
     }
     .to_instantiate2_msg(
         &router_instantiate2_config,
@@ -154,6 +173,7 @@ pub fn instantiate(
         ibc_transfer_timeout: msg.timeouts.ibc_transfer_timeout,
         clock_address: clock_instantiate2_config.addr.to_string(),
         next_contract: liquid_pooler_instantiate2_config.addr.to_string(),
+        ibc_fee_override: msg.ibc_fee_overrides.liquid_staker.clone(),
     }
     .to_instantiate2_msg(
         &liquid_staker_instantiate2_config,
@@ -168,23 +188,34 @@ pub fn instantiate(
         clock_instantiate2_config.addr.to_string(),
         holder_instantiate2_config.addr.to_string(),
         msg.pool_price_config,
+        msg.emergency_committee.clone(),
     )?;
 
     let mut split_config_map: BTreeMap<String, Decimal> = BTreeMap::new();
-    split_config_map.insert(
-        ls_forwarder_instantiate2_config.addr.to_string(),
-        msg.remote_chain_splitter_config.ls_share,
-    );
-    split_config_map.insert(
-        lp_forwarder_instantiate2_config.addr.to_string(),
-        msg.remote_chain_splitter_config.native_share,
-    );
+    if msg.pre_staked_contribution {
+        // the ls contribution does not go through the splitter
+        split_config_map.insert(
+            lp_forwarder_instantiate2_config.addr.to_string(),
+            Decimal::one(),
+        );
+    } else {
+        split_config_map.insert(
+            ls_forwarder_instantiate2_config.addr.to_string(),
+            msg.remote_chain_splitter_config.ls_share,
+        );
+        split_config_map.insert(
+            lp_forwarder_instantiate2_config.addr.to_string(),
+            msg.remote_chain_splitter_config.native_share,
+        );
+    }
 
     let mut splits: BTreeMap<String, SplitConfig> = BTreeMap::new();
     splits.insert(
         msg.remote_chain_splitter_config.denom.to_string(),
         SplitConfig {
             receivers: split_config_map,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
         },
     );
 
@@ -200,6 +231,10 @@ pub fn instantiate(
         ibc_transfer_timeout: msg.timeouts.ibc_transfer_timeout,
         splits,
         fallback_address: msg.remote_chain_splitter_config.fallback_address,
+        emergency_committee: msg.emergency_committee.clone(),
+        owner: None,
+        op_admin: None,
+        ibc_fee_override: msg.ibc_fee_overrides.splitter.clone(),
     }
     .to_instantiate2_msg(
         &splitter_instantiate2_config,
@@ -208,13 +243,22 @@ pub fn instantiate(
     )?;
 
     let mut messages = vec![
-        liquid_staker_instantiate2_msg,
         holder_instantiate2_msg,
         liquid_pooler_instantiate2_msg,
         splitter_instantiate2_msg,
         router_instantiate2_msg,
     ];
 
+    // pre-staked contributions skip the liquid staker and
+    // get forwarded to the liquid pooler directly
+    let ls_forwarder_next_contract = if msg.pre_staked_contribution {
+        liquid_pooler_instantiate2_config.addr.to_string()
+    } else {
+        LIQUID_STAKER_ADDR.save(deps.storage, &liquid_staker_instantiate2_config.addr)?;
+        messages.insert(0, liquid_staker_instantiate2_msg);
+        liquid_staker_instantiate2_config.addr.to_string()
+    };
+
     if let CovenantPartyConfig::Interchain(config) = msg.ls_forwarder_config {
         LS_FORWARDER_ADDR.save(deps.storage, &ls_forwarder_instantiate2_config.addr)?;
         clock_initial_queue.insert(0, ls_forwarder_instantiate2_config.addr.to_string());
@@ -222,7 +266,7 @@ pub fn instantiate(
             op_mode_cfg: ContractOperationModeConfig::Permissioned(vec![clock_instantiate2_config
                 .addr
                 .to_string()]),
-            next_contract: liquid_staker_instantiate2_config.addr.to_string(),
+            next_contract: ls_forwarder_next_contract,
             remote_chain_connection_id: config.party_chain_connection_id,
             remote_chain_channel_id: config.party_to_host_chain_channel_id,
             denom: config.remote_chain_denom,
@@ -230,6 +274,8 @@ pub fn instantiate(
             ibc_transfer_timeout: msg.timeouts.ibc_transfer_timeout,
             ica_timeout: msg.timeouts.ica_timeout,
             fallback_address: config.fallback_address,
+            milestone_callback: None,
+            ibc_fee_override: msg.ibc_fee_overrides.forwarder.clone(),
         };
         messages.push(instantiate_msg.to_instantiate2_msg(
             &ls_forwarder_instantiate2_config,
@@ -253,6 +299,8 @@ pub fn instantiate(
             ibc_transfer_timeout: msg.timeouts.ibc_transfer_timeout,
             ica_timeout: msg.timeouts.ica_timeout,
             fallback_address: config.fallback_address,
+            milestone_callback: None,
+            ibc_fee_override: msg.ibc_fee_overrides.forwarder.clone(),
         };
         messages.push(instantiate_msg.to_instantiate2_msg(
             &lp_forwarder_instantiate2_config,
@@ -265,6 +313,10 @@ pub fn instantiate(
         tick_max_gas: msg.clock_tick_max_gas,
         whitelist: clock_whitelist,
         initial_queue: clock_initial_queue,
+        tick_intervals: msg
+            .router_tick_interval
+            .map(|interval| vec![(router_instantiate2_config.addr.to_string(), interval)])
+            .unwrap_or_default(),
     }
     .to_instantiate2_msg(
         clock_instantiate2_config.code,
@@ -274,9 +326,11 @@ pub fn instantiate(
     )?;
     messages.insert(0, clock_instantiate2_msg);
 
+    let deployment_plan = DeploymentPlan::build(deps.as_ref(), &creator_address, messages)?;
+    DEPLOYMENT_PLAN.save(deps.storage, &deployment_plan)?;
+
     HOLDER_ADDR.save(deps.storage, &holder_instantiate2_config.addr)?;
     LIQUID_POOLER_ADDR.save(deps.storage, &liquid_pooler_instantiate2_config.addr)?;
-    LIQUID_STAKER_ADDR.save(deps.storage, &liquid_staker_instantiate2_config.addr)?;
     COVENANT_CLOCK_ADDR.save(deps.storage, &clock_instantiate2_config.addr)?;
     SPLITTER_ADDR.save(deps.storage, &splitter_instantiate2_config.addr)?;
     ROUTER_ADDR.save(deps.storage, &router_instantiate2_config.addr)?;
@@ -294,7 +348,87 @@ pub fn instantiate(
         .add_attribute("liquid_staker_addr", liquid_staker_instantiate2_config.addr)
         .add_attribute("liquid_pooler_addr", liquid_pooler_instantiate2_config.addr)
         .add_attribute("router_addr", router_instantiate2_config.addr)
-        .add_messages(messages))
+        .add_attribute(
+            "pre_staked_contribution",
+            msg.pre_staked_contribution.to_string(),
+        )
+        .add_attribute(
+            "deployment_plan_msg_size",
+            deployment_plan.total_msg_size().to_string(),
+        )
+        .add_messages(deployment_plan.messages()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ShadowDeploy { codes } => try_shadow_deploy(deps, env, info, codes),
+    }
+}
+
+/// instantiates a shadow copy of the deployment plan from the currently
+/// stored contract codes, and migrates it to the candidate codes
+fn try_shadow_deploy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    codes: CovenantContractCodeIds,
+) -> Result<Response, ContractError> {
+    let contract_codes = CONTRACT_CODES.load(deps.storage)?;
+    let (shadow_deployment, messages) = ShadowDeployment::deploy(
+        deps.as_ref(),
+        &env,
+        &info,
+        &DEPLOYMENT_PLAN.load(deps.storage)?,
+        SHADOW_DEPLOYMENT.may_load(deps.storage)?,
+        |step| get_shadow_code_id(&contract_codes, step),
+        |step| get_shadow_code_id(&codes, step),
+    )?;
+    SHADOW_DEPLOYMENT.save(deps.storage, &shadow_deployment)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_shadow_deploy")
+        .add_attribute("nonce", shadow_deployment.nonce.to_string())
+        .add_submessages(messages))
+}
+
+/// maps a deployment step to the code id of its contract
+fn get_shadow_code_id(codes: &CovenantContractCodeIds, step: &DeploymentStep) -> StdResult<u64> {
+    match step.salt.as_slice() {
+        CLOCK_SALT => Ok(codes.clock_code),
+        HOLDER_SALT => Ok(codes.holder_code),
+        REMOTE_CHAIN_SPLITTER_SALT => Ok(codes.remote_chain_splitter_code),
+        LS_FORWARDER_SALT | LP_FORWARDER_SALT => Ok(codes.ibc_forwarder_code),
+        LIQUID_POOLER_SALT => Ok(codes.liquid_pooler_code),
+        LIQUID_STAKER_SALT => Ok(codes.liquid_staker_code),
+        ROUTER_SALT => Ok(codes.interchain_router_code),
+        _ => Err(StdError::generic_err(format!(
+            "unknown deployment step {}",
+            step.label
+        ))),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SHADOW_DEPLOY_REPLY_ID | SHADOW_MIGRATE_REPLY_ID => {
+            let mut shadow_deployment = SHADOW_DEPLOYMENT.load(deps.storage)?;
+            let (step, messages) = shadow_deployment.record_reply(deps.as_ref(), &env, msg)?;
+            SHADOW_DEPLOYMENT.save(deps.storage, &shadow_deployment)?;
+
+            Ok(Response::default()
+                .add_attribute("method", "shadow_deploy_reply")
+                .add_attributes(shadow_deployment.get_reply_attributes(&step)?)
+                .add_submessages(messages))
+        }
+        _ => Err(ContractError::UnknownReplyId {}),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -336,6 +470,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             Ok(to_json_binary(&ica)?)
         }
         QueryMsg::ContractCodes {} => Ok(to_json_binary(&CONTRACT_CODES.load(deps.storage)?)?),
+        QueryMsg::DeploymentPlan {} => Ok(to_json_binary(&DEPLOYMENT_PLAN.load(deps.storage)?)?),
+        QueryMsg::ShadowDeployment {} => {
+            Ok(to_json_binary(&SHADOW_DEPLOYMENT.may_load(deps.storage)?)?)
+        }
     }
 }
 
@@ -3,7 +3,11 @@ use std::collections::BTreeMap;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, Decimal, StdResult, Uint128, Uint64, WasmMsg};
 use covenant_utils::{
-    instantiate2_helper::Instantiate2HelperConfig, op_mode::ContractOperationModeConfig,
+    ibc_memo::resolve_covenant_placeholders,
+    instantiate2_helper::Instantiate2HelperConfig,
+    neutron::IbcFeeOverrides,
+    op_mode::ContractOperationModeConfig,
+    plan::{DeploymentPlan, ShadowDeployment},
     CovenantParty, DestinationConfig, InterchainCovenantParty, NativeCovenantParty,
     PacketForwardMiddlewareConfig, PoolPriceConfig, ReceiverConfig,
 };
@@ -19,6 +23,16 @@ pub struct InstantiateMsg {
     pub timeouts: Timeouts,
     pub contract_codes: CovenantContractCodeIds,
     pub clock_tick_max_gas: Option<Uint64>,
+    /// minimum number of seconds between two clock ticks of the
+    /// router, which idles until funds get distributed to it.
+    /// the router is ticked as often as possible if unset.
+    #[serde(default)]
+    pub router_tick_interval: Option<Uint64>,
+    /// ibc fees of the modules overriding the chain minimum, where they
+    /// exceed it. stride bound ica transactions of the liquid staker may
+    /// e.g. require higher fees than the transfers of the router.
+    #[serde(default)]
+    pub ibc_fee_overrides: IbcFeeOverrides,
     pub lockup_period: Expiration,
     pub ls_info: LsInfo,
     pub ls_forwarder_config: CovenantPartyConfig,
@@ -28,6 +42,28 @@ pub struct InstantiateMsg {
     pub emergency_committee: Option<String>,
     pub covenant_party_config: InterchainCovenantParty,
     pub liquid_pooler_config: LiquidPoolerConfig,
+    /// template of the memo attached to ibc distributions of the router.
+    /// `{label}`, `{covenant}`, and `{party}` are replaced with the covenant
+    /// label, covenant address, and `party`, while `{denom}` and `{amount}`
+    /// are replaced with the distributed coin.
+    #[serde(default)]
+    pub ibc_memo_template: Option<String>,
+    /// set if the party already holds the liquid staked denom. the
+    /// `ls_forwarder_config` then forwards the ls denom straight to the
+    /// liquid pooler, no liquid staker is instantiated, and the splitter
+    /// routes the whole native contribution to the lp forwarder,
+    /// disregarding the configured `ls_share` and `native_share`.
+    #[serde(default)]
+    pub pre_staked_contribution: bool,
+}
+
+impl InstantiateMsg {
+    /// memo template of the router, with the covenant placeholders resolved
+    pub fn get_party_memo_template(&self, covenant: &Addr) -> Option<String> {
+        self.ibc_memo_template.as_ref().map(|template| {
+            resolve_covenant_placeholders(template, &self.label, covenant.as_str(), "party")
+        })
+    }
 }
 
 #[cw_serde]
@@ -37,6 +73,7 @@ pub enum LiquidPoolerConfig {
 }
 
 impl LiquidPoolerConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn to_instantiate2_msg(
         &self,
         instantiate2_helper: &Instantiate2HelperConfig,
@@ -45,6 +82,7 @@ impl LiquidPoolerConfig {
         clock_addr: String,
         holder_addr: String,
         pool_price_config: PoolPriceConfig,
+        emergency_committee: Option<String>,
     ) -> StdResult<WasmMsg> {
         match self {
             LiquidPoolerConfig::Osmosis(config) => Ok(config
@@ -52,6 +90,7 @@ impl LiquidPoolerConfig {
                     clock_addr.to_string(),
                     holder_addr.to_string(),
                     pool_price_config,
+                    emergency_committee,
                 )
                 .to_instantiate2_msg(instantiate2_helper, admin, label)?),
             LiquidPoolerConfig::Astroport(config) => Ok(config
@@ -59,6 +98,7 @@ impl LiquidPoolerConfig {
                     holder_addr.to_string(),
                     pool_price_config,
                     ContractOperationModeConfig::Permissioned(vec![clock_addr.to_string()]),
+                    emergency_committee,
                 )
                 .to_instantiate2_msg(instantiate2_helper, admin, label)?),
         }
@@ -101,10 +141,10 @@ impl CovenantPartyConfig {
                 destination_receiver_addr: config.party_receiver_addr.to_string(),
                 ibc_transfer_timeout: config.ibc_transfer_timeout,
                 denom_to_pfm_map: BTreeMap::new(),
+                memo_template: None,
+                wasm_hook: None,
             }),
-            CovenantPartyConfig::Native(config) => {
-                ReceiverConfig::Native(config.party_receiver_addr.to_string())
-            }
+            CovenantPartyConfig::Native(config) => config.to_receiver_config(),
         }
     }
 
@@ -172,6 +212,16 @@ impl Default for Timeouts {
     }
 }
 
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// admin-only. instantiates a parallel set of the covenant contracts
+    /// from the currently stored contract codes, without routing any funds,
+    /// and migrates each of them to its candidate code. reports whether each
+    /// of them deployed as planned and runs the candidate code after migrating.
+    /// the shadow contracts are handed over to the covenant admin afterwards.
+    ShadowDeploy { codes: CovenantContractCodeIds },
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -193,6 +243,13 @@ pub enum QueryMsg {
     InterchainRouterAddress {},
     #[returns(CovenantContractCodeIds)]
     ContractCodes {},
+    /// returns the ordered instantiation messages emitted by the covenant,
+    /// along with the code checksums and derived addresses they relied on
+    #[returns(DeploymentPlan)]
+    DeploymentPlan {},
+    /// returns the report of the latest shadow deployment, if any
+    #[returns(Option<ShadowDeployment>)]
+    ShadowDeployment {},
 }
 
 #[allow(clippy::large_enum_variant)]
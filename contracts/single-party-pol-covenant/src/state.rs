@@ -1,5 +1,6 @@
 use crate::msg::CovenantContractCodeIds;
 use cosmwasm_std::Addr;
+use covenant_utils::plan::{DeploymentPlan, ShadowDeployment};
 use cw_storage_plus::Item;
 
 pub const COVENANT_CLOCK_ADDR: Item<Addr> = Item::new("covenant_clock_addr");
@@ -12,3 +13,7 @@ pub const LP_FORWARDER_ADDR: Item<Addr> = Item::new("lp_forwarder_addr");
 pub const ROUTER_ADDR: Item<Addr> = Item::new("router_addr");
 
 pub const CONTRACT_CODES: Item<CovenantContractCodeIds> = Item::new("contract_codes");
+
+/// ordered instantiation messages emitted by the covenant upon instantiation
+pub const DEPLOYMENT_PLAN: Item<DeploymentPlan> = Item::new("deployment_plan");
+pub const SHADOW_DEPLOYMENT: Item<ShadowDeployment> = Item::new("shadow_deployment");
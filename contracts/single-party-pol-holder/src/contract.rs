@@ -1,13 +1,17 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, to_json_binary, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    ensure, to_json_binary, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdResult,
 };
+use covenant_utils::clock::ensure_single_trigger_per_block;
+use covenant_utils::dashboard::query_pool_position;
+use covenant_utils::deadline::Deadline;
 use covenant_utils::withdraw_lp_helper::{generate_withdraw_msg, EMERGENCY_COMMITTEE_ADDR};
 use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::msg::{DashboardResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{LOCKUP_PERIOD, POOLER_ADDRESS, WITHDRAWER, WITHDRAW_STATE, WITHDRAW_TO};
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -51,7 +55,7 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Withdrawer {} => Ok(to_json_binary(&WITHDRAWER.may_load(deps.storage)?)?),
         QueryMsg::WithdrawTo {} => Ok(to_json_binary(&WITHDRAW_TO.may_load(deps.storage)?)?),
@@ -60,6 +64,50 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             &EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?,
         )?),
         QueryMsg::LockupConfig {} => Ok(to_json_binary(&LOCKUP_PERIOD.load(deps.storage)?)?),
+        QueryMsg::TimeToExpiry {} => Ok(to_json_binary(
+            &Deadline::from(LOCKUP_PERIOD.load(deps.storage)?).time_to_expiry(&env.block),
+        )?),
+        QueryMsg::Dashboard {} => Ok(to_json_binary(&query_dashboard(deps, env)?)?),
+        QueryMsg::ClaimPreview { party } => {
+            Ok(to_json_binary(&query_claim_preview(deps, env, party)?)?)
+        }
+    }
+}
+
+fn query_dashboard(deps: Deps, env: Env) -> StdResult<DashboardResponse> {
+    let pooler_address = POOLER_ADDRESS.load(deps.storage)?;
+    let pool_position = query_pool_position(deps.querier, &pooler_address);
+    let claimable = match &pool_position {
+        Some(position) => position.estimate_share(deps.querier, Decimal::one())?,
+        None => vec![],
+    };
+
+    Ok(DashboardResponse {
+        withdrawer: WITHDRAWER.load(deps.storage)?,
+        withdraw_to: WITHDRAW_TO.load(deps.storage)?,
+        emergency_committee_addr: EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?,
+        pooler_address,
+        lockup: Deadline::from(LOCKUP_PERIOD.load(deps.storage)?).time_to_expiry(&env.block),
+        withdraw_in_progress: WITHDRAW_STATE.may_load(deps.storage)?.is_some(),
+        pool_position,
+        claimable,
+    })
+}
+
+/// simulates the withdraw path of a claim, which withdraws the
+/// whole position of the liquid pooler to the withdrawer
+fn query_claim_preview(deps: Deps, env: Env, party: String) -> StdResult<Vec<Coin>> {
+    if WITHDRAWER.load(deps.storage)? != party
+        || !LOCKUP_PERIOD.load(deps.storage)?.is_expired(&env.block)
+        || WITHDRAW_STATE.may_load(deps.storage)?.is_some()
+    {
+        return Ok(vec![]);
+    }
+
+    let pooler_address = POOLER_ADDRESS.load(deps.storage)?;
+    match query_pool_position(deps.querier, &pooler_address) {
+        Some(position) => position.estimate_share(deps.querier, Decimal::one()),
+        None => Ok(vec![]),
     }
 }
 
@@ -82,6 +130,7 @@ fn try_claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Con
     if WITHDRAW_STATE.load(deps.storage).is_ok() {
         return Err(ContractError::WithdrawAlreadyStarted {});
     }
+    ensure_single_trigger_per_block(deps.storage, &env.block, "claim")?;
 
     let lockup_period = LOCKUP_PERIOD.load(deps.storage)?;
     ensure!(
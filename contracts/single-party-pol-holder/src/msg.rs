@@ -1,7 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{to_json_binary, Addr, Binary, StdResult, WasmMsg};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, StdResult, WasmMsg};
 use covenant_macros::{covenant_holder_distribute, covenant_holder_emergency_withdraw};
-use covenant_utils::instantiate2_helper::Instantiate2HelperConfig;
+use covenant_utils::{
+    dashboard::PoolPosition, deadline::TimeToExpiry, instantiate2_helper::Instantiate2HelperConfig,
+};
 use cw_utils::Expiration;
 
 #[cw_serde]
@@ -60,6 +62,31 @@ pub enum QueryMsg {
     EmergencyCommitteeAddr {},
     #[returns(Expiration)]
     LockupConfig {},
+    /// time left until the lockup period expires
+    #[returns(TimeToExpiry)]
+    TimeToExpiry {},
+    /// aggregated holder state, for frontends to fetch in a single query
+    #[returns(DashboardResponse)]
+    Dashboard {},
+    /// underlying assets the party would receive if it claimed now
+    #[returns(Vec<Coin>)]
+    ClaimPreview { party: String },
+}
+
+#[cw_serde]
+pub struct DashboardResponse {
+    pub withdrawer: Addr,
+    pub withdraw_to: Addr,
+    pub emergency_committee_addr: Option<Addr>,
+    pub pooler_address: Addr,
+    /// time left until the lockup period expires
+    pub lockup: TimeToExpiry,
+    /// true if a withdrawal is in progress
+    pub withdraw_in_progress: bool,
+    /// LP position of the liquid pooler, if it can be resolved
+    pub pool_position: Option<PoolPosition>,
+    /// estimated underlying assets claimable by the withdrawer
+    pub claimable: Vec<Coin>,
 }
 
 #[cw_serde]
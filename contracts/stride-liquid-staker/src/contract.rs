@@ -5,21 +5,26 @@ use cosmwasm_std::{
     to_json_binary, to_json_string, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
     StdError, StdResult, Uint128,
 };
-use covenant_utils::neutron::{self, get_proto_coin, RemoteChainInfo, SudoPayload};
-use covenant_utils::{
-    clock::{enqueue_msg, verify_clock},
-    ica::{
-        get_ica, msg_with_sudo_callback, prepare_sudo_payload, query_ica_registration_fee,
-        sudo_error, sudo_open_ack, sudo_response, sudo_timeout, INTERCHAIN_ACCOUNT_ID,
-    },
+use covenant_ica::{
+    registration::register_ica,
+    state::{get_ica, load_ica_state, IcaState},
+    sudo::{handle_sudo, prepare_sudo_payload, submit_ica_tx},
+    INTERCHAIN_ACCOUNT_ID,
+};
+use covenant_utils::clock::{
+    enqueue_msg, ensure_single_trigger_per_block, increment_tick_nonce, verify_clock,
+};
+use covenant_utils::mock_ibc::get_contract_version;
+use covenant_utils::neutron::{
+    self, get_proto_coin, query_ibc_fee_with_override, validate_ibc_fee_override, RemoteChainInfo,
+    SudoPayload,
 };
 use cw2::set_contract_version;
-use neutron_sdk::query::min_ibc_fee::MinIbcFeeResponse;
 
 use crate::helpers::{Autopilot, AutopilotConfig};
 use crate::msg::{ContractState, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{
-    LiquidStakerIcaStateHelper, CLOCK_ADDRESS, CONTRACT_STATE, INTERCHAIN_ACCOUNTS, NEXT_CONTRACT,
+    LiquidStakerIcaHooks, CLOCK_ADDRESS, CONTRACT_STATE, IBC_FEE_OVERRIDE, NEXT_CONTRACT,
     REMOTE_CHAIN_INFO,
 };
 pub const SUDO_PAYLOAD_REPLY_ID: u64 = 1u64;
@@ -43,7 +48,11 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_contract_version(
+        deps.storage,
+        CONTRACT_NAME,
+        get_contract_version(CONTRACT_VERSION),
+    )?;
 
     // validate the addresses
     let clock_addr = deps.api.addr_validate(&msg.clock_address)?;
@@ -60,6 +69,10 @@ pub fn instantiate(
     };
     REMOTE_CHAIN_INFO.save(deps.storage, &remote_chain_info)?;
     CONTRACT_STATE.save(deps.storage, &ContractState::Instantiated)?;
+    if let Some(ibc_fee) = &msg.ibc_fee_override {
+        validate_ibc_fee_override(ibc_fee)?;
+        IBC_FEE_OVERRIDE.save(deps.storage, ibc_fee)?;
+    }
 
     Ok(Response::default()
         .add_message(enqueue_msg(clock_addr.as_str())?)
@@ -77,14 +90,12 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
     match msg {
-        ExecuteMsg::Tick {} => try_tick(deps, env, info),
+        ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
+            Ok(try_tick(deps, env, info)?.add_attribute("tick_nonce", tick_nonce.to_string()))
+        }
         ExecuteMsg::Transfer { amount } => {
-            let ica_address = get_ica(
-                &LiquidStakerIcaStateHelper,
-                deps.storage,
-                env.contract.address.as_ref(),
-                INTERCHAIN_ACCOUNT_ID,
-            );
+            let ica_address = get_ica(deps.storage);
             match ica_address {
                 Ok(_) => try_execute_transfer(deps, env, info, amount),
                 Err(_) => Ok(Response::default()
@@ -110,20 +121,18 @@ fn try_tick(deps: ExecuteDeps, env: Env, info: MessageInfo) -> NeutronResult<Res
 /// registers an interchain account on stride with port_id associated with `INTERCHAIN_ACCOUNT_ID`
 fn try_register_stride_ica(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<NeutronMsg>> {
     let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
-    let ica_registration_fee = query_ica_registration_fee(deps.querier)?;
-    let register: NeutronMsg = NeutronMsg::register_interchain_account(
+    // the account gets activated in the sudo_open_ack callback
+    let register = register_ica(
+        &LiquidStakerIcaHooks,
+        deps.storage,
+        deps.querier,
+        &env,
         remote_chain_info.connection_id,
-        INTERCHAIN_ACCOUNT_ID.to_string(),
-        Some(ica_registration_fee),
-    );
-    let key = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
-
-    // we are saving empty data here because we handle response of registering ICA in sudo_open_ack method
-    INTERCHAIN_ACCOUNTS.save(deps.storage, key, &None)?;
+    )?;
 
     Ok(Response::new()
         .add_attribute("method", "try_register_stride_ica")
-        .add_message(register))
+        .add_messages(register))
 }
 
 /// this is a permisionless transfer method. once liquid staked funds are in this
@@ -132,9 +141,18 @@ fn try_register_stride_ica(deps: ExecuteDeps, env: Env) -> NeutronResult<Respons
 fn try_execute_transfer(
     deps: ExecuteDeps,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     amount: Uint128,
 ) -> NeutronResult<Response<NeutronMsg>> {
+    // transfers submit an ica tx, which must not be repeated by retries
+    // landing in the same block. the transfer is permissionless, so the
+    // guard is kept per sender for others not to be locked out.
+    ensure_single_trigger_per_block(
+        deps.storage,
+        &env.block,
+        &format!("transfer_{}", info.sender),
+    )?;
+
     // first we verify whether the next contract is ready for receiving the funds
     let next_contract = NEXT_CONTRACT.load(deps.storage)?;
     let deposit_address_query = deps
@@ -149,12 +167,15 @@ fn try_execute_transfer(
     };
 
     let port_id = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
-    let interchain_account = INTERCHAIN_ACCOUNTS.load(deps.storage, port_id.clone())?;
-    let min_fee_query_response: MinIbcFeeResponse =
-        deps.querier.query(&NeutronQuery::MinIbcFee {}.into())?;
-
-    match interchain_account {
-        Some((address, controller_conn_id)) => {
+    let ica_state = load_ica_state(deps.storage)?;
+    let min_ibc_fee_config =
+        query_ibc_fee_with_override(deps.querier, IBC_FEE_OVERRIDE.may_load(deps.storage)?)?;
+
+    match ica_state {
+        IcaState::Active {
+            address,
+            controller_connection_id: controller_conn_id,
+        } => {
             let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
 
             // inner MsgTransfer that will be sent from stride to neutron.
@@ -179,19 +200,12 @@ fn try_execute_transfer(
 
             // wrap the protobuf of MsgTransfer into a message to be executed
             // by our interchain account
-            let submit_msg = NeutronMsg::submit_tx(
+            let sudo_msgs = submit_ica_tx(
+                deps.storage,
                 controller_conn_id,
-                INTERCHAIN_ACCOUNT_ID.to_string(),
                 vec![protobuf],
-                "".to_string(),
                 remote_chain_info.ica_timeout.u64(),
-                min_fee_query_response.min_fee,
-            );
-            let state_helper = LiquidStakerIcaStateHelper;
-            let sudo_msg = msg_with_sudo_callback(
-                &state_helper,
-                deps,
-                submit_msg,
+                min_ibc_fee_config.ibc_fee,
                 SudoPayload {
                     port_id,
                     message: "permisionless_transfer".to_string(),
@@ -199,10 +213,10 @@ fn try_execute_transfer(
                 SUDO_PAYLOAD_REPLY_ID,
             )?;
             Ok(Response::default()
-                .add_submessage(sudo_msg)
+                .add_submessages(sudo_msgs)
                 .add_attribute("method", "try_execute_transfer"))
         }
-        None => {
+        _ => {
             // I can't think of a case of how we could end up here as `sudo_open_ack`
             // callback advances the state to `ICACreated` and stores the ICA.
             // just in case, we revert the state to `Instantiated` to restart the flow.
@@ -215,27 +229,13 @@ fn try_execute_transfer(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> NeutronResult<Binary> {
+pub fn query(deps: QueryDeps, _env: Env, msg: QueryMsg) -> NeutronResult<Binary> {
     match msg {
         QueryMsg::ClockAddress {} => Ok(to_json_binary(&CLOCK_ADDRESS.may_load(deps.storage)?)?),
-        QueryMsg::IcaAddress {} => Ok(to_json_binary(
-            &get_ica(
-                &LiquidStakerIcaStateHelper,
-                deps.storage,
-                env.contract.address.as_str(),
-                INTERCHAIN_ACCOUNT_ID,
-            )?
-            .0,
-        )?),
+        QueryMsg::IcaAddress {} => Ok(to_json_binary(&get_ica(deps.storage)?.0)?),
         QueryMsg::ContractState {} => Ok(to_json_binary(&CONTRACT_STATE.may_load(deps.storage)?)?),
         QueryMsg::DepositAddress {} => {
-            let ica = get_ica(
-                &LiquidStakerIcaStateHelper,
-                deps.storage,
-                env.contract.address.as_str(),
-                INTERCHAIN_ACCOUNT_ID,
-            )?
-            .0;
+            let ica = get_ica(deps.storage)?.0;
 
             let autopilot = Autopilot {
                 autopilot: AutopilotConfig {
@@ -254,15 +254,12 @@ pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> NeutronResult<Binary>
         QueryMsg::RemoteChainInfo {} => {
             Ok(to_json_binary(&REMOTE_CHAIN_INFO.may_load(deps.storage)?)?)
         }
+        QueryMsg::IbcFeeOverride {} => {
+            Ok(to_json_binary(&IBC_FEE_OVERRIDE.may_load(deps.storage)?)?)
+        }
         QueryMsg::NextMemo {} => {
             // 1. receiver = query ICA
-            let ica = get_ica(
-                &LiquidStakerIcaStateHelper,
-                deps.storage,
-                env.contract.address.as_str(),
-                INTERCHAIN_ACCOUNT_ID,
-            )?
-            .0;
+            let ica = get_ica(deps.storage)?.0;
 
             let autopilot = Autopilot {
                 autopilot: AutopilotConfig {
@@ -282,42 +279,14 @@ pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> NeutronResult<Binary>
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> Result<Response<NeutronMsg>, StdError> {
-    match msg {
-        // For handling successful (non-error) acknowledgements.
-        SudoMsg::Response { request, data } => sudo_response(request, data),
-
-        // For handling error acknowledgements.
-        SudoMsg::Error { request, details } => sudo_error(request, details),
-
-        // For handling error timeouts.
-        SudoMsg::Timeout { request } => {
-            sudo_timeout(&LiquidStakerIcaStateHelper, deps, env, request)
-        }
-
-        // For handling successful registering of ICA
-        SudoMsg::OpenAck {
-            port_id,
-            channel_id,
-            counterparty_channel_id,
-            counterparty_version,
-        } => sudo_open_ack(
-            &LiquidStakerIcaStateHelper,
-            deps,
-            env,
-            port_id,
-            channel_id,
-            counterparty_channel_id,
-            counterparty_version,
-        ),
-        _ => Ok(Response::default()),
-    }
+pub fn sudo(deps: ExecuteDeps, _env: Env, msg: SudoMsg) -> Result<Response<NeutronMsg>, StdError> {
+    handle_sudo(&LiquidStakerIcaHooks, deps, msg)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: ExecuteDeps, env: Env, msg: Reply) -> StdResult<Response<NeutronMsg>> {
+pub fn reply(deps: ExecuteDeps, _env: Env, msg: Reply) -> StdResult<Response<NeutronMsg>> {
     match msg.id {
-        SUDO_PAYLOAD_REPLY_ID => prepare_sudo_payload(&LiquidStakerIcaStateHelper, deps, env, msg),
+        SUDO_PAYLOAD_REPLY_ID => prepare_sudo_payload(deps, msg),
         _ => Err(StdError::generic_err(format!(
             "unsupported reply message id {}",
             msg.id
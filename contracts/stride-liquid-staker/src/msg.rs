@@ -5,6 +5,7 @@ use covenant_macros::{
     covenant_remote_chain,
 };
 use covenant_utils::{instantiate2_helper::Instantiate2HelperConfig, neutron::RemoteChainInfo};
+use neutron_sdk::bindings::msg::IbcFee;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -36,6 +37,11 @@ pub struct InstantiateMsg {
     /// if the ICA times out, the destination chain receiving the funds
     /// will also receive the IBC packet with an expired timestamp.
     pub ibc_transfer_timeout: Uint64,
+    /// IBC fee to pay for the ICA transactions instead of the chain
+    /// minimum, where it exceeds it. Stride bound ICA transactions
+    /// may require higher fees than plain transfers.
+    #[serde(default)]
+    pub ibc_fee_override: Option<IbcFee>,
 }
 
 impl InstantiateMsg {
@@ -76,6 +82,8 @@ pub enum QueryMsg {
     ContractState {},
     #[returns(String)]
     NextMemo {},
+    #[returns(Option<IbcFee>)]
+    IbcFeeOverride {},
 }
 
 #[cw_serde]
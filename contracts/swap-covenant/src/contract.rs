@@ -1,25 +1,36 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, to_json_binary, to_json_string, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult, WasmMsg,
+    coin, ensure, to_json_binary, to_json_string, Binary, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Reply, Response, StdError, StdResult, WasmMsg,
 };
 use covenant_utils::{
-    instantiate2_helper::get_instantiate2_salt_and_address, op_mode::ContractOperationModeConfig,
-    split::remap_splits, CovenantPartiesConfig, CovenantTerms, SwapCovenantTerms,
+    decommission::generate_decommission_msg,
+    instantiate2_helper::get_instantiate2_salt_and_address,
+    op_mode::ContractOperationModeConfig,
+    plan::{
+        DeploymentPlan, DeploymentStep, ShadowDeployment, SHADOW_DEPLOY_REPLY_ID,
+        SHADOW_MIGRATE_REPLY_ID,
+    },
+    split::{remap_splits, validate_covenant_splits},
+    CovenantPartiesConfig, CovenantTerms, SwapCovenantTerms,
 };
 use cw2::set_contract_version;
+use valence_native_splitter::msg::CompletionConfig;
 use valence_swap_holder::msg::RefundConfig;
 
 use crate::{
     error::ContractError,
-    msg::{CovenantPartyConfig, InstantiateMsg, MigrateMsg, QueryMsg, RouterMigrateMsg},
+    msg::{
+        CovenantContractCodes, CovenantPartyConfig, ExecuteMsg, InstantiateMsg, MigrateMsg,
+        QueryMsg, RouterMigrateMsg,
+    },
     state::{
         CONTRACT_CODES, COVENANT_CLOCK_ADDR, COVENANT_INTERCHAIN_SPLITTER_ADDR,
-        COVENANT_SWAP_HOLDER_ADDR, PARTY_A_IBC_FORWARDER_ADDR, PARTY_A_ROUTER_ADDR,
-        PARTY_B_IBC_FORWARDER_ADDR, PARTY_B_ROUTER_ADDR,
+        COVENANT_SWAP_HOLDER_ADDR, DEPLOYMENT_PLAN, PARTY_A_IBC_FORWARDER_ADDR,
+        PARTY_A_ROUTER_ADDR, PARTY_B_IBC_FORWARDER_ADDR, PARTY_B_ROUTER_ADDR, SHADOW_DEPLOYMENT,
     },
 };
 
@@ -45,6 +56,8 @@ pub fn instantiate(
     let mut resp = Response::default().add_attribute("method", "instantiate_swap_covenant");
 
     let creator_address = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let party_a_memo_template = msg.get_party_memo_template(&env.contract.address, "party_a");
+    let party_b_memo_template = msg.get_party_memo_template(&env.contract.address, "party_b");
     let covenant_denoms: BTreeSet<String> = msg.splits.keys().map(|k| k.to_string()).collect();
 
     // first we generate the instantiate2 addresses for each contract
@@ -118,15 +131,21 @@ pub fn instantiate(
         format!("{}_party_a_router", msg.label),
         env.contract.address.to_string(),
         clock_instantiate2_config.addr.clone(),
+        holder_instantiate2_config.addr.clone(),
         covenant_denoms.clone(),
         party_a_router_instantiate2_config.clone(),
+        party_a_memo_template.clone(),
+        msg.ibc_fee_overrides.router.clone(),
     )?;
     let party_b_router_instantiate2_msg = msg.party_b_config.get_router_instantiate2_wasm_msg(
         format!("{}_party_b_router", msg.label),
         env.contract.address.to_string(),
         clock_instantiate2_config.addr.clone(),
+        holder_instantiate2_config.addr.clone(),
         covenant_denoms.clone(),
         party_b_router_instantiate2_config.clone(),
+        party_b_memo_template.clone(),
+        msg.ibc_fee_overrides.router.clone(),
     )?;
 
     // we validate that denoms explicitly defined in splits are the
@@ -147,6 +166,29 @@ pub fn instantiate(
             format!("{:?}", covenant_denoms)
         )
     );
+    // and that every split is between the party receivers
+    let mut expected_denoms = BTreeSet::from([
+        msg.party_a_config.get_native_denom(),
+        msg.party_b_config.get_native_denom(),
+    ]);
+    expected_denoms.extend(msg.reward_denoms.clone().unwrap_or_default());
+    validate_covenant_splits(
+        &msg.splits,
+        &expected_denoms,
+        (
+            &msg.party_a_config.get_final_receiver_address(),
+            &msg.party_b_config.get_final_receiver_address(),
+        ),
+    )?;
+    if msg.direct_settlement {
+        validate_direct_settlement_split(&msg, &msg.party_a_config, &msg.party_b_config)?;
+        validate_direct_settlement_split(&msg, &msg.party_b_config, &msg.party_a_config)?;
+    }
+    // directly settled contributions never reach the splitter
+    ensure!(
+        !(msg.direct_settlement && msg.complete_on_settlement),
+        ContractError::CompletionWithDirectSettlement {}
+    );
 
     let splitter_instantiate2_msg = valence_native_splitter::msg::InstantiateMsg {
         clock_address: clock_instantiate2_config.addr.to_string(),
@@ -170,6 +212,26 @@ pub fn instantiate(
             )?),
             None => None,
         },
+        emergency_committee: None,
+        owner: None,
+        completion_config: match msg.complete_on_settlement {
+            true => Some(CompletionConfig {
+                expected_amounts: vec![
+                    coin(
+                        msg.party_a_config.get_contribution().amount.u128(),
+                        msg.party_a_config.get_native_denom(),
+                    ),
+                    coin(
+                        msg.party_b_config.get_contribution().amount.u128(),
+                        msg.party_b_config.get_native_denom(),
+                    ),
+                ],
+            }),
+            false => None,
+        },
+        receiver_types: BTreeMap::new(),
+        op_admin: None,
+        validate_denoms: msg.validate_denoms,
     }
     .to_instantiate2_msg(
         &splitter_instantiate2_config,
@@ -180,8 +242,8 @@ pub fn instantiate(
     let holder_instantiate2_msg = valence_swap_holder::msg::InstantiateMsg {
         lockup_config: msg.lockup_config,
         parties_config: CovenantPartiesConfig {
-            party_a: msg.party_a_config.to_covenant_party(),
-            party_b: msg.party_b_config.to_covenant_party(),
+            party_a: msg.party_a_config.to_covenant_party(party_a_memo_template),
+            party_b: msg.party_b_config.to_covenant_party(party_b_memo_template),
         },
         covenant_terms: CovenantTerms::TokenSwap(SwapCovenantTerms {
             party_a_amount: msg.party_a_config.get_contribution().amount,
@@ -193,6 +255,9 @@ pub fn instantiate(
             party_a_refund_address: party_a_router_instantiate2_config.addr.to_string(),
             party_b_refund_address: party_b_router_instantiate2_config.addr.to_string(),
         },
+        direct_settlement: msg.direct_settlement,
+        owner: None,
+        validate_denoms: msg.validate_denoms,
     }
     .to_instantiate2_msg(
         &holder_instantiate2_config,
@@ -237,6 +302,8 @@ pub fn instantiate(
                 .to_string()]),
             next_contract: holder_instantiate2_config.addr.to_string(),
             fallback_address: msg.fallback_address.clone(),
+            milestone_callback: None,
+            ibc_fee_override: msg.ibc_fee_overrides.forwarder.clone(),
         }
         .to_instantiate2_msg(
             &party_a_forwarder_instantiate2_config,
@@ -280,6 +347,8 @@ pub fn instantiate(
                 .to_string()]),
             next_contract: holder_instantiate2_config.addr.to_string(),
             fallback_address: msg.fallback_address,
+            milestone_callback: None,
+            ibc_fee_override: msg.ibc_fee_overrides.forwarder.clone(),
         }
         .to_instantiate2_msg(
             &party_b_forwarder_instantiate2_config,
@@ -300,6 +369,21 @@ pub fn instantiate(
             tick_max_gas: msg.clock_tick_max_gas,
             whitelist: clock_whitelist,
             initial_queue: clock_initial_queue,
+            tick_intervals: msg
+                .router_tick_interval
+                .map(|interval| {
+                    vec![
+                        (
+                            party_a_router_instantiate2_config.addr.to_string(),
+                            interval,
+                        ),
+                        (
+                            party_b_router_instantiate2_config.addr.to_string(),
+                            interval,
+                        ),
+                    ]
+                })
+                .unwrap_or_default(),
         }
         .to_instantiate2_msg(
             clock_instantiate2_config.code,
@@ -309,6 +393,10 @@ pub fn instantiate(
         )?,
     );
 
+    // build the deployment plan out of the ordered instantiation messages
+    let deployment_plan = DeploymentPlan::build(deps.as_ref(), &creator_address, messages)?;
+    DEPLOYMENT_PLAN.save(deps.storage, &deployment_plan)?;
+
     // save the contract addresses
     COVENANT_CLOCK_ADDR.save(deps.storage, &clock_instantiate2_config.addr)?;
     PARTY_A_ROUTER_ADDR.save(deps.storage, &party_a_router_instantiate2_config.addr)?;
@@ -334,8 +422,109 @@ pub fn instantiate(
             "splitter_address",
             splitter_instantiate2_config.addr.to_string(),
         )
-        .add_attribute("instantiation_messages", to_json_string(&messages)?)
-        .add_messages(messages))
+        .add_attribute(
+            "instantiation_messages",
+            to_json_string(&deployment_plan.messages())?,
+        )
+        .add_messages(deployment_plan.messages()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ShadowDeploy { codes } => try_shadow_deploy(deps, env, info, codes),
+    }
+}
+
+/// instantiates a shadow copy of the deployment plan from the currently
+/// stored contract codes, and migrates it to the candidate codes
+fn try_shadow_deploy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    codes: CovenantContractCodes,
+) -> Result<Response, ContractError> {
+    let contract_codes = CONTRACT_CODES.load(deps.storage)?;
+    let (shadow_deployment, messages) = ShadowDeployment::deploy(
+        deps.as_ref(),
+        &env,
+        &info,
+        &DEPLOYMENT_PLAN.load(deps.storage)?,
+        SHADOW_DEPLOYMENT.may_load(deps.storage)?,
+        |step| get_shadow_code_id(&contract_codes, step),
+        |step| get_shadow_code_id(&codes, step),
+    )?;
+    SHADOW_DEPLOYMENT.save(deps.storage, &shadow_deployment)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_shadow_deploy")
+        .add_attribute("nonce", shadow_deployment.nonce.to_string())
+        .add_submessages(messages))
+}
+
+/// maps a deployment step to the code id of its contract
+fn get_shadow_code_id(codes: &CovenantContractCodes, step: &DeploymentStep) -> StdResult<u64> {
+    match step.salt.as_slice() {
+        CLOCK_SALT => Ok(codes.clock),
+        HOLDER_SALT => Ok(codes.holder),
+        SPLITTER_SALT => Ok(codes.splitter),
+        PARTY_A_ROUTER_SALT => Ok(codes.party_a_router),
+        PARTY_B_ROUTER_SALT => Ok(codes.party_b_router),
+        PARTY_A_FORWARDER_SALT => Ok(codes.party_a_forwarder),
+        PARTY_B_FORWARDER_SALT => Ok(codes.party_b_forwarder),
+        _ => Err(StdError::generic_err(format!(
+            "unknown deployment step {}",
+            step.label
+        ))),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SHADOW_DEPLOY_REPLY_ID | SHADOW_MIGRATE_REPLY_ID => {
+            let mut shadow_deployment = SHADOW_DEPLOYMENT.load(deps.storage)?;
+            let (step, messages) = shadow_deployment.record_reply(deps.as_ref(), &env, msg)?;
+            SHADOW_DEPLOYMENT.save(deps.storage, &shadow_deployment)?;
+
+            Ok(Response::default()
+                .add_attribute("method", "shadow_deploy_reply")
+                .add_attributes(shadow_deployment.get_reply_attributes(&step)?)
+                .add_submessages(messages))
+        }
+        _ => Err(ContractError::UnknownReplyId {}),
+    }
+}
+
+/// direct settlement delivers the entire contribution of a party to
+/// its counterparty, so the split of that denom must do the same
+fn validate_direct_settlement_split(
+    msg: &InstantiateMsg,
+    party: &CovenantPartyConfig,
+    counterparty: &CovenantPartyConfig,
+) -> Result<(), ContractError> {
+    let denom = party.get_native_denom();
+    let counterparty_share = msg
+        .splits
+        .get(&denom)
+        .and_then(|split| {
+            split
+                .receivers
+                .get(&counterparty.get_final_receiver_address())
+        })
+        .copied()
+        .unwrap_or_default();
+
+    ensure!(
+        counterparty_share == Decimal::one(),
+        ContractError::DirectSettlementSplitError(denom)
+    );
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -397,6 +586,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             Ok(to_json_binary(&resp)?)
         }
         QueryMsg::ContractCodes {} => Ok(to_json_binary(&CONTRACT_CODES.load(deps.storage)?)?),
+        QueryMsg::DeploymentPlan {} => Ok(to_json_binary(&DEPLOYMENT_PLAN.load(deps.storage)?)?),
+        QueryMsg::ShadowDeployment {} => {
+            Ok(to_json_binary(&SHADOW_DEPLOYMENT.may_load(deps.storage)?)?)
+        }
     }
 }
 
@@ -505,6 +698,37 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response>
 
             Ok(resp.add_messages(migrate_msgs))
         }
+        MigrateMsg::Decommission {} => {
+            let holder = COVENANT_SWAP_HOLDER_ADDR.load(deps.storage)?;
+            let holder_state: valence_swap_holder::msg::ContractState =
+                deps.querier.query_wasm_smart(
+                    holder,
+                    &valence_swap_holder::msg::QueryMsg::ContractState {},
+                )?;
+            ensure!(
+                holder_state == valence_swap_holder::msg::ContractState::Complete,
+                StdError::generic_err("covenant is not complete")
+            );
+
+            let mut decommission_msgs = vec![generate_decommission_msg(
+                COVENANT_INTERCHAIN_SPLITTER_ADDR
+                    .load(deps.storage)?
+                    .to_string(),
+            )?];
+            for forwarder in [
+                PARTY_A_IBC_FORWARDER_ADDR.may_load(deps.storage)?,
+                PARTY_B_IBC_FORWARDER_ADDR.may_load(deps.storage)?,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                decommission_msgs.push(generate_decommission_msg(forwarder.to_string())?);
+            }
+
+            Ok(Response::default()
+                .add_attribute("method", "decommission")
+                .add_messages(decommission_msgs))
+        }
         MigrateMsg::UpdateCodeId { data: _ } => {
             // This is a migrate message to update code id,
             // Data is optional base64 that we can parse to any data we would like in the future
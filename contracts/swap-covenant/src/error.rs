@@ -30,4 +30,10 @@ pub enum ContractError {
 
     #[error("{0} contribution missing an explicit split configuration (got {1})")]
     DenomMisconfigurationError(String, String),
+
+    #[error("direct settlement requires {0} to be split entirely to its counterparty")]
+    DirectSettlementSplitError(String),
+
+    #[error("splitter can not complete on settlement with direct settlement enabled")]
+    CompletionWithDirectSettlement {},
 }
@@ -3,11 +3,16 @@ use std::collections::{BTreeMap, BTreeSet};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, Coin, StdResult, Uint64, WasmMsg};
 use covenant_utils::{
-    instantiate2_helper::Instantiate2HelperConfig, op_mode::ContractOperationModeConfig,
-    split::SplitConfig, CovenantParty, DestinationConfig, InterchainCovenantParty,
-    NativeCovenantParty, ReceiverConfig,
+    ibc_memo::resolve_covenant_placeholders,
+    instantiate2_helper::Instantiate2HelperConfig,
+    neutron::IbcFeeOverrides,
+    op_mode::ContractOperationModeConfig,
+    plan::{DeploymentPlan, ShadowDeployment},
+    split::SplitConfig,
+    CovenantParty, DestinationConfig, InterchainCovenantParty, NativeCovenantParty, ReceiverConfig,
 };
 use cw_utils::Expiration;
+use neutron_sdk::bindings::msg::IbcFee;
 
 pub const DEFAULT_TIMEOUT: u64 = 60 * 60 * 5; // 5 hours
 
@@ -17,12 +22,54 @@ pub struct InstantiateMsg {
     pub timeouts: Timeouts,
     pub contract_codes: SwapCovenantContractCodeIds,
     pub clock_tick_max_gas: Option<Uint64>,
+    /// minimum number of seconds between two clock ticks of the party
+    /// routers, which idle until funds get distributed to them.
+    /// routers are ticked as often as possible if unset.
+    #[serde(default)]
+    pub router_tick_interval: Option<Uint64>,
+    /// ibc fees of the party forwarders and interchain routers overriding the
+    /// chain minimum, where they exceed it. other overrides are not applicable.
+    #[serde(default)]
+    pub ibc_fee_overrides: IbcFeeOverrides,
     pub lockup_config: Expiration,
     pub party_a_config: CovenantPartyConfig,
     pub party_b_config: CovenantPartyConfig,
     pub splits: BTreeMap<String, SplitConfig>,
     pub fallback_split: Option<SplitConfig>,
     pub fallback_address: Option<String>,
+    /// denoms besides the party contributions that the covenant
+    /// may receive (e.g. rewards), and that are thus split as well
+    #[serde(default)]
+    pub reward_denoms: Option<Vec<String>>,
+    /// if enabled, the holder delivers each contribution directly to the
+    /// counterparty receiver instead of routing it through the splitter.
+    /// requires the splits of party contributions to be a pure swap.
+    #[serde(default)]
+    pub direct_settlement: bool,
+    /// if enabled, the splitter completes and dequeues from the clock once
+    /// it distributed both party contributions. leave it disabled if the
+    /// splitter should keep distributing funds received later (e.g. rewards).
+    #[serde(default)]
+    pub complete_on_settlement: bool,
+    /// template of the memo attached to ibc distributions to the
+    /// parties. `{label}`, `{covenant}`, and `{party}` are replaced
+    /// with the covenant label, covenant address, and party id, while
+    /// `{denom}` and `{amount}` are replaced with the distributed coin.
+    #[serde(default)]
+    pub ibc_memo_template: Option<String>,
+    /// if enabled, the holder requires the party denoms (and the splitter
+    /// the split denoms) to have a supply on neutron upon instantiation
+    #[serde(default)]
+    pub validate_denoms: bool,
+}
+
+impl InstantiateMsg {
+    /// memo template of the party, with the covenant placeholders resolved
+    pub fn get_party_memo_template(&self, covenant: &Addr, party: &str) -> Option<String> {
+        self.ibc_memo_template.as_ref().map(|template| {
+            resolve_covenant_placeholders(template, &self.label, covenant.as_str(), party)
+        })
+    }
 }
 
 #[cw_serde]
@@ -32,7 +79,7 @@ pub enum CovenantPartyConfig {
 }
 
 impl CovenantPartyConfig {
-    pub fn to_receiver_config(&self) -> ReceiverConfig {
+    pub fn to_receiver_config(&self, memo_template: Option<String>) -> ReceiverConfig {
         match self {
             CovenantPartyConfig::Interchain(config) => ReceiverConfig::Ibc(DestinationConfig {
                 local_to_destination_chain_channel_id: config
@@ -41,10 +88,10 @@ impl CovenantPartyConfig {
                 destination_receiver_addr: config.party_receiver_addr.to_string(),
                 ibc_transfer_timeout: config.ibc_transfer_timeout,
                 denom_to_pfm_map: config.denom_to_pfm_map.clone(),
+                memo_template,
+                wasm_hook: None,
             }),
-            CovenantPartyConfig::Native(config) => {
-                ReceiverConfig::Native(config.party_receiver_addr.to_string())
-            }
+            CovenantPartyConfig::Native(config) => config.to_receiver_config(),
         }
     }
 
@@ -55,17 +102,17 @@ impl CovenantPartyConfig {
         }
     }
 
-    pub fn to_covenant_party(&self) -> CovenantParty {
+    pub fn to_covenant_party(&self, memo_template: Option<String>) -> CovenantParty {
         match self {
             CovenantPartyConfig::Interchain(config) => CovenantParty {
                 addr: config.addr.to_string(),
                 native_denom: config.native_denom.to_string(),
-                receiver_config: self.to_receiver_config(),
+                receiver_config: self.to_receiver_config(memo_template),
             },
             CovenantPartyConfig::Native(config) => CovenantParty {
                 addr: config.addr.to_string(),
                 native_denom: config.native_denom.to_string(),
-                receiver_config: self.to_receiver_config(),
+                receiver_config: self.to_receiver_config(memo_template),
             },
         }
     }
@@ -91,13 +138,17 @@ impl CovenantPartyConfig {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn get_router_instantiate2_wasm_msg(
         &self,
         label: String,
         admin: String,
         clock_addr: Addr,
+        holder_addr: Addr,
         covenant_denoms: BTreeSet<String>,
         instantiate2_helper: Instantiate2HelperConfig,
+        memo_template: Option<String>,
+        ibc_fee_override: Option<IbcFee>,
     ) -> StdResult<WasmMsg> {
         match self {
             CovenantPartyConfig::Interchain(party) => {
@@ -108,11 +159,19 @@ impl CovenantPartyConfig {
                     destination_receiver_addr: party.party_receiver_addr.to_string(),
                     ibc_transfer_timeout: party.ibc_transfer_timeout,
                     denom_to_pfm_map: party.denom_to_pfm_map.clone(),
+                    memo_template,
+                    wasm_hook: None,
                 };
                 let instantiate_msg = valence_interchain_router::msg::InstantiateMsg {
                     clock_address: clock_addr.to_string(),
                     destination_config,
                     denoms: covenant_denoms,
+                    halt_config: None,
+                    holder_address: Some(holder_addr.to_string()),
+                    owner: None,
+                    denom_to_destination_map: BTreeMap::new(),
+                    min_route_amounts: BTreeMap::new(),
+                    ibc_fee_override,
                 };
                 Ok(instantiate_msg.to_instantiate2_msg(&instantiate2_helper, admin, label)?)
             }
@@ -122,7 +181,12 @@ impl CovenantPartyConfig {
                         clock_addr.to_string()
                     ]),
                     receiver_address: party.party_receiver_addr.to_string(),
+                    receiver_msg: party.receiver_msg.clone(),
                     denoms: covenant_denoms,
+                    halt_config: None,
+                    holder_address: Some(holder_addr.to_string()),
+                    min_route_amounts: BTreeMap::new(),
+                    cw20_tokens: BTreeSet::new(),
                 };
                 Ok(instantiate_msg.to_instantiate2_msg(&instantiate2_helper, admin, label)?)
             }
@@ -176,7 +240,14 @@ impl Default for Timeouts {
 }
 
 #[cw_serde]
-pub enum ExecuteMsg {}
+pub enum ExecuteMsg {
+    /// admin-only. instantiates a parallel set of the covenant contracts
+    /// from the currently stored contract codes, without routing any funds,
+    /// and migrates each of them to its candidate code. reports whether each
+    /// of them deployed as planned and runs the candidate code after migrating.
+    /// the shadow contracts are handed over to the covenant admin afterwards.
+    ShadowDeploy { codes: CovenantContractCodes },
+}
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -195,6 +266,13 @@ pub enum QueryMsg {
     PartyDepositAddress { party: String },
     #[returns(CovenantContractCodes)]
     ContractCodes {},
+    /// returns the ordered instantiation messages emitted by the covenant,
+    /// along with the code checksums and derived addresses they relied on
+    #[returns(DeploymentPlan)]
+    DeploymentPlan {},
+    /// returns the report of the latest shadow deployment, if any
+    #[returns(Option<ShadowDeployment>)]
+    ShadowDeployment {},
 }
 
 #[cw_serde]
@@ -209,6 +287,9 @@ pub enum MigrateMsg {
         party_a_forwarder: Box<Option<valence_ibc_forwarder::msg::MigrateMsg>>,
         party_b_forwarder: Box<Option<valence_ibc_forwarder::msg::MigrateMsg>>,
     },
+    /// decommissions the splitter and the party ibc forwarders
+    /// once the swap holder is complete
+    Decommission {},
     UpdateCodeId {
         data: Option<Binary>,
     },
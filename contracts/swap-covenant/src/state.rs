@@ -1,4 +1,5 @@
 use cosmwasm_std::Addr;
+use covenant_utils::plan::{DeploymentPlan, ShadowDeployment};
 use cw_storage_plus::Item;
 
 use crate::msg::CovenantContractCodes;
@@ -13,3 +14,7 @@ pub const PARTY_A_ROUTER_ADDR: Item<Addr> = Item::new("party_a_router_addr");
 pub const PARTY_B_ROUTER_ADDR: Item<Addr> = Item::new("party_b_router_addr");
 
 pub(crate) const CONTRACT_CODES: Item<CovenantContractCodes> = Item::new("contract_codes");
+
+/// ordered instantiation messages emitted by the covenant upon instantiation
+pub(crate) const DEPLOYMENT_PLAN: Item<DeploymentPlan> = Item::new("deployment_plan");
+pub(crate) const SHADOW_DEPLOYMENT: Item<ShadowDeployment> = Item::new("shadow_deployment");
@@ -1,26 +1,46 @@
 use cosmwasm_std::{
-    to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult, Uint128,
+    ensure, from_json, to_json_binary, Addr, Attribute, BankMsg, Binary, CosmosMsg, Deps, DepsMut,
+    Env, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128,
 };
 use covenant_utils::{
-    clock::{enqueue_msg, verify_clock},
-    CovenantTerms,
+    clock::{enqueue_msg, increment_tick_nonce, verify_clock},
+    deadline::Deadline,
+    denom::ensure_denoms_exist,
+    get_local_delivery_msg,
+    neutron::query_ibc_fee,
+    CovenantPartiesConfig, CovenantParty, CovenantTerms, ReceiverConfig,
+};
+use cw_utils::Expiration;
+use neutron_sdk::{
+    bindings::{
+        msg::{MsgIbcTransferResponse, NeutronMsg},
+        query::NeutronQuery,
+    },
+    sudo::msg::{RequestPacket, SudoMsg},
 };
 
 use crate::{
     error::ContractError,
-    msg::{ContractState, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{
+        ContractState, ExecuteMsg, InFlightSettlement, InstantiateMsg, MigrateMsg, QueryMsg,
+        RefundConfig, SettlementTransfer,
+    },
     state::{
-        CLOCK_ADDRESS, CONTRACT_STATE, COVENANT_TERMS, LOCKUP_CONFIG, NEXT_CONTRACT,
-        PARTIES_CONFIG, REFUND_CONFIG,
+        CLOCK_ADDRESS, CONTRACT_STATE, COVENANT_TERMS, DIRECT_SETTLEMENT, IN_FLIGHT_SETTLEMENTS,
+        LOCKUP_CONFIG, NEXT_CONTRACT, PARTIES_CONFIG, PENDING_SETTLEMENTS, REFUND_CONFIG,
+        TIMED_OUT_SETTLEMENTS,
     },
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
+use covenant_utils::mock_ibc::get_contract_version;
 use cw2::set_contract_version;
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const IBC_TRANSFER_REPLY_ID: u64 = 1;
+
+type ExecuteDeps<'a> = DepsMut<'a, NeutronQuery>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -29,11 +49,24 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_contract_version(
+        deps.storage,
+        CONTRACT_NAME,
+        get_contract_version(CONTRACT_VERSION),
+    )?;
 
     let next_contract = deps.api.addr_validate(&msg.next_contract)?;
     let clock_addr = deps.api.addr_validate(&msg.clock_address)?;
     msg.parties_config.validate_party_addresses(deps.api)?;
+    if msg.validate_denoms {
+        ensure_denoms_exist(
+            &deps.querier,
+            vec![
+                msg.parties_config.party_a.native_denom.as_str(),
+                msg.parties_config.party_b.native_denom.as_str(),
+            ],
+        )?;
+    }
     if msg.lockup_config.is_expired(&env.block) {
         return Err(ContractError::Std(StdError::generic_err(
             "past lockup config",
@@ -51,27 +84,69 @@ pub fn instantiate(
     COVENANT_TERMS.save(deps.storage, &msg.covenant_terms)?;
     CONTRACT_STATE.save(deps.storage, &ContractState::Instantiated)?;
     REFUND_CONFIG.save(deps.storage, &msg.refund_config)?;
+    DIRECT_SETTLEMENT.save(deps.storage, &msg.direct_settlement)?;
+    let ownership = cw_ownable::initialize_owner(deps.storage, deps.api, msg.owner.as_deref())?;
 
     Ok(Response::default()
         .add_message(enqueue_msg(clock_addr.as_str())?)
         .add_attribute("method", "swap_holder_instantiate")
-        .add_attributes(msg.get_response_attributes()))
+        .add_attributes(msg.get_response_attributes())
+        .add_attributes(ownership.into_attributes()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    deps: ExecuteDeps,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
+) -> Result<Response<NeutronMsg>, ContractError> {
     match msg {
-        ExecuteMsg::Tick {} => try_tick(deps, env, info),
+        ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
+            Ok(try_tick(deps, env, info)?.add_attribute("tick_nonce", tick_nonce.to_string()))
+        }
+        ExecuteMsg::UpdateConfig {
+            clock_addr,
+            next_contract,
+            lockup_config,
+            parites_config,
+            covenant_terms,
+            refund_config,
+            direct_settlement,
+        } => {
+            cw_ownable::assert_owner(deps.storage, &info.sender)?;
+            let attrs = update_config(
+                deps.into_empty(),
+                &env,
+                clock_addr,
+                next_contract,
+                lockup_config,
+                *parites_config,
+                covenant_terms,
+                refund_config,
+                direct_settlement,
+            )?;
+            Ok(Response::default()
+                .add_attribute("method", "update_config")
+                .add_attributes(attrs))
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            let ownership =
+                cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
+            Ok(Response::default()
+                .add_attribute("method", "update_ownership")
+                .add_attributes(ownership.into_attributes()))
+        }
     }
 }
 
 /// attempts to advance the state machine. performs `info.sender` validation
-fn try_tick(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+fn try_tick(
+    deps: ExecuteDeps,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response<NeutronMsg>, ContractError> {
     // Verify caller is the clock
     verify_clock(&info.sender, &CLOCK_ADDRESS.load(deps.storage)?)?;
 
@@ -79,6 +154,7 @@ fn try_tick(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Cont
     match current_state {
         ContractState::Instantiated => try_forward(deps, env, info.sender),
         ContractState::Expired => try_refund(deps, env),
+        ContractState::Settling => try_retry_settlements(deps, env),
         ContractState::Complete => Ok(Response::default()
             .add_attribute("contract_state", "complete")
             .add_attribute("method", "try_tick")),
@@ -87,7 +163,7 @@ fn try_tick(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Cont
 
 /// attempts to route any available covenant party contribution denoms to
 /// the parties that were responsible for contributing that denom.
-fn try_refund(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+fn try_refund(deps: ExecuteDeps, env: Env) -> Result<Response<NeutronMsg>, ContractError> {
     let parties = PARTIES_CONFIG.load(deps.storage)?;
     let refund_config = REFUND_CONFIG.load(deps.storage)?;
 
@@ -101,7 +177,7 @@ fn try_refund(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
         parties.party_b.native_denom,
     )?;
 
-    let refund_messages: Vec<CosmosMsg> =
+    let refund_messages: Vec<CosmosMsg<NeutronMsg>> =
         match (party_a_bal.amount.is_zero(), party_b_bal.amount.is_zero()) {
             // both balances empty, nothing to refund
             (true, true) => vec![],
@@ -135,7 +211,11 @@ fn try_refund(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
         .add_messages(refund_messages))
 }
 
-fn try_forward(mut deps: DepsMut, env: Env, clock_addr: Addr) -> Result<Response, ContractError> {
+fn try_forward(
+    mut deps: ExecuteDeps,
+    env: Env,
+    clock_addr: Addr,
+) -> Result<Response<NeutronMsg>, ContractError> {
     let lockup_config = LOCKUP_CONFIG.load(deps.storage)?;
     // check if covenant is expired
     if lockup_config.is_expired(&env.block) {
@@ -149,12 +229,14 @@ fn try_forward(mut deps: DepsMut, env: Env, clock_addr: Addr) -> Result<Response
     let parties = PARTIES_CONFIG.load(deps.storage)?;
     let CovenantTerms::TokenSwap(covenant_terms) = COVENANT_TERMS.load(deps.storage)?;
 
-    let mut party_a_coin = deps
-        .querier
-        .query_balance(env.contract.address.clone(), parties.party_a.native_denom)?;
-    let mut party_b_coin = deps
-        .querier
-        .query_balance(env.contract.address, parties.party_b.native_denom)?;
+    let mut party_a_coin = deps.querier.query_balance(
+        env.contract.address.clone(),
+        parties.party_a.native_denom.to_string(),
+    )?;
+    let mut party_b_coin = deps.querier.query_balance(
+        env.contract.address.clone(),
+        parties.party_b.native_denom.to_string(),
+    )?;
 
     if party_a_coin.amount < covenant_terms.party_a_amount {
         party_a_coin.amount = Uint128::zero();
@@ -168,6 +250,43 @@ fn try_forward(mut deps: DepsMut, env: Env, clock_addr: Addr) -> Result<Response
         return Err(ContractError::InsufficientFunds {});
     }
 
+    // with direct settlement each party receives the counterparty
+    // contribution without going through the next module
+    if DIRECT_SETTLEMENT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        let transfers = vec![
+            SettlementTransfer {
+                party: parties.party_b.addr.to_string(),
+                coin: party_a_coin,
+            },
+            SettlementTransfer {
+                party: parties.party_a.addr.to_string(),
+                coin: party_b_coin,
+            },
+        ];
+        let submessages = get_settlement_submessages(deps.branch(), &env, transfers)?;
+
+        // ibc transfers may time out, so the holder completes
+        // only once all of them are acknowledged
+        let response = Response::default()
+            .add_attribute("method", "try_forward")
+            .add_attribute("result", "direct_settlement")
+            .add_submessages(submessages);
+        if PENDING_SETTLEMENTS
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .is_empty()
+        {
+            let dequeue_msg =
+                ContractState::complete_and_dequeue(deps.branch(), clock_addr.as_str())?;
+            return Ok(response.add_message(dequeue_msg));
+        }
+        CONTRACT_STATE.save(deps.storage, &ContractState::Settling)?;
+        return Ok(response.add_attribute("contract_state", "settling"));
+    }
+
     // otherwise we are ready to forward the funds to the next module
     let amount = vec![party_a_coin, party_b_coin];
 
@@ -199,11 +318,345 @@ fn try_forward(mut deps: DepsMut, env: Env, clock_addr: Addr) -> Result<Response
         .add_message(dequeue_msg))
 }
 
+/// retries the settlement transfers that timed out. the holder
+/// completes once the in flight transfers are acknowledged.
+fn try_retry_settlements(
+    mut deps: ExecuteDeps,
+    env: Env,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let timed_out_settlements = TIMED_OUT_SETTLEMENTS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    TIMED_OUT_SETTLEMENTS.remove(deps.storage);
+
+    let retried_attributes: Vec<Attribute> = timed_out_settlements
+        .iter()
+        .map(|transfer| Attribute::new("retried", transfer.coin.to_string()))
+        .collect();
+    let submessages = get_settlement_submessages(deps.branch(), &env, timed_out_settlements)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_retry_settlements")
+        .add_attribute("contract_state", "settling")
+        .add_attributes(retried_attributes)
+        .add_submessages(submessages))
+}
+
+/// builds the messages delivering each coin to the receiver of its
+/// party. ibc transfers are submitted with a reply, so that they can
+/// be tracked by their sequence until acknowledged.
+fn get_settlement_submessages(
+    deps: ExecuteDeps,
+    env: &Env,
+    transfers: Vec<SettlementTransfer>,
+) -> Result<Vec<SubMsg<NeutronMsg>>, ContractError> {
+    let parties = PARTIES_CONFIG.load(deps.storage)?;
+    let ibc_transfers = transfers
+        .iter()
+        .filter(|transfer| {
+            matches!(
+                get_receiving_party(&parties, &transfer.party).map(|p| &p.receiver_config),
+                Ok(ReceiverConfig::Ibc(_))
+            )
+        })
+        .count();
+
+    // the holder pays the fees of the ibc transfers out of its own
+    // untrn balance, so we make sure it is covered before sending
+    let min_ibc_fee = match ibc_transfers {
+        0 => None,
+        count => {
+            let min_ibc_fee = query_ibc_fee(deps.querier)?;
+            ensure_ibc_fee_coverage(
+                deps.as_ref(),
+                env,
+                &transfers,
+                min_ibc_fee
+                    .total_ntrn_fee
+                    .checked_mul(Uint128::from(count as u128))
+                    .map_err(StdError::from)?,
+            )?;
+            Some(min_ibc_fee)
+        }
+    };
+
+    let mut pending_settlements = PENDING_SETTLEMENTS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let mut submessages = vec![];
+    for transfer in transfers {
+        let receiving_party = get_receiving_party(&parties, &transfer.party)?;
+        match &receiving_party.receiver_config {
+            ReceiverConfig::Native(addr) => submessages.push(SubMsg::new(BankMsg::Send {
+                to_address: addr.to_string(),
+                amount: vec![transfer.coin],
+            })),
+            ReceiverConfig::Contract { addr, msg } => submessages.push(SubMsg::new(
+                get_local_delivery_msg(addr, Some(msg.clone()), vec![transfer.coin]),
+            )),
+            ReceiverConfig::Ibc(destination_config) => {
+                let min_ibc_fee = min_ibc_fee
+                    .as_ref()
+                    .ok_or_else(|| StdError::generic_err("min ibc fee is missing"))?;
+                for msg in destination_config.get_ibc_transfer_messages_for_coins(
+                    vec![transfer.coin.clone()],
+                    env.block.time,
+                    env.contract.address.to_string(),
+                    min_ibc_fee,
+                )? {
+                    // mock ibc builds deliver the coins with a bank send
+                    if matches!(msg, CosmosMsg::Custom(NeutronMsg::IbcTransfer { .. })) {
+                        pending_settlements.push(transfer.clone());
+                        submessages.push(SubMsg::reply_on_success(msg, IBC_TRANSFER_REPLY_ID));
+                    } else {
+                        submessages.push(SubMsg::new(msg));
+                    }
+                }
+            }
+        }
+    }
+    PENDING_SETTLEMENTS.save(deps.storage, &pending_settlements)?;
+
+    Ok(submessages)
+}
+
+fn get_receiving_party<'a>(
+    parties: &'a CovenantPartiesConfig,
+    party: &str,
+) -> StdResult<&'a CovenantParty> {
+    if parties.party_a.addr == party {
+        Ok(&parties.party_a)
+    } else if parties.party_b.addr == party {
+        Ok(&parties.party_b)
+    } else {
+        Err(StdError::generic_err(format!("unknown party {party}")))
+    }
+}
+
+/// ensures that the untrn balance of the holder not being settled
+/// covers the fees of the settlement ibc transfers
+fn ensure_ibc_fee_coverage(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    transfers: &[SettlementTransfer],
+    expected: Uint128,
+) -> Result<(), ContractError> {
+    let settled_untrn = transfers
+        .iter()
+        .filter(|transfer| transfer.coin.denom == "untrn")
+        .map(|transfer| transfer.coin.amount)
+        .sum::<Uint128>();
+    let available = deps
+        .querier
+        .query_balance(env.contract.address.to_string(), "untrn")?
+        .amount
+        .saturating_sub(settled_untrn);
+
+    ensure!(
+        available >= expected,
+        ContractError::InsufficientIbcFees {
+            expected,
+            available
+        }
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    deps: ExecuteDeps,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    match msg.id {
+        IBC_TRANSFER_REPLY_ID => handle_ibc_transfer_reply(deps, msg),
+        _ => Err(ContractError::UnexpectedReplyId {}),
+    }
+}
+
+/// records the submitted settlement transfer as in flight under its sequence.
+/// submessages reply in order, so the earliest pending transfer is the one.
+fn handle_ibc_transfer_reply(
+    deps: ExecuteDeps,
+    msg: Reply,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let data = msg
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data
+        .ok_or_else(|| StdError::generic_err("ibc transfer response is missing"))?;
+    let transfer_response: MsgIbcTransferResponse = from_json(data)?;
+
+    let mut pending_settlements = PENDING_SETTLEMENTS.load(deps.storage)?;
+    ensure!(
+        !pending_settlements.is_empty(),
+        StdError::generic_err("no pending settlement transfer")
+    );
+    let transfer = pending_settlements.remove(0);
+    PENDING_SETTLEMENTS.save(deps.storage, &pending_settlements)?;
+
+    IN_FLIGHT_SETTLEMENTS.save(
+        deps.storage,
+        (
+            transfer_response.channel.to_string(),
+            transfer_response.sequence,
+        ),
+        &transfer,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "handle_ibc_transfer_reply")
+        .add_attribute("channel_id", transfer_response.channel)
+        .add_attribute("sequence", transfer_response.sequence.to_string())
+        .add_attribute("coin", transfer.coin.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: ExecuteDeps, _env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
+    match msg {
+        SudoMsg::Response { request, .. } => sudo_settlement_acknowledged(deps, request),
+        SudoMsg::Error { request, details } => sudo_settlement_failed(deps, request, details),
+        SudoMsg::Timeout { request } => sudo_settlement_timed_out(deps, request),
+        _ => Ok(Response::default().add_attribute("method", "sudo")),
+    }
+}
+
+/// removes the settlement transfer of the packet from the in flight transfers
+fn take_in_flight_settlement(
+    storage: &mut dyn Storage,
+    request: RequestPacket,
+) -> StdResult<Option<SettlementTransfer>> {
+    let (Some(channel_id), Some(sequence)) = (request.source_channel, request.sequence) else {
+        return Ok(None);
+    };
+    let key = (channel_id, sequence);
+    let transfer = IN_FLIGHT_SETTLEMENTS.may_load(storage, key.clone())?;
+    IN_FLIGHT_SETTLEMENTS.remove(storage, key);
+    Ok(transfer)
+}
+
+/// completes the holder and dequeues it from the clock once
+/// no settlement transfers are left to be acknowledged or retried
+fn try_complete_settlement(
+    mut deps: ExecuteDeps,
+    response: Response<NeutronMsg>,
+) -> StdResult<Response<NeutronMsg>> {
+    let unsettled = !matches!(CONTRACT_STATE.load(deps.storage)?, ContractState::Settling)
+        || IN_FLIGHT_SETTLEMENTS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+        || !PENDING_SETTLEMENTS
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .is_empty()
+        || !TIMED_OUT_SETTLEMENTS
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .is_empty();
+    if unsettled {
+        return Ok(response);
+    }
+
+    let clock_addr = CLOCK_ADDRESS.load(deps.storage)?;
+    let dequeue_msg = ContractState::complete_and_dequeue(deps.branch(), clock_addr.as_str())?;
+    Ok(response
+        .add_attribute("contract_state", "complete")
+        .add_message(dequeue_msg))
+}
+
+fn sudo_settlement_acknowledged(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+) -> StdResult<Response<NeutronMsg>> {
+    let response = Response::default().add_attribute("method", "sudo_response");
+    let Some(transfer) = take_in_flight_settlement(deps.storage, request)? else {
+        return Ok(response);
+    };
+
+    try_complete_settlement(
+        deps,
+        response.add_attribute("acknowledged", transfer.coin.to_string()),
+    )
+}
+
+/// failed transfers are refunded to the holder. the receiver config of the
+/// receiving party rejected the coin, so it is returned to its contributor.
+fn sudo_settlement_failed(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+    details: String,
+) -> StdResult<Response<NeutronMsg>> {
+    let response = Response::default()
+        .add_attribute("method", "sudo_error")
+        .add_attribute("error", details);
+    let Some(transfer) = take_in_flight_settlement(deps.storage, request)? else {
+        return Ok(response);
+    };
+
+    let parties = PARTIES_CONFIG.load(deps.storage)?;
+    let refund_config = REFUND_CONFIG.load(deps.storage)?;
+    let refund_address = if parties.party_a.addr == transfer.party {
+        refund_config.party_b_refund_address
+    } else {
+        refund_config.party_a_refund_address
+    };
+
+    try_complete_settlement(
+        deps,
+        response
+            .add_attribute("refunded", transfer.coin.to_string())
+            .add_attribute("refund_address", refund_address.to_string())
+            .add_message(BankMsg::Send {
+                to_address: refund_address,
+                amount: vec![transfer.coin],
+            }),
+    )
+}
+
+/// timed out transfers are refunded and retried on the next tick
+fn sudo_settlement_timed_out(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+) -> StdResult<Response<NeutronMsg>> {
+    let response = Response::default().add_attribute("method", "sudo_timeout");
+    let Some(transfer) = take_in_flight_settlement(deps.storage, request)? else {
+        return Ok(response);
+    };
+
+    let timed_out = transfer.coin.to_string();
+    let mut timed_out_settlements = TIMED_OUT_SETTLEMENTS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    timed_out_settlements.push(transfer);
+    TIMED_OUT_SETTLEMENTS.save(deps.storage, &timed_out_settlements)?;
+
+    Ok(response.add_attribute("timed_out", timed_out))
+}
+
+fn query_in_flight_settlements(deps: Deps) -> StdResult<Vec<InFlightSettlement>> {
+    IN_FLIGHT_SETTLEMENTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            let ((channel_id, sequence), transfer) = entry?;
+            Ok(InFlightSettlement {
+                channel_id,
+                sequence,
+                transfer,
+            })
+        })
+        .collect()
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::NextContract {} => Ok(to_json_binary(&NEXT_CONTRACT.may_load(deps.storage)?)?),
         QueryMsg::LockupConfig {} => Ok(to_json_binary(&LOCKUP_CONFIG.may_load(deps.storage)?)?),
+        QueryMsg::TimeToExpiry {} => Ok(to_json_binary(
+            &Deadline::from(LOCKUP_CONFIG.load(deps.storage)?).time_to_expiry(&env.block),
+        )?),
         QueryMsg::CovenantParties {} => {
             Ok(to_json_binary(&PARTIES_CONFIG.may_load(deps.storage)?)?)
         }
@@ -213,9 +666,87 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         // the deposit address for swap-holder is the contract itself
         QueryMsg::DepositAddress {} => Ok(to_json_binary(&Some(env.contract.address))?),
         QueryMsg::RefundConfig {} => Ok(to_json_binary(&REFUND_CONFIG.may_load(deps.storage)?)?),
+        QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
+        QueryMsg::DirectSettlement {} => Ok(to_json_binary(
+            &DIRECT_SETTLEMENT
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::InFlightSettlements {} => {
+            Ok(to_json_binary(&query_in_flight_settlements(deps)?)?)
+        }
     }
 }
 
+/// updates the config, either via the owner or via a migration
+#[allow(clippy::too_many_arguments)]
+fn update_config(
+    deps: DepsMut,
+    env: &Env,
+    clock_addr: Option<String>,
+    next_contract: Option<String>,
+    lockup_config: Option<Expiration>,
+    parites_config: Option<CovenantPartiesConfig>,
+    covenant_terms: Option<CovenantTerms>,
+    refund_config: Option<RefundConfig>,
+    direct_settlement: Option<bool>,
+) -> StdResult<Vec<Attribute>> {
+    let mut attrs = vec![];
+
+    if let Some(addr) = clock_addr {
+        let clock_address = deps.api.addr_validate(&addr)?;
+        CLOCK_ADDRESS.save(deps.storage, &clock_address)?;
+        attrs.push(Attribute::new("clock_addr", addr));
+    }
+
+    if let Some(addr) = next_contract {
+        let next_contract_addr = deps.api.addr_validate(&addr)?;
+        NEXT_CONTRACT.save(deps.storage, &next_contract_addr)?;
+        attrs.push(Attribute::new("next_contract", addr));
+    }
+
+    if let Some(expiry_config) = lockup_config {
+        if expiry_config.is_expired(&env.block) {
+            return Err(StdError::generic_err("lockup config is already past"));
+        }
+        LOCKUP_CONFIG.save(deps.storage, &expiry_config)?;
+        attrs.push(Attribute::new("lockup_config", expiry_config.to_string()));
+    }
+
+    if let Some(parites_config) = parites_config {
+        PARTIES_CONFIG.save(deps.storage, &parites_config)?;
+        attrs.push(Attribute::new(
+            "parites_config",
+            format!("{parites_config:?}"),
+        ));
+    }
+
+    if let Some(covenant_terms) = covenant_terms {
+        COVENANT_TERMS.save(deps.storage, &covenant_terms)?;
+        attrs.push(Attribute::new(
+            "covenant_terms",
+            format!("{covenant_terms:?}"),
+        ));
+    }
+
+    if let Some(config) = refund_config {
+        deps.api.addr_validate(&config.party_a_refund_address)?;
+        deps.api.addr_validate(&config.party_b_refund_address)?;
+        REFUND_CONFIG.save(deps.storage, &config)?;
+        attrs.push(Attribute::new("refund_config", format!("{config:?}")));
+    }
+
+    if let Some(direct_settlement) = direct_settlement {
+        DIRECT_SETTLEMENT.save(deps.storage, &direct_settlement)?;
+        attrs.push(Attribute::new(
+            "direct_settlement",
+            direct_settlement.to_string(),
+        ));
+    }
+
+    Ok(attrs)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
     match msg {
@@ -226,47 +757,22 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response>
             parites_config,
             covenant_terms,
             refund_config,
+            direct_settlement,
         } => {
-            let mut resp = Response::default().add_attribute("method", "update_config");
-
-            if let Some(addr) = clock_addr {
-                let clock_address = deps.api.addr_validate(&addr)?;
-                CLOCK_ADDRESS.save(deps.storage, &clock_address)?;
-                resp = resp.add_attribute("clock_addr", addr);
-            }
-
-            if let Some(addr) = next_contract {
-                let next_contract_addr = deps.api.addr_validate(&addr)?;
-                NEXT_CONTRACT.save(deps.storage, &next_contract_addr)?;
-                resp = resp.add_attribute("next_contract", addr);
-            }
-
-            if let Some(expiry_config) = lockup_config {
-                if expiry_config.is_expired(&env.block) {
-                    return Err(StdError::generic_err("lockup config is already past"));
-                }
-                LOCKUP_CONFIG.save(deps.storage, &expiry_config)?;
-                resp = resp.add_attribute("lockup_config", expiry_config.to_string());
-            }
-
-            if let Some(parites_config) = *parites_config {
-                PARTIES_CONFIG.save(deps.storage, &parites_config)?;
-                resp = resp.add_attribute("parites_config", format!("{parites_config:?}"));
-            }
-
-            if let Some(covenant_terms) = covenant_terms {
-                COVENANT_TERMS.save(deps.storage, &covenant_terms)?;
-                resp = resp.add_attribute("covenant_terms", format!("{covenant_terms:?}"));
-            }
-
-            if let Some(config) = refund_config {
-                deps.api.addr_validate(&config.party_a_refund_address)?;
-                deps.api.addr_validate(&config.party_b_refund_address)?;
-                REFUND_CONFIG.save(deps.storage, &config)?;
-                resp = resp.add_attribute("refund_config", format!("{config:?}"));
-            }
-
-            Ok(resp)
+            let attrs = update_config(
+                deps,
+                &env,
+                clock_addr,
+                next_contract,
+                lockup_config,
+                *parites_config,
+                covenant_terms,
+                refund_config,
+                direct_settlement,
+            )?;
+            Ok(Response::default()
+                .add_attribute("method", "update_config")
+                .add_attributes(attrs))
         }
         MigrateMsg::UpdateCodeId { data: _ } => {
             // This is a migrate message to update code id,
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use neutron_sdk::NeutronError;
 use thiserror::Error;
 
@@ -10,6 +10,9 @@ pub enum ContractError {
     #[error(transparent)]
     NeutronError(#[from] NeutronError),
 
+    #[error(transparent)]
+    OwnershipError(#[from] cw_ownable::OwnershipError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -19,6 +22,14 @@ pub enum ContractError {
     #[error("Insufficient funds to forward")]
     InsufficientFunds {},
 
+    #[error(
+        "insufficient untrn balance to cover the ibc fees: expected {expected}, got {available}"
+    )]
+    InsufficientIbcFees {
+        expected: Uint128,
+        available: Uint128,
+    },
+
     #[error("unexpected reply id")]
     UnexpectedReplyId {},
 }
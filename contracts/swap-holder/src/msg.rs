@@ -1,13 +1,15 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    to_json_binary, Addr, Attribute, Binary, DepsMut, StdError, StdResult, WasmMsg,
+    to_json_binary, Addr, Attribute, Binary, Coin, DepsMut, StdError, StdResult, WasmMsg,
 };
 use covenant_macros::{clocked, covenant_clock_address, covenant_deposit_address};
 use covenant_utils::{
-    clock::dequeue_msg, instantiate2_helper::Instantiate2HelperConfig, CovenantPartiesConfig,
-    CovenantTerms,
+    clock::dequeue_msg, deadline::TimeToExpiry, instantiate2_helper::Instantiate2HelperConfig,
+    CovenantPartiesConfig, CovenantTerms,
 };
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 use cw_utils::Expiration;
+use neutron_sdk::bindings::query::NeutronQuery;
 
 use crate::state::CONTRACT_STATE;
 
@@ -28,6 +30,19 @@ pub struct InstantiateMsg {
     pub covenant_terms: CovenantTerms,
     /// refund configuration containing party router adresses
     pub refund_config: RefundConfig,
+    /// if enabled, each side of the swap is delivered directly to the
+    /// counterparty receiver (over IBC for interchain receivers) instead
+    /// of being forwarded to the next contract. holder must then hold
+    /// enough untrn to cover the IBC fees.
+    #[serde(default)]
+    pub direct_settlement: bool,
+    /// address authorized to update the config. without an owner,
+    /// the config can only be updated by migrating the contract.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// if enabled, the party denoms must have a supply on this chain
+    #[serde(default)]
+    pub validate_denoms: bool,
 }
 
 impl InstantiateMsg {
@@ -54,6 +69,7 @@ impl InstantiateMsg {
             Attribute::new("clock_addr", self.clock_address),
             Attribute::new("next_contract", self.next_contract),
             Attribute::new("lockup_config", self.lockup_config.to_string()),
+            Attribute::new("direct_settlement", self.direct_settlement.to_string()),
         ];
         attrs.extend(self.parties_config.get_response_attributes());
         attrs.extend(self.covenant_terms.get_response_attributes());
@@ -68,11 +84,25 @@ pub struct RefundConfig {
 }
 
 #[clocked]
+#[cw_ownable_execute]
 #[cw_serde]
-pub enum ExecuteMsg {}
+pub enum ExecuteMsg {
+    /// update the config without a migration. only the owner may
+    /// submit updates.
+    UpdateConfig {
+        clock_addr: Option<String>,
+        next_contract: Option<String>,
+        lockup_config: Option<Expiration>,
+        parites_config: Box<Option<CovenantPartiesConfig>>,
+        covenant_terms: Option<CovenantTerms>,
+        refund_config: Option<RefundConfig>,
+        direct_settlement: Option<bool>,
+    },
+}
 
 #[covenant_clock_address]
 #[covenant_deposit_address]
+#[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -80,6 +110,9 @@ pub enum QueryMsg {
     NextContract {},
     #[returns(Expiration)]
     LockupConfig {},
+    /// time left until the lockup expires
+    #[returns(TimeToExpiry)]
+    TimeToExpiry {},
     #[returns(CovenantPartiesConfig)]
     CovenantParties {},
     #[returns(CovenantTerms)]
@@ -88,6 +121,20 @@ pub enum QueryMsg {
     ContractState {},
     #[returns(RefundConfig)]
     RefundConfig {},
+    /// whether the swapped funds are delivered directly to the parties
+    #[returns(bool)]
+    DirectSettlement {},
+    /// direct settlement ibc transfers awaiting their acknowledgement
+    #[returns(Vec<InFlightSettlement>)]
+    InFlightSettlements {},
+}
+
+/// counterparty contribution delivered to a party on direct settlement
+#[cw_serde]
+pub struct SettlementTransfer {
+    /// address of the receiving party
+    pub party: String,
+    pub coin: Coin,
 }
 
 #[cw_serde]
@@ -95,12 +142,17 @@ pub enum ContractState {
     Instantiated,
     /// covenant has reached its expiration date.
     Expired,
+    /// direct settlement ibc transfers are awaiting their acknowledgement.
+    Settling,
     /// underlying funds have been withdrawn.
     Complete,
 }
 
 impl ContractState {
-    pub fn complete_and_dequeue(deps: DepsMut, clock_addr: &str) -> Result<WasmMsg, StdError> {
+    pub fn complete_and_dequeue(
+        deps: DepsMut<NeutronQuery>,
+        clock_addr: &str,
+    ) -> Result<WasmMsg, StdError> {
         CONTRACT_STATE.save(deps.storage, &ContractState::Complete)?;
         dequeue_msg(clock_addr)
     }
@@ -115,8 +167,17 @@ pub enum MigrateMsg {
         parites_config: Box<Option<CovenantPartiesConfig>>,
         covenant_terms: Option<CovenantTerms>,
         refund_config: Option<RefundConfig>,
+        direct_settlement: Option<bool>,
     },
     UpdateCodeId {
         data: Option<Binary>,
     },
 }
+
+/// settlement ibc transfer awaiting its acknowledgement
+#[cw_serde]
+pub struct InFlightSettlement {
+    pub channel_id: String,
+    pub sequence: u64,
+    pub transfer: SettlementTransfer,
+}
@@ -1,9 +1,9 @@
 use cosmwasm_std::Addr;
 use covenant_utils::{CovenantPartiesConfig, CovenantTerms};
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use cw_utils::Expiration;
 
-use crate::msg::{ContractState, RefundConfig};
+use crate::msg::{ContractState, RefundConfig, SettlementTransfer};
 
 pub const CONTRACT_STATE: Item<ContractState> = Item::new("contract_state");
 pub const CLOCK_ADDRESS: Item<Addr> = Item::new("clock_address");
@@ -12,3 +12,14 @@ pub const PARTIES_CONFIG: Item<CovenantPartiesConfig> = Item::new("parties_confi
 pub const LOCKUP_CONFIG: Item<Expiration> = Item::new("lockup_config");
 pub const COVENANT_TERMS: Item<CovenantTerms> = Item::new("covenant_terms");
 pub const REFUND_CONFIG: Item<RefundConfig> = Item::new("refund_config");
+pub const DIRECT_SETTLEMENT: Item<bool> = Item::new("direct_settlement");
+
+/// submitted settlement ibc transfers awaiting their sequence, in submission order
+pub const PENDING_SETTLEMENTS: Item<Vec<SettlementTransfer>> = Item::new("pending_settlements");
+
+/// settlement ibc transfers awaiting their acknowledgement, keyed by (channel, sequence)
+pub const IN_FLIGHT_SETTLEMENTS: Map<(String, u64), SettlementTransfer> =
+    Map::new("in_flight_settlements");
+
+/// timed out settlement transfers to be retried on the next tick
+pub const TIMED_OUT_SETTLEMENTS: Item<Vec<SettlementTransfer>> = Item::new("timed_out_settlements");
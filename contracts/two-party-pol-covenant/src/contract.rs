@@ -3,12 +3,19 @@ use std::collections::BTreeSet;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, CanonicalAddr, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, WasmMsg,
+    to_json_binary, Binary, CanonicalAddr, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, StdResult, WasmMsg,
 };
 use covenant_utils::{
-    instantiate2_helper::get_instantiate2_salt_and_address, op_mode::ContractOperationModeConfig,
-    split::remap_splits,
+    forwarder_milestone::ForwarderMilestone,
+    instantiate2_helper::get_instantiate2_salt_and_address,
+    op_mode::ContractOperationModeConfig,
+    plan::{
+        DeploymentPlan, DeploymentProgress, DeploymentStep, ShadowDeployment, StagedDeployment,
+        SHADOW_DEPLOY_REPLY_ID, SHADOW_MIGRATE_REPLY_ID,
+    },
+    router_halt::{generate_halt_msg, RouterHaltConfig},
+    split::{get_allocation_splits, remap_splits, validate_covenant_splits},
 };
 use cw2::set_contract_version;
 use valence_ibc_forwarder::msg::InstantiateMsg as IbcForwarderInstantiateMsg;
@@ -17,13 +24,13 @@ use valence_two_party_pol_holder::msg::{RagequitConfig, TwoPartyPolCovenantConfi
 use crate::{
     error::ContractError,
     msg::{
-        CovenantPartyConfig, InstantiateMsg, LiquidPoolerMigrateMsg, MigrateMsg, QueryMsg,
-        RouterMigrateMsg,
+        CovenantContractCodes, CovenantPartyConfig, ExecuteMsg, InstantiateMsg, LiquidPoolerConfig,
+        LiquidPoolerMigrateMsg, MigrateMsg, PartyDepositStatus, QueryMsg, RouterMigrateMsg,
     },
     state::{
-        CONTRACT_CODES, COVENANT_CLOCK_ADDR, COVENANT_POL_HOLDER_ADDR, LIQUID_POOLER_ADDR,
-        PARTY_A_IBC_FORWARDER_ADDR, PARTY_A_ROUTER_ADDR, PARTY_B_IBC_FORWARDER_ADDR,
-        PARTY_B_ROUTER_ADDR,
+        CONTRACT_CODES, COVENANT_CLOCK_ADDR, COVENANT_POL_HOLDER_ADDR, DEPLOYMENT_PLAN,
+        FORWARDER_MILESTONES, LIQUID_POOLER_ADDR, PARTY_A_IBC_FORWARDER_ADDR, PARTY_A_ROUTER_ADDR,
+        PARTY_B_IBC_FORWARDER_ADDR, PARTY_B_ROUTER_ADDR, SHADOW_DEPLOYMENT, STAGED_DEPLOYMENT,
     },
 };
 
@@ -49,8 +56,50 @@ pub fn instantiate(
     let mut resp = Response::default().add_attribute("method", "instantiate");
     let creator_address: CanonicalAddr =
         deps.api.addr_canonicalize(env.contract.address.as_str())?;
-
-    let covenant_denoms: BTreeSet<String> = msg.splits.keys().map(|k| k.to_string()).collect();
+    let party_a_memo_template = msg.get_party_memo_template(&env.contract.address, "party_a");
+    let party_b_memo_template = msg.get_party_memo_template(&env.contract.address, "party_b");
+
+    // every denom the covenant expects must be split between the party receivers
+    let mut expected_denoms = BTreeSet::from([
+        msg.party_a_config.get_native_denom(),
+        msg.party_b_config.get_native_denom(),
+    ]);
+    expected_denoms.extend(msg.reward_denoms.clone().unwrap_or_default());
+    // adapted contributions are pooled (and later withdrawn) in the target denom
+    expected_denoms.extend(
+        [
+            &msg.party_a_contribution_adapter,
+            &msg.party_b_contribution_adapter,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|adapter| adapter.target_denom.to_string()),
+    );
+    // omitted splits are derived from the party allocations
+    let splits = if msg.splits.is_empty() {
+        get_allocation_splits(
+            &expected_denoms,
+            (
+                &msg.party_a_config.get_final_receiver_address(),
+                msg.party_a_share,
+            ),
+            (
+                &msg.party_b_config.get_final_receiver_address(),
+                msg.party_b_share,
+            ),
+        )
+    } else {
+        msg.splits.clone()
+    };
+    validate_covenant_splits(
+        &splits,
+        &expected_denoms,
+        (
+            &msg.party_a_config.get_final_receiver_address(),
+            &msg.party_b_config.get_final_receiver_address(),
+        ),
+    )?;
+    let covenant_denoms: BTreeSet<String> = splits.keys().map(|k| k.to_string()).collect();
 
     let clock_instantiate2_config = get_instantiate2_salt_and_address(
         deps.as_ref(),
@@ -70,12 +119,6 @@ pub fn instantiate(
         &creator_address,
         msg.party_b_config.get_router_code_id(&msg.contract_codes),
     )?;
-    let holder_instantiate2_config = get_instantiate2_salt_and_address(
-        deps.as_ref(),
-        HOLDER_SALT,
-        &creator_address,
-        msg.contract_codes.holder_code,
-    )?;
     let liquid_pooler_instantiate2_config = get_instantiate2_salt_and_address(
         deps.as_ref(),
         LIQUID_POOLER_SALT,
@@ -84,7 +127,6 @@ pub fn instantiate(
     )?;
 
     let mut clock_whitelist: Vec<String> = Vec::with_capacity(6);
-    clock_whitelist.push(holder_instantiate2_config.addr.to_string());
 
     let mut clock_initial_queue = vec![];
     clock_initial_queue.push(liquid_pooler_instantiate2_config.addr.to_string());
@@ -109,50 +151,95 @@ pub fn instantiate(
         }
     }
 
-    let holder_instantiate2_msg = valence_two_party_pol_holder::msg::InstantiateMsg {
-        clock_address: clock_instantiate2_config.addr.to_string(),
-        lockup_config: msg.lockup_config,
-        next_contract: liquid_pooler_instantiate2_config.addr.to_string(),
-        ragequit_config: msg.ragequit_config.unwrap_or(RagequitConfig::Disabled),
-        deposit_deadline: msg.deposit_deadline,
-        splits: remap_splits(
-            msg.splits,
-            (
-                msg.party_a_config.get_final_receiver_address(),
-                party_a_router_instantiate2_config.addr.to_string(),
-            ),
-            (
-                msg.party_b_config.get_final_receiver_address(),
-                party_b_router_instantiate2_config.addr.to_string(),
-            ),
-        )?,
-        fallback_split: match msg.fallback_split {
-            Some(config) => Some(config.remap_receivers_to_routers(
-                msg.party_a_config.get_final_receiver_address(),
-                party_a_router_instantiate2_config.addr.to_string(),
-                msg.party_b_config.get_final_receiver_address(),
-                party_b_router_instantiate2_config.addr.to_string(),
-            )?),
-            None => None,
-        },
-        covenant_config: TwoPartyPolCovenantConfig {
-            party_a: msg.party_a_config.to_two_party_pol_party(
-                msg.party_a_share,
-                party_a_router_instantiate2_config.addr.to_string(),
-            ),
-            party_b: msg.party_b_config.to_two_party_pol_party(
-                msg.party_b_share,
-                party_b_router_instantiate2_config.addr.to_string(),
-            ),
-            covenant_type: msg.covenant_type.clone(),
-        },
-        emergency_committee_addr: msg.emergency_committee,
+    // osmosis liquid pooler holds its LP position remotely, so it can not be donated
+    if msg.residual_lp_donation.is_some() {
+        if let LiquidPoolerConfig::Osmosis(_) = msg.liquid_pooler_config {
+            return Err(ContractError::Std(StdError::generic_err(
+                "residual lp donation requires the astroport liquid pooler",
+            )));
+        }
     }
-    .to_instantiate2_msg(
-        &holder_instantiate2_config,
-        env.contract.address.to_string(),
-        format!("{}_holder", msg.label),
-    )?;
+
+    // a pre-deployed holder is used as is. otherwise the standard
+    // two party holder gets instantiated along with the other contracts.
+    let mut messages = vec![];
+    let holder_addr = match msg.external_holder_address.clone() {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        None => {
+            let holder_instantiate2_config = get_instantiate2_salt_and_address(
+                deps.as_ref(),
+                HOLDER_SALT,
+                &creator_address,
+                msg.contract_codes.holder_code,
+            )?;
+            let holder_instantiate2_msg = valence_two_party_pol_holder::msg::InstantiateMsg {
+                clock_address: clock_instantiate2_config.addr.to_string(),
+                lockup_config: msg.lockup_config,
+                next_contract: liquid_pooler_instantiate2_config.addr.to_string(),
+                ragequit_config: msg.ragequit_config.unwrap_or(RagequitConfig::Disabled),
+                deposit_deadline: msg.deposit_deadline,
+                splits: remap_splits(
+                    splits,
+                    (
+                        msg.party_a_config.get_final_receiver_address(),
+                        party_a_router_instantiate2_config.addr.to_string(),
+                    ),
+                    (
+                        msg.party_b_config.get_final_receiver_address(),
+                        party_b_router_instantiate2_config.addr.to_string(),
+                    ),
+                )?,
+                fallback_split: match msg.fallback_split {
+                    Some(config) => Some(config.remap_receivers_to_routers(
+                        msg.party_a_config.get_final_receiver_address(),
+                        party_a_router_instantiate2_config.addr.to_string(),
+                        msg.party_b_config.get_final_receiver_address(),
+                        party_b_router_instantiate2_config.addr.to_string(),
+                    )?),
+                    None => None,
+                },
+                covenant_config: TwoPartyPolCovenantConfig {
+                    party_a: msg.party_a_config.to_two_party_pol_party(
+                        msg.party_a_share,
+                        party_a_router_instantiate2_config.addr.to_string(),
+                        msg.party_a_contribution_adapter.clone(),
+                    ),
+                    party_b: msg.party_b_config.to_two_party_pol_party(
+                        msg.party_b_share,
+                        party_b_router_instantiate2_config.addr.to_string(),
+                        msg.party_b_contribution_adapter.clone(),
+                    ),
+                    covenant_type: msg.covenant_type.clone(),
+                },
+                emergency_committee_addr: msg.emergency_committee.clone(),
+                residual_lp_donation: msg.residual_lp_donation.clone(),
+                lockup_extension_window: msg.lockup_extension_window,
+                auto_distribute_on_expiry: msg.auto_distribute_on_expiry,
+                fee_refund_config: msg.fee_refund_config.clone(),
+                generator_staking: msg.generator_staking.clone(),
+                provisioning_stall: msg.provisioning_stall.clone(),
+                depositor_allowlist: msg.depositor_allowlist.clone(),
+                vesting_config: msg.vesting_config.clone(),
+                depeg_guard: msg.depeg_guard.clone(),
+                validate_denoms: msg.validate_denoms,
+            }
+            .to_instantiate2_msg(
+                &holder_instantiate2_config,
+                env.contract.address.to_string(),
+                format!("{}_holder", msg.label),
+            )?;
+            messages.push(holder_instantiate2_msg);
+            holder_instantiate2_config.addr
+        }
+    };
+    clock_whitelist.insert(0, holder_addr.to_string());
+
+    // routers can be halted by the covenant or the emergency committee,
+    // returning their remaining balances to the holder
+    let router_halt_config = RouterHaltConfig {
+        holder_address: holder_addr.to_string(),
+        emergency_committee: msg.emergency_committee.clone(),
+    };
 
     let party_a_router_instantiate2_msg = msg.party_a_config.to_router_instantiate2_msg(
         env.contract.address.to_string(),
@@ -160,6 +247,9 @@ pub fn instantiate(
         format!("{}_party_a_router", msg.label),
         covenant_denoms.clone(),
         party_a_router_instantiate2_config.clone(),
+        router_halt_config.clone(),
+        party_a_memo_template,
+        msg.ibc_fee_overrides.router.clone(),
     )?;
 
     let party_b_router_instantiate2_msg = msg.party_b_config.to_router_instantiate2_msg(
@@ -168,6 +258,9 @@ pub fn instantiate(
         format!("{}_party_b_router", msg.label),
         covenant_denoms.clone(),
         party_b_router_instantiate2_config.clone(),
+        router_halt_config.clone(),
+        party_b_memo_template,
+        msg.ibc_fee_overrides.router.clone(),
     )?;
 
     let liquid_pooler_instantiate2_msg = msg.liquid_pooler_config.to_instantiate2_msg(
@@ -175,16 +268,16 @@ pub fn instantiate(
         env.contract.address.to_string(),
         format!("{}_liquid_pooler", msg.label),
         clock_instantiate2_config.addr.to_string(),
-        holder_instantiate2_config.addr.to_string(),
+        holder_addr.to_string(),
         msg.pool_price_config,
+        msg.emergency_committee.clone(),
     )?;
 
-    let mut messages = vec![
-        holder_instantiate2_msg,
+    messages.extend([
         party_a_router_instantiate2_msg,
         party_b_router_instantiate2_msg,
         liquid_pooler_instantiate2_msg,
-    ];
+    ]);
 
     if let CovenantPartyConfig::Interchain(config) = &msg.party_a_config {
         let party_a_forwarder_instantiate2_config = get_instantiate2_salt_and_address(
@@ -200,7 +293,7 @@ pub fn instantiate(
             op_mode_cfg: ContractOperationModeConfig::Permissioned(vec![clock_instantiate2_config
                 .addr
                 .to_string()]),
-            next_contract: holder_instantiate2_config.addr.to_string(),
+            next_contract: holder_addr.to_string(),
             remote_chain_connection_id: config.party_chain_connection_id.to_string(),
             remote_chain_channel_id: config.party_to_host_chain_channel_id.to_string(),
             denom: config.remote_chain_denom.to_string(),
@@ -208,6 +301,8 @@ pub fn instantiate(
             ica_timeout: msg.timeouts.ica_timeout,
             ibc_transfer_timeout: msg.timeouts.ibc_transfer_timeout,
             fallback_address: msg.fallback_address.clone(),
+            milestone_callback: Some(env.contract.address.to_string()),
+            ibc_fee_override: msg.ibc_fee_overrides.forwarder.clone(),
         };
 
         messages.push(instantiate_msg.to_instantiate2_msg(
@@ -235,7 +330,7 @@ pub fn instantiate(
             op_mode_cfg: ContractOperationModeConfig::Permissioned(vec![clock_instantiate2_config
                 .addr
                 .to_string()]),
-            next_contract: holder_instantiate2_config.addr.to_string(),
+            next_contract: holder_addr.to_string(),
             remote_chain_connection_id: config.party_chain_connection_id.to_string(),
             remote_chain_channel_id: config.party_to_host_chain_channel_id.to_string(),
             denom: config.remote_chain_denom.to_string(),
@@ -243,6 +338,8 @@ pub fn instantiate(
             ica_timeout: msg.timeouts.ica_timeout,
             ibc_transfer_timeout: msg.timeouts.ibc_transfer_timeout,
             fallback_address: msg.fallback_address,
+            milestone_callback: Some(env.contract.address.to_string()),
+            ibc_fee_override: msg.ibc_fee_overrides.forwarder.clone(),
         };
 
         messages.push(instantiate_msg.to_instantiate2_msg(
@@ -260,6 +357,21 @@ pub fn instantiate(
         tick_max_gas: msg.clock_tick_max_gas,
         whitelist: clock_whitelist,
         initial_queue: clock_initial_queue,
+        tick_intervals: msg
+            .router_tick_interval
+            .map(|interval| {
+                vec![
+                    (
+                        party_a_router_instantiate2_config.addr.to_string(),
+                        interval,
+                    ),
+                    (
+                        party_b_router_instantiate2_config.addr.to_string(),
+                        interval,
+                    ),
+                ]
+            })
+            .unwrap_or_default(),
     }
     .to_instantiate2_msg(
         clock_instantiate2_config.code,
@@ -269,6 +381,9 @@ pub fn instantiate(
     )?;
     messages.insert(0, clock_instantiate2_msg);
 
+    let deployment_plan = DeploymentPlan::build(deps.as_ref(), &creator_address, messages)?;
+    DEPLOYMENT_PLAN.save(deps.storage, &deployment_plan)?;
+
     CONTRACT_CODES.save(
         deps.storage,
         &msg.contract_codes.to_covenant_codes_config(
@@ -276,12 +391,22 @@ pub fn instantiate(
             party_b_router_instantiate2_config.code,
         ),
     )?;
-    COVENANT_POL_HOLDER_ADDR.save(deps.storage, &holder_instantiate2_config.addr)?;
+    COVENANT_POL_HOLDER_ADDR.save(deps.storage, &holder_addr)?;
     LIQUID_POOLER_ADDR.save(deps.storage, &liquid_pooler_instantiate2_config.addr)?;
     PARTY_B_ROUTER_ADDR.save(deps.storage, &party_b_router_instantiate2_config.addr)?;
     PARTY_A_ROUTER_ADDR.save(deps.storage, &party_a_router_instantiate2_config.addr)?;
     COVENANT_CLOCK_ADDR.save(deps.storage, &clock_instantiate2_config.addr)?;
 
+    // staged deployments instantiate the planned contracts with `DeployNext {}`
+    let deployment_messages = match msg.deployment_batch_size {
+        Some(batch_size) => {
+            STAGED_DEPLOYMENT.save(deps.storage, &StagedDeployment::new(batch_size)?)?;
+            resp = resp.add_attribute("deployment_batch_size", batch_size.to_string());
+            vec![]
+        }
+        None => deployment_plan.messages(),
+    };
+
     Ok(resp
         .add_attribute("clock_addr", clock_instantiate2_config.addr)
         .add_attribute("liquid_pooler_addr", liquid_pooler_instantiate2_config.addr)
@@ -293,10 +418,138 @@ pub fn instantiate(
             "party_b_router_addr",
             party_b_router_instantiate2_config.addr,
         )
-        .add_attribute("holder_addr", holder_instantiate2_config.addr)
+        .add_attribute("holder_addr", holder_addr)
+        .add_attribute(
+            "deployment_plan_msg_size",
+            deployment_plan.total_msg_size().to_string(),
+        )
+        .add_messages(deployment_messages))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ForwarderMilestone { milestone } => {
+            try_record_forwarder_milestone(deps, info, milestone)
+        }
+        ExecuteMsg::ShadowDeploy { codes } => try_shadow_deploy(deps, env, info, codes),
+        ExecuteMsg::DeployNext {} => try_deploy_next(deps),
+    }
+}
+
+/// instantiates the next batch of contracts of a staged deployment
+fn try_deploy_next(deps: DepsMut) -> Result<Response, ContractError> {
+    let Some(mut staged_deployment) = STAGED_DEPLOYMENT.may_load(deps.storage)? else {
+        return Err(ContractError::Std(StdError::generic_err(
+            "covenant is not deployed in stages",
+        )));
+    };
+    let deployment_plan = DEPLOYMENT_PLAN.load(deps.storage)?;
+    let messages = staged_deployment.next_batch(&deployment_plan)?;
+    STAGED_DEPLOYMENT.save(deps.storage, &staged_deployment)?;
+
+    let progress = staged_deployment.get_progress(&deployment_plan);
+    Ok(Response::default()
+        .add_attribute("method", "try_deploy_next")
+        .add_attribute(
+            "deployed_steps",
+            staged_deployment.deployed_steps.to_string(),
+        )
+        .add_attribute("pending_steps", progress.pending.len().to_string())
         .add_messages(messages))
 }
 
+/// instantiates a shadow copy of the deployment plan from the currently
+/// stored contract codes, and migrates it to the candidate codes
+fn try_shadow_deploy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    codes: CovenantContractCodes,
+) -> Result<Response, ContractError> {
+    let contract_codes = CONTRACT_CODES.load(deps.storage)?;
+    let (shadow_deployment, messages) = ShadowDeployment::deploy(
+        deps.as_ref(),
+        &env,
+        &info,
+        &DEPLOYMENT_PLAN.load(deps.storage)?,
+        SHADOW_DEPLOYMENT.may_load(deps.storage)?,
+        |step| get_shadow_code_id(&contract_codes, step),
+        |step| get_shadow_code_id(&codes, step),
+    )?;
+    SHADOW_DEPLOYMENT.save(deps.storage, &shadow_deployment)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_shadow_deploy")
+        .add_attribute("nonce", shadow_deployment.nonce.to_string())
+        .add_submessages(messages))
+}
+
+/// maps a deployment step to the code id of its contract
+fn get_shadow_code_id(codes: &CovenantContractCodes, step: &DeploymentStep) -> StdResult<u64> {
+    match step.salt.as_slice() {
+        CLOCK_SALT => Ok(codes.clock),
+        HOLDER_SALT => Ok(codes.holder),
+        LIQUID_POOLER_SALT => Ok(codes.liquid_pooler),
+        PARTY_A_ROUTER_SALT => Ok(codes.party_a_router),
+        PARTY_B_ROUTER_SALT => Ok(codes.party_b_router),
+        PARTY_A_FORWARDER_SALT => Ok(codes.party_a_forwarder),
+        PARTY_B_FORWARDER_SALT => Ok(codes.party_b_forwarder),
+        _ => Err(StdError::generic_err(format!(
+            "unknown deployment step {}",
+            step.label
+        ))),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SHADOW_DEPLOY_REPLY_ID | SHADOW_MIGRATE_REPLY_ID => {
+            let mut shadow_deployment = SHADOW_DEPLOYMENT.load(deps.storage)?;
+            let (step, messages) = shadow_deployment.record_reply(deps.as_ref(), &env, msg)?;
+            SHADOW_DEPLOYMENT.save(deps.storage, &shadow_deployment)?;
+
+            Ok(Response::default()
+                .add_attribute("method", "shadow_deploy_reply")
+                .add_attributes(shadow_deployment.get_reply_attributes(&step)?)
+                .add_submessages(messages))
+        }
+        _ => Err(ContractError::UnknownReplyId {}),
+    }
+}
+
+/// records a milestone reported by one of the party ibc forwarders
+fn try_record_forwarder_milestone(
+    deps: DepsMut,
+    info: MessageInfo,
+    milestone: ForwarderMilestone,
+) -> Result<Response, ContractError> {
+    let party = if PARTY_A_IBC_FORWARDER_ADDR.may_load(deps.storage)? == Some(info.sender.clone()) {
+        "party_a"
+    } else if PARTY_B_IBC_FORWARDER_ADDR.may_load(deps.storage)? == Some(info.sender) {
+        "party_b"
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+
+    let mut milestones = FORWARDER_MILESTONES
+        .may_load(deps.storage, party)?
+        .unwrap_or_default();
+    milestones.record(milestone.clone());
+    FORWARDER_MILESTONES.save(deps.storage, party, &milestones)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_record_forwarder_milestone")
+        .add_attribute("party", party)
+        .add_attributes(milestone.to_event().attributes))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -355,7 +608,50 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             };
             Ok(to_json_binary(&resp)?)
         }
+        QueryMsg::PartyDepositStatus { party } => {
+            let forwarder = match party.as_str() {
+                "party_a" => PARTY_A_IBC_FORWARDER_ADDR.may_load(deps.storage)?,
+                "party_b" => PARTY_B_IBC_FORWARDER_ADDR.may_load(deps.storage)?,
+                _ => return Err(StdError::not_found("not found")),
+            };
+            // interchain parties deposit to the forwarder ICA, which is
+            // ready once its registration milestone is reported.
+            // native parties deposit to the holder directly.
+            let status = match forwarder {
+                Some(_) => {
+                    let milestones = FORWARDER_MILESTONES
+                        .may_load(deps.storage, &party)?
+                        .unwrap_or_default();
+                    PartyDepositStatus {
+                        deposit_address: milestones.ica_address.clone(),
+                        ready: milestones.ica_address.is_some(),
+                        forwarder_milestones: Some(milestones),
+                    }
+                }
+                None => {
+                    let holder = COVENANT_POL_HOLDER_ADDR.may_load(deps.storage)?;
+                    PartyDepositStatus {
+                        ready: holder.is_some(),
+                        deposit_address: holder.map(|addr| addr.to_string()),
+                        forwarder_milestones: None,
+                    }
+                }
+            };
+            Ok(to_json_binary(&status)?)
+        }
         QueryMsg::ContractCodes {} => Ok(to_json_binary(&CONTRACT_CODES.load(deps.storage)?)?),
+        QueryMsg::DeploymentPlan {} => Ok(to_json_binary(&DEPLOYMENT_PLAN.load(deps.storage)?)?),
+        QueryMsg::ShadowDeployment {} => {
+            Ok(to_json_binary(&SHADOW_DEPLOYMENT.may_load(deps.storage)?)?)
+        }
+        QueryMsg::DeploymentProgress {} => {
+            let deployment_plan = DEPLOYMENT_PLAN.load(deps.storage)?;
+            let progress = match STAGED_DEPLOYMENT.may_load(deps.storage)? {
+                Some(staged_deployment) => staged_deployment.get_progress(&deployment_plan),
+                None => DeploymentProgress::complete(&deployment_plan),
+            };
+            Ok(to_json_binary(&progress)?)
+        }
     }
 }
 
@@ -465,6 +761,15 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response>
 
             Ok(resp.add_messages(migrate_msgs))
         }
+        MigrateMsg::HaltRouters {} => {
+            let party_a_router = PARTY_A_ROUTER_ADDR.load(deps.storage)?;
+            let party_b_router = PARTY_B_ROUTER_ADDR.load(deps.storage)?;
+
+            Ok(Response::default()
+                .add_attribute("method", "halt_routers")
+                .add_message(generate_halt_msg(party_a_router.to_string())?)
+                .add_message(generate_halt_msg(party_b_router.to_string())?))
+        }
         MigrateMsg::UpdateCodeId { data: _ } => {
             // This is a migrate message to update code id,
             // Data is optional base64 that we can parse to any data we would like in the future
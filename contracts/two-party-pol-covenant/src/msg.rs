@@ -3,14 +3,27 @@ use std::collections::{BTreeMap, BTreeSet};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{coin, Addr, Binary, Decimal, StdResult, Uint64, WasmMsg};
 use covenant_utils::{
-    instantiate2_helper::Instantiate2HelperConfig, op_mode::ContractOperationModeConfig,
-    split::SplitConfig, CovenantParty, DestinationConfig, InterchainCovenantParty,
-    NativeCovenantParty, PoolPriceConfig, ReceiverConfig,
+    contribution_adapter::ContributionAdapter,
+    forwarder_milestone::{ForwarderMilestone, ForwarderMilestones},
+    ibc_memo::resolve_covenant_placeholders,
+    instantiate2_helper::Instantiate2HelperConfig,
+    neutron::IbcFeeOverrides,
+    op_mode::ContractOperationModeConfig,
+    plan::{DeploymentPlan, DeploymentProgress, ShadowDeployment},
+    router_halt::RouterHaltConfig,
+    split::SplitConfig,
+    CovenantParty, DestinationConfig, InterchainCovenantParty, NativeCovenantParty,
+    PoolPriceConfig, ReceiverConfig,
 };
 use cw_utils::Expiration;
+use neutron_sdk::bindings::msg::IbcFee;
 use valence_astroport_liquid_pooler::msg::AstroportLiquidPoolerConfig;
 use valence_osmo_liquid_pooler::msg::OsmosisLiquidPoolerConfig;
-use valence_two_party_pol_holder::msg::{CovenantType, RagequitConfig, TwoPartyPolCovenantParty};
+use valence_two_party_pol_holder::msg::{
+    CovenantType, DepegGuardConfig, DepositorAllowlist, FeeRefundConfig, GeneratorStakingConfig,
+    ProvisioningStallConfig, RagequitConfig, ResidualLpDonationConfig, TwoPartyPolCovenantParty,
+    VestingConfig,
+};
 
 pub const DEFAULT_TIMEOUT: u64 = 60 * 60 * 5; // 5 hours
 
@@ -20,6 +33,15 @@ pub struct InstantiateMsg {
     pub timeouts: Timeouts,
     pub contract_codes: CovenantContractCodeIds,
     pub clock_tick_max_gas: Option<Uint64>,
+    /// minimum number of seconds between two clock ticks of the party
+    /// routers, which idle until funds get distributed to them.
+    /// routers are ticked as often as possible if unset.
+    #[serde(default)]
+    pub router_tick_interval: Option<Uint64>,
+    /// ibc fees of the party forwarders and interchain routers overriding the
+    /// chain minimum, where they exceed it. other overrides are not applicable.
+    #[serde(default)]
+    pub ibc_fee_overrides: IbcFeeOverrides,
     pub lockup_config: Expiration,
     pub party_a_config: CovenantPartyConfig,
     pub party_b_config: CovenantPartyConfig,
@@ -29,11 +51,93 @@ pub struct InstantiateMsg {
     pub party_a_share: Decimal,
     pub party_b_share: Decimal,
     pub pool_price_config: PoolPriceConfig,
+    /// splits of the covenant denoms between the party receivers. if
+    /// omitted, every denom is split according to the party shares
+    #[serde(default)]
     pub splits: BTreeMap<String, SplitConfig>,
     pub fallback_split: Option<SplitConfig>,
     pub emergency_committee: Option<String>,
     pub liquid_pooler_config: LiquidPoolerConfig,
     pub fallback_address: Option<String>,
+    /// donation of the LP position left unclaimed long after the lockup.
+    /// only supported with the astroport liquid pooler.
+    #[serde(default)]
+    pub residual_lp_donation: Option<ResidualLpDonationConfig>,
+    /// address of a pre-deployed holder to wire the covenant contracts to,
+    /// instead of instantiating the standard two party holder. the holder
+    /// specific fields of this message are then ignored.
+    #[serde(default)]
+    pub external_holder_address: Option<String>,
+    /// denoms besides the party contributions that the covenant
+    /// may receive (e.g. rewards), and that are thus split as well
+    #[serde(default)]
+    pub reward_denoms: Option<Vec<String>>,
+    /// adapter converting the contribution of party a into the denom
+    /// expected by the liquid pooler upon deposit
+    #[serde(default)]
+    pub party_a_contribution_adapter: Option<ContributionAdapter>,
+    /// adapter converting the contribution of party b into the denom
+    /// expected by the liquid pooler upon deposit
+    #[serde(default)]
+    pub party_b_contribution_adapter: Option<ContributionAdapter>,
+    /// template of the memo attached to ibc distributions of the
+    /// interchain routers. `{label}`, `{covenant}`, and `{party}`
+    /// are replaced with the covenant label, covenant address, and
+    /// party id, while `{denom}` and `{amount}` are replaced with
+    /// the distributed coin.
+    #[serde(default)]
+    pub ibc_memo_template: Option<String>,
+    /// seconds during which a lockup extension proposed by one of
+    /// the parties can be approved by the counterparty
+    #[serde(default)]
+    pub lockup_extension_window: Option<u64>,
+    /// if enabled, the holder distributes the position to both
+    /// parties once the lockup expires, without waiting for claims
+    #[serde(default)]
+    pub auto_distribute_on_expiry: bool,
+    /// routes the untrn fee refunds received by the holder back to
+    /// the fee payer instead of distributing them to the parties
+    #[serde(default)]
+    pub fee_refund_config: Option<FeeRefundConfig>,
+    /// stakes the LP position into the astroport generator while
+    /// it is locked up, distributing the rewards to the parties
+    #[serde(default)]
+    pub generator_staking: Option<GeneratorStakingConfig>,
+    /// detects liquid poolers unable to provide liquidity while the
+    /// covenant is active, optionally refunding the parties
+    #[serde(default)]
+    pub provisioning_stall: Option<ProvisioningStallConfig>,
+    /// restricts the holder deposits to the funds attached to
+    /// `Deposit {}` messages of the allowed depositors
+    #[serde(default)]
+    pub depositor_allowlist: Option<DepositorAllowlist>,
+    /// unlocks the party allocations linearly while the covenant is
+    /// active, instead of a single cliff at the lockup expiration
+    #[serde(default)]
+    pub vesting_config: Option<VestingConfig>,
+    /// protects stable pair covenants against a depeg of either
+    /// party denom while the covenant is active
+    #[serde(default)]
+    pub depeg_guard: Option<DepegGuardConfig>,
+    /// if set, the covenant contracts are not instantiated along with the
+    /// covenant. they are instead instantiated in batches of this many
+    /// contracts with `DeployNext {}`, spreading the deployment over
+    /// multiple transactions.
+    #[serde(default)]
+    pub deployment_batch_size: Option<u64>,
+    /// if enabled, the holder requires the contribution and split
+    /// denoms to have a supply on neutron upon instantiation
+    #[serde(default)]
+    pub validate_denoms: bool,
+}
+
+impl InstantiateMsg {
+    /// memo template of the party router, with the covenant placeholders resolved
+    pub fn get_party_memo_template(&self, covenant: &Addr, party: &str) -> Option<String> {
+        self.ibc_memo_template.as_ref().map(|template| {
+            resolve_covenant_placeholders(template, &self.label, covenant.as_str(), party)
+        })
+    }
 }
 
 #[cw_serde]
@@ -43,6 +147,7 @@ pub enum LiquidPoolerConfig {
 }
 
 impl LiquidPoolerConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn to_instantiate2_msg(
         &self,
         instantiate2_helper: &Instantiate2HelperConfig,
@@ -51,6 +156,7 @@ impl LiquidPoolerConfig {
         clock_addr: String,
         holder_addr: String,
         pool_price_config: PoolPriceConfig,
+        emergency_committee: Option<String>,
     ) -> StdResult<WasmMsg> {
         match self {
             LiquidPoolerConfig::Osmosis(config) => Ok(config
@@ -58,6 +164,7 @@ impl LiquidPoolerConfig {
                     clock_addr.to_string(),
                     holder_addr.to_string(),
                     pool_price_config,
+                    emergency_committee,
                 )
                 .to_instantiate2_msg(instantiate2_helper, admin, label)?),
             LiquidPoolerConfig::Astroport(config) => Ok(config
@@ -65,6 +172,7 @@ impl LiquidPoolerConfig {
                     holder_addr.to_string(),
                     pool_price_config,
                     ContractOperationModeConfig::Permissioned(vec![clock_addr.to_string()]),
+                    emergency_committee,
                 )
                 .to_instantiate2_msg(instantiate2_helper, admin, label)?),
         }
@@ -81,10 +189,10 @@ impl CovenantPartyConfig {
                 destination_receiver_addr: config.party_receiver_addr.to_string(),
                 ibc_transfer_timeout: config.ibc_transfer_timeout,
                 denom_to_pfm_map: config.denom_to_pfm_map.clone(),
+                memo_template: None,
+                wasm_hook: None,
             }),
-            CovenantPartyConfig::Native(config) => {
-                ReceiverConfig::Native(config.party_receiver_addr.to_string())
-            }
+            CovenantPartyConfig::Native(config) => config.to_receiver_config(),
         }
     }
 
@@ -114,6 +222,7 @@ impl CovenantPartyConfig {
         &self,
         allocation: Decimal,
         router: String,
+        contribution_adapter: Option<ContributionAdapter>,
     ) -> TwoPartyPolCovenantParty {
         match &self {
             CovenantPartyConfig::Interchain(config) => TwoPartyPolCovenantParty {
@@ -125,6 +234,7 @@ impl CovenantPartyConfig {
                 controller_addr: config.party_receiver_addr.to_string(),
                 allocation,
                 router,
+                contribution_adapter,
             },
             CovenantPartyConfig::Native(config) => TwoPartyPolCovenantParty {
                 contribution: config.contribution.clone(),
@@ -132,6 +242,7 @@ impl CovenantPartyConfig {
                 controller_addr: config.party_receiver_addr.to_string(),
                 allocation,
                 router,
+                contribution_adapter,
             },
         }
     }
@@ -150,6 +261,7 @@ impl CovenantPartyConfig {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn to_router_instantiate2_msg(
         &self,
         admin_addr: String,
@@ -157,6 +269,9 @@ impl CovenantPartyConfig {
         label: String,
         denoms: BTreeSet<String>,
         instantiate2_helper: Instantiate2HelperConfig,
+        halt_config: RouterHaltConfig,
+        memo_template: Option<String>,
+        ibc_fee_override: Option<IbcFee>,
     ) -> StdResult<WasmMsg> {
         match self {
             CovenantPartyConfig::Interchain(party) => {
@@ -169,8 +284,16 @@ impl CovenantPartyConfig {
                         destination_receiver_addr: party.party_receiver_addr.to_string(),
                         ibc_transfer_timeout: party.ibc_transfer_timeout,
                         denom_to_pfm_map: party.denom_to_pfm_map.clone(),
+                        memo_template,
+                        wasm_hook: None,
                     },
                     denoms,
+                    holder_address: Some(halt_config.holder_address.to_string()),
+                    halt_config: Some(halt_config),
+                    owner: None,
+                    denom_to_destination_map: BTreeMap::new(),
+                    min_route_amounts: BTreeMap::new(),
+                    ibc_fee_override,
                 };
                 Ok(instantiate_msg.to_instantiate2_msg(&instantiate2_helper, admin_addr, label)?)
             }
@@ -180,7 +303,12 @@ impl CovenantPartyConfig {
                         clock_addr.to_string()
                     ]),
                     receiver_address: party.party_receiver_addr.to_string(),
+                    receiver_msg: party.receiver_msg.clone(),
                     denoms,
+                    holder_address: Some(halt_config.holder_address.to_string()),
+                    halt_config: Some(halt_config),
+                    min_route_amounts: BTreeMap::new(),
+                    cw20_tokens: BTreeSet::new(),
                 };
                 Ok(instantiate_msg.to_instantiate2_msg(&instantiate2_helper, admin_addr, label)?)
             }
@@ -251,7 +379,21 @@ impl Default for Timeouts {
 }
 
 #[cw_serde]
-pub enum ExecuteMsg {}
+pub enum ExecuteMsg {
+    /// callback through which the party ibc forwarders report
+    /// their milestones
+    ForwarderMilestone { milestone: ForwarderMilestone },
+    /// admin-only. instantiates a parallel set of the covenant contracts
+    /// from the currently stored contract codes, without routing any funds,
+    /// and migrates each of them to its candidate code. reports whether each
+    /// of them deployed as planned and runs the candidate code after migrating.
+    /// the shadow contracts are handed over to the covenant admin afterwards.
+    ShadowDeploy { codes: CovenantContractCodes },
+    /// instantiates the next batch of covenant contracts of a staged
+    /// deployment. permissionless, as the deployment plan is fixed
+    /// upon instantiation.
+    DeployNext {},
+}
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -268,8 +410,23 @@ pub enum QueryMsg {
     LiquidPoolerAddress {},
     #[returns(Addr)]
     PartyDepositAddress { party: String },
+    /// returns the party deposit address along with whether
+    /// it is ready to receive the party contribution
+    #[returns(PartyDepositStatus)]
+    PartyDepositStatus { party: String },
     #[returns(CovenantContractCodes)]
     ContractCodes {},
+    /// returns the ordered instantiation messages emitted by the covenant,
+    /// along with the code checksums and derived addresses they relied on
+    #[returns(DeploymentPlan)]
+    DeploymentPlan {},
+    /// returns the report of the latest shadow deployment, if any
+    #[returns(Option<ShadowDeployment>)]
+    ShadowDeployment {},
+    /// returns the covenant contracts instantiated so far,
+    /// along with the ones still pending
+    #[returns(DeploymentProgress)]
+    DeploymentProgress {},
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -285,6 +442,8 @@ pub enum MigrateMsg {
         party_a_forwarder: Option<valence_ibc_forwarder::msg::MigrateMsg>,
         party_b_forwarder: Option<valence_ibc_forwarder::msg::MigrateMsg>,
     },
+    /// halts both party routers, returning their remaining balances to the holder
+    HaltRouters {},
     UpdateCodeId {
         data: Option<Binary>,
     },
@@ -301,3 +460,13 @@ pub enum RouterMigrateMsg {
     Interchain(valence_interchain_router::msg::MigrateMsg),
     Native(valence_native_router::msg::MigrateMsg),
 }
+
+#[cw_serde]
+pub struct PartyDepositStatus {
+    /// address the party should deposit its contribution to, if it exists yet
+    pub deposit_address: Option<String>,
+    /// whether the deposit address is ready to receive the contribution
+    pub ready: bool,
+    /// milestones reached by the party ibc forwarder, if the party is interchain
+    pub forwarder_milestones: Option<ForwarderMilestones>,
+}
@@ -1,6 +1,10 @@
 use crate::msg::CovenantContractCodes;
 use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use covenant_utils::{
+    forwarder_milestone::ForwarderMilestones,
+    plan::{DeploymentPlan, ShadowDeployment, StagedDeployment},
+};
+use cw_storage_plus::{Item, Map};
 
 pub const COVENANT_CLOCK_ADDR: Item<Addr> = Item::new("covenant_clock_addr");
 pub const COVENANT_POL_HOLDER_ADDR: Item<Addr> = Item::new("covenant_two_party_pol_holder_addr");
@@ -10,4 +14,13 @@ pub const PARTY_A_ROUTER_ADDR: Item<Addr> = Item::new("party_a_router_addr");
 pub const PARTY_B_ROUTER_ADDR: Item<Addr> = Item::new("party_b_router_addr");
 pub const LIQUID_POOLER_ADDR: Item<Addr> = Item::new("liquid_pooler_addr");
 
+/// milestones reported by the party ibc forwarders, keyed by party
+pub const FORWARDER_MILESTONES: Map<&str, ForwarderMilestones> = Map::new("forwarder_milestones");
+
 pub(crate) const CONTRACT_CODES: Item<CovenantContractCodes> = Item::new("contract_codes");
+
+/// ordered instantiation messages emitted by the covenant upon instantiation
+pub(crate) const DEPLOYMENT_PLAN: Item<DeploymentPlan> = Item::new("deployment_plan");
+/// progress of the deployment plan, if it is instantiated in batches
+pub(crate) const STAGED_DEPLOYMENT: Item<StagedDeployment> = Item::new("staged_deployment");
+pub(crate) const SHADOW_DEPLOYMENT: Item<ShadowDeployment> = Item::new("shadow_deployment");
@@ -2,29 +2,59 @@ use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
 use cosmwasm_std::{
-    ensure, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult,
+    ensure, to_json_binary, Addr, Attribute, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, Decimal,
+    Deps, DepsMut, Env, Event, HexBinary, MessageInfo, Order, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, SubMsgResult, Uint128, WasmMsg,
 };
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use covenant_utils::clock::{enqueue_msg, verify_clock};
+use astroport::generator::{ExecuteMsg as GeneratorExecuteMsg, QueryMsg as GeneratorQueryMsg};
+use covenant_utils::clock::{
+    enqueue_msg, ensure_single_trigger_per_block, increment_tick_nonce, verify_clock,
+};
+use covenant_utils::dashboard::{query_pool_position, PoolPosition};
+use covenant_utils::deadline::Deadline;
+use covenant_utils::denom::ensure_denoms_exist;
+use covenant_utils::distribution_receipt::{
+    generate_register_distribution_msg, DeliveryStatus, DistributionReceipt,
+};
 use covenant_utils::split::SplitConfig;
-use covenant_utils::withdraw_lp_helper::{generate_withdraw_msg, EMERGENCY_COMMITTEE_ADDR};
+use covenant_utils::split_receiver_fix::{
+    fix_split_receiver, SplitReceiverFixStatus, PENDING_SPLIT_RECEIVER_FIXES,
+};
+use covenant_utils::withdraw_lp_helper::{
+    generate_donate_lp_msg, generate_pause_provisioning_msg, generate_stake_lp_msg,
+    generate_withdraw_msg, EMERGENCY_COMMITTEE_ADDR,
+};
 use cw2::set_contract_version;
-
-use crate::msg::CovenantType;
-use crate::state::{WithdrawState, LIQUID_POOLER_ADDRESS, WITHDRAW_STATE};
+use cw20::Cw20ExecuteMsg;
+use cw_utils::Expiration;
+
+use crate::msg::{CovenantType, DEFAULT_LOCKUP_EXTENSION_WINDOW, FEE_DENOM};
+use crate::state::{
+    WithdrawState, ACCRUED_DISTRIBUTIONS, AMENDMENT_COUNT, AMENDMENT_HISTORY,
+    AUTO_DISTRIBUTE_ON_EXPIRY, CLAIM_RECEIPTS, COMPLETED_AT, DEPEG, DEPEG_GUARD_CONFIG,
+    DEPOSITOR_ALLOWLIST, DEPOSIT_RECORDS, FEE_REFUNDS, FEE_REFUND_CONFIG, GENERATOR_STAKING_CONFIG,
+    INSTANTIATED_AT, LIQUID_POOLER_ADDRESS, LOCKUP_EXTENSION_WINDOW, NEXT_CLAIM_ID,
+    PARTY_DISTRIBUTIONS, PENDING_AMENDMENT, PENDING_DEPOSIT_DEADLINE_EXTENSION,
+    PENDING_LOCKUP_EXTENSION, PENDING_REBINDINGS, PROVISIONING_STALL, PROVISIONING_STALL_CONFIG,
+    RETAINED_CLAIM_FUNDS, VESTING_CLAIMED_SHARES, VESTING_CONFIG, WITHDRAW_STATE,
+};
 use crate::{
     error::ContractError,
     msg::{
-        ContractState, DenomSplits, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-        RagequitConfig, RagequitState, TwoPartyPolCovenantConfig, TwoPartyPolCovenantParty,
+        AmendmentProposal, AmendmentRecord, ContractState, DashboardResponse, DenomSplits, Depeg,
+        DepegAction, DepositDeadlineExtension, DepositRecord, ExecuteMsg, GeneratorStakingConfig,
+        InstantiateMsg, LockupExtensionProposal, MigrateMsg, PartyRebinding, PartySettlement,
+        ProvisioningStall, QueryMsg, RagequitConfig, RagequitPenalty, RagequitSettlement,
+        RagequitState, RagequitTerms, ReinvestTarget, SettlementReport, TermsAmendment,
+        TwoPartyPolCovenantConfig, TwoPartyPolCovenantParty,
     },
     state::{
         CLOCK_ADDRESS, CONTRACT_STATE, COVENANT_CONFIG, DENOM_SPLITS, DEPOSIT_DEADLINE,
-        LOCKUP_CONFIG, RAGEQUIT_CONFIG,
+        LOCKUP_CONFIG, RAGEQUIT_CONFIG, RESIDUAL_LP_DONATION,
     },
 };
 
@@ -64,10 +94,63 @@ pub fn instantiate(
         EMERGENCY_COMMITTEE_ADDR.save(deps.storage, &committee_addr)?;
     }
 
+    if let Some(config) = &msg.residual_lp_donation {
+        config.validate(deps.api, &msg.lockup_config)?;
+        RESIDUAL_LP_DONATION.save(deps.storage, config)?;
+    }
+
+    if let Some(config) = &msg.fee_refund_config {
+        deps.api.addr_validate(&config.fee_payer)?;
+        FEE_REFUND_CONFIG.save(deps.storage, config)?;
+    }
+
+    if let Some(config) = &msg.generator_staking {
+        config.validate(deps.api)?;
+        GENERATOR_STAKING_CONFIG.save(deps.storage, config)?;
+    }
+
+    if let Some(config) = &msg.provisioning_stall {
+        PROVISIONING_STALL_CONFIG.save(deps.storage, config)?;
+    }
+
+    if let Some(allowlist) = &msg.depositor_allowlist {
+        allowlist.validate(deps.api)?;
+        DEPOSITOR_ALLOWLIST.save(deps.storage, allowlist)?;
+    }
+
+    if let Some(config) = &msg.vesting_config {
+        config.validate(&msg.covenant_config.covenant_type)?;
+        VESTING_CONFIG.save(deps.storage, config)?;
+    }
+
+    if let Some(config) = &msg.depeg_guard {
+        config.validate(deps.api)?;
+        DEPEG_GUARD_CONFIG.save(deps.storage, config)?;
+    }
+
     msg.covenant_config.validate(deps.api)?;
+
+    // if enabled, validate that the configured denoms exist on this chain
+    if msg.validate_denoms {
+        let mut denoms = vec![
+            msg.covenant_config.party_a.contribution.denom.as_str(),
+            msg.covenant_config.party_b.contribution.denom.as_str(),
+        ];
+        denoms.extend(msg.splits.keys().map(String::as_str));
+        // adapted contributions reach the liquid pooler in the target denom
+        denoms.extend(
+            [&msg.covenant_config.party_a, &msg.covenant_config.party_b]
+                .into_iter()
+                .filter_map(|party| party.contribution_adapter.as_ref())
+                .map(|adapter| adapter.target_denom.as_str()),
+        );
+        ensure_denoms_exist(&deps.querier, denoms)?;
+    }
+
     msg.ragequit_config.validate(
         msg.covenant_config.party_a.allocation,
         msg.covenant_config.party_b.allocation,
+        &msg.covenant_config.covenant_type,
     )?;
 
     // validate the splits and collect them into map
@@ -105,8 +188,15 @@ pub fn instantiate(
     LIQUID_POOLER_ADDRESS.save(deps.storage, &next_contract)?;
     CLOCK_ADDRESS.save(deps.storage, &clock_addr)?;
     LOCKUP_CONFIG.save(deps.storage, &msg.lockup_config)?;
+    LOCKUP_EXTENSION_WINDOW.save(
+        deps.storage,
+        &msg.lockup_extension_window
+            .unwrap_or(DEFAULT_LOCKUP_EXTENSION_WINDOW),
+    )?;
+    AUTO_DISTRIBUTE_ON_EXPIRY.save(deps.storage, &msg.auto_distribute_on_expiry)?;
     RAGEQUIT_CONFIG.save(deps.storage, &msg.ragequit_config)?;
     CONTRACT_STATE.save(deps.storage, &ContractState::Instantiated)?;
+    INSTANTIATED_AT.save(deps.storage, &env.block.time)?;
     COVENANT_CONFIG.save(deps.storage, &msg.covenant_config)?;
     DEPOSIT_DEADLINE.save(deps.storage, &msg.deposit_deadline)?;
 
@@ -125,55 +215,617 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Ragequit {} => try_ragequit(deps, env, info),
-        ExecuteMsg::Tick {} => try_tick(deps, env, info),
-        ExecuteMsg::Claim {} => try_claim(deps, info),
-        ExecuteMsg::Distribute {} => try_distribute(deps, info),
+        ExecuteMsg::Tick {} => {
+            let tick_nonce = increment_tick_nonce(deps.storage)?;
+            Ok(try_tick(deps, env, info)?.add_attribute("tick_nonce", tick_nonce.to_string()))
+        }
+        ExecuteMsg::Claim {
+            reinvest,
+            to,
+            denoms,
+        } => try_claim(deps, env, info, reinvest, to, denoms),
+        ExecuteMsg::Distribute {} => try_distribute(deps, env, info),
         ExecuteMsg::WithdrawFailed {} => try_withdraw_failed(deps, info),
         ExecuteMsg::DistributeFallbackSplit { denoms } => {
             try_distribute_fallback_split(deps, env, denoms)
         }
-        ExecuteMsg::EmergencyWithdraw {} => try_emergency_withdraw(deps, info),
+        ExecuteMsg::EmergencyWithdraw {} => try_emergency_withdraw(deps, env, info),
+        ExecuteMsg::AcknowledgeDistribution { id } => {
+            try_settle_distribution(deps, info, id, DeliveryStatus::Delivered)
+        }
+        ExecuteMsg::ReportDistributionFailure { id, reason } => {
+            try_settle_distribution(deps, info, id, DeliveryStatus::Failed { reason })
+        }
+        ExecuteMsg::RequestPartyRebinding { rebinding } => {
+            try_request_party_rebinding(deps, info, rebinding)
+        }
+        ExecuteMsg::ApprovePartyRebinding { party } => {
+            try_approve_party_rebinding(deps, info, party)
+        }
+        ExecuteMsg::FixSplitReceiver { denom, old, new } => {
+            try_fix_split_receiver(deps, env, info, denom, old, new)
+        }
+        ExecuteMsg::ProposeLockupExtension { new_expiry } => {
+            try_propose_lockup_extension(deps, env, info, new_expiry)
+        }
+        ExecuteMsg::ApproveLockupExtension {} => try_approve_lockup_extension(deps, env, info),
+        ExecuteMsg::Deposit {} => try_deposit_funds(deps, env, info),
+        ExecuteMsg::NotifyDeposit {} => try_notify_deposit(deps, env, info),
+        ExecuteMsg::ExtendDepositDeadline { expiry } => {
+            try_extend_deposit_deadline(deps, env, info, expiry)
+        }
+        ExecuteMsg::ProposeAmendment { amendment } => {
+            try_propose_amendment(deps, env, info, amendment)
+        }
+        ExecuteMsg::ApproveAmendment { hash } => try_approve_amendment(deps, env, info, hash),
     }
 }
 
-fn try_distribute_fallback_split(
+/// the lockup can only be extended while it is still running
+fn ensure_lockup_extendable(deps: Deps, env: &Env) -> Result<Expiration, ContractError> {
+    let state = CONTRACT_STATE.load(deps.storage)?;
+    let lockup_config = LOCKUP_CONFIG.load(deps.storage)?;
+    ensure!(
+        matches!(state, ContractState::Instantiated | ContractState::Active)
+            && !lockup_config.is_expired(&env.block),
+        ContractError::LockupExtensionUnavailable {}
+    );
+    Ok(lockup_config)
+}
+
+/// stores the lockup extension proposed by the sender party. any
+/// previously proposed extension is replaced.
+fn try_propose_lockup_extension(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_expiry: Expiration,
+) -> Result<Response, ContractError> {
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let (party, _) = covenant_config.authorize_party_sender(&deps.querier, &info.sender)?;
+
+    let lockup_config = ensure_lockup_extendable(deps.as_ref(), &env)?;
+    // extensions must be of the same unit as the current lockup
+    ensure!(
+        new_expiry.partial_cmp(&lockup_config) == Some(Ordering::Greater),
+        ContractError::LockupExtensionValidationError {}
+    );
+
+    let window = LOCKUP_EXTENSION_WINDOW
+        .may_load(deps.storage)?
+        .unwrap_or(DEFAULT_LOCKUP_EXTENSION_WINDOW);
+    let proposal = LockupExtensionProposal {
+        proposer: party.host_addr,
+        new_expiry,
+        expires_at: env.block.time.plus_seconds(window),
+    };
+    PENDING_LOCKUP_EXTENSION.save(deps.storage, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_propose_lockup_extension")
+        .add_attributes(proposal.get_response_attributes()))
+}
+
+/// applies the lockup extension proposed by the counterparty of the sender
+fn try_approve_lockup_extension(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let (party, _) = covenant_config.authorize_party_sender(&deps.querier, &info.sender)?;
+
+    let proposal = PENDING_LOCKUP_EXTENSION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingLockupExtension {})?;
+    // parties may not approve their own proposals
+    ensure!(
+        proposal.proposer != party.host_addr,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        env.block.time < proposal.expires_at,
+        ContractError::LockupExtensionProposalExpired {}
+    );
+    ensure_lockup_extendable(deps.as_ref(), &env)?;
+
+    PENDING_LOCKUP_EXTENSION.remove(deps.storage);
+    LOCKUP_CONFIG.save(deps.storage, &proposal.new_expiry)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_approve_lockup_extension")
+        .add_attribute("approver", party.host_addr)
+        .add_attribute("lockup_config", proposal.new_expiry.to_string()))
+}
+
+/// terms can be amended until the lockup expires. the amended terms
+/// are validated just like the instantiation ones.
+fn validate_terms_amendment(
+    deps: Deps,
+    env: &Env,
+    amendment: &TermsAmendment,
+) -> Result<(), ContractError> {
+    ensure!(!amendment.is_empty(), ContractError::EmptyAmendment {});
+    let state = CONTRACT_STATE.load(deps.storage)?;
+    ensure!(
+        matches!(state, ContractState::Instantiated | ContractState::Active)
+            && !LOCKUP_CONFIG.load(deps.storage)?.is_expired(&env.block),
+        ContractError::AmendmentUnavailable {}
+    );
+
+    if let Some(lockup_config) = &amendment.lockup_config {
+        ensure!(
+            !lockup_config.is_expired(&env.block),
+            ContractError::AmendedLockupExpired {}
+        );
+        match DEPOSIT_DEADLINE
+            .load(deps.storage)?
+            .partial_cmp(lockup_config)
+        {
+            Some(ordering) => ensure!(
+                ordering == Ordering::Less,
+                ContractError::LockupValidationError {}
+            ),
+            None => return Err(ContractError::ExpirationValidationError {}),
+        };
+    }
+
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    if let Some(ragequit_config) = &amendment.ragequit_config {
+        ragequit_config.validate(
+            covenant_config.party_a.allocation,
+            covenant_config.party_b.allocation,
+            &covenant_config.covenant_type,
+        )?;
+    }
+    for split in amendment
+        .denom_splits
+        .iter()
+        .flat_map(|splits| splits.values())
+        .chain(amendment.fallback_split.iter())
+    {
+        split.validate(
+            &covenant_config.party_a.router,
+            &covenant_config.party_b.router,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// stores the terms amendment proposed by the sender party. any
+/// previously proposed amendment is replaced.
+fn try_propose_amendment(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amendment: TermsAmendment,
+) -> Result<Response, ContractError> {
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let (party, _) = covenant_config.authorize_party_sender(&deps.querier, &info.sender)?;
+    validate_terms_amendment(deps.as_ref(), &env, &amendment)?;
+
+    let proposal = AmendmentProposal {
+        proposer: party.host_addr,
+        hash: amendment.get_hash()?,
+        amendment,
+    };
+    PENDING_AMENDMENT.save(deps.storage, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_propose_amendment")
+        .add_attribute("proposer", &proposal.proposer)
+        .add_attribute("hash", proposal.hash.to_hex())
+        .add_attributes(proposal.amendment.get_response_attributes()))
+}
+
+/// applies the terms amendment proposed by the counterparty of the
+/// sender, and appends it to the amendment history
+fn try_approve_amendment(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hash: HexBinary,
+) -> Result<Response, ContractError> {
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let (party, _) = covenant_config.authorize_party_sender(&deps.querier, &info.sender)?;
+
+    let proposal = PENDING_AMENDMENT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAmendment {})?;
+    // parties may not approve their own proposals
+    ensure!(
+        proposal.proposer != party.host_addr,
+        ContractError::Unauthorized {}
+    );
+    // the approval is bound to the exact amendment the approver inspected
+    ensure!(
+        proposal.hash == hash,
+        ContractError::AmendmentHashMismatch {}
+    );
+    // the terms may have changed since the proposal
+    validate_terms_amendment(deps.as_ref(), &env, &proposal.amendment)?;
+
+    let amendment = proposal.amendment;
+    if let Some(lockup_config) = &amendment.lockup_config {
+        LOCKUP_CONFIG.save(deps.storage, lockup_config)?;
+    }
+    if let Some(ragequit_config) = &amendment.ragequit_config {
+        RAGEQUIT_CONFIG.save(deps.storage, ragequit_config)?;
+    }
+    DENOM_SPLITS.update(deps.storage, |mut denom_splits| -> StdResult<_> {
+        if let Some(explicit_splits) = &amendment.denom_splits {
+            denom_splits.explicit_splits = explicit_splits.clone();
+        }
+        if let Some(fallback_split) = &amendment.fallback_split {
+            denom_splits.fallback_split = Some(fallback_split.clone());
+        }
+        Ok(denom_splits)
+    })?;
+    if amendment.denom_splits.is_some() {
+        // fixes scheduled against the previous splits no longer apply
+        PENDING_SPLIT_RECEIVER_FIXES.clear(deps.storage);
+    }
+
+    let index = AMENDMENT_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    let record = AmendmentRecord {
+        index,
+        proposer: proposal.proposer,
+        approver: party.host_addr,
+        amendment,
+        hash,
+        height: env.block.height,
+    };
+    AMENDMENT_HISTORY.save(deps.storage, index, &record)?;
+    AMENDMENT_COUNT.save(deps.storage, &(index + 1))?;
+    PENDING_AMENDMENT.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("method", "try_approve_amendment")
+        .add_attribute("approver", &record.approver)
+        .add_attribute("amendment_index", index.to_string())
+        .add_attribute("hash", record.hash.to_hex())
+        .add_attributes(record.amendment.get_response_attributes()))
+}
+
+/// records the deposit deadline extension submitted by the sender party,
+/// replacing any pending one. once the counterparty submits the same
+/// expiry, the deposit deadline is extended.
+fn try_extend_deposit_deadline(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    expiry: Expiration,
+) -> Result<Response, ContractError> {
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let (party, _) = covenant_config.authorize_party_sender(&deps.querier, &info.sender)?;
+
+    // deadlines can only be extended while the deposits are still awaited
+    let deposit_deadline = DEPOSIT_DEADLINE.load(deps.storage)?;
+    ensure!(
+        CONTRACT_STATE.load(deps.storage)? == ContractState::Instantiated
+            && !deposit_deadline.is_expired(&env.block),
+        ContractError::DepositDeadlineExtensionUnavailable {}
+    );
+    // extensions must be of the same unit as the current deadline,
+    // and still expire before the lockup
+    ensure!(
+        expiry.partial_cmp(&deposit_deadline) == Some(Ordering::Greater)
+            && expiry.partial_cmp(&LOCKUP_CONFIG.load(deps.storage)?) == Some(Ordering::Less),
+        ContractError::DepositDeadlineExtensionValidationError {}
+    );
+
+    let approved = PENDING_DEPOSIT_DEADLINE_EXTENSION
+        .may_load(deps.storage)?
+        .is_some_and(|pending| pending.proposer != party.host_addr && pending.expiry == expiry);
+    if !approved {
+        PENDING_DEPOSIT_DEADLINE_EXTENSION.save(
+            deps.storage,
+            &DepositDeadlineExtension {
+                proposer: party.host_addr.to_string(),
+                expiry,
+            },
+        )?;
+        return Ok(Response::default()
+            .add_attribute("method", "try_extend_deposit_deadline")
+            .add_attribute("proposer", party.host_addr)
+            .add_attribute("pending_deposit_deadline", expiry.to_string()));
+    }
+
+    PENDING_DEPOSIT_DEADLINE_EXTENSION.remove(deps.storage);
+    DEPOSIT_DEADLINE.save(deps.storage, &expiry)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_extend_deposit_deadline")
+        .add_attribute("approver", party.host_addr)
+        .add_attribute("deposit_deadline", expiry.to_string()))
+}
+
+/// schedules (or applies, once timelocked) the replacement of a
+/// receiver of the explicit split of the denom
+fn try_fix_split_receiver(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    old: String,
+    new: String,
+) -> Result<Response, ContractError> {
+    let committee_addr = EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?;
+    ensure!(
+        Some(&info.sender) == committee_addr.as_ref(),
+        ContractError::Unauthorized {}
+    );
+    deps.api.addr_validate(&new)?;
+
+    let mut denom_splits = DENOM_SPLITS.load(deps.storage)?;
+    let split = denom_splits
+        .explicit_splits
+        .get_mut(&denom)
+        .ok_or_else(|| StdError::not_found(format!("split for denom {denom:?}")))?;
+
+    let status = fix_split_receiver(deps.storage, split, denom.to_string(), old, new, &env.block)?;
+    if let SplitReceiverFixStatus::Applied(_) = status {
+        DENOM_SPLITS.save(deps.storage, &denom_splits)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "try_fix_split_receiver")
+        .add_event(status.to_event()))
+}
+
+/// stores the rebinding requested by the sender party. any previously
+/// requested rebinding of the party is replaced.
+fn try_request_party_rebinding(
+    deps: DepsMut,
+    info: MessageInfo,
+    rebinding: PartyRebinding,
+) -> Result<Response, ContractError> {
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let (party, counterparty) =
+        covenant_config.authorize_party_sender(&deps.querier, &info.sender)?;
+
+    rebinding.validate(deps.api)?;
+    // parties may not take over the addresses of their counterparty
+    ensure!(
+        rebinding.host_addr.as_ref() != Some(&counterparty.host_addr)
+            && rebinding.router.as_ref() != Some(&counterparty.router),
+        ContractError::PartyRebindingConflict {}
+    );
+
+    PENDING_REBINDINGS.save(deps.storage, party.host_addr.to_string(), &rebinding)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_request_party_rebinding")
+        .add_attribute("party", party.host_addr)
+        .add_attributes(rebinding.get_response_attributes()))
+}
+
+/// applies the pending rebinding of the party. only the counterparty
+/// or the emergency committee may approve it. the covenant config,
+/// the split receivers, and the lifetime distributions of the party
+/// are updated together.
+fn try_approve_party_rebinding(
+    deps: DepsMut,
+    info: MessageInfo,
+    party_host_addr: String,
+) -> Result<Response, ContractError> {
+    let mut covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let (party, counterparty) = if covenant_config.party_a.host_addr == party_host_addr {
+        (&mut covenant_config.party_a, &covenant_config.party_b)
+    } else if covenant_config.party_b.host_addr == party_host_addr {
+        (&mut covenant_config.party_b, &covenant_config.party_a)
+    } else {
+        return Err(ContractError::PartyNotFound {});
+    };
+
+    let committee_addr = EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?;
+    ensure!(
+        Some(&info.sender) == committee_addr.as_ref()
+            || counterparty.is_sender(&deps.querier, &info.sender)?,
+        ContractError::Unauthorized {}
+    );
+    // in-flight withdrawals refer to the current party addresses
+    ensure!(
+        WITHDRAW_STATE.may_load(deps.storage)?.is_none(),
+        ContractError::WithdrawAlreadyStarted {}
+    );
+
+    let rebinding = PENDING_REBINDINGS
+        .may_load(deps.storage, party_host_addr.to_string())?
+        .ok_or(ContractError::NoPendingPartyRebinding {})?;
+    PENDING_REBINDINGS.remove(deps.storage, party_host_addr.to_string());
+
+    if let Some(router) = &rebinding.router {
+        let mut denom_splits = DENOM_SPLITS.load(deps.storage)?;
+        denom_splits
+            .explicit_splits
+            .values_mut()
+            .chain(denom_splits.fallback_split.iter_mut())
+            .try_for_each(|split| split.replace_receiver(&party.router, router))?;
+        DENOM_SPLITS.save(deps.storage, &denom_splits)?;
+        party.router = router.to_string();
+    }
+
+    if let Some(host_addr) = &rebinding.host_addr {
+        let distributions = PARTY_DISTRIBUTIONS
+            .prefix(party.host_addr.to_string())
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (denom, amount) in distributions {
+            PARTY_DISTRIBUTIONS.remove(deps.storage, (party.host_addr.to_string(), denom.clone()));
+            PARTY_DISTRIBUTIONS.save(deps.storage, (host_addr.to_string(), denom), &amount)?;
+        }
+        let retained_funds = RETAINED_CLAIM_FUNDS
+            .prefix(party.host_addr.to_string())
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (denom, amount) in retained_funds {
+            RETAINED_CLAIM_FUNDS.remove(deps.storage, (party.host_addr.to_string(), denom.clone()));
+            RETAINED_CLAIM_FUNDS.save(deps.storage, (host_addr.to_string(), denom), &amount)?;
+        }
+        if let Some(record) = DEPOSIT_RECORDS.may_load(deps.storage, party.host_addr.to_string())? {
+            DEPOSIT_RECORDS.remove(deps.storage, party.host_addr.to_string());
+            DEPOSIT_RECORDS.save(deps.storage, host_addr.to_string(), &record)?;
+        }
+        party.host_addr = host_addr.to_string();
+    }
+
+    if let Some(controller_addr) = &rebinding.controller_addr {
+        party.controller_addr = controller_addr.to_string();
+    }
+
+    let party_attributes = party.get_response_attributes();
+    COVENANT_CONFIG.save(deps.storage, &covenant_config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_approve_party_rebinding")
+        .add_attribute("approver", info.sender)
+        .add_attributes(party_attributes))
+}
+
+/// updates the delivery status of the receipt issued to the sender
+/// as part of the claim with the given id
+fn try_settle_distribution(
     deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    status: DeliveryStatus,
+) -> Result<Response, ContractError> {
+    let mut receipts = CLAIM_RECEIPTS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::UnknownClaim { id })?;
+
+    let receipt = receipts
+        .iter_mut()
+        .find(|receipt| receipt.router == info.sender)
+        .ok_or(ContractError::Unauthorized {})?;
+    ensure!(
+        receipt.status == DeliveryStatus::Pending,
+        ContractError::DistributionAlreadySettled {}
+    );
+    receipt.status = status.clone();
+
+    CLAIM_RECEIPTS.save(deps.storage, id, &receipts)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_settle_distribution")
+        .add_attribute("claim_id", id.to_string())
+        .add_attribute("router", info.sender)
+        .add_attribute("status", format!("{:?}", status)))
+}
+
+fn try_distribute_fallback_split(
+    mut deps: DepsMut,
     env: Env,
     denoms: Vec<String>,
 ) -> Result<Response, ContractError> {
     let mut available_balances = Vec::with_capacity(denoms.len());
+    let mut accrued_distributions = BTreeMap::new();
+    let mut fee_refund_msg = None;
     let denom_splits = DENOM_SPLITS.load(deps.storage)?;
 
     for denom in denoms {
         if denom_splits.explicit_splits.contains_key(&denom) {
             return Err(ContractError::UnauthorizedDenomDistribution {});
         }
-        let queried_coin = deps
+        // untrn fee refunds go back to the fee payer rather than the parties
+        if denom == FEE_DENOM && FEE_REFUND_CONFIG.exists(deps.storage) {
+            fee_refund_msg = get_fee_refund_msg(deps.branch(), &env, Uint128::zero())?;
+            continue;
+        }
+        let accrued = ACCRUED_DISTRIBUTIONS
+            .may_load(deps.storage, denom.to_string())?
+            .unwrap_or_default();
+        accrued_distributions.insert(denom.to_string(), accrued);
+        let mut queried_coin = deps
             .querier
             .query_balance(env.contract.address.to_string(), denom)?;
+        // funds retained by selective claims belong to the claim party
+        queried_coin.amount = queried_coin
+            .amount
+            .saturating_sub(query_retained_claim_total(
+                deps.storage,
+                &queried_coin.denom,
+            )?);
         available_balances.push(queried_coin);
     }
 
-    let fallback_distribution_messages =
-        denom_splits.get_fallback_distribution_messages(available_balances);
+    // once the covenant is complete, accrued amounts are flushed
+    // regardless of the receiver min amounts
+    let flush = CONTRACT_STATE.load(deps.storage)? == ContractState::Complete;
+    let fallback_distribution_messages = denom_splits.get_fallback_distribution_messages(
+        available_balances,
+        &mut accrued_distributions,
+        flush,
+    );
+
+    for (denom, accrued) in accrued_distributions {
+        if accrued.is_empty() {
+            ACCRUED_DISTRIBUTIONS.remove(deps.storage, denom);
+        } else {
+            ACCRUED_DISTRIBUTIONS.save(deps.storage, denom, &accrued)?;
+        }
+    }
+
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    record_party_distributions(
+        deps.storage,
+        &covenant_config,
+        &fallback_distribution_messages,
+    )?;
 
     Ok(Response::default()
         .add_attribute("method", "try_distribute_fallback_split")
-        .add_messages(fallback_distribution_messages))
+        .add_messages(fallback_distribution_messages)
+        .add_messages(fee_refund_msg))
 }
 
 /// On claim, we should simply ask the LPer to withdraw the liquidity and execute a Distribute msg on the holder
-fn try_claim(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+fn try_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reinvest: Option<ReinvestTarget>,
+    to: Option<String>,
+    denoms: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
     if WITHDRAW_STATE.load(deps.storage).is_ok() {
         return Err(ContractError::WithdrawAlreadyStarted {});
     }
+    ensure!(
+        !matches!(denoms.as_deref(), Some([])),
+        ContractError::InvalidClaimDenoms {}
+    );
+    // each party gets to claim once per block, so that retried
+    // claims can not queue up additional withdrawals
+    ensure_single_trigger_per_block(deps.storage, &env.block, &format!("claim_{}", info.sender))?;
 
     let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
-    let (claim_party, counterparty) = covenant_config.authorize_sender(info.sender.to_string())?;
+    let (claim_party, counterparty) =
+        covenant_config.authorize_party_sender(&deps.querier, &info.sender)?;
+
+    // funds retained by earlier selective claims were already
+    // withdrawn, so they are paid out right away
+    let retained_funds =
+        load_retained_claim_funds(deps.storage, &claim_party.host_addr, denoms.as_deref())?;
+    if claim_party.allocation.is_zero() && !retained_funds.is_empty() {
+        let (reinvest_to, receiver) =
+            validate_claim_destination(deps.as_ref(), reinvest, to, &claim_party, &counterparty)?;
+        return try_claim_retained_funds(
+            deps,
+            claim_party,
+            covenant_config,
+            retained_funds,
+            reinvest_to,
+            receiver,
+        );
+    }
 
     // if both parties already claimed everything we complete early
     if claim_party.allocation.is_zero() && counterparty.allocation.is_zero() {
         let clock_address = CLOCK_ADDRESS.load(deps.storage)?;
+        record_completion(deps.storage, &env.block)?;
         let dequeue_message = ContractState::complete_and_dequeue(deps, clock_address.as_str())?;
 
         return Ok(Response::default()
@@ -182,54 +834,293 @@ fn try_claim(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError
             .add_message(dequeue_message));
     }
 
-    // we exit early if contract is not in ragequit or expired state
     let contract_state = CONTRACT_STATE.load(deps.storage)?;
-    contract_state.validate_claim_state()?;
+    let vested_share = query_vested_share(deps.storage, &env.block, &contract_state)?;
+    let claim_share = match vested_share {
+        Some(vested_share) => {
+            let share = get_vested_withdraw_share(deps.storage, &claim_party, vested_share)?;
+            ensure!(!share.is_zero(), ContractError::NothingVested {});
+            share
+        }
+        None => {
+            // we exit early if contract is not in ragequit or expired state
+            contract_state.validate_claim_state()?;
+            claim_party.allocation
+        }
+    };
+
+    let (reinvest_to, receiver) =
+        validate_claim_destination(deps.as_ref(), reinvest, to, &claim_party, &counterparty)?;
 
     // set WithdrawState to include original data
     WITHDRAW_STATE.save(
         deps.storage,
         &WithdrawState::Processing {
             claimer_addr: claim_party.host_addr,
+            reinvest_to,
+            receiver,
+            denoms,
+            vested_share,
         },
     )?;
 
-    // If type is share we only withdraw the claim party allocation
-    // if type is side, we withdraw 100% of funds
+    // If type is share we only withdraw the claimable part of the
+    // claim party allocation. if type is side, we withdraw 100% of funds
     let withdraw_percentage = match covenant_config.covenant_type {
-        CovenantType::Share => Some(claim_party.allocation),
+        CovenantType::Share => Some(claim_share),
         CovenantType::Side => None, // 100%
     };
 
+    let unstake_msgs = get_unstake_msgs(deps.as_ref(), &env)?;
     let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
     let withdraw_msg = generate_withdraw_msg(lper.to_string(), withdraw_percentage)?;
 
-    Ok(Response::default().add_message(withdraw_msg))
+    Ok(Response::default()
+        .add_messages(unstake_msgs)
+        .add_message(withdraw_msg))
 }
 
-fn try_emergency_withdraw(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    if WITHDRAW_STATE.load(deps.storage).is_ok() {
-        return Err(ContractError::WithdrawAlreadyStarted {});
+/// share of the party allocations vested so far, if the covenant is
+/// active and vests them. in any other state the allocations are
+/// either not claimable yet, or claimable in full.
+fn query_vested_share(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    contract_state: &ContractState,
+) -> StdResult<Option<Decimal>> {
+    if !matches!(contract_state, ContractState::Active) {
+        return Ok(None);
     }
+    Ok(VESTING_CONFIG
+        .may_load(storage)?
+        .map(|config| config.get_vested_share(block)))
+}
 
-    let committee_addr = EMERGENCY_COMMITTEE_ADDR.load(deps.storage)?;
-    ensure!(
-        info.sender == committee_addr,
-        ContractError::Unauthorized {}
-    );
+/// share of the position withdrawn by a vested claim, i.e. the part of
+/// the party allocation vested since its previous vested claims
+fn get_vested_withdraw_share(
+    storage: &dyn Storage,
+    claim_party: &TwoPartyPolCovenantParty,
+    vested_share: Decimal,
+) -> StdResult<Decimal> {
+    // fully vested allocations are withdrawn without rounding leftovers
+    if vested_share >= Decimal::one() {
+        return Ok(claim_party.allocation);
+    }
+    let claimed_share = VESTING_CLAIMED_SHARES
+        .may_load(storage, claim_party.host_addr.to_string())?
+        .unwrap_or_default();
+    if vested_share <= claimed_share {
+        return Ok(Decimal::zero());
+    }
+    Ok(claim_party.allocation * (vested_share - claimed_share) / (Decimal::one() - claimed_share))
+}
 
-    WITHDRAW_STATE.save(deps.storage, &WithdrawState::Emergency {})?;
+/// resolves the reinvest target pooler and the alternate receiver of a claim
+fn validate_claim_destination(
+    deps: Deps,
+    reinvest: Option<ReinvestTarget>,
+    to: Option<String>,
+    claim_party: &TwoPartyPolCovenantParty,
+    counterparty: &TwoPartyPolCovenantParty,
+) -> Result<(Option<String>, Option<String>), ContractError> {
+    let reinvest_to = match reinvest {
+        Some(target) => Some(query_reinvest_pooler(deps, &target, claim_party)?),
+        None => None,
+    };
+    let receiver = match to {
+        Some(to) => Some(validate_claim_receiver(
+            deps,
+            &to,
+            counterparty,
+            reinvest_to.is_some(),
+        )?),
+        None => None,
+    };
+    Ok((reinvest_to, receiver))
+}
 
-    let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
-    let withdraw_msg = generate_withdraw_msg(lper.to_string(), None)?;
+/// pays out the funds retained for the party by its earlier selective claims
+fn try_claim_retained_funds(
+    mut deps: DepsMut,
+    claim_party: TwoPartyPolCovenantParty,
+    covenant_config: TwoPartyPolCovenantConfig,
+    retained_funds: Vec<Coin>,
+    reinvest_to: Option<String>,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    for coin in &retained_funds {
+        RETAINED_CLAIM_FUNDS.remove(
+            deps.storage,
+            (claim_party.host_addr.to_string(), coin.denom.to_string()),
+        );
+    }
 
-    Ok(Response::default().add_message(withdraw_msg))
+    let messages: Vec<CosmosMsg> = vec![BankMsg::Send {
+        to_address: claim_party.router.to_string(),
+        amount: retained_funds,
+    }
+    .into()];
+    record_party_distributions(deps.storage, &covenant_config, &messages)?;
+    let messages = redirect_claim_party_funds(
+        messages,
+        &claim_party,
+        reinvest_to.clone().or(receiver.clone()),
+    );
+    let (claim_id, receipt_submsgs) =
+        issue_claim_receipts(deps.branch(), &covenant_config, &messages)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "claim_retained_funds")
+        .add_attribute("claim_id", claim_id.to_string())
+        .add_attribute(
+            "reinvest_to",
+            reinvest_to.unwrap_or_else(|| "None".to_string()),
+        )
+        .add_attribute(
+            "claim_receiver",
+            receiver.unwrap_or_else(|| "None".to_string()),
+        )
+        .add_messages(messages)
+        .add_submessages(receipt_submsgs))
 }
 
-fn try_distribute(mut deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    // Only pooler can call this
-    let pooler_addr = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
-    ensure!(info.sender == pooler_addr, ContractError::Unauthorized {});
+/// validates that the reinvest target is a single party holder withdrawn by
+/// the claiming party, and returns the address of its liquid pooler
+fn query_reinvest_pooler(
+    deps: Deps,
+    target: &ReinvestTarget,
+    claim_party: &TwoPartyPolCovenantParty,
+) -> Result<String, ContractError> {
+    let holder_addr = deps.api.addr_validate(&target.holder_address)?;
+    let withdrawer: Addr = deps
+        .querier
+        .query_wasm_smart(
+            holder_addr.to_string(),
+            &valence_single_party_pol_holder::msg::QueryMsg::Withdrawer {},
+        )
+        .map_err(|_| ContractError::UnauthorizedReinvestTarget {})?;
+    ensure!(
+        withdrawer.as_str() == claim_party.host_addr,
+        ContractError::UnauthorizedReinvestTarget {}
+    );
+
+    let pooler_addr: Addr = deps.querier.query_wasm_smart(
+        holder_addr.to_string(),
+        &valence_single_party_pol_holder::msg::QueryMsg::PoolerAddress {},
+    )?;
+
+    Ok(pooler_addr.to_string())
+}
+
+/// validates the alternate receiver of a claim, which may be any
+/// address other than the ones of the counterparty. claims are either
+/// reinvested or routed to an alternate receiver, but not both.
+fn validate_claim_receiver(
+    deps: Deps,
+    to: &str,
+    counterparty: &TwoPartyPolCovenantParty,
+    is_reinvested: bool,
+) -> Result<String, ContractError> {
+    let receiver = deps.api.addr_validate(to)?;
+    ensure!(
+        !is_reinvested && receiver != counterparty.host_addr && receiver != counterparty.router,
+        ContractError::InvalidClaimReceiver {}
+    );
+    Ok(receiver.to_string())
+}
+
+fn try_emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if WITHDRAW_STATE.load(deps.storage).is_ok() {
+        return Err(ContractError::WithdrawAlreadyStarted {});
+    }
+
+    let committee_addr = EMERGENCY_COMMITTEE_ADDR.load(deps.storage)?;
+    ensure!(
+        info.sender == committee_addr,
+        ContractError::Unauthorized {}
+    );
+
+    WITHDRAW_STATE.save(deps.storage, &WithdrawState::Emergency {})?;
+
+    let unstake_msgs = get_unstake_msgs(deps.as_ref(), &env)?;
+    let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    let withdraw_msg = generate_withdraw_msg(lper.to_string(), None)?;
+
+    Ok(Response::default()
+        .add_messages(unstake_msgs)
+        .add_message(withdraw_msg))
+}
+
+/// distributes the withdrawn funds. untrn fee refunds accumulated
+/// by the holder are routed back to the fee payer, if configured.
+fn try_distribute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // untrn withdrawn along with the position belongs to the parties
+    let distributed_untrn: Uint128 = info
+        .funds
+        .iter()
+        .filter(|c| c.denom == FEE_DENOM)
+        .map(|c| c.amount)
+        .sum();
+    let fee_refund_msg = get_fee_refund_msg(deps.branch(), &env, distributed_untrn)?;
+
+    let response = try_distribute_withdrawn_funds(deps.branch(), info)?;
+    if CONTRACT_STATE.load(deps.storage)? == ContractState::Complete {
+        record_completion(deps.storage, &env.block)?;
+    }
+
+    Ok(response.add_messages(fee_refund_msg))
+}
+
+/// returns the message refunding the untrn held beyond the
+/// reserve to the fee payer, if a fee refund sink is configured.
+/// the refunded amount is accounted for the settlement report.
+fn get_fee_refund_msg(
+    deps: DepsMut,
+    env: &Env,
+    distributed: Uint128,
+) -> StdResult<Option<CosmosMsg>> {
+    let Some(config) = FEE_REFUND_CONFIG.may_load(deps.storage)? else {
+        return Ok(None);
+    };
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address.to_string(), FEE_DENOM)?;
+
+    let refund_msg = config.get_refund_msg(balance.amount, distributed);
+    if let Some(CosmosMsg::Bank(BankMsg::Send { amount, .. })) = &refund_msg {
+        let refunded: Uint128 = amount.iter().map(|c| c.amount).sum();
+        let total = FEE_REFUNDS.may_load(deps.storage)?.unwrap_or_default();
+        FEE_REFUNDS.save(deps.storage, &total.checked_add(refunded)?)?;
+    }
+
+    Ok(refund_msg)
+}
+
+/// records the block time the covenant completed at, unless
+/// an earlier completion was already recorded
+fn record_completion(storage: &mut dyn Storage, block: &BlockInfo) -> StdResult<()> {
+    if !COMPLETED_AT.exists(storage) {
+        COMPLETED_AT.save(storage, &block.time)?;
+    }
+    Ok(())
+}
+
+fn try_distribute_withdrawn_funds(
+    mut deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Only pooler can call this
+    let pooler_addr = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    ensure!(info.sender == pooler_addr, ContractError::Unauthorized {});
 
     let withdraw_state = WITHDRAW_STATE
         .load(deps.storage)
@@ -238,11 +1129,35 @@ fn try_distribute(mut deps: DepsMut, info: MessageInfo) -> Result<Response, Cont
     let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
     let denom_splits = DENOM_SPLITS.load(deps.storage)?;
 
-    let (claim_party, counterparty, denom_splits, is_rq) = match withdraw_state {
-        WithdrawState::Processing { claimer_addr } => {
+    let (
+        claim_party,
+        counterparty,
+        denom_splits,
+        is_rq,
+        reinvest_to,
+        receiver,
+        claim_denoms,
+        vested_share,
+    ) = match withdraw_state {
+        WithdrawState::Processing {
+            claimer_addr,
+            reinvest_to,
+            receiver,
+            denoms,
+            vested_share,
+        } => {
             let (claim_party, counterparty) = covenant_config.authorize_sender(claimer_addr)?;
 
-            (claim_party, counterparty, denom_splits, false)
+            (
+                claim_party,
+                counterparty,
+                denom_splits,
+                false,
+                reinvest_to,
+                receiver,
+                denoms,
+                vested_share,
+            )
         }
         WithdrawState::ProcessingRagequit {
             claimer_addr,
@@ -250,9 +1165,42 @@ fn try_distribute(mut deps: DepsMut, info: MessageInfo) -> Result<Response, Cont
         } => {
             let (rq_party, counterparty) = covenant_config.authorize_sender(claimer_addr)?;
             let new_denom_split =
-                denom_splits.apply_penalty(terms.penalty, &rq_party, &counterparty)?;
+                denom_splits.apply_penalty(&terms.penalty, &rq_party, &counterparty)?;
 
-            (rq_party, counterparty, new_denom_split, true)
+            (
+                rq_party,
+                counterparty,
+                new_denom_split,
+                true,
+                None,
+                None,
+                None,
+                None,
+            )
+        }
+        WithdrawState::AutoDistribution {} => {
+            WITHDRAW_STATE.remove(deps.storage);
+            return match covenant_config.covenant_type {
+                CovenantType::Share => {
+                    try_auto_distribute_share_based(deps, info.funds, covenant_config, denom_splits)
+                }
+                CovenantType::Side => try_claim_side_based(
+                    deps,
+                    covenant_config.party_a.clone(),
+                    covenant_config.party_b.clone(),
+                    info.funds,
+                    covenant_config,
+                    denom_splits,
+                    None,
+                    None,
+                    None,
+                ),
+            };
+        }
+        WithdrawState::StalledProvisionRefund {} => {
+            WITHDRAW_STATE.remove(deps.storage);
+            PROVISIONING_STALL.remove(deps.storage);
+            return try_refund_stalled_provision(deps, info.funds, covenant_config);
         }
         WithdrawState::Emergency {} => {
             return try_claim_side_based(
@@ -262,132 +1210,881 @@ fn try_distribute(mut deps: DepsMut, info: MessageInfo) -> Result<Response, Cont
                 info.funds,
                 covenant_config,
                 denom_splits,
+                None,
+                None,
+                None,
             )
         }
     };
 
-    WITHDRAW_STATE.remove(deps.storage);
+    WITHDRAW_STATE.remove(deps.storage);
+
+    match covenant_config.covenant_type {
+        CovenantType::Share => {
+            if is_rq {
+                apply_rq_state_share(deps.branch(), claim_party.clone(), info.funds.clone())?;
+            }
+
+            try_claim_share_based(
+                deps,
+                claim_party,
+                counterparty,
+                info.funds,
+                covenant_config,
+                denom_splits,
+                reinvest_to,
+                receiver,
+                claim_denoms,
+                vested_share,
+            )
+        }
+        CovenantType::Side => {
+            if is_rq {
+                apply_rq_state_side(deps.branch(), claim_party.clone(), info.funds.clone())?;
+            }
+
+            try_claim_side_based(
+                deps,
+                claim_party,
+                counterparty,
+                info.funds,
+                covenant_config,
+                denom_splits,
+                reinvest_to,
+                receiver,
+                claim_denoms,
+            )
+        }
+    }
+}
+
+/// We don't do much on failed withdraw, as nothing changed so far.
+/// We only change state on distribute msg.
+fn try_withdraw_failed(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // Assert the caller is the pooler
+    let pooler_addr = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    ensure!(info.sender == pooler_addr, ContractError::Unauthorized {});
+
+    WITHDRAW_STATE.remove(deps.storage);
+
+    Ok(Response::default())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_claim_share_based(
+    mut deps: DepsMut,
+    mut claim_party: TwoPartyPolCovenantParty,
+    mut counterparty: TwoPartyPolCovenantParty,
+    funds: Vec<Coin>,
+    mut covenant_config: TwoPartyPolCovenantConfig,
+    denom_splits: DenomSplits,
+    reinvest_to: Option<String>,
+    receiver: Option<String>,
+    claim_denoms: Option<Vec<String>>,
+    vested_share: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let undistributed_denoms = denom_splits.get_undistributed_denoms(&funds);
+    let messages = denom_splits
+        .get_single_receiver_distribution_messages(funds, claim_party.router.to_string());
+    let messages = retain_unclaimed_funds(deps.storage, messages, &claim_party, claim_denoms)?;
+    record_party_distributions(deps.storage, &covenant_config, &messages)?;
+    let mut messages = redirect_claim_party_funds(
+        messages,
+        &claim_party,
+        reinvest_to.clone().or(receiver.clone()),
+    );
+    let (claim_id, receipt_submsgs) =
+        issue_claim_receipts(deps.branch(), &covenant_config, &messages)?;
+
+    if let Some(vested_share) = vested_share.filter(|share| share < &Decimal::one()) {
+        // vested claims withdraw a part of the claim party allocation,
+        // so both allocations are rescaled to the remaining position
+        let withdrawn_share = get_vested_withdraw_share(deps.storage, &claim_party, vested_share)?;
+        let remaining_share = Decimal::one() - withdrawn_share;
+        claim_party.allocation = (claim_party.allocation - withdrawn_share) / remaining_share;
+        counterparty.allocation /= remaining_share;
+        VESTING_CLAIMED_SHARES.save(
+            deps.storage,
+            claim_party.host_addr.to_string(),
+            &vested_share,
+        )?;
+    } else if !counterparty.allocation.is_zero() {
+        // if other party had not claimed yet, we assign it the full position
+        claim_party.allocation = Decimal::zero();
+        counterparty.allocation = Decimal::one();
+    } else {
+        claim_party.allocation = Decimal::zero();
+        // otherwise both parties claimed everything and we can complete
+        let clock_address = CLOCK_ADDRESS.load(deps.storage)?;
+        let dequeue_message =
+            ContractState::complete_and_dequeue(deps.branch(), clock_address.as_str())?;
+        messages.push(dequeue_message.into());
+    };
+
+    covenant_config.update_parties(claim_party, counterparty);
+
+    COVENANT_CONFIG.save(deps.storage, &covenant_config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "claim_share_based")
+        .add_attribute("claim_id", claim_id.to_string())
+        .add_attributes(get_undistributed_denoms_attributes(undistributed_denoms))
+        .add_attribute(
+            "reinvest_to",
+            reinvest_to.unwrap_or_else(|| "None".to_string()),
+        )
+        .add_attribute(
+            "claim_receiver",
+            receiver.unwrap_or_else(|| "None".to_string()),
+        )
+        .add_messages(messages)
+        .add_submessages(receipt_submsgs))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_claim_side_based(
+    mut deps: DepsMut,
+    mut claim_party: TwoPartyPolCovenantParty,
+    mut counterparty: TwoPartyPolCovenantParty,
+    funds: Vec<Coin>,
+    mut covenant_config: TwoPartyPolCovenantConfig,
+    denom_splits: DenomSplits,
+    reinvest_to: Option<String>,
+    receiver: Option<String>,
+    claim_denoms: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let undistributed_denoms = denom_splits.get_undistributed_denoms(&funds);
+    let messages: Vec<CosmosMsg> = denom_splits.get_shared_distribution_messages(funds);
+    let messages = retain_unclaimed_funds(deps.storage, messages, &claim_party, claim_denoms)?;
+    record_party_distributions(deps.storage, &covenant_config, &messages)?;
+    let messages = redirect_claim_party_funds(
+        messages,
+        &claim_party,
+        reinvest_to.clone().or(receiver.clone()),
+    );
+    let (claim_id, receipt_submsgs) =
+        issue_claim_receipts(deps.branch(), &covenant_config, &messages)?;
+
+    claim_party.allocation = Decimal::zero();
+    counterparty.allocation = Decimal::zero();
+    covenant_config.update_parties(claim_party, counterparty);
+
+    // update the states and dequeue from the clock
+    COVENANT_CONFIG.save(deps.storage, &covenant_config)?;
+    let clock_address = CLOCK_ADDRESS.load(deps.storage)?;
+    let dequeue_message = ContractState::complete_and_dequeue(deps, clock_address.as_str())?;
+
+    Ok(Response::default()
+        .add_attribute("method", "claim_side_based")
+        .add_attribute("claim_id", claim_id.to_string())
+        .add_attributes(get_undistributed_denoms_attributes(undistributed_denoms))
+        .add_attribute(
+            "reinvest_to",
+            reinvest_to.unwrap_or_else(|| "None".to_string()),
+        )
+        .add_attribute(
+            "claim_receiver",
+            receiver.unwrap_or_else(|| "None".to_string()),
+        )
+        .add_messages(messages)
+        .add_submessages(receipt_submsgs)
+        .add_message(dequeue_message))
+}
+
+/// routes the withdrawn position to both parties, according to their
+/// remaining allocations, and completes the covenant
+fn try_auto_distribute_share_based(
+    mut deps: DepsMut,
+    funds: Vec<Coin>,
+    mut covenant_config: TwoPartyPolCovenantConfig,
+    denom_splits: DenomSplits,
+) -> Result<Response, ContractError> {
+    let undistributed_denoms = denom_splits.get_undistributed_denoms(&funds);
+    let mut party_a_funds: Vec<Coin> = vec![];
+    let mut party_b_funds: Vec<Coin> = vec![];
+    for coin in funds {
+        let party_a_amount = coin.amount.mul_floor(covenant_config.party_a.allocation);
+        let party_b_amount = coin.amount.checked_sub(party_a_amount)?;
+        if !party_a_amount.is_zero() {
+            party_a_funds.push(Coin::new(party_a_amount.u128(), coin.denom.to_string()));
+        }
+        if !party_b_amount.is_zero() {
+            party_b_funds.push(Coin::new(party_b_amount.u128(), coin.denom));
+        }
+    }
+
+    let mut messages = denom_splits
+        .clone()
+        .get_single_receiver_distribution_messages(
+            party_a_funds,
+            covenant_config.party_a.router.to_string(),
+        );
+    messages.extend(denom_splits.get_single_receiver_distribution_messages(
+        party_b_funds,
+        covenant_config.party_b.router.to_string(),
+    ));
+    record_party_distributions(deps.storage, &covenant_config, &messages)?;
+    let (claim_id, receipt_submsgs) =
+        issue_claim_receipts(deps.branch(), &covenant_config, &messages)?;
+
+    covenant_config.party_a.allocation = Decimal::zero();
+    covenant_config.party_b.allocation = Decimal::zero();
+    COVENANT_CONFIG.save(deps.storage, &covenant_config)?;
+
+    let clock_address = CLOCK_ADDRESS.load(deps.storage)?;
+    let dequeue_message = ContractState::complete_and_dequeue(deps, clock_address.as_str())?;
+
+    Ok(Response::default()
+        .add_attribute("method", "auto_distribute_share_based")
+        .add_attribute("claim_id", claim_id.to_string())
+        .add_attributes(get_undistributed_denoms_attributes(undistributed_denoms))
+        .add_messages(messages)
+        .add_submessages(receipt_submsgs)
+        .add_message(dequeue_message))
+}
+
+/// reports the withdrawn denoms that could not be distributed for the lack
+/// of a split. they remain in the holder until a fallback split is set.
+fn get_undistributed_denoms_attributes(undistributed_denoms: Vec<String>) -> Vec<Attribute> {
+    if undistributed_denoms.is_empty() {
+        return vec![];
+    }
+    vec![Attribute::new(
+        "undistributed_denoms",
+        undistributed_denoms.join(","),
+    )]
+}
+
+/// refunds the assets withdrawn from a stalled liquid pooler to the
+/// routers of the parties that contributed them, and completes the
+/// covenant. assets not contributed by either party are left to the
+/// fallback split.
+fn try_refund_stalled_provision(
+    deps: DepsMut,
+    funds: Vec<Coin>,
+    covenant_config: TwoPartyPolCovenantConfig,
+) -> Result<Response, ContractError> {
+    let messages: Vec<CosmosMsg> = funds
+        .into_iter()
+        .filter_map(|coin| {
+            // adapted contributions reach the liquid pooler in the target denom
+            [&covenant_config.party_a, &covenant_config.party_b]
+                .into_iter()
+                .find(|party| {
+                    party.contribution.denom == coin.denom
+                        || party
+                            .contribution_adapter
+                            .as_ref()
+                            .is_some_and(|adapter| adapter.target_denom == coin.denom)
+                })
+                .map(|party| {
+                    BankMsg::Send {
+                        to_address: party.router.to_string(),
+                        amount: vec![coin],
+                    }
+                    .into()
+                })
+        })
+        .collect();
+    record_party_distributions(deps.storage, &covenant_config, &messages)?;
+
+    // the clock keeps ticking the completed covenant to refund any leftovers
+    CONTRACT_STATE.save(deps.storage, &ContractState::Complete)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "refund_stalled_provision")
+        .add_attribute("contract_state", "complete")
+        .add_messages(messages))
+}
+
+/// redirects the funds routed to the claim party router to the liquid
+/// pooler of its reinvest target or to its alternate receiver. the
+/// entitlement is still accounted to the claim party, but no receipt
+/// gets issued for it.
+fn redirect_claim_party_funds(
+    messages: Vec<CosmosMsg>,
+    claim_party: &TwoPartyPolCovenantParty,
+    redirect_to: Option<String>,
+) -> Vec<CosmosMsg> {
+    let Some(redirect_to) = redirect_to else {
+        return messages;
+    };
+    messages
+        .into_iter()
+        .map(|msg| match msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount })
+                if to_address == claim_party.router =>
+            {
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: redirect_to.to_string(),
+                    amount,
+                })
+            }
+            msg => msg,
+        })
+        .collect()
+}
+
+/// withholds the claim party funds outside of the claimed denoms,
+/// retaining them in the holder for later claims by the party
+fn retain_unclaimed_funds(
+    storage: &mut dyn Storage,
+    messages: Vec<CosmosMsg>,
+    claim_party: &TwoPartyPolCovenantParty,
+    claim_denoms: Option<Vec<String>>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let Some(claim_denoms) = claim_denoms else {
+        return Ok(messages);
+    };
+
+    let mut claimed_messages = Vec::with_capacity(messages.len());
+    for msg in messages {
+        match msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount })
+                if to_address == claim_party.router =>
+            {
+                let (claimed, retained): (Vec<Coin>, Vec<Coin>) = amount
+                    .into_iter()
+                    .partition(|coin| claim_denoms.contains(&coin.denom));
+                for coin in retained {
+                    RETAINED_CLAIM_FUNDS.update(
+                        storage,
+                        (claim_party.host_addr.to_string(), coin.denom),
+                        |total| -> StdResult<_> {
+                            Ok(total.unwrap_or_default().checked_add(coin.amount)?)
+                        },
+                    )?;
+                }
+                if !claimed.is_empty() {
+                    claimed_messages.push(
+                        BankMsg::Send {
+                            to_address,
+                            amount: claimed,
+                        }
+                        .into(),
+                    );
+                }
+            }
+            msg => claimed_messages.push(msg),
+        }
+    }
+    Ok(claimed_messages)
+}
+
+/// funds retained for the party, optionally restricted to the given denoms
+fn load_retained_claim_funds(
+    storage: &dyn Storage,
+    host_addr: &str,
+    denoms: Option<&[String]>,
+) -> StdResult<Vec<Coin>> {
+    RETAINED_CLAIM_FUNDS
+        .prefix(host_addr.to_string())
+        .range(storage, None, None, Order::Ascending)
+        .filter(|entry| match (entry, denoms) {
+            (Ok((denom, _)), Some(denoms)) => denoms.contains(denom),
+            _ => true,
+        })
+        .map(|entry| entry.map(|(denom, amount)| Coin { denom, amount }))
+        .collect()
+}
+
+/// amount of the denom retained for both parties
+fn query_retained_claim_total(storage: &dyn Storage, denom: &str) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    for entry in RETAINED_CLAIM_FUNDS.range(storage, None, None, Order::Ascending) {
+        let ((_, retained_denom), amount) = entry?;
+        if retained_denom == denom {
+            total = total.checked_add(amount)?;
+        }
+    }
+    Ok(total)
+}
+
+/// accumulates the amounts sent to the party routers into the
+/// lifetime distributions of the respective parties
+fn record_party_distributions(
+    storage: &mut dyn Storage,
+    covenant_config: &TwoPartyPolCovenantConfig,
+    messages: &[CosmosMsg],
+) -> StdResult<()> {
+    for msg in messages {
+        if let CosmosMsg::Bank(BankMsg::Send { to_address, amount }) = msg {
+            let party = if *to_address == covenant_config.party_a.router {
+                &covenant_config.party_a
+            } else if *to_address == covenant_config.party_b.router {
+                &covenant_config.party_b
+            } else {
+                continue;
+            };
+
+            for coin in amount {
+                PARTY_DISTRIBUTIONS.update(
+                    storage,
+                    (party.host_addr.to_string(), coin.denom.to_string()),
+                    |total| -> StdResult<_> {
+                        Ok(total.unwrap_or_default().checked_add(coin.amount)?)
+                    },
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// issues a receipt for every party router receiving claim proceeds.
+/// routers that are contracts get notified about the distribution and
+/// are expected to acknowledge it once they send the funds out. funds
+/// sent to plain accounts are considered delivered right away.
+/// returns the claim id along with the notification submessages.
+fn issue_claim_receipts(
+    deps: DepsMut,
+    covenant_config: &TwoPartyPolCovenantConfig,
+    messages: &[CosmosMsg],
+) -> StdResult<(u64, Vec<SubMsg>)> {
+    let mut receipts: Vec<DistributionReceipt> = vec![];
+    for msg in messages {
+        if let CosmosMsg::Bank(BankMsg::Send { to_address, amount }) = msg {
+            if *to_address != covenant_config.party_a.router
+                && *to_address != covenant_config.party_b.router
+            {
+                continue;
+            }
+            match receipts
+                .iter_mut()
+                .find(|receipt| receipt.router == *to_address)
+            {
+                Some(receipt) => receipt.coins.extend(amount.iter().cloned()),
+                None => receipts.push(DistributionReceipt {
+                    router: deps.api.addr_validate(to_address)?,
+                    coins: amount.clone(),
+                    status: DeliveryStatus::Pending,
+                }),
+            }
+        }
+    }
+
+    let claim_id = NEXT_CLAIM_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_CLAIM_ID.save(deps.storage, &(claim_id + 1))?;
+
+    let mut submsgs = vec![];
+    for (index, receipt) in receipts.iter_mut().enumerate() {
+        if deps
+            .querier
+            .query_wasm_contract_info(receipt.router.to_string())
+            .is_err()
+        {
+            receipt.status = DeliveryStatus::Delivered;
+            continue;
+        }
+        // registration errors must not block the claim, so we
+        // dispatch it with a reply id pointing back to this receipt
+        submsgs.push(SubMsg::reply_on_error(
+            generate_register_distribution_msg(
+                receipt.router.to_string(),
+                claim_id,
+                receipt.coins.clone(),
+            )?,
+            to_receipt_reply_id(claim_id, index),
+        ));
+    }
+
+    CLAIM_RECEIPTS.save(deps.storage, claim_id, &receipts)?;
+
+    Ok((claim_id, submsgs))
+}
+
+/// a claim pushes funds to at most two routers, so the receipt index
+/// gets encoded into the lowest bit of the reply id
+fn to_receipt_reply_id(claim_id: u64, index: usize) -> u64 {
+    claim_id * 2 + index as u64
+}
+
+fn from_receipt_reply_id(reply_id: u64) -> (u64, usize) {
+    (reply_id / 2, (reply_id % 2) as usize)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.result {
+        SubMsgResult::Err(e) => {
+            let (claim_id, index) = from_receipt_reply_id(msg.id);
+            let mut receipts = CLAIM_RECEIPTS
+                .may_load(deps.storage, claim_id)?
+                .ok_or(ContractError::UnknownClaim { id: claim_id })?;
+            if let Some(receipt) = receipts.get_mut(index) {
+                receipt.status = DeliveryStatus::Failed {
+                    reason: format!("failed to register distribution: {e}"),
+                };
+            }
+            CLAIM_RECEIPTS.save(deps.storage, claim_id, &receipts)?;
+
+            Ok(Response::default()
+                .add_attribute("method", "reply")
+                .add_attribute("claim_id", claim_id.to_string())
+                .add_attribute("status", "registration_failed"))
+        }
+        SubMsgResult::Ok(_) => Ok(Response::default().add_attribute("method", "reply")),
+    }
+}
+
+fn try_tick(mut deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let state = CONTRACT_STATE.load(deps.storage)?;
+    let clock_addr = CLOCK_ADDRESS.load(deps.storage)?;
+    verify_clock(&info.sender, &clock_addr)
+        .map_err(|e| ContractError::Std(StdError::generic_err(e.to_string())))?;
+
+    match state {
+        ContractState::Instantiated => try_deposit(deps, env, info),
+        ContractState::Active => {
+            // expiration is checked ahead of the other duties of the
+            // tick, and the depeg guard no longer runs once expired
+            let expiration_resp = check_expiration(deps.branch(), env.clone())?;
+            let staking_msgs = get_generator_staking_msgs(deps.branch(), &env, true)?;
+            let stall_resp = check_provisioning_stall(deps.branch(), &env)?;
+            let depeg_resp = match CONTRACT_STATE.load(deps.storage)? {
+                ContractState::Active => check_depeg_guard(deps.branch(), &env)?,
+                _ => Response::default(),
+            };
+            Ok(expiration_resp
+                .add_messages(staking_msgs)
+                .add_attributes(stall_resp.attributes)
+                .add_events(stall_resp.events)
+                .add_submessages(stall_resp.messages)
+                .add_attributes(depeg_resp.attributes)
+                .add_events(depeg_resp.events)
+                .add_submessages(depeg_resp.messages))
+        }
+        ContractState::Expired
+            if AUTO_DISTRIBUTE_ON_EXPIRY
+                .may_load(deps.storage)?
+                .unwrap_or_default() =>
+        {
+            try_auto_distribute(deps, env)
+        }
+        ContractState::Expired | ContractState::Ragequit => {
+            // the rest of the position remains staked until claimed
+            let staking_msgs = get_generator_staking_msgs(deps.branch(), &env, false)?;
+            Ok(try_donate_residual_lp(deps, env, state)?.add_messages(staking_msgs))
+        }
+        ContractState::Complete => try_refund(deps, env),
+    }
+}
+
+/// tracks the consecutive ticks observing the liquid pooler unable to
+/// provide liquidity. stalls lasting for more than the configured ticks
+/// are reported, and stalls outlasting the refund timeout get the assets
+/// held by the liquid pooler withdrawn to be refunded to the parties.
+fn check_provisioning_stall(deps: DepsMut, env: &Env) -> Result<Response, ContractError> {
+    let Some(config) = PROVISIONING_STALL_CONFIG.may_load(deps.storage)? else {
+        return Ok(Response::default());
+    };
+    if !is_provisioning_stalled(deps.as_ref(), env)? {
+        PROVISIONING_STALL.remove(deps.storage);
+        return Ok(Response::default());
+    }
+
+    let mut stall = PROVISIONING_STALL
+        .may_load(deps.storage)?
+        .unwrap_or(ProvisioningStall {
+            stalled_ticks: 0,
+            since: env.block.time,
+        });
+    stall.stalled_ticks += 1;
+    PROVISIONING_STALL.save(deps.storage, &stall)?;
+
+    if stall.stalled_ticks <= config.max_stalled_ticks {
+        return Ok(Response::default());
+    }
+
+    let resp = Response::default().add_event(
+        Event::new("provisioning_stalled")
+            .add_attribute("stalled_ticks", stall.stalled_ticks.to_string())
+            .add_attribute("stalled_since", stall.since.to_string()),
+    );
+
+    let timed_out = config
+        .refund_timeout
+        .is_some_and(|timeout| stall.since.plus_seconds(timeout) <= env.block.time);
+    if !timed_out || WITHDRAW_STATE.may_load(deps.storage)?.is_some() {
+        return Ok(resp);
+    }
+
+    WITHDRAW_STATE.save(deps.storage, &WithdrawState::StalledProvisionRefund {})?;
+    let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+
+    Ok(resp
+        .add_attribute("provisioning_stall", "refund")
+        .add_message(generate_withdraw_msg(lper.to_string(), None)?))
+}
+
+/// tracks the consecutive ticks observing the pool price deviating
+/// from the fair one. once the depeg outlasts the max duration,
+/// the configured action is taken. paused provisioning is resumed as
+/// soon as the pool price recovers. failing price queries are reported
+/// without affecting the observed depeg, so that an unreachable price
+/// source cannot block the tick.
+fn check_depeg_guard(deps: DepsMut, env: &Env) -> Result<Response, ContractError> {
+    let Some(config) = DEPEG_GUARD_CONFIG.may_load(deps.storage)? else {
+        return Ok(Response::default());
+    };
+    let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    let depegged = match config.is_depegged(deps.as_ref()) {
+        Ok(depegged) => depegged,
+        Err(e) => return Ok(Response::default().add_attribute("depeg_guard_error", e.to_string())),
+    };
+
+    if !depegged {
+        let Some(depeg) = DEPEG.may_load(deps.storage)? else {
+            return Ok(Response::default());
+        };
+        DEPEG.remove(deps.storage);
+        if depeg.acted && config.action == DepegAction::PauseProvisioning {
+            return Ok(Response::default()
+                .add_attribute("depeg_guard", "resume_provisioning")
+                .add_message(generate_pause_provisioning_msg(lper.to_string(), false)?));
+        }
+        return Ok(Response::default());
+    }
+
+    let mut depeg = DEPEG.may_load(deps.storage)?.unwrap_or(Depeg {
+        since: env.block.time,
+        acted: false,
+    });
+    let due = depeg.since.plus_seconds(config.max_duration) <= env.block.time;
+    // in-flight withdrawals are allowed to complete first
+    if depeg.acted || !due || WITHDRAW_STATE.may_load(deps.storage)?.is_some() {
+        DEPEG.save(deps.storage, &depeg)?;
+        return Ok(Response::default());
+    }
+    depeg.acted = true;
+    DEPEG.save(deps.storage, &depeg)?;
+
+    let resp = Response::default().add_event(
+        Event::new("depeg_guard")
+            .add_attribute("action", config.action.to_string())
+            .add_attribute("depegged_since", depeg.since.to_string()),
+    );
+    match config.action {
+        DepegAction::Ragequit => {
+            // the position is withdrawn and distributed to both
+            // parties by their allocations, without any penalty
+            WITHDRAW_STATE.save(deps.storage, &WithdrawState::AutoDistribution {})?;
+            let unstake_msgs = get_unstake_msgs(deps.as_ref(), env)?;
+            Ok(resp
+                .add_messages(unstake_msgs)
+                .add_message(generate_withdraw_msg(lper.to_string(), None)?))
+        }
+        DepegAction::PauseProvisioning => {
+            Ok(resp.add_message(generate_pause_provisioning_msg(lper.to_string(), true)?))
+        }
+    }
+}
+
+/// provisioning is stalled if the liquid pooler holds some of the pool
+/// assets, but neither holds nor staked any LP tokens. positions outside
+/// of astroport can not be inspected and are never considered stalled.
+fn is_provisioning_stalled(deps: Deps, env: &Env) -> StdResult<bool> {
+    let liquid_pooler = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    let Some(position) = query_pool_position(deps.querier, &liquid_pooler) else {
+        return Ok(false);
+    };
+    if !position.lp_balance.is_zero() {
+        return Ok(false);
+    }
+    if let Some(config) = GENERATOR_STAKING_CONFIG.may_load(deps.storage)? {
+        if !query_staked_lp_amount(deps, env, &config)?.is_zero() {
+            return Ok(false);
+        }
+    }
+
+    for denom in position.pool_denoms {
+        let balance = deps.querier.query_balance(&liquid_pooler, denom)?;
+        if !balance.amount.is_zero() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// distributes the generator rewards claimed by the previous ticks
+/// according to the explicit denom splits, optionally stakes the newly
+/// provided liquidity, and claims the rewards accrued since.
+fn get_generator_staking_msgs(
+    deps: DepsMut,
+    env: &Env,
+    stake: bool,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let Some(config) = GENERATOR_STAKING_CONFIG.may_load(deps.storage)? else {
+        return Ok(vec![]);
+    };
+
+    let rewards = config
+        .reward_denoms
+        .iter()
+        .map(|denom| {
+            deps.querier
+                .query_balance(env.contract.address.to_string(), denom)
+        })
+        .collect::<StdResult<Vec<Coin>>>()?;
+    // rewards without an explicit split are left to the fallback split
+    let denom_splits = DENOM_SPLITS.load(deps.storage)?;
+    let rewards = rewards
+        .into_iter()
+        .filter(|c| denom_splits.explicit_splits.contains_key(&c.denom))
+        .collect();
+    let mut msgs = denom_splits.get_shared_distribution_messages(rewards);
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    record_party_distributions(deps.storage, &covenant_config, &msgs)?;
 
-    match covenant_config.covenant_type {
-        CovenantType::Share => {
-            if is_rq {
-                apply_rq_state_share(deps.branch(), claim_party.clone(), info.funds.clone())?;
-            }
+    if stake {
+        let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+        msgs.push(
+            generate_stake_lp_msg(lper.to_string(), config.generator_address.to_string())?.into(),
+        );
+    }
 
-            try_claim_share_based(
-                deps,
-                claim_party,
-                counterparty,
-                info.funds,
-                covenant_config,
-                denom_splits,
-            )
-        }
-        CovenantType::Side => {
-            if is_rq {
-                apply_rq_state_side(deps.branch(), claim_party.clone(), info.funds.clone())?;
+    if !query_staked_lp_amount(deps.as_ref(), env, &config)?.is_zero() {
+        msgs.push(
+            WasmMsg::Execute {
+                contract_addr: config.generator_address.to_string(),
+                msg: to_json_binary(&GeneratorExecuteMsg::ClaimRewards {
+                    lp_tokens: vec![config.lp_token.to_string()],
+                })?,
+                funds: vec![],
             }
-
-            try_claim_side_based(
-                deps,
-                claim_party,
-                counterparty,
-                info.funds,
-                covenant_config,
-                denom_splits,
-            )
-        }
+            .into(),
+        );
     }
+
+    Ok(msgs)
 }
 
-/// We don't do much on failed withdraw, as nothing changed so far.
-/// We only change state on distribute msg.
-fn try_withdraw_failed(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    // Assert the caller is the pooler
-    let pooler_addr = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
-    ensure!(info.sender == pooler_addr, ContractError::Unauthorized {});
+/// amount of LP tokens staked in the generator on behalf of the holder
+fn query_staked_lp_amount(
+    deps: Deps,
+    env: &Env,
+    config: &GeneratorStakingConfig,
+) -> StdResult<Uint128> {
+    deps.querier.query_wasm_smart(
+        config.generator_address.to_string(),
+        &GeneratorQueryMsg::Deposit {
+            lp_token: config.lp_token.to_string(),
+            user: env.contract.address.to_string(),
+        },
+    )
+}
 
-    WITHDRAW_STATE.remove(deps.storage);
+/// unstakes the whole LP position from the generator and hands it
+/// back to the liquid pooler, so that the liquidity can be withdrawn
+fn get_unstake_msgs(deps: Deps, env: &Env) -> StdResult<Vec<WasmMsg>> {
+    let Some(config) = GENERATOR_STAKING_CONFIG.may_load(deps.storage)? else {
+        return Ok(vec![]);
+    };
+    let staked_amount = query_staked_lp_amount(deps, env, &config)?;
+    if staked_amount.is_zero() {
+        return Ok(vec![]);
+    }
 
-    Ok(Response::default())
+    let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    Ok(vec![
+        WasmMsg::Execute {
+            contract_addr: config.generator_address.to_string(),
+            msg: to_json_binary(&GeneratorExecuteMsg::Withdraw {
+                lp_token: config.lp_token.to_string(),
+                amount: staked_amount,
+            })?,
+            funds: vec![],
+        },
+        WasmMsg::Execute {
+            contract_addr: config.lp_token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: lper.to_string(),
+                amount: staked_amount,
+            })?,
+            funds: vec![],
+        },
+    ])
 }
 
-#[allow(clippy::too_many_arguments)]
-fn try_claim_share_based(
-    mut deps: DepsMut,
-    mut claim_party: TwoPartyPolCovenantParty,
-    mut counterparty: TwoPartyPolCovenantParty,
-    funds: Vec<Coin>,
-    mut covenant_config: TwoPartyPolCovenantConfig,
-    denom_splits: DenomSplits,
-) -> Result<Response, ContractError> {
-    let mut messages = denom_splits
-        .get_single_receiver_distribution_messages(funds, claim_party.router.to_string());
-
-    claim_party.allocation = Decimal::zero();
+/// withdraws the whole LP position so that it gets distributed to both
+/// parties. claims already in flight are allowed to complete first.
+fn try_auto_distribute(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    if WITHDRAW_STATE.may_load(deps.storage)?.is_some() {
+        return Ok(Response::default()
+            .add_attribute("method", "tick")
+            .add_attribute("contract_state", ContractState::Expired.to_string()));
+    }
 
-    // if other party had not claimed yet, we assign it the full position
-    if !counterparty.allocation.is_zero() {
-        counterparty.allocation = Decimal::one();
-    } else {
-        // otherwise both parties claimed everything and we can complete
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    // if both parties already claimed everything we complete right away
+    if covenant_config.party_a.allocation.is_zero() && covenant_config.party_b.allocation.is_zero()
+    {
         let clock_address = CLOCK_ADDRESS.load(deps.storage)?;
-        let dequeue_message =
-            ContractState::complete_and_dequeue(deps.branch(), clock_address.as_str())?;
-        messages.push(dequeue_message.into());
-    };
+        record_completion(deps.storage, &env.block)?;
+        let dequeue_message = ContractState::complete_and_dequeue(deps, clock_address.as_str())?;
 
-    covenant_config.update_parties(claim_party, counterparty);
+        return Ok(Response::default()
+            .add_attribute("method", "try_auto_distribute")
+            .add_attribute("contract_state", "complete")
+            .add_message(dequeue_message));
+    }
 
-    COVENANT_CONFIG.save(deps.storage, &covenant_config)?;
+    WITHDRAW_STATE.save(deps.storage, &WithdrawState::AutoDistribution {})?;
+
+    let unstake_msgs = get_unstake_msgs(deps.as_ref(), &env)?;
+    let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    let withdraw_msg = generate_withdraw_msg(lper.to_string(), None)?;
 
     Ok(Response::default()
-        .add_attribute("method", "claim_share_based")
-        .add_messages(messages))
+        .add_attribute("method", "try_auto_distribute")
+        .add_messages(unstake_msgs)
+        .add_message(withdraw_msg))
 }
 
-#[allow(clippy::too_many_arguments)]
-fn try_claim_side_based(
+/// once the claim window past the lockup expiration is over, any LP
+/// position left unclaimed is donated to the configured receiver and
+/// the covenant completes. until then, ticks are no-ops.
+fn try_donate_residual_lp(
     deps: DepsMut,
-    mut claim_party: TwoPartyPolCovenantParty,
-    mut counterparty: TwoPartyPolCovenantParty,
-    funds: Vec<Coin>,
-    mut covenant_config: TwoPartyPolCovenantConfig,
-    denom_splits: DenomSplits,
+    env: Env,
+    state: ContractState,
 ) -> Result<Response, ContractError> {
-    let messages: Vec<CosmosMsg> = denom_splits.get_shared_distribution_messages(funds);
+    let idle_response = Response::default()
+        .add_attribute("method", "tick")
+        .add_attribute("contract_state", state.to_string());
 
-    claim_party.allocation = Decimal::zero();
-    counterparty.allocation = Decimal::zero();
-    covenant_config.update_parties(claim_party, counterparty);
+    let donation_config = match RESIDUAL_LP_DONATION.may_load(deps.storage)? {
+        Some(config) => config,
+        None => return Ok(idle_response),
+    };
+    let lockup_config = LOCKUP_CONFIG.load(deps.storage)?;
+    let donation_expiration = donation_config.get_donation_expiration(&lockup_config)?;
+
+    // in-flight withdrawals are allowed to complete first
+    if !donation_expiration.is_expired(&env.block)
+        || WITHDRAW_STATE.may_load(deps.storage)?.is_some()
+    {
+        return Ok(idle_response);
+    }
+
+    let unstake_msgs = get_unstake_msgs(deps.as_ref(), &env)?;
+    let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    let donate_msg =
+        generate_donate_lp_msg(lper.to_string(), donation_config.receiver.to_string())?;
 
-    // update the states and dequeue from the clock
-    COVENANT_CONFIG.save(deps.storage, &covenant_config)?;
     let clock_address = CLOCK_ADDRESS.load(deps.storage)?;
+    record_completion(deps.storage, &env.block)?;
     let dequeue_message = ContractState::complete_and_dequeue(deps, clock_address.as_str())?;
 
     Ok(Response::default()
-        .add_attribute("method", "claim_side_based")
-        .add_messages(messages)
+        .add_attribute("method", "try_donate_residual_lp")
+        .add_attribute("residual_lp_receiver", donation_config.receiver)
+        .add_attribute("contract_state", "complete")
+        .add_messages(unstake_msgs)
+        .add_message(donate_msg)
         .add_message(dequeue_message))
 }
 
-fn try_tick(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
-    let state = CONTRACT_STATE.load(deps.storage)?;
-    let clock_addr = CLOCK_ADDRESS.load(deps.storage)?;
-    verify_clock(&info.sender, &clock_addr)
-        .map_err(|e| ContractError::Std(StdError::generic_err(e.to_string())))?;
-
-    match state {
-        ContractState::Instantiated => try_deposit(deps, env, info),
-        ContractState::Active => check_expiration(deps, env),
-        ContractState::Expired | ContractState::Ragequit => Ok(Response::default()
-            .add_attribute("method", "tick")
-            .add_attribute("contract_state", state.to_string())),
-        ContractState::Complete => try_refund(deps, env),
-    }
-}
-
 /// attempts to route any available covenant party contribution denoms to
 /// the parties that were responsible for contributing that denom.
 fn try_refund(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
@@ -396,11 +2093,11 @@ fn try_refund(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
     // assert the balances
     let party_a_bal = deps.querier.query_balance(
         env.contract.address.to_string(),
-        config.party_a.contribution.denom,
+        config.party_a.contribution.denom.to_string(),
     )?;
     let party_b_bal = deps.querier.query_balance(
         env.contract.address.to_string(),
-        config.party_b.contribution.denom,
+        config.party_b.contribution.denom.to_string(),
     )?;
 
     let refund_messages: Vec<CosmosMsg> =
@@ -409,12 +2106,12 @@ fn try_refund(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
             (true, true) => vec![],
             // refund party B
             (true, false) => vec![CosmosMsg::Bank(BankMsg::Send {
-                to_address: config.party_b.router,
+                to_address: config.party_b.router.to_string(),
                 amount: vec![party_b_bal],
             })],
             // refund party A
             (false, true) => vec![CosmosMsg::Bank(BankMsg::Send {
-                to_address: config.party_a.router,
+                to_address: config.party_a.router.to_string(),
                 amount: vec![party_a_bal],
             })],
             // refund both
@@ -424,12 +2121,14 @@ fn try_refund(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
                     amount: vec![party_a_bal],
                 }),
                 CosmosMsg::Bank(BankMsg::Send {
-                    to_address: config.party_b.router,
+                    to_address: config.party_b.router.to_string(),
                     amount: vec![party_b_bal],
                 }),
             ],
         };
 
+    record_party_distributions(deps.storage, &config, &refund_messages)?;
+
     Ok(Response::default()
         .add_attribute("contract_state", "complete")
         .add_attribute("method", "try_refund")
@@ -437,48 +2136,207 @@ fn try_refund(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
 }
 
 fn try_deposit(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response, ContractError> {
+    let config = COVENANT_CONFIG.load(deps.storage)?;
+
+    // deposits of allowed depositors are recorded as they are received,
+    // while legacy deposits are read off the holder balances
+    let balance_deposits = DEPOSITOR_ALLOWLIST
+        .may_load(deps.storage)?
+        .map_or(true, |allowlist| allowlist.legacy_balance_deposits);
+    let (party_a_record, party_b_record) = if balance_deposits {
+        // assert the balances
+        let party_a_bal = deps.querier.query_balance(
+            env.contract.address.to_string(),
+            &config.party_a.contribution.denom,
+        )?;
+        let party_b_bal = deps.querier.query_balance(
+            env.contract.address.to_string(),
+            &config.party_b.contribution.denom,
+        )?;
+
+        // parties may fund their contributions in multiple installments
+        (
+            record_deposit(deps.storage, &config.party_a, party_a_bal.amount)?,
+            record_deposit(deps.storage, &config.party_b, party_b_bal.amount)?,
+        )
+    } else {
+        (
+            load_deposit_record(deps.storage, &config.party_a)?,
+            load_deposit_record(deps.storage, &config.party_b)?,
+        )
+    };
+
+    // balance deposits are recorded even past the deadline so that
+    // the settlement report reflects what the parties contributed
     let deposit_deadline = DEPOSIT_DEADLINE.load(deps.storage)?;
     if deposit_deadline.is_expired(&env.block) {
         CONTRACT_STATE.save(deps.storage, &ContractState::Complete)?;
+        record_completion(deps.storage, &env.block)?;
         return Ok(Response::default()
             .add_attribute("method", "try_deposit")
             .add_attribute("deposit_deadline", "expired")
             .add_attribute("action", "complete"));
     }
 
-    let config = COVENANT_CONFIG.load(deps.storage)?;
-
-    // assert the balances
-    let party_a_bal = deps.querier.query_balance(
-        env.contract.address.to_string(),
-        config.party_a.contribution.denom,
-    )?;
-    let party_b_bal = deps.querier.query_balance(
-        env.contract.address.to_string(),
-        config.party_b.contribution.denom,
-    )?;
-
-    let party_a_fulfilled = config.party_a.contribution.amount <= party_a_bal.amount;
-    let party_b_fulfilled = config.party_b.contribution.amount <= party_b_bal.amount;
+    let party_a_fulfilled = config.party_a.contribution.amount <= party_a_record.amount;
+    let party_b_fulfilled = config.party_b.contribution.amount <= party_b_record.amount;
 
     if !party_a_fulfilled || !party_b_fulfilled {
-        // if deposit deadline is not yet due and both parties did not fulfill we error
-        return Err(ContractError::InsufficientDeposits {});
+        // deposit deadline is not yet due, we keep waiting for the deposits
+        return Ok(Response::default()
+            .add_attribute("method", "try_deposit")
+            .add_attribute("party_a_deposited", party_a_record.amount)
+            .add_attribute("party_b_deposited", party_b_record.amount)
+            .add_attribute("contract_state", "instantiated"));
     }
 
     // LiquidPooler is the next contract
     let liquid_pooler = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
-    let msg = BankMsg::Send {
-        to_address: liquid_pooler.to_string(),
-        amount: vec![party_a_bal, party_b_bal],
-    };
+
+    // contributions with an adapter are converted on their way to the
+    // liquid pooler, the rest is sent as is
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut bank_send_coins = vec![];
+    let mut attributes = vec![];
+    for (party, record) in [
+        (&config.party_a, &party_a_record),
+        (&config.party_b, &party_b_record),
+    ] {
+        let balance = Coin {
+            denom: party.contribution.denom.to_string(),
+            amount: record.amount,
+        };
+        match &party.contribution_adapter {
+            Some(adapter) => {
+                attributes.push(Attribute::new(
+                    "converted_contribution",
+                    balance.to_string(),
+                ));
+                messages.push(
+                    adapter
+                        .generate_convert_msg(vec![balance], liquid_pooler.to_string())?
+                        .into(),
+                );
+            }
+            None => bank_send_coins.push(balance),
+        }
+    }
+    if !bank_send_coins.is_empty() {
+        messages.push(
+            BankMsg::Send {
+                to_address: liquid_pooler.to_string(),
+                amount: bank_send_coins,
+            }
+            .into(),
+        );
+    }
 
     // advance the state to Active
     CONTRACT_STATE.save(deps.storage, &ContractState::Active)?;
 
     Ok(Response::default()
         .add_attribute("method", "deposit_to_next_contract")
-        .add_message(msg))
+        .add_attributes(attributes)
+        .add_messages(messages))
+}
+
+/// checks the deposits of the parties right away instead of on the next
+/// tick. permissionless, so that deposit contracts or keepers can notify
+/// the holder of contributions transferred to it with plain bank sends.
+fn try_notify_deposit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure!(
+        CONTRACT_STATE.load(deps.storage)? == ContractState::Instantiated,
+        ContractError::DepositsClosed {}
+    );
+    try_deposit(deps, env, info)
+}
+
+/// accounts the attached funds towards the contributions of the parties
+/// depositing in the respective denoms. if a depositor allowlist is
+/// configured, only the allowed depositors can deposit. once both
+/// contributions are fulfilled, the covenant gets activated right away.
+fn try_deposit_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure!(
+        CONTRACT_STATE.load(deps.storage)? == ContractState::Instantiated
+            && !DEPOSIT_DEADLINE.load(deps.storage)?.is_expired(&env.block),
+        ContractError::DepositsClosed {}
+    );
+    if let Some(allowlist) = DEPOSITOR_ALLOWLIST.may_load(deps.storage)? {
+        ensure!(
+            allowlist
+                .allowed_depositors
+                .contains(&info.sender.to_string()),
+            ContractError::UnauthorizedDepositor {}
+        );
+    }
+    ensure!(!info.funds.is_empty(), ContractError::InvalidDeposit {});
+
+    let config = COVENANT_CONFIG.load(deps.storage)?;
+    let mut attributes = vec![];
+    for coin in info.funds.iter() {
+        let party = [&config.party_a, &config.party_b]
+            .into_iter()
+            .find(|party| party.contribution.denom == coin.denom)
+            .ok_or(ContractError::InvalidDeposit {})?;
+
+        let mut record = load_deposit_record(deps.storage, party)?;
+        record.amount += coin.amount;
+        record.installments += 1;
+        DEPOSIT_RECORDS.save(deps.storage, party.host_addr.to_string(), &record)?;
+
+        attributes.push(Attribute::new(
+            format!("{}_deposited", party.host_addr),
+            record.amount,
+        ));
+    }
+
+    let depositor = info.sender.to_string();
+    let activation = try_deposit(deps, env, info)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_deposit_funds")
+        .add_attribute("depositor", depositor)
+        .add_attributes(attributes)
+        .add_attributes(
+            activation
+                .attributes
+                .into_iter()
+                .filter(|attr| attr.key != "method"),
+        )
+        .add_submessages(activation.messages))
+}
+
+fn load_deposit_record(
+    storage: &dyn Storage,
+    party: &TwoPartyPolCovenantParty,
+) -> StdResult<DepositRecord> {
+    Ok(DEPOSIT_RECORDS
+        .may_load(storage, party.host_addr.to_string())?
+        .unwrap_or_default())
+}
+
+/// records the balance of the party contribution denom as its cumulative
+/// deposit. every increase observed is counted as a new installment.
+fn record_deposit(
+    storage: &mut dyn Storage,
+    party: &TwoPartyPolCovenantParty,
+    balance: Uint128,
+) -> StdResult<DepositRecord> {
+    let mut record = load_deposit_record(storage, party)?;
+    if balance > record.amount {
+        record.amount = balance;
+        record.installments += 1;
+        DEPOSIT_RECORDS.save(storage, party.host_addr.to_string(), &record)?;
+    }
+    Ok(record)
 }
 
 fn check_expiration(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
@@ -525,12 +2383,17 @@ fn try_ragequit(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response,
 
     // authorize the message sender
     let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
-    let (rq_party, _) = covenant_config.authorize_sender(info.sender.to_string())?;
+    let (rq_party, _) = covenant_config.authorize_party_sender(&deps.querier, &info.sender)?;
 
     // If type is share we only withdraw the claim party allocation
     // if type is side, we withdraw 100% of funds
     let withdraw_percentage = match covenant_config.covenant_type {
-        CovenantType::Share => Some(rq_party.allocation - rq_terms.penalty),
+        CovenantType::Share => match &rq_terms.penalty {
+            RagequitPenalty::Uniform(penalty) => Some(rq_party.allocation - *penalty),
+            RagequitPenalty::PerDenom(_) => {
+                return Err(ContractError::RagequitPerDenomPenaltyError {})
+            }
+        },
         CovenantType::Side => None, // 100%
     };
 
@@ -543,10 +2406,13 @@ fn try_ragequit(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response,
         },
     )?;
 
+    let unstake_msgs = get_unstake_msgs(deps.as_ref(), &env)?;
     let lper = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
     let withdraw_msg = generate_withdraw_msg(lper.to_string(), withdraw_percentage)?;
 
-    Ok(Response::default().add_message(withdraw_msg))
+    Ok(Response::default()
+        .add_messages(unstake_msgs)
+        .add_message(withdraw_msg))
 }
 
 pub fn apply_rq_state_side(
@@ -595,13 +2461,288 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             &COVENANT_CONFIG.load(deps.storage)?.party_b,
         )?),
         QueryMsg::DepositDeadline {} => Ok(to_json_binary(&DEPOSIT_DEADLINE.load(deps.storage)?)?),
+        QueryMsg::TimeToExpiry {} => Ok(to_json_binary(
+            &Deadline::from(LOCKUP_CONFIG.load(deps.storage)?).time_to_expiry(&env.block),
+        )?),
         QueryMsg::Config {} => Ok(to_json_binary(&COVENANT_CONFIG.load(deps.storage)?)?),
         QueryMsg::DepositAddress {} => Ok(to_json_binary(&env.contract.address)?),
         QueryMsg::DenomSplits {} => Ok(to_json_binary(&DENOM_SPLITS.load(deps.storage)?)?),
         QueryMsg::EmergencyCommittee {} => Ok(to_json_binary(
             &EMERGENCY_COMMITTEE_ADDR.may_load(deps.storage)?,
         )?),
+        QueryMsg::DistributionsByParty { party } => {
+            let distributions = PARTY_DISTRIBUTIONS
+                .prefix(party)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|entry| entry.map(|(denom, amount)| Coin { denom, amount }))
+                .collect::<StdResult<Vec<Coin>>>()?;
+            Ok(to_json_binary(&distributions)?)
+        }
+        QueryMsg::RetainedClaimFunds { party } => Ok(to_json_binary(&load_retained_claim_funds(
+            deps.storage,
+            &party,
+            None,
+        )?)?),
+        QueryMsg::DepositRecord { party } => Ok(to_json_binary(
+            &DEPOSIT_RECORDS
+                .may_load(deps.storage, party)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::PendingPartyRebinding { party } => Ok(to_json_binary(
+            &PENDING_REBINDINGS.may_load(deps.storage, party)?,
+        )?),
+        QueryMsg::AccruedDistributions { denom } => Ok(to_json_binary(
+            &ACCRUED_DISTRIBUTIONS
+                .may_load(deps.storage, denom)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::PendingSplitReceiverFix { denom } => Ok(to_json_binary(
+            &PENDING_SPLIT_RECEIVER_FIXES.may_load(deps.storage, denom)?,
+        )?),
+        QueryMsg::PendingLockupExtension {} => Ok(to_json_binary(
+            &PENDING_LOCKUP_EXTENSION.may_load(deps.storage)?,
+        )?),
+        QueryMsg::PendingDepositDeadlineExtension {} => Ok(to_json_binary(
+            &PENDING_DEPOSIT_DEADLINE_EXTENSION.may_load(deps.storage)?,
+        )?),
+        QueryMsg::ResidualLpDonation {} => Ok(to_json_binary(
+            &RESIDUAL_LP_DONATION.may_load(deps.storage)?,
+        )?),
+        QueryMsg::ClaimReceipts { id } => Ok(to_json_binary(
+            &CLAIM_RECEIPTS
+                .may_load(deps.storage, id)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::AutoDistributeOnExpiry {} => Ok(to_json_binary(
+            &AUTO_DISTRIBUTE_ON_EXPIRY
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::FeeRefundConfig {} => {
+            Ok(to_json_binary(&FEE_REFUND_CONFIG.may_load(deps.storage)?)?)
+        }
+        QueryMsg::GeneratorStaking {} => Ok(to_json_binary(
+            &GENERATOR_STAKING_CONFIG.may_load(deps.storage)?,
+        )?),
+        QueryMsg::ProvisioningStallConfig {} => Ok(to_json_binary(
+            &PROVISIONING_STALL_CONFIG.may_load(deps.storage)?,
+        )?),
+        QueryMsg::DepositorAllowlist {} => Ok(to_json_binary(
+            &DEPOSITOR_ALLOWLIST.may_load(deps.storage)?,
+        )?),
+        QueryMsg::VestingConfig {} => Ok(to_json_binary(&VESTING_CONFIG.may_load(deps.storage)?)?),
+        QueryMsg::VestingClaimedShare { party } => Ok(to_json_binary(
+            &VESTING_CLAIMED_SHARES
+                .may_load(deps.storage, party)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::ProvisioningStall {} => {
+            Ok(to_json_binary(&PROVISIONING_STALL.may_load(deps.storage)?)?)
+        }
+        QueryMsg::DepegGuardConfig {} => {
+            Ok(to_json_binary(&DEPEG_GUARD_CONFIG.may_load(deps.storage)?)?)
+        }
+        QueryMsg::Depeg {} => Ok(to_json_binary(&DEPEG.may_load(deps.storage)?)?),
+        QueryMsg::PendingAmendment {} => {
+            Ok(to_json_binary(&PENDING_AMENDMENT.may_load(deps.storage)?)?)
+        }
+        QueryMsg::Amendment { index } => Ok(to_json_binary(
+            &AMENDMENT_HISTORY.load(deps.storage, index)?,
+        )?),
+        QueryMsg::AmendmentCount {} => Ok(to_json_binary(
+            &AMENDMENT_COUNT.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::Dashboard {} => Ok(to_json_binary(&query_dashboard(deps, env)?)?),
+        QueryMsg::SettlementReport {} => Ok(to_json_binary(&query_settlement_report(deps)?)?),
+        QueryMsg::ClaimPreview { party } => {
+            Ok(to_json_binary(&query_claim_preview(deps, env, party)?)?)
+        }
+    }
+}
+
+fn query_settlement_report(deps: Deps) -> StdResult<Option<SettlementReport>> {
+    let Some(completed_at) = COMPLETED_AT.may_load(deps.storage)? else {
+        return Ok(None);
+    };
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let settle_party = |party: &TwoPartyPolCovenantParty| -> StdResult<PartySettlement> {
+        let deposit = load_deposit_record(deps.storage, party)?;
+        let distributed = PARTY_DISTRIBUTIONS
+            .prefix(party.host_addr.to_string())
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|entry| entry.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<Vec<Coin>>>()?;
+        Ok(PartySettlement {
+            host_addr: party.host_addr.to_string(),
+            contributed: Coin {
+                denom: party.contribution.denom.to_string(),
+                amount: deposit.amount,
+            },
+            distributed,
+        })
+    };
+
+    let ragequit = match RAGEQUIT_CONFIG.load(deps.storage)? {
+        RagequitConfig::Enabled(RagequitTerms {
+            penalty,
+            state: Some(state),
+        }) => Some(RagequitSettlement {
+            ragequit_party: state.rq_party.host_addr,
+            penalty,
+        }),
+        _ => None,
+    };
+    let instantiated_at = INSTANTIATED_AT.may_load(deps.storage)?;
+
+    Ok(Some(SettlementReport {
+        party_a: settle_party(&covenant_config.party_a)?,
+        party_b: settle_party(&covenant_config.party_b)?,
+        ragequit,
+        fee_refunds: Coin {
+            denom: FEE_DENOM.to_string(),
+            amount: FEE_REFUNDS.may_load(deps.storage)?.unwrap_or_default(),
+        },
+        instantiated_at,
+        completed_at,
+        duration: instantiated_at
+            .map(|start| completed_at.seconds().saturating_sub(start.seconds())),
+    }))
+}
+
+fn query_dashboard(deps: Deps, env: Env) -> StdResult<DashboardResponse> {
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let denom_splits = DENOM_SPLITS.load(deps.storage)?;
+    let liquid_pooler_address = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    let pool_position = query_pool_position(deps.querier, &liquid_pooler_address);
+
+    let (party_a_claimable, party_b_claimable) = match &pool_position {
+        Some(position) => (
+            estimate_party_claim(
+                deps,
+                position,
+                &covenant_config.party_a,
+                &covenant_config.covenant_type,
+                &denom_splits,
+            )?,
+            estimate_party_claim(
+                deps,
+                position,
+                &covenant_config.party_b,
+                &covenant_config.covenant_type,
+                &denom_splits,
+            )?,
+        ),
+        None => (vec![], vec![]),
+    };
+
+    Ok(DashboardResponse {
+        contract_state: CONTRACT_STATE.load(deps.storage)?,
+        lockup: Deadline::from(LOCKUP_CONFIG.load(deps.storage)?).time_to_expiry(&env.block),
+        deposit_deadline: Deadline::from(DEPOSIT_DEADLINE.load(deps.storage)?)
+            .time_to_expiry(&env.block),
+        ragequit_config: RAGEQUIT_CONFIG.load(deps.storage)?,
+        covenant_config,
+        denom_splits,
+        liquid_pooler_address,
+        pool_position,
+        party_a_claimable,
+        party_b_claimable,
+    })
+}
+
+/// estimates the underlying assets the party would receive upon claiming.
+/// share based parties claim their allocation of the position, while
+/// side based parties receive their split of every denom.
+fn estimate_party_claim(
+    deps: Deps,
+    position: &PoolPosition,
+    party: &TwoPartyPolCovenantParty,
+    covenant_type: &CovenantType,
+    denom_splits: &DenomSplits,
+) -> StdResult<Vec<Coin>> {
+    match covenant_type {
+        CovenantType::Share => position.estimate_share(deps.querier, party.allocation),
+        CovenantType::Side => Ok(position
+            .estimate_share(deps.querier, Decimal::one())?
+            .into_iter()
+            .map(|coin| Coin {
+                amount: coin
+                    .amount
+                    .mul_floor(denom_splits.get_receiver_share(&coin.denom, &party.router)),
+                denom: coin.denom,
+            })
+            .collect()),
+    }
+}
+
+/// simulates the withdraw path of a claim: the (staked and unstaked) LP
+/// position is withdrawn according to the party allocation, and the
+/// underlying assets are routed through the denom splits. only the coins
+/// routed to the party router are returned.
+fn query_claim_preview(deps: Deps, env: Env, party: String) -> StdResult<Vec<Coin>> {
+    let covenant_config = COVENANT_CONFIG.load(deps.storage)?;
+    let claim_party = if covenant_config.party_a.host_addr == party {
+        covenant_config.party_a.clone()
+    } else if covenant_config.party_b.host_addr == party {
+        covenant_config.party_b.clone()
+    } else {
+        return Err(StdError::generic_err(format!(
+            "{party} is not a covenant party"
+        )));
+    };
+
+    if WITHDRAW_STATE.may_load(deps.storage)?.is_some() {
+        return Ok(vec![]);
+    }
+    let retained_funds = load_retained_claim_funds(deps.storage, &claim_party.host_addr, None)?;
+    if claim_party.allocation.is_zero() && !retained_funds.is_empty() {
+        return Ok(retained_funds);
+    }
+
+    let contract_state = CONTRACT_STATE.load(deps.storage)?;
+    let claim_share = match query_vested_share(deps.storage, &env.block, &contract_state)? {
+        Some(vested_share) => get_vested_withdraw_share(deps.storage, &claim_party, vested_share)?,
+        None if contract_state.validate_claim_state().is_ok() => claim_party.allocation,
+        None => Decimal::zero(),
+    };
+    if claim_share.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let liquid_pooler_address = LIQUID_POOLER_ADDRESS.load(deps.storage)?;
+    let Some(mut position) = query_pool_position(deps.querier, &liquid_pooler_address) else {
+        return Ok(vec![]);
+    };
+    if let Some(config) = GENERATOR_STAKING_CONFIG.may_load(deps.storage)? {
+        position.lp_balance += query_staked_lp_amount(deps, &env, &config)?;
+    }
+
+    let denom_splits = DENOM_SPLITS.load(deps.storage)?;
+    let messages = match covenant_config.covenant_type {
+        CovenantType::Share => denom_splits.get_single_receiver_distribution_messages(
+            position.estimate_share(deps.querier, claim_share)?,
+            claim_party.router.to_string(),
+        ),
+        CovenantType::Side => denom_splits.get_shared_distribution_messages(
+            position.estimate_share(deps.querier, Decimal::one())?,
+        ),
+    };
+
+    let mut entitlements: BTreeMap<String, Uint128> = BTreeMap::new();
+    for msg in messages {
+        if let CosmosMsg::Bank(BankMsg::Send { to_address, amount }) = msg {
+            if to_address == claim_party.router {
+                for coin in amount {
+                    *entitlements.entry(coin.denom).or_default() += coin.amount;
+                }
+            }
+        }
     }
+
+    Ok(entitlements
+        .into_iter()
+        .map(|(denom, amount)| Coin { denom, amount })
+        .collect())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -673,6 +2814,8 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response>
                     current_splits.explicit_splits = splits;
                     Ok(current_splits)
                 })?;
+                // fixes scheduled against the previous splits no longer apply
+                PENDING_SPLIT_RECEIVER_FIXES.clear(deps.storage);
             }
 
             if let Some(split) = fallback_split {
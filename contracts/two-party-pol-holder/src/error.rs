@@ -15,6 +15,9 @@ pub enum ContractError {
     #[error("Ragequit penalty exceeds party allocation")]
     RagequitPenaltyExceedsPartyAllocationError {},
 
+    #[error("Per denom ragequit penalties are only supported by side based covenants")]
+    RagequitPerDenomPenaltyError {},
+
     #[error("unauthorized")]
     Unauthorized {},
 
@@ -27,9 +30,6 @@ pub enum ContractError {
     #[error("covenant is active but expired; tick to proceed")]
     Expired {},
 
-    #[error("both parties have not deposited")]
-    InsufficientDeposits {},
-
     #[error("failed to multiply amount by share")]
     FractionMulError {},
 
@@ -77,4 +77,91 @@ pub enum ContractError {
 
     #[error("Party contribution cannot be zero")]
     PartyContributionConfigError {},
+
+    #[error("unknown claim id: {id}")]
+    UnknownClaim { id: u64 },
+
+    #[error("distribution receipt is already settled")]
+    DistributionAlreadySettled {},
+
+    #[error("party rebinding must replace at least one address")]
+    EmptyPartyRebinding {},
+
+    #[error("party rebinding conflicts with the counterparty")]
+    PartyRebindingConflict {},
+
+    #[error("no pending rebinding for party")]
+    NoPendingPartyRebinding {},
+
+    #[error("residual lp donation claim window must be of the same unit as the lockup config")]
+    ResidualLpDonationWindowError {},
+
+    #[error(
+        "claim receiver can not belong to the counterparty or be combined with a reinvest target"
+    )]
+    InvalidClaimReceiver {},
+
+    #[error("selective claims must specify at least one denom")]
+    InvalidClaimDenoms {},
+
+    #[error("reinvest target must be a single party holder withdrawn by the claiming party")]
+    UnauthorizedReinvestTarget {},
+
+    #[error("lockup can only be extended before it expires")]
+    LockupExtensionUnavailable {},
+
+    #[error("lockup extension must expire after the current lockup")]
+    LockupExtensionValidationError {},
+
+    #[error("no pending lockup extension")]
+    NoPendingLockupExtension {},
+
+    #[error("lockup extension proposal expired")]
+    LockupExtensionProposalExpired {},
+
+    #[error("deposits are only accepted before the deposit deadline")]
+    DepositsClosed {},
+
+    #[error("deposit deadline can only be extended before it expires")]
+    DepositDeadlineExtensionUnavailable {},
+
+    #[error(
+        "deposit deadline extension must expire after the current deadline and before the lockup"
+    )]
+    DepositDeadlineExtensionValidationError {},
+
+    #[error("depositor is not allowed to deposit")]
+    UnauthorizedDepositor {},
+
+    #[error("deposits must attach the contribution denoms of the parties")]
+    InvalidDeposit {},
+
+    #[error("vesting is only supported by share based covenants")]
+    VestingUnsupported {},
+
+    #[error("vesting must end after it starts, with both expirations of the same unit")]
+    VestingConfigError {},
+
+    #[error("no allocation vested since the previous claim")]
+    NothingVested {},
+
+    #[error("terms can only be amended before the lockup expires")]
+    AmendmentUnavailable {},
+
+    #[error("amendment must change at least one term")]
+    EmptyAmendment {},
+
+    #[error("amended lockup is already past")]
+    AmendedLockupExpired {},
+
+    #[error("no pending amendment")]
+    NoPendingAmendment {},
+
+    #[error("amendment hash does not match the pending amendment")]
+    AmendmentHashMismatch {},
+
+    #[error(
+        "depeg guard expects the pool price of a pool twap and a max deviation in the range of (0.00, 1.00)"
+    )]
+    DepegGuardConfigError {},
 }
@@ -2,20 +2,34 @@ use std::{collections::BTreeMap, fmt};
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    ensure, to_json_binary, Addr, Api, Attribute, Binary, Coin, CosmosMsg, Decimal, DepsMut,
-    StdError, StdResult, WasmMsg,
+    ensure, to_json_binary, to_json_vec, Addr, Api, Attribute, BankMsg, Binary, BlockInfo, Coin,
+    CosmosMsg, CustomQuery, Decimal, Deps, DepsMut, HexBinary, QuerierWrapper, StdError, StdResult,
+    Timestamp, Uint128, WasmMsg,
 };
 use covenant_macros::{
     clocked, covenant_clock_address, covenant_deposit_address, covenant_holder_distribute,
-    covenant_holder_emergency_withdraw, covenant_next_contract,
+    covenant_holder_distribution_receipt, covenant_holder_emergency_withdraw,
+    covenant_next_contract,
 };
+use covenant_oracle::{query_price, PriceGuard, PriceQuery};
 use covenant_utils::{
-    clock::dequeue_msg, instantiate2_helper::Instantiate2HelperConfig, split::SplitConfig,
+    clock::dequeue_msg, contribution_adapter::ContributionAdapter, dao::is_dao_proposal_module,
+    dashboard::PoolPosition, deadline::TimeToExpiry, distribution_receipt::DistributionReceipt,
+    instantiate2_helper::Instantiate2HelperConfig, split::SplitConfig,
+    split_receiver_fix::SplitReceiverFix,
 };
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
+use sha2::{Digest, Sha256};
 
 use crate::{error::ContractError, state::CONTRACT_STATE};
 
+/// default number of seconds during which a proposed lockup
+/// extension can be approved by the counterparty
+pub const DEFAULT_LOCKUP_EXTENSION_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+/// denom in which the neutron fees are paid and refunded
+pub const FEE_DENOM: &str = "untrn";
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// address of authorized clock
@@ -37,6 +51,46 @@ pub struct InstantiateMsg {
     pub fallback_split: Option<SplitConfig>,
     /// address of the emergency committee
     pub emergency_committee_addr: Option<String>,
+    /// donation of the LP position left unclaimed long after the lockup
+    #[serde(default)]
+    pub residual_lp_donation: Option<ResidualLpDonationConfig>,
+    /// seconds during which a proposed lockup extension can be approved
+    /// by the counterparty. defaults to `DEFAULT_LOCKUP_EXTENSION_WINDOW`.
+    #[serde(default)]
+    pub lockup_extension_window: Option<u64>,
+    /// if enabled, the first tick after the lockup expiration withdraws
+    /// the whole LP position and routes the shares of both parties to
+    /// their routers, without waiting for the parties to claim
+    #[serde(default)]
+    pub auto_distribute_on_expiry: bool,
+    /// routes the untrn fee refunds received by the holder back to
+    /// the fee payer instead of distributing them to the parties
+    #[serde(default)]
+    pub fee_refund_config: Option<FeeRefundConfig>,
+    /// stakes the LP position into the astroport generator while
+    /// it is locked up, distributing the rewards to the parties
+    #[serde(default)]
+    pub generator_staking: Option<GeneratorStakingConfig>,
+    /// detects liquid poolers unable to provide liquidity while the
+    /// covenant is active, optionally refunding the parties
+    #[serde(default)]
+    pub provisioning_stall: Option<ProvisioningStallConfig>,
+    /// restricts the deposits to the funds attached to `Deposit {}`
+    /// messages of the allowed depositors
+    #[serde(default)]
+    pub depositor_allowlist: Option<DepositorAllowlist>,
+    /// unlocks the party allocations linearly while the covenant is
+    /// active, instead of a single cliff at the lockup expiration
+    #[serde(default)]
+    pub vesting_config: Option<VestingConfig>,
+    /// protects stable pair covenants against a depeg of either
+    /// party denom while the covenant is active
+    #[serde(default)]
+    pub depeg_guard: Option<DepegGuardConfig>,
+    /// if enabled, the contribution and split denoms must have
+    /// a supply on this chain
+    #[serde(default)]
+    pub validate_denoms: bool,
 }
 
 impl InstantiateMsg {
@@ -74,15 +128,357 @@ impl InstantiateMsg {
             Attribute::new("next_contract", self.next_contract.to_string()),
             Attribute::new("lockup_config", self.lockup_config.to_string()),
             Attribute::new("deposit_deadline", self.deposit_deadline.to_string()),
+            Attribute::new(
+                "auto_distribute_on_expiry",
+                self.auto_distribute_on_expiry.to_string(),
+            ),
             fallback_attr,
         ];
         attrs.extend(self.ragequit_config.get_response_attributes());
+        if let Some(config) = &self.residual_lp_donation {
+            attrs.extend(config.get_response_attributes());
+        }
+        if let Some(config) = &self.fee_refund_config {
+            attrs.extend(config.get_response_attributes());
+        }
+        if let Some(config) = &self.generator_staking {
+            attrs.extend(config.get_response_attributes());
+        }
+        if let Some(config) = &self.provisioning_stall {
+            attrs.extend(config.get_response_attributes());
+        }
+        if let Some(allowlist) = &self.depositor_allowlist {
+            attrs.extend(allowlist.get_response_attributes());
+        }
+        if let Some(config) = &self.vesting_config {
+            attrs.extend(config.get_response_attributes());
+        }
+        if let Some(config) = &self.depeg_guard {
+            attrs.extend(config.get_response_attributes());
+        }
         attrs.extend(splits_attr);
         attrs.extend(self.covenant_config.get_response_attributes());
         attrs
     }
 }
 
+/// configuration of the residual LP position donation. if the LP
+/// position is not fully claimed by the end of the claim window (counted
+/// from the lockup expiration), it is sent to the receiver so that no LP
+/// tokens remain orphaned with the covenant.
+#[cw_serde]
+pub struct ResidualLpDonationConfig {
+    /// address receiving the residual LP tokens, e.g. a protocol-owned address
+    pub receiver: String,
+    /// duration after the lockup expiration during which
+    /// the parties are expected to claim their positions
+    pub claim_window: Duration,
+}
+
+impl ResidualLpDonationConfig {
+    pub fn validate(&self, api: &dyn Api, lockup_config: &Expiration) -> Result<(), ContractError> {
+        api.addr_validate(&self.receiver)?;
+        self.get_donation_expiration(lockup_config)?;
+        Ok(())
+    }
+
+    /// returns the expiration past which the residual LP position
+    /// gets donated. claim window must match the lockup config unit.
+    pub fn get_donation_expiration(
+        &self,
+        lockup_config: &Expiration,
+    ) -> Result<Expiration, ContractError> {
+        (*lockup_config + self.claim_window)
+            .map_err(|_| ContractError::ResidualLpDonationWindowError {})
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("residual_lp_receiver", self.receiver.to_string()),
+            Attribute::new(
+                "residual_lp_claim_window",
+                format!("{:?}", self.claim_window),
+            ),
+        ]
+    }
+}
+
+/// linear unlock schedule of the party allocations of share based
+/// covenants. while the covenant is active, each party can claim the
+/// part of its allocation vested between `start` and `end`. once the
+/// lockup expires, the remaining allocations are claimable in full.
+#[cw_serde]
+pub struct VestingConfig {
+    pub start: Expiration,
+    pub end: Expiration,
+}
+
+impl VestingConfig {
+    pub fn validate(&self, covenant_type: &CovenantType) -> Result<(), ContractError> {
+        ensure!(
+            matches!(covenant_type, CovenantType::Share),
+            ContractError::VestingUnsupported {}
+        );
+        let is_valid = match (&self.start, &self.end) {
+            (Expiration::AtHeight(start), Expiration::AtHeight(end)) => start < end,
+            (Expiration::AtTime(start), Expiration::AtTime(end)) => start < end,
+            _ => false,
+        };
+        ensure!(is_valid, ContractError::VestingConfigError {});
+        Ok(())
+    }
+
+    /// share of the party allocations vested by the given block
+    pub fn get_vested_share(&self, block: &BlockInfo) -> Decimal {
+        let (elapsed, duration) = match (&self.start, &self.end) {
+            (Expiration::AtHeight(start), Expiration::AtHeight(end)) => {
+                (block.height.saturating_sub(*start), end - start)
+            }
+            (Expiration::AtTime(start), Expiration::AtTime(end)) => (
+                block.time.nanos().saturating_sub(start.nanos()),
+                end.nanos() - start.nanos(),
+            ),
+            _ => return Decimal::zero(),
+        };
+        Decimal::from_ratio(elapsed.min(duration), duration)
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("vesting_start", self.start.to_string()),
+            Attribute::new("vesting_end", self.end.to_string()),
+        ]
+    }
+}
+
+/// configuration of the fee refund sink. untrn held by the holder
+/// beyond the reserve is considered a refund of the fees funded by
+/// the fee payer (e.g. ack fee refunds), rather than a part of the
+/// position to be distributed to the parties.
+#[cw_serde]
+pub struct FeeRefundConfig {
+    /// address that funded the fees, e.g. the covenant instantiator
+    pub fee_payer: String,
+    /// untrn amount kept by the holder to cover future fees
+    pub reserve: Uint128,
+}
+
+impl FeeRefundConfig {
+    /// returns the message refunding the untrn balance in excess of the
+    /// reserve, leaving out the amount distributed to the parties
+    pub fn get_refund_msg(&self, balance: Uint128, distributed: Uint128) -> Option<CosmosMsg> {
+        let refund = balance
+            .saturating_sub(distributed)
+            .saturating_sub(self.reserve);
+        if refund.is_zero() {
+            return None;
+        }
+        Some(
+            BankMsg::Send {
+                to_address: self.fee_payer.to_string(),
+                amount: vec![Coin::new(refund.u128(), FEE_DENOM)],
+            }
+            .into(),
+        )
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("fee_payer", self.fee_payer.to_string()),
+            Attribute::new("fee_reserve", self.reserve.to_string()),
+        ]
+    }
+}
+
+/// configuration of the astroport generator staking. while active, the
+/// LP tokens are staked on behalf of the holder and the rewards claimed
+/// on every tick. rewards in explicitly split denoms are distributed on
+/// the following tick, while the rest is left to the fallback split.
+#[cw_serde]
+pub struct GeneratorStakingConfig {
+    /// address of the astroport generator
+    pub generator_address: String,
+    /// LP token of the pool the liquid pooler provides liquidity to
+    pub lp_token: String,
+    /// denoms of the ASTRO and proxy rewards
+    pub reward_denoms: Vec<String>,
+}
+
+impl GeneratorStakingConfig {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        api.addr_validate(&self.generator_address)?;
+        api.addr_validate(&self.lp_token)?;
+        Ok(())
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("generator_address", self.generator_address.to_string()),
+            Attribute::new("generator_lp_token", self.lp_token.to_string()),
+            Attribute::new("generator_reward_denoms", self.reward_denoms.join(",")),
+        ]
+    }
+}
+
+/// restricts the deposits counted towards the party contributions to
+/// the funds attached to `Deposit {}` messages sent by the allowed
+/// depositors, so that third party transfers can not poison the
+/// deposit accounting.
+#[cw_serde]
+pub struct DepositorAllowlist {
+    pub allowed_depositors: Vec<String>,
+    /// if enabled, the contribution denom balances of the holder still
+    /// count as deposits, regardless of how they were funded
+    pub legacy_balance_deposits: bool,
+}
+
+impl DepositorAllowlist {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        for depositor in &self.allowed_depositors {
+            api.addr_validate(depositor)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("allowed_depositors", self.allowed_depositors.join(",")),
+            Attribute::new(
+                "legacy_balance_deposits",
+                self.legacy_balance_deposits.to_string(),
+            ),
+        ]
+    }
+}
+
+/// configuration of the provisioning stall detection. provisioning is
+/// stalled while the liquid pooler holds pool assets without holding or
+/// staking any LP tokens, e.g. because the pool price drifted out of the
+/// range accepted by the liquid pooler.
+#[cw_serde]
+pub struct ProvisioningStallConfig {
+    /// consecutive stalled ticks after which every tick
+    /// emits a `provisioning_stalled` event
+    pub max_stalled_ticks: u64,
+    /// seconds since the stall was first observed after which the assets
+    /// held by the liquid pooler are withdrawn and refunded to the parties.
+    /// if not set, stalls are only reported.
+    pub refund_timeout: Option<u64>,
+}
+
+impl ProvisioningStallConfig {
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("max_stalled_ticks", self.max_stalled_ticks.to_string()),
+            Attribute::new(
+                "stall_refund_timeout",
+                self.refund_timeout
+                    .map_or_else(|| "none".to_string(), |timeout| timeout.to_string()),
+            ),
+        ]
+    }
+}
+
+/// provisioning stall observed by the consecutive ticks
+#[cw_serde]
+pub struct ProvisioningStall {
+    /// number of consecutive ticks that observed the stall
+    pub stalled_ticks: u64,
+    /// time of the first tick that observed the stall
+    pub since: Timestamp,
+}
+
+/// configuration of the depeg guard of stable pair covenants. the pool
+/// is depegged while the pool price deviates from the fair price of the
+/// pair (e.g. the redemption rate of the liquid staked denom) by more
+/// than the max deviation of the fair price guard.
+#[cw_serde]
+pub struct DepegGuardConfig {
+    /// price observed by the pool, e.g. its twap. the source must be
+    /// a pool twap, as the raw pool reserves misprice stableswap pools.
+    pub pool_price: PriceQuery,
+    /// fair price of the pair, quoted in the same order as the pool price.
+    /// max deviation is relative to the fair price, in the range of (0.00, 1.00)
+    pub fair_price: PriceGuard,
+    /// seconds the depeg must last for before the action is taken
+    pub max_duration: u64,
+    pub action: DepegAction,
+}
+
+impl DepegGuardConfig {
+    pub fn validate(&self, api: &dyn Api) -> Result<(), ContractError> {
+        self.pool_price.validate(api)?;
+        self.fair_price.validate(api)?;
+        ensure!(
+            self.pool_price.source.is_pool_twap() && self.fair_price.max_deviation < Decimal::one(),
+            ContractError::DepegGuardConfigError {}
+        );
+        Ok(())
+    }
+
+    /// whether the pool price deviates from the fair price
+    /// by more than the max deviation
+    pub fn is_depegged<C: CustomQuery>(&self, deps: Deps<C>) -> StdResult<bool> {
+        let pool_price = self.pool_price.query(deps)?;
+        let fair_price = query_price(
+            deps,
+            &self.fair_price.source,
+            &self.fair_price.base,
+            &self.fair_price.quote,
+        )?;
+        Ok(pool_price.abs_diff(fair_price)
+            > fair_price.checked_mul(self.fair_price.max_deviation)?)
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new(
+                "depeg_pool_price",
+                format!("{}/{}", self.pool_price.base, self.pool_price.quote),
+            ),
+            Attribute::new(
+                "depeg_fair_price",
+                format!("{}/{}", self.fair_price.base, self.fair_price.quote),
+            ),
+            Attribute::new(
+                "depeg_max_deviation",
+                self.fair_price.max_deviation.to_string(),
+            ),
+            Attribute::new("depeg_max_duration", self.max_duration.to_string()),
+            Attribute::new("depeg_action", self.action.to_string()),
+        ]
+    }
+}
+
+/// action taken once a depeg outlasts the max duration
+#[cw_serde]
+pub enum DepegAction {
+    /// the whole LP position is withdrawn and distributed to both
+    /// parties according to their allocations, without any penalty.
+    /// the covenant then completes.
+    Ragequit,
+    /// the liquidity provision is paused until the pool ratio recovers
+    PauseProvisioning,
+}
+
+impl fmt::Display for DepegAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DepegAction::Ragequit => write!(f, "ragequit"),
+            DepegAction::PauseProvisioning => write!(f, "pause_provisioning"),
+        }
+    }
+}
+
+/// depeg observed by the consecutive ticks
+#[cw_serde]
+pub struct Depeg {
+    /// time of the first tick that observed the depeg
+    pub since: Timestamp,
+    /// whether the configured action was taken
+    pub acted: bool,
+}
+
 #[cw_serde]
 pub enum CovenantType {
     Share,
@@ -108,7 +504,40 @@ pub struct DenomSplits {
 }
 
 impl DenomSplits {
-    pub fn get_fallback_distribution_messages(self, available_coins: Vec<Coin>) -> Vec<CosmosMsg> {
+    /// share of the given denom that is split to the receiver
+    pub fn get_receiver_share(&self, denom: &str, receiver: &str) -> Decimal {
+        self.get_split(denom)
+            .and_then(|split| split.receivers.get(receiver).copied())
+            .unwrap_or_default()
+    }
+
+    /// split applying to the given denom, if any
+    pub fn get_split(&self, denom: &str) -> Option<&SplitConfig> {
+        self.explicit_splits
+            .get(denom)
+            .or(self.fallback_split.as_ref())
+    }
+
+    /// denoms of the given coins that can not be distributed, as they
+    /// are neither split explicitly nor covered by a fallback split
+    pub fn get_undistributed_denoms(&self, coins: &[Coin]) -> Vec<String> {
+        coins
+            .iter()
+            .filter(|c| self.get_split(&c.denom).is_none())
+            .map(|c| c.denom.to_string())
+            .collect()
+    }
+
+    /// distributes the available coins according to the fallback split.
+    /// the amounts `accrued` by the receivers (keyed by denom) are not split
+    /// again. instead, they are topped up with the new entitlements and only
+    /// distributed once they reach the receiver min amount, or on `flush`.
+    pub fn get_fallback_distribution_messages(
+        self,
+        available_coins: Vec<Coin>,
+        accrued: &mut BTreeMap<String, BTreeMap<String, Uint128>>,
+        flush: bool,
+    ) -> Vec<CosmosMsg> {
         available_coins
             .iter()
             .filter_map(|c| {
@@ -116,8 +545,14 @@ impl DenomSplits {
                 if self.explicit_splits.contains_key(&c.denom) {
                     None
                 } else if let Some(fallback_split) = &self.fallback_split {
-                    match fallback_split.get_transfer_messages(c.amount, c.denom.to_string(), None)
-                    {
+                    let denom_accrued = accrued.entry(c.denom.to_string()).or_default();
+                    let accrued_total: Uint128 = denom_accrued.values().sum();
+                    match fallback_split.get_accrued_transfer_messages(
+                        c.amount.saturating_sub(accrued_total),
+                        c.denom.to_string(),
+                        denom_accrued,
+                        flush,
+                    ) {
                         Ok(msgs) => Some(msgs),
                         Err(_) => None,
                     }
@@ -139,7 +574,8 @@ impl DenomSplits {
             .filter_map(|c| {
                 // for each coin denom we want to distribute,
                 // we look for it in our explicitly defined split configs
-                if let Some(config) = self.explicit_splits.get(&c.denom) {
+                // and default to the fallback split for the unknown ones
+                if let Some(config) = self.get_split(&c.denom) {
                     // found it, generate the msg or filter out
                     match config.get_transfer_messages(
                         c.amount,
@@ -163,8 +599,8 @@ impl DenomSplits {
             .filter_map(|c| {
                 // for each coin denom we want to distribute,
                 // we look for it in our explicitly defined split configs
-                let split = self.explicit_splits.get(&c.denom);
-                if let Some(config) = split {
+                // and default to the fallback split for the unknown ones
+                if let Some(config) = self.get_split(&c.denom) {
                     // found it, generate the msg or filter out
                     match config.get_transfer_messages(c.amount, c.denom.to_string(), None) {
                         Ok(msgs) => Some(msgs),
@@ -180,12 +616,13 @@ impl DenomSplits {
 
     pub fn apply_penalty(
         mut self,
-        penalty: Decimal,
+        penalties: &RagequitPenalty,
         party: &TwoPartyPolCovenantParty,
         counterparty: &TwoPartyPolCovenantParty,
     ) -> Result<DenomSplits, ContractError> {
         // we iterate over explicitly defined splits for each denom
         for (denom, mut config) in self.explicit_splits.clone().into_iter() {
+            let penalty = penalties.get_denom_penalty(&denom);
             let party_share = config
                 .receivers
                 // get current party shares or error out if not found
@@ -222,6 +659,7 @@ impl DenomSplits {
         }
 
         if let Some(mut split_config) = self.fallback_split {
+            let penalty = penalties.get_fallback_penalty();
             // apply the ragequit penalty to rq party and its counterparty
             let new_party_share = split_config
                 .receivers
@@ -280,6 +718,12 @@ impl TwoPartyPolCovenantConfig {
         api.addr_validate(&self.party_a.host_addr)?;
         api.addr_validate(&self.party_b.host_addr)?;
 
+        for party in [&self.party_a, &self.party_b] {
+            if let Some(adapter) = &party.contribution_adapter {
+                adapter.validate(api, &party.contribution.denom)?;
+            }
+        }
+
         ensure!(
             !self.party_a.contribution.amount.is_zero()
                 && !self.party_b.contribution.amount.is_zero(),
@@ -320,17 +764,37 @@ pub struct TwoPartyPolCovenantParty {
     pub allocation: Decimal,
     /// address of the interchain router associated with this party
     pub router: String,
+    /// optional adapter converting the contribution upon deposit, for
+    /// parties contributing a derivative (e.g. an LSD or a vault share)
+    /// of the denom expected by the liquid pooler
+    #[serde(default)]
+    pub contribution_adapter: Option<ContributionAdapter>,
 }
 
 impl TwoPartyPolCovenantParty {
     pub fn get_response_attributes(&self) -> Vec<Attribute> {
-        vec![
+        let mut attrs = vec![
             Attribute::new("contribution", self.contribution.to_string()),
             Attribute::new("host_addr", self.host_addr.to_string()),
             Attribute::new("controller_addr", self.controller_addr.to_string()),
             Attribute::new("allocation", self.allocation.to_string()),
             Attribute::new("router", self.router.to_string()),
-        ]
+        ];
+        if let Some(adapter) = &self.contribution_adapter {
+            attrs.extend(adapter.get_response_attributes());
+        }
+        attrs
+    }
+
+    /// returns true if the sender is the host of the party or, for
+    /// DAO DAO parties, one of the active proposal modules of the core
+    pub fn is_sender<C: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<C>,
+        sender: &Addr,
+    ) -> StdResult<bool> {
+        Ok(self.host_addr == *sender
+            || is_dao_proposal_module(querier, &self.host_addr, sender.as_str())?)
     }
 }
 
@@ -357,19 +821,243 @@ impl TwoPartyPolCovenantConfig {
 
         Ok(parties)
     }
+
+    /// like `authorize_sender`, but also accepts messages relayed by an
+    /// active proposal module of a party whose host address is a DAO DAO
+    /// core. this allows DAOs to act as a party through their proposals.
+    pub fn authorize_party_sender<C: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<C>,
+        sender: &Addr,
+    ) -> Result<(TwoPartyPolCovenantParty, TwoPartyPolCovenantParty), ContractError> {
+        if self.party_a.host_addr != *sender && self.party_b.host_addr != *sender {
+            for party in [&self.party_a, &self.party_b] {
+                if is_dao_proposal_module(querier, &party.host_addr, sender.as_str())? {
+                    return self.authorize_sender(party.host_addr.to_string());
+                }
+            }
+        }
+        self.authorize_sender(sender.to_string())
+    }
 }
 
 #[clocked]
 #[covenant_holder_distribute]
 #[covenant_holder_emergency_withdraw]
+#[covenant_holder_distribution_receipt]
 #[cw_serde]
 pub enum ExecuteMsg {
     /// initiate the ragequit
     Ragequit {},
-    /// withdraw the liquidity party is entitled to
-    Claim {},
+    /// withdraw the liquidity party is entitled to. if a reinvest target
+    /// is passed, the entitlement is deposited into it instead of being
+    /// routed to the party. if an alternate receiver is passed, the
+    /// entitlement is sent to it instead of the party router.
+    /// if denoms are passed, only the withdrawn funds in those denoms are
+    /// claimed, and the rest is retained by the holder for later claims.
+    Claim {
+        #[serde(default)]
+        reinvest: Option<ReinvestTarget>,
+        #[serde(default)]
+        to: Option<String>,
+        #[serde(default)]
+        denoms: Option<Vec<String>>,
+    },
     /// distribute any unspecified denoms
     DistributeFallbackSplit { denoms: Vec<String> },
+    /// request replacing the addresses of the sender party.
+    /// takes effect once approved by the counterparty or the
+    /// emergency committee.
+    RequestPartyRebinding { rebinding: PartyRebinding },
+    /// approve the pending rebinding of the party identified
+    /// by its (current) host address
+    ApprovePartyRebinding { party: String },
+    /// replace a mis-entered receiver of the explicit split of the denom.
+    /// only the emergency committee may submit fixes. the first submission
+    /// schedules the fix, and resubmitting it after a 24h timelock applies it.
+    FixSplitReceiver {
+        denom: String,
+        old: String,
+        new: String,
+    },
+    /// propose extending the lockup to a later expiration of the same
+    /// unit. replaces any pending proposal.
+    ProposeLockupExtension { new_expiry: Expiration },
+    /// approve the lockup extension proposed by the counterparty
+    ApproveLockupExtension {},
+    /// extend the deposit deadline to a later expiration of the same
+    /// unit. takes effect once both parties submitted the same expiry.
+    ExtendDepositDeadline { expiry: Expiration },
+    /// deposit the attached funds towards the contributions of the
+    /// parties. restricted to the allowed depositors, if configured.
+    Deposit {},
+    /// check the deposits of the parties right away instead of on the
+    /// next tick, activating the covenant once both are fulfilled
+    NotifyDeposit {},
+    /// propose amending the covenant terms. replaces any pending proposal.
+    ProposeAmendment { amendment: TermsAmendment },
+    /// approve the amendment proposed by the counterparty, identified
+    /// by its hash. the amendment is applied atomically.
+    ApproveAmendment { hash: HexBinary },
+}
+
+/// lockup extension awaiting the approval of the counterparty
+#[cw_serde]
+pub struct LockupExtensionProposal {
+    /// host address of the proposing party
+    pub proposer: String,
+    /// expiration replacing the current lockup
+    pub new_expiry: Expiration,
+    /// time after which the proposal can no longer be approved
+    pub expires_at: Timestamp,
+}
+
+impl LockupExtensionProposal {
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("proposer", &self.proposer),
+            Attribute::new("new_expiry", self.new_expiry.to_string()),
+            Attribute::new("expires_at", self.expires_at.to_string()),
+        ]
+    }
+}
+
+/// amendment of the covenant terms. unset terms are left as they are.
+#[cw_serde]
+#[derive(Default)]
+pub struct TermsAmendment {
+    pub lockup_config: Option<Expiration>,
+    pub ragequit_config: Option<RagequitConfig>,
+    /// explicit denom splits replacing the current ones
+    pub denom_splits: Option<BTreeMap<String, SplitConfig>>,
+    pub fallback_split: Option<SplitConfig>,
+}
+
+impl TermsAmendment {
+    pub fn is_empty(&self) -> bool {
+        self == &TermsAmendment::default()
+    }
+
+    /// sha256 of the json encoded amendment
+    pub fn get_hash(&self) -> StdResult<HexBinary> {
+        Ok(HexBinary::from(
+            Sha256::digest(to_json_vec(self)?).as_slice(),
+        ))
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        let mut attrs = vec![];
+        if let Some(lockup_config) = &self.lockup_config {
+            attrs.push(Attribute::new("lockup_config", lockup_config.to_string()));
+        }
+        if let Some(ragequit_config) = &self.ragequit_config {
+            attrs.extend(ragequit_config.get_response_attributes());
+        }
+        if let Some(denom_splits) = &self.denom_splits {
+            attrs.push(Attribute::new(
+                "explicit_splits",
+                format!("{:?}", denom_splits),
+            ));
+        }
+        if let Some(fallback_split) = &self.fallback_split {
+            attrs.push(Attribute::new(
+                "fallback_split",
+                format!("{:?}", fallback_split),
+            ));
+        }
+        attrs
+    }
+}
+
+/// terms amendment awaiting the approval of the counterparty
+#[cw_serde]
+pub struct AmendmentProposal {
+    /// host address of the proposing party
+    pub proposer: String,
+    pub amendment: TermsAmendment,
+    /// hash the counterparty approves the amendment by
+    pub hash: HexBinary,
+}
+
+/// terms amendment applied to the covenant
+#[cw_serde]
+pub struct AmendmentRecord {
+    /// position of the amendment in the history, starting at 0
+    pub index: u64,
+    /// host address of the proposing party
+    pub proposer: String,
+    /// host address of the approving party
+    pub approver: String,
+    pub amendment: TermsAmendment,
+    pub hash: HexBinary,
+    /// block height the amendment was applied at
+    pub height: u64,
+}
+
+/// deposit deadline extension submitted by one of the parties
+#[cw_serde]
+pub struct DepositDeadlineExtension {
+    /// host address of the submitting party
+    pub proposer: String,
+    /// expiration replacing the current deposit deadline
+    pub expiry: Expiration,
+}
+
+/// cumulative contribution deposited by a party, possibly
+/// funded over multiple installments
+#[cw_serde]
+#[derive(Default)]
+pub struct DepositRecord {
+    pub amount: Uint128,
+    /// number of ticks that observed a new deposit
+    pub installments: u64,
+}
+
+/// replacement addresses requested by a party.
+/// addresses left empty remain unchanged.
+#[cw_serde]
+pub struct PartyRebinding {
+    /// new neutron address authorized by the party to perform claims/ragequits
+    pub host_addr: Option<String>,
+    /// new address of the party on the controller chain
+    pub controller_addr: Option<String>,
+    /// new router of the party. split receivers get remapped to it.
+    pub router: Option<String>,
+}
+
+impl PartyRebinding {
+    pub fn validate(&self, api: &dyn Api) -> Result<(), ContractError> {
+        ensure!(
+            self.host_addr.is_some() || self.controller_addr.is_some() || self.router.is_some(),
+            ContractError::EmptyPartyRebinding {}
+        );
+        if let Some(addr) = &self.host_addr {
+            api.addr_validate(addr)?;
+        }
+        if let Some(addr) = &self.router {
+            api.addr_validate(addr)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        let to_attribute = |key: &str, val: &Option<String>| {
+            Attribute::new(key, val.clone().unwrap_or_else(|| "None".to_string()))
+        };
+        vec![
+            to_attribute("new_host_addr", &self.host_addr),
+            to_attribute("new_controller_addr", &self.controller_addr),
+            to_attribute("new_router", &self.router),
+        ]
+    }
+}
+
+/// single party covenant to roll the claimed entitlement over into
+#[cw_serde]
+pub struct ReinvestTarget {
+    /// address of the single-party-pol-holder owned by the claiming party.
+    /// the entitlement is deposited into the liquid pooler of that holder.
+    pub holder_address: String,
 }
 
 #[cw_serde]
@@ -452,12 +1140,148 @@ pub enum QueryMsg {
     ConfigPartyB {},
     #[returns(Expiration)]
     DepositDeadline {},
+    /// time left until the lockup expires
+    #[returns(TimeToExpiry)]
+    TimeToExpiry {},
     #[returns(TwoPartyPolCovenantConfig)]
     Config {},
     #[returns(DenomSplits)]
     DenomSplits {},
     #[returns(Addr)]
     EmergencyCommittee {},
+    /// lifetime amounts of every denom distributed to the party
+    /// (identified by its host address) via claims, ragequits,
+    /// refunds, and fallback distributions
+    #[returns(Vec<Coin>)]
+    DistributionsByParty { party: String },
+    /// contribution deposited so far by the party
+    /// (identified by its host address)
+    #[returns(DepositRecord)]
+    DepositRecord { party: String },
+    /// delivery status of the claim proceeds pushed to the party
+    /// routers as part of the claim with the given id
+    #[returns(Vec<DistributionReceipt>)]
+    ClaimReceipts { id: u64 },
+    /// rebinding requested by the party (identified by its
+    /// host address) that is awaiting approval
+    #[returns(Option<PartyRebinding>)]
+    PendingPartyRebinding { party: String },
+    #[returns(Option<ResidualLpDonationConfig>)]
+    ResidualLpDonation {},
+    /// amounts of the denom accrued by the receivers below
+    /// their fallback split min amounts
+    #[returns(BTreeMap<String, Uint128>)]
+    AccruedDistributions { denom: String },
+    /// split receiver fix scheduled for the denom
+    #[returns(Option<SplitReceiverFix>)]
+    PendingSplitReceiverFix { denom: String },
+    /// lockup extension awaiting the approval of the counterparty
+    #[returns(Option<LockupExtensionProposal>)]
+    PendingLockupExtension {},
+    /// deposit deadline extension awaiting the approval of the counterparty
+    #[returns(Option<DepositDeadlineExtension>)]
+    PendingDepositDeadlineExtension {},
+    /// whether the position is distributed automatically once the lockup expires
+    #[returns(bool)]
+    AutoDistributeOnExpiry {},
+    #[returns(Option<FeeRefundConfig>)]
+    FeeRefundConfig {},
+    #[returns(Option<GeneratorStakingConfig>)]
+    GeneratorStaking {},
+    #[returns(Option<ProvisioningStallConfig>)]
+    ProvisioningStallConfig {},
+    #[returns(Option<DepositorAllowlist>)]
+    DepositorAllowlist {},
+    /// provisioning stall currently observed, if any
+    #[returns(Option<ProvisioningStall>)]
+    ProvisioningStall {},
+    #[returns(Option<DepegGuardConfig>)]
+    DepegGuardConfig {},
+    /// depeg observed by the latest ticks, if any
+    #[returns(Option<Depeg>)]
+    Depeg {},
+    /// aggregated holder state, for frontends to fetch in a single query
+    #[returns(DashboardResponse)]
+    Dashboard {},
+    /// coins the party (by host address) would receive if it claimed now.
+    /// empty if the party can not claim in the current state.
+    #[returns(Vec<Coin>)]
+    ClaimPreview { party: String },
+    /// withdrawn funds retained for the party (by host address) by its
+    /// earlier selective claims
+    #[returns(Vec<Coin>)]
+    RetainedClaimFunds { party: String },
+    #[returns(Option<VestingConfig>)]
+    VestingConfig {},
+    /// share of the party (by host address) allocation claimed
+    /// under the vesting schedule so far
+    #[returns(Decimal)]
+    VestingClaimedShare { party: String },
+    /// terms amendment awaiting the approval of the counterparty
+    #[returns(Option<AmendmentProposal>)]
+    PendingAmendment {},
+    /// applied terms amendment at the given index of the history
+    #[returns(AmendmentRecord)]
+    Amendment { index: u64 },
+    /// number of terms amendments applied so far
+    #[returns(u64)]
+    AmendmentCount {},
+    /// settlement of the covenant, for the parties to attach to their
+    /// books. none until the covenant is complete.
+    #[returns(Option<SettlementReport>)]
+    SettlementReport {},
+}
+
+#[cw_serde]
+pub struct DashboardResponse {
+    pub contract_state: ContractState,
+    /// time left until the lockup expires
+    pub lockup: TimeToExpiry,
+    /// time left until the deposit deadline expires
+    pub deposit_deadline: TimeToExpiry,
+    pub ragequit_config: RagequitConfig,
+    pub covenant_config: TwoPartyPolCovenantConfig,
+    pub denom_splits: DenomSplits,
+    pub liquid_pooler_address: Addr,
+    /// LP position of the liquid pooler, if it can be resolved
+    pub pool_position: Option<PoolPosition>,
+    /// estimated underlying assets claimable by party a
+    pub party_a_claimable: Vec<Coin>,
+    /// estimated underlying assets claimable by party b
+    pub party_b_claimable: Vec<Coin>,
+}
+
+/// structured record of how the covenant settled
+#[cw_serde]
+pub struct SettlementReport {
+    pub party_a: PartySettlement,
+    pub party_b: PartySettlement,
+    /// ragequit penalty applied, if a party ragequit
+    pub ragequit: Option<RagequitSettlement>,
+    /// untrn refunded to the fee payer
+    pub fee_refunds: Coin,
+    /// none for covenants instantiated before the instantiation
+    /// time got recorded
+    pub instantiated_at: Option<Timestamp>,
+    pub completed_at: Timestamp,
+    /// seconds elapsed between instantiation and completion, if known
+    pub duration: Option<u64>,
+}
+
+#[cw_serde]
+pub struct PartySettlement {
+    pub host_addr: String,
+    /// cumulative deposit of the party contribution denom
+    pub contributed: Coin,
+    /// total distributed to the party router, per denom
+    pub distributed: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct RagequitSettlement {
+    /// host address of the ragequitting party
+    pub ragequit_party: String,
+    pub penalty: RagequitPenalty,
 }
 
 #[cw_serde]
@@ -483,17 +1307,28 @@ impl RagequitConfig {
         &self,
         a_allocation: Decimal,
         b_allocation: Decimal,
+        covenant_type: &CovenantType,
     ) -> Result<(), ContractError> {
         match self {
             RagequitConfig::Disabled => Ok(()),
             RagequitConfig::Enabled(terms) => {
-                // first we validate the range: [0.00, 1.00)
-                if terms.penalty >= Decimal::one() || terms.penalty < Decimal::zero() {
-                    return Err(ContractError::RagequitPenaltyRangeError {});
+                // share based ragequits withdraw a single fraction of the
+                // LP position, which can not reflect per-denom penalties
+                if let (RagequitPenalty::PerDenom(_), CovenantType::Share) =
+                    (&terms.penalty, covenant_type)
+                {
+                    return Err(ContractError::RagequitPerDenomPenaltyError {});
                 }
-                // then validate that rq penalty does not exceed either party allocations
-                if terms.penalty > a_allocation || terms.penalty > b_allocation {
-                    return Err(ContractError::RagequitPenaltyExceedsPartyAllocationError {});
+
+                for penalty in terms.penalty.values() {
+                    // first we validate the range: [0.00, 1.00)
+                    if penalty >= Decimal::one() || penalty < Decimal::zero() {
+                        return Err(ContractError::RagequitPenaltyRangeError {});
+                    }
+                    // then validate that rq penalty does not exceed either party allocations
+                    if penalty > a_allocation || penalty > b_allocation {
+                        return Err(ContractError::RagequitPenaltyExceedsPartyAllocationError {});
+                    }
                 }
 
                 Ok(())
@@ -502,12 +1337,74 @@ impl RagequitConfig {
     }
 }
 
+/// penalty applied to the allocation of the ragequitting party,
+/// in favor of its counterparty
+#[cw_serde]
+#[serde(untagged)]
+pub enum RagequitPenalty {
+    /// single penalty applied to every denom
+    Uniform(Decimal),
+    /// penalty per denom. denoms without a configured penalty,
+    /// including the ones distributed by the fallback split,
+    /// are not penalized.
+    PerDenom(BTreeMap<String, Decimal>),
+}
+
+impl From<Decimal> for RagequitPenalty {
+    fn from(penalty: Decimal) -> Self {
+        RagequitPenalty::Uniform(penalty)
+    }
+}
+
+impl fmt::Display for RagequitPenalty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RagequitPenalty::Uniform(penalty) => write!(f, "{penalty}"),
+            RagequitPenalty::PerDenom(penalties) => {
+                let penalties: Vec<String> = penalties
+                    .iter()
+                    .map(|(denom, penalty)| format!("{denom}:{penalty}"))
+                    .collect();
+                write!(f, "{}", penalties.join(","))
+            }
+        }
+    }
+}
+
+impl RagequitPenalty {
+    /// all of the configured penalties
+    pub fn values(&self) -> Vec<Decimal> {
+        match self {
+            RagequitPenalty::Uniform(penalty) => vec![*penalty],
+            RagequitPenalty::PerDenom(penalties) => penalties.values().copied().collect(),
+        }
+    }
+
+    pub fn get_denom_penalty(&self, denom: &str) -> Decimal {
+        match self {
+            RagequitPenalty::Uniform(penalty) => *penalty,
+            RagequitPenalty::PerDenom(penalties) => {
+                penalties.get(denom).copied().unwrap_or_default()
+            }
+        }
+    }
+
+    pub fn get_fallback_penalty(&self) -> Decimal {
+        match self {
+            RagequitPenalty::Uniform(penalty) => *penalty,
+            RagequitPenalty::PerDenom(_) => Decimal::zero(),
+        }
+    }
+}
+
 #[cw_serde]
 pub struct RagequitTerms {
     /// decimal based penalty to be applied on a party
-    /// for initiating ragequit. Must be in the range of (0.00, 1.00).
-    /// Also must not exceed either party allocations in raw values.
-    pub penalty: Decimal,
+    /// for initiating ragequit, either for all denoms or per denom.
+    /// Each penalty must be in the range of (0.00, 1.00) and must not
+    /// exceed either party allocations in raw values.
+    /// Per denom penalties are only supported by side based covenants.
+    pub penalty: RagequitPenalty,
     /// optional rq state. none indicates no ragequit.
     /// some holds the ragequit related config
     pub state: Option<RagequitState>,
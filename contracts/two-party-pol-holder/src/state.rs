@@ -1,10 +1,17 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use covenant_utils::distribution_receipt::DistributionReceipt;
+use cw_storage_plus::{Item, Map};
 use cw_utils::Expiration;
 
 use crate::msg::{
-    ContractState, DenomSplits, RagequitConfig, RagequitTerms, TwoPartyPolCovenantConfig,
+    AmendmentProposal, AmendmentRecord, ContractState, DenomSplits, Depeg, DepegGuardConfig,
+    DepositDeadlineExtension, DepositRecord, DepositorAllowlist, FeeRefundConfig,
+    GeneratorStakingConfig, LockupExtensionProposal, PartyRebinding, ProvisioningStall,
+    ProvisioningStallConfig, RagequitConfig, RagequitTerms, ResidualLpDonationConfig,
+    TwoPartyPolCovenantConfig, VestingConfig,
 };
 
 pub const CONTRACT_STATE: Item<ContractState> = Item::new("contract_state");
@@ -27,6 +34,10 @@ pub const DEPOSIT_DEADLINE: Item<Expiration> = Item::new("deposit_deadline");
 /// of the party initiating the ragequit
 pub const RAGEQUIT_CONFIG: Item<RagequitConfig> = Item::new("ragequit_config");
 
+/// configuration describing where the residual LP position goes
+/// if it remains unclaimed long after the lockup expiration
+pub const RESIDUAL_LP_DONATION: Item<ResidualLpDonationConfig> = Item::new("residual_lp_donation");
+
 /// configuration storing both parties information
 pub const COVENANT_CONFIG: Item<TwoPartyPolCovenantConfig> = Item::new("covenant_config");
 
@@ -35,14 +46,126 @@ pub const DENOM_SPLITS: Item<DenomSplits> = Item::new("denom_splits");
 
 pub const WITHDRAW_STATE: Item<WithdrawState> = Item::new("withdraw_state");
 
+/// lifetime amounts distributed to each party, keyed by
+/// (party host address, denom)
+pub const PARTY_DISTRIBUTIONS: Map<(String, String), Uint128> = Map::new("party_distributions");
+
+/// withdrawn funds retained by selective claims, keyed by
+/// (party host address, denom)
+pub const RETAINED_CLAIM_FUNDS: Map<(String, String), Uint128> = Map::new("retained_claim_funds");
+
+/// id to be assigned to the next claim
+pub const NEXT_CLAIM_ID: Item<u64> = Item::new("next_claim_id");
+
+/// receipts of the claim proceeds pushed to the party routers, keyed by claim id
+pub const CLAIM_RECEIPTS: Map<u64, Vec<DistributionReceipt>> = Map::new("claim_receipts");
+
+/// rebindings awaiting approval, keyed by the current host address
+/// of the requesting party
+pub const PENDING_REBINDINGS: Map<String, PartyRebinding> = Map::new("pending_rebindings");
+
+/// fallback split amounts accrued by the receivers below their
+/// min amounts, keyed by denom
+pub const ACCRUED_DISTRIBUTIONS: Map<String, BTreeMap<String, Uint128>> =
+    Map::new("accrued_distributions");
+
+/// seconds during which a proposed lockup extension can be approved
+pub const LOCKUP_EXTENSION_WINDOW: Item<u64> = Item::new("lockup_extension_window");
+
+/// lockup extension awaiting the approval of the counterparty
+pub const PENDING_LOCKUP_EXTENSION: Item<LockupExtensionProposal> =
+    Item::new("pending_lockup_extension");
+
+/// terms amendment awaiting the approval of the counterparty
+pub const PENDING_AMENDMENT: Item<AmendmentProposal> = Item::new("pending_amendment");
+
+/// append-only history of the applied terms amendments, keyed by index
+pub const AMENDMENT_HISTORY: Map<u64, AmendmentRecord> = Map::new("amendment_history");
+
+/// number of terms amendments applied so far
+pub const AMENDMENT_COUNT: Item<u64> = Item::new("amendment_count");
+
+/// whether the expiration tick distributes the position to both parties.
+/// holders instantiated before this option was introduced store nothing.
+pub const AUTO_DISTRIBUTE_ON_EXPIRY: Item<bool> = Item::new("auto_distribute_on_expiry");
+
+/// deposit deadline extension awaiting the approval of the counterparty
+pub const PENDING_DEPOSIT_DEADLINE_EXTENSION: Item<DepositDeadlineExtension> =
+    Item::new("pending_deposit_deadline_extension");
+
+/// cumulative deposits of each party, keyed by party host address
+pub const DEPOSIT_RECORDS: Map<String, DepositRecord> = Map::new("deposit_records");
+
+/// depositors allowed to deposit, if deposits are restricted
+pub const DEPOSITOR_ALLOWLIST: Item<DepositorAllowlist> = Item::new("depositor_allowlist");
+
+/// configuration describing where the untrn fee refunds go
+pub const FEE_REFUND_CONFIG: Item<FeeRefundConfig> = Item::new("fee_refund_config");
+
+/// configuration of the astroport generator staking, if opted into
+pub const GENERATOR_STAKING_CONFIG: Item<GeneratorStakingConfig> =
+    Item::new("generator_staking_config");
+
+/// configuration of the provisioning stall detection, if opted into
+pub const PROVISIONING_STALL_CONFIG: Item<ProvisioningStallConfig> =
+    Item::new("provisioning_stall_config");
+
+/// provisioning stall observed by the latest ticks, cleared
+/// as soon as a tick observes the liquidity provided
+pub const PROVISIONING_STALL: Item<ProvisioningStall> = Item::new("provisioning_stall");
+
+/// linear unlock schedule of the party allocations, if opted into
+pub const VESTING_CONFIG: Item<VestingConfig> = Item::new("vesting_config");
+
+/// share of the party allocation claimed under the vesting schedule,
+/// keyed by party host address
+pub const VESTING_CLAIMED_SHARES: Map<String, Decimal> = Map::new("vesting_claimed_shares");
+
+/// configuration of the depeg guard, if opted into
+pub const DEPEG_GUARD_CONFIG: Item<DepegGuardConfig> = Item::new("depeg_guard_config");
+
+/// depeg observed by the latest ticks, cleared as soon
+/// as a tick observes the pool ratio recovered
+pub const DEPEG: Item<Depeg> = Item::new("depeg");
+
+/// block time of the holder instantiation
+pub const INSTANTIATED_AT: Item<Timestamp> = Item::new("instantiated_at");
+
+/// block time at which the covenant completed
+pub const COMPLETED_AT: Item<Timestamp> = Item::new("completed_at");
+
+/// cumulative untrn refunded to the fee payer
+pub const FEE_REFUNDS: Item<Uint128> = Item::new("fee_refunds");
+
 #[cw_serde]
 pub enum WithdrawState {
     Processing {
         claimer_addr: String,
+        /// liquid pooler of the single party covenant the claimer
+        /// reinvests into, if any
+        #[serde(default)]
+        reinvest_to: Option<String>,
+        /// address receiving the claim instead of the claimer
+        /// router, if any
+        #[serde(default)]
+        receiver: Option<String>,
+        /// denoms claimed, if the claim is selective
+        #[serde(default)]
+        denoms: Option<Vec<String>>,
+        /// share of the claimer allocation vested so far, if the
+        /// claim is subject to the vesting schedule
+        #[serde(default)]
+        vested_share: Option<Decimal>,
     },
     ProcessingRagequit {
         claimer_addr: String,
         terms: RagequitTerms,
     },
     Emergency {},
+    /// withdrawal of the whole position, distributed to
+    /// both parties once the lockup expired
+    AutoDistribution {},
+    /// withdrawal of the assets held by a stalled liquid
+    /// pooler, refunded to the parties that contributed them
+    StalledProvisionRefund {},
 }
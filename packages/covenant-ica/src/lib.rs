@@ -0,0 +1,9 @@
+//! interchain account controller shared by the contracts that operate an
+//! ICA on a remote chain. the ICA lifecycle is tracked by the `IcaState`
+//! state machine, stored by this package on behalf of the contract.
+
+pub mod registration;
+pub mod state;
+pub mod sudo;
+
+pub const INTERCHAIN_ACCOUNT_ID: &str = "valence-ica";
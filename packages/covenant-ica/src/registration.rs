@@ -0,0 +1,68 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Coin, Env, QuerierWrapper, QueryRequest, StdResult, Storage, Uint64};
+use covenant_utils::mock_ibc::MOCK_IBC;
+use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
+
+use crate::{
+    state::{transition_ica_state, IcaState, IcaStateHooks},
+    INTERCHAIN_ACCOUNT_ID,
+};
+
+/// moves the ICA into `Registering` and returns the registration msg.
+/// the account gets activated once the channel open ack is received.
+pub fn register_ica<H: IcaStateHooks>(
+    hooks: &H,
+    storage: &mut dyn Storage,
+    querier: QuerierWrapper<'_, NeutronQuery>,
+    env: &Env,
+    connection_id: String,
+) -> StdResult<Vec<NeutronMsg>> {
+    transition_ica_state(storage, IcaState::Registering)?;
+
+    // without relayers no channel gets opened, so the contract
+    // stands in for its interchain account right away
+    if MOCK_IBC {
+        transition_ica_state(
+            storage,
+            IcaState::Active {
+                address: env.contract.address.to_string(),
+                controller_connection_id: connection_id,
+            },
+        )?;
+        hooks.on_active(storage)?;
+        return Ok(vec![]);
+    }
+
+    Ok(vec![NeutronMsg::register_interchain_account(
+        connection_id,
+        INTERCHAIN_ACCOUNT_ID.to_string(),
+        Some(query_ica_registration_fee(querier)?),
+    )])
+}
+
+// manual definitions for neutron ictxs module
+#[cw_serde]
+pub struct Params {
+    pub msg_submit_tx_max_messages: Uint64,
+    pub register_fee: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct QueryParamsResponse {
+    pub params: Params,
+}
+
+pub fn get_ictxs_module_params_query_msg() -> QueryRequest<NeutronQuery> {
+    QueryRequest::Stargate {
+        path: "/neutron.interchaintxs.v1.Query/Params".to_string(),
+        data: Binary(Vec::new()),
+    }
+}
+
+pub fn query_ica_registration_fee(
+    querier: QuerierWrapper<'_, NeutronQuery>,
+) -> StdResult<Vec<Coin>> {
+    let query_msg = get_ictxs_module_params_query_msg();
+    let response: QueryParamsResponse = querier.query(&query_msg)?;
+    Ok(response.params.register_fee)
+}
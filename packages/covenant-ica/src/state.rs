@@ -0,0 +1,193 @@
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+
+/// lifecycle of the interchain account controlled by the contract:
+/// `Unregistered -> Registering -> Active -> Closed -> Registering -> ...`
+#[cw_serde]
+pub enum IcaState {
+    /// no registration was submitted yet
+    Unregistered,
+    /// registration was submitted, awaiting the channel open ack
+    Registering,
+    /// channel is open and the account can submit txs
+    Active {
+        address: String,
+        controller_connection_id: String,
+    },
+    /// channel got closed by a timeout. the account has to be registered again
+    Closed,
+}
+
+impl IcaState {
+    /// address of the account, if it is active
+    pub fn address(&self) -> Option<String> {
+        match self {
+            IcaState::Active { address, .. } => Some(address.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn can_transition_to(&self, next: &IcaState) -> bool {
+        match next {
+            // failed registrations are retried, but active accounts are never re-registered
+            IcaState::Registering => !matches!(self, IcaState::Active { .. }),
+            IcaState::Active { .. } => matches!(self, IcaState::Registering),
+            // timeouts may close the channel at any point
+            IcaState::Closed => true,
+            IcaState::Unregistered => false,
+        }
+    }
+}
+
+impl fmt::Display for IcaState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcaState::Unregistered => write!(f, "unregistered"),
+            IcaState::Registering => write!(f, "registering"),
+            IcaState::Active { .. } => write!(f, "active"),
+            IcaState::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+pub const ICA_STATE: Item<IcaState> = Item::new("ica_state");
+
+/// ICA storage of contracts instantiated before `ICA_STATE`, in form of
+/// (port_id) -> (address, controller_connection_id)
+const LEGACY_INTERCHAIN_ACCOUNTS: Map<String, Option<(String, String)>> =
+    Map::new("interchain_accounts");
+
+/// payload of the latest submitted tx, awaiting its reply
+pub(crate) const REPLY_ID_STORAGE: Item<Vec<u8>> = Item::new("reply_queue_id");
+/// interchain tx payloads in form of (channel_id, seq_id) -> payload
+pub(crate) const SUDO_PAYLOAD: Map<(String, u64), Vec<u8>> = Map::new("sudo_payload");
+
+/// contract specific reactions to the ICA state transitions
+pub trait IcaStateHooks {
+    /// called once the channel open ack activated the account
+    fn on_active(&self, storage: &mut dyn Storage) -> StdResult<()>;
+    /// called once a timeout closed the channel
+    fn on_closed(&self, storage: &mut dyn Storage) -> StdResult<()>;
+}
+
+pub fn load_ica_state(storage: &dyn Storage) -> StdResult<IcaState> {
+    if let Some(state) = ICA_STATE.may_load(storage)? {
+        return Ok(state);
+    }
+
+    let legacy_entry = LEGACY_INTERCHAIN_ACCOUNTS
+        .range(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?;
+    Ok(match legacy_entry {
+        None => IcaState::Unregistered,
+        Some((_, None)) => IcaState::Registering,
+        Some((_, Some((address, controller_connection_id)))) => IcaState::Active {
+            address,
+            controller_connection_id,
+        },
+    })
+}
+
+/// advances the state machine, erroring on transitions it does not allow
+pub fn transition_ica_state(storage: &mut dyn Storage, next: IcaState) -> StdResult<()> {
+    let current = load_ica_state(storage)?;
+    if !current.can_transition_to(&next) {
+        return Err(StdError::generic_err(format!(
+            "invalid ica state transition: {current} -> {next}"
+        )));
+    }
+
+    // the state machine supersedes the legacy storage
+    LEGACY_INTERCHAIN_ACCOUNTS.clear(storage);
+    ICA_STATE.save(storage, &next)
+}
+
+/// returns the (address, controller_connection_id) of the active account
+pub fn get_ica(storage: &dyn Storage) -> StdResult<(String, String)> {
+    match load_ica_state(storage)? {
+        IcaState::Active {
+            address,
+            controller_connection_id,
+        } => Ok((address, controller_connection_id)),
+        _ => Err(StdError::generic_err(
+            "Interchain account is not created yet",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::testing::MockStorage;
+
+    use super::{
+        get_ica, load_ica_state, transition_ica_state, IcaState, LEGACY_INTERCHAIN_ACCOUNTS,
+    };
+
+    fn active() -> IcaState {
+        IcaState::Active {
+            address: "ica".to_string(),
+            controller_connection_id: "connection-0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_ica_lifecycle() {
+        let mut storage = MockStorage::default();
+        assert_eq!(load_ica_state(&storage).unwrap(), IcaState::Unregistered);
+        get_ica(&storage).unwrap_err();
+
+        transition_ica_state(&mut storage, IcaState::Registering).unwrap();
+        // failed registrations are retried
+        transition_ica_state(&mut storage, IcaState::Registering).unwrap();
+        transition_ica_state(&mut storage, active()).unwrap();
+        assert_eq!(
+            get_ica(&storage).unwrap(),
+            ("ica".to_string(), "connection-0".to_string())
+        );
+
+        transition_ica_state(&mut storage, IcaState::Closed).unwrap();
+        assert_eq!(load_ica_state(&storage).unwrap().address(), None);
+        transition_ica_state(&mut storage, IcaState::Registering).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        let mut storage = MockStorage::default();
+        let err = transition_ica_state(&mut storage, active()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid ica state transition: unregistered -> active"));
+
+        transition_ica_state(&mut storage, IcaState::Registering).unwrap();
+        transition_ica_state(&mut storage, active()).unwrap();
+        transition_ica_state(&mut storage, IcaState::Registering).unwrap_err();
+        transition_ica_state(&mut storage, active()).unwrap_err();
+        transition_ica_state(&mut storage, IcaState::Unregistered).unwrap_err();
+    }
+
+    #[test]
+    fn test_legacy_storage() {
+        let mut storage = MockStorage::default();
+        LEGACY_INTERCHAIN_ACCOUNTS
+            .save(&mut storage, "icacontroller-port".to_string(), &None)
+            .unwrap();
+        assert_eq!(load_ica_state(&storage).unwrap(), IcaState::Registering);
+
+        LEGACY_INTERCHAIN_ACCOUNTS
+            .save(
+                &mut storage,
+                "icacontroller-port".to_string(),
+                &Some(("ica".to_string(), "connection-0".to_string())),
+            )
+            .unwrap();
+        assert_eq!(load_ica_state(&storage).unwrap(), active());
+
+        transition_ica_state(&mut storage, IcaState::Closed).unwrap();
+        assert!(LEGACY_INTERCHAIN_ACCOUNTS.is_empty(&storage));
+        assert_eq!(load_ica_state(&storage).unwrap(), IcaState::Closed);
+    }
+}
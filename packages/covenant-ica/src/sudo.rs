@@ -0,0 +1,393 @@
+use cosmwasm_std::{
+    from_json, to_json_vec, Binary, CosmosMsg, CustomQuery, DepsMut, Reply, Response, StdError,
+    StdResult, Storage, SubMsg,
+};
+use covenant_utils::{
+    mock_ibc::{get_mock_ica_tx_msgs, MOCK_IBC},
+    neutron::{OpenAckVersion, SudoPayload},
+};
+use neutron_sdk::{
+    bindings::{
+        msg::{IbcFee, MsgSubmitTxResponse, NeutronMsg},
+        types::ProtobufAny,
+    },
+    sudo::msg::{RequestPacket, SudoMsg},
+};
+
+use crate::{
+    state::{transition_ica_state, IcaState, IcaStateHooks, REPLY_ID_STORAGE, SUDO_PAYLOAD},
+    INTERCHAIN_ACCOUNT_ID,
+};
+
+/// dispatches the ICA related sudo callbacks
+pub fn handle_sudo<Q: CustomQuery, H: IcaStateHooks>(
+    hooks: &H,
+    deps: DepsMut<Q>,
+    msg: SudoMsg,
+) -> StdResult<Response<NeutronMsg>> {
+    match msg {
+        // For handling successful (non-error) acknowledgements.
+        SudoMsg::Response { request, data } => sudo_response(request, data),
+
+        // For handling error acknowledgements.
+        SudoMsg::Error { request, details } => sudo_error(request, details),
+
+        // For handling error timeouts.
+        SudoMsg::Timeout { request } => sudo_timeout(hooks, deps, request),
+
+        // For handling successful registering of ICA
+        SudoMsg::OpenAck {
+            port_id,
+            channel_id,
+            counterparty_channel_id,
+            counterparty_version,
+        } => sudo_open_ack(
+            hooks,
+            deps,
+            port_id,
+            channel_id,
+            counterparty_channel_id,
+            counterparty_version,
+        ),
+        _ => Ok(Response::default()),
+    }
+}
+
+/// closes the ICA, forcing its re-registration. channel is already closed.
+pub fn sudo_timeout<Q: CustomQuery, H: IcaStateHooks>(
+    hooks: &H,
+    deps: DepsMut<Q>,
+    _request: RequestPacket,
+) -> StdResult<Response<NeutronMsg>> {
+    transition_ica_state(deps.storage, IcaState::Closed)?;
+    hooks.on_closed(deps.storage)?;
+
+    // returning Ok as this is anticipated. channel is already closed.
+    Ok(Response::default())
+}
+
+/// handles the response. if request sequence or source channel are missing,
+/// it will return an error and close the channel. otherwise returns an Ok()
+/// with data encoded in base64 as a response attribute.
+pub fn sudo_response(request: RequestPacket, data: Binary) -> StdResult<Response<NeutronMsg>> {
+    // either of these errors will close the channel
+    request
+        .sequence
+        .ok_or_else(|| StdError::generic_err("sequence not found"))?;
+
+    request
+        .source_channel
+        .ok_or_else(|| StdError::generic_err("channel_id not found"))?;
+
+    Ok(Response::default()
+        .add_attribute("method", "sudo_response")
+        .add_attribute("data", data.to_base64()))
+}
+
+/// handles the sudo error. if request sequence or source channel are missing,
+/// it will return an error and close the channel. otherwise returns an Ok().
+pub fn sudo_error(request: RequestPacket, _details: String) -> StdResult<Response<NeutronMsg>> {
+    // either of these errors will close the channel
+    request
+        .sequence
+        .ok_or_else(|| StdError::generic_err("sequence not found"))?;
+
+    request
+        .source_channel
+        .ok_or_else(|| StdError::generic_err("channel_id not found"))?;
+
+    Ok(Response::default().add_attribute("method", "sudo_error"))
+}
+
+/// activates the ICA with the address reported by the counterparty
+pub fn sudo_open_ack<Q: CustomQuery, H: IcaStateHooks>(
+    hooks: &H,
+    deps: DepsMut<Q>,
+    _port_id: String,
+    _channel_id: String,
+    _counterparty_channel_id: String,
+    counterparty_version: String,
+) -> StdResult<Response<NeutronMsg>> {
+    // The version variable contains a JSON value with multiple fields,
+    // including the generated account address.
+    let parsed_version: Result<OpenAckVersion, _> =
+        serde_json_wasm::from_str(counterparty_version.as_str());
+
+    // get the parsed OpenAckVersion or return an error if we fail
+    let Ok(parsed_version) = parsed_version else {
+        return Err(StdError::generic_err("Can't parse counterparty_version"));
+    };
+
+    transition_ica_state(
+        deps.storage,
+        IcaState::Active {
+            address: parsed_version.address,
+            controller_connection_id: parsed_version.controller_connection_id,
+        },
+    )?;
+    hooks.on_active(deps.storage)?;
+
+    Ok(Response::default().add_attribute("method", "sudo_open_ack"))
+}
+
+/// saves the payload to be attached to the submitted tx once its
+/// reply is received, see `prepare_sudo_payload`
+pub fn msg_with_sudo_callback<C: Into<CosmosMsg<T>>, T>(
+    storage: &mut dyn Storage,
+    msg: C,
+    payload: SudoPayload,
+    reply_id: u64,
+) -> StdResult<SubMsg<T>> {
+    REPLY_ID_STORAGE.save(storage, &to_json_vec(&payload)?)?;
+    Ok(SubMsg::reply_on_success(msg, reply_id))
+}
+
+/// submits the msgs as a tx of the ICA, with the payload to be read back
+/// once the tx is acknowledged. without relayers, the msgs are executed
+/// by the contract itself and no acknowledgement is received.
+pub fn submit_ica_tx(
+    storage: &mut dyn Storage,
+    controller_connection_id: String,
+    msgs: Vec<ProtobufAny>,
+    timeout: u64,
+    fee: IbcFee,
+    payload: SudoPayload,
+    reply_id: u64,
+) -> StdResult<Vec<SubMsg<NeutronMsg>>> {
+    if MOCK_IBC {
+        return Ok(get_mock_ica_tx_msgs(msgs)?
+            .into_iter()
+            .map(SubMsg::new)
+            .collect());
+    }
+
+    let submit_msg = NeutronMsg::submit_tx(
+        controller_connection_id,
+        INTERCHAIN_ACCOUNT_ID.to_string(),
+        msgs,
+        "".to_string(),
+        timeout,
+        fee,
+    );
+    Ok(vec![msg_with_sudo_callback(
+        storage, submit_msg, payload, reply_id,
+    )?])
+}
+
+/// prepare_sudo_payload is called from reply handler
+/// The method is used to extract sequence id and channel from SubmitTxResponse to
+/// process sudo payload defined in msg_with_sudo_callback later in Sudo handler.
+/// Such flow msg_with_sudo_callback() -> reply() -> prepare_sudo_payload() -> sudo()
+/// allows you "attach" some payload to your SubmitTx message
+/// and process this payload when an acknowledgement for the SubmitTx message
+/// is received in Sudo handler
+pub fn prepare_sudo_payload<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    msg: Reply,
+) -> StdResult<Response<NeutronMsg>> {
+    let payload = REPLY_ID_STORAGE.load(deps.storage)?;
+
+    let resp: MsgSubmitTxResponse = serde_json_wasm::from_slice(
+        msg.result
+            .into_result()
+            .map_err(StdError::generic_err)?
+            .data
+            .ok_or_else(|| StdError::generic_err("no result"))?
+            .as_slice(),
+    )
+    .map_err(|e| StdError::generic_err(format!("failed to parse response: {e:?}")))?;
+
+    SUDO_PAYLOAD.save(deps.storage, (resp.channel, resp.sequence_id), &payload)?;
+
+    Ok(Response::default())
+}
+
+/// payload attached to the interchain tx with the given channel and sequence
+pub fn read_sudo_payload(
+    storage: &dyn Storage,
+    channel_id: String,
+    seq_id: u64,
+) -> StdResult<Option<SudoPayload>> {
+    SUDO_PAYLOAD
+        .may_load(storage, (channel_id, seq_id))?
+        .map(from_json)
+        .transpose()
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        testing::mock_dependencies, to_json_binary, to_json_string, Reply, StdResult, Storage,
+        SubMsgResponse, SubMsgResult,
+    };
+    use covenant_utils::neutron::{OpenAckVersion, SudoPayload};
+    use cw_storage_plus::Item;
+    use neutron_sdk::{
+        bindings::msg::{MsgSubmitTxResponse, NeutronMsg},
+        sudo::msg::{RequestPacket, SudoMsg},
+    };
+
+    use super::{handle_sudo, msg_with_sudo_callback, prepare_sudo_payload, read_sudo_payload};
+    use crate::state::{get_ica, load_ica_state, transition_ica_state, IcaState, IcaStateHooks};
+
+    const HOOK_CALLS: Item<Vec<String>> = Item::new("hook_calls");
+
+    struct RecordingHooks;
+
+    impl IcaStateHooks for RecordingHooks {
+        fn on_active(&self, storage: &mut dyn Storage) -> StdResult<()> {
+            record(storage, "active")
+        }
+
+        fn on_closed(&self, storage: &mut dyn Storage) -> StdResult<()> {
+            record(storage, "closed")
+        }
+    }
+
+    fn record(storage: &mut dyn Storage, call: &str) -> StdResult<()> {
+        let mut calls = HOOK_CALLS.may_load(storage)?.unwrap_or_default();
+        calls.push(call.to_string());
+        HOOK_CALLS.save(storage, &calls)
+    }
+
+    fn open_ack(address: &str) -> SudoMsg {
+        SudoMsg::OpenAck {
+            port_id: "icacontroller-port".to_string(),
+            channel_id: "channel-1".to_string(),
+            counterparty_channel_id: "channel-2".to_string(),
+            counterparty_version: to_json_string(&OpenAckVersion {
+                version: "ics27-1".to_string(),
+                controller_connection_id: "connection-0".to_string(),
+                host_connection_id: "connection-1".to_string(),
+                address: address.to_string(),
+                encoding: "proto3".to_string(),
+                tx_type: "sdk_multi_msg".to_string(),
+            })
+            .unwrap(),
+        }
+    }
+
+    fn request_packet() -> RequestPacket {
+        RequestPacket {
+            sequence: Some(1),
+            source_port: None,
+            source_channel: Some("channel-1".to_string()),
+            destination_port: None,
+            destination_channel: None,
+            data: None,
+            timeout_height: None,
+            timeout_timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_open_ack_and_timeout() {
+        let mut deps = mock_dependencies();
+        transition_ica_state(deps.as_mut().storage, IcaState::Registering).unwrap();
+
+        handle_sudo(&RecordingHooks, deps.as_mut(), open_ack("ica")).unwrap();
+        assert_eq!(
+            get_ica(deps.as_ref().storage).unwrap(),
+            ("ica".to_string(), "connection-0".to_string())
+        );
+
+        handle_sudo(
+            &RecordingHooks,
+            deps.as_mut(),
+            SudoMsg::Timeout {
+                request: request_packet(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            load_ica_state(deps.as_ref().storage).unwrap(),
+            IcaState::Closed
+        );
+        assert_eq!(
+            HOOK_CALLS.load(deps.as_ref().storage).unwrap(),
+            vec!["active", "closed"]
+        );
+    }
+
+    #[test]
+    fn test_open_ack_requires_registration() {
+        let mut deps = mock_dependencies();
+        handle_sudo(&RecordingHooks, deps.as_mut(), open_ack("ica")).unwrap_err();
+
+        transition_ica_state(deps.as_mut().storage, IcaState::Registering).unwrap();
+        let err = handle_sudo(
+            &RecordingHooks,
+            deps.as_mut(),
+            SudoMsg::OpenAck {
+                port_id: "icacontroller-port".to_string(),
+                channel_id: "channel-1".to_string(),
+                counterparty_channel_id: "channel-2".to_string(),
+                counterparty_version: "not json".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Can't parse counterparty_version"));
+        assert!(HOOK_CALLS
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_sudo_response_requires_sequence() {
+        let mut deps = mock_dependencies();
+        let mut request = request_packet();
+        request.sequence = None;
+
+        handle_sudo(
+            &RecordingHooks,
+            deps.as_mut(),
+            SudoMsg::Response {
+                request,
+                data: to_json_binary("ack").unwrap(),
+            },
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_sudo_payload_roundtrip() {
+        let mut deps = mock_dependencies();
+        let payload = SudoPayload {
+            message: "try_forward_funds".to_string(),
+            port_id: "icacontroller-port".to_string(),
+        };
+
+        msg_with_sudo_callback::<_, NeutronMsg>(
+            deps.as_mut().storage,
+            NeutronMsg::remove_interchain_query(1),
+            payload.clone(),
+            1,
+        )
+        .unwrap();
+
+        let reply = Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(
+                    to_json_binary(&MsgSubmitTxResponse {
+                        sequence_id: 7,
+                        channel: "channel-1".to_string(),
+                    })
+                    .unwrap(),
+                ),
+            }),
+        };
+        prepare_sudo_payload(deps.as_mut(), reply).unwrap();
+
+        assert_eq!(
+            read_sudo_payload(deps.as_ref().storage, "channel-1".to_string(), 7).unwrap(),
+            Some(payload)
+        );
+        assert_eq!(
+            read_sudo_payload(deps.as_ref().storage, "channel-1".to_string(), 8).unwrap(),
+            None
+        );
+    }
+}
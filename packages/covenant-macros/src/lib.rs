@@ -186,3 +186,81 @@ pub fn covenant_holder_emergency_withdraw(
         .into(),
     )
 }
+
+#[proc_macro_attribute]
+pub fn covenant_holder_distribution_receipt(
+    metadata: TokenStream,
+    input: TokenStream,
+) -> TokenStream {
+    merge_variants(
+        metadata,
+        input,
+        quote!(
+            enum DistributionReceiptMsgs {
+                /// Sent by a router once it successfully sent out the funds
+                /// it received as part of the distribution with the given id
+                AcknowledgeDistribution { id: u64 },
+                /// Sent by a router if it is unable to deliver the funds
+                /// it received as part of the distribution with the given id
+                ReportDistributionFailure { id: u64, reason: String },
+            }
+        )
+        .into(),
+    )
+}
+
+#[proc_macro_attribute]
+pub fn covenant_router_register_distribution(
+    metadata: TokenStream,
+    input: TokenStream,
+) -> TokenStream {
+    merge_variants(
+        metadata,
+        input,
+        quote!(
+            enum RegisterDistributionMsgs {
+                /// Sent by a holder along with the funds of a distribution.
+                /// The router calls back the sender once the funds are delivered
+                RegisterDistribution {
+                    id: u64,
+                    coins: Vec<::cosmwasm_std::Coin>,
+                },
+            }
+        )
+        .into(),
+    )
+}
+
+#[proc_macro_attribute]
+pub fn covenant_router_halt(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    merge_variants(
+        metadata,
+        input,
+        quote!(
+            enum RouterHaltMsgs {
+                /// Stops any further routing and returns the remaining balances
+                /// to the holder. Only callable by the covenant that administers
+                /// the router, or by the emergency committee
+                Halt {},
+            }
+        )
+        .into(),
+    )
+}
+
+#[proc_macro_attribute]
+pub fn covenant_decommission(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    merge_variants(
+        metadata,
+        input,
+        quote!(
+            enum DecommissionMsgs {
+                /// Winds the module down once the covenant is complete,
+                /// returning its residual balances. Only callable by the
+                /// covenant that administers the module
+                Decommission {},
+            }
+        )
+        .into(),
+    )
+}
@@ -0,0 +1,46 @@
+use astroport::asset::PairInfo;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{ensure, CustomQuery, Decimal, QuerierWrapper, StdError, StdResult};
+
+use crate::orient_price;
+
+/// subset of the astroport pair queries exposing its price oracle
+#[cw_serde]
+pub enum OracleQueryMsg {
+    /// returns the price observation from `seconds_ago` seconds back
+    Observe { seconds_ago: u64 },
+}
+
+/// averaged price of the first pool asset denominated in the second one
+#[cw_serde]
+pub struct OracleObservation {
+    pub timestamp: u64,
+    pub price: Decimal,
+}
+
+/// queries the time-weighted price of `base` denominated in `quote`
+/// from the oracle of a two-asset astroport pool
+pub fn query_twap<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    pool_address: &str,
+    seconds_ago: u64,
+    base: &str,
+    quote: &str,
+) -> StdResult<Decimal> {
+    let pair_info: PairInfo =
+        querier.query_wasm_smart(pool_address, &astroport::pair::QueryMsg::Pair {})?;
+    let assets: Vec<String> = pair_info
+        .asset_infos
+        .iter()
+        .map(|info| info.to_string())
+        .collect();
+    ensure!(
+        assets.len() == 2,
+        StdError::generic_err("astroport price oracle requires a two-asset pool")
+    );
+
+    let observation: OracleObservation =
+        querier.query_wasm_smart(pool_address, &OracleQueryMsg::Observe { seconds_ago })?;
+
+    orient_price(observation.price, (&assets[0], &assets[1]), base, quote)
+}
@@ -0,0 +1,229 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{ensure, Api, CustomQuery, Decimal, Deps, StdError, StdResult};
+
+pub mod astroport;
+pub mod osmosis;
+pub mod slinky;
+
+/// venue to source the price of an asset pair from.
+/// prices are always expressed as the amount of quote asset per
+/// one unit of the base asset.
+#[cw_serde]
+pub enum PriceSource {
+    /// Neutron x/oracle (Slinky). base and quote are the oracle
+    /// tickers of the currency pair, e.g. `ATOM` and `USD`.
+    Slinky {},
+    /// time-weighted price observed by the oracle of an astroport pool.
+    /// base and quote are the asset denoms (or cw20 addresses) of the pool.
+    AstroportTwap {
+        pool_address: String,
+        /// age of the observation to query, in seconds
+        seconds_ago: u64,
+    },
+    /// arithmetic twap of an osmosis pool, derived from the accumulators of
+    /// its twap records. the registered KV interchain query has to read the
+    /// pool twap record at the start of the window along with the most recent
+    /// one. base and quote are the osmosis denoms of the pool assets.
+    OsmosisTwap {
+        query_id: u64,
+        /// minimum time span between both twap records, in seconds
+        window_seconds: u64,
+    },
+}
+
+impl PriceSource {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        match self {
+            PriceSource::AstroportTwap { pool_address, .. } => {
+                api.addr_validate(pool_address)?;
+            }
+            PriceSource::OsmosisTwap { window_seconds, .. } => ensure!(
+                *window_seconds > 0,
+                StdError::generic_err("osmosis twap window must be non-zero")
+            ),
+            PriceSource::Slinky {} => (),
+        }
+        Ok(())
+    }
+
+    /// whether the source observes the price of a pool rather than the market
+    pub fn is_pool_twap(&self) -> bool {
+        matches!(
+            self,
+            PriceSource::AstroportTwap { .. } | PriceSource::OsmosisTwap { .. }
+        )
+    }
+}
+
+/// queries the price of `base` denominated in `quote` from the given source
+pub fn query_price<C: CustomQuery>(
+    deps: Deps<C>,
+    source: &PriceSource,
+    base: &str,
+    quote: &str,
+) -> StdResult<Decimal> {
+    let price = match source {
+        PriceSource::Slinky {} => slinky::query_price(&deps.querier, base, quote)?,
+        PriceSource::AstroportTwap {
+            pool_address,
+            seconds_ago,
+        } => astroport::query_twap(&deps.querier, pool_address, *seconds_ago, base, quote)?,
+        PriceSource::OsmosisTwap {
+            query_id,
+            window_seconds,
+        } => osmosis::query_twap(&deps.querier, *query_id, *window_seconds, base, quote)?,
+    };
+
+    ensure!(
+        !price.is_zero(),
+        StdError::generic_err(format!("price source returned no price for {base}/{quote}"))
+    );
+    Ok(price)
+}
+
+/// price of an asset pair as reported by a price source
+#[cw_serde]
+pub struct PriceQuery {
+    pub source: PriceSource,
+    /// identifier of the priced asset, as understood by the source
+    pub base: String,
+    /// identifier of the asset the price is quoted in, as understood by the source
+    pub quote: String,
+}
+
+impl PriceQuery {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        self.source.validate(api)
+    }
+
+    pub fn query<C: CustomQuery>(&self, deps: Deps<C>) -> StdResult<Decimal> {
+        query_price(deps, &self.source, &self.base, &self.quote)
+    }
+}
+
+/// bounds the deviation of a price (e.g. the spot price of a pool)
+/// from the price reported by a price source
+#[cw_serde]
+pub struct PriceGuard {
+    pub source: PriceSource,
+    /// identifier of the priced asset, as understood by the source
+    pub base: String,
+    /// identifier of the asset the price is quoted in, as understood by the source
+    pub quote: String,
+    /// highest acceptable deviation relative to the source price
+    pub max_deviation: Decimal,
+}
+
+impl PriceGuard {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        self.source.validate(api)?;
+        ensure!(
+            !self.max_deviation.is_zero(),
+            StdError::generic_err("price guard max deviation must be non-zero")
+        );
+        Ok(())
+    }
+
+    /// range of prices within the max deviation of the given source price
+    pub fn get_price_range(&self, source_price: Decimal) -> StdResult<(Decimal, Decimal)> {
+        let max_delta = source_price.checked_mul(self.max_deviation)?;
+        Ok((
+            source_price.saturating_sub(max_delta),
+            source_price.checked_add(max_delta)?,
+        ))
+    }
+
+    /// errors if `price` deviates from the source price by more than
+    /// the max deviation. otherwise returns the source price.
+    pub fn check_price<C: CustomQuery>(&self, deps: Deps<C>, price: Decimal) -> StdResult<Decimal> {
+        let source_price = query_price(deps, &self.source, &self.base, &self.quote)?;
+        let (min_price, max_price) = self.get_price_range(source_price)?;
+
+        ensure!(
+            min_price <= price && price <= max_price,
+            StdError::generic_err(format!(
+                "price {price} deviates from the source price {source_price} by more than {}",
+                self.max_deviation
+            ))
+        );
+        Ok(source_price)
+    }
+}
+
+/// given the price of `asset_0` denominated in `asset_1`, returns
+/// the price of `base` denominated in `quote`
+pub(crate) fn orient_price(
+    price: Decimal,
+    (asset_0, asset_1): (&str, &str),
+    base: &str,
+    quote: &str,
+) -> StdResult<Decimal> {
+    if (base, quote) == (asset_0, asset_1) {
+        Ok(price)
+    } else if (base, quote) == (asset_1, asset_0) {
+        ensure!(
+            !price.is_zero(),
+            StdError::generic_err(format!("price source returned no price for {base}/{quote}"))
+        );
+        Decimal::one()
+            .checked_div(price)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    } else {
+        Err(StdError::generic_err(format!(
+            "price source does not quote {base}/{quote}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use cosmwasm_std::Decimal;
+
+    use super::{orient_price, PriceGuard, PriceSource};
+
+    #[test]
+    fn test_orient_price_matching_order() {
+        let price = Decimal::from_str("2.5").unwrap();
+        assert_eq!(
+            orient_price(price, ("uatom", "untrn"), "uatom", "untrn").unwrap(),
+            price
+        );
+    }
+
+    #[test]
+    fn test_orient_price_inverted_order() {
+        let price = Decimal::from_str("2.5").unwrap();
+        assert_eq!(
+            orient_price(price, ("uatom", "untrn"), "untrn", "uatom").unwrap(),
+            Decimal::from_str("0.4").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_price_guard_range() {
+        let guard = PriceGuard {
+            source: PriceSource::Slinky {},
+            base: "ATOM".to_string(),
+            quote: "USD".to_string(),
+            max_deviation: Decimal::percent(10),
+        };
+
+        assert_eq!(
+            guard
+                .get_price_range(Decimal::from_str("2.5").unwrap())
+                .unwrap(),
+            (
+                Decimal::from_str("2.25").unwrap(),
+                Decimal::from_str("2.75").unwrap()
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "price source does not quote uosmo/untrn")]
+    fn test_orient_price_validates_pair() {
+        orient_price(Decimal::one(), ("uatom", "untrn"), "uosmo", "untrn").unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    ensure, from_json, to_json_vec, ContractResult, CustomQuery, Decimal, Decimal256,
+    QuerierWrapper, QueryRequest, StdError, StdResult, SystemResult, Uint256,
+};
+use neutron_sdk::bindings::query::{NeutronQuery, QueryRegisteredQueryResultResponse};
+use prost::Message;
+
+use crate::orient_price;
+
+/// osmosis `sdk.Dec` values are encoded as integers scaled by 10^18
+const OSMOSIS_DEC_PRECISION: u32 = 18;
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Timestamp {
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+// manual definition of the osmosis twap record. fields irrelevant
+// to the arithmetic twap (geometric accumulator and errors) are left out.
+#[derive(Clone, PartialEq, Message)]
+pub struct TwapRecord {
+    #[prost(uint64, tag = "1")]
+    pub pool_id: u64,
+    #[prost(string, tag = "2")]
+    pub asset0_denom: String,
+    #[prost(string, tag = "3")]
+    pub asset1_denom: String,
+    #[prost(int64, tag = "4")]
+    pub height: i64,
+    #[prost(message, optional, tag = "5")]
+    pub time: Option<Timestamp>,
+    /// spot price of asset1 denominated in asset0
+    #[prost(string, tag = "6")]
+    pub p0_last_spot_price: String,
+    /// spot price of asset0 denominated in asset1
+    #[prost(string, tag = "7")]
+    pub p1_last_spot_price: String,
+    /// p0 spot prices accumulated over the record lifetime, in milliseconds
+    #[prost(string, tag = "8")]
+    pub p0_arithmetic_twap_accumulator: String,
+    /// p1 spot prices accumulated over the record lifetime, in milliseconds
+    #[prost(string, tag = "9")]
+    pub p1_arithmetic_twap_accumulator: String,
+}
+
+impl TwapRecord {
+    /// returns the record time in milliseconds, as used by the osmosis accumulators
+    pub fn get_time_ms(&self) -> StdResult<i64> {
+        let time = self.time.as_ref().ok_or_else(|| {
+            StdError::generic_err(format!("twap record of pool {} has no time", self.pool_id))
+        })?;
+        Ok(time.seconds * 1000 + i64::from(time.nanos) / 1_000_000)
+    }
+
+    /// returns the accumulated p0 spot prices
+    pub fn get_p0_accumulator(&self) -> StdResult<Decimal256> {
+        let atomics = Uint256::from_str(&self.p0_arithmetic_twap_accumulator)?;
+        Decimal256::from_atomics(atomics, OSMOSIS_DEC_PRECISION)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
+
+/// arithmetic twap of asset1 denominated in asset0 between two records
+/// of the same pool, computed like the osmosis twap module does
+pub fn get_arithmetic_twap(start: &TwapRecord, end: &TwapRecord) -> StdResult<Decimal> {
+    ensure!(
+        (start.pool_id, &start.asset0_denom, &start.asset1_denom)
+            == (end.pool_id, &end.asset0_denom, &end.asset1_denom),
+        StdError::generic_err("twap records belong to different pools")
+    );
+
+    let time_delta_ms = end.get_time_ms()? - start.get_time_ms()?;
+    ensure!(
+        time_delta_ms > 0,
+        StdError::generic_err("twap window must end after it starts")
+    );
+
+    let accumulator_delta = end
+        .get_p0_accumulator()?
+        .checked_sub(start.get_p0_accumulator()?)?;
+    let twap = accumulator_delta
+        .checked_div(Decimal256::from_ratio(time_delta_ms as u128, 1u128))
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Decimal::try_from(twap).map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+/// reads the twap records stored as the results of the given KV interchain query
+pub fn query_twap_records<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    query_id: u64,
+) -> StdResult<Vec<TwapRecord>> {
+    // interchain query results are served by the neutron bindings, regardless
+    // of the custom query type the calling contract is instantiated with
+    let request: QueryRequest<NeutronQuery> =
+        QueryRequest::Custom(NeutronQuery::InterchainQueryResult { query_id });
+    let response: QueryRegisteredQueryResultResponse =
+        match querier.raw_query(&to_json_vec(&request)?) {
+            SystemResult::Err(e) => {
+                return Err(StdError::generic_err(format!("Querier system error: {e}")))
+            }
+            SystemResult::Ok(ContractResult::Err(e)) => {
+                return Err(StdError::generic_err(format!(
+                    "Querier contract error: {e}"
+                )))
+            }
+            SystemResult::Ok(ContractResult::Ok(value)) => from_json(value)?,
+        };
+
+    response
+        .result
+        .kv_results
+        .iter()
+        .map(|kv| {
+            TwapRecord::decode(kv.value.as_slice())
+                .map_err(|e| StdError::generic_err(e.to_string()))
+        })
+        .collect()
+}
+
+/// queries the arithmetic twap of `base` denominated in `quote` over the
+/// window spanned by the two twap records read by the given interchain
+/// query: the record at the start of the window and the most recent one.
+/// the window must span at least `window_seconds`.
+pub fn query_twap<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    query_id: u64,
+    window_seconds: u64,
+    base: &str,
+    quote: &str,
+) -> StdResult<Decimal> {
+    let mut records = query_twap_records(querier, query_id)?;
+    ensure!(
+        records.len() == 2,
+        StdError::generic_err(format!(
+            "interchain query {query_id} must return the twap records at both ends of the window"
+        ))
+    );
+    records.sort_by_key(|record| record.get_time_ms().unwrap_or_default());
+    let (start, end) = (&records[0], &records[1]);
+
+    ensure!(
+        end.get_time_ms()? - start.get_time_ms()? >= window_seconds as i64 * 1000,
+        StdError::generic_err(format!(
+            "twap records of interchain query {query_id} span less than {window_seconds}s"
+        ))
+    );
+
+    orient_price(
+        get_arithmetic_twap(start, end)?,
+        (&end.asset1_denom, &end.asset0_denom),
+        base,
+        quote,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use cosmwasm_std::Decimal;
+    use prost::Message;
+
+    use super::{get_arithmetic_twap, Timestamp, TwapRecord};
+
+    fn record(seconds: i64, p0_accumulator: &str) -> TwapRecord {
+        TwapRecord {
+            pool_id: 1,
+            asset0_denom: "uatom".to_string(),
+            asset1_denom: "uosmo".to_string(),
+            height: 100,
+            time: Some(Timestamp { seconds, nanos: 0 }),
+            p0_last_spot_price: "1500000000000000000".to_string(),
+            p1_last_spot_price: "666666666666666667".to_string(),
+            p0_arithmetic_twap_accumulator: p0_accumulator.to_string(),
+            p1_arithmetic_twap_accumulator: "0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_twap_record_decoding() {
+        let record = record(1_700_000_000, "3000000000000000000000");
+        let decoded = TwapRecord::decode(record.encode_to_vec().as_slice()).unwrap();
+
+        assert_eq!(decoded, record);
+        assert_eq!(decoded.get_time_ms().unwrap(), 1_700_000_000_000);
+    }
+
+    #[test]
+    fn test_arithmetic_twap() {
+        // spot price of 2.0 for 1s, followed by 1.0 for 3s
+        let start = record(100, "1000000000000000000000");
+        let end = record(104, "6000000000000000000000");
+
+        assert_eq!(
+            get_arithmetic_twap(&start, &end).unwrap(),
+            Decimal::from_str("1.25").unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "twap window must end after it starts")]
+    fn test_arithmetic_twap_validates_window() {
+        let start = record(100, "1000000000000000000000");
+        get_arithmetic_twap(&start, &start).unwrap();
+    }
+}
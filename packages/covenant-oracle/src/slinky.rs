@@ -0,0 +1,109 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    ensure, Binary, CustomQuery, Decimal, Int128, QuerierWrapper, QueryRequest, StdError,
+    StdResult, Uint128, Uint64,
+};
+use prost::Message;
+
+/// grpc path of the x/oracle price query
+pub const GET_PRICE_QUERY_PATH: &str = "/slinky.oracle.v1.Query/GetPrice";
+
+// manual definitions for the slinky x/oracle module
+#[derive(Clone, PartialEq, Message)]
+pub struct CurrencyPair {
+    #[prost(string, tag = "1")]
+    pub base: String,
+    #[prost(string, tag = "2")]
+    pub quote: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct GetPriceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub currency_pair: Option<CurrencyPair>,
+}
+
+#[cw_serde]
+pub struct QuotePrice {
+    /// price scaled up by 10^decimals
+    pub price: Int128,
+    pub block_timestamp: String,
+    pub block_height: Option<Uint64>,
+}
+
+#[cw_serde]
+pub struct GetPriceResponse {
+    pub price: QuotePrice,
+    pub nonce: Uint64,
+    pub decimals: Uint64,
+    pub id: Uint64,
+}
+
+impl GetPriceResponse {
+    /// converts the scaled oracle price into a decimal
+    pub fn to_decimal(&self) -> StdResult<Decimal> {
+        let price = self.price.price.i128();
+        ensure!(
+            price > 0,
+            StdError::generic_err(format!("invalid oracle price: {price}"))
+        );
+        Decimal::from_atomics(Uint128::new(price as u128), self.decimals.u64() as u32)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
+
+/// queries the x/oracle price of `base` denominated in `quote`
+pub fn query_price<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    base: &str,
+    quote: &str,
+) -> StdResult<Decimal> {
+    let request = GetPriceRequest {
+        currency_pair: Some(CurrencyPair {
+            base: base.to_string(),
+            quote: quote.to_string(),
+        }),
+    };
+    let response: GetPriceResponse = querier.query(&QueryRequest::Stargate {
+        path: GET_PRICE_QUERY_PATH.to_string(),
+        data: Binary(request.encode_to_vec()),
+    })?;
+
+    response.to_decimal()
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use cosmwasm_std::{Decimal, Int128, Uint64};
+
+    use super::{GetPriceResponse, QuotePrice};
+
+    fn get_price_response(price: i128, decimals: u64) -> GetPriceResponse {
+        GetPriceResponse {
+            price: QuotePrice {
+                price: Int128::new(price),
+                block_timestamp: "2024-01-01T00:00:00Z".to_string(),
+                block_height: Some(Uint64::new(100)),
+            },
+            nonce: Uint64::new(1),
+            decimals: Uint64::new(decimals),
+            id: Uint64::new(0),
+        }
+    }
+
+    #[test]
+    fn test_price_response_to_decimal() {
+        assert_eq!(
+            get_price_response(1_234_567, 6).to_decimal().unwrap(),
+            Decimal::from_str("1.234567").unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid oracle price: 0")]
+    fn test_price_response_validates_price() {
+        get_price_response(0, 6).to_decimal().unwrap();
+    }
+}
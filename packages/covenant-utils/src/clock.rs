@@ -1,5 +1,8 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Addr, StdError, StdResult, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, Addr, BlockInfo, StdError, StdResult, Storage, Uint64, WasmMsg,
+};
+use cw_storage_plus::{Item, Map};
 use neutron_sdk::NeutronError;
 use thiserror::Error;
 
@@ -15,6 +18,12 @@ impl From<ClockError> for NeutronError {
     }
 }
 
+/// number of ticks processed by the contract so far
+pub const TICK_NONCE: Item<u64> = Item::new("tick_nonce");
+
+/// block heights at which side effecting triggers were last processed
+pub const TRIGGER_HEIGHTS: Map<&str, u64> = Map::new("trigger_heights");
+
 #[cw_serde]
 enum ClockMsg {
     /// Enqueues the message sender for ticks (serialized as messages
@@ -27,6 +36,10 @@ enum ClockMsg {
     /// ticks. Only callable if the message sender is currently
     /// enqueued.
     Dequeue {},
+    /// Declares the minimum number of seconds between two ticks of
+    /// the message sender. Only callable if the message sender is
+    /// currently enqueued.
+    SetTickInterval { tick_interval: Uint64 },
 }
 
 pub fn enqueue_msg(addr: &str) -> StdResult<WasmMsg> {
@@ -45,6 +58,16 @@ pub fn dequeue_msg(addr: &str) -> StdResult<WasmMsg> {
     })
 }
 
+pub fn set_tick_interval_msg(addr: &str, tick_interval: u64) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: addr.to_string(),
+        msg: to_json_binary(&ClockMsg::SetTickInterval {
+            tick_interval: Uint64::new(tick_interval),
+        })?,
+        funds: vec![],
+    })
+}
+
 pub fn verify_clock(caller: &Addr, clock_addr: &Addr) -> Result<(), NeutronError> {
     if caller != clock_addr {
         return Err(ClockError::NotClock.into());
@@ -52,3 +75,58 @@ pub fn verify_clock(caller: &Addr, clock_addr: &Addr) -> Result<(), NeutronError
 
     Ok(())
 }
+
+/// increments the tick nonce of the contract and returns its new value.
+/// the nonce is emitted with every processed tick, which lets keepers
+/// tell whether a retried tick got processed already.
+pub fn increment_tick_nonce(storage: &mut dyn Storage) -> StdResult<u64> {
+    let tick_nonce = TICK_NONCE.may_load(storage)?.unwrap_or_default() + 1;
+    TICK_NONCE.save(storage, &tick_nonce)?;
+    Ok(tick_nonce)
+}
+
+/// records the trigger as processed in the current block. errors if it
+/// was already processed in this block, which makes duplicate external
+/// triggers (e.g. keeper retries) free of side effects.
+pub fn ensure_single_trigger_per_block(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    trigger: &str,
+) -> StdResult<()> {
+    if TRIGGER_HEIGHTS.may_load(storage, trigger)? == Some(block.height) {
+        return Err(StdError::generic_err(format!(
+            "{trigger} was already triggered at height {}",
+            block.height
+        )));
+    }
+    TRIGGER_HEIGHTS.save(storage, trigger, &block.height)
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::testing::{mock_env, MockStorage};
+
+    use super::{ensure_single_trigger_per_block, increment_tick_nonce};
+
+    #[test]
+    fn test_increment_tick_nonce() {
+        let mut storage = MockStorage::new();
+
+        assert_eq!(increment_tick_nonce(&mut storage).unwrap(), 1);
+        assert_eq!(increment_tick_nonce(&mut storage).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_ensure_single_trigger_per_block() {
+        let mut storage = MockStorage::new();
+        let mut block = mock_env().block;
+
+        ensure_single_trigger_per_block(&mut storage, &block, "claim").unwrap();
+        // distinct triggers are tracked separately
+        ensure_single_trigger_per_block(&mut storage, &block, "ica_submit").unwrap();
+        ensure_single_trigger_per_block(&mut storage, &block, "claim").unwrap_err();
+
+        block.height += 1;
+        ensure_single_trigger_per_block(&mut storage, &block, "claim").unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Api, Attribute, Coin, StdError, StdResult, WasmMsg};
+
+/// adapter converting a party contribution into the denom expected by
+/// the liquid pooler, e.g. a liquid staker or a vault share unwrapper.
+/// allows parties to contribute the derivative they actually hold.
+#[cw_serde]
+pub struct ContributionAdapter {
+    /// address of the conversion contract
+    pub address: String,
+    /// denom received from the adapter in exchange for the contribution
+    pub target_denom: String,
+}
+
+/// messages expected to be implemented by contribution adapters
+#[cw_serde]
+pub enum ContributionAdapterMsgs {
+    /// converts the attached funds into the target denom of the adapter
+    /// and sends the result to the receiver
+    Convert { receiver: String },
+}
+
+impl ContributionAdapter {
+    /// validates the adapter address and ensures that the adapter
+    /// actually converts the contribution into a different denom
+    pub fn validate(&self, api: &dyn Api, contribution_denom: &str) -> StdResult<()> {
+        api.addr_validate(&self.address)?;
+        if self.target_denom.is_empty() || self.target_denom == contribution_denom {
+            return Err(StdError::generic_err(format!(
+                "contribution adapter must convert {contribution_denom} into a different denom"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn generate_convert_msg(&self, funds: Vec<Coin>, receiver: String) -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: self.address.to_string(),
+            msg: to_json_binary(&ContributionAdapterMsgs::Convert { receiver })?,
+            funds,
+        })
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("contribution_adapter", self.address.to_string()),
+            Attribute::new(
+                "contribution_adapter_target_denom",
+                self.target_denom.to_string(),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coin, from_json, testing::MockApi, WasmMsg};
+
+    use super::{ContributionAdapter, ContributionAdapterMsgs};
+
+    #[test]
+    fn test_validate() {
+        let api = MockApi::default();
+        let adapter = ContributionAdapter {
+            address: api.addr_make("adapter").to_string(),
+            target_denom: "uatom".to_string(),
+        };
+
+        adapter.validate(&api, "ustatom").unwrap();
+        adapter.validate(&api, "uatom").unwrap_err();
+
+        ContributionAdapter {
+            address: "INVALID".to_string(),
+            ..adapter
+        }
+        .validate(&api, "ustatom")
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_generate_convert_msg() {
+        let adapter = ContributionAdapter {
+            address: "adapter".to_string(),
+            target_denom: "uatom".to_string(),
+        };
+
+        let WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        } = adapter
+            .generate_convert_msg(vec![coin(100, "ustatom")], "pooler".to_string())
+            .unwrap()
+        else {
+            panic!("expected execute msg");
+        };
+
+        assert_eq!(contract_addr, "adapter");
+        assert_eq!(funds, vec![coin(100, "ustatom")]);
+        assert_eq!(
+            from_json::<ContributionAdapterMsgs>(msg).unwrap(),
+            ContributionAdapterMsgs::Convert {
+                receiver: "pooler".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CustomQuery, QuerierWrapper, StdResult};
+
+/// upper bound of proposal modules fetched per DAO core query
+const PROPOSAL_MODULES_PAGE_LIMIT: u32 = 30;
+
+/// subset of the DAO DAO core query messages relevant to covenants
+#[cw_serde]
+pub enum DaoCoreQueryMsg {
+    /// paginated list of the proposal modules allowed to execute
+    /// messages on behalf of the DAO
+    ActiveProposalModules {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+/// subset of the DAO DAO proposal module query messages relevant to covenants
+#[cw_serde]
+pub enum DaoProposalModuleQueryMsg {
+    /// returns the address of the DAO core the module belongs to
+    Dao {},
+}
+
+#[cw_serde]
+pub enum ProposalModuleStatus {
+    Enabled,
+    Disabled,
+}
+
+/// proposal module as returned by the DAO DAO core
+#[cw_serde]
+pub struct ProposalModule {
+    pub address: Addr,
+    pub prefix: String,
+    pub status: ProposalModuleStatus,
+}
+
+/// returns true if `sender` is an enabled proposal module of the DAO DAO
+/// core at `dao_core`. proposal modules relay the messages of passed
+/// proposals, so they act on behalf of the core.
+/// the relation is verified both ways: the module has to point to the core,
+/// and the core has to list the module as active. addresses that are not
+/// DAO DAO contracts fail the queries and are treated as unrelated.
+pub fn is_dao_proposal_module<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    dao_core: &str,
+    sender: &str,
+) -> StdResult<bool> {
+    let module_dao: Addr =
+        match querier.query_wasm_smart(sender, &DaoProposalModuleQueryMsg::Dao {}) {
+            Ok(dao) => dao,
+            Err(_) => return Ok(false),
+        };
+    if module_dao != dao_core {
+        return Ok(false);
+    }
+
+    let mut start_after = None;
+    loop {
+        let modules: Vec<ProposalModule> = match querier.query_wasm_smart(
+            dao_core,
+            &DaoCoreQueryMsg::ActiveProposalModules {
+                start_after: start_after.clone(),
+                limit: Some(PROPOSAL_MODULES_PAGE_LIMIT),
+            },
+        ) {
+            Ok(modules) => modules,
+            Err(_) => return Ok(false),
+        };
+
+        if modules
+            .iter()
+            .any(|m| m.address == sender && m.status == ProposalModuleStatus::Enabled)
+        {
+            return Ok(true);
+        }
+
+        match modules.last() {
+            Some(last) if modules.len() as u32 == PROPOSAL_MODULES_PAGE_LIMIT => {
+                start_after = Some(last.address.to_string());
+            }
+            _ => return Ok(false),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use astroport::asset::{Asset, AssetInfo};
+use cosmwasm_schema::{cw_serde, serde::Deserialize};
+use cosmwasm_std::{Addr, Coin, Decimal, QuerierWrapper, StdResult, Uint128};
+
+use crate::astroport::query_astro_pool_token;
+
+/// subset of the liquid pooler query messages needed to locate its position
+#[cw_serde]
+pub enum LiquidPoolerQueryMsg {
+    LpConfig {},
+}
+
+/// subset of the liquid pooler lp config needed to locate its position.
+/// unknown fields are ignored, so that any pooler config can be parsed.
+#[derive(Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct LiquidPoolerLpConfig {
+    pool_address: Addr,
+    /// generator the LP tokens are auto-staked into, if any
+    #[serde(default)]
+    generator_address: Option<Addr>,
+}
+
+/// LP position held by a liquid pooler
+#[cw_serde]
+pub struct PoolPosition {
+    /// address of the pool the liquid pooler provides liquidity to
+    pub pool_address: String,
+    /// liquidity token of the pool
+    pub lp_token: String,
+    /// amount of liquidity tokens held by the liquid pooler,
+    /// including the ones it auto-staked into the generator
+    pub lp_balance: Uint128,
+    /// native denoms of the pool assets
+    #[serde(default)]
+    pub pool_denoms: Vec<String>,
+}
+
+impl PoolPosition {
+    /// estimates the underlying assets of the given share of the position
+    pub fn estimate_share(&self, querier: QuerierWrapper, share: Decimal) -> StdResult<Vec<Coin>> {
+        let amount = self.lp_balance.mul_floor(share);
+        if amount.is_zero() {
+            return Ok(vec![]);
+        }
+        let assets: Vec<Asset> = querier.query_wasm_smart(
+            &self.pool_address,
+            &astroport::pair::QueryMsg::Share { amount },
+        )?;
+
+        Ok(assets
+            .into_iter()
+            .map(|asset| Coin {
+                denom: asset.info.to_string(),
+                amount: asset.amount,
+            })
+            .collect())
+    }
+}
+
+/// queries the LP position of an astroport liquid pooler.
+/// returns `None` if the position can not be resolved (e.g. the pooler
+/// provides liquidity outside of astroport), so that dashboards built on
+/// top of it do not fail.
+pub fn query_pool_position(querier: QuerierWrapper, liquid_pooler: &Addr) -> Option<PoolPosition> {
+    let lp_config: LiquidPoolerLpConfig = querier
+        .query_wasm_smart(liquid_pooler, &LiquidPoolerQueryMsg::LpConfig {})
+        .ok()?;
+    let pool_token = query_astro_pool_token(
+        querier,
+        lp_config.pool_address.to_string(),
+        liquid_pooler.to_string(),
+    )
+    .ok()?;
+
+    let staked_lp_balance = match lp_config.generator_address {
+        Some(generator) => querier
+            .query_wasm_smart::<Uint128>(
+                generator,
+                &astroport::generator::QueryMsg::Deposit {
+                    lp_token: pool_token.pair_info.liquidity_token.to_string(),
+                    user: liquid_pooler.to_string(),
+                },
+            )
+            .ok()?,
+        None => Uint128::zero(),
+    };
+
+    let pool_denoms = pool_token
+        .pair_info
+        .asset_infos
+        .into_iter()
+        .filter_map(|asset_info| match asset_info {
+            AssetInfo::NativeToken { denom } => Some(denom),
+            AssetInfo::Token { .. } => None,
+        })
+        .collect();
+
+    Some(PoolPosition {
+        pool_address: lp_config.pool_address.to_string(),
+        lp_token: pool_token.pair_info.liquidity_token.to_string(),
+        lp_balance: pool_token
+            .balance_response
+            .balance
+            .checked_add(staked_lp_balance)
+            .ok()?,
+        pool_denoms,
+    })
+}
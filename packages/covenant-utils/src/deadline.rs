@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::BlockInfo;
+use cosmwasm_std::{BlockInfo, StdError, StdResult};
 use cw_utils::{Duration, Expiration};
 
 #[cw_serde]
@@ -15,6 +15,12 @@ impl Default for Deadline {
     }
 }
 
+impl From<Expiration> for Deadline {
+    fn from(expiration: Expiration) -> Self {
+        Deadline::Expiration(expiration)
+    }
+}
+
 impl Deadline {
     pub fn into_expiration(self, block: &BlockInfo) -> Expiration {
         match self {
@@ -22,12 +28,87 @@ impl Deadline {
             Deadline::Duration(duration) => duration.after(block),
         }
     }
+
+    /// durations are relative deadlines that have not started counting
+    /// down yet, so they resolve against the given block
+    fn to_expiration(&self, block: &BlockInfo) -> Expiration {
+        self.clone().into_expiration(block)
+    }
+
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.to_expiration(block).is_expired(block)
+    }
+
+    /// validates that the deadline is not yet expired at the given block.
+    /// this rejects zero durations, as they expire right away.
+    pub fn validate(&self, block: &BlockInfo) -> StdResult<()> {
+        if self.is_expired(block) {
+            return Err(StdError::generic_err("deadline must be in the future"));
+        }
+        Ok(())
+    }
+
+    /// blocks left until a height based deadline expires.
+    /// `None` for time based and never expiring deadlines.
+    pub fn remaining_blocks(&self, block: &BlockInfo) -> Option<u64> {
+        match self.to_expiration(block) {
+            Expiration::AtHeight(height) => Some(height.saturating_sub(block.height)),
+            Expiration::AtTime(_) | Expiration::Never {} => None,
+        }
+    }
+
+    /// seconds left until a time based deadline expires.
+    /// `None` for height based and never expiring deadlines.
+    pub fn remaining_seconds(&self, block: &BlockInfo) -> Option<u64> {
+        match self.to_expiration(block) {
+            Expiration::AtTime(time) => Some(time.seconds().saturating_sub(block.time.seconds())),
+            Expiration::AtHeight(_) | Expiration::Never {} => None,
+        }
+    }
+
+    /// returns true if the deadline expires within the given window
+    /// (or has already expired). windows measured in a different unit
+    /// than the deadline are never near.
+    pub fn is_near(&self, block: &BlockInfo, window: Duration) -> bool {
+        if self.is_expired(block) {
+            return true;
+        }
+        match window {
+            Duration::Height(blocks) => self
+                .remaining_blocks(block)
+                .is_some_and(|remaining| remaining <= blocks),
+            Duration::Time(seconds) => self
+                .remaining_seconds(block)
+                .is_some_and(|remaining| remaining <= seconds),
+        }
+    }
+
+    pub fn time_to_expiry(&self, block: &BlockInfo) -> TimeToExpiry {
+        TimeToExpiry {
+            expiration: self.to_expiration(block),
+            expired: self.is_expired(block),
+            remaining_blocks: self.remaining_blocks(block),
+            remaining_seconds: self.remaining_seconds(block),
+        }
+    }
+}
+
+/// snapshot of a deadline relative to the current block
+#[cw_serde]
+pub struct TimeToExpiry {
+    pub expiration: Expiration,
+    pub expired: bool,
+    /// blocks left until expiry, if the deadline is height based
+    pub remaining_blocks: Option<u64>,
+    /// seconds left until expiry, if the deadline is time based
+    pub remaining_seconds: Option<u64>,
 }
 
 #[cfg(test)]
 mod test {
     use cosmwasm_schema::cw_serde;
-    use cosmwasm_std::from_json;
+    use cosmwasm_std::{from_json, testing::mock_env, Timestamp};
+    use cw_utils::{Duration, Expiration};
 
     use super::Deadline;
 
@@ -41,4 +122,90 @@ mod test {
         let json_string = "{\"expires\": {\"never\": {}}}";
         println!("{:?}", from_json::<Example>(&json_string).unwrap());
     }
+
+    #[test]
+    fn test_validate() {
+        let block = mock_env().block;
+
+        Deadline::Duration(Duration::Height(1))
+            .validate(&block)
+            .unwrap();
+        Deadline::from(Expiration::Never {})
+            .validate(&block)
+            .unwrap();
+        Deadline::from(Expiration::AtHeight(block.height + 1))
+            .validate(&block)
+            .unwrap();
+        Deadline::Duration(Duration::Time(0))
+            .validate(&block)
+            .unwrap_err();
+        Deadline::from(Expiration::AtHeight(block.height))
+            .validate(&block)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_remaining_blocks() {
+        let block = mock_env().block;
+        let deadline = Deadline::from(Expiration::AtHeight(block.height + 10));
+
+        assert_eq!(deadline.remaining_blocks(&block), Some(10));
+        assert_eq!(deadline.remaining_seconds(&block), None);
+        assert_eq!(
+            Deadline::Duration(Duration::Height(5)).remaining_blocks(&block),
+            Some(5)
+        );
+        assert_eq!(
+            Deadline::from(Expiration::AtHeight(block.height - 1)).remaining_blocks(&block),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_remaining_seconds() {
+        let block = mock_env().block;
+        let deadline = Deadline::from(Expiration::AtTime(block.time.plus_seconds(60)));
+
+        assert_eq!(deadline.remaining_seconds(&block), Some(60));
+        assert_eq!(deadline.remaining_blocks(&block), None);
+        assert_eq!(
+            Deadline::from(Expiration::AtTime(Timestamp::from_seconds(0)))
+                .remaining_seconds(&block),
+            Some(0)
+        );
+        assert_eq!(
+            Deadline::from(Expiration::Never {}).remaining_seconds(&block),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_near() {
+        let block = mock_env().block;
+        let deadline = Deadline::from(Expiration::AtHeight(block.height + 10));
+
+        assert!(deadline.is_near(&block, Duration::Height(10)));
+        assert!(!deadline.is_near(&block, Duration::Height(9)));
+        // mismatched units are never near
+        assert!(!deadline.is_near(&block, Duration::Time(1_000)));
+        // expired deadlines are always near
+        assert!(
+            Deadline::from(Expiration::AtHeight(block.height)).is_near(&block, Duration::Time(0))
+        );
+        assert!(!Deadline::from(Expiration::Never {}).is_near(&block, Duration::Height(100)));
+    }
+
+    #[test]
+    fn test_time_to_expiry() {
+        let block = mock_env().block;
+        let time_to_expiry = Deadline::Duration(Duration::Time(30)).time_to_expiry(&block);
+
+        assert_eq!(
+            time_to_expiry.expiration,
+            Expiration::AtTime(block.time.plus_seconds(30))
+        );
+        assert!(!time_to_expiry.expired);
+        assert_eq!(time_to_expiry.remaining_seconds, Some(30));
+        assert_eq!(time_to_expiry.remaining_blocks, None);
+    }
 }
@@ -0,0 +1,52 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, CustomQuery, Env, QuerierWrapper, StdError, StdResult, WasmMsg,
+};
+use covenant_macros::covenant_decommission;
+use neutron_sdk::NeutronError;
+use thiserror::Error;
+
+#[covenant_decommission]
+#[cw_serde]
+pub enum DecommissionMsgs {}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DecommissionError {
+    #[error("only the covenant can decommission the contract")]
+    Unauthorized,
+
+    #[error("contract is decommissioned")]
+    Decommissioned,
+}
+
+impl From<DecommissionError> for NeutronError {
+    fn from(val: DecommissionError) -> Self {
+        NeutronError::Std(StdError::generic_err(val.to_string()))
+    }
+}
+
+/// errors unless the sender is the contract admin,
+/// which is the covenant that instantiated it
+pub fn verify_decommission_authority<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    env: &Env,
+    sender: &Addr,
+) -> Result<(), DecommissionError> {
+    let admin = querier
+        .query_wasm_contract_info(env.contract.address.to_string())
+        .map_err(|_| DecommissionError::Unauthorized)?
+        .admin;
+    if admin.as_deref() == Some(sender.as_str()) {
+        Ok(())
+    } else {
+        Err(DecommissionError::Unauthorized)
+    }
+}
+
+pub fn generate_decommission_msg(contract: String) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: contract,
+        msg: to_json_binary(&DecommissionMsgs::Decommission {})?,
+        funds: vec![],
+    })
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::{CustomQuery, QuerierWrapper, StdError, StdResult};
+
+/// ensures that the denom is known to the bank module of this chain.
+/// denoms with no supply are treated as non-existent, which catches typos
+/// in ibc denom hashes that would otherwise never receive any deposits.
+/// ibc denoms that are yet to be bridged have no supply either, so
+/// contracts only run this check if configured to.
+pub fn ensure_denom_exists<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    denom: &str,
+) -> StdResult<()> {
+    let supply = querier.query_supply(denom)?;
+    if supply.amount.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "denom {denom} does not exist on this chain"
+        )));
+    }
+    Ok(())
+}
+
+/// ensures that every denom in the list is known to the bank module
+pub fn ensure_denoms_exist<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    denoms: Vec<&str>,
+) -> StdResult<()> {
+    denoms
+        .into_iter()
+        .try_for_each(|denom| ensure_denom_exists(querier, denom))
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeSet;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    ensure, to_json_binary, Addr, Coin, Order, StdError, StdResult, Storage, WasmMsg,
+};
+use covenant_macros::{
+    covenant_holder_distribution_receipt, covenant_router_register_distribution,
+};
+use cw_storage_plus::Map;
+
+/// distributions registered with a router that are yet to be
+/// acknowledged, keyed by (holder address, distribution id)
+pub const PENDING_DISTRIBUTIONS: Map<(Addr, u64), Vec<Coin>> = Map::new("pending_distributions");
+
+/// distributions whose funds were transferred out by a router over ibc,
+/// awaiting the acknowledgement of the transfers before being acknowledged
+pub const ROUTED_DISTRIBUTIONS: Map<(Addr, u64), Vec<Coin>> = Map::new("routed_distributions");
+
+/// upper bound of acknowledgements a router sends out per tick
+pub const MAX_ACKNOWLEDGEMENTS_PER_TICK: usize = 10;
+
+/// reply id routers use to dispatch acknowledgements. acknowledgement
+/// errors are ignored so that they can never block the routing.
+pub const ACKNOWLEDGE_DISTRIBUTION_REPLY_ID: u64 = 1u64;
+
+#[covenant_holder_distribution_receipt]
+#[covenant_router_register_distribution]
+#[cw_serde]
+pub enum DistributionReceiptMsgs {}
+
+/// delivery status of funds pushed from a holder to a router
+#[cw_serde]
+pub enum DeliveryStatus {
+    /// funds were sent to the router, awaiting its confirmation
+    Pending,
+    /// router confirmed sending the funds out
+    Delivered,
+    /// router reported that it is unable to deliver the funds
+    Failed { reason: String },
+}
+
+/// receipt of funds pushed from a holder to a single router
+#[cw_serde]
+pub struct DistributionReceipt {
+    pub router: Addr,
+    pub coins: Vec<Coin>,
+    pub status: DeliveryStatus,
+}
+
+/// distribution registered with a router, awaiting acknowledgement
+#[cw_serde]
+pub struct PendingDistribution {
+    pub holder: Addr,
+    pub id: u64,
+    pub coins: Vec<Coin>,
+}
+
+pub fn generate_register_distribution_msg(
+    router: String,
+    id: u64,
+    coins: Vec<Coin>,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: router,
+        msg: to_json_binary(&DistributionReceiptMsgs::RegisterDistribution { id, coins })?,
+        funds: vec![],
+    })
+}
+
+pub fn generate_acknowledge_distribution_msg(holder: String, id: u64) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: holder,
+        msg: to_json_binary(&DistributionReceiptMsgs::AcknowledgeDistribution { id })?,
+        funds: vec![],
+    })
+}
+
+pub fn generate_distribution_failure_msg(
+    holder: String,
+    id: u64,
+    reason: String,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: holder,
+        msg: to_json_binary(&DistributionReceiptMsgs::ReportDistributionFailure { id, reason })?,
+        funds: vec![],
+    })
+}
+
+/// registers the distribution with the router so that it gets acknowledged
+/// along with the next routing. if the router does not route any of the
+/// distributed denoms, the failure message to the holder is returned instead.
+pub fn register_distribution(
+    storage: &mut dyn Storage,
+    holder: Addr,
+    id: u64,
+    coins: Vec<Coin>,
+    target_denoms: &BTreeSet<String>,
+) -> StdResult<Option<WasmMsg>> {
+    if let Some(coin) = coins.iter().find(|c| !target_denoms.contains(&c.denom)) {
+        return Ok(Some(generate_distribution_failure_msg(
+            holder.to_string(),
+            id,
+            format!("denom {} is not routed", coin.denom),
+        )?));
+    }
+
+    PENDING_DISTRIBUTIONS.save(storage, (holder, id), &coins)?;
+    Ok(None)
+}
+
+/// errors unless the sender is the holder distributing to the router
+pub fn verify_distribution_holder(holder: Option<Addr>, sender: &Addr) -> StdResult<()> {
+    ensure!(
+        holder.as_ref() == Some(sender),
+        StdError::generic_err("only the holder can register distributions")
+    );
+    Ok(())
+}
+
+/// removes up to `MAX_ACKNOWLEDGEMENTS_PER_TICK` pending distributions
+/// and returns the acknowledgement messages for their holders
+pub fn take_distribution_acknowledgements(storage: &mut dyn Storage) -> StdResult<Vec<WasmMsg>> {
+    take_acknowledgements(storage, PENDING_DISTRIBUTIONS)
+}
+
+/// moves up to `MAX_ACKNOWLEDGEMENTS_PER_TICK` pending distributions to the
+/// routed ones, to be acknowledged once the transfers carrying their funds are
+pub fn mark_distributions_routed(storage: &mut dyn Storage) -> StdResult<()> {
+    let pending: Vec<((Addr, u64), Vec<Coin>)> = PENDING_DISTRIBUTIONS
+        .range(storage, None, None, Order::Ascending)
+        .take(MAX_ACKNOWLEDGEMENTS_PER_TICK)
+        .collect::<StdResult<_>>()?;
+
+    for (key, coins) in pending {
+        PENDING_DISTRIBUTIONS.remove(storage, key.clone());
+        ROUTED_DISTRIBUTIONS.save(storage, key, &coins)?;
+    }
+    Ok(())
+}
+
+/// removes up to `MAX_ACKNOWLEDGEMENTS_PER_TICK` routed distributions
+/// and returns the acknowledgement messages for their holders
+pub fn take_routed_distribution_acknowledgements(
+    storage: &mut dyn Storage,
+) -> StdResult<Vec<WasmMsg>> {
+    take_acknowledgements(storage, ROUTED_DISTRIBUTIONS)
+}
+
+fn take_acknowledgements(
+    storage: &mut dyn Storage,
+    distributions: Map<(Addr, u64), Vec<Coin>>,
+) -> StdResult<Vec<WasmMsg>> {
+    let keys: Vec<(Addr, u64)> = distributions
+        .keys(storage, None, None, Order::Ascending)
+        .take(MAX_ACKNOWLEDGEMENTS_PER_TICK)
+        .collect::<StdResult<_>>()?;
+
+    keys.into_iter()
+        .map(|(holder, id)| {
+            distributions.remove(storage, (holder.clone(), id));
+            generate_acknowledge_distribution_msg(holder.to_string(), id)
+        })
+        .collect()
+}
+
+/// removes all pending distributions and returns the failure
+/// reports for their holders
+pub fn take_distribution_failures(
+    storage: &mut dyn Storage,
+    reason: &str,
+) -> StdResult<Vec<WasmMsg>> {
+    let pending: Vec<(Addr, u64)> = PENDING_DISTRIBUTIONS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    pending
+        .into_iter()
+        .map(|(holder, id)| {
+            PENDING_DISTRIBUTIONS.remove(storage, (holder.clone(), id));
+            generate_distribution_failure_msg(holder.to_string(), id, reason.to_string())
+        })
+        .collect()
+}
+
+/// returns all distributions pending acknowledgement, including
+/// the routed ones awaiting the acknowledgement of their transfers
+pub fn query_pending_distributions(storage: &dyn Storage) -> StdResult<Vec<PendingDistribution>> {
+    PENDING_DISTRIBUTIONS
+        .range(storage, None, None, Order::Ascending)
+        .chain(ROUTED_DISTRIBUTIONS.range(storage, None, None, Order::Ascending))
+        .map(|entry| entry.map(|((holder, id), coins)| PendingDistribution { holder, id, coins }))
+        .collect()
+}
@@ -0,0 +1,101 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Event, StdResult, Uint128, WasmMsg};
+
+/// milestones an ibc forwarder reaches on its way to depositing
+/// the party contribution
+#[cw_serde]
+pub enum ForwarderMilestone {
+    /// the forwarder ICA was registered on the remote chain
+    IcaRegistered { address: String, channel: String },
+    /// the first transfer out of the forwarder ICA was acknowledged
+    FirstForward { amount: Uint128 },
+}
+
+impl ForwarderMilestone {
+    pub fn to_event(&self) -> Event {
+        match self {
+            ForwarderMilestone::IcaRegistered { address, channel } => Event::new("ica_registered")
+                .add_attribute("address", address)
+                .add_attribute("channel", channel),
+            ForwarderMilestone::FirstForward { amount } => {
+                Event::new("first_forward").add_attribute("amount", amount.to_string())
+            }
+        }
+    }
+}
+
+/// milestones recorded for a forwarder so far
+#[cw_serde]
+#[derive(Default)]
+pub struct ForwarderMilestones {
+    /// address of the registered forwarder ICA
+    pub ica_address: Option<String>,
+    /// channel the forwarder ICA was registered on
+    pub ica_channel: Option<String>,
+    /// amount forwarded by the first acknowledged transfer
+    pub first_forward: Option<Uint128>,
+}
+
+impl ForwarderMilestones {
+    pub fn record(&mut self, milestone: ForwarderMilestone) {
+        match milestone {
+            ForwarderMilestone::IcaRegistered { address, channel } => {
+                self.ica_address = Some(address);
+                self.ica_channel = Some(channel);
+            }
+            ForwarderMilestone::FirstForward { amount } => {
+                self.first_forward.get_or_insert(amount);
+            }
+        }
+    }
+}
+
+/// execute message a forwarder sends to its milestone callback
+#[cw_serde]
+pub enum ForwarderMilestoneMsgs {
+    ForwarderMilestone { milestone: ForwarderMilestone },
+}
+
+pub fn generate_milestone_callback_msg(
+    callback: String,
+    milestone: ForwarderMilestone,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: callback,
+        msg: to_json_binary(&ForwarderMilestoneMsgs::ForwarderMilestone { milestone })?,
+        funds: vec![],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::Uint128;
+
+    use super::{ForwarderMilestone, ForwarderMilestones};
+
+    #[test]
+    fn test_record_milestones() {
+        let mut milestones = ForwarderMilestones::default();
+
+        milestones.record(ForwarderMilestone::IcaRegistered {
+            address: "ica".to_string(),
+            channel: "channel-1".to_string(),
+        });
+        milestones.record(ForwarderMilestone::FirstForward {
+            amount: Uint128::new(100),
+        });
+        // only the first forward is recorded
+        milestones.record(ForwarderMilestone::FirstForward {
+            amount: Uint128::new(200),
+        });
+
+        assert_eq!(
+            milestones,
+            ForwarderMilestones {
+                ica_address: Some("ica".to_string()),
+                ica_channel: Some("channel-1".to_string()),
+                first_forward: Some(Uint128::new(100)),
+            }
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_string, Binary, Coin, StdError, StdResult};
+
+/// placeholders resolved by the covenant when instantiating the router
+pub const LABEL_PLACEHOLDER: &str = "{label}";
+pub const COVENANT_PLACEHOLDER: &str = "{covenant}";
+pub const PARTY_PLACEHOLDER: &str = "{party}";
+
+/// placeholders resolved by the router on every distribution
+pub const DENOM_PLACEHOLDER: &str = "{denom}";
+pub const AMOUNT_PLACEHOLDER: &str = "{amount}";
+
+/// upper bound on the length of a memo template, keeping the
+/// ibc packets of every distribution reasonably small
+pub const MAX_IBC_MEMO_TEMPLATE_LENGTH: usize = 256;
+
+pub fn validate_memo_template(template: &str) -> StdResult<()> {
+    if template.is_empty() || template.len() > MAX_IBC_MEMO_TEMPLATE_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "ibc memo template must be between 1 and {MAX_IBC_MEMO_TEMPLATE_LENGTH} characters long"
+        )));
+    }
+    Ok(())
+}
+
+/// resolves the covenant specific placeholders of the template, leaving
+/// the distribution specific ones to be resolved by the router
+pub fn resolve_covenant_placeholders(
+    template: &str,
+    label: &str,
+    covenant: &str,
+    party: &str,
+) -> String {
+    template
+        .replace(LABEL_PLACEHOLDER, label)
+        .replace(COVENANT_PLACEHOLDER, covenant)
+        .replace(PARTY_PLACEHOLDER, party)
+}
+
+/// resolves the distribution specific placeholders of the template.
+/// routers without a template keep the legacy memo format.
+pub fn format_distribution_memo(template: Option<&str>, coin: &Coin) -> String {
+    match template {
+        Some(template) => template
+            .replace(DENOM_PLACEHOLDER, &coin.denom)
+            .replace(AMOUNT_PLACEHOLDER, &coin.amount.to_string()),
+        None => format!("ibc_distribution: {:?}:{:?}", coin.denom, coin.amount),
+    }
+}
+
+/// ibc-hooks config, executing the contract with the msg on the
+/// destination chain along with the transferred funds
+#[cw_serde]
+pub struct WasmHookConfig {
+    /// contract to execute. ibc-hooks require it to be the transfer receiver.
+    pub contract: String,
+    /// json object to execute the contract with
+    pub msg: Binary,
+}
+
+impl WasmHookConfig {
+    pub fn validate(&self, receiver: &str) -> StdResult<()> {
+        if self.contract != receiver {
+            return Err(StdError::generic_err(
+                "wasm hook contract must be the destination receiver",
+            ));
+        }
+        let msg = self.get_msg_str()?;
+        if !msg.starts_with('{') || !msg.ends_with('}') {
+            return Err(StdError::generic_err("wasm hook msg must be a json object"));
+        }
+        Ok(())
+    }
+
+    /// formats the ibc-hooks `{"wasm":{"contract":..,"msg":..}}` memo
+    pub fn to_memo(&self) -> StdResult<String> {
+        Ok(format!(
+            r#"{{"wasm":{{"contract":{},"msg":{}}}}}"#,
+            to_json_string(&self.contract)?,
+            self.get_msg_str()?
+        ))
+    }
+
+    fn get_msg_str(&self) -> StdResult<&str> {
+        std::str::from_utf8(self.msg.as_slice())
+            .map(str::trim)
+            .map_err(|e| StdError::generic_err(format!("invalid wasm hook msg: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coin, Binary, Uint128};
+
+    use super::{
+        format_distribution_memo, resolve_covenant_placeholders, validate_memo_template,
+        WasmHookConfig, MAX_IBC_MEMO_TEMPLATE_LENGTH,
+    };
+
+    #[test]
+    fn test_validate_memo_template() {
+        validate_memo_template("{party}: {amount}{denom}").unwrap();
+        validate_memo_template("").unwrap_err();
+        validate_memo_template(&"a".repeat(MAX_IBC_MEMO_TEMPLATE_LENGTH + 1)).unwrap_err();
+    }
+
+    #[test]
+    fn test_format_distribution_memo() {
+        let template = resolve_covenant_placeholders(
+            "{label}/{covenant}/{party}: {amount}{denom}",
+            "covenant_label",
+            "covenant_addr",
+            "party_a",
+        );
+        assert_eq!(
+            template,
+            "covenant_label/covenant_addr/party_a: {amount}{denom}"
+        );
+
+        assert_eq!(
+            format_distribution_memo(Some(&template), &coin(100, "uatom")),
+            "covenant_label/covenant_addr/party_a: 100uatom"
+        );
+        assert_eq!(
+            format_distribution_memo(None, &coin(100, "uatom")),
+            format!("ibc_distribution: {:?}:{:?}", "uatom", Uint128::new(100))
+        );
+    }
+
+    #[test]
+    fn test_wasm_hook_memo() {
+        let wasm_hook = WasmHookConfig {
+            contract: "vault".to_string(),
+            msg: Binary::from(br#" {"deposit":{}} "#),
+        };
+        wasm_hook.validate("vault").unwrap();
+        wasm_hook.validate("receiver").unwrap_err();
+        assert_eq!(
+            wasm_hook.to_memo().unwrap(),
+            r#"{"wasm":{"contract":"vault","msg":{"deposit":{}}}}"#
+        );
+
+        WasmHookConfig {
+            contract: "vault".to_string(),
+            msg: Binary::from(br#""deposit""#),
+        }
+        .validate("vault")
+        .unwrap_err();
+    }
+}
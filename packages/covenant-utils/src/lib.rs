@@ -2,25 +2,37 @@ use std::collections::BTreeMap;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_json_string, Addr, Api, Attribute, Coin, CosmosMsg, Decimal, StdError, StdResult, Timestamp,
-    Uint128, Uint64,
-};
-use neutron::flatten_ibc_fee_total_amount;
-use neutron_sdk::{
-    bindings::msg::{IbcFee, NeutronMsg},
-    sudo::msg::RequestPacketTimeoutHeight,
+    to_json_string, Addr, Api, Attribute, BankMsg, Binary, Coin, CosmosMsg, Decimal, StdError,
+    StdResult, Timestamp, Uint128, Uint64, WasmMsg,
 };
+use ibc_memo::{format_distribution_memo, WasmHookConfig};
+use neutron::MinIbcFeeConfig;
+use neutron_sdk::{bindings::msg::NeutronMsg, sudo::msg::RequestPacketTimeoutHeight};
 
 pub mod astroport;
 pub mod clock;
+pub mod contribution_adapter;
+pub mod dao;
+pub mod dashboard;
 pub mod deadline;
-pub mod ica;
+pub mod decommission;
+pub mod denom;
+pub mod distribution_receipt;
+pub mod forwarder_milestone;
+pub mod ibc_memo;
 pub mod instantiate2_helper;
 pub mod liquid_pooler_withdraw;
+pub mod mock_ibc;
+pub mod multi_asset;
 pub mod neutron;
 pub mod op_mode;
+pub mod plan;
 pub mod polytone;
+pub mod pool_ownership;
+pub mod router_dust;
+pub mod router_halt;
 pub mod split;
+pub mod split_receiver_fix;
 pub mod withdraw_lp_helper;
 
 #[cw_serde]
@@ -59,6 +71,22 @@ pub struct NativeCovenantParty {
     pub addr: String,
     /// coin provided by the party on its native chain
     pub contribution: Coin,
+    /// msg to execute the receiver with when delivering the funds,
+    /// for receivers that are contracts (e.g. vaults)
+    #[serde(default)]
+    pub receiver_msg: Option<Binary>,
+}
+
+impl NativeCovenantParty {
+    pub fn to_receiver_config(&self) -> ReceiverConfig {
+        match &self.receiver_msg {
+            Some(msg) => ReceiverConfig::Contract {
+                addr: self.party_receiver_addr.to_string(),
+                msg: msg.clone(),
+            },
+            None => ReceiverConfig::Native(self.party_receiver_addr.to_string()),
+        }
+    }
 }
 
 #[cw_serde]
@@ -67,6 +95,9 @@ pub enum ReceiverConfig {
     Native(String),
     /// party expects to receive funds on a remote chain
     Ibc(DestinationConfig),
+    /// party expects the funds to be delivered to a contract on the
+    /// same chain, executing it with `msg`
+    Contract { addr: String, msg: Binary },
 }
 
 impl ReceiverConfig {
@@ -75,6 +106,10 @@ impl ReceiverConfig {
             ReceiverConfig::Native(addr) => {
                 vec![Attribute::new("receiver_config_native_addr", addr)]
             }
+            ReceiverConfig::Contract { addr, msg } => vec![
+                Attribute::new("receiver_config_contract_addr", addr),
+                Attribute::new("receiver_config_contract_msg", msg.to_base64()),
+            ],
             ReceiverConfig::Ibc(destination_config) => destination_config
                 .get_response_attributes()
                 .into_iter()
@@ -87,6 +122,31 @@ impl ReceiverConfig {
     }
 }
 
+/// delivers the coins to a receiver on the local chain with a single msg.
+/// receivers configured with an execute msg get executed with the coins
+/// attached, others receive a plain bank send.
+pub fn get_local_delivery_msg<T>(
+    receiver: &str,
+    receiver_msg: Option<Binary>,
+    mut coins: Vec<Coin>,
+) -> CosmosMsg<T> {
+    match receiver_msg {
+        Some(msg) => {
+            // attached funds have to be sorted by denom
+            coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: receiver.to_string(),
+                msg,
+                funds: coins,
+            })
+        }
+        None => CosmosMsg::Bank(BankMsg::Send {
+            to_address: receiver.to_string(),
+            amount: coins,
+        }),
+    }
+}
+
 #[cw_serde]
 pub struct CovenantParty {
     /// authorized address of the party
@@ -101,6 +161,7 @@ impl CovenantParty {
     pub fn validate_receiver_address(&self, api: &dyn Api) -> StdResult<Addr> {
         match &self.receiver_config {
             ReceiverConfig::Native(addr) => api.addr_validate(addr),
+            ReceiverConfig::Contract { addr, .. } => api.addr_validate(addr),
             ReceiverConfig::Ibc(destination_config) => {
                 match soft_validate_remote_chain_addr(
                     api,
@@ -204,6 +265,14 @@ pub struct DestinationConfig {
     pub ibc_transfer_timeout: Uint64,
     /// pfm configurations for denoms
     pub denom_to_pfm_map: BTreeMap<String, PacketForwardMiddlewareConfig>,
+    /// template of the memo attached to direct ibc transfers.
+    /// `{denom}` and `{amount}` are replaced with the transferred coin.
+    #[serde(default)]
+    pub memo_template: Option<String>,
+    /// ibc-hooks config of direct ibc transfers. if set, the memo
+    /// executes the hook contract instead of the plain text memo.
+    #[serde(default)]
+    pub wasm_hook: Option<WasmHookConfig>,
 }
 
 #[cw_serde]
@@ -211,6 +280,48 @@ pub struct PacketForwardMiddlewareConfig {
     pub local_to_hop_chain_channel_id: String,
     pub hop_to_destination_chain_channel_id: String,
     pub hop_chain_receiver_address: String,
+    /// further chains to forward through after the first hop chain, in order.
+    /// the last of them forwards to the destination chain over
+    /// `hop_to_destination_chain_channel_id`.
+    #[serde(default)]
+    pub next_hops: Vec<HopConfig>,
+}
+
+/// intermediate chain on a multi-hop pfm path
+#[cw_serde]
+pub struct HopConfig {
+    /// channel id from the previous hop chain to this one
+    pub channel_id: String,
+    /// address of the receiver on this hop chain
+    pub receiver_address: String,
+}
+
+impl PacketForwardMiddlewareConfig {
+    /// builds the pfm metadata forwarding a transfer received on the first
+    /// hop chain through the next hops, and on to the destination receiver
+    pub fn get_packet_metadata(&self, destination_receiver_addr: &str) -> PacketMetadata {
+        let mut metadata = PacketMetadata {
+            forward: Some(ForwardMetadata {
+                receiver: destination_receiver_addr.to_string(),
+                port: "transfer".to_string(),
+                // last hop chain to final receiver chain channel
+                channel: self.hop_to_destination_chain_channel_id.to_string(),
+                next: None,
+            }),
+        };
+        // nest the metadata from the last hop outwards
+        for hop in self.next_hops.iter().rev() {
+            metadata = PacketMetadata {
+                forward: Some(ForwardMetadata {
+                    receiver: hop.receiver_address.to_string(),
+                    port: "transfer".to_string(),
+                    channel: hop.channel_id.to_string(),
+                    next: Some(Box::new(metadata)),
+                }),
+            };
+        }
+        metadata
+    }
 }
 
 pub fn get_default_ica_fee() -> Coin {
@@ -231,22 +342,44 @@ pub struct ForwardMetadata {
     pub receiver: String,
     pub port: String,
     pub channel: String,
+    /// metadata to be forwarded on by the next chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Box<PacketMetadata>>,
 }
 
 impl DestinationConfig {
+    pub fn validate_memo_template(&self) -> StdResult<()> {
+        match &self.memo_template {
+            Some(template) => ibc_memo::validate_memo_template(template),
+            None => Ok(()),
+        }
+    }
+
+    pub fn validate_wasm_hook(&self) -> StdResult<()> {
+        match &self.wasm_hook {
+            Some(wasm_hook) => wasm_hook.validate(&self.destination_receiver_addr),
+            None => Ok(()),
+        }
+    }
+
     pub fn get_ibc_transfer_messages_for_coins(
         &self,
         coins: Vec<Coin>,
         current_timestamp: Timestamp,
         sender_address: String,
-        ibc_fee: IbcFee,
+        min_ibc_fee: &MinIbcFeeConfig,
     ) -> StdResult<Vec<CosmosMsg<NeutronMsg>>> {
         let mut messages: Vec<CosmosMsg<NeutronMsg>> = vec![];
         // we get the number of target denoms we have to reserve
         // neutron fees for
         let count = Uint128::from(1 + coins.len() as u128);
 
-        let total_fee = flatten_ibc_fee_total_amount(&ibc_fee);
+        let total_fee = min_ibc_fee.total_ntrn_fee;
+        let wasm_hook_memo = self
+            .wasm_hook
+            .as_ref()
+            .map(WasmHookConfig::to_memo)
+            .transpose()?;
 
         for coin in coins {
             let send_coin = if coin.denom != "untrn" {
@@ -267,6 +400,16 @@ impl DestinationConfig {
             };
 
             if let Some(c) = send_coin {
+                // without relayers, the coins are delivered to the
+                // receiver on the local chain
+                if mock_ibc::MOCK_IBC {
+                    messages.push(CosmosMsg::Bank(BankMsg::Send {
+                        to_address: self.destination_receiver_addr.to_string(),
+                        amount: vec![c],
+                    }));
+                    continue;
+                }
+
                 match self.denom_to_pfm_map.get(&c.denom) {
                     Some(pfm_config) => {
                         messages.push(CosmosMsg::Custom(NeutronMsg::IbcTransfer {
@@ -283,17 +426,10 @@ impl DestinationConfig {
                             timeout_timestamp: current_timestamp
                                 .plus_seconds(self.ibc_transfer_timeout.u64())
                                 .nanos(),
-                            memo: to_json_string(&PacketMetadata {
-                                forward: Some(ForwardMetadata {
-                                    receiver: self.destination_receiver_addr.to_string(),
-                                    port: "transfer".to_string(),
-                                    // hop chain to final receiver chain channel
-                                    channel: pfm_config
-                                        .hop_to_destination_chain_channel_id
-                                        .to_string(),
-                                }),
-                            })?,
-                            fee: ibc_fee.clone(),
+                            memo: to_json_string(
+                                &pfm_config.get_packet_metadata(&self.destination_receiver_addr),
+                            )?,
+                            fee: min_ibc_fee.ibc_fee.clone(),
                         }))
                     }
                     None => {
@@ -310,9 +446,11 @@ impl DestinationConfig {
                             timeout_timestamp: current_timestamp
                                 .plus_seconds(self.ibc_transfer_timeout.u64())
                                 .nanos(),
-                            memo: format!("ibc_distribution: {:?}:{:?}", c.denom, c.amount,)
-                                .to_string(),
-                            fee: ibc_fee.clone(),
+                            memo: match &wasm_hook_memo {
+                                Some(memo) => memo.to_string(),
+                                None => format_distribution_memo(self.memo_template.as_deref(), &c),
+                            },
+                            fee: min_ibc_fee.ibc_fee.clone(),
                         }));
                     }
                 }
@@ -333,6 +471,16 @@ impl DestinationConfig {
                 self.destination_receiver_addr.to_string(),
             ),
             Attribute::new("ibc_transfer_timeout", self.ibc_transfer_timeout),
+            Attribute::new(
+                "memo_template",
+                self.memo_template.as_deref().unwrap_or("none"),
+            ),
+            Attribute::new(
+                "wasm_hook_contract",
+                self.wasm_hook
+                    .as_ref()
+                    .map_or("none", |wasm_hook| wasm_hook.contract.as_str()),
+            ),
         ]
     }
 }
@@ -352,6 +500,44 @@ pub struct PfmUnwindingConfig {
 pub struct SingleSideLpLimits {
     pub asset_a_limit: Uint128,
     pub asset_b_limit: Uint128,
+    /// optional relative limit. if set, the highest amount we consider
+    /// acceptable to provide single-sided is this share of the current
+    /// pool reserves of the asset, and the absolute limits above are ignored.
+    #[serde(default)]
+    pub pool_reserve_share: Option<Decimal>,
+}
+
+impl SingleSideLpLimits {
+    /// validates that the relative limit (if any) is within (0, 1]
+    pub fn validate(&self) -> StdResult<()> {
+        if let Some(share) = self.pool_reserve_share {
+            if share.is_zero() || share > Decimal::one() {
+                return Err(StdError::generic_err(
+                    "single side lp pool reserve share must be within (0, 1]",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// returns the effective single-side limit of an asset, given its
+    /// absolute limit and the current pool reserves of that asset
+    pub fn get_effective_limit(&self, absolute_limit: Uint128, pool_reserve: Uint128) -> Uint128 {
+        match self.pool_reserve_share {
+            Some(share) => pool_reserve.mul_floor(share),
+            None => absolute_limit,
+        }
+    }
+
+    /// returns the effective single-side limit of asset a
+    pub fn get_asset_a_limit(&self, pool_reserve: Uint128) -> Uint128 {
+        self.get_effective_limit(self.asset_a_limit, pool_reserve)
+    }
+
+    /// returns the effective single-side limit of asset b
+    pub fn get_asset_b_limit(&self, pool_reserve: Uint128) -> Uint128 {
+        self.get_effective_limit(self.asset_b_limit, pool_reserve)
+    }
 }
 
 /// config for the pool price expectations upon covenant instantiation
@@ -388,3 +574,41 @@ pub fn soft_validate_remote_chain_addr(api: &dyn Api, addr: &str) -> StdResult<(
         ))),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{HopConfig, PacketForwardMiddlewareConfig};
+
+    #[test]
+    fn test_multi_hop_packet_metadata() {
+        let mut pfm_config = PacketForwardMiddlewareConfig {
+            local_to_hop_chain_channel_id: "channel-1".to_string(),
+            hop_to_destination_chain_channel_id: "channel-2".to_string(),
+            hop_chain_receiver_address: "hop_1_receiver".to_string(),
+            next_hops: vec![],
+        };
+        assert_eq!(
+            cosmwasm_std::to_json_string(&pfm_config.get_packet_metadata("receiver")).unwrap(),
+            r#"{"forward":{"receiver":"receiver","port":"transfer","channel":"channel-2"}}"#
+        );
+
+        pfm_config.next_hops = vec![
+            HopConfig {
+                channel_id: "channel-10".to_string(),
+                receiver_address: "hop_2_receiver".to_string(),
+            },
+            HopConfig {
+                channel_id: "channel-20".to_string(),
+                receiver_address: "hop_3_receiver".to_string(),
+            },
+        ];
+        assert_eq!(
+            cosmwasm_std::to_json_string(&pfm_config.get_packet_metadata("receiver")).unwrap(),
+            concat!(
+                r#"{"forward":{"receiver":"hop_2_receiver","port":"transfer","channel":"channel-10","next":"#,
+                r#"{"forward":{"receiver":"hop_3_receiver","port":"transfer","channel":"channel-20","next":"#,
+                r#"{"forward":{"receiver":"receiver","port":"transfer","channel":"channel-2"}}}}}}"#,
+            )
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! local stand-ins for the IBC transfers and interchain account txs of the
+//! covenant contracts, enabled with the `mock-ibc` feature. contracts built
+//! with it deliver their transfers with bank sends on the local chain and
+//! act as their own interchain accounts, so that a covenant can be rehearsed
+//! on a single chain without relayers.
+
+use std::str::FromStr;
+
+use cosmwasm_std::{BankMsg, Coin, CosmosMsg, StdError, StdResult, Uint128};
+use neutron_sdk::bindings::types::ProtobufAny;
+use prost::Message;
+
+/// whether the IBC transfers and interchain account txs are mocked
+pub const MOCK_IBC: bool = cfg!(feature = "mock-ibc");
+
+/// build metadata appended to the cw2 version of contracts built with
+/// the `mock-ibc` feature, so that mocked builds can be told apart on chain.
+/// empty (and absent from the binary) otherwise.
+pub const MOCK_IBC_VERSION_SUFFIX: &str = if MOCK_IBC { "+mock-ibc" } else { "" };
+
+/// returns the cw2 version to store for a contract of the given version
+pub fn get_contract_version(version: &str) -> String {
+    format!("{version}{MOCK_IBC_VERSION_SUFFIX}")
+}
+
+/// fields of the ibc MsgTransfer needed to deliver it locally
+#[derive(Clone, PartialEq, Message)]
+struct MsgTransfer {
+    #[prost(message, optional, tag = "3")]
+    pub token: Option<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+    #[prost(string, tag = "5")]
+    pub receiver: String,
+}
+
+/// executes the msgs of an interchain account tx on the local chain, with
+/// the calling contract standing in for its interchain account. bank msgs
+/// are executed as they are, while ibc transfers become bank sends to
+/// their receivers.
+pub fn get_mock_ica_tx_msgs<T>(msgs: Vec<ProtobufAny>) -> StdResult<Vec<CosmosMsg<T>>> {
+    msgs.into_iter()
+        .map(|msg| match msg.type_url.as_str() {
+            "/cosmos.bank.v1beta1.MsgSend" | "/cosmos.bank.v1beta1.MsgMultiSend" => {
+                Ok(CosmosMsg::Stargate {
+                    type_url: msg.type_url,
+                    value: msg.value,
+                })
+            }
+            "/ibc.applications.transfer.v1.MsgTransfer" => {
+                let transfer = MsgTransfer::decode(msg.value.as_slice())
+                    .map_err(|e| StdError::parse_err("MsgTransfer", e.to_string()))?;
+                let token = transfer
+                    .token
+                    .ok_or_else(|| StdError::generic_err("MsgTransfer token is missing"))?;
+
+                Ok(BankMsg::Send {
+                    to_address: transfer.receiver,
+                    amount: vec![Coin {
+                        denom: token.denom,
+                        amount: Uint128::from_str(&token.amount)?,
+                    }],
+                }
+                .into())
+            }
+            type_url => Err(StdError::generic_err(format!(
+                "unsupported interchain account msg: {type_url}"
+            ))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coin, BankMsg, Binary, CosmosMsg, Empty};
+    use neutron_sdk::bindings::types::ProtobufAny;
+    use prost::Message;
+
+    use super::{get_contract_version, get_mock_ica_tx_msgs, MsgTransfer, MOCK_IBC};
+    use crate::neutron::get_proto_coin;
+
+    #[test]
+    fn test_get_mock_ica_tx_msgs() {
+        let transfer = MsgTransfer {
+            token: Some(get_proto_coin("uatom".to_string(), 100u128.into())),
+            receiver: "receiver".to_string(),
+        };
+        let multi_send = ProtobufAny {
+            type_url: "/cosmos.bank.v1beta1.MsgMultiSend".to_string(),
+            value: Binary::from(vec![1, 2, 3]),
+        };
+
+        let msgs = get_mock_ica_tx_msgs::<Empty>(vec![
+            ProtobufAny {
+                type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+                value: Binary::from(transfer.encode_to_vec()),
+            },
+            multi_send.clone(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            msgs,
+            vec![
+                BankMsg::Send {
+                    to_address: "receiver".to_string(),
+                    amount: vec![coin(100, "uatom")],
+                }
+                .into(),
+                CosmosMsg::Stargate {
+                    type_url: multi_send.type_url,
+                    value: multi_send.value,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_mock_ica_tx_msgs_unsupported() {
+        let err = get_mock_ica_tx_msgs::<Empty>(vec![ProtobufAny {
+            type_url: "/stride.stakeibc.MsgLiquidStake".to_string(),
+            value: Binary::default(),
+        }])
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unsupported interchain account msg: /stride.stakeibc.MsgLiquidStake"));
+    }
+
+    #[test]
+    fn test_get_contract_version() {
+        let expected = if MOCK_IBC { "1.0.0+mock-ibc" } else { "1.0.0" };
+        assert_eq!(get_contract_version("1.0.0"), expected);
+    }
+}
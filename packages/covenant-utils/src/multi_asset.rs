@@ -0,0 +1,119 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{StdError, StdResult, Uint128, Uint256};
+
+/// configuration of a pool asset beyond the two party assets.
+/// used to provide liquidity into pools of three or more tokens.
+#[cw_serde]
+pub struct PoolAssetConfig {
+    /// denom of the asset as held by the liquid pooler
+    pub denom: String,
+    /// highest amount we consider acceptable to provide single-sided
+    pub single_side_limit: Uint128,
+}
+
+/// given the pool balances and our available balances (ordered
+/// identically), returns the largest amounts we can provide without
+/// shifting the pool composition.
+/// the asset with the lowest available-to-pool ratio is fully used up,
+/// amounts of the remaining assets are rounded down.
+pub fn get_proportional_amounts(
+    pool_balances: &[Uint128],
+    available: &[Uint128],
+) -> StdResult<Vec<Uint128>> {
+    if pool_balances.len() != available.len() {
+        return Err(StdError::generic_err(
+            "pool and available balances must be of equal length",
+        ));
+    }
+    if pool_balances.iter().any(|bal| bal.is_zero()) {
+        return Err(StdError::generic_err("all pool assets must be non-zero"));
+    }
+
+    // find the limiting asset by comparing available_i / pool_i ratios.
+    // cross-multiplication is done in 256 bits to rule out overflows.
+    let mut limiting_index = 0;
+    for i in 1..pool_balances.len() {
+        let current = available[i].full_mul(pool_balances[limiting_index]);
+        let limiting = available[limiting_index].full_mul(pool_balances[i]);
+        if current < limiting {
+            limiting_index = i;
+        }
+    }
+
+    let (limiting_available, limiting_pool) =
+        (available[limiting_index], pool_balances[limiting_index]);
+
+    pool_balances
+        .iter()
+        .map(|pool_bal| {
+            let amount = Uint256::from(*pool_bal)
+                .checked_mul(limiting_available.into())?
+                .checked_div(limiting_pool.into())?;
+            Ok(Uint128::try_from(amount)?)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::Uint128;
+
+    use super::get_proportional_amounts;
+
+    fn to_uints(amounts: &[u128]) -> Vec<Uint128> {
+        amounts.iter().map(|a| Uint128::new(*a)).collect()
+    }
+
+    #[test]
+    fn test_proportional_amounts_balanced_pool() {
+        let amounts =
+            get_proportional_amounts(&to_uints(&[100, 100, 100]), &to_uints(&[10, 20, 30]))
+                .unwrap();
+        assert_eq!(amounts, to_uints(&[10, 10, 10]));
+    }
+
+    #[test]
+    fn test_proportional_amounts_weighted_pool() {
+        // 50/30/20 pool composition
+        let amounts = get_proportional_amounts(
+            &to_uints(&[5_000, 3_000, 2_000]),
+            &to_uints(&[1_000, 1_000, 1_000]),
+        )
+        .unwrap();
+        assert_eq!(amounts, to_uints(&[1_000, 600, 400]));
+    }
+
+    #[test]
+    fn test_proportional_amounts_never_exceed_available() {
+        let pool = to_uints(&[7, 13, 1_000_003]);
+        let available = to_uints(&[3, 11, 999_999]);
+        let amounts = get_proportional_amounts(&pool, &available).unwrap();
+
+        amounts
+            .iter()
+            .zip(available.iter())
+            .for_each(|(amount, available)| assert!(amount <= available));
+    }
+
+    #[test]
+    fn test_proportional_amounts_large_balances() {
+        let amounts = get_proportional_amounts(
+            &to_uints(&[u128::MAX, u128::MAX / 2]),
+            &to_uints(&[u128::MAX, u128::MAX]),
+        )
+        .unwrap();
+        assert_eq!(amounts, to_uints(&[u128::MAX, u128::MAX / 2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "all pool assets must be non-zero")]
+    fn test_proportional_amounts_validates_pool_balances() {
+        get_proportional_amounts(&to_uints(&[100, 0, 100]), &to_uints(&[1, 1, 1])).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "pool and available balances must be of equal length")]
+    fn test_proportional_amounts_validates_lengths() {
+        get_proportional_amounts(&to_uints(&[100, 100]), &to_uints(&[1, 1, 1])).unwrap();
+    }
+}
@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
+
+use cosmos_sdk_proto::cosmos::bank::v1beta1::{Input, MsgMultiSend, Output};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    Attribute, Binary, MessageInfo, QuerierWrapper, StdError, StdResult, Uint128, Uint64,
+    Attribute, BankMsg, Binary, Coin, CosmosMsg, MessageInfo, QuerierWrapper, StdError, StdResult,
+    Uint128, Uint64,
 };
 use cw_utils::must_pay;
 use neutron_sdk::{
@@ -131,12 +135,86 @@ pub fn to_proto_msg_multi_send(msg: impl Message) -> NeutronResult<ProtobufAny>
     })
 }
 
+/// consolidates the bank sends among `msgs` into a single message.
+/// sends to a single receiver are merged into one `BankMsg::Send`, while
+/// sends to multiple receivers are merged into a `MsgMultiSend` from `sender`.
+/// other messages are kept in order, followed by the consolidated send.
+pub fn consolidate_bank_sends<T>(
+    sender: &str,
+    msgs: Vec<CosmosMsg<T>>,
+) -> StdResult<Vec<CosmosMsg<T>>> {
+    let mut consolidated_msgs = Vec::with_capacity(msgs.len());
+    let mut receiver_amounts: BTreeMap<String, BTreeMap<String, Uint128>> = BTreeMap::new();
+
+    for msg in msgs {
+        match msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                let denom_amounts = receiver_amounts.entry(to_address).or_default();
+                for coin in amount.into_iter().filter(|c| !c.amount.is_zero()) {
+                    let denom_amount = denom_amounts.entry(coin.denom).or_default();
+                    *denom_amount = denom_amount.checked_add(coin.amount)?;
+                }
+            }
+            msg => consolidated_msgs.push(msg),
+        }
+    }
+    receiver_amounts.retain(|_, denom_amounts| !denom_amounts.is_empty());
+
+    if receiver_amounts.len() == 1 {
+        if let Some((to_address, denom_amounts)) = receiver_amounts.pop_first() {
+            consolidated_msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address,
+                amount: denom_amounts
+                    .into_iter()
+                    .map(|(denom, amount)| Coin { denom, amount })
+                    .collect(),
+            }));
+        }
+    } else if receiver_amounts.len() > 1 {
+        let mut total_amounts: BTreeMap<String, Uint128> = BTreeMap::new();
+        let mut outputs = Vec::with_capacity(receiver_amounts.len());
+
+        for (address, denom_amounts) in receiver_amounts {
+            let mut coins = Vec::with_capacity(denom_amounts.len());
+            for (denom, amount) in denom_amounts {
+                let total_amount = total_amounts.entry(denom.to_string()).or_default();
+                *total_amount = total_amount.checked_add(amount)?;
+                coins.push(get_proto_coin(denom, amount));
+            }
+            outputs.push(Output { address, coins });
+        }
+
+        let multi_send_msg = MsgMultiSend {
+            inputs: vec![Input {
+                address: sender.to_string(),
+                coins: total_amounts
+                    .into_iter()
+                    .map(|(denom, amount)| get_proto_coin(denom, amount))
+                    .collect(),
+            }],
+            outputs,
+        };
+        let protobuf_msg = to_proto_msg_multi_send(multi_send_msg)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+        consolidated_msgs.push(CosmosMsg::Stargate {
+            type_url: protobuf_msg.type_url,
+            value: protobuf_msg.value,
+        });
+    }
+
+    Ok(consolidated_msgs)
+}
+
 #[cw_serde]
 pub struct MinIbcFeeConfig {
     pub ibc_fee: IbcFee,
     pub total_ntrn_fee: Uint128,
 }
 
+/// queries the min ibc fee currently required by the chain, along with
+/// its total amount. fees should always be derived from this query, as
+/// the minimums are chain parameters subject to change.
 pub fn query_ibc_fee(querier: QuerierWrapper<'_, NeutronQuery>) -> StdResult<MinIbcFeeConfig> {
     let min_fee_query_response: MinIbcFeeResponse =
         querier.query(&NeutronQuery::MinIbcFee {}.into())?;
@@ -148,6 +226,68 @@ pub fn query_ibc_fee(querier: QuerierWrapper<'_, NeutronQuery>) -> StdResult<Min
     })
 }
 
+/// per module ibc fees overriding the chain minimum, passed by the
+/// covenants to their modules of each kind. stride bound ica
+/// transactions may e.g. require higher fees than plain transfers.
+#[cw_serde]
+#[derive(Default)]
+pub struct IbcFeeOverrides {
+    /// ibc forwarders of the contributions
+    pub forwarder: Option<IbcFee>,
+    /// remote chain splitter
+    pub splitter: Option<IbcFee>,
+    /// interchain routers
+    pub router: Option<IbcFee>,
+    /// stride liquid staker
+    pub liquid_staker: Option<IbcFee>,
+}
+
+/// validates an ibc fee override, as neutron does not support recv fees
+pub fn validate_ibc_fee_override(ibc_fee: &IbcFee) -> StdResult<()> {
+    if ibc_fee.recv_fee.iter().any(|coin| !coin.amount.is_zero()) {
+        return Err(StdError::generic_err(
+            "ibc fee override must not include a recv fee",
+        ));
+    }
+    Ok(())
+}
+
+/// queries the min ibc fee currently required by the chain, raised to the
+/// given override. every fee of the override only applies where it exceeds
+/// the chain minimum of its denom, so that overrides can never underpay.
+pub fn query_ibc_fee_with_override(
+    querier: QuerierWrapper<'_, NeutronQuery>,
+    fee_override: Option<IbcFee>,
+) -> StdResult<MinIbcFeeConfig> {
+    let min_ibc_fee = query_ibc_fee(querier)?;
+    let Some(fee_override) = fee_override else {
+        return Ok(min_ibc_fee);
+    };
+
+    let ibc_fee = IbcFee {
+        recv_fee: min_ibc_fee.ibc_fee.recv_fee,
+        ack_fee: get_max_fee_coins(&min_ibc_fee.ibc_fee.ack_fee, &fee_override.ack_fee),
+        timeout_fee: get_max_fee_coins(&min_ibc_fee.ibc_fee.timeout_fee, &fee_override.timeout_fee),
+    };
+    Ok(MinIbcFeeConfig {
+        total_ntrn_fee: flatten_ibc_fee_total_amount(&ibc_fee),
+        ibc_fee,
+    })
+}
+
+/// merges both fee coin lists, keeping the highest amount of every denom
+fn get_max_fee_coins(fee: &[Coin], other: &[Coin]) -> Vec<Coin> {
+    let mut amounts: BTreeMap<String, Uint128> = BTreeMap::new();
+    for coin in fee.iter().chain(other) {
+        let amount = amounts.entry(coin.denom.to_string()).or_default();
+        *amount = (*amount).max(coin.amount);
+    }
+    amounts
+        .into_iter()
+        .map(|(denom, amount)| Coin { denom, amount })
+        .collect()
+}
+
 pub fn flatten_ibc_fee_total_amount(ibc_fee: &IbcFee) -> Uint128 {
     let mut total_amount = Uint128::zero();
 
@@ -187,3 +327,103 @@ pub fn assert_ibc_fee_coverage(
         )),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
+    use cosmwasm_std::{coin, BankMsg, CosmosMsg, Empty, WasmMsg};
+    use prost::Message;
+
+    use super::{consolidate_bank_sends, get_max_fee_coins, get_proto_coin};
+
+    #[test]
+    fn test_consolidate_bank_sends_single_receiver() {
+        let wasm_msg = CosmosMsg::Wasm(WasmMsg::ClearAdmin {
+            contract_addr: "contract".to_string(),
+        });
+        let msgs = consolidate_bank_sends::<Empty>(
+            "sender",
+            vec![
+                BankMsg::Send {
+                    to_address: "a".to_string(),
+                    amount: vec![coin(10, "untrn")],
+                }
+                .into(),
+                wasm_msg.clone(),
+                BankMsg::Send {
+                    to_address: "a".to_string(),
+                    amount: vec![coin(5, "uatom"), coin(5, "untrn"), coin(0, "uosmo")],
+                }
+                .into(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            msgs,
+            vec![
+                wasm_msg,
+                BankMsg::Send {
+                    to_address: "a".to_string(),
+                    amount: vec![coin(5, "uatom"), coin(15, "untrn")],
+                }
+                .into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_consolidate_bank_sends_multiple_receivers() {
+        let msgs = consolidate_bank_sends::<Empty>(
+            "sender",
+            vec![
+                BankMsg::Send {
+                    to_address: "a".to_string(),
+                    amount: vec![coin(10, "untrn")],
+                }
+                .into(),
+                BankMsg::Send {
+                    to_address: "b".to_string(),
+                    amount: vec![coin(20, "untrn")],
+                }
+                .into(),
+                BankMsg::Send {
+                    to_address: "c".to_string(),
+                    amount: vec![coin(0, "untrn")],
+                }
+                .into(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(msgs.len(), 1);
+        let CosmosMsg::Stargate { type_url, value } = &msgs[0] else {
+            panic!("expected a stargate message");
+        };
+        assert_eq!(type_url, "/cosmos.bank.v1beta1.MsgMultiSend");
+
+        let multi_send = MsgMultiSend::decode(value.as_slice()).unwrap();
+        assert_eq!(multi_send.inputs.len(), 1);
+        assert_eq!(multi_send.inputs[0].address, "sender");
+        assert_eq!(
+            multi_send.inputs[0].coins,
+            vec![get_proto_coin("untrn".to_string(), 30u128.into())]
+        );
+        // zero amount sends are dropped
+        assert_eq!(multi_send.outputs.len(), 2);
+        assert_eq!(multi_send.outputs[0].address, "a");
+        assert_eq!(multi_send.outputs[1].address, "b");
+    }
+
+    #[test]
+    fn test_get_max_fee_coins() {
+        let fee = get_max_fee_coins(
+            &[coin(1_000, "untrn"), coin(10, "uatom")],
+            &[coin(5_000, "untrn"), coin(1, "uatom"), coin(3, "uosmo")],
+        );
+        assert_eq!(
+            fee,
+            vec![coin(10, "uatom"), coin(5_000, "untrn"), coin(3, "uosmo")]
+        );
+    }
+}
@@ -0,0 +1,481 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    instantiate2_address, to_json_binary, to_json_string, Addr, Attribute, Binary, CanonicalAddr,
+    CodeInfoResponse, Deps, Env, HexBinary, MessageInfo, QuerierWrapper, Reply, StdError,
+    StdResult, SubMsg, WasmMsg,
+};
+use cw_utils::parse_reply_instantiate_data;
+
+/// reply id of the instantiations performed by a shadow deployment
+pub const SHADOW_DEPLOY_REPLY_ID: u64 = 314;
+
+/// reply id of the migrations performed by a shadow deployment
+pub const SHADOW_MIGRATE_REPLY_ID: u64 = 315;
+
+/// a single instantiation step of a covenant deployment, along with
+/// the values a reviewer needs to verify it ahead of time.
+#[cw_serde]
+pub struct DeploymentStep {
+    /// label of the contract being instantiated
+    pub label: String,
+    /// code id the contract gets instantiated from
+    pub code_id: u64,
+    /// checksum of the code behind `code_id` at the time of planning
+    pub checksum: HexBinary,
+    /// salt used for the instantiate2 address derivation
+    pub salt: Binary,
+    /// precomputed instantiate2 address of the contract
+    pub address: Addr,
+    /// size (in bytes) of the serialized instantiate msg
+    pub msg_size: u64,
+    /// the exact message emitted by the covenant
+    pub msg: WasmMsg,
+}
+
+/// ordered list of messages a top-level covenant emits upon
+/// instantiation.
+#[cw_serde]
+pub struct DeploymentPlan {
+    pub steps: Vec<DeploymentStep>,
+}
+
+impl DeploymentPlan {
+    /// builds the deployment plan for the given ordered messages.
+    /// every message is expected to be a `WasmMsg::Instantiate2`, with
+    /// the address derived from `creator` and the checksum of the code.
+    pub fn build(deps: Deps, creator: &CanonicalAddr, messages: Vec<WasmMsg>) -> StdResult<Self> {
+        let steps = messages
+            .into_iter()
+            .map(|msg| DeploymentStep::build(deps, creator, msg))
+            .collect::<StdResult<Vec<DeploymentStep>>>()?;
+
+        Ok(DeploymentPlan { steps })
+    }
+
+    /// returns the planned messages in the order they are to be executed
+    pub fn messages(&self) -> Vec<WasmMsg> {
+        self.steps.iter().map(|step| step.msg.clone()).collect()
+    }
+
+    /// returns the total size (in bytes) of all serialized instantiate msgs
+    pub fn total_msg_size(&self) -> u64 {
+        self.steps.iter().map(|step| step.msg_size).sum()
+    }
+
+    /// builds a parallel deployment plan out of this one. every step gets
+    /// instantiated from the code id returned by `get_code_id`, under a salt
+    /// distinct for each shadow deployment `nonce`. references between the
+    /// planned contracts are remapped to their shadow counterparts, and no
+    /// funds are attached to the shadow instantiations.
+    pub fn shadow(
+        &self,
+        deps: Deps,
+        creator: &CanonicalAddr,
+        nonce: u64,
+        get_code_id: impl Fn(&DeploymentStep) -> StdResult<u64>,
+    ) -> StdResult<Self> {
+        let mut shadow_steps = vec![];
+        for step in &self.steps {
+            let WasmMsg::Instantiate2 { admin, msg, .. } = &step.msg else {
+                return Err(StdError::generic_err(
+                    "deployment plan only supports instantiate2 messages",
+                ));
+            };
+            let msg = WasmMsg::Instantiate2 {
+                admin: admin.clone(),
+                code_id: get_code_id(step)?,
+                label: format!("{}_shadow_{nonce}", step.label),
+                msg: msg.clone(),
+                funds: vec![],
+                salt: Binary(
+                    [step.salt.as_slice(), format!("_shadow_{nonce}").as_bytes()].concat(),
+                ),
+            };
+            shadow_steps.push(DeploymentStep::build(deps, creator, msg)?);
+        }
+
+        // point the shadow contracts to each other instead of the live ones
+        let address_remapping: Vec<(&Addr, Addr)> = self
+            .steps
+            .iter()
+            .zip(shadow_steps.iter())
+            .map(|(step, shadow_step)| (&step.address, shadow_step.address.clone()))
+            .collect();
+        for step in shadow_steps.iter_mut() {
+            if let WasmMsg::Instantiate2 { msg, .. } = &mut step.msg {
+                let mut remapped_msg = String::from_utf8(msg.to_vec())
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                for (live_address, shadow_address) in &address_remapping {
+                    remapped_msg =
+                        remapped_msg.replace(live_address.as_str(), shadow_address.as_str());
+                }
+                *msg = Binary(remapped_msg.into_bytes());
+                step.msg_size = msg.len() as u64;
+            }
+        }
+
+        Ok(DeploymentPlan {
+            steps: shadow_steps,
+        })
+    }
+}
+
+impl DeploymentStep {
+    fn build(deps: Deps, creator: &CanonicalAddr, msg: WasmMsg) -> StdResult<Self> {
+        match &msg {
+            WasmMsg::Instantiate2 {
+                code_id,
+                label,
+                msg: instantiate_msg,
+                salt,
+                ..
+            } => {
+                let CodeInfoResponse { checksum, .. } =
+                    deps.querier.query_wasm_code_info(*code_id)?;
+                let address = match instantiate2_address(&checksum, creator, salt) {
+                    Ok(addr) => deps.api.addr_humanize(&addr)?,
+                    Err(e) => return Err(StdError::generic_err(e.to_string())),
+                };
+
+                Ok(DeploymentStep {
+                    label: label.to_string(),
+                    code_id: *code_id,
+                    checksum,
+                    salt: salt.clone(),
+                    address,
+                    msg_size: instantiate_msg.len() as u64,
+                    msg,
+                })
+            }
+            _ => Err(StdError::generic_err(
+                "deployment plan only supports instantiate2 messages",
+            )),
+        }
+    }
+}
+
+/// progress of a deployment plan instantiated in batches over multiple
+/// transactions, for covenants too large to deploy within a single block
+#[cw_serde]
+pub struct StagedDeployment {
+    /// highest number of steps instantiated per transaction
+    pub batch_size: u64,
+    /// number of steps instantiated so far, in the order of the plan
+    pub deployed_steps: u64,
+}
+
+impl StagedDeployment {
+    pub fn new(batch_size: u64) -> StdResult<Self> {
+        if batch_size == 0 {
+            return Err(StdError::generic_err(
+                "deployment batch size must be non-zero",
+            ));
+        }
+        Ok(StagedDeployment {
+            batch_size,
+            deployed_steps: 0,
+        })
+    }
+
+    /// returns the messages of the next batch of steps,
+    /// recording them as deployed. errors if none are left.
+    pub fn next_batch(&mut self, plan: &DeploymentPlan) -> StdResult<Vec<WasmMsg>> {
+        let messages: Vec<WasmMsg> = plan
+            .steps
+            .iter()
+            .skip(self.deployed_steps as usize)
+            .take(self.batch_size as usize)
+            .map(|step| step.msg.clone())
+            .collect();
+        if messages.is_empty() {
+            return Err(StdError::generic_err("deployment is already complete"));
+        }
+        self.deployed_steps += messages.len() as u64;
+        Ok(messages)
+    }
+
+    pub fn get_progress(&self, plan: &DeploymentPlan) -> DeploymentProgress {
+        let (deployed, pending) = plan
+            .steps
+            .split_at((self.deployed_steps as usize).min(plan.steps.len()));
+        DeploymentProgress {
+            deployed: deployed.iter().map(|step| step.label.to_string()).collect(),
+            pending: pending.iter().map(|step| step.label.to_string()).collect(),
+        }
+    }
+}
+
+/// labels of the deployment plan steps, split by whether
+/// they got instantiated yet
+#[cw_serde]
+pub struct DeploymentProgress {
+    pub deployed: Vec<String>,
+    pub pending: Vec<String>,
+}
+
+impl DeploymentProgress {
+    /// progress of a plan instantiated as a whole
+    pub fn complete(plan: &DeploymentPlan) -> Self {
+        DeploymentProgress {
+            deployed: plan
+                .steps
+                .iter()
+                .map(|step| step.label.to_string())
+                .collect(),
+            pending: vec![],
+        }
+    }
+}
+
+/// outcome of a single shadow step
+#[cw_serde]
+pub enum ShadowStepStatus {
+    /// instantiation reply was not received yet
+    Pending,
+    /// contract got instantiated at the planned address, and
+    /// awaits its migration to the candidate code
+    Instantiated,
+    /// contract got instantiated at the planned address and
+    /// migrated to the candidate code
+    Compatible,
+    /// contract got instantiated at an unexpected address
+    AddressMismatch { address: String },
+    /// instantiation failed with the given error
+    Failed { error: String },
+    /// migration to the candidate code failed with the given error
+    MigrationFailed { error: String },
+    /// contract reports a code other than the candidate one after migrating
+    CodeMismatch { code_id: u64 },
+}
+
+#[cw_serde]
+pub struct ShadowStep {
+    pub label: String,
+    /// code id the shadow contract gets instantiated from
+    pub code_id: u64,
+    /// code id the shadow contract gets migrated to
+    pub candidate_code_id: u64,
+    /// planned address of the shadow contract
+    pub address: Addr,
+    pub status: ShadowStepStatus,
+}
+
+/// migrate msg understood by every covenant contract, used to move
+/// the shadow contracts to their candidate code as is
+#[cw_serde]
+enum ShadowMigrateMsg {
+    UpdateCodeId { data: Option<Binary> },
+}
+
+/// report of a shadow deployment, used to validate a code upgrade
+/// path of a covenant before migrating its live contracts
+#[cw_serde]
+pub struct ShadowDeployment {
+    /// sequence number of the shadow deployment, used to derive
+    /// distinct salts for every deployment
+    pub nonce: u64,
+    pub steps: Vec<ShadowStep>,
+    /// true if every step got instantiated at its planned address
+    /// and migrated to its candidate code
+    pub compatible: bool,
+}
+
+impl ShadowDeployment {
+    /// shadows the deployment plan from the live contract codes and returns
+    /// the report along with the instantiation submessages. every shadow
+    /// contract then gets migrated to the code returned by
+    /// `get_candidate_code_id`. only the covenant admin may shadow deploy.
+    pub fn deploy(
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        plan: &DeploymentPlan,
+        previous: Option<ShadowDeployment>,
+        get_code_id: impl Fn(&DeploymentStep) -> StdResult<u64>,
+        get_candidate_code_id: impl Fn(&DeploymentStep) -> StdResult<u64>,
+    ) -> StdResult<(Self, Vec<SubMsg>)> {
+        ensure_contract_admin(&deps.querier, env, &info.sender)?;
+
+        let nonce = previous.map_or(1, |shadow_deployment| shadow_deployment.nonce + 1);
+        let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+        let shadow_plan = plan.shadow(deps, &creator, nonce, get_code_id)?;
+
+        let steps = plan
+            .steps
+            .iter()
+            .zip(shadow_plan.steps.iter())
+            .map(|(step, shadow_step)| {
+                Ok(ShadowStep {
+                    label: shadow_step.label.to_string(),
+                    code_id: shadow_step.code_id,
+                    candidate_code_id: get_candidate_code_id(step)?,
+                    address: shadow_step.address.clone(),
+                    status: ShadowStepStatus::Pending,
+                })
+            })
+            .collect::<StdResult<Vec<ShadowStep>>>()?;
+        let messages = shadow_plan
+            .messages()
+            .into_iter()
+            .map(|msg| SubMsg::reply_always(msg, SHADOW_DEPLOY_REPLY_ID))
+            .collect();
+
+        Ok((
+            ShadowDeployment {
+                nonce,
+                steps,
+                compatible: false,
+            },
+            messages,
+        ))
+    }
+
+    /// records the outcome of the next pending instantiation or migration.
+    /// returns the updated step along with the messages continuing it:
+    /// the migration of an instantiated contract, or the handover of a
+    /// migrated contract to the covenant admin.
+    pub fn record_reply(
+        &mut self,
+        deps: Deps,
+        env: &Env,
+        reply: Reply,
+    ) -> StdResult<(ShadowStep, Vec<SubMsg>)> {
+        let (step, messages) = match reply.id {
+            SHADOW_DEPLOY_REPLY_ID => self.record_instantiate_reply(deps, env, reply)?,
+            SHADOW_MIGRATE_REPLY_ID => self.record_migrate_reply(deps, env, reply)?,
+            id => {
+                return Err(StdError::generic_err(format!(
+                    "unknown shadow deployment reply id {id}"
+                )))
+            }
+        };
+
+        self.compatible = self
+            .steps
+            .iter()
+            .all(|step| step.status == ShadowStepStatus::Compatible);
+
+        Ok((step, messages))
+    }
+
+    fn record_instantiate_reply(
+        &mut self,
+        deps: Deps,
+        env: &Env,
+        reply: Reply,
+    ) -> StdResult<(ShadowStep, Vec<SubMsg>)> {
+        let step = self
+            .steps
+            .iter_mut()
+            .find(|step| step.status == ShadowStepStatus::Pending)
+            .ok_or_else(|| StdError::generic_err("no pending shadow deployment step"))?;
+
+        let mut messages = vec![];
+        step.status = match parse_reply_instantiate_data(reply) {
+            Ok(data) if data.contract_address == step.address.as_str() => {
+                messages.push(SubMsg::reply_always(
+                    WasmMsg::Migrate {
+                        contract_addr: step.address.to_string(),
+                        new_code_id: step.candidate_code_id,
+                        msg: to_json_binary(&ShadowMigrateMsg::UpdateCodeId { data: None })?,
+                    },
+                    SHADOW_MIGRATE_REPLY_ID,
+                ));
+                ShadowStepStatus::Instantiated
+            }
+            Ok(data) => {
+                messages.push(SubMsg::new(get_handover_msg(
+                    &deps.querier,
+                    env,
+                    &data.contract_address,
+                )?));
+                ShadowStepStatus::AddressMismatch {
+                    address: data.contract_address,
+                }
+            }
+            Err(e) => ShadowStepStatus::Failed {
+                error: e.to_string(),
+            },
+        };
+
+        Ok((step.clone(), messages))
+    }
+
+    fn record_migrate_reply(
+        &mut self,
+        deps: Deps,
+        env: &Env,
+        reply: Reply,
+    ) -> StdResult<(ShadowStep, Vec<SubMsg>)> {
+        let step = self
+            .steps
+            .iter_mut()
+            .find(|step| step.status == ShadowStepStatus::Instantiated)
+            .ok_or_else(|| StdError::generic_err("no instantiated shadow deployment step"))?;
+
+        step.status = match reply.result.into_result() {
+            Ok(_) => {
+                // verify that the shadow contract now runs the candidate code
+                let code_id = deps
+                    .querier
+                    .query_wasm_contract_info(step.address.to_string())?
+                    .code_id;
+                if code_id == step.candidate_code_id {
+                    ShadowStepStatus::Compatible
+                } else {
+                    ShadowStepStatus::CodeMismatch { code_id }
+                }
+            }
+            Err(error) => ShadowStepStatus::MigrationFailed { error },
+        };
+
+        // the shadow contracts are of no further use to the covenant
+        let handover_msg = get_handover_msg(&deps.querier, env, step.address.as_str())?;
+
+        Ok((step.clone(), vec![SubMsg::new(handover_msg)]))
+    }
+
+    pub fn get_reply_attributes(&self, step: &ShadowStep) -> StdResult<Vec<Attribute>> {
+        Ok(vec![
+            Attribute::new("shadow_step", step.label.to_string()),
+            Attribute::new("shadow_step_address", step.address.to_string()),
+            Attribute::new("shadow_step_status", to_json_string(&step.status)?),
+            Attribute::new("compatible", self.compatible.to_string()),
+        ])
+    }
+}
+
+/// hands the admin rights of a shadow contract over to the covenant admin,
+/// so that the shadow contracts are not left behind without an operator
+fn get_handover_msg(
+    querier: &QuerierWrapper,
+    env: &Env,
+    contract_addr: &str,
+) -> StdResult<WasmMsg> {
+    let covenant_admin = querier
+        .query_wasm_contract_info(env.contract.address.to_string())?
+        .admin;
+    Ok(match covenant_admin {
+        Some(admin) => WasmMsg::UpdateAdmin {
+            contract_addr: contract_addr.to_string(),
+            admin,
+        },
+        None => WasmMsg::ClearAdmin {
+            contract_addr: contract_addr.to_string(),
+        },
+    })
+}
+
+/// errors unless the sender is the admin of the contract
+fn ensure_contract_admin(querier: &QuerierWrapper, env: &Env, sender: &Addr) -> StdResult<()> {
+    let admin = querier
+        .query_wasm_contract_info(env.contract.address.to_string())?
+        .admin;
+    if admin.as_deref() == Some(sender.as_str()) {
+        Ok(())
+    } else {
+        Err(StdError::generic_err(
+            "only the covenant admin can shadow deploy",
+        ))
+    }
+}
@@ -0,0 +1,167 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128};
+
+/// share of a pool owned by a liquid pooler
+#[cw_serde]
+pub struct PoolOwnership {
+    /// pool shares owned by the liquid pooler
+    pub owned_shares: Uint128,
+    /// total supply of the pool shares
+    pub total_shares: Uint128,
+    /// share of the total supply owned by the liquid pooler
+    pub ownership: Decimal,
+    /// highest share of the pool the liquid pooler may own, if limited
+    pub max_pool_ownership: Option<Decimal>,
+}
+
+impl PoolOwnership {
+    pub fn new(
+        owned_shares: Uint128,
+        total_shares: Uint128,
+        max_pool_ownership: Option<Decimal>,
+    ) -> Self {
+        let ownership = if total_shares.is_zero() {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(owned_shares, total_shares)
+        };
+        PoolOwnership {
+            owned_shares,
+            total_shares,
+            ownership,
+            max_pool_ownership,
+        }
+    }
+
+    /// returns the highest amount of shares that can be minted to the
+    /// liquid pooler without exceeding its max pool ownership.
+    /// `None` means that minting is not limited.
+    pub fn get_max_mintable_shares(&self) -> StdResult<Option<Uint128>> {
+        let max_ownership = match self.max_pool_ownership {
+            Some(max_ownership) if max_ownership < Decimal::one() => max_ownership,
+            _ => return Ok(None),
+        };
+
+        // (owned + minted) / (total + minted) <= max_ownership
+        // => minted <= (max_ownership * total - owned) / (1 - max_ownership)
+        let max_owned_shares = self.total_shares.mul_floor(max_ownership);
+        let max_mintable_shares = max_owned_shares
+            .saturating_sub(self.owned_shares)
+            .checked_multiply_ratio(
+                Decimal::one().atomics(),
+                (Decimal::one() - max_ownership).atomics(),
+            )
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+        Ok(Some(max_mintable_shares))
+    }
+
+    /// caps the amount of an asset to be provided to the pool, so that
+    /// the shares it mints stay within `max_mintable_shares`.
+    /// the asset amount worth a pool share is derived from its `pool_reserve`.
+    pub fn cap_provided_amount(
+        &self,
+        amount: Uint128,
+        pool_reserve: Uint128,
+        max_mintable_shares: Option<Uint128>,
+    ) -> StdResult<Uint128> {
+        match max_mintable_shares {
+            Some(max_shares) if !self.total_shares.is_zero() => {
+                let max_amount = max_shares
+                    .checked_multiply_ratio(pool_reserve, self.total_shares)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                Ok(amount.min(max_amount))
+            }
+            _ => Ok(amount),
+        }
+    }
+}
+
+/// validates that the max pool ownership (if any) is within (0, 1]
+pub fn validate_max_pool_ownership(max_pool_ownership: Option<Decimal>) -> StdResult<()> {
+    if let Some(max_ownership) = max_pool_ownership {
+        if max_ownership.is_zero() || max_ownership > Decimal::one() {
+            return Err(StdError::generic_err(
+                "max pool ownership must be within (0, 1]",
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{Decimal, Uint128};
+
+    use super::{validate_max_pool_ownership, PoolOwnership};
+
+    #[test]
+    fn test_validate_max_pool_ownership() {
+        validate_max_pool_ownership(None).unwrap();
+        validate_max_pool_ownership(Some(Decimal::percent(10))).unwrap();
+        validate_max_pool_ownership(Some(Decimal::one())).unwrap();
+        validate_max_pool_ownership(Some(Decimal::zero())).unwrap_err();
+        validate_max_pool_ownership(Some(Decimal::percent(101))).unwrap_err();
+    }
+
+    #[test]
+    fn test_get_max_mintable_shares() {
+        // unlimited ownership
+        let ownership = PoolOwnership::new(Uint128::new(100), Uint128::new(1_000), None);
+        assert_eq!(ownership.ownership, Decimal::percent(10));
+        assert_eq!(ownership.get_max_mintable_shares().unwrap(), None);
+
+        // owning 100 of 1000 shares, with at most 20% ownership
+        // we can mint 125 shares: (100 + 125) / (1000 + 125) = 20%
+        let ownership = PoolOwnership::new(
+            Uint128::new(100),
+            Uint128::new(1_000),
+            Some(Decimal::percent(20)),
+        );
+        assert_eq!(
+            ownership.get_max_mintable_shares().unwrap(),
+            Some(Uint128::new(125))
+        );
+
+        // already above the max ownership
+        let ownership = PoolOwnership::new(
+            Uint128::new(300),
+            Uint128::new(1_000),
+            Some(Decimal::percent(20)),
+        );
+        assert_eq!(
+            ownership.get_max_mintable_shares().unwrap(),
+            Some(Uint128::zero())
+        );
+    }
+
+    #[test]
+    fn test_cap_provided_amount() {
+        let ownership = PoolOwnership::new(
+            Uint128::new(100),
+            Uint128::new(1_000),
+            Some(Decimal::percent(20)),
+        );
+        let max_shares = ownership.get_max_mintable_shares().unwrap();
+
+        // 125 shares are worth 250 of an asset with 2000 in reserves
+        assert_eq!(
+            ownership
+                .cap_provided_amount(Uint128::new(1_000), Uint128::new(2_000), max_shares)
+                .unwrap(),
+            Uint128::new(250)
+        );
+        assert_eq!(
+            ownership
+                .cap_provided_amount(Uint128::new(100), Uint128::new(2_000), max_shares)
+                .unwrap(),
+            Uint128::new(100)
+        );
+        assert_eq!(
+            ownership
+                .cap_provided_amount(Uint128::new(1_000), Uint128::new(2_000), None)
+                .unwrap(),
+            Uint128::new(1_000)
+        );
+    }
+}
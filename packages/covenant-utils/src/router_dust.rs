@@ -0,0 +1,95 @@
+//! minimum amounts that routers route per denom. balances below
+//! them are considered dust and are left on the router until they
+//! accumulate, instead of spending fees on transferring them.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use cosmwasm_std::{
+    to_json_string, Attribute, Coin, CustomQuery, QuerierWrapper, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::Item;
+
+/// minimum amounts to route, keyed by denom
+pub const MIN_ROUTE_AMOUNTS: Item<BTreeMap<String, Uint128>> = Item::new("min_route_amounts");
+
+/// splits the balances into the ones to be routed and the dust
+pub fn split_dust_balances(
+    balances: Vec<Coin>,
+    min_route_amounts: &BTreeMap<String, Uint128>,
+) -> (Vec<Coin>, Vec<Coin>) {
+    balances
+        .into_iter()
+        .partition(|balance| match min_route_amounts.get(&balance.denom) {
+            Some(min_amount) => balance.amount >= *min_amount,
+            None => true,
+        })
+}
+
+/// queries the balances of the denoms that are currently below
+/// their minimum route amount, and thus are not being routed
+pub fn query_dust_balances<C: CustomQuery>(
+    querier: QuerierWrapper<C>,
+    storage: &dyn Storage,
+    address: &str,
+    denoms: &BTreeSet<String>,
+) -> StdResult<Vec<Coin>> {
+    let min_route_amounts = MIN_ROUTE_AMOUNTS.may_load(storage)?.unwrap_or_default();
+    let mut dust_balances = vec![];
+    for (denom, min_amount) in min_route_amounts {
+        if !denoms.contains(&denom) {
+            continue;
+        }
+        let balance = querier.query_balance(address, denom)?;
+        if !balance.amount.is_zero() && balance.amount < min_amount {
+            dust_balances.push(balance);
+        }
+    }
+    Ok(dust_balances)
+}
+
+pub fn get_min_route_amount_attributes(
+    min_route_amounts: &BTreeMap<String, Uint128>,
+) -> Vec<Attribute> {
+    min_route_amounts
+        .iter()
+        .map(|(denom, amount)| {
+            Attribute::new(format!("{denom}_min_route_amount"), amount.to_string())
+        })
+        .collect()
+}
+
+/// returns the dust balances as an attribute, if there are any
+pub fn get_dust_attribute(dust_balances: &[Coin]) -> StdResult<Option<Attribute>> {
+    if dust_balances.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Attribute::new(
+        "skipped_dust",
+        to_json_string(dust_balances)?,
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use cosmwasm_std::{coin, Uint128};
+
+    use super::split_dust_balances;
+
+    #[test]
+    fn test_split_dust_balances() {
+        let min_route_amounts = BTreeMap::from([
+            ("uatom".to_string(), Uint128::new(100)),
+            ("untrn".to_string(), Uint128::new(10)),
+        ]);
+
+        let (balances, dust) = split_dust_balances(
+            vec![coin(99, "uatom"), coin(10, "untrn"), coin(1, "uosmo")],
+            &min_route_amounts,
+        );
+
+        assert_eq!(balances, vec![coin(10, "untrn"), coin(1, "uosmo")]);
+        assert_eq!(dust, vec![coin(99, "uatom")]);
+    }
+}
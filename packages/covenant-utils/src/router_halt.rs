@@ -0,0 +1,139 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, Attribute, BankMsg, Coin, CustomQuery, Env, QuerierWrapper,
+    StdError, StdResult, WasmMsg,
+};
+use covenant_macros::covenant_router_halt;
+use cw_storage_plus::Item;
+use neutron_sdk::NeutronError;
+use thiserror::Error;
+
+/// set once the router got halted
+pub const ROUTER_HALTED: Item<bool> = Item::new("router_halted");
+
+#[covenant_router_halt]
+#[cw_serde]
+pub enum RouterHaltMsgs {}
+
+#[cw_serde]
+enum HolderQueryMsg {
+    ContractState {},
+}
+
+/// contract states reported by the covenant holders
+#[cw_serde]
+pub enum HolderContractState {
+    Instantiated,
+    Active,
+    Ragequit,
+    Expired,
+    Settling,
+    Complete,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RouterHaltError {
+    #[error("router halting is not configured")]
+    NotConfigured,
+
+    #[error("only the covenant or the emergency committee can halt the router")]
+    Unauthorized,
+
+    #[error("router is halted")]
+    Halted,
+
+    #[error("router has no holder to be swept after")]
+    HolderNotConfigured,
+
+    #[error("router can only be swept once the holder is complete")]
+    HolderNotComplete,
+}
+
+impl From<RouterHaltError> for NeutronError {
+    fn from(val: RouterHaltError) -> Self {
+        NeutronError::Std(StdError::generic_err(val.to_string()))
+    }
+}
+
+/// configures who can halt a router and where its remaining balances go.
+/// besides the emergency committee, the router can always be halted by
+/// its admin, which is the covenant that instantiated it.
+#[cw_serde]
+pub struct RouterHaltConfig {
+    /// holder that receives the remaining router balances once halted
+    pub holder_address: String,
+    /// optional emergency committee allowed to halt the router
+    pub emergency_committee: Option<String>,
+}
+
+impl RouterHaltConfig {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        api.addr_validate(&self.holder_address)?;
+        if let Some(addr) = &self.emergency_committee {
+            api.addr_validate(addr)?;
+        }
+        Ok(())
+    }
+
+    /// errors unless the sender is either the router admin or the emergency committee
+    pub fn verify_halt_authority<C: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<C>,
+        env: &Env,
+        sender: &Addr,
+    ) -> Result<(), RouterHaltError> {
+        if self.emergency_committee.as_deref() == Some(sender.as_str()) {
+            return Ok(());
+        }
+
+        let admin = querier
+            .query_wasm_contract_info(env.contract.address.to_string())
+            .map_err(|_| RouterHaltError::Unauthorized)?
+            .admin;
+        if admin.as_deref() == Some(sender.as_str()) {
+            Ok(())
+        } else {
+            Err(RouterHaltError::Unauthorized)
+        }
+    }
+
+    /// bank sends returning the (non-zero) balances to the holder
+    pub fn get_return_msgs(&self, balances: Vec<Coin>) -> Vec<BankMsg> {
+        balances
+            .into_iter()
+            .filter(|c| !c.amount.is_zero())
+            .map(|c| BankMsg::Send {
+                to_address: self.holder_address.to_string(),
+                amount: vec![c],
+            })
+            .collect()
+    }
+
+    pub fn get_response_attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("halt_holder_address", &self.holder_address),
+            Attribute::new(
+                "halt_emergency_committee",
+                self.emergency_committee.as_deref().unwrap_or("none"),
+            ),
+        ]
+    }
+}
+
+/// whether the holder reports its contract state as complete
+pub fn query_holder_complete<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    holder: &Addr,
+) -> StdResult<bool> {
+    let contract_state: HolderContractState =
+        querier.query_wasm_smart(holder, &HolderQueryMsg::ContractState {})?;
+    Ok(contract_state == HolderContractState::Complete)
+}
+
+pub fn generate_halt_msg(router: String) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: router,
+        msg: to_json_binary(&RouterHaltMsgs::Halt {})?,
+        funds: vec![],
+    })
+}
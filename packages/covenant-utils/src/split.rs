@@ -1,14 +1,55 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    Api, Attribute, BankMsg, Coin, CosmosMsg, Decimal, Fraction, StdError, StdResult, Uint128,
+    Addr, Api, Attribute, BankMsg, Coin, CosmosMsg, Decimal, Event, Fraction, StdError, StdResult,
+    Uint128, Uint256,
 };
 
+use crate::soft_validate_remote_chain_addr;
+
 #[cw_serde]
 pub struct SplitConfig {
     /// map receiver address to its share of the split
     pub receivers: BTreeMap<String, Decimal>,
+    /// policy determining where the integer division remainders go
+    #[serde(default)]
+    pub rounding: SplitRounding,
+    /// map receiver address to the minimum amount distributed to it at once.
+    /// smaller entitlements accrue until they reach it.
+    #[serde(default)]
+    pub min_amounts: BTreeMap<String, Uint128>,
+}
+
+/// every receiver entitlement is rounded down. because of that, splitting
+/// an amount may leave some remainder (dust) that is not allocated to anyone.
+/// this policy determines where that remainder goes.
+#[cw_serde]
+#[derive(Default)]
+pub enum SplitRounding {
+    /// remainder goes to the first receiver (in address order)
+    /// with a non-zero share. this is how splitters allocated the
+    /// remainder before the policy was configurable.
+    #[default]
+    FloorToFirst,
+    /// remainder goes to the last receiver (in address order)
+    /// with a non-zero share
+    FloorToLast,
+    /// remainder is divided equally between the receivers, and the units
+    /// that do not divide equally go one each to the receivers whose
+    /// entitlements were truncated the most
+    ProRata,
+    /// remainder goes to the specified address
+    SinkAddress(String),
+}
+
+impl SplitRounding {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        if let SplitRounding::SinkAddress(addr) = self {
+            api.addr_validate(addr)?;
+        }
+        Ok(())
+    }
 }
 
 impl SplitConfig {
@@ -38,11 +79,46 @@ impl SplitConfig {
             }
         };
 
+        let mut new_min_amounts = BTreeMap::new();
+        if let Some(min_amount) = self.min_amounts.get(&receiver_a) {
+            new_min_amounts.insert(router_a, *min_amount);
+        }
+        if let Some(min_amount) = self.min_amounts.get(&receiver_b) {
+            new_min_amounts.insert(router_b, *min_amount);
+        }
+
         Ok(SplitConfig {
             receivers: new_receivers,
+            rounding: self.rounding.clone(),
+            min_amounts: new_min_amounts,
         })
     }
 
+    /// moves the share (along with the min amount and the rounding
+    /// remainder, if sunk to it) of the `old` receiver over to the `new`
+    /// receiver. splits not involving `old` are left untouched.
+    /// errors if `new` already receives a share of the split.
+    pub fn replace_receiver(&mut self, old: &str, new: &str) -> StdResult<()> {
+        if old == new || !self.receivers.contains_key(old) {
+            return Ok(());
+        }
+        if self.receivers.contains_key(new) {
+            return Err(StdError::generic_err(format!(
+                "receiver {new:?} is already part of the split"
+            )));
+        }
+        if let Some(share) = self.receivers.remove(old) {
+            self.receivers.insert(new.to_string(), share);
+        }
+        if let Some(min_amount) = self.min_amounts.remove(old) {
+            self.min_amounts.insert(new.to_string(), min_amount);
+        }
+        if self.rounding == SplitRounding::SinkAddress(old.to_string()) {
+            self.rounding = SplitRounding::SinkAddress(new.to_string());
+        }
+        Ok(())
+    }
+
     pub fn validate(&self, party_a: &str, party_b: &str) -> Result<(), StdError> {
         let share_a = match self.receivers.get(party_a) {
             Some(val) => *val,
@@ -59,15 +135,29 @@ impl SplitConfig {
             ));
         }
 
-        Ok(())
+        self.validate_min_amounts()
     }
 
     /// Validate that all shares are added to one
     pub fn validate_shares_and_receiver_addresses(&self, api: &dyn Api) -> Result<(), StdError> {
+        self.validate_shares_and_receivers(api, &BTreeSet::new())
+    }
+
+    /// like `validate_shares_and_receiver_addresses`, except that the
+    /// `remote_receivers` are soft-validated as remote chain addresses
+    pub fn validate_shares_and_receivers(
+        &self,
+        api: &dyn Api,
+        remote_receivers: &BTreeSet<String>,
+    ) -> Result<(), StdError> {
         let mut total_shares = Decimal::zero();
 
         for (addr, share) in self.receivers.clone() {
-            api.addr_validate(&addr)?;
+            if remote_receivers.contains(&addr) {
+                soft_validate_remote_chain_addr(api, &addr)?;
+            } else {
+                api.addr_validate(&addr)?;
+            }
             total_shares += share;
         }
 
@@ -77,53 +167,168 @@ impl SplitConfig {
             ));
         }
 
+        self.validate_min_amounts()?;
+        match &self.rounding {
+            SplitRounding::SinkAddress(sink) if remote_receivers.contains(sink) => Ok(()),
+            rounding => rounding.validate(api),
+        }
+    }
+
+    /// validates that min amounts are only set for receivers of the split
+    pub fn validate_min_amounts(&self) -> StdResult<()> {
+        for addr in self.min_amounts.keys() {
+            if !self.receivers.contains_key(addr) {
+                return Err(StdError::generic_err(format!(
+                    "min amount set for unknown receiver {addr:?}"
+                )));
+            }
+        }
         Ok(())
     }
 
+    /// splits the amount between the receivers according to their shares
+    /// and the rounding policy. the returned amounts always add up to the
+    /// full amount, and zero amounts are omitted.
+    pub fn get_split_amounts(&self, amount: Uint128) -> StdResult<Vec<(String, Uint128)>> {
+        let mut entitlements: Vec<(String, Uint128)> = Vec::with_capacity(self.receivers.len());
+        // truncated fractions (numerators over the share denominators) used
+        // to rank the receivers when distributing the remainder pro rata
+        let mut truncations: Vec<Uint256> = Vec::with_capacity(self.receivers.len());
+
+        for (addr, share) in self.receivers.iter().filter(|(_, share)| !share.is_zero()) {
+            let entitlement = amount
+                .checked_multiply_ratio(share.numerator(), share.denominator())
+                .map_err(|_| StdError::generic_err("failed to checked_multiply".to_string()))?;
+            let truncation =
+                amount.full_mul(share.numerator()) % Uint256::from_uint128(share.denominator());
+
+            entitlements.push((addr.to_string(), entitlement));
+            truncations.push(truncation);
+        }
+
+        let allocated = entitlements
+            .iter()
+            .try_fold(Uint128::zero(), |acc, (_, amt)| acc.checked_add(*amt))?;
+        let remainder = amount
+            .checked_sub(allocated)
+            .map_err(|_| StdError::generic_err("split shares exceed 1.0".to_string()))?;
+
+        if !remainder.is_zero() {
+            match &self.rounding {
+                SplitRounding::FloorToFirst => {
+                    if let Some((_, amt)) = entitlements.first_mut() {
+                        *amt += remainder;
+                    }
+                }
+                SplitRounding::FloorToLast => {
+                    if let Some((_, amt)) = entitlements.last_mut() {
+                        *amt += remainder;
+                    }
+                }
+                SplitRounding::ProRata => {
+                    let receivers_count = Uint128::from(entitlements.len() as u128);
+                    if !receivers_count.is_zero() {
+                        // every receiver gets an equal part of the remainder,
+                        // and whatever is left goes to the ones with the largest
+                        // truncations. ties are resolved in address order.
+                        let base = remainder / receivers_count;
+                        let extra = (remainder % receivers_count).u128() as usize;
+
+                        let mut ranking: Vec<usize> = (0..entitlements.len()).collect();
+                        ranking.sort_by(|a, b| truncations[*b].cmp(&truncations[*a]));
+
+                        for (rank, i) in ranking.into_iter().enumerate() {
+                            let bonus = if rank < extra {
+                                Uint128::one()
+                            } else {
+                                Uint128::zero()
+                            };
+                            entitlements[i].1 += base + bonus;
+                        }
+                    }
+                }
+                SplitRounding::SinkAddress(sink) => {
+                    match entitlements.iter_mut().find(|(addr, _)| addr == sink) {
+                        Some((_, amt)) => *amt += remainder,
+                        None => entitlements.push((sink.to_string(), remainder)),
+                    }
+                }
+            }
+        }
+
+        Ok(entitlements
+            .into_iter()
+            .filter(|(_, amt)| !amt.is_zero())
+            .collect())
+    }
+
+    /// splits the amount like `get_split_amounts`, and adds the entitlements
+    /// to the amounts accrued by the receivers so far. returns the accrued
+    /// amounts that reached the min amount of their receiver (or all of them,
+    /// if `flush` is set), which are to be distributed. the rest is kept in
+    /// `accrued` until a later distribution.
+    pub fn accrue_split_amounts(
+        &self,
+        amount: Uint128,
+        accrued: &mut BTreeMap<String, Uint128>,
+        flush: bool,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        for (addr, amt) in self.get_split_amounts(amount)? {
+            let accrued_amt = accrued.entry(addr).or_default();
+            *accrued_amt = accrued_amt.checked_add(amt)?;
+        }
+
+        let mut distributions = vec![];
+        accrued.retain(|addr, amt| {
+            let min_amount = self.min_amounts.get(addr).copied().unwrap_or_default();
+            if flush || *amt >= min_amount {
+                if !amt.is_zero() {
+                    distributions.push((addr.to_string(), *amt));
+                }
+                false
+            } else {
+                true
+            }
+        });
+
+        Ok(distributions)
+    }
+
+    /// transfer messages for the accrued amounts that are to be
+    /// distributed, as determined by `accrue_split_amounts`
+    pub fn get_accrued_transfer_messages(
+        &self,
+        amount: Uint128,
+        denom: String,
+        accrued: &mut BTreeMap<String, Uint128>,
+        flush: bool,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let distributions = self.accrue_split_amounts(amount, accrued, flush)?;
+        Ok(get_bank_transfer_messages(distributions, &denom))
+    }
+
     pub fn get_transfer_messages(
         &self,
         amount: Uint128,
         denom: String,
         filter_addr: Option<String>,
     ) -> Result<Vec<CosmosMsg>, StdError> {
-        let msgs: Result<Vec<CosmosMsg>, StdError> = self
-            .receivers
-            .iter()
-            .map(|(addr, share)| {
-                // if we are filtering for a single receiver,
-                // then we wish to transfer only to that receiver.
-                // we thus set receiver share to 1.0, as the
-                // entitlement already takes that into account.
-                match &filter_addr {
-                    Some(filter) => {
-                        if filter == addr {
-                            (addr, Decimal::one())
-                        } else {
-                            (addr, Decimal::zero())
-                        }
-                    }
-                    None => (addr, *share),
+        let entitlements = match &filter_addr {
+            // if we are filtering for a single receiver,
+            // then we wish to transfer only to that receiver.
+            // the entitlement already takes the share into account,
+            // so the receiver gets the full amount.
+            Some(filter) => {
+                if self.receivers.contains_key(filter) && !amount.is_zero() {
+                    vec![(filter.to_string(), amount)]
+                } else {
+                    vec![]
                 }
-            })
-            .filter(|(_, share)| !share.is_zero())
-            .map(|(addr, share)| {
-                let entitlement = amount
-                    .checked_multiply_ratio(share.numerator(), share.denominator())
-                    .map_err(|_| StdError::generic_err("failed to checked_multiply".to_string()))?;
-
-                let amount = Coin {
-                    denom: denom.to_string(),
-                    amount: entitlement,
-                };
-
-                Ok(CosmosMsg::Bank(BankMsg::Send {
-                    to_address: addr.to_string(),
-                    amount: vec![amount],
-                }))
-            })
-            .collect();
+            }
+            None => self.get_split_amounts(amount)?,
+        };
 
-        msgs
+        Ok(get_bank_transfer_messages(entitlements, &denom))
     }
 
     pub fn get_response_attribute(&self, denom: String) -> Attribute {
@@ -140,6 +345,32 @@ impl SplitConfig {
     }
 }
 
+fn get_bank_transfer_messages(amounts: Vec<(String, Uint128)>, denom: &str) -> Vec<CosmosMsg> {
+    amounts
+        .into_iter()
+        .map(|(addr, amount)| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: addr,
+                amount: vec![Coin {
+                    denom: denom.to_string(),
+                    amount,
+                }],
+            })
+        })
+        .collect()
+}
+
+/// event emitted once the op admin updated the splits at runtime
+pub fn get_splits_updated_event(op_admin: &Addr, splits: &BTreeMap<String, SplitConfig>) -> Event {
+    Event::new("splits_updated")
+        .add_attribute("op_admin", op_admin)
+        .add_attributes(
+            splits
+                .iter()
+                .map(|(denom, split)| split.get_response_attribute(denom.to_string())),
+        )
+}
+
 pub fn remap_splits(
     splits: BTreeMap<String, SplitConfig>,
     (party_a_receiver, party_a_router): (String, String),
@@ -159,3 +390,402 @@ pub fn remap_splits(
 
     Ok(remapped_splits)
 }
+
+/// splits of the given denoms between both party receivers according to
+/// their allocations, used in place of splits omitted at instantiation
+pub fn get_allocation_splits(
+    denoms: &BTreeSet<String>,
+    (party_a_receiver, party_a_share): (&str, Decimal),
+    (party_b_receiver, party_b_share): (&str, Decimal),
+) -> BTreeMap<String, SplitConfig> {
+    let split = SplitConfig {
+        receivers: BTreeMap::from([
+            (party_a_receiver.to_string(), party_a_share),
+            (party_b_receiver.to_string(), party_b_share),
+        ]),
+        rounding: SplitRounding::default(),
+        min_amounts: BTreeMap::new(),
+    };
+
+    denoms
+        .iter()
+        .map(|denom| (denom.to_string(), split.clone()))
+        .collect()
+}
+
+/// validates that the splits configure exactly the expected denoms (the
+/// party contribution denoms along with any reward denoms), and that every
+/// split is between the final receivers of both parties. otherwise some of
+/// the covenant funds could only be distributed with the fallback split.
+pub fn validate_covenant_splits(
+    splits: &BTreeMap<String, SplitConfig>,
+    expected_denoms: &BTreeSet<String>,
+    (party_a_receiver, party_b_receiver): (&str, &str),
+) -> StdResult<()> {
+    if let Some(denom) = expected_denoms.iter().find(|d| !splits.contains_key(*d)) {
+        return Err(StdError::generic_err(format!(
+            "missing split configuration for denom {denom}"
+        )));
+    }
+    if let Some(denom) = splits.keys().find(|d| !expected_denoms.contains(*d)) {
+        return Err(StdError::generic_err(format!(
+            "denom {denom} is neither contributed nor a reward denom"
+        )));
+    }
+
+    let party_receivers = BTreeSet::from([party_a_receiver, party_b_receiver]);
+    for (denom, split) in splits {
+        let receivers: BTreeSet<&str> = split.receivers.keys().map(String::as_str).collect();
+        if receivers != party_receivers {
+            return Err(StdError::generic_err(format!(
+                "split of {denom} must be between the party receivers {party_a_receiver} and {party_b_receiver}"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        str::FromStr,
+    };
+
+    use cosmwasm_std::{Decimal, Uint128};
+
+    use super::{get_allocation_splits, validate_covenant_splits, SplitConfig, SplitRounding};
+
+    fn split(shares: &[(&str, &str)], rounding: SplitRounding) -> SplitConfig {
+        SplitConfig {
+            receivers: shares
+                .iter()
+                .map(|(addr, share)| (addr.to_string(), Decimal::from_str(share).unwrap()))
+                .collect::<BTreeMap<String, Decimal>>(),
+            rounding,
+            min_amounts: BTreeMap::new(),
+        }
+    }
+
+    fn total(amounts: &[(String, Uint128)]) -> Uint128 {
+        amounts.iter().map(|(_, amt)| *amt).sum()
+    }
+
+    fn amount_of(amounts: &[(String, Uint128)], addr: &str) -> Uint128 {
+        amounts
+            .iter()
+            .find(|(a, _)| a == addr)
+            .map(|(_, amt)| *amt)
+            .unwrap_or_default()
+    }
+
+    /// share sets designed to maximize the truncation of every entitlement
+    fn adversarial_share_sets() -> Vec<Vec<(&'static str, &'static str)>> {
+        vec![
+            vec![
+                ("a", "0.333333333333333333"),
+                ("b", "0.333333333333333333"),
+                ("c", "0.333333333333333334"),
+            ],
+            vec![("a", "0.999999999999999999"), ("b", "0.000000000000000001")],
+            vec![
+                ("a", "0.142857142857142857"),
+                ("b", "0.142857142857142857"),
+                ("c", "0.142857142857142857"),
+                ("d", "0.142857142857142857"),
+                ("e", "0.142857142857142857"),
+                ("f", "0.142857142857142857"),
+                ("g", "0.142857142857142858"),
+            ],
+            vec![("a", "0.5"), ("b", "0.5")],
+            vec![("a", "1.0"), ("b", "0.0")],
+        ]
+    }
+
+    fn adversarial_amounts() -> Vec<Uint128> {
+        vec![
+            Uint128::zero(),
+            Uint128::one(),
+            Uint128::new(2),
+            Uint128::new(6),
+            Uint128::new(100_001),
+            Uint128::new(999_999_999_999_999_999),
+            Uint128::MAX / Uint128::new(1_000_000_000_000_000_000),
+        ]
+    }
+
+    #[test]
+    fn test_split_amounts_conserve_total() {
+        let policies = vec![
+            SplitRounding::FloorToFirst,
+            SplitRounding::FloorToLast,
+            SplitRounding::ProRata,
+            SplitRounding::SinkAddress("sink".to_string()),
+        ];
+
+        for shares in adversarial_share_sets() {
+            for rounding in policies.iter() {
+                let config = split(&shares, rounding.clone());
+                for amount in adversarial_amounts() {
+                    let amounts = config.get_split_amounts(amount).unwrap();
+                    assert_eq!(total(&amounts), amount, "{shares:?} {rounding:?} {amount}");
+                    assert!(amounts.iter().all(|(_, amt)| !amt.is_zero()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_rounding_floors_to_first() {
+        // remote chain splitter payouts from before the rounding policy
+        let config = split(&[("a", "0.107"), ("b", "0.893")], SplitRounding::default());
+        let amounts = config.get_split_amounts(Uint128::new(100)).unwrap();
+
+        assert_eq!(config.rounding, SplitRounding::FloorToFirst);
+        assert_eq!(amount_of(&amounts, "a"), Uint128::new(11));
+        assert_eq!(amount_of(&amounts, "b"), Uint128::new(89));
+    }
+
+    #[test]
+    fn test_floor_to_first_skips_zero_shares() {
+        let config = split(&[("a", "0.0"), ("b", "1.0")], SplitRounding::FloorToFirst);
+        let amounts = config.get_split_amounts(Uint128::new(7)).unwrap();
+
+        assert_eq!(amounts, vec![("b".to_string(), Uint128::new(7))]);
+    }
+
+    #[test]
+    fn test_floor_to_last() {
+        let config = split(
+            &[
+                ("a", "0.333333333333333333"),
+                ("b", "0.333333333333333333"),
+                ("c", "0.333333333333333334"),
+            ],
+            SplitRounding::FloorToLast,
+        );
+        let amounts = config.get_split_amounts(Uint128::new(2)).unwrap();
+
+        assert_eq!(amounts, vec![("c".to_string(), Uint128::new(2))]);
+    }
+
+    #[test]
+    fn test_floor_to_last_skips_zero_shares() {
+        let config = split(&[("a", "1.0"), ("b", "0.0")], SplitRounding::FloorToLast);
+        let amounts = config.get_split_amounts(Uint128::new(7)).unwrap();
+
+        assert_eq!(amounts, vec![("a".to_string(), Uint128::new(7))]);
+    }
+
+    #[test]
+    fn test_pro_rata() {
+        let config = split(
+            &[("a", "0.3"), ("b", "0.3"), ("c", "0.4")],
+            SplitRounding::ProRata,
+        );
+        // entitlements are 3.3, 3.3 and 4.4, so the single remainder unit
+        // goes to the receiver with the largest truncation
+        let amounts = config.get_split_amounts(Uint128::new(11)).unwrap();
+
+        assert_eq!(amount_of(&amounts, "a"), Uint128::new(3));
+        assert_eq!(amount_of(&amounts, "b"), Uint128::new(3));
+        assert_eq!(amount_of(&amounts, "c"), Uint128::new(5));
+    }
+
+    #[test]
+    fn test_pro_rata_favors_largest_truncation() {
+        let config = split(&[("a", "0.25"), ("b", "0.75")], SplitRounding::ProRata);
+        // entitlements are 0.5 and 1.5, tie is resolved in address order
+        let amounts = config.get_split_amounts(Uint128::new(2)).unwrap();
+        assert_eq!(amount_of(&amounts, "a"), Uint128::new(1));
+        assert_eq!(amount_of(&amounts, "b"), Uint128::new(1));
+
+        // entitlements are 0.25 and 0.75
+        let amounts = config.get_split_amounts(Uint128::new(1)).unwrap();
+        assert_eq!(amounts, vec![("b".to_string(), Uint128::new(1))]);
+    }
+
+    #[test]
+    fn test_sink_address() {
+        let config = split(
+            &[("a", "0.5"), ("b", "0.5")],
+            SplitRounding::SinkAddress("sink".to_string()),
+        );
+        let amounts = config.get_split_amounts(Uint128::new(101)).unwrap();
+
+        assert_eq!(amount_of(&amounts, "a"), Uint128::new(50));
+        assert_eq!(amount_of(&amounts, "b"), Uint128::new(50));
+        assert_eq!(amount_of(&amounts, "sink"), Uint128::new(1));
+    }
+
+    #[test]
+    fn test_sink_address_receiver() {
+        let config = split(
+            &[("a", "0.5"), ("b", "0.5")],
+            SplitRounding::SinkAddress("a".to_string()),
+        );
+        let amounts = config.get_split_amounts(Uint128::new(101)).unwrap();
+
+        assert_eq!(amounts.len(), 2);
+        assert_eq!(amount_of(&amounts, "a"), Uint128::new(51));
+        assert_eq!(amount_of(&amounts, "b"), Uint128::new(50));
+    }
+
+    #[test]
+    fn test_accrue_split_amounts() {
+        let mut config = split(&[("a", "0.1"), ("b", "0.9")], SplitRounding::FloorToLast);
+        config.min_amounts.insert("a".to_string(), Uint128::new(20));
+        let mut accrued = BTreeMap::new();
+
+        // a is entitled to 10, which accrues below its min amount
+        let amounts = config
+            .accrue_split_amounts(Uint128::new(100), &mut accrued, false)
+            .unwrap();
+        assert_eq!(amounts, vec![("b".to_string(), Uint128::new(90))]);
+        assert_eq!(accrued.get("a"), Some(&Uint128::new(10)));
+
+        // crossing the min amount distributes everything accrued so far
+        let amounts = config
+            .accrue_split_amounts(Uint128::new(100), &mut accrued, false)
+            .unwrap();
+        assert_eq!(amount_of(&amounts, "a"), Uint128::new(20));
+        assert_eq!(amount_of(&amounts, "b"), Uint128::new(90));
+        assert!(accrued.is_empty());
+
+        // flushing ignores the min amounts
+        config
+            .accrue_split_amounts(Uint128::new(100), &mut accrued, false)
+            .unwrap();
+        let amounts = config
+            .accrue_split_amounts(Uint128::zero(), &mut accrued, true)
+            .unwrap();
+        assert_eq!(amounts, vec![("a".to_string(), Uint128::new(10))]);
+        assert!(accrued.is_empty());
+    }
+
+    #[test]
+    fn test_validate_min_amounts() {
+        let mut config = split(&[("a", "0.5"), ("b", "0.5")], SplitRounding::FloorToLast);
+        config.min_amounts.insert("a".to_string(), Uint128::new(20));
+        config.validate("a", "b").unwrap();
+
+        config.min_amounts.insert("c".to_string(), Uint128::new(20));
+        config.validate("a", "b").unwrap_err();
+    }
+
+    #[test]
+    fn test_replace_receiver() {
+        let mut config = split(
+            &[("a", "0.3"), ("b", "0.7")],
+            SplitRounding::SinkAddress("a".to_string()),
+        );
+        config.replace_receiver("a", "c").unwrap();
+
+        assert_eq!(
+            config,
+            split(
+                &[("c", "0.3"), ("b", "0.7")],
+                SplitRounding::SinkAddress("c".to_string()),
+            )
+        );
+
+        // unknown receivers are a no-op
+        config.replace_receiver("d", "e").unwrap();
+        assert_eq!(config.receivers.len(), 2);
+    }
+
+    #[test]
+    fn test_replace_receiver_rejects_existing_receiver() {
+        let mut config = split(&[("a", "0.3"), ("b", "0.7")], SplitRounding::ProRata);
+
+        config.replace_receiver("a", "b").unwrap_err();
+        // the split is left untouched
+        assert_eq!(
+            config,
+            split(&[("a", "0.3"), ("b", "0.7")], SplitRounding::ProRata)
+        );
+    }
+
+    #[test]
+    fn test_transfer_messages_with_filter() {
+        let config = split(&[("a", "0.5"), ("b", "0.5")], SplitRounding::ProRata);
+
+        let msgs = config
+            .get_transfer_messages(
+                Uint128::new(101),
+                "denom".to_string(),
+                Some("a".to_string()),
+            )
+            .unwrap();
+        assert_eq!(msgs.len(), 1);
+
+        let msgs = config
+            .get_transfer_messages(
+                Uint128::new(101),
+                "denom".to_string(),
+                Some("c".to_string()),
+            )
+            .unwrap();
+        assert!(msgs.is_empty());
+    }
+
+    fn covenant_splits(denoms: &[&str], receivers: &[&str]) -> BTreeMap<String, SplitConfig> {
+        let shares: Vec<(&str, &str)> = receivers.iter().map(|r| (*r, "0.5")).collect();
+        denoms
+            .iter()
+            .map(|d| (d.to_string(), split(&shares, SplitRounding::FloorToLast)))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_covenant_splits() {
+        let expected = BTreeSet::from(["uatom".to_string(), "untrn".to_string()]);
+        let splits = covenant_splits(&["uatom", "untrn"], &["a", "b"]);
+
+        validate_covenant_splits(&splits, &expected, ("a", "b")).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "missing split configuration for denom untrn")]
+    fn test_validate_covenant_splits_missing_denom() {
+        let expected = BTreeSet::from(["uatom".to_string(), "untrn".to_string()]);
+        let splits = covenant_splits(&["uatom"], &["a", "b"]);
+
+        validate_covenant_splits(&splits, &expected, ("a", "b")).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "denom uosmo is neither contributed nor a reward denom")]
+    fn test_validate_covenant_splits_unexpected_denom() {
+        let expected = BTreeSet::from(["uatom".to_string()]);
+        let splits = covenant_splits(&["uatom", "uosmo"], &["a", "b"]);
+
+        validate_covenant_splits(&splits, &expected, ("a", "b")).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "split of uatom must be between the party receivers a and b")]
+    fn test_validate_covenant_splits_receivers() {
+        let expected = BTreeSet::from(["uatom".to_string()]);
+        let splits = covenant_splits(&["uatom"], &["a", "c"]);
+
+        validate_covenant_splits(&splits, &expected, ("a", "b")).unwrap();
+    }
+
+    #[test]
+    fn test_allocation_splits() {
+        let expected = BTreeSet::from(["uatom".to_string(), "untrn".to_string()]);
+        let splits = get_allocation_splits(
+            &expected,
+            ("a", Decimal::percent(60)),
+            ("b", Decimal::percent(40)),
+        );
+
+        validate_covenant_splits(&splits, &expected, ("a", "b")).unwrap();
+        assert_eq!(
+            splits["untrn"],
+            split(&[("a", "0.6"), ("b", "0.4")], SplitRounding::default())
+        );
+    }
+}
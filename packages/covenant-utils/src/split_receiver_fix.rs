@@ -0,0 +1,272 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{BlockInfo, Event, StdError, StdResult, Storage, Timestamp};
+use cw_storage_plus::Map;
+
+use crate::split::SplitConfig;
+
+/// delay between the emergency committee submitting a split receiver fix
+/// and the fix being applicable
+pub const SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS: u64 = 24 * 60 * 60;
+
+/// split receiver fixes awaiting their timelock, keyed by denom.
+/// scheduled fixes refer to the splits they were submitted against,
+/// so contracts clear them whenever those splits get replaced.
+pub const PENDING_SPLIT_RECEIVER_FIXES: Map<String, SplitReceiverFix> =
+    Map::new("pending_split_receiver_fixes");
+
+/// correction of a mis-entered split receiver address
+#[cw_serde]
+pub struct SplitReceiverFix {
+    /// denom of the split to correct
+    pub denom: String,
+    /// receiver address to be replaced
+    pub old: String,
+    /// address taking over the share of the `old` receiver
+    pub new: String,
+    /// time after which resubmitting the fix applies it
+    pub executable_at: Timestamp,
+}
+
+impl SplitReceiverFix {
+    pub fn new(denom: String, old: String, new: String, block: &BlockInfo) -> Self {
+        SplitReceiverFix {
+            denom,
+            old,
+            new,
+            executable_at: block.time.plus_seconds(SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS),
+        }
+    }
+
+    /// whether both fixes correct the same receiver in the same way
+    pub fn is_same_fix(&self, other: &SplitReceiverFix) -> bool {
+        self.denom == other.denom && self.old == other.old && self.new == other.new
+    }
+
+    pub fn to_event(&self, status: &str) -> Event {
+        Event::new(format!("split_receiver_fix_{status}"))
+            .add_attribute("denom", &self.denom)
+            .add_attribute("old", &self.old)
+            .add_attribute("new", &self.new)
+            .add_attribute("executable_at", self.executable_at.to_string())
+    }
+}
+
+/// outcome of submitting a split receiver fix
+#[cw_serde]
+pub enum SplitReceiverFixStatus {
+    /// the fix is pending until its timelock expires
+    Scheduled(SplitReceiverFix),
+    /// the fix was applied to the split
+    Applied(SplitReceiverFix),
+}
+
+impl SplitReceiverFixStatus {
+    pub fn to_event(&self) -> Event {
+        match self {
+            SplitReceiverFixStatus::Scheduled(fix) => fix.to_event("scheduled"),
+            SplitReceiverFixStatus::Applied(fix) => fix.to_event("applied"),
+        }
+    }
+}
+
+/// submits the fix against the split of its denom.
+/// the first submission schedules the fix, and resubmitting it once
+/// the timelock of the `pending` fix expired applies it to the split.
+/// submitting a different fix reschedules with a fresh timelock.
+pub fn submit_split_receiver_fix(
+    split: &mut SplitConfig,
+    pending: Option<SplitReceiverFix>,
+    fix: SplitReceiverFix,
+    block: &BlockInfo,
+) -> StdResult<SplitReceiverFixStatus> {
+    if !split.receivers.contains_key(&fix.old) {
+        return Err(StdError::not_found(format!("receiver {:?}", fix.old)));
+    }
+    if split.receivers.contains_key(&fix.new) {
+        return Err(StdError::generic_err(format!(
+            "receiver {:?} is already part of the split",
+            fix.new
+        )));
+    }
+
+    match pending {
+        Some(pending) if pending.is_same_fix(&fix) => {
+            if block.time < pending.executable_at {
+                return Err(StdError::generic_err(format!(
+                    "split receiver fix is timelocked until {}",
+                    pending.executable_at
+                )));
+            }
+            split.replace_receiver(&pending.old, &pending.new)?;
+            Ok(SplitReceiverFixStatus::Applied(pending))
+        }
+        _ => Ok(SplitReceiverFixStatus::Scheduled(fix)),
+    }
+}
+
+/// submits the replacement of the `old` receiver of the split of `denom`
+/// and keeps track of the pending fix. once the fix is applied to `split`,
+/// the caller is left to persist it.
+pub fn fix_split_receiver(
+    storage: &mut dyn Storage,
+    split: &mut SplitConfig,
+    denom: String,
+    old: String,
+    new: String,
+    block: &BlockInfo,
+) -> StdResult<SplitReceiverFixStatus> {
+    let fix = SplitReceiverFix::new(denom.to_string(), old, new, block);
+    let pending = PENDING_SPLIT_RECEIVER_FIXES.may_load(storage, denom.to_string())?;
+    let status = submit_split_receiver_fix(split, pending, fix, block)?;
+
+    match &status {
+        SplitReceiverFixStatus::Scheduled(fix) => {
+            PENDING_SPLIT_RECEIVER_FIXES.save(storage, denom, fix)?;
+        }
+        SplitReceiverFixStatus::Applied(_) => {
+            PENDING_SPLIT_RECEIVER_FIXES.remove(storage, denom);
+        }
+    }
+    Ok(status)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, str::FromStr};
+
+    use cosmwasm_std::{
+        testing::{mock_env, MockStorage},
+        Decimal,
+    };
+
+    use crate::split::SplitConfig;
+
+    use super::{
+        fix_split_receiver, submit_split_receiver_fix, SplitReceiverFix, SplitReceiverFixStatus,
+        PENDING_SPLIT_RECEIVER_FIXES, SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS,
+    };
+
+    #[test]
+    fn test_submit_split_receiver_fix() {
+        let mut split = SplitConfig {
+            receivers: BTreeMap::from([
+                ("a".to_string(), Decimal::from_str("0.3").unwrap()),
+                ("b".to_string(), Decimal::from_str("0.7").unwrap()),
+            ]),
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        };
+        let mut block = mock_env().block;
+        let fix = SplitReceiverFix::new(
+            "denom".to_string(),
+            "a".to_string(),
+            "c".to_string(),
+            &block,
+        );
+
+        // first submission schedules the fix
+        let status = submit_split_receiver_fix(&mut split, None, fix.clone(), &block).unwrap();
+        assert_eq!(status, SplitReceiverFixStatus::Scheduled(fix.clone()));
+
+        // resubmitting within the timelock errors
+        block.time = block
+            .time
+            .plus_seconds(SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS - 1);
+        let resubmitted = SplitReceiverFix::new(
+            "denom".to_string(),
+            "a".to_string(),
+            "c".to_string(),
+            &block,
+        );
+        submit_split_receiver_fix(&mut split, Some(fix.clone()), resubmitted.clone(), &block)
+            .unwrap_err();
+
+        // resubmitting after the timelock applies the pending fix
+        block.time = block.time.plus_seconds(1);
+        let status =
+            submit_split_receiver_fix(&mut split, Some(fix.clone()), resubmitted, &block).unwrap();
+        assert_eq!(status, SplitReceiverFixStatus::Applied(fix));
+        assert_eq!(
+            split.receivers.get("c"),
+            Some(&Decimal::from_str("0.3").unwrap())
+        );
+        assert!(!split.receivers.contains_key("a"));
+    }
+
+    #[test]
+    fn test_submit_split_receiver_fix_validates_receivers() {
+        let mut split = SplitConfig {
+            receivers: BTreeMap::from([
+                ("a".to_string(), Decimal::from_str("0.3").unwrap()),
+                ("b".to_string(), Decimal::from_str("0.7").unwrap()),
+            ]),
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        };
+        let block = mock_env().block;
+
+        // unknown old receiver
+        let fix = SplitReceiverFix::new(
+            "denom".to_string(),
+            "c".to_string(),
+            "d".to_string(),
+            &block,
+        );
+        submit_split_receiver_fix(&mut split, None, fix, &block).unwrap_err();
+
+        // new receiver already in the split
+        let fix = SplitReceiverFix::new(
+            "denom".to_string(),
+            "a".to_string(),
+            "b".to_string(),
+            &block,
+        );
+        submit_split_receiver_fix(&mut split, None, fix, &block).unwrap_err();
+    }
+
+    #[test]
+    fn test_fix_split_receiver_tracks_pending_fix() {
+        let mut storage = MockStorage::new();
+        let mut split = SplitConfig {
+            receivers: BTreeMap::from([
+                ("a".to_string(), Decimal::from_str("0.3").unwrap()),
+                ("b".to_string(), Decimal::from_str("0.7").unwrap()),
+            ]),
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        };
+        let mut block = mock_env().block;
+        let status = fix_split_receiver(
+            &mut storage,
+            &mut split,
+            "denom".to_string(),
+            "a".to_string(),
+            "c".to_string(),
+            &block,
+        )
+        .unwrap();
+        let SplitReceiverFixStatus::Scheduled(fix) = status else {
+            panic!("expected the fix to be scheduled");
+        };
+        assert_eq!(
+            PENDING_SPLIT_RECEIVER_FIXES
+                .load(&storage, "denom".to_string())
+                .unwrap(),
+            fix
+        );
+
+        block.time = fix.executable_at;
+        let status = fix_split_receiver(
+            &mut storage,
+            &mut split,
+            "denom".to_string(),
+            "a".to_string(),
+            "c".to_string(),
+            &block,
+        )
+        .unwrap();
+        assert_eq!(status, SplitReceiverFixStatus::Applied(fix));
+        assert!(!PENDING_SPLIT_RECEIVER_FIXES.has(&storage, "denom".to_string()));
+        assert!(split.receivers.contains_key("c"));
+    }
+}
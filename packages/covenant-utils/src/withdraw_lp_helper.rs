@@ -11,6 +11,31 @@ pub const EMERGENCY_COMMITTEE_ADDR: Item<Addr> = Item::new("e_c_a");
 #[cw_serde]
 pub enum WithdrawLPMsgs {}
 
+/// liquid pooler messages for giving up the LP position
+#[cw_serde]
+pub enum DonateLPMsgs {
+    /// Tells the LPer to send all of its LP tokens to the receiver
+    /// Should only be called by the holder of the covenant
+    DonateLpTokens { receiver: String },
+}
+
+/// liquid pooler messages for staking the LP position
+#[cw_serde]
+pub enum StakeLPMsgs {
+    /// Tells the LPer to stake all of its LP tokens into the generator
+    /// on behalf of the holder, who then accrues the rewards.
+    /// Should only be called by the holder of the covenant
+    StakeLpTokens { generator: String },
+}
+
+/// liquid pooler messages for pausing the liquidity provision
+#[cw_serde]
+pub enum PauseProvisioningMsgs {
+    /// Tells the LPer to pause or resume providing liquidity.
+    /// Should only be called by the holder of the covenant
+    SetProvisioningPaused { paused: bool },
+}
+
 pub fn generate_withdraw_msg(
     contract_addr: String,
     percentage: Option<Decimal>,
@@ -21,3 +46,36 @@ pub fn generate_withdraw_msg(
         funds: vec![],
     })
 }
+
+pub fn generate_donate_lp_msg(
+    contract_addr: String,
+    receiver: String,
+) -> Result<WasmMsg, StdError> {
+    Ok(WasmMsg::Execute {
+        contract_addr,
+        msg: to_json_binary(&DonateLPMsgs::DonateLpTokens { receiver })?,
+        funds: vec![],
+    })
+}
+
+pub fn generate_stake_lp_msg(
+    contract_addr: String,
+    generator: String,
+) -> Result<WasmMsg, StdError> {
+    Ok(WasmMsg::Execute {
+        contract_addr,
+        msg: to_json_binary(&StakeLPMsgs::StakeLpTokens { generator })?,
+        funds: vec![],
+    })
+}
+
+pub fn generate_pause_provisioning_msg(
+    contract_addr: String,
+    paused: bool,
+) -> Result<WasmMsg, StdError> {
+    Ok(WasmMsg::Execute {
+        contract_addr,
+        msg: to_json_binary(&PauseProvisioningMsgs::SetProvisioningPaused { paused })?,
+        funds: vec![],
+    })
+}
@@ -82,6 +82,19 @@ where
         self.mapping.reverse().has(storage, t)
     }
 
+    /// Returns up to `limit` elements in the order that they will
+    /// be dequeued, without removing them. O(limit)
+    pub fn peek(&self, storage: &dyn Storage, limit: Option<u32>) -> StdResult<Vec<T>> {
+        let range = self
+            .mapping
+            .range(storage, None, None, cosmwasm_std::Order::Ascending)
+            .map(|item| item.map(|(_, t)| t));
+        match limit {
+            None => range.collect::<StdResult<_>>(),
+            Some(limit) => range.take(limit as usize).collect::<StdResult<_>>(),
+        }
+    }
+
     pub fn query_queue(
         &self,
         storage: &dyn Storage,
@@ -65,3 +65,31 @@ fn test_querying_queue() {
         .unwrap();
     assert_eq!(q, vec![(Addr::unchecked("mmh"), 1)]);
 }
+
+#[test]
+fn test_peek() {
+    let mut deps = mock_dependencies();
+    let storage = &mut deps.storage;
+
+    let queue = FIFOQueue::new("f", "b", "s");
+
+    queue.enqueue(storage, Addr::unchecked("zzz")).unwrap();
+    queue.enqueue(storage, Addr::unchecked("aaa")).unwrap();
+    queue.enqueue(storage, Addr::unchecked("mmm")).unwrap();
+    queue.remove(storage, Addr::unchecked("aaa")).unwrap();
+
+    // Elements are returned in the order they will be dequeued.
+    assert_eq!(
+        queue.peek(storage, None).unwrap(),
+        vec![Addr::unchecked("zzz"), Addr::unchecked("mmm")]
+    );
+    assert_eq!(
+        queue.peek(storage, Some(1)).unwrap(),
+        vec![Addr::unchecked("zzz")]
+    );
+    // Peeking does not modify the queue.
+    assert_eq!(
+        queue.dequeue(storage).unwrap(),
+        Some(Addr::unchecked("zzz"))
+    );
+}
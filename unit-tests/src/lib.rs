@@ -7,10 +7,18 @@ pub mod setup;
 #[cfg(test)]
 pub mod test_astroport_liquid_pooler;
 #[cfg(test)]
+pub mod test_benchmarks;
+#[cfg(test)]
+pub mod test_covenant_invariant_checker;
+#[cfg(test)]
+pub mod test_dao_party;
+#[cfg(test)]
 pub mod test_ibc_forwarder;
 #[cfg(test)]
 pub mod test_interchain_router;
 #[cfg(test)]
+pub mod test_migrations;
+#[cfg(test)]
 pub mod test_native_router;
 #[cfg(test)]
 pub mod test_native_splitter;
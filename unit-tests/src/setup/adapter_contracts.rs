@@ -0,0 +1,134 @@
+//! minimal stand-ins for contracts that covenant modules interact with.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coin, to_json_binary, BankMsg, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdResult,
+};
+use covenant_utils::contribution_adapter::ContributionAdapterMsgs;
+use cw20::Cw20ReceiveMsg;
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
+
+const TARGET_DENOM: Item<String> = Item::new("target_denom");
+const CONTRACT_STATE: Item<String> = Item::new("contract_state");
+const RECEIVED_CW20_MSG: Item<Binary> = Item::new("received_cw20_msg");
+
+#[cw_serde]
+pub struct ContributionAdapterInstantiateMsg {
+    pub target_denom: String,
+}
+
+/// contribution adapter (e.g. a liquid staker or a vault share unwrapper).
+/// it converts the attached funds 1:1 into its target denom, paid out of
+/// its own balance.
+pub fn contribution_adapter_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let exec = |deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                info: MessageInfo,
+                msg: ContributionAdapterMsgs|
+     -> StdResult<Response<NeutronMsg>> {
+        match msg {
+            ContributionAdapterMsgs::Convert { receiver } => {
+                let target_denom = TARGET_DENOM.load(deps.storage)?;
+                let amount = info.funds.iter().map(|c| c.amount.u128()).sum::<u128>();
+                Ok(Response::default().add_message(BankMsg::Send {
+                    to_address: receiver,
+                    amount: vec![coin(amount, target_denom)],
+                }))
+            }
+        }
+    };
+
+    let init = |deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                msg: ContributionAdapterInstantiateMsg|
+     -> StdResult<Response<NeutronMsg>> {
+        TARGET_DENOM.save(deps.storage, &msg.target_denom)?;
+        Ok(Response::default())
+    };
+
+    let query = |deps: Deps<NeutronQuery>, _env: Env, _msg: Empty| -> StdResult<Binary> {
+        to_json_binary(&TARGET_DENOM.load(deps.storage)?)
+    };
+
+    Box::new(ContractWrapper::new(exec, init, query))
+}
+
+#[cw_serde]
+pub struct MockHolderMsg {
+    pub contract_state: String,
+}
+
+#[cw_serde]
+pub enum MockHolderQueryMsg {
+    ContractState {},
+}
+
+/// holder reporting the contract state it was instantiated or executed with
+pub fn mock_holder_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let exec = |deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                msg: MockHolderMsg|
+     -> StdResult<Response<NeutronMsg>> {
+        CONTRACT_STATE.save(deps.storage, &msg.contract_state)?;
+        Ok(Response::default())
+    };
+
+    let query =
+        |deps: Deps<NeutronQuery>, _env: Env, msg: MockHolderQueryMsg| -> StdResult<Binary> {
+            match msg {
+                MockHolderQueryMsg::ContractState {} => {
+                    to_json_binary(&CONTRACT_STATE.load(deps.storage)?)
+                }
+            }
+        };
+
+    Box::new(ContractWrapper::new(exec, exec, query))
+}
+
+#[cw_serde]
+pub enum MockCw20ReceiverMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+#[cw_serde]
+pub enum MockCw20ReceiverQueryMsg {
+    ReceivedMsg {},
+}
+
+/// cw20 receiver keeping the tokens sent to it along with the last msg
+pub fn mock_cw20_receiver_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let exec = |deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                msg: MockCw20ReceiverMsg|
+     -> StdResult<Response<NeutronMsg>> {
+        match msg {
+            MockCw20ReceiverMsg::Receive(cw20_msg) => {
+                RECEIVED_CW20_MSG.save(deps.storage, &cw20_msg.msg)?;
+                Ok(Response::default())
+            }
+        }
+    };
+
+    let init = |_deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                _msg: Empty|
+     -> StdResult<Response<NeutronMsg>> { Ok(Response::default()) };
+
+    let query =
+        |deps: Deps<NeutronQuery>, _env: Env, msg: MockCw20ReceiverQueryMsg| -> StdResult<Binary> {
+            match msg {
+                MockCw20ReceiverQueryMsg::ReceivedMsg {} => {
+                    to_json_binary(&RECEIVED_CW20_MSG.load(deps.storage)?)
+                }
+            }
+        };
+
+    Box::new(ContractWrapper::new(exec, init, query))
+}
@@ -1,8 +1,6 @@
 use std::fmt::Display;
 
-use cosmwasm_std::{
-    CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdError, SubMsg,
-};
+use cosmwasm_std::{CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, SubMsg};
 use cw_multi_test::{Contract, ContractWrapper};
 use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
 
@@ -135,6 +133,8 @@ pub fn interchain_router_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery
         valence_interchain_router::contract::instantiate,
         valence_interchain_router::contract::query,
     )
+    .with_reply(valence_interchain_router::contract::reply)
+    .with_sudo(valence_interchain_router::contract::sudo)
     .with_migrate(valence_interchain_router::contract::migrate);
     Box::new(contract)
 }
@@ -190,7 +190,20 @@ pub fn osmo_lp_outpost_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>>
         valence_outpost_osmo_liquid_pooler::contract::query(get_empty_deps(deps), env, msg)
     };
 
-    Box::new(ContractWrapper::new(exec, init, query).with_reply(reply))
+    let migrate = |deps: DepsMut<NeutronQuery>,
+                   env: Env,
+                   msg: valence_outpost_osmo_liquid_pooler::msg::MigrateMsg| {
+        execute_into_neutron(valence_outpost_osmo_liquid_pooler::contract::migrate(
+            get_empty_depsmut(deps),
+            env,
+            msg,
+        ))
+    };
+
+    let contract = ContractWrapper::new(exec, init, query)
+        .with_migrate(migrate)
+        .with_reply(reply);
+    Box::new(contract)
 }
 
 pub fn native_router_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
@@ -231,16 +244,36 @@ pub fn native_router_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
             ))
         };
 
-    let contract = ContractWrapper::new(exec, init, query).with_migrate(migrate);
+    let reply = |deps: DepsMut<NeutronQuery>, env: Env, reply: Reply| {
+        execute_into_neutron(valence_native_router::contract::reply(
+            get_empty_depsmut(deps),
+            env,
+            reply,
+        ))
+    };
+
+    let contract = ContractWrapper::new(exec, init, query)
+        .with_reply(reply)
+        .with_migrate(migrate);
     Box::new(contract)
 }
 
 pub fn native_splitter_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let contract = ContractWrapper::new(
+        valence_native_splitter::contract::execute,
+        valence_native_splitter::contract::instantiate,
+        valence_native_splitter::contract::query,
+    )
+    .with_migrate(valence_native_splitter::contract::migrate);
+    Box::new(contract)
+}
+
+pub fn single_party_covenant_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
     let exec = |deps: DepsMut<NeutronQuery>,
                 env: Env,
                 info: MessageInfo,
-                msg: valence_native_splitter::msg::ExecuteMsg| {
-        execute_into_neutron(valence_native_splitter::contract::execute(
+                msg: valence_covenant_single_party_pol::msg::ExecuteMsg| {
+        execute_into_neutron(valence_covenant_single_party_pol::contract::execute(
             get_empty_depsmut(deps),
             env,
             info,
@@ -248,45 +281,6 @@ pub fn native_splitter_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>>
         ))
     };
 
-    let init = |deps: DepsMut<NeutronQuery>,
-                env: Env,
-                info: MessageInfo,
-                msg: valence_native_splitter::msg::InstantiateMsg| {
-        execute_into_neutron(valence_native_splitter::contract::instantiate(
-            get_empty_depsmut(deps),
-            env,
-            info,
-            msg,
-        ))
-    };
-
-    let query =
-        |deps: Deps<NeutronQuery>, env: Env, msg: valence_native_splitter::msg::QueryMsg| {
-            valence_native_splitter::contract::query(get_empty_deps(deps), env, msg)
-        };
-
-    let migrate =
-        |deps: DepsMut<NeutronQuery>, env: Env, msg: valence_native_splitter::msg::MigrateMsg| {
-            execute_into_neutron(valence_native_splitter::contract::migrate(
-                get_empty_depsmut(deps),
-                env,
-                msg,
-            ))
-        };
-
-    let contract = ContractWrapper::new(exec, init, query).with_migrate(migrate);
-    Box::new(contract)
-}
-
-pub fn single_party_covenant_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
-    let exec = |_deps: DepsMut<NeutronQuery>,
-                _env: Env,
-                _info: MessageInfo,
-                _msg: Empty|
-     -> Result<Response<NeutronMsg>, StdError> {
-        Err(StdError::generic_err("Execute msg is not implemented"))
-    };
-
     let init = |deps: DepsMut<NeutronQuery>,
                 env: Env,
                 info: MessageInfo,
@@ -315,7 +309,17 @@ pub fn single_party_covenant_contract() -> Box<dyn Contract<NeutronMsg, NeutronQ
         ))
     };
 
-    let contract = ContractWrapper::new(exec, init, query).with_migrate(migrate);
+    let reply = |deps: DepsMut<NeutronQuery>, env: Env, reply: Reply| {
+        execute_into_neutron(valence_covenant_single_party_pol::contract::reply(
+            get_empty_depsmut(deps),
+            env,
+            reply,
+        ))
+    };
+
+    let contract = ContractWrapper::new(exec, init, query)
+        .with_reply(reply)
+        .with_migrate(migrate);
     Box::new(contract)
 }
 
@@ -377,12 +381,16 @@ pub fn stride_lser_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
 }
 
 pub fn swap_covenant_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
-    let exec = |_deps: DepsMut<NeutronQuery>,
-                _env: Env,
-                _info: MessageInfo,
-                _msg: Empty|
-     -> Result<Response<NeutronMsg>, StdError> {
-        Err(StdError::generic_err("Execute msg is not implemented"))
+    let exec = |deps: DepsMut<NeutronQuery>,
+                env: Env,
+                info: MessageInfo,
+                msg: valence_covenant_swap::msg::ExecuteMsg| {
+        execute_into_neutron(valence_covenant_swap::contract::execute(
+            get_empty_depsmut(deps),
+            env,
+            info,
+            msg,
+        ))
     };
 
     let init = |deps: DepsMut<NeutronQuery>,
@@ -410,23 +418,21 @@ pub fn swap_covenant_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
             ))
         };
 
-    let contract = ContractWrapper::new(exec, init, query).with_migrate(migrate);
-    Box::new(contract)
-}
-
-pub fn swap_holder_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
-    let exec = |deps: DepsMut<NeutronQuery>,
-                env: Env,
-                info: MessageInfo,
-                msg: valence_swap_holder::msg::ExecuteMsg| {
-        execute_into_neutron(valence_swap_holder::contract::execute(
+    let reply = |deps: DepsMut<NeutronQuery>, env: Env, reply: Reply| {
+        execute_into_neutron(valence_covenant_swap::contract::reply(
             get_empty_depsmut(deps),
             env,
-            info,
-            msg,
+            reply,
         ))
     };
 
+    let contract = ContractWrapper::new(exec, init, query)
+        .with_reply(reply)
+        .with_migrate(migrate);
+    Box::new(contract)
+}
+
+pub fn swap_holder_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
     let init = |deps: DepsMut<NeutronQuery>,
                 env: Env,
                 info: MessageInfo,
@@ -452,17 +458,24 @@ pub fn swap_holder_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
             ))
         };
 
-    let contract = ContractWrapper::new(exec, init, query).with_migrate(migrate);
+    let contract = ContractWrapper::new(valence_swap_holder::contract::execute, init, query)
+        .with_reply(valence_swap_holder::contract::reply)
+        .with_sudo(valence_swap_holder::contract::sudo)
+        .with_migrate(migrate);
     Box::new(contract)
 }
 
 pub fn two_party_covenant_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
-    let exec = |_deps: DepsMut<NeutronQuery>,
-                _env: Env,
-                _info: MessageInfo,
-                _msg: Empty|
-     -> Result<Response<NeutronMsg>, StdError> {
-        Err(StdError::generic_err("Execute msg is not implemented"))
+    let exec = |deps: DepsMut<NeutronQuery>,
+                env: Env,
+                info: MessageInfo,
+                msg: valence_covenant_two_party_pol::msg::ExecuteMsg| {
+        execute_into_neutron(valence_covenant_two_party_pol::contract::execute(
+            get_empty_depsmut(deps),
+            env,
+            info,
+            msg,
+        ))
     };
 
     let init = |deps: DepsMut<NeutronQuery>,
@@ -492,7 +505,17 @@ pub fn two_party_covenant_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuer
         ))
     };
 
-    let contract = ContractWrapper::new(exec, init, query).with_migrate(migrate);
+    let reply = |deps: DepsMut<NeutronQuery>, env: Env, reply: Reply| {
+        execute_into_neutron(valence_covenant_two_party_pol::contract::reply(
+            get_empty_depsmut(deps),
+            env,
+            reply,
+        ))
+    };
+
+    let contract = ContractWrapper::new(exec, init, query)
+        .with_reply(reply)
+        .with_migrate(migrate);
     Box::new(contract)
 }
 
@@ -526,6 +549,14 @@ pub fn two_party_holder_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>
             valence_two_party_pol_holder::contract::query(get_empty_deps(deps), env, msg)
         };
 
+    let reply = |deps: DepsMut<NeutronQuery>, env: Env, reply: Reply| {
+        execute_into_neutron(valence_two_party_pol_holder::contract::reply(
+            get_empty_depsmut(deps),
+            env,
+            reply,
+        ))
+    };
+
     let migrate = |deps: DepsMut<NeutronQuery>,
                    env: Env,
                    msg: valence_two_party_pol_holder::msg::MigrateMsg| {
@@ -536,7 +567,9 @@ pub fn two_party_holder_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>
         ))
     };
 
-    let contract = ContractWrapper::new(exec, init, query).with_migrate(migrate);
+    let contract = ContractWrapper::new(exec, init, query)
+        .with_reply(reply)
+        .with_migrate(migrate);
     Box::new(contract)
 }
 
@@ -594,3 +627,48 @@ pub fn astroport_pooler_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>
         .with_migrate(migrate);
     Box::new(contract)
 }
+
+pub fn invariant_checker_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let exec = |deps: DepsMut<NeutronQuery>,
+                env: Env,
+                info: MessageInfo,
+                msg: valence_covenant_invariant_checker::msg::ExecuteMsg| {
+        execute_into_neutron(valence_covenant_invariant_checker::contract::execute(
+            get_empty_depsmut(deps),
+            env,
+            info,
+            msg,
+        ))
+    };
+
+    let init = |deps: DepsMut<NeutronQuery>,
+                env: Env,
+                info: MessageInfo,
+                msg: valence_covenant_invariant_checker::msg::InstantiateMsg| {
+        execute_into_neutron(valence_covenant_invariant_checker::contract::instantiate(
+            get_empty_depsmut(deps),
+            env,
+            info,
+            msg,
+        ))
+    };
+
+    let query = |deps: Deps<NeutronQuery>,
+                 env: Env,
+                 msg: valence_covenant_invariant_checker::msg::QueryMsg| {
+        valence_covenant_invariant_checker::contract::query(get_empty_deps(deps), env, msg)
+    };
+
+    let migrate = |deps: DepsMut<NeutronQuery>,
+                   env: Env,
+                   msg: valence_covenant_invariant_checker::msg::MigrateMsg| {
+        execute_into_neutron(valence_covenant_invariant_checker::contract::migrate(
+            get_empty_depsmut(deps),
+            env,
+            msg,
+        ))
+    };
+
+    let contract = ContractWrapper::new(exec, init, query).with_migrate(migrate);
+    Box::new(contract)
+}
@@ -1,25 +1,41 @@
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
 use cosmwasm_schema::serde::de::DeserializeOwned;
 use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Api, Binary, BlockInfo, CustomMsg, CustomQuery, Querier,
-    Storage,
+    coins, from_json, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, CustomMsg,
+    CustomQuery, Querier, Storage, Uint128,
 };
-use covenant_utils::ica::{Params, QueryParamsResponse};
+use covenant_ica::registration::{Params, QueryParamsResponse};
 use cw_multi_test::error::{AnyError, AnyResult};
-use cw_multi_test::{AppResponse, CosmosRouter, Module, StargateQuery};
+use cw_multi_test::{AppResponse, BankSudo, CosmosRouter, Module, StargateMsg, StargateQuery};
 use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_std::types::osmosis::concentratedliquidity::v1beta1::{
+    FullPositionBreakdown, MsgAddToPosition, MsgAddToPositionResponse, MsgCreatePositionResponse,
+    MsgWithdrawPositionResponse, Position, PositionByIdRequest, PositionByIdResponse,
+};
 use osmosis_std::types::osmosis::gamm::v1beta1::{
-    PoolAsset, QueryCalcExitPoolCoinsFromSharesResponse, QueryCalcJoinPoolNoSwapSharesResponse,
-    QueryCalcJoinPoolSharesResponse, QueryPoolResponse,
+    MsgJoinPool, MsgJoinSwapExternAmountIn, PoolAsset, QueryCalcExitPoolCoinsFromSharesResponse,
+    QueryCalcJoinPoolNoSwapSharesResponse, QueryCalcJoinPoolSharesResponse, QueryPoolResponse,
+};
+use osmosis_std::types::osmosis::poolmanager::v1beta1::{
+    MsgSwapExactAmountIn, PoolRequest, PoolResponse, TotalPoolLiquidityResponse,
 };
 use prost::Message;
 
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 use crate::setup::DENOM_LS_ATOM_ON_NTRN;
 
-use super::{DENOM_ATOM, DENOM_FALLBACK};
+use super::{DENOM_ATOM, DENOM_FALLBACK, DENOM_NTRN};
+
+/// balancer pool that rejects joins
+pub const FAILING_JOIN_POOL_ID: u64 = 4;
+/// balancer pool with 80:20 weights
+pub const WEIGHTED_POOL_ID: u64 = 5;
+/// balancer pool of three assets
+pub const MULTI_ASSET_POOL_ID: u64 = 6;
 
 pub struct CustomStargateKeeper<ExecT, QueryT, SudoT>(
     PhantomData<(ExecT, QueryT, SudoT)>,
@@ -46,18 +62,137 @@ where
 
     fn execute<ExecC, QueryC>(
         &self,
-        _api: &dyn Api,
-        _storage: &mut dyn Storage,
-        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
-        _block: &BlockInfo,
-        _sender: Addr,
-        _msg: Self::ExecT,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: Self::ExecT,
     ) -> AnyResult<AppResponse>
     where
         ExecC: CustomMsg + DeserializeOwned + 'static,
         QueryC: CustomQuery + DeserializeOwned + 'static,
     {
-        Ok(AppResponse::default())
+        let msg: StargateMsg = from_json(to_json_binary(&msg).unwrap()).unwrap();
+        let mut response = AppResponse::default();
+
+        // multi sends from the contract are executed as a bank send per output
+        if msg.type_url == "/cosmos.bank.v1beta1.MsgMultiSend" {
+            let msg = MsgMultiSend::decode(msg.value.as_slice()).unwrap();
+            for output in msg.outputs {
+                let send_response = router.execute(
+                    api,
+                    storage,
+                    block,
+                    sender.clone(),
+                    BankMsg::Send {
+                        to_address: output.address,
+                        amount: output
+                            .coins
+                            .iter()
+                            .map(|c| {
+                                cosmwasm_std::coin(
+                                    Uint128::from_str(&c.amount).unwrap().u128(),
+                                    c.denom.to_string(),
+                                )
+                            })
+                            .collect(),
+                    }
+                    .into(),
+                )?;
+                response.events.extend(send_response.events);
+            }
+        }
+
+        // joins to the failing pool are rejected
+        let join_pool_id = match msg.type_url.as_str() {
+            "/osmosis.gamm.v1beta1.MsgJoinPool" => {
+                Some(MsgJoinPool::decode(msg.value.as_slice()).unwrap().pool_id)
+            }
+            "/osmosis.gamm.v1beta1.MsgJoinSwapExternAmountIn" => Some(
+                MsgJoinSwapExternAmountIn::decode(msg.value.as_slice())
+                    .unwrap()
+                    .pool_id,
+            ),
+            _ => None,
+        };
+        if join_pool_id == Some(FAILING_JOIN_POOL_ID) {
+            return Err(AnyError::msg("join pool failed"));
+        }
+
+        // swaps are executed 1:1 by burning the token in and minting
+        // the token out, and fail if that is below the minimum out
+        if msg.type_url == "/osmosis.poolmanager.v1beta1.MsgSwapExactAmountIn" {
+            let msg = MsgSwapExactAmountIn::decode(msg.value.as_slice()).unwrap();
+            let token_in = msg.token_in.unwrap();
+            let amount = Uint128::from_str(&token_in.amount).unwrap();
+            if amount < Uint128::from_str(&msg.token_out_min_amount).unwrap() {
+                return Err(AnyError::msg(
+                    "token amount calculated is lesser than min amount",
+                ));
+            }
+            router.execute(
+                api,
+                storage,
+                block,
+                sender.clone(),
+                BankMsg::Burn {
+                    amount: coins(amount.u128(), token_in.denom),
+                }
+                .into(),
+            )?;
+            router.sudo(
+                api,
+                storage,
+                block,
+                BankSudo::Mint {
+                    to_address: sender.to_string(),
+                    amount: coins(amount.u128(), msg.routes[0].token_out_denom.to_string()),
+                }
+                .into(),
+            )?;
+        }
+
+        // concentrated liquidity positions are numbered from 1, and
+        // adding to a position replaces it with the next one
+        if msg.type_url == "/osmosis.concentratedliquidity.v1beta1.MsgCreatePosition" {
+            let position_response = MsgCreatePositionResponse {
+                position_id: 1,
+                ..Default::default()
+            };
+            response.data = Some(position_response.encode_to_vec().into());
+        }
+        if msg.type_url == "/osmosis.concentratedliquidity.v1beta1.MsgAddToPosition" {
+            let msg = MsgAddToPosition::decode(msg.value.as_slice()).unwrap();
+            let position_response = MsgAddToPositionResponse {
+                position_id: msg.position_id + 1,
+                ..Default::default()
+            };
+            response.data = Some(position_response.encode_to_vec().into());
+        }
+        // withdrawing from a position mints 10 of each pool token
+        if msg.type_url == "/osmosis.concentratedliquidity.v1beta1.MsgWithdrawPosition" {
+            router.sudo(
+                api,
+                storage,
+                block,
+                BankSudo::Mint {
+                    to_address: sender.to_string(),
+                    amount: vec![
+                        cosmwasm_std::coin(10, DENOM_ATOM),
+                        cosmwasm_std::coin(10, DENOM_LS_ATOM_ON_NTRN),
+                    ],
+                }
+                .into(),
+            )?;
+            let withdraw_response = MsgWithdrawPositionResponse {
+                amount0: "10".to_string(),
+                amount1: "10".to_string(),
+            };
+            response.data = Some(withdraw_response.encode_to_vec().into());
+        }
+
+        Ok(response)
     }
 
     fn sudo<ExecC, QueryC>(
@@ -100,46 +235,69 @@ where
         }
 
         if query.path == "/osmosis.gamm.v1beta1.Query/Pool" {
-            let pool = osmosis_std::types::osmosis::gamm::v1beta1::Pool {
-                address: "address".to_string(),
-                id: 1,
-                pool_params: None,
-                future_pool_governor: "governor".to_string(),
-                total_shares: Some(Coin {
-                    amount: "101010".to_string(),
-                    denom: DENOM_FALLBACK.to_string(),
-                }),
-                pool_assets: vec![
-                    PoolAsset {
-                        token: Some(Coin {
-                            amount: "100".to_string(),
-                            denom: DENOM_ATOM.to_string(),
-                        }),
-                        weight: "50".to_string(),
-                    },
-                    PoolAsset {
-                        token: Some(Coin {
-                            amount: "100".to_string(),
-                            denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
-                        }),
-                        weight: "50".to_string(),
-                    },
-                ],
-                total_weight: "123123".to_string(),
+            let response = QueryPoolResponse {
+                pool: Some(get_balancer_pool_shim(1, "50", "50")),
             };
 
-            let pool_shim = osmosis_std::shim::Any {
-                type_url: "/osmosis.gamm.v1beta1.Pool".to_string(),
-                value: pool.encode_to_vec(),
-            };
+            return Ok(to_json_binary(&response).unwrap());
+        }
 
-            let response = QueryPoolResponse {
+        // pool 2 is a concentrated liquidity pool, pool 3 is a stableswap pool,
+        // pool 5 is an 80:20 balancer pool, pool 6 is a three asset balancer
+        // pool, and any other is the 50:50 balancer pool. joins to pool 4 fail.
+        if query.path == "/osmosis.poolmanager.v1beta1.Query/Pool" {
+            let request = PoolRequest::decode(query.data.as_slice()).unwrap();
+            let pool_shim = match request.pool_id {
+                2 => get_concentrated_pool_shim(),
+                3 => get_stableswap_pool_shim(),
+                WEIGHTED_POOL_ID => get_balancer_pool_shim(WEIGHTED_POOL_ID, "80", "20"),
+                MULTI_ASSET_POOL_ID => get_multi_asset_pool_shim(),
+                pool_id => get_balancer_pool_shim(pool_id, "50", "50"),
+            };
+            let response = PoolResponse {
                 pool: Some(pool_shim),
             };
 
             return Ok(to_json_binary(&response).unwrap());
         }
 
+        // every position belongs to the concentrated liquidity pool
+        if query.path == "/osmosis.concentratedliquidity.v1beta1.Query/PositionById" {
+            let request = PositionByIdRequest::decode(query.data.as_slice()).unwrap();
+            let response = PositionByIdResponse {
+                position: Some(FullPositionBreakdown {
+                    position: Some(Position {
+                        position_id: request.position_id,
+                        pool_id: 2,
+                        lower_tick: -100,
+                        upper_tick: 100,
+                        liquidity: "100.000000000000000000".to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            };
+
+            return Ok(to_json_binary(&response).unwrap());
+        }
+
+        if query.path == "/osmosis.poolmanager.v1beta1.Query/TotalPoolLiquidity" {
+            let response = TotalPoolLiquidityResponse {
+                liquidity: vec![
+                    Coin {
+                        amount: "100".to_string(),
+                        denom: DENOM_ATOM.to_string(),
+                    },
+                    Coin {
+                        amount: "100".to_string(),
+                        denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+                    },
+                ],
+            };
+
+            return Ok(to_json_binary(&response).unwrap());
+        }
+
         if query.path == "/osmosis.gamm.v1beta1.Query/CalcExitPoolCoinsFromShares" {
             let tokens_out = vec![
                 Coin {
@@ -197,3 +355,106 @@ where
         Err(AnyError::msg(self.2))
     }
 }
+
+fn get_balancer_pool_shim(pool_id: u64, weight_0: &str, weight_1: &str) -> osmosis_std::shim::Any {
+    let pool = osmosis_std::types::osmosis::gamm::v1beta1::Pool {
+        address: "address".to_string(),
+        id: pool_id,
+        pool_params: None,
+        future_pool_governor: "governor".to_string(),
+        total_shares: Some(Coin {
+            amount: "101010".to_string(),
+            denom: DENOM_FALLBACK.to_string(),
+        }),
+        pool_assets: vec![
+            PoolAsset {
+                token: Some(Coin {
+                    amount: "100".to_string(),
+                    denom: DENOM_ATOM.to_string(),
+                }),
+                weight: weight_0.to_string(),
+            },
+            PoolAsset {
+                token: Some(Coin {
+                    amount: "100".to_string(),
+                    denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+                }),
+                weight: weight_1.to_string(),
+            },
+        ],
+        total_weight: "123123".to_string(),
+    };
+
+    osmosis_std::shim::Any {
+        type_url: "/osmosis.gamm.v1beta1.Pool".to_string(),
+        value: pool.encode_to_vec(),
+    }
+}
+
+fn get_multi_asset_pool_shim() -> osmosis_std::shim::Any {
+    let mut pool = osmosis_std::types::osmosis::gamm::v1beta1::Pool::decode(
+        get_balancer_pool_shim(MULTI_ASSET_POOL_ID, "50", "30")
+            .value
+            .as_slice(),
+    )
+    .unwrap();
+    pool.pool_assets.push(PoolAsset {
+        token: Some(Coin {
+            amount: "100".to_string(),
+            denom: DENOM_NTRN.to_string(),
+        }),
+        weight: "20".to_string(),
+    });
+
+    osmosis_std::shim::Any {
+        type_url: "/osmosis.gamm.v1beta1.Pool".to_string(),
+        value: pool.encode_to_vec(),
+    }
+}
+
+fn get_concentrated_pool_shim() -> osmosis_std::shim::Any {
+    let pool = osmosis_std::types::osmosis::concentratedliquidity::v1beta1::Pool {
+        address: "cl_address".to_string(),
+        id: 2,
+        token0: DENOM_ATOM.to_string(),
+        token1: DENOM_LS_ATOM_ON_NTRN.to_string(),
+        current_sqrt_price: "1.000000000000000000".to_string(),
+        tick_spacing: 100,
+        ..Default::default()
+    };
+
+    osmosis_std::shim::Any {
+        type_url: "/osmosis.concentratedliquidity.v1beta1.Pool".to_string(),
+        value: pool.encode_to_vec(),
+    }
+}
+
+fn get_stableswap_pool_shim() -> osmosis_std::shim::Any {
+    let pool = osmosis_std::types::osmosis::gamm::poolmodels::stableswap::v1beta1::Pool {
+        address: "stableswap_address".to_string(),
+        id: 3,
+        pool_params: None,
+        future_pool_governor: "governor".to_string(),
+        total_shares: Some(Coin {
+            amount: "101010".to_string(),
+            denom: DENOM_FALLBACK.to_string(),
+        }),
+        pool_liquidity: vec![
+            Coin {
+                amount: "100".to_string(),
+                denom: DENOM_ATOM.to_string(),
+            },
+            Coin {
+                amount: "100".to_string(),
+                denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+            },
+        ],
+        scaling_factors: vec![1, 1],
+        scaling_factor_controller: "controller".to_string(),
+    };
+
+    osmosis_std::shim::Any {
+        type_url: "/osmosis.gamm.poolmodels.stableswap.v1beta1.Pool".to_string(),
+        value: pool.encode_to_vec(),
+    }
+}
@@ -10,10 +10,10 @@ use cw_multi_test::{
     prefixed_storage::{prefixed, prefixed_read},
     AppResponse, BankSudo, Module, WasmSudo,
 };
-use cw_storage_plus::Map;
+use cw_storage_plus::{Item, Map};
 use neutron_sdk::{
     bindings::{
-        msg::{MsgSubmitTxResponse, NeutronMsg},
+        msg::{MsgIbcTransferResponse, MsgSubmitTxResponse, NeutronMsg},
         query::NeutronQuery,
     },
     interchain_txs::helpers::get_port_id,
@@ -40,6 +40,9 @@ const LOCAL_CHANNELS_VALUES: Map<String, String> = Map::new("local_channels_valu
 const REMOTE_CHANNELS: Map<String, String> = Map::new("remote_channels");
 const REMOTE_CHANNELS_VALUES: Map<String, String> = Map::new("remote_channels_values");
 
+/// sequence of the last ibc transfer
+const IBC_TRANSFER_SEQUENCE: Item<u64> = Item::new("ibc_transfer_sequence");
+
 pub trait Neutron:
     Module<ExecT = NeutronMsg, QueryT = NeutronQuery, SudoT = neutron_sdk::sudo::msg::SudoMsg>
 {
@@ -361,7 +364,21 @@ impl Module for NeutronKeeper {
                     .into(),
                 )?;
 
-                Ok(AppResponse::default())
+                // Respond with the sequence of the transfer, so it can be acknowledged later
+                let mut ntrn_storage = prefixed(storage, NAMESPACE_NEUTRON);
+                let sequence = IBC_TRANSFER_SEQUENCE
+                    .may_load(&ntrn_storage)?
+                    .unwrap_or_default()
+                    + 1;
+                IBC_TRANSFER_SEQUENCE.save(&mut ntrn_storage, &sequence)?;
+
+                Ok(AppResponse {
+                    data: Some(to_json_binary(&MsgIbcTransferResponse {
+                        sequence,
+                        channel: source_channel,
+                    })?),
+                    events: vec![],
+                })
             }
             NeutronMsg::SubmitTx {
                 connection_id,
@@ -0,0 +1,123 @@
+//! minimal stand-ins for the DAO DAO core and proposal module contracts.
+//! they only implement the parts covenants rely on to verify that
+//! a message was relayed by a proposal of the DAO.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdResult,
+};
+use covenant_utils::dao::{
+    DaoCoreQueryMsg, DaoProposalModuleQueryMsg, ProposalModule, ProposalModuleStatus,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::{Bound, Item, Map};
+use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
+
+const PROPOSAL_MODULES: Map<Addr, (String, ProposalModuleStatus)> = Map::new("proposal_modules");
+const DAO: Item<Addr> = Item::new("dao");
+
+#[cw_serde]
+pub enum DaoCoreExecuteMsg {
+    SetProposalModule {
+        address: String,
+        status: ProposalModuleStatus,
+    },
+}
+
+#[cw_serde]
+pub struct DaoProposalModuleInstantiateMsg {
+    pub dao: String,
+}
+
+#[cw_serde]
+pub enum DaoProposalModuleExecuteMsg {
+    /// executes the messages of a passed proposal
+    Execute { msgs: Vec<CosmosMsg<NeutronMsg>> },
+}
+
+pub fn dao_core_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let exec = |deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                msg: DaoCoreExecuteMsg|
+     -> StdResult<Response<NeutronMsg>> {
+        match msg {
+            DaoCoreExecuteMsg::SetProposalModule { address, status } => {
+                let address = deps.api.addr_validate(&address)?;
+                PROPOSAL_MODULES.save(deps.storage, address, &("A".to_string(), status))?;
+                Ok(Response::default())
+            }
+        }
+    };
+
+    let init = |_deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                _msg: Empty|
+     -> StdResult<Response<NeutronMsg>> { Ok(Response::default()) };
+
+    let query = |deps: Deps<NeutronQuery>, _env: Env, msg: DaoCoreQueryMsg| -> StdResult<Binary> {
+        match msg {
+            DaoCoreQueryMsg::ActiveProposalModules { start_after, limit } => {
+                let start_after = start_after
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .transpose()?;
+                let modules: Vec<ProposalModule> = PROPOSAL_MODULES
+                    .range(
+                        deps.storage,
+                        start_after.map(Bound::exclusive),
+                        None,
+                        Order::Ascending,
+                    )
+                    .take(limit.unwrap_or(10) as usize)
+                    .map(|entry| {
+                        entry.map(|(address, (prefix, status))| ProposalModule {
+                            address,
+                            prefix,
+                            status,
+                        })
+                    })
+                    .collect::<StdResult<_>>()?;
+                to_json_binary(&modules)
+            }
+        }
+    };
+
+    Box::new(ContractWrapper::new(exec, init, query))
+}
+
+pub fn dao_proposal_module_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let exec = |_deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                msg: DaoProposalModuleExecuteMsg|
+     -> StdResult<Response<NeutronMsg>> {
+        match msg {
+            DaoProposalModuleExecuteMsg::Execute { msgs } => {
+                Ok(Response::default().add_messages(msgs))
+            }
+        }
+    };
+
+    let init = |deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                msg: DaoProposalModuleInstantiateMsg|
+     -> StdResult<Response<NeutronMsg>> {
+        let dao = deps.api.addr_validate(&msg.dao)?;
+        DAO.save(deps.storage, &dao)?;
+        Ok(Response::default())
+    };
+
+    let query = |deps: Deps<NeutronQuery>,
+                 _env: Env,
+                 msg: DaoProposalModuleQueryMsg|
+     -> StdResult<Binary> {
+        match msg {
+            DaoProposalModuleQueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        }
+    };
+
+    Box::new(ContractWrapper::new(exec, init, query))
+}
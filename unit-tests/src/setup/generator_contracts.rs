@@ -0,0 +1,105 @@
+//! minimal stand-in for the astroport generator. it keeps track of the
+//! LP tokens deposited on behalf of users, pays out a fixed reward out
+//! of its own balance on every claim, and returns the LP tokens on
+//! withdrawal.
+
+use astroport::generator::{Cw20HookMsg, ExecuteMsg, QueryMsg};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_json, to_json_binary, BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::{Item, Map};
+use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
+
+const REWARD: Item<Coin> = Item::new("reward");
+/// LP tokens deposited, keyed by (lp token, user)
+const DEPOSITS: Map<(String, String), Uint128> = Map::new("deposits");
+
+#[cw_serde]
+pub struct GeneratorInstantiateMsg {
+    /// paid out to the claimer on every claim
+    pub reward: Coin,
+}
+
+pub fn generator_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let exec = |deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                info: MessageInfo,
+                msg: ExecuteMsg|
+     -> StdResult<Response<NeutronMsg>> {
+        match msg {
+            ExecuteMsg::Receive(cw20_msg) => match from_json(&cw20_msg.msg)? {
+                Cw20HookMsg::DepositFor(user) => {
+                    DEPOSITS.update(
+                        deps.storage,
+                        (info.sender.to_string(), user),
+                        |deposit| -> StdResult<_> {
+                            Ok(deposit.unwrap_or_default() + cw20_msg.amount)
+                        },
+                    )?;
+                    Ok(Response::default())
+                }
+                _ => unimplemented!(),
+            },
+            ExecuteMsg::ClaimRewards { lp_tokens } => {
+                let reward = REWARD.load(deps.storage)?;
+                let mut resp = Response::default();
+                for lp_token in lp_tokens {
+                    let deposit = DEPOSITS
+                        .may_load(deps.storage, (lp_token, info.sender.to_string()))?
+                        .unwrap_or_default();
+                    if !deposit.is_zero() {
+                        resp = resp.add_message(BankMsg::Send {
+                            to_address: info.sender.to_string(),
+                            amount: vec![reward.clone()],
+                        });
+                    }
+                }
+                Ok(resp)
+            }
+            ExecuteMsg::Withdraw { lp_token, amount } => {
+                DEPOSITS.update(
+                    deps.storage,
+                    (lp_token.to_string(), info.sender.to_string()),
+                    |deposit| -> StdResult<_> {
+                        Ok(deposit.unwrap_or_default().checked_sub(amount)?)
+                    },
+                )?;
+                Ok(Response::default().add_message(WasmMsg::Execute {
+                    contract_addr: lp_token,
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: info.sender.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                }))
+            }
+            _ => unimplemented!(),
+        }
+    };
+
+    let init = |deps: DepsMut<NeutronQuery>,
+                _env: Env,
+                _info: MessageInfo,
+                msg: GeneratorInstantiateMsg|
+     -> StdResult<Response<NeutronMsg>> {
+        REWARD.save(deps.storage, &msg.reward)?;
+        Ok(Response::default())
+    };
+
+    let query = |deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg| -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Deposit { lp_token, user } => to_json_binary(
+                &DEPOSITS
+                    .may_load(deps.storage, (lp_token, user))?
+                    .unwrap_or_default(),
+            ),
+            _ => unimplemented!(),
+        }
+    };
+
+    Box::new(ContractWrapper::new(exec, init, query))
+}
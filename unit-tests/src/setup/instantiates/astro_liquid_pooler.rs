@@ -1,6 +1,8 @@
 use astroport::factory::PairType;
-use cosmwasm_std::{Decimal, Uint128};
-use covenant_utils::{op_mode::ContractOperationModeConfig, PoolPriceConfig, SingleSideLpLimits};
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use covenant_utils::{
+    deadline::Deadline, op_mode::ContractOperationModeConfig, PoolPriceConfig, SingleSideLpLimits,
+};
 
 use crate::setup::{DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN};
 
@@ -36,10 +38,81 @@ impl AstroLiquidPoolerInstantiate {
                 pool_price_config,
                 pair_type,
                 holder_address,
+                pair_type_migration_policy: None,
+                emergency_committee: None,
+                oracle_price_guard: None,
+                max_pool_ownership: None,
+                generator_address: None,
+                twap_window: None,
+                single_side_rebalance: None,
+                min_lp_out_tolerance: None,
+                max_provision_per_tick: None,
+                funding_deadline: None,
             },
         }
     }
 
+    pub fn with_pair_type_migration_policy(
+        &mut self,
+        policy: Option<valence_astroport_liquid_pooler::msg::PairTypeMigrationPolicy>,
+    ) -> &mut Self {
+        self.msg.pair_type_migration_policy = policy;
+        self
+    }
+
+    pub fn with_emergency_committee(&mut self, emergency_committee: Option<String>) -> &mut Self {
+        self.msg.emergency_committee = emergency_committee;
+        self
+    }
+
+    pub fn with_oracle_price_guard(
+        &mut self,
+        guard: Option<covenant_oracle::PriceGuard>,
+    ) -> &mut Self {
+        self.msg.oracle_price_guard = guard;
+        self
+    }
+
+    pub fn with_max_pool_ownership(&mut self, max_pool_ownership: Option<Decimal>) -> &mut Self {
+        self.msg.max_pool_ownership = max_pool_ownership;
+        self
+    }
+
+    pub fn with_generator_address(&mut self, generator_address: Option<String>) -> &mut Self {
+        self.msg.generator_address = generator_address;
+        self
+    }
+
+    pub fn with_twap_window(&mut self, twap_window: Option<u64>) -> &mut Self {
+        self.msg.twap_window = twap_window;
+        self
+    }
+
+    pub fn with_single_side_rebalance(
+        &mut self,
+        single_side_rebalance: Option<
+            valence_astroport_liquid_pooler::msg::SingleSideRebalanceConfig,
+        >,
+    ) -> &mut Self {
+        self.msg.single_side_rebalance = single_side_rebalance;
+        self
+    }
+
+    pub fn with_min_lp_out_tolerance(&mut self, tolerance: Option<Decimal>) -> &mut Self {
+        self.msg.min_lp_out_tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_provision_per_tick(&mut self, cap: Option<Coin>) -> &mut Self {
+        self.msg.max_provision_per_tick = cap;
+        self
+    }
+
+    pub fn with_funding_deadline(&mut self, deadline: Option<Deadline>) -> &mut Self {
+        self.msg.funding_deadline = deadline;
+        self
+    }
+
     pub fn with_pool_address(&mut self, pool_address: String) -> &mut Self {
         self.msg.pool_address = pool_address;
         self
@@ -101,10 +174,12 @@ impl AstroLiquidPoolerInstantiate {
                 assets: valence_astroport_liquid_pooler::msg::AssetData {
                     asset_a_denom: DENOM_ATOM_ON_NTRN.to_string(),
                     asset_b_denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+                    additional_assets: vec![],
                 },
                 single_side_lp_limits: SingleSideLpLimits {
                     asset_a_limit: Uint128::new(100000),
                     asset_b_limit: Uint128::new(100000),
+                    pool_reserve_share: None,
                 },
                 pool_price_config: PoolPriceConfig {
                     expected_spot_price: Decimal::one(),
@@ -112,6 +187,16 @@ impl AstroLiquidPoolerInstantiate {
                 },
                 pair_type: PairType::Stable {},
                 holder_address,
+                pair_type_migration_policy: None,
+                emergency_committee: None,
+                oracle_price_guard: None,
+                max_pool_ownership: None,
+                generator_address: None,
+                twap_window: None,
+                single_side_rebalance: None,
+                min_lp_out_tolerance: None,
+                max_provision_per_tick: None,
+                funding_deadline: None,
             },
         }
     }
@@ -20,6 +20,7 @@ impl ClockInstantiate {
                 tick_max_gas,
                 whitelist,
                 initial_queue: vec![],
+                tick_intervals: vec![],
             },
         }
     }
@@ -46,6 +47,7 @@ impl ClockInstantiate {
                 tick_max_gas,
                 whitelist,
                 initial_queue: vec![],
+                tick_intervals: vec![],
             },
         }
     }
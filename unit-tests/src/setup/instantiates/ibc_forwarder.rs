@@ -1,5 +1,6 @@
 use cosmwasm_std::{Uint128, Uint64};
 use covenant_utils::op_mode::ContractOperationModeConfig;
+use neutron_sdk::bindings::msg::IbcFee;
 
 use crate::setup::{DENOM_ATOM_ON_NTRN, NTRN_HUB_CHANNEL};
 
@@ -36,6 +37,8 @@ impl IbcForwarderInstantiate {
                 ibc_transfer_timeout,
                 ica_timeout,
                 fallback_address,
+                milestone_callback: None,
+                ibc_fee_override: None,
             },
         }
     }
@@ -75,6 +78,16 @@ impl IbcForwarderInstantiate {
         self
     }
 
+    pub fn with_milestone_callback(&mut self, addr: String) -> &mut Self {
+        self.msg.milestone_callback = Some(addr);
+        self
+    }
+
+    pub fn with_ibc_fee_override(&mut self, ibc_fee: IbcFee) -> &mut Self {
+        self.msg.ibc_fee_override = Some(ibc_fee);
+        self
+    }
+
     pub fn with_ibc_transfer_timeout(&mut self, addr: Uint64) -> &mut Self {
         self.msg.ibc_transfer_timeout = addr;
         self
@@ -103,6 +116,8 @@ impl IbcForwarderInstantiate {
                 ica_timeout: Uint64::from(100u64),
                 ibc_transfer_timeout: Uint64::from(100u64),
                 fallback_address,
+                milestone_callback: None,
+                ibc_fee_override: None,
             },
         }
     }
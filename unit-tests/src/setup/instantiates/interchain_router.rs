@@ -1,7 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use cosmwasm_std::{Addr, Uint64};
-use covenant_utils::DestinationConfig;
+use cosmwasm_std::{Addr, Uint128, Uint64};
+use covenant_utils::{router_halt::RouterHaltConfig, DestinationConfig};
 
 use crate::setup::{DENOM_ATOM_ON_NTRN, NTRN_HUB_CHANNEL};
 
@@ -26,6 +26,12 @@ impl InterchainRouterInstantiate {
                 clock_address: clock_address.to_string(),
                 destination_config,
                 denoms,
+                halt_config: None,
+                holder_address: None,
+                owner: None,
+                denom_to_destination_map: BTreeMap::new(),
+                min_route_amounts: BTreeMap::new(),
+                ibc_fee_override: None,
             },
         }
     }
@@ -44,6 +50,37 @@ impl InterchainRouterInstantiate {
         self.msg.denoms = denoms;
         self
     }
+
+    pub fn with_owner(&mut self, addr: &str) -> &mut Self {
+        self.msg.owner = Some(addr.to_string());
+        self
+    }
+
+    pub fn with_denom_destination(
+        &mut self,
+        denom: &str,
+        destination_config: DestinationConfig,
+    ) -> &mut Self {
+        self.msg
+            .denom_to_destination_map
+            .insert(denom.to_string(), destination_config);
+        self
+    }
+
+    pub fn with_min_route_amount(&mut self, denom: &str, amount: Uint128) -> &mut Self {
+        self.msg.min_route_amounts.insert(denom.to_string(), amount);
+        self
+    }
+
+    pub fn with_halt_config(&mut self, halt_config: Option<RouterHaltConfig>) -> &mut Self {
+        self.msg.halt_config = halt_config;
+        self
+    }
+
+    pub fn with_holder_address(&mut self, addr: Option<String>) -> &mut Self {
+        self.msg.holder_address = addr;
+        self
+    }
 }
 
 impl InterchainRouterInstantiate {
@@ -55,6 +92,8 @@ impl InterchainRouterInstantiate {
             destination_receiver_addr: party_receiver,
             ibc_transfer_timeout: Uint64::new(1000),
             denom_to_pfm_map: BTreeMap::new(),
+            memo_template: None,
+            wasm_hook: None,
         };
 
         Self::new(clock_address, destination_config, denoms)
@@ -1,7 +1,7 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
-use cosmwasm_std::Addr;
-use covenant_utils::op_mode::ContractOperationModeConfig;
+use cosmwasm_std::{Addr, Binary, Uint128};
+use covenant_utils::{op_mode::ContractOperationModeConfig, router_halt::RouterHaltConfig};
 
 use crate::setup::DENOM_ATOM_ON_NTRN;
 
@@ -25,7 +25,12 @@ impl NativeRouterInstantiate {
             msg: valence_native_router::msg::InstantiateMsg {
                 op_mode_cfg,
                 receiver_address: receiver_address.to_string(),
+                receiver_msg: None,
                 denoms,
+                halt_config: None,
+                holder_address: None,
+                min_route_amounts: BTreeMap::new(),
+                cw20_tokens: BTreeSet::new(),
             },
         }
     }
@@ -40,10 +45,35 @@ impl NativeRouterInstantiate {
         self
     }
 
+    pub fn with_receiver_msg(&mut self, receiver_msg: Option<Binary>) -> &mut Self {
+        self.msg.receiver_msg = receiver_msg;
+        self
+    }
+
     pub fn with_denoms(&mut self, denoms: BTreeSet<String>) -> &mut Self {
         self.msg.denoms = denoms;
         self
     }
+
+    pub fn with_halt_config(&mut self, halt_config: Option<RouterHaltConfig>) -> &mut Self {
+        self.msg.halt_config = halt_config;
+        self
+    }
+
+    pub fn with_holder_address(&mut self, addr: Option<String>) -> &mut Self {
+        self.msg.holder_address = addr;
+        self
+    }
+
+    pub fn with_cw20_tokens(&mut self, cw20_tokens: BTreeSet<String>) -> &mut Self {
+        self.msg.cw20_tokens = cw20_tokens;
+        self
+    }
+
+    pub fn with_min_route_amount(&mut self, denom: &str, amount: Uint128) -> &mut Self {
+        self.msg.min_route_amounts.insert(denom.to_string(), amount);
+        self
+    }
 }
 
 impl NativeRouterInstantiate {
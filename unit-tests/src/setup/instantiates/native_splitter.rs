@@ -26,6 +26,12 @@ impl NativeSplitterInstantiate {
                 clock_address,
                 splits,
                 fallback_split,
+                emergency_committee: None,
+                owner: None,
+                completion_config: None,
+                receiver_types: BTreeMap::new(),
+                op_admin: None,
+                validate_denoms: false,
             },
         }
     }
@@ -44,6 +50,40 @@ impl NativeSplitterInstantiate {
         self.msg.fallback_split = fallback_split;
         self
     }
+
+    pub fn with_emergency_committee(&mut self, addr: &str) -> &mut Self {
+        self.msg.emergency_committee = Some(addr.to_string());
+        self
+    }
+
+    pub fn with_owner(&mut self, addr: &str) -> &mut Self {
+        self.msg.owner = Some(addr.to_string());
+        self
+    }
+
+    pub fn with_op_admin(&mut self, addr: &str) -> &mut Self {
+        self.msg.op_admin = Some(addr.to_string());
+        self
+    }
+
+    pub fn with_completion_config(
+        &mut self,
+        completion_config: Option<valence_native_splitter::msg::CompletionConfig>,
+    ) -> &mut Self {
+        self.msg.completion_config = completion_config;
+        self
+    }
+
+    pub fn with_receiver_type(
+        &mut self,
+        receiver: &str,
+        receiver_type: valence_native_splitter::msg::ReceiverType,
+    ) -> &mut Self {
+        self.msg
+            .receiver_types
+            .insert(receiver.to_string(), receiver_type);
+        self
+    }
 }
 
 impl NativeSplitterInstantiate {
@@ -52,7 +92,11 @@ impl NativeSplitterInstantiate {
         splits.insert(party_a_addr, Decimal::from_str("0.5").unwrap());
         splits.insert(party_b_addr, Decimal::from_str("0.5").unwrap());
 
-        let split_config = SplitConfig { receivers: splits };
+        let split_config = SplitConfig {
+            receivers: splits,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        };
         let mut denom_to_split_config_map = BTreeMap::new();
         denom_to_split_config_map.insert(DENOM_ATOM_ON_NTRN.to_string(), split_config.clone());
         denom_to_split_config_map.insert(DENOM_LS_ATOM_ON_NTRN.to_string(), split_config.clone());
@@ -62,6 +106,12 @@ impl NativeSplitterInstantiate {
                 clock_address,
                 splits: denom_to_split_config_map,
                 fallback_split: Some(split_config),
+                emergency_committee: None,
+                owner: None,
+                completion_config: None,
+                receiver_types: BTreeMap::new(),
+                op_admin: None,
+                validate_denoms: false,
             },
         }
     }
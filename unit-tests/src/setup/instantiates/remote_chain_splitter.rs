@@ -37,6 +37,10 @@ impl RemoteChainSplitterInstantiate {
                 ica_timeout,
                 ibc_transfer_timeout,
                 fallback_address,
+                emergency_committee: None,
+                owner: None,
+                op_admin: None,
+                ibc_fee_override: None,
             },
         }
     }
@@ -81,6 +85,21 @@ impl RemoteChainSplitterInstantiate {
         self
     }
 
+    pub fn with_emergency_committee(&mut self, addr: &str) -> &mut Self {
+        self.msg.emergency_committee = Some(addr.to_string());
+        self
+    }
+
+    pub fn with_owner(&mut self, addr: &str) -> &mut Self {
+        self.msg.owner = Some(addr.to_string());
+        self
+    }
+
+    pub fn with_op_admin(&mut self, addr: &str) -> &mut Self {
+        self.msg.op_admin = Some(addr.to_string());
+        self
+    }
+
     pub fn with_ibc_transfer_timeout(&mut self, ibc_transfer_timeout: Uint64) -> &mut Self {
         self.msg.ibc_transfer_timeout = ibc_transfer_timeout;
         self
@@ -97,7 +116,11 @@ impl RemoteChainSplitterInstantiate {
         splits.insert(party_a_addr.to_string(), Decimal::from_str("0.5").unwrap());
         splits.insert(party_b_addr.to_string(), Decimal::from_str("0.5").unwrap());
 
-        let split_config = SplitConfig { receivers: splits };
+        let split_config = SplitConfig {
+            receivers: splits,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        };
         let mut denom_to_split_config_map = BTreeMap::new();
         denom_to_split_config_map.insert(DENOM_ATOM_ON_NTRN.to_string(), split_config.clone());
 
@@ -112,6 +135,10 @@ impl RemoteChainSplitterInstantiate {
                 ica_timeout: Uint64::from(100u64),
                 ibc_transfer_timeout: Uint64::from(100u64),
                 fallback_address: None,
+                emergency_committee: None,
+                owner: None,
+                op_admin: None,
+                ibc_fee_override: None,
             },
         }
     }
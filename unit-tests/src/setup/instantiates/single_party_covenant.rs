@@ -125,6 +125,7 @@ impl SinglePartyCovenantInstantiate {
                 native_denom: denom.to_string(),
                 addr: recevier.to_string(),
                 contribution: coin(amount, denom),
+                receiver_msg: None,
             },
         )
     }
@@ -161,6 +162,16 @@ impl SinglePartyCovenantInstantiate {
                 asset_a_denom: denom_a.into(),
                 asset_b_denom: denom_b.into(),
                 single_side_lp_limits,
+                pair_type_migration_policy: None,
+                additional_assets: vec![],
+                oracle_price_guard: None,
+                max_pool_ownership: None,
+                generator_address: None,
+                twap_window: None,
+                single_side_rebalance: None,
+                min_lp_out_tolerance: None,
+                max_provision_per_tick: None,
+                funding_deadline: None,
             },
         )
     }
@@ -199,6 +210,8 @@ impl SinglePartyCovenantInstantiate {
                 timeouts,
                 contract_codes,
                 clock_tick_max_gas,
+                router_tick_interval: None,
+                ibc_fee_overrides: Default::default(),
                 lockup_period,
                 ls_info,
                 ls_forwarder_config,
@@ -208,6 +221,8 @@ impl SinglePartyCovenantInstantiate {
                 emergency_committee,
                 covenant_party_config,
                 liquid_pooler_config,
+                ibc_memo_template: None,
+                pre_staked_contribution: false,
             },
         }
     }
@@ -255,4 +270,9 @@ impl SinglePartyCovenantInstantiate {
         self.msg.emergency_committee = Some(emergency_committee.into());
         self
     }
+
+    pub fn with_pre_staked_contribution(&mut self, pre_staked: bool) -> &mut Self {
+        self.msg.pre_staked_contribution = pre_staked;
+        self
+    }
 }
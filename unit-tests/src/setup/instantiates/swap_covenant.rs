@@ -86,6 +86,7 @@ impl SwapCovenantInstantiate {
                 native_denom: denom.to_string(),
                 addr: recevier.to_string(),
                 contribution: coin(amount, denom),
+                receiver_msg: None,
             },
         )
     }
@@ -102,7 +103,11 @@ impl SwapCovenantInstantiate {
                 receivers.insert(receiver.to_string(), *amount);
             });
 
-            let split = covenant_utils::split::SplitConfig { receivers };
+            let split = covenant_utils::split::SplitConfig {
+                receivers,
+                rounding: Default::default(),
+                min_amounts: Default::default(),
+            };
 
             map.insert(denom.to_string(), split);
         });
@@ -130,12 +135,19 @@ impl SwapCovenantInstantiate {
                 timeouts,
                 contract_codes,
                 clock_tick_max_gas,
+                router_tick_interval: None,
+                ibc_fee_overrides: Default::default(),
                 lockup_config,
                 party_a_config,
                 party_b_config,
                 splits,
                 fallback_split,
                 fallback_address,
+                reward_denoms: None,
+                direct_settlement: false,
+                complete_on_settlement: false,
+                ibc_memo_template: None,
+                validate_denoms: false,
             },
         }
     }
@@ -206,7 +218,11 @@ impl SwapCovenantInstantiate {
             receivers.insert(receiver.to_string(), *amount);
         });
 
-        self.msg.fallback_split = Some(covenant_utils::split::SplitConfig { receivers });
+        self.msg.fallback_split = Some(covenant_utils::split::SplitConfig {
+            receivers,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        });
         self
     }
 
@@ -214,4 +230,14 @@ impl SwapCovenantInstantiate {
         self.msg.fallback_address = Some(addr);
         self
     }
+
+    pub fn with_direct_settlement(&mut self, direct_settlement: bool) -> &mut Self {
+        self.msg.direct_settlement = direct_settlement;
+        self
+    }
+
+    pub fn with_complete_on_settlement(&mut self, complete_on_settlement: bool) -> &mut Self {
+        self.msg.complete_on_settlement = complete_on_settlement;
+        self
+    }
 }
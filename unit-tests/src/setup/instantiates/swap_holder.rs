@@ -32,6 +32,9 @@ impl SwapHolderInstantiate {
                 covenant_terms,
                 parties_config,
                 refund_config,
+                direct_settlement: false,
+                owner: None,
+                validate_denoms: false,
             },
         }
     }
@@ -65,6 +68,16 @@ impl SwapHolderInstantiate {
         self.msg.refund_config = config;
         self
     }
+
+    pub fn with_direct_settlement(&mut self, direct_settlement: bool) -> &mut Self {
+        self.msg.direct_settlement = direct_settlement;
+        self
+    }
+
+    pub fn with_owner(&mut self, addr: &str) -> &mut Self {
+        self.msg.owner = Some(addr.to_string());
+        self
+    }
 }
 
 impl SwapHolderInstantiate {
@@ -101,6 +114,9 @@ impl SwapHolderInstantiate {
                     party_a_refund_address,
                     party_b_refund_address,
                 },
+                direct_settlement: false,
+                owner: None,
+                validate_denoms: false,
             },
         }
     }
@@ -40,6 +40,11 @@ impl TwoPartyCovenantInstantiate {
         self
     }
 
+    pub fn with_router_tick_interval(&mut self, router_tick_interval: Option<Uint64>) -> &mut Self {
+        self.msg.router_tick_interval = router_tick_interval;
+        self
+    }
+
     pub fn with_lockup_config(&mut self, lockup_config: Expiration) -> &mut Self {
         self.msg.lockup_config = lockup_config;
         self
@@ -102,6 +107,11 @@ impl TwoPartyCovenantInstantiate {
         self
     }
 
+    pub fn with_reward_denoms(&mut self, reward_denoms: Option<Vec<String>>) -> &mut Self {
+        self.msg.reward_denoms = reward_denoms;
+        self
+    }
+
     pub fn with_fallback_split(&mut self, fallback_split: Option<SplitConfig>) -> &mut Self {
         self.msg.fallback_split = fallback_split;
         self
@@ -112,6 +122,11 @@ impl TwoPartyCovenantInstantiate {
         self
     }
 
+    pub fn with_external_holder_address(&mut self, addr: Option<String>) -> &mut Self {
+        self.msg.external_holder_address = addr;
+        self
+    }
+
     pub fn with_liquid_pooler_config(
         &mut self,
         liquid_pooler_config: valence_covenant_two_party_pol::msg::LiquidPoolerConfig,
@@ -119,6 +134,11 @@ impl TwoPartyCovenantInstantiate {
         self.msg.liquid_pooler_config = liquid_pooler_config;
         self
     }
+
+    pub fn with_deployment_batch_size(&mut self, batch_size: Option<u64>) -> &mut Self {
+        self.msg.deployment_batch_size = batch_size;
+        self
+    }
 }
 
 impl TwoPartyCovenantInstantiate {
@@ -141,7 +161,11 @@ impl TwoPartyCovenantInstantiate {
         splits.insert(party_a_addr.to_string(), Decimal::from_str("0.5").unwrap());
         splits.insert(party_b_addr.to_string(), Decimal::from_str("0.5").unwrap());
 
-        let split_config = SplitConfig { receivers: splits };
+        let split_config = SplitConfig {
+            receivers: splits,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        };
         let mut denom_to_split_config_map = BTreeMap::new();
         denom_to_split_config_map.insert(DENOM_ATOM_ON_NTRN.to_string(), split_config.clone());
         denom_to_split_config_map.insert(DENOM_LS_ATOM_ON_NTRN.to_string(), split_config.clone());
@@ -155,6 +179,8 @@ impl TwoPartyCovenantInstantiate {
                 },
                 contract_codes,
                 clock_tick_max_gas: None,
+                router_tick_interval: None,
+                ibc_fee_overrides: Default::default(),
                 lockup_config: Expiration::AtHeight(200000),
                 ragequit_config: None,
                 deposit_deadline: Expiration::AtHeight(100000),
@@ -163,12 +189,14 @@ impl TwoPartyCovenantInstantiate {
                     native_denom: DENOM_ATOM_ON_NTRN.to_string(),
                     addr: party_a_addr.to_string(),
                     contribution: coin(10_000, DENOM_ATOM_ON_NTRN),
+                    receiver_msg: None,
                 }),
                 party_b_config: CovenantPartyConfig::Native(NativeCovenantParty {
                     party_receiver_addr: party_b_addr.to_string(),
                     native_denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
                     addr: party_b_addr.to_string(),
                     contribution: coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+                    receiver_msg: None,
                 }),
                 covenant_type: valence_two_party_pol_holder::msg::CovenantType::Share {},
                 party_a_share: Decimal::from_str("0.5").unwrap(),
@@ -190,10 +218,37 @@ impl TwoPartyCovenantInstantiate {
                             single_side_lp_limits: SingleSideLpLimits {
                                 asset_a_limit: Uint128::new(10_000),
                                 asset_b_limit: Uint128::new(10_000),
+                                pool_reserve_share: None,
                             },
+                            pair_type_migration_policy: None,
+                            additional_assets: vec![],
+                            oracle_price_guard: None,
+                            max_pool_ownership: None,
+                            generator_address: None,
+                            twap_window: None,
+                            single_side_rebalance: None,
+                            min_lp_out_tolerance: None,
+                            max_provision_per_tick: None,
+                            funding_deadline: None,
                         },
                     ),
                 fallback_address: None,
+                residual_lp_donation: None,
+                external_holder_address: None,
+                reward_denoms: None,
+                party_a_contribution_adapter: None,
+                party_b_contribution_adapter: None,
+                ibc_memo_template: None,
+                lockup_extension_window: None,
+                auto_distribute_on_expiry: false,
+                fee_refund_config: None,
+                generator_staking: None,
+                provisioning_stall: None,
+                depositor_allowlist: None,
+                vesting_config: None,
+                depeg_guard: None,
+                deployment_batch_size: None,
+                validate_denoms: false,
             },
         }
     }
@@ -41,6 +41,16 @@ impl TwoPartyHolderInstantiate {
                 splits,
                 fallback_split,
                 emergency_committee_addr,
+                residual_lp_donation: None,
+                lockup_extension_window: None,
+                auto_distribute_on_expiry: false,
+                fee_refund_config: None,
+                generator_staking: None,
+                provisioning_stall: None,
+                depositor_allowlist: None,
+                vesting_config: None,
+                depeg_guard: None,
+                validate_denoms: false,
             },
         }
     }
@@ -96,6 +106,72 @@ impl TwoPartyHolderInstantiate {
         self.msg.emergency_committee_addr = Some(addr.to_string());
         self
     }
+
+    pub fn with_lockup_extension_window(&mut self, window: u64) -> &mut Self {
+        self.msg.lockup_extension_window = Some(window);
+        self
+    }
+
+    pub fn with_auto_distribute_on_expiry(&mut self, enabled: bool) -> &mut Self {
+        self.msg.auto_distribute_on_expiry = enabled;
+        self
+    }
+
+    pub fn with_fee_refund_config(
+        &mut self,
+        config: valence_two_party_pol_holder::msg::FeeRefundConfig,
+    ) -> &mut Self {
+        self.msg.fee_refund_config = Some(config);
+        self
+    }
+
+    pub fn with_generator_staking(
+        &mut self,
+        config: valence_two_party_pol_holder::msg::GeneratorStakingConfig,
+    ) -> &mut Self {
+        self.msg.generator_staking = Some(config);
+        self
+    }
+
+    pub fn with_provisioning_stall(
+        &mut self,
+        config: valence_two_party_pol_holder::msg::ProvisioningStallConfig,
+    ) -> &mut Self {
+        self.msg.provisioning_stall = Some(config);
+        self
+    }
+
+    pub fn with_depositor_allowlist(
+        &mut self,
+        allowlist: valence_two_party_pol_holder::msg::DepositorAllowlist,
+    ) -> &mut Self {
+        self.msg.depositor_allowlist = Some(allowlist);
+        self
+    }
+
+    pub fn with_vesting_config(
+        &mut self,
+        config: valence_two_party_pol_holder::msg::VestingConfig,
+    ) -> &mut Self {
+        self.msg.vesting_config = Some(config);
+        self
+    }
+
+    pub fn with_depeg_guard(
+        &mut self,
+        config: valence_two_party_pol_holder::msg::DepegGuardConfig,
+    ) -> &mut Self {
+        self.msg.depeg_guard = Some(config);
+        self
+    }
+
+    pub fn with_residual_lp_donation(
+        &mut self,
+        config: valence_two_party_pol_holder::msg::ResidualLpDonationConfig,
+    ) -> &mut Self {
+        self.msg.residual_lp_donation = Some(config);
+        self
+    }
 }
 
 impl TwoPartyHolderInstantiate {
@@ -109,7 +185,11 @@ impl TwoPartyHolderInstantiate {
         splits.insert(party_a_addr.to_string(), Decimal::from_str("0.5").unwrap());
         splits.insert(party_b_addr.to_string(), Decimal::from_str("0.5").unwrap());
 
-        let split_config = SplitConfig { receivers: splits };
+        let split_config = SplitConfig {
+            receivers: splits,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        };
         let mut denom_to_split_config_map = BTreeMap::new();
         denom_to_split_config_map.insert(DENOM_ATOM_ON_NTRN.to_string(), split_config.clone());
         denom_to_split_config_map.insert(DENOM_LS_ATOM_ON_NTRN.to_string(), split_config.clone());
@@ -128,6 +208,7 @@ impl TwoPartyHolderInstantiate {
                         controller_addr: party_a_addr.to_string(),
                         allocation: Decimal::from_str("0.5").unwrap(),
                         router: party_a_addr.to_string(),
+                        contribution_adapter: None,
                     },
                     party_b: valence_two_party_pol_holder::msg::TwoPartyPolCovenantParty {
                         contribution: coin(10_000, DENOM_LS_ATOM_ON_NTRN),
@@ -135,12 +216,23 @@ impl TwoPartyHolderInstantiate {
                         controller_addr: party_b_addr.to_string(),
                         allocation: Decimal::from_str("0.5").unwrap(),
                         router: party_b_addr.to_string(),
+                        contribution_adapter: None,
                     },
                     covenant_type: valence_two_party_pol_holder::msg::CovenantType::Share {},
                 },
                 splits: denom_to_split_config_map,
                 fallback_split: None,
                 emergency_committee_addr: None,
+                residual_lp_donation: None,
+                lockup_extension_window: None,
+                auto_distribute_on_expiry: false,
+                fee_refund_config: None,
+                generator_staking: None,
+                provisioning_stall: None,
+                depositor_allowlist: None,
+                vesting_config: None,
+                depeg_guard: None,
+                validate_denoms: false,
             },
         }
     }
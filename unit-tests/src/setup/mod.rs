@@ -8,11 +8,14 @@ use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
 
 use self::{custom_keepers::CustomStargateKeeper, custom_module::NeutronKeeper};
 
+pub mod adapter_contracts;
 pub mod astro_contracts;
 pub mod base_suite;
 pub mod contracts;
 pub mod custom_keepers;
 pub mod custom_module;
+pub mod dao_contracts;
+pub mod generator_contracts;
 pub mod instantiates;
 pub mod suite_builder;
 
@@ -53,6 +56,7 @@ pub const ALL_DENOMS: &[&str] = &[
     DENOM_LS_ATOM_ON_NTRN,
     DENOM_FALLBACK_ON_HUB,
     DENOM_OSMO_ON_HUB_FROM_NTRN,
+    DENOM_OSMO_ON_NTRN,
 ];
 
 // Addrs
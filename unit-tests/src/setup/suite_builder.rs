@@ -4,7 +4,7 @@ use astroport::pair_concentrated::ConcentratedPoolParams;
 use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
     coin, coins, instantiate2_address, to_json_binary, Addr, Api, CodeInfoResponse, Coin, Decimal,
-    Empty,
+    Empty, Uint128,
 };
 use cw_multi_test::{
     addons::{MockAddressGenerator, MockApiBech32},
@@ -21,10 +21,11 @@ use super::{
     },
     contracts::{
         astroport_pooler_contract, clock_contract, ibc_forwarder_contract,
-        interchain_router_contract, native_router_contract, native_splitter_contract,
-        osmo_lp_outpost_contract, remote_splitter_contract, single_party_covenant_contract,
-        single_party_holder_contract, stride_lser_contract, swap_covenant_contract,
-        swap_holder_contract, two_party_covenant_contract, two_party_holder_contract,
+        interchain_router_contract, invariant_checker_contract, native_router_contract,
+        native_splitter_contract, osmo_lp_outpost_contract, remote_splitter_contract,
+        single_party_covenant_contract, single_party_holder_contract, stride_lser_contract,
+        swap_covenant_contract, swap_holder_contract, two_party_covenant_contract,
+        two_party_holder_contract,
     },
     custom_keepers::CustomStargateKeeper,
     custom_module::{NeutronKeeper, CHAIN_PREFIX},
@@ -61,6 +62,7 @@ pub struct SuiteBuilder {
     pub stride_staker_code_id: u64,
     pub two_party_holder_code_id: u64,
     pub osmo_lp_outpost_code_id: u64,
+    pub invariant_checker_code_id: u64,
 
     // astro contracts
     pub astro_token_code_id: u64,
@@ -144,6 +146,8 @@ impl SuiteBuilder {
 
         let two_party_covenant_code_id = app.store_code(two_party_covenant_contract());
 
+        let invariant_checker_code_id = app.store_code(invariant_checker_contract());
+
         Self {
             faucet: app.api().addr_make(FAUCET),
             admin: app.api().addr_make(ADMIN),
@@ -167,6 +171,7 @@ impl SuiteBuilder {
             stride_staker_code_id,
             two_party_holder_code_id,
             osmo_lp_outpost_code_id,
+            invariant_checker_code_id,
 
             astro_token_code_id,
             astro_whitelist_code_id,
@@ -184,6 +189,63 @@ impl SuiteBuilder {
         pair_type: astroport::factory::PairType,
         coin_a: Coin,
         coin_b: Coin,
+    ) -> (Addr, Addr) {
+        self.init_astro_pool_with_generator(pair_type, coin_a, coin_b, None)
+    }
+
+    /// inits a pool whose factory auto-stakes LP tokens into the generator
+    pub fn init_astro_pool_with_generator(
+        &mut self,
+        pair_type: astroport::factory::PairType,
+        coin_a: Coin,
+        coin_b: Coin,
+        generator: Option<Addr>,
+    ) -> (Addr, Addr) {
+        let assets = vec![
+            astroport::asset::Asset {
+                info: astroport::asset::AssetInfo::NativeToken {
+                    denom: coin_a.denom,
+                },
+                amount: coin_a.amount,
+            },
+            astroport::asset::Asset {
+                info: astroport::asset::AssetInfo::NativeToken {
+                    denom: coin_b.denom,
+                },
+                amount: coin_b.amount,
+            },
+        ];
+        self.init_astro_pool_with_assets(pair_type, assets, generator)
+    }
+
+    /// inits a cw20 token with the whole supply minted to the faucet
+    pub fn init_cw20_token(&mut self, symbol: &str, supply: Uint128) -> Addr {
+        let token_init = astroport::token::InstantiateMsg {
+            name: format!("{symbol} token"),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: vec![cw20::Cw20Coin {
+                address: self.faucet.to_string(),
+                amount: supply,
+            }],
+            mint: None,
+            marketing: None,
+        };
+        self.contract_init(
+            self.astro_token_code_id,
+            symbol.to_string(),
+            &token_init,
+            &[],
+        )
+    }
+
+    /// inits a pool of the given native and cw20 assets, providing
+    /// the initial liquidity from the faucet
+    pub fn init_astro_pool_with_assets(
+        &mut self,
+        pair_type: astroport::factory::PairType,
+        assets: Vec<astroport::asset::Asset>,
+        generator: Option<Addr>,
     ) -> (Addr, Addr) {
         let registery_init = astroport::native_coin_registry::InstantiateMsg {
             owner: self.admin.to_string(),
@@ -207,7 +269,15 @@ impl SuiteBuilder {
                 self.admin.clone(),
                 coin_registry_addr.clone(),
                 &astroport::native_coin_registry::ExecuteMsg::Add {
-                    native_coins: vec![(coin_a.denom.clone(), 6), (coin_b.denom.clone(), 6)],
+                    native_coins: assets
+                        .iter()
+                        .filter_map(|asset| match &asset.info {
+                            astroport::asset::AssetInfo::NativeToken { denom } => {
+                                Some((denom.to_string(), 6))
+                            }
+                            astroport::asset::AssetInfo::Token { .. } => None,
+                        })
+                        .collect(),
                 },
                 &[],
             )
@@ -244,7 +314,7 @@ impl SuiteBuilder {
             ],
             token_code_id: self.astro_token_code_id,
             fee_address: None,
-            generator_address: None,
+            generator_address: generator.map(|addr| addr.to_string()),
             owner: self.admin.to_string(),
             whitelist_code_id: self.astro_whitelist_code_id,
             coin_registry_address: coin_registry_addr.to_string(),
@@ -262,14 +332,8 @@ impl SuiteBuilder {
             .unwrap();
         self.app.update_block(|b| b.height += 5);
 
-        let asset_infos = vec![
-            astroport::asset::AssetInfo::NativeToken {
-                denom: coin_a.denom.clone(),
-            },
-            astroport::asset::AssetInfo::NativeToken {
-                denom: coin_b.denom.clone(),
-            },
-        ];
+        let asset_infos: Vec<astroport::asset::AssetInfo> =
+            assets.iter().map(|asset| asset.info.clone()).collect();
 
         let init_params = match &pair_type {
             astroport::factory::PairType::Stable {} => {
@@ -325,23 +389,29 @@ impl SuiteBuilder {
             )
             .unwrap();
 
-        // provide liquidity to the pool
-        let balances = vec![coin_a.clone(), coin_b.clone()];
-
-        let assets = vec![
-            astroport::asset::Asset {
-                info: astroport::asset::AssetInfo::NativeToken {
-                    denom: coin_a.denom.to_string(),
-                },
-                amount: coin_a.amount,
-            },
-            astroport::asset::Asset {
-                info: astroport::asset::AssetInfo::NativeToken {
-                    denom: coin_b.denom,
-                },
-                amount: coin_b.amount,
-            },
-        ];
+        // provide liquidity to the pool, approving any cw20 assets first
+        let mut balances = vec![];
+        for asset in assets.iter() {
+            match &asset.info {
+                astroport::asset::AssetInfo::NativeToken { denom } => {
+                    balances.push(coin(asset.amount.u128(), denom))
+                }
+                astroport::asset::AssetInfo::Token { contract_addr } => {
+                    self.app
+                        .execute_contract(
+                            self.faucet.clone(),
+                            contract_addr.clone(),
+                            &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                                spender: pool_info.contract_addr.to_string(),
+                                amount: asset.amount,
+                                expires: None,
+                            },
+                            &[],
+                        )
+                        .unwrap();
+                }
+            }
+        }
 
         let provide_liquidity_msg = astroport::pair::ExecuteMsg::ProvideLiquidity {
             assets,
@@ -1,19 +1,25 @@
 use astroport::factory::PairType;
-use cosmwasm_std::{coin, Addr, Coin, Decimal};
+use cosmwasm_std::{coin, Addr, Binary, Coin, Decimal, Uint128};
+use covenant_oracle::PriceGuard;
 use covenant_utils::{
+    deadline::Deadline,
     op_mode::{ContractOperationMode, ContractOperationModeConfig},
+    pool_ownership::PoolOwnership,
     PoolPriceConfig, SingleSideLpLimits,
 };
 use cw_multi_test::{AppResponse, Executor};
 use cw_utils::Expiration;
-use valence_astroport_liquid_pooler::msg::{LpConfig, ProvidedLiquidityInfo, QueryMsg};
+use valence_astroport_liquid_pooler::msg::{
+    LpConfig, PairTypeMigrationPolicy, ProvideSimulation, ProvidedLiquidityInfo, ProvisionFailure,
+    ProvisionSimulation, QueryMsg, SingleSideRebalanceConfig,
+};
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
     instantiates::astro_liquid_pooler::AstroLiquidPoolerInstantiate,
     suite_builder::SuiteBuilder,
-    CustomApp, ASTRO_LIQUID_POOLER_SALT, CLOCK_SALT, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN,
-    SINGLE_PARTY_HOLDER_SALT,
+    CustomApp, ADMIN, ASTRO_LIQUID_POOLER_SALT, CLOCK_SALT, DENOM_ATOM_ON_NTRN,
+    DENOM_LS_ATOM_ON_NTRN, SINGLE_PARTY_HOLDER_SALT,
 };
 
 pub struct AstroLiquidPoolerBuilder {
@@ -54,6 +60,7 @@ impl Default for AstroLiquidPoolerBuilder {
             tick_max_gas: None,
             whitelist: vec![liquid_pooler_addr.to_string()],
             initial_queue: vec![],
+            tick_intervals: vec![],
         };
 
         builder.contract_init2(
@@ -135,6 +142,66 @@ impl AstroLiquidPoolerBuilder {
         self
     }
 
+    pub fn with_pair_type_migration_policy(
+        mut self,
+        policy: Option<PairTypeMigrationPolicy>,
+    ) -> Self {
+        self.instantiate_msg.with_pair_type_migration_policy(policy);
+        self
+    }
+
+    pub fn with_emergency_committee(mut self, emergency_committee: Option<String>) -> Self {
+        self.instantiate_msg
+            .with_emergency_committee(emergency_committee);
+        self
+    }
+
+    pub fn with_oracle_price_guard(mut self, guard: Option<PriceGuard>) -> Self {
+        self.instantiate_msg.with_oracle_price_guard(guard);
+        self
+    }
+
+    pub fn with_max_pool_ownership(mut self, max_pool_ownership: Option<Decimal>) -> Self {
+        self.instantiate_msg
+            .with_max_pool_ownership(max_pool_ownership);
+        self
+    }
+
+    pub fn with_generator_address(mut self, generator_address: Option<String>) -> Self {
+        self.instantiate_msg
+            .with_generator_address(generator_address);
+        self
+    }
+
+    pub fn with_twap_window(mut self, twap_window: Option<u64>) -> Self {
+        self.instantiate_msg.with_twap_window(twap_window);
+        self
+    }
+
+    pub fn with_min_lp_out_tolerance(mut self, tolerance: Option<Decimal>) -> Self {
+        self.instantiate_msg.with_min_lp_out_tolerance(tolerance);
+        self
+    }
+
+    pub fn with_max_provision_per_tick(mut self, cap: Option<Coin>) -> Self {
+        self.instantiate_msg.with_max_provision_per_tick(cap);
+        self
+    }
+
+    pub fn with_funding_deadline(mut self, deadline: Option<Deadline>) -> Self {
+        self.instantiate_msg.with_funding_deadline(deadline);
+        self
+    }
+
+    pub fn with_single_side_rebalance(
+        mut self,
+        single_side_rebalance: Option<SingleSideRebalanceConfig>,
+    ) -> Self {
+        self.instantiate_msg
+            .with_single_side_rebalance(single_side_rebalance);
+        self
+    }
+
     pub fn with_holder_address(mut self, holder_address: String) -> Self {
         self.instantiate_msg.with_holder_address(holder_address);
         self
@@ -299,6 +366,235 @@ impl Suite {
             )
             .unwrap()
     }
+
+    pub(crate) fn query_pool_ownership(&self) -> PoolOwnership {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::PoolOwnership {},
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_simulate_provision(&self) -> ProvisionSimulation {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::SimulateProvision {},
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_simulate_provide(&self, coin_a: Coin, coin_b: Coin) -> ProvideSimulation {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::SimulateProvide { coin_a, coin_b },
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_oracle_price_guard(&self) -> Option<PriceGuard> {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::OraclePriceGuard {},
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_pair_type_migration_policy(&self) -> PairTypeMigrationPolicy {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::PairTypeMigrationPolicy {},
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_provisioning_paused(&self) -> bool {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::ProvisioningPaused {},
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_last_provision_failure(&self) -> Option<ProvisionFailure> {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::LastProvisionFailure {},
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_funding_expiration(&self) -> Option<Expiration> {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::FundingExpiration {},
+            )
+            .unwrap()
+    }
+
+    /// sends cw20 tokens from the faucet to the liquid pooler with a hook
+    pub(crate) fn send_cw20(&mut self, token: &Addr, amount: Uint128) -> AppResponse {
+        let faucet = self.faucet.clone();
+        let liquid_pooler = self.liquid_pooler_addr.to_string();
+        self.app
+            .execute_contract(
+                faucet,
+                token.clone(),
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: liquid_pooler,
+                    amount,
+                    msg: Binary::default(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_cw20_balance(&self, token: &Addr, addr: &Addr) -> Uint128 {
+        let balance: cw20::BalanceResponse = self
+            .get_app()
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: addr.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    }
+
+    /// queries the LP tokens staked in the generator by the liquid pooler
+    pub(crate) fn query_generator_deposit(&self, generator: &Addr) -> Uint128 {
+        let pair_info: astroport::asset::PairInfo = self
+            .get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.lp_config.pool_address.clone(),
+                &astroport::pair::QueryMsg::Pair {},
+            )
+            .unwrap();
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                generator,
+                &astroport::generator::QueryMsg::Deposit {
+                    lp_token: pair_info.liquidity_token.to_string(),
+                    user: self.liquid_pooler_addr.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_withdrawn_liquidity(&self) -> Vec<Coin> {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::WithdrawnLiquidity {},
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn query_idle_balances(&self) -> Vec<Coin> {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.liquid_pooler_addr.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::IdleBalances {},
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn return_idle_funds(&mut self, sender: &Addr) -> AppResponse {
+        let pooler = self.liquid_pooler_addr.clone();
+        self.get_app()
+            .execute_contract(
+                sender.clone(),
+                pooler,
+                &valence_astroport_liquid_pooler::msg::ExecuteMsg::ReturnIdleFunds {},
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn update_pair_type_migration_policy(
+        &mut self,
+        sender: &Addr,
+        policy: PairTypeMigrationPolicy,
+    ) -> AppResponse {
+        let pooler = self.liquid_pooler_addr.clone();
+        self.get_app()
+            .execute_contract(
+                sender.clone(),
+                pooler,
+                &valence_astroport_liquid_pooler::msg::ExecuteMsg::UpdatePairTypeMigrationPolicy {
+                    policy,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub(crate) fn accept_pair_type_migration(&mut self, sender: &Addr) -> AppResponse {
+        let pooler = self.liquid_pooler_addr.clone();
+        self.get_app()
+            .execute_contract(
+                sender.clone(),
+                pooler,
+                &valence_astroport_liquid_pooler::msg::ExecuteMsg::AcceptPairTypeMigration {},
+                &[],
+            )
+            .unwrap()
+    }
+
+    /// simulates the pool migrating away from the configured pair type
+    /// by reconfiguring the pooler with a different one
+    pub(crate) fn migrate_configured_pair_type(&mut self, pair_type: PairType) {
+        let mut lp_config = self.query_lp_config();
+        lp_config.pair_type = pair_type;
+        let pooler = self.liquid_pooler_addr.clone();
+        self.get_app()
+            .migrate_contract(
+                Addr::unchecked(ADMIN),
+                pooler,
+                &valence_astroport_liquid_pooler::msg::MigrateMsg::UpdateConfig {
+                    op_mode: None,
+                    holder_address: None,
+                    lp_config: Some(Box::new(lp_config)),
+                    emergency_committee: None,
+                },
+                11,
+            )
+            .unwrap();
+    }
+
+    pub(crate) fn set_provisioning_paused(&mut self, sender: &Addr, paused: bool) -> AppResponse {
+        let pooler = self.liquid_pooler_addr.clone();
+        self.get_app()
+            .execute_contract(
+                sender.clone(),
+                pooler,
+                &valence_astroport_liquid_pooler::msg::ExecuteMsg::SetProvisioningPaused { paused },
+                &[],
+            )
+            .unwrap()
+    }
 }
 
 impl BaseSuiteMut for Suite {
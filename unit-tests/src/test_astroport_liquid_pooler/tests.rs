@@ -1,19 +1,27 @@
 use std::str::FromStr;
 
 use cosmwasm_std::{coin, coins, Addr, Decimal, Event, Uint128};
+use covenant_oracle::{PriceGuard, PriceSource};
 use covenant_utils::{
+    deadline::Deadline,
+    multi_asset::PoolAssetConfig,
     op_mode::{ContractOperationMode, ContractOperationModeConfig},
-    PoolPriceConfig,
+    PoolPriceConfig, SingleSideLpLimits,
 };
 use cw_multi_test::Executor;
-use valence_astroport_liquid_pooler::msg::{AssetData, ProvidedLiquidityInfo};
+use cw_utils::{Duration, Expiration};
+use valence_astroport_liquid_pooler::msg::{
+    AssetData, PairTypeMigrationPolicy, ProvidedLiquidityInfo, ProvisionAction,
+    SingleSideRebalanceConfig,
+};
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
-    ADMIN, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN,
+    generator_contracts::{generator_contract, GeneratorInstantiateMsg},
+    ADMIN, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN, DENOM_NTRN,
 };
 
-use super::suite::AstroLiquidPoolerBuilder;
+use super::suite::{AstroLiquidPoolerBuilder, Suite};
 
 #[test]
 fn test_instantiate_with_valid_op_mode() {
@@ -112,6 +120,141 @@ fn test_instantiate_validates_pool_pair_type() {
         .build();
 }
 
+#[test]
+#[should_panic(expected = "is configured more than once")]
+fn test_instantiate_validates_unique_asset_denoms() {
+    AstroLiquidPoolerBuilder::default()
+        .with_assets(AssetData {
+            asset_a_denom: DENOM_ATOM_ON_NTRN.to_string(),
+            asset_b_denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+            additional_assets: vec![PoolAssetConfig {
+                denom: DENOM_ATOM_ON_NTRN.to_string(),
+                single_side_limit: Uint128::new(100_000),
+            }],
+        })
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Xyk pools do not support more than two assets")]
+fn test_instantiate_validates_multi_asset_pair_type() {
+    AstroLiquidPoolerBuilder::default()
+        .with_custom_astroport_pool(
+            astroport::factory::PairType::Xyk {},
+            coin(1_000_000, DENOM_ATOM_ON_NTRN),
+            coin(1_000_000, DENOM_LS_ATOM_ON_NTRN),
+        )
+        .with_pair_type(astroport::factory::PairType::Xyk {})
+        .with_assets(AssetData {
+            asset_a_denom: DENOM_ATOM_ON_NTRN.to_string(),
+            asset_b_denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+            additional_assets: vec![PoolAssetConfig {
+                denom: DENOM_NTRN.to_string(),
+                single_side_limit: Uint128::new(100_000),
+            }],
+        })
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Concentrated pools do not support a slippage tolerance above 0.5")]
+fn test_instantiate_validates_pcl_slippage_tolerance() {
+    let pcl_pair_type = astroport::factory::PairType::Custom("concentrated".to_string());
+    AstroLiquidPoolerBuilder::default()
+        .with_custom_astroport_pool(
+            pcl_pair_type.clone(),
+            coin(1_000_000, DENOM_ATOM_ON_NTRN),
+            coin(1_000_000, DENOM_LS_ATOM_ON_NTRN),
+        )
+        .with_pair_type(pcl_pair_type)
+        .with_slippage_tolerance(Some(Decimal::percent(60)))
+        .build();
+}
+
+#[test]
+fn test_simulate_pcl_provision_validates_price_scale() {
+    let pcl_pair_type = astroport::factory::PairType::Custom("concentrated".to_string());
+    let suite = AstroLiquidPoolerBuilder::default()
+        .with_custom_astroport_pool(
+            pcl_pair_type.clone(),
+            coin(1_000_000_000, DENOM_ATOM_ON_NTRN),
+            coin(1_000_000_000, DENOM_LS_ATOM_ON_NTRN),
+        )
+        .with_pair_type(pcl_pair_type)
+        .with_slippage_tolerance(Some(Decimal::percent(50)))
+        .build();
+
+    // the pool is initialized with a price scale of one
+    let simulation = suite.query_simulate_provision();
+    assert_eq!(simulation.pool_ratio, Decimal::one());
+    assert_eq!(simulation.pool_ratio_error, None);
+}
+
+#[test]
+fn test_instantiate_defaults_pair_type_migration_policy_to_reject() {
+    let suite = AstroLiquidPoolerBuilder::default().build();
+
+    assert_eq!(
+        suite.query_pair_type_migration_policy(),
+        PairTypeMigrationPolicy::Reject
+    );
+}
+
+#[test]
+fn test_instantiate_with_pair_type_migration_policy() {
+    let suite = AstroLiquidPoolerBuilder::default()
+        .with_pair_type_migration_policy(Some(PairTypeMigrationPolicy::PauseForCommittee))
+        .build();
+
+    assert_eq!(
+        suite.query_pair_type_migration_policy(),
+        PairTypeMigrationPolicy::PauseForCommittee
+    );
+}
+
+#[test]
+fn test_instantiate_without_oracle_price_guard() {
+    let suite = AstroLiquidPoolerBuilder::default().build();
+
+    assert_eq!(suite.query_oracle_price_guard(), None);
+}
+
+#[test]
+fn test_instantiate_with_oracle_price_guard() {
+    let guard = PriceGuard {
+        source: PriceSource::Slinky {},
+        base: "ATOM".to_string(),
+        quote: "USD".to_string(),
+        max_deviation: Decimal::percent(5),
+    };
+    let suite = AstroLiquidPoolerBuilder::default()
+        .with_oracle_price_guard(Some(guard.clone()))
+        .build();
+
+    assert_eq!(suite.query_oracle_price_guard(), Some(guard));
+}
+
+#[test]
+#[should_panic(expected = "price guard max deviation must be non-zero")]
+fn test_instantiate_validates_oracle_price_guard() {
+    AstroLiquidPoolerBuilder::default()
+        .with_oracle_price_guard(Some(PriceGuard {
+            source: PriceSource::Slinky {},
+            base: "ATOM".to_string(),
+            quote: "USD".to_string(),
+            max_deviation: Decimal::zero(),
+        }))
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "max pool ownership must be within (0, 1]")]
+fn test_instantiate_validates_max_pool_ownership() {
+    AstroLiquidPoolerBuilder::default()
+        .with_max_pool_ownership(Some(Decimal::percent(101)))
+        .build();
+}
+
 #[test]
 #[should_panic(expected = "Withdraw percentage range must belong to range (0.0, 1.0]")]
 fn test_withdraw_validates_percentage_range_ceiling() {
@@ -247,6 +390,221 @@ fn test_withdraw_no_percentage_defaults_to_full_position() {
     suite.assert_balance(&holder, coin(500_000, DENOM_LS_ATOM_ON_NTRN));
 }
 
+#[test]
+fn test_withdraw_records_withdrawn_liquidity() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let withdrawer = suite.clock_addr.clone();
+
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+    assert!(suite.query_withdrawn_liquidity().is_empty());
+
+    suite.expire_lockup();
+    let resp = suite.withdraw(&withdrawer, None);
+
+    let withdrawn_liquidity = suite.query_withdrawn_liquidity();
+    assert_eq!(
+        withdrawn_liquidity,
+        vec![
+            suite.query_balance(&suite.holder_addr, DENOM_ATOM_ON_NTRN),
+            suite.query_balance(&suite.holder_addr, DENOM_LS_ATOM_ON_NTRN),
+        ]
+    );
+    assert!(resp.has_event(
+        &Event::new("wasm")
+            .add_attribute("method", "handle_withdraw_reply_id")
+            .add_attribute(
+                "withdrawn_coins",
+                cosmwasm_std::to_json_string(&withdrawn_liquidity).unwrap()
+            )
+    ));
+}
+
+/// builds a liquid pooler auto-staking its LP tokens into a mock
+/// generator paying out 1_000untrn on every claim
+fn build_auto_staking_suite() -> (Suite, Addr) {
+    let mut builder = AstroLiquidPoolerBuilder::default();
+    let generator_code_id = builder.builder.app.store_code(generator_contract());
+    let generator = builder.builder.contract_init(
+        generator_code_id,
+        "generator".to_string(),
+        &GeneratorInstantiateMsg {
+            reward: coin(1_000, DENOM_NTRN),
+        },
+        &[],
+    );
+    let faucet = builder.builder.faucet.clone();
+    builder
+        .builder
+        .app
+        .send_tokens(faucet, generator.clone(), &[coin(10_000, DENOM_NTRN)])
+        .unwrap();
+
+    let (pool_addr, _lp_token_addr) = builder.builder.init_astro_pool_with_generator(
+        astroport::factory::PairType::Stable {},
+        coin(10_000_000_000_000, DENOM_ATOM_ON_NTRN),
+        coin(10_000_000_000_000, DENOM_LS_ATOM_ON_NTRN),
+        Some(generator.clone()),
+    );
+    let suite = builder
+        .with_pool_address(pool_addr.to_string())
+        .with_generator_address(Some(generator.to_string()))
+        .build();
+
+    (suite, generator)
+}
+
+#[test]
+fn test_auto_stake_lp_tokens_and_claim_rewards() {
+    let (mut suite, generator) = build_auto_staking_suite();
+    suite.fund_contract(
+        &[
+            coin(500_000, DENOM_ATOM_ON_NTRN),
+            coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    // liquidity gets provided with the LP tokens staked right away
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+    let staked_lp_amount = suite.query_generator_deposit(&generator);
+    assert!(!staked_lp_amount.is_zero());
+    assert_eq!(suite.query_pool_ownership().owned_shares, staked_lp_amount);
+
+    // rewards are claimed on the following ticks
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+    suite.assert_balance(&suite.liquid_pooler_addr, coin(1_000, DENOM_NTRN));
+}
+
+#[test]
+fn test_withdraw_unstakes_lp_tokens() {
+    let (mut suite, generator) = build_auto_staking_suite();
+    let withdrawer = suite.clock_addr.clone();
+    suite.fund_contract(
+        &[
+            coin(500_000, DENOM_ATOM_ON_NTRN),
+            coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+    assert!(!suite.query_generator_deposit(&generator).is_zero());
+
+    suite.expire_lockup();
+    suite.withdraw(&withdrawer, None);
+
+    assert!(suite.query_generator_deposit(&generator).is_zero());
+    let withdrawn_liquidity = suite.query_withdrawn_liquidity();
+    assert_eq!(withdrawn_liquidity.len(), 2);
+    assert_eq!(
+        withdrawn_liquidity,
+        vec![
+            suite.query_balance(&suite.holder_addr, DENOM_ATOM_ON_NTRN),
+            suite.query_balance(&suite.holder_addr, DENOM_LS_ATOM_ON_NTRN),
+        ]
+    );
+}
+
+/// builds a liquid pooler providing liquidity to a stable pool
+/// of a cw20 token and a native denom
+fn build_cw20_pool_suite() -> (Suite, Addr) {
+    let mut builder = AstroLiquidPoolerBuilder::default();
+    let token = builder
+        .builder
+        .init_cw20_token("CWATOM", Uint128::new(100_000_000_000_000));
+    let (pool_addr, _lp_token_addr) = builder.builder.init_astro_pool_with_assets(
+        astroport::factory::PairType::Stable {},
+        vec![
+            astroport::asset::Asset {
+                info: astroport::asset::AssetInfo::Token {
+                    contract_addr: token.clone(),
+                },
+                amount: Uint128::new(10_000_000_000_000),
+            },
+            astroport::asset::Asset {
+                info: astroport::asset::AssetInfo::NativeToken {
+                    denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+                },
+                amount: Uint128::new(10_000_000_000_000),
+            },
+        ],
+        None,
+    );
+    let suite = builder
+        .with_pool_address(pool_addr.to_string())
+        .with_assets(AssetData {
+            asset_a_denom: token.to_string(),
+            asset_b_denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+            additional_assets: vec![],
+        })
+        .build();
+
+    (suite, token)
+}
+
+#[test]
+fn test_provide_liquidity_with_cw20_asset() {
+    let (mut suite, token) = build_cw20_pool_suite();
+    assert_eq!(suite.lp_config.cw20_assets, vec![token.clone()]);
+
+    suite.send_cw20(&token, Uint128::new(500_000));
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    assert_eq!(
+        suite.query_idle_balances(),
+        vec![
+            coin(500_000, token.as_str()),
+            coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+        ]
+    );
+
+    let resp = suite.tick_contract(suite.liquid_pooler_addr.clone());
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "double_side_lp"));
+
+    assert!(suite.query_idle_balances().is_empty());
+    assert!(!suite.query_pool_ownership().owned_shares.is_zero());
+    assert_eq!(
+        suite.query_provided_liquidity_info().provided_coin_a,
+        coin(500_000, token.as_str())
+    );
+}
+
+#[test]
+#[should_panic(expected = "is not a pool asset")]
+fn test_receive_cw20_validates_token() {
+    let mut builder = AstroLiquidPoolerBuilder::default();
+    let other_token = builder
+        .builder
+        .init_cw20_token("CWOSMO", Uint128::new(100_000_000_000_000));
+    let mut suite = builder.build();
+
+    suite.send_cw20(&other_token, Uint128::new(500_000));
+}
+
+#[test]
+fn test_return_idle_cw20_funds() {
+    let (mut suite, token) = build_cw20_pool_suite();
+    let holder = suite.holder_addr.clone();
+
+    suite.send_cw20(&token, Uint128::new(500_000));
+    suite.return_idle_funds(&holder);
+
+    assert!(suite.query_idle_balances().is_empty());
+    assert_eq!(
+        suite.query_cw20_balance(&token, &holder),
+        Uint128::new(500_000)
+    );
+}
+
 #[test]
 #[should_panic(expected = "Contract operation unauthorized")]
 fn test_tick_unauthorized() {
@@ -283,6 +641,32 @@ fn test_provide_liquidity_validates_pair_type() {
     suite.tick_contract(suite.liquid_pooler_addr.clone());
 }
 
+#[test]
+#[should_panic(expected = "all pool assets must be non-zero")]
+fn test_provide_multi_asset_liquidity_validates_pool_assets() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_assets(AssetData {
+            asset_a_denom: DENOM_ATOM_ON_NTRN.to_string(),
+            asset_b_denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+            additional_assets: vec![PoolAssetConfig {
+                denom: DENOM_NTRN.to_string(),
+                single_side_limit: Uint128::new(100_000),
+            }],
+        })
+        .build();
+
+    suite.fund_contract(
+        &coins(1_000_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(1_000_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+}
+
 #[test]
 #[should_panic(expected = "all pool assets must be non-zero")]
 fn test_provide_liquidity_determine_pool_ratio_asset_b_denom_invalid() {
@@ -290,6 +674,7 @@ fn test_provide_liquidity_determine_pool_ratio_asset_b_denom_invalid() {
         .with_assets(AssetData {
             asset_a_denom: DENOM_ATOM_ON_NTRN.to_string(),
             asset_b_denom: "invalid denom".to_string(),
+            additional_assets: vec![],
         })
         .build();
 
@@ -328,20 +713,21 @@ fn test_provide_liquidity_validates_pool_ratio() {
 }
 
 #[test]
-fn test_provide_liquidity_no_assets() {
-    let mut suite = AstroLiquidPoolerBuilder::default().build();
-
-    suite
-        .tick_contract(suite.liquid_pooler_addr.clone())
-        .assert_event(&Event::new("wasm").add_attribute("status", "not enough funds"));
+#[should_panic(expected = "min lp out tolerance must be within [0, 1]")]
+fn test_instantiate_validates_min_lp_out_tolerance() {
+    AstroLiquidPoolerBuilder::default()
+        .with_min_lp_out_tolerance(Some(Decimal::percent(101)))
+        .build();
 }
 
 #[test]
-fn test_provide_stable_liquidity_single_side_asset_a() {
-    let mut suite = AstroLiquidPoolerBuilder::default().build();
+fn test_provide_liquidity_validates_min_lp_out() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_min_lp_out_tolerance(Some(Decimal::percent(1)))
+        .build();
 
     suite.fund_contract(
-        &coins(570_000, DENOM_ATOM_ON_NTRN),
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
         suite.liquid_pooler_addr.clone(),
     );
     suite.fund_contract(
@@ -349,56 +735,87 @@ fn test_provide_stable_liquidity_single_side_asset_a() {
         suite.liquid_pooler_addr.clone(),
     );
 
-    suite.tick_contract(suite.liquid_pooler_addr.clone());
+    // the reply validates the LP tokens received
+    let resp = suite.tick_contract(suite.liquid_pooler_addr.clone());
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "handle_double_sided_reply_id"));
+    assert!(resp
+        .events
+        .iter()
+        .any(|event| event.attributes.iter().any(|attr| attr.key == "lp_out")));
+}
 
-    suite.assert_balance(
+#[test]
+#[should_panic(expected = "max provision per tick must be a non-zero amount of asset a or b")]
+fn test_instantiate_validates_max_provision_per_tick() {
+    AstroLiquidPoolerBuilder::default()
+        .with_max_provision_per_tick(Some(coin(100_000, DENOM_NTRN)))
+        .build();
+}
+
+#[test]
+fn test_provide_double_sided_liquidity_capped_per_tick() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_max_provision_per_tick(Some(coin(200_000, DENOM_ATOM_ON_NTRN)))
+        .build();
+
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
         suite.liquid_pooler_addr.clone(),
-        coin(70_000, DENOM_ATOM_ON_NTRN),
     );
-    suite.assert_balance(
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
         suite.liquid_pooler_addr.clone(),
-        coin(0, DENOM_LS_ATOM_ON_NTRN),
-    );
-    assert_eq!(
-        suite.query_provided_liquidity_info(),
-        ProvidedLiquidityInfo {
-            provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
-        }
     );
 
+    // the balances are deployed over three ticks
+    for provided in [200_000, 400_000, 500_000] {
+        suite.tick_contract(suite.liquid_pooler_addr.clone());
+        assert_eq!(
+            suite.query_provided_liquidity_info(),
+            ProvidedLiquidityInfo {
+                provided_coin_a: coin(provided, DENOM_ATOM_ON_NTRN),
+                provided_coin_b: coin(provided, DENOM_LS_ATOM_ON_NTRN),
+                provided_additional_coins: vec![],
+            }
+        );
+    }
+    assert!(suite.query_idle_balances().is_empty());
+}
+
+#[test]
+fn test_provide_single_sided_liquidity_capped_per_tick() {
+    // the cap in asset b caps asset a at its pool ratio equivalent
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_max_provision_per_tick(Some(coin(40_000, DENOM_LS_ATOM_ON_NTRN)))
+        .build();
+
+    suite.fund_contract(
+        &coins(100_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
     suite
         .tick_contract(suite.liquid_pooler_addr.clone())
         .assert_event(&Event::new("wasm").add_attribute("method", "single_side_lp"));
+
     suite.assert_balance(
         suite.liquid_pooler_addr.clone(),
-        coin(0, DENOM_ATOM_ON_NTRN),
-    );
-    suite.assert_balance(
-        suite.liquid_pooler_addr.clone(),
-        coin(0, DENOM_LS_ATOM_ON_NTRN),
-    );
-    assert_eq!(
-        suite.query_provided_liquidity_info(),
-        ProvidedLiquidityInfo {
-            provided_coin_a: coin(570_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
-        }
+        coin(60_000, DENOM_ATOM_ON_NTRN),
     );
 }
 
 #[test]
-fn test_provide_custom_concentrated_liquidity_single_side_asset_a() {
-    let custom_concentrated_pair_type =
-        astroport::factory::PairType::Custom("concentrated".to_string());
-    let mut suite = AstroLiquidPoolerBuilder::default()
-        .with_custom_astroport_pool(
-            custom_concentrated_pair_type.clone(),
-            coin(1_000_000_000, DENOM_ATOM_ON_NTRN),
-            coin(1_000_000_000, DENOM_LS_ATOM_ON_NTRN),
-        )
-        .with_pair_type(custom_concentrated_pair_type)
-        .build();
+fn test_simulate_provision() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+
+    let simulation = suite.query_simulate_provision();
+    assert_eq!(simulation.pool_ratio, Decimal::one());
+    assert_eq!(simulation.pool_ratio_error, None);
+    assert_eq!(
+        simulation.action,
+        ProvisionAction::Nothing {
+            reason: "not enough funds".to_string()
+        }
+    );
 
     suite.fund_contract(
         &coins(570_000, DENOM_ATOM_ON_NTRN),
@@ -409,8 +826,169 @@ fn test_provide_custom_concentrated_liquidity_single_side_asset_a() {
         suite.liquid_pooler_addr.clone(),
     );
 
-    suite.tick_contract(suite.liquid_pooler_addr.clone());
-
+    // simulating does not provide liquidity
+    assert_eq!(
+        suite.query_simulate_provision().action,
+        ProvisionAction::DoubleSided {
+            coins: vec![
+                coin(500_000, DENOM_ATOM_ON_NTRN),
+                coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            ]
+        }
+    );
+    assert_eq!(
+        suite.query_provided_liquidity_info(),
+        suite.provided_liquidity_info
+    );
+
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "double_side_lp"));
+
+    assert_eq!(
+        suite.query_simulate_provision().action,
+        ProvisionAction::SingleSided {
+            coin: coin(70_000, DENOM_ATOM_ON_NTRN)
+        }
+    );
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "single_side_lp"));
+}
+
+#[test]
+fn test_simulate_provide() {
+    let suite = AstroLiquidPoolerBuilder::default().build();
+
+    let simulation = suite.query_simulate_provide(
+        coin(570_000, DENOM_ATOM_ON_NTRN),
+        coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+    );
+    assert_eq!(simulation.pool_ratio_error, None);
+    assert_eq!(
+        simulation.provided_assets,
+        vec![
+            coin(500_000, DENOM_ATOM_ON_NTRN),
+            coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+        ]
+    );
+    assert!(!simulation.expected_lp_out.is_zero());
+
+    // the simulated balances are not the ones of the pooler
+    assert_eq!(
+        suite.query_simulate_provision().action,
+        ProvisionAction::Nothing {
+            reason: "not enough funds".to_string()
+        }
+    );
+
+    // the single-sided leftover is provided with zero amounts of the other asset
+    let simulation = suite.query_simulate_provide(
+        coin(70_000, DENOM_ATOM_ON_NTRN),
+        coin(0, DENOM_LS_ATOM_ON_NTRN),
+    );
+    assert_eq!(
+        simulation.action,
+        ProvisionAction::SingleSided {
+            coin: coin(70_000, DENOM_ATOM_ON_NTRN)
+        }
+    );
+    assert_eq!(
+        simulation.provided_assets,
+        vec![
+            coin(70_000, DENOM_ATOM_ON_NTRN),
+            coin(0, DENOM_LS_ATOM_ON_NTRN),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "provide simulation expects coins of asset a and b")]
+fn test_simulate_provide_validates_denoms() {
+    let suite = AstroLiquidPoolerBuilder::default().build();
+
+    suite.query_simulate_provide(
+        coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+        coin(500_000, DENOM_ATOM_ON_NTRN),
+    );
+}
+
+#[test]
+#[should_panic(expected = "twap window must be non-zero")]
+fn test_instantiate_validates_twap_window() {
+    AstroLiquidPoolerBuilder::default()
+        .with_twap_window(Some(0))
+        .build();
+}
+
+#[test]
+fn test_twap_ratio_available_after_window() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_twap_window(Some(600))
+        .build();
+
+    // until a snapshot is a window old, the spot ratio is validated
+    assert_eq!(suite.query_simulate_provision().twap_ratio, None);
+    suite
+        .app
+        .update_block(|b| b.time = b.time.plus_seconds(300));
+    assert_eq!(suite.query_simulate_provision().twap_ratio, None);
+
+    suite
+        .app
+        .update_block(|b| b.time = b.time.plus_seconds(300));
+    let simulation = suite.query_simulate_provision();
+    let twap_ratio = simulation.twap_ratio.unwrap();
+    assert!(twap_ratio > Decimal::percent(99) && twap_ratio < Decimal::percent(101));
+    assert_eq!(simulation.pool_ratio_error, None);
+}
+
+#[test]
+fn test_simulate_provision_reports_pool_ratio_error() {
+    let suite = AstroLiquidPoolerBuilder::default()
+        .with_pool_price_config(PoolPriceConfig {
+            expected_spot_price: Decimal::from_str("3").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.1").unwrap(),
+        })
+        .build();
+
+    let simulation = suite.query_simulate_provision();
+    assert!(simulation
+        .pool_ratio_error
+        .unwrap()
+        .contains("Price range error"));
+    assert_eq!(
+        simulation.action,
+        ProvisionAction::Nothing {
+            reason: "invalid pool ratio".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_provide_liquidity_no_assets() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("status", "not enough funds"));
+}
+
+#[test]
+fn test_provide_stable_liquidity_single_side_asset_a() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+
+    suite.fund_contract(
+        &coins(570_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+
     suite.assert_balance(
         suite.liquid_pooler_addr.clone(),
         coin(70_000, DENOM_ATOM_ON_NTRN),
@@ -423,7 +1001,8 @@ fn test_provide_custom_concentrated_liquidity_single_side_asset_a() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 
@@ -442,7 +1021,70 @@ fn test_provide_custom_concentrated_liquidity_single_side_asset_a() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(570_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_provide_custom_concentrated_liquidity_single_side_asset_a() {
+    let custom_concentrated_pair_type =
+        astroport::factory::PairType::Custom("concentrated".to_string());
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_custom_astroport_pool(
+            custom_concentrated_pair_type.clone(),
+            coin(1_000_000_000, DENOM_ATOM_ON_NTRN),
+            coin(1_000_000_000, DENOM_LS_ATOM_ON_NTRN),
+        )
+        .with_pair_type(custom_concentrated_pair_type)
+        .build();
+
+    suite.fund_contract(
+        &coins(570_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+
+    suite.assert_balance(
+        suite.liquid_pooler_addr.clone(),
+        coin(70_000, DENOM_ATOM_ON_NTRN),
+    );
+    suite.assert_balance(
+        suite.liquid_pooler_addr.clone(),
+        coin(0, DENOM_LS_ATOM_ON_NTRN),
+    );
+    assert_eq!(
+        suite.query_provided_liquidity_info(),
+        ProvidedLiquidityInfo {
+            provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
+        }
+    );
+
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "single_side_lp"));
+    suite.assert_balance(
+        suite.liquid_pooler_addr.clone(),
+        coin(0, DENOM_ATOM_ON_NTRN),
+    );
+    suite.assert_balance(
+        suite.liquid_pooler_addr.clone(),
+        coin(0, DENOM_LS_ATOM_ON_NTRN),
+    );
+    assert_eq!(
+        suite.query_provided_liquidity_info(),
+        ProvidedLiquidityInfo {
+            provided_coin_a: coin(570_000, DENOM_ATOM_ON_NTRN),
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 }
@@ -482,7 +1124,8 @@ fn test_provide_xyk_liquidity_single_side_asset_a() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 
@@ -542,7 +1185,8 @@ fn test_provide_xyk_liquidity_single_side_asset_b() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 
@@ -594,7 +1238,8 @@ fn test_provide_liquidity_single_side_asset_a_exceeds_limits() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 
@@ -603,11 +1248,69 @@ fn test_provide_liquidity_single_side_asset_a_exceeds_limits() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 }
 
+#[test]
+#[should_panic(expected = "max swap slippage must be within (0, 0.5]")]
+fn test_instantiate_validates_single_side_rebalance_max_swap_slippage() {
+    AstroLiquidPoolerBuilder::default()
+        .with_single_side_rebalance(Some(SingleSideRebalanceConfig {
+            max_swap_slippage: Decimal::percent(60),
+        }))
+        .build();
+}
+
+#[test]
+fn test_provide_liquidity_single_side_exceeding_limits_rebalances() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_custom_astroport_pool(
+            astroport::factory::PairType::Xyk {},
+            coin(1_000_000_000, DENOM_ATOM_ON_NTRN),
+            coin(1_000_000_000, DENOM_LS_ATOM_ON_NTRN),
+        )
+        .with_pair_type(astroport::factory::PairType::Xyk {})
+        .with_single_side_rebalance(Some(SingleSideRebalanceConfig {
+            max_swap_slippage: Decimal::percent(5),
+        }))
+        .build();
+
+    suite.fund_contract(
+        &coins(1_000_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    let ProvisionAction::Rebalance { swap, coins } = suite.query_simulate_provision().action else {
+        panic!("expected a rebalance");
+    };
+    assert_eq!(swap.denom, DENOM_ATOM_ON_NTRN);
+    assert!(swap.amount < Uint128::new(500_000));
+
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "rebalance_lp"));
+
+    // all of the swapped asset is provided, along with most of the rest
+    suite.assert_balance(
+        suite.liquid_pooler_addr.clone(),
+        coin(0, DENOM_LS_ATOM_ON_NTRN),
+    );
+    let provided_liquidity_info = suite.query_provided_liquidity_info();
+    assert_eq!(provided_liquidity_info.provided_coin_a, coins[0]);
+    assert_eq!(provided_liquidity_info.provided_coin_b, coins[1]);
+    assert!(
+        provided_liquidity_info
+            .provided_coin_a
+            .amount
+            .checked_add(swap.amount)
+            .unwrap()
+            > Uint128::new(990_000)
+    );
+}
+
 #[test]
 fn test_provide_liquidity_single_side_validates_single_side_limits() {
     let mut suite = AstroLiquidPoolerBuilder::default().build();
@@ -639,7 +1342,8 @@ fn test_provide_liquidity_single_side_validates_single_side_limits() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 
@@ -660,11 +1364,89 @@ fn test_provide_liquidity_single_side_validates_single_side_limits() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(570_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(570_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 }
 
+#[test]
+#[should_panic(expected = "single side lp pool reserve share must be within (0, 1]")]
+fn test_instantiate_validates_single_side_pool_reserve_share() {
+    AstroLiquidPoolerBuilder::default()
+        .with_single_side_lp_limits(SingleSideLpLimits {
+            asset_a_limit: Uint128::new(100_000),
+            asset_b_limit: Uint128::new(100_000),
+            pool_reserve_share: Some(Decimal::from_str("1.1").unwrap()),
+        })
+        .build();
+}
+
+#[test]
+fn test_provide_liquidity_single_side_pool_reserve_share_overrides_absolute_limits() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_single_side_lp_limits(SingleSideLpLimits {
+            asset_a_limit: Uint128::new(100_000),
+            asset_b_limit: Uint128::new(100_000),
+            pool_reserve_share: Some(Decimal::from_str("0.01").unwrap()),
+        })
+        .build();
+
+    suite.fund_contract(
+        &coins(1_000_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "double_side_lp"));
+
+    // 500_000 exceeds the absolute limit but is well within 1% of pool reserves
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "single_side_lp"));
+    suite.assert_balance(
+        suite.liquid_pooler_addr.clone(),
+        coin(0, DENOM_ATOM_ON_NTRN),
+    );
+    assert_eq!(
+        suite.query_provided_liquidity_info(),
+        ProvidedLiquidityInfo {
+            provided_coin_a: coin(1_000_000, DENOM_ATOM_ON_NTRN),
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Single side LP limit exceeded")]
+fn test_provide_liquidity_single_side_exceeds_pool_reserve_share() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_custom_astroport_pool(
+            astroport::factory::PairType::Stable {},
+            coin(1_000_000, DENOM_ATOM_ON_NTRN),
+            coin(1_000_000, DENOM_LS_ATOM_ON_NTRN),
+        )
+        .with_single_side_lp_limits(SingleSideLpLimits {
+            asset_a_limit: Uint128::new(1_000_000_000),
+            asset_b_limit: Uint128::new(1_000_000_000),
+            pool_reserve_share: Some(Decimal::from_str("0.1").unwrap()),
+        })
+        .build();
+
+    // limit is 10% of the 1_000_000 pool reserves
+    suite.fund_contract(
+        &coins(100_001, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+}
+
 #[test]
 fn test_provide_liquidity_single_side_asset_b() {
     let mut suite = AstroLiquidPoolerBuilder::default().build();
@@ -696,7 +1478,8 @@ fn test_provide_liquidity_single_side_asset_b() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 
@@ -717,7 +1500,8 @@ fn test_provide_liquidity_single_side_asset_b() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(570_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(570_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 }
@@ -750,7 +1534,8 @@ fn test_provide_liquidity_single_side_asset_b_exceeds_limits() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 
@@ -759,7 +1544,8 @@ fn test_provide_liquidity_single_side_asset_b_exceeds_limits() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 }
@@ -791,7 +1577,8 @@ fn test_provide_liquidity_double_side_excess_a_denom() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 }
@@ -823,11 +1610,63 @@ fn test_provide_liquidity_double_side_excess_b_denom() {
         suite.query_provided_liquidity_info(),
         ProvidedLiquidityInfo {
             provided_coin_a: coin(500_000, DENOM_ATOM_ON_NTRN),
-            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+            provided_coin_b: coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+            provided_additional_coins: vec![],
         }
     );
 }
 
+#[test]
+fn test_provide_liquidity_capped_by_max_pool_ownership() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_custom_astroport_pool(
+            astroport::factory::PairType::Stable {},
+            coin(1_000_000, DENOM_ATOM_ON_NTRN),
+            coin(1_000_000, DENOM_LS_ATOM_ON_NTRN),
+        )
+        .with_max_pool_ownership(Some(Decimal::percent(10)))
+        .build();
+
+    let pool_ownership = suite.query_pool_ownership();
+    assert_eq!(pool_ownership.owned_shares, Uint128::zero());
+    assert_eq!(
+        pool_ownership.max_pool_ownership,
+        Some(Decimal::percent(10))
+    );
+
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+
+    // only the amounts within the max pool ownership are provided,
+    // the rest is kept as idle balance
+    let provided_liquidity_info = suite.query_provided_liquidity_info();
+    assert!(provided_liquidity_info.provided_coin_a.amount < Uint128::new(500_000));
+    assert!(provided_liquidity_info.provided_coin_b.amount < Uint128::new(500_000));
+    let idle_a = suite.query_balance(&suite.liquid_pooler_addr, DENOM_ATOM_ON_NTRN);
+    let idle_b = suite.query_balance(&suite.liquid_pooler_addr, DENOM_LS_ATOM_ON_NTRN);
+    assert_eq!(
+        idle_a.amount + provided_liquidity_info.provided_coin_a.amount,
+        Uint128::new(500_000)
+    );
+    assert_eq!(
+        idle_b.amount + provided_liquidity_info.provided_coin_b.amount,
+        Uint128::new(500_000)
+    );
+
+    // stable pools mint shares by their invariant, so allow for rounding
+    let pool_ownership = suite.query_pool_ownership();
+    assert!(pool_ownership.ownership > Decimal::percent(9));
+    assert!(pool_ownership.ownership < Decimal::percent(11));
+}
+
 #[test]
 fn test_migrate_update_config() {
     let mut suite = AstroLiquidPoolerBuilder::default().build();
@@ -849,6 +1688,7 @@ fn test_migrate_update_config() {
                 ])),
                 holder_address: Some(clock.to_string()),
                 lp_config: Some(Box::new(lp_config)),
+                emergency_committee: None,
             },
             11,
         )
@@ -870,3 +1710,307 @@ fn test_migrate_update_config() {
         valence_astroport_liquid_pooler::msg::ContractState::Instantiated {}
     );
 }
+
+#[test]
+fn test_update_pair_type_migration_policy() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let holder = suite.holder_addr.clone();
+
+    suite.update_pair_type_migration_policy(&holder, PairTypeMigrationPolicy::AcceptWithReratio);
+
+    assert_eq!(
+        suite.query_pair_type_migration_policy(),
+        PairTypeMigrationPolicy::AcceptWithReratio
+    );
+}
+
+#[test]
+fn test_update_pair_type_migration_policy_by_emergency_committee() {
+    let mut builder = AstroLiquidPoolerBuilder::default();
+    let committee = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_emergency_committee(Some(committee.to_string()))
+        .build();
+
+    suite.update_pair_type_migration_policy(&committee, PairTypeMigrationPolicy::Reject);
+
+    assert_eq!(
+        suite.query_pair_type_migration_policy(),
+        PairTypeMigrationPolicy::Reject
+    );
+}
+
+#[test]
+fn test_accept_pair_type_migration_resumes_paused_provision() {
+    let mut builder = AstroLiquidPoolerBuilder::default();
+    let committee = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_pair_type_migration_policy(Some(PairTypeMigrationPolicy::PauseForCommittee))
+        .with_emergency_committee(Some(committee.to_string()))
+        .build();
+    let expected_range = suite.lp_config.expected_pool_ratio_range.clone();
+    suite.migrate_configured_pair_type(astroport::factory::PairType::Xyk {});
+
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("status", "paused"));
+
+    suite.accept_pair_type_migration(&committee);
+    let lp_config = suite.query_lp_config();
+    assert_eq!(lp_config.pair_type, astroport::factory::PairType::Stable {});
+    assert_eq!(lp_config.expected_pool_ratio_range, expected_range);
+
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "double_side_lp"));
+}
+
+#[test]
+#[should_panic(expected = "Pool did not migrate to a different pair type")]
+fn test_accept_pair_type_migration_validates_migration() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let holder = suite.holder_addr.clone();
+
+    suite.accept_pair_type_migration(&holder);
+}
+
+#[test]
+fn test_accept_with_reratio_keeps_configured_range_without_oracle() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_pair_type_migration_policy(Some(PairTypeMigrationPolicy::AcceptWithReratio))
+        .build();
+    let expected_range = suite.lp_config.expected_pool_ratio_range.clone();
+    suite.migrate_configured_pair_type(astroport::factory::PairType::Xyk {});
+
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+
+    let lp_config = suite.query_lp_config();
+    assert_eq!(lp_config.pair_type, astroport::factory::PairType::Stable {});
+    assert_eq!(lp_config.expected_pool_ratio_range, expected_range);
+}
+
+#[test]
+#[should_panic(
+    expected = "Only the holder or the emergency committee can resolve pair type migrations"
+)]
+fn test_update_pair_type_migration_policy_validates_holder() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let not_the_holder = suite.faucet.clone();
+
+    suite.update_pair_type_migration_policy(
+        &not_the_holder,
+        PairTypeMigrationPolicy::AcceptWithReratio,
+    );
+}
+
+#[test]
+fn test_set_provisioning_paused() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let holder = suite.holder_addr.clone();
+    assert!(!suite.query_provisioning_paused());
+
+    suite.set_provisioning_paused(&holder, true);
+    assert!(suite.query_provisioning_paused());
+
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("provisioning_paused", "true"));
+    assert_eq!(suite.query_idle_balances().len(), 2);
+
+    suite.set_provisioning_paused(&holder, false);
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+    assert!(suite.query_idle_balances().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Only holder can withdraw the position")]
+fn test_set_provisioning_paused_validates_holder() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let not_the_holder = suite.faucet.clone();
+
+    suite.set_provisioning_paused(&not_the_holder, true);
+}
+
+#[test]
+fn test_failed_provision_is_recorded() {
+    // xyk pairs reject a slippage tolerance above 50%, which makes
+    // the provision fail after it was submitted to the pool
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_custom_astroport_pool(
+            astroport::factory::PairType::Xyk {},
+            coin(1_000_000_000, DENOM_ATOM_ON_NTRN),
+            coin(1_000_000_000, DENOM_LS_ATOM_ON_NTRN),
+        )
+        .with_pair_type(astroport::factory::PairType::Xyk {})
+        .with_slippage_tolerance(Some(Decimal::percent(60)))
+        .build();
+    assert_eq!(suite.query_last_provision_failure(), None);
+
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "handle_provision_failure"));
+
+    let failure = suite.query_last_provision_failure().unwrap();
+    assert_eq!(failure.coins.len(), 2);
+    assert_eq!(failure.failed_at, suite.get_app().block_info().time);
+
+    // the optimistic update of the provided liquidity is reverted
+    let provided_liquidity_info = suite.query_provided_liquidity_info();
+    assert_eq!(
+        provided_liquidity_info.provided_coin_a.amount,
+        Uint128::zero()
+    );
+    assert_eq!(
+        provided_liquidity_info.provided_coin_b.amount,
+        Uint128::zero()
+    );
+
+    // and the funds remain on the pooler for the next tick
+    assert_eq!(
+        suite.query_balance(&suite.liquid_pooler_addr, DENOM_ATOM_ON_NTRN),
+        coin(500_000, DENOM_ATOM_ON_NTRN)
+    );
+    assert_eq!(
+        suite.query_balance(&suite.liquid_pooler_addr, DENOM_LS_ATOM_ON_NTRN),
+        coin(500_000, DENOM_LS_ATOM_ON_NTRN)
+    );
+}
+
+#[test]
+#[should_panic(expected = "deadline must be in the future")]
+fn test_instantiate_validates_funding_deadline() {
+    AstroLiquidPoolerBuilder::default()
+        .with_funding_deadline(Some(Deadline::Duration(Duration::Time(0))))
+        .build();
+}
+
+#[test]
+fn test_funding_deadline_returns_leftover_funds() {
+    let mut suite = AstroLiquidPoolerBuilder::default()
+        .with_funding_deadline(Some(Deadline::Duration(Duration::Time(1_000))))
+        .build();
+    let funding_expiration = suite.query_funding_expiration().unwrap();
+    assert_eq!(
+        funding_expiration,
+        Expiration::AtTime(suite.get_app().block_info().time.plus_seconds(1_000))
+    );
+
+    let holder = suite.holder_addr.clone();
+
+    suite.fund_contract(
+        &coins(1_000_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+
+    // the excess asset a is left on the pooler
+    assert_eq!(
+        suite.query_idle_balances(),
+        vec![coin(500_000, DENOM_ATOM_ON_NTRN)]
+    );
+
+    // past the deadline, ticks return the leftovers to the holder
+    suite
+        .app
+        .update_block(|b| b.time = b.time.plus_seconds(1_000));
+    let holder_balance = suite.query_balance(&holder, DENOM_ATOM_ON_NTRN);
+    suite
+        .tick_contract(suite.liquid_pooler_addr.clone())
+        .assert_event(&Event::new("wasm").add_attribute("method", "try_return_leftover_funds"));
+
+    assert!(suite.query_idle_balances().is_empty());
+    suite.assert_balance(
+        holder,
+        coin(holder_balance.amount.u128() + 500_000, DENOM_ATOM_ON_NTRN),
+    );
+}
+
+#[test]
+fn test_return_idle_funds() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let holder = suite.holder_addr.clone();
+
+    suite.fund_contract(
+        &coins(1_000_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+
+    // the excess asset a is left on the pooler
+    assert_eq!(
+        suite.query_idle_balances(),
+        vec![coin(500_000, DENOM_ATOM_ON_NTRN)]
+    );
+
+    let holder_balance = suite.query_balance(&holder, DENOM_ATOM_ON_NTRN);
+    suite.return_idle_funds(&holder);
+
+    assert!(suite.query_idle_balances().is_empty());
+    suite.assert_balance(
+        holder,
+        coin(holder_balance.amount.u128() + 500_000, DENOM_ATOM_ON_NTRN),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only holder can withdraw the position")]
+fn test_return_idle_funds_validates_holder() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let not_the_holder = suite.faucet.clone();
+
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.return_idle_funds(&not_the_holder);
+}
+
+#[test]
+#[should_panic(expected = "no idle funds to return")]
+fn test_return_idle_funds_validates_idle_balances() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    let holder = suite.holder_addr.clone();
+
+    suite.return_idle_funds(&holder);
+}
@@ -0,0 +1,114 @@
+//! multi-test does not meter gas, so hot paths are profiled by the work
+//! they trigger instead: contract executions, bank transfers, emitted
+//! attributes, and bytes written to contract storage. each of these is
+//! charged for on chain, which makes them a stable gas baseline.
+
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Addr, Record};
+use cw_multi_test::AppResponse;
+
+use crate::setup::CustomApp;
+
+/// work triggered by a single transaction
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ExecutionProfile {
+    /// contract executions, including submessages and replies
+    pub executions: usize,
+    /// bank transfers
+    pub transfers: usize,
+    /// attributes emitted across all events
+    pub attributes: usize,
+    /// bytes of the records written to the storage of the profiled contracts
+    pub storage_bytes_written: usize,
+}
+
+impl ExecutionProfile {
+    pub fn from_response(resp: &AppResponse) -> Self {
+        let count_events = |ty: &str| resp.events.iter().filter(|e| e.ty == ty).count();
+        Self {
+            executions: count_events("execute") + count_events("reply"),
+            transfers: count_events("transfer"),
+            attributes: resp.events.iter().map(|e| e.attributes.len()).sum(),
+            storage_bytes_written: 0,
+        }
+    }
+
+    /// asserts that none of the profiled metrics exceed the budget
+    pub fn assert_within(&self, budget: &ExecutionProfile, label: &str) {
+        let metrics = [
+            ("executions", self.executions, budget.executions),
+            ("transfers", self.transfers, budget.transfers),
+            ("attributes", self.attributes, budget.attributes),
+            (
+                "storage_bytes_written",
+                self.storage_bytes_written,
+                budget.storage_bytes_written,
+            ),
+        ];
+        for (metric, profiled, budgeted) in metrics {
+            assert!(
+                profiled <= budgeted,
+                "{label}: {metric} of {profiled} exceed the budget of {budgeted}"
+            );
+        }
+    }
+}
+
+/// budget made of a fixed base and a per item cost, for hot
+/// paths scaling with the number of denoms or receivers
+pub struct ScalingBudget {
+    pub base: ExecutionProfile,
+    pub per_item: ExecutionProfile,
+}
+
+impl ScalingBudget {
+    pub fn at(&self, items: usize) -> ExecutionProfile {
+        ExecutionProfile {
+            executions: self.base.executions + self.per_item.executions * items,
+            transfers: self.base.transfers + self.per_item.transfers * items,
+            attributes: self.base.attributes + self.per_item.attributes * items,
+            storage_bytes_written: self.base.storage_bytes_written
+                + self.per_item.storage_bytes_written * items,
+        }
+    }
+}
+
+/// runs the transaction and profiles it, counting the bytes
+/// written to the storage of the given contracts
+pub fn profile(
+    app: &mut CustomApp,
+    contracts: &[Addr],
+    tx: impl FnOnce(&mut CustomApp) -> AppResponse,
+) -> ExecutionProfile {
+    let snapshots: Vec<Vec<Record>> = contracts
+        .iter()
+        .map(|contract| app.dump_wasm_raw(contract))
+        .collect();
+
+    let resp = tx(app);
+
+    let storage_bytes_written = contracts
+        .iter()
+        .zip(snapshots)
+        .map(|(contract, before)| storage_bytes_written(&before, &app.dump_wasm_raw(contract)))
+        .sum();
+
+    ExecutionProfile {
+        storage_bytes_written,
+        ..ExecutionProfile::from_response(&resp)
+    }
+}
+
+/// bytes of the records that were added or modified
+fn storage_bytes_written(before: &[Record], after: &[Record]) -> usize {
+    let before: BTreeMap<&[u8], &[u8]> = before
+        .iter()
+        .map(|(k, v)| (k.as_slice(), v.as_slice()))
+        .collect();
+    after
+        .iter()
+        .filter(|(k, v)| before.get(k.as_slice()) != Some(&v.as_slice()))
+        .map(|(k, v)| k.len() + v.len())
+        .sum()
+}
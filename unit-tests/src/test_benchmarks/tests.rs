@@ -0,0 +1,256 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use cosmwasm_std::{coin, coins, Addr, Decimal};
+use covenant_utils::split::SplitConfig;
+use cw_multi_test::Executor;
+
+use crate::{
+    setup::{
+        base_suite::BaseSuiteMut, DENOM_ATOM_ON_NTRN, DENOM_FALLBACK, DENOM_FALLBACK_ON_HUB,
+        DENOM_LS_ATOM_ON_NTRN, DENOM_NTRN, DENOM_OSMO, DENOM_OSMO_ON_HUB_FROM_NTRN,
+        DENOM_OSMO_ON_NTRN,
+    },
+    test_astroport_liquid_pooler::suite::AstroLiquidPoolerBuilder,
+    test_native_splitter::suite::NativeSplitterBuilder,
+    test_two_party_pol_holder::suite::TwoPartyHolderBuilder,
+};
+
+use super::suite::{profile, ExecutionProfile, ScalingBudget};
+
+/// upper bound on the size of an optimized contract, leaving
+/// headroom below the maximum wasm size accepted by the chain
+const MAX_CONTRACT_SIZE_BYTES: u64 = 800 * 1024;
+
+const SPLITTER_DISTRIBUTION_BUDGET: ScalingBudget = ScalingBudget {
+    base: ExecutionProfile {
+        executions: 1,
+        transfers: 0,
+        attributes: 32,
+        storage_bytes_written: 512,
+    },
+    per_item: ExecutionProfile {
+        executions: 0,
+        transfers: 1,
+        attributes: 8,
+        storage_bytes_written: 256,
+    },
+};
+
+const HOLDER_FALLBACK_DISTRIBUTION_BUDGET: ScalingBudget = ScalingBudget {
+    base: ExecutionProfile {
+        executions: 1,
+        transfers: 0,
+        attributes: 32,
+        storage_bytes_written: 512,
+    },
+    per_item: ExecutionProfile {
+        executions: 0,
+        transfers: 2,
+        attributes: 16,
+        storage_bytes_written: 512,
+    },
+};
+
+const HOLDER_CLAIM_BUDGET: ExecutionProfile = ExecutionProfile {
+    executions: 16,
+    transfers: 8,
+    attributes: 192,
+    storage_bytes_written: 4096,
+};
+
+const POOLER_TICK_BUDGET: ExecutionProfile = ExecutionProfile {
+    executions: 8,
+    transfers: 6,
+    attributes: 128,
+    storage_bytes_written: 2048,
+};
+
+fn profile_splitter_distribution(receivers: usize) -> ExecutionProfile {
+    let mut builder = NativeSplitterBuilder::default();
+    let split_receivers = (0..receivers)
+        .map(|_| {
+            (
+                builder.builder.get_random_addr().to_string(),
+                Decimal::from_ratio(1u128, receivers as u128),
+            )
+        })
+        .collect();
+    let splits = BTreeMap::from([(
+        DENOM_ATOM_ON_NTRN.to_string(),
+        SplitConfig {
+            receivers: split_receivers,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        },
+    )]);
+    let mut suite = builder.with_splits(splits).build();
+    suite.fund_contract(
+        &coins(1_000_000, DENOM_ATOM_ON_NTRN),
+        suite.splitter.clone(),
+    );
+
+    let (clock, splitter) = (suite.clock_addr.clone(), suite.splitter.clone());
+    profile(&mut suite.app, &[splitter.clone()], |app| {
+        app.execute_contract(
+            clock,
+            splitter,
+            &valence_clock::msg::ExecuteMsg::Tick {},
+            &[],
+        )
+        .unwrap()
+    })
+}
+
+fn profile_holder_fallback_distribution(denoms: &[&str]) -> ExecutionProfile {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let covenant_config = &builder.instantiate_msg.msg.covenant_config;
+    builder.instantiate_msg.msg.fallback_split = Some(SplitConfig {
+        receivers: vec![
+            (
+                covenant_config.party_a.router.to_string(),
+                Decimal::percent(50),
+            ),
+            (
+                covenant_config.party_b.router.to_string(),
+                Decimal::percent(50),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        rounding: Default::default(),
+        min_amounts: Default::default(),
+    });
+    let mut suite = builder.build();
+    for denom in denoms {
+        suite.fund_contract(&coins(1_000_000, *denom), suite.holder_addr.clone());
+    }
+
+    let (clock, holder) = (suite.clock_addr.clone(), suite.holder_addr.clone());
+    profile(&mut suite.app, &[holder.clone()], |app| {
+        app.execute_contract(
+            clock,
+            holder,
+            &valence_two_party_pol_holder::msg::ExecuteMsg::DistributeFallbackSplit {
+                denoms: denoms.iter().map(|d| d.to_string()).collect(),
+            },
+            &[],
+        )
+        .unwrap()
+    })
+}
+
+#[test]
+fn test_benchmark_splitter_distribution() {
+    for receivers in [2, 4, 8] {
+        profile_splitter_distribution(receivers).assert_within(
+            &SPLITTER_DISTRIBUTION_BUDGET.at(receivers),
+            &format!("splitter distribution to {receivers} receivers"),
+        );
+    }
+}
+
+#[test]
+fn test_benchmark_splitter_distribution_scales_linearly() {
+    let two_receivers = profile_splitter_distribution(2);
+    let eight_receivers = profile_splitter_distribution(8);
+
+    // every additional receiver costs a single transfer
+    assert_eq!(eight_receivers.executions, two_receivers.executions);
+    assert_eq!(eight_receivers.transfers - two_receivers.transfers, 6);
+}
+
+#[test]
+fn test_benchmark_holder_fallback_distribution() {
+    let denoms = [
+        DENOM_NTRN,
+        DENOM_OSMO,
+        DENOM_FALLBACK,
+        DENOM_FALLBACK_ON_HUB,
+        DENOM_OSMO_ON_HUB_FROM_NTRN,
+        DENOM_OSMO_ON_NTRN,
+    ];
+    for n in [1, 3, 6] {
+        profile_holder_fallback_distribution(&denoms[..n]).assert_within(
+            &HOLDER_FALLBACK_DISTRIBUTION_BUDGET.at(n),
+            &format!("holder fallback distribution of {n} denoms"),
+        );
+    }
+
+    let three_denoms = profile_holder_fallback_distribution(&denoms[..3]);
+    let six_denoms = profile_holder_fallback_distribution(&denoms);
+    assert_eq!(six_denoms.executions, three_denoms.executions);
+    assert_eq!(six_denoms.transfers, 2 * three_denoms.transfers);
+}
+
+#[test]
+fn test_benchmark_holder_claim() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    let party_a = Addr::unchecked(suite.covenant_config.party_a.host_addr.clone());
+    let (holder, pooler) = (suite.holder_addr.clone(), suite.next_contract.clone());
+    profile(&mut suite.app, &[holder.clone(), pooler], |app| {
+        app.execute_contract(
+            party_a,
+            holder,
+            &valence_two_party_pol_holder::msg::ExecuteMsg::Claim {
+                reinvest: None,
+                to: None,
+                denoms: None,
+            },
+            &[],
+        )
+        .unwrap()
+    })
+    .assert_within(&HOLDER_CLAIM_BUDGET, "holder claim");
+}
+
+#[test]
+fn test_benchmark_pooler_double_sided_tick() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    suite.fund_contract(
+        &coins(500_000, DENOM_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.fund_contract(
+        &coins(500_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.liquid_pooler_addr.clone(),
+    );
+
+    let (clock, pooler) = (suite.clock_addr.clone(), suite.liquid_pooler_addr.clone());
+    profile(&mut suite.app, &[pooler.clone()], |app| {
+        app.execute_contract(clock, pooler, &valence_clock::msg::ExecuteMsg::Tick {}, &[])
+            .unwrap()
+    })
+    .assert_within(&POOLER_TICK_BUDGET, "pooler double sided tick");
+}
+
+/// checks the optimized contracts when they were built, e.g. in CI
+/// after running the optimizer, and is a noop otherwise
+#[test]
+fn test_benchmark_contract_sizes() {
+    let artifacts = Path::new(env!("CARGO_MANIFEST_DIR")).join("../artifacts");
+    let Ok(entries) = fs::read_dir(artifacts) else {
+        return;
+    };
+    for path in entries.map(|entry| entry.unwrap().path()) {
+        if path.extension().is_some_and(|ext| ext == "wasm") {
+            let size = fs::metadata(&path).unwrap().len();
+            assert!(
+                size <= MAX_CONTRACT_SIZE_BYTES,
+                "{} is {size} bytes, exceeding the budget of {MAX_CONTRACT_SIZE_BYTES}",
+                path.display()
+            );
+        }
+    }
+}
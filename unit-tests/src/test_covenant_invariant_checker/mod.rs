@@ -0,0 +1,2 @@
+pub(crate) mod suite;
+mod test;
@@ -0,0 +1,101 @@
+use cosmwasm_std::Addr;
+use cw_multi_test::{AppResponse, Executor};
+use valence_covenant_invariant_checker::msg::{
+    ExecuteMsg, InstantiateMsg, InvariantReport, QueryMsg,
+};
+
+use crate::{
+    setup::ADMIN,
+    test_two_party_covenant::suite::{Suite as CovenantSuite, TwoPartyCovenantBuilder},
+};
+
+/// two party covenant along with an invariant checker
+pub struct InvariantCheckerBuilder {
+    pub builder: TwoPartyCovenantBuilder,
+    pub invariant_checker: Addr,
+}
+
+impl Default for InvariantCheckerBuilder {
+    fn default() -> Self {
+        let mut builder = TwoPartyCovenantBuilder::default();
+
+        let invariant_checker = builder.builder.contract_init(
+            builder.builder.invariant_checker_code_id,
+            "invariant_checker".to_string(),
+            &InstantiateMsg {},
+            &[],
+        );
+
+        Self {
+            builder,
+            invariant_checker,
+        }
+    }
+}
+
+impl InvariantCheckerBuilder {
+    pub fn build(self) -> Suite {
+        Suite {
+            covenant: self.builder.build(),
+            invariant_checker: self.invariant_checker,
+        }
+    }
+}
+
+pub struct Suite {
+    pub covenant: CovenantSuite,
+    pub invariant_checker: Addr,
+}
+
+impl Suite {
+    pub fn query_check_invariants(&self) -> InvariantReport {
+        self.covenant
+            .app
+            .wrap()
+            .query_wasm_smart(
+                self.invariant_checker.clone(),
+                &QueryMsg::CheckInvariants {
+                    covenant: self.covenant.covenant_addr.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn assert_invariants(&mut self) -> AppResponse {
+        let sender = self.covenant.app.api().addr_make(ADMIN);
+        self.covenant
+            .app
+            .execute_contract(
+                sender,
+                self.invariant_checker.clone(),
+                &ExecuteMsg::AssertInvariants {
+                    covenant: self.covenant.covenant_addr.to_string(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    /// removes the holder from the clock, as if it was dequeued prematurely
+    pub fn remove_holder_from_clock(&mut self) {
+        let clock_code_id = self
+            .covenant
+            .app
+            .wrap()
+            .query_wasm_contract_info(self.covenant.clock_addr.clone())
+            .unwrap()
+            .code_id;
+        self.covenant
+            .app
+            .migrate_contract(
+                self.covenant.covenant_addr.clone(),
+                self.covenant.clock_addr.clone(),
+                &valence_clock::msg::MigrateMsg::ManageWhitelist {
+                    add: None,
+                    remove: Some(vec![self.covenant.holder_addr.to_string()]),
+                },
+                clock_code_id,
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+use cosmwasm_std::Event;
+use valence_covenant_invariant_checker::msg::{Invariant, InvariantViolation};
+
+use super::suite::InvariantCheckerBuilder;
+
+#[test]
+fn test_check_invariants() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+
+    let report = suite.query_check_invariants();
+    assert!(report.is_ok());
+    assert_eq!(report.covenant, suite.covenant.covenant_addr.to_string());
+
+    let resp = suite.assert_invariants();
+    resp.assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_assert_invariants")
+            .add_attribute("covenant", suite.covenant.covenant_addr.to_string()),
+    );
+}
+
+#[test]
+fn test_check_invariants_reports_clock_coverage() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    suite.remove_holder_from_clock();
+
+    let report = suite.query_check_invariants();
+    assert_eq!(
+        report.violations,
+        vec![InvariantViolation {
+            invariant: Invariant::ClockCoverage,
+            reason: format!(
+                "{} is neither queued nor whitelisted on the clock",
+                suite.covenant.holder_addr
+            ),
+        }]
+    );
+}
+
+#[test]
+#[should_panic(expected = "covenant violates 1 invariant(s)")]
+fn test_assert_invariants_validates_violations() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    suite.remove_holder_from_clock();
+
+    suite.assert_invariants();
+}
@@ -0,0 +1,148 @@
+use cosmwasm_std::{to_json_binary, Addr, Empty, WasmMsg};
+use covenant_utils::dao::ProposalModuleStatus;
+use cw_multi_test::{AppResponse, Executor};
+use valence_two_party_pol_holder::msg::ExecuteMsg;
+
+use crate::{
+    setup::dao_contracts::{
+        dao_core_contract, dao_proposal_module_contract, DaoCoreExecuteMsg,
+        DaoProposalModuleExecuteMsg, DaoProposalModuleInstantiateMsg,
+    },
+    test_two_party_pol_holder::suite::{Suite, TwoPartyHolderBuilder},
+};
+
+/// two party holder where party A is represented by a DAO DAO core
+pub struct DaoPartyBuilder {
+    pub builder: TwoPartyHolderBuilder,
+    pub proposal_module_code_id: u64,
+    pub dao_core: Addr,
+    pub proposal_module: Addr,
+}
+
+impl Default for DaoPartyBuilder {
+    fn default() -> Self {
+        let mut builder = TwoPartyHolderBuilder::default();
+
+        let dao_core_code_id = builder.builder.app.store_code(dao_core_contract());
+        let proposal_module_code_id = builder
+            .builder
+            .app
+            .store_code(dao_proposal_module_contract());
+
+        let dao_core =
+            builder
+                .builder
+                .contract_init(dao_core_code_id, "dao_core".to_string(), &Empty {}, &[]);
+        let proposal_module = builder.builder.contract_init(
+            proposal_module_code_id,
+            "dao_proposal_module".to_string(),
+            &DaoProposalModuleInstantiateMsg {
+                dao: dao_core.to_string(),
+            },
+            &[],
+        );
+        builder
+            .builder
+            .app
+            .execute_contract(
+                dao_core.clone(),
+                dao_core.clone(),
+                &DaoCoreExecuteMsg::SetProposalModule {
+                    address: proposal_module.to_string(),
+                    status: ProposalModuleStatus::Enabled,
+                },
+                &[],
+            )
+            .unwrap();
+
+        builder
+            .instantiate_msg
+            .msg
+            .covenant_config
+            .party_a
+            .host_addr = dao_core.to_string();
+
+        Self {
+            builder,
+            proposal_module_code_id,
+            dao_core,
+            proposal_module,
+        }
+    }
+}
+
+impl DaoPartyBuilder {
+    pub fn with_proposal_module_status(mut self, status: ProposalModuleStatus) -> Self {
+        self.builder
+            .builder
+            .app
+            .execute_contract(
+                self.dao_core.clone(),
+                self.dao_core.clone(),
+                &DaoCoreExecuteMsg::SetProposalModule {
+                    address: self.proposal_module.to_string(),
+                    status,
+                },
+                &[],
+            )
+            .unwrap();
+        self
+    }
+
+    pub fn build(self) -> DaoPartySuite {
+        DaoPartySuite {
+            proposal_module_code_id: self.proposal_module_code_id,
+            dao_core: self.dao_core,
+            proposal_module: self.proposal_module,
+            holder: self.builder.build(),
+        }
+    }
+}
+
+pub struct DaoPartySuite {
+    pub holder: Suite,
+    pub proposal_module_code_id: u64,
+    pub dao_core: Addr,
+    pub proposal_module: Addr,
+}
+
+impl DaoPartySuite {
+    /// executes the holder msg through a passed proposal of the given module
+    pub fn execute_proposal(&mut self, proposal_module: &Addr, msg: &ExecuteMsg) -> AppResponse {
+        let proposal_msg = WasmMsg::Execute {
+            contract_addr: self.holder.holder_addr.to_string(),
+            msg: to_json_binary(msg).unwrap(),
+            funds: vec![],
+        };
+
+        self.holder
+            .app
+            .execute_contract(
+                self.holder.admin.clone(),
+                proposal_module.clone(),
+                &DaoProposalModuleExecuteMsg::Execute {
+                    msgs: vec![proposal_msg.into()],
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    /// instantiates a proposal module pointing to the DAO core of the
+    /// party without the core listing it among its proposal modules
+    pub fn init_unlisted_proposal_module(&mut self) -> Addr {
+        self.holder
+            .app
+            .instantiate_contract(
+                self.proposal_module_code_id,
+                self.holder.admin.clone(),
+                &DaoProposalModuleInstantiateMsg {
+                    dao: self.dao_core.to_string(),
+                },
+                &[],
+                "unlisted_dao_proposal_module",
+                None,
+            )
+            .unwrap()
+    }
+}
@@ -0,0 +1,117 @@
+use covenant_utils::dao::ProposalModuleStatus;
+use valence_two_party_pol_holder::msg::{ExecuteMsg, PartyRebinding};
+
+use super::suite::DaoPartyBuilder;
+
+fn new_controller_rebinding() -> PartyRebinding {
+    PartyRebinding {
+        host_addr: None,
+        controller_addr: Some("new_controller".to_string()),
+        router: None,
+    }
+}
+
+#[test]
+fn test_dao_proposal_acts_as_party() {
+    let mut suite = DaoPartyBuilder::default().build();
+    let proposal_module = suite.proposal_module.clone();
+    let rebinding = new_controller_rebinding();
+
+    suite.execute_proposal(
+        &proposal_module,
+        &ExecuteMsg::RequestPartyRebinding {
+            rebinding: rebinding.clone(),
+        },
+    );
+
+    // the request is attributed to the DAO core, not the proposal module
+    assert_eq!(
+        suite
+            .holder
+            .query_pending_party_rebinding(suite.dao_core.as_str()),
+        Some(rebinding)
+    );
+}
+
+#[test]
+fn test_dao_proposal_approves_counterparty_rebinding() {
+    let mut suite = DaoPartyBuilder::default().build();
+    let proposal_module = suite.proposal_module.clone();
+    let party_b = suite.holder.covenant_config.party_b.host_addr.to_string();
+
+    suite
+        .holder
+        .request_party_rebinding(&party_b, new_controller_rebinding());
+    suite.execute_proposal(
+        &proposal_module,
+        &ExecuteMsg::ApprovePartyRebinding {
+            party: party_b.to_string(),
+        },
+    );
+
+    let covenant_config = suite.holder.query_covenant_config();
+    assert_eq!(covenant_config.party_b.controller_addr, "new_controller");
+}
+
+#[test]
+fn test_dao_core_acts_as_party() {
+    let mut suite = DaoPartyBuilder::default().build();
+    let dao_core = suite.dao_core.to_string();
+    let rebinding = new_controller_rebinding();
+
+    suite
+        .holder
+        .request_party_rebinding(&dao_core, rebinding.clone());
+
+    assert_eq!(
+        suite.holder.query_pending_party_rebinding(&dao_core),
+        Some(rebinding)
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_disabled_dao_proposal_module_is_unauthorized() {
+    let mut suite = DaoPartyBuilder::default()
+        .with_proposal_module_status(ProposalModuleStatus::Disabled)
+        .build();
+    let proposal_module = suite.proposal_module.clone();
+
+    suite.execute_proposal(
+        &proposal_module,
+        &ExecuteMsg::RequestPartyRebinding {
+            rebinding: new_controller_rebinding(),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_unlisted_dao_proposal_module_is_unauthorized() {
+    let mut suite = DaoPartyBuilder::default().build();
+    let proposal_module = suite.init_unlisted_proposal_module();
+
+    suite.execute_proposal(
+        &proposal_module,
+        &ExecuteMsg::RequestPartyRebinding {
+            rebinding: new_controller_rebinding(),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_dao_proposal_cannot_act_as_counterparty() {
+    let mut suite = DaoPartyBuilder::default().build();
+    let proposal_module = suite.proposal_module.clone();
+    let dao_core = suite.dao_core.to_string();
+
+    suite
+        .holder
+        .request_party_rebinding(&dao_core, new_controller_rebinding());
+    // the DAO may not approve rebindings of its own party
+    suite.execute_proposal(
+        &proposal_module,
+        &ExecuteMsg::ApprovePartyRebinding { party: dao_core },
+    );
+}
@@ -1,12 +1,18 @@
 use std::str::FromStr;
 
-use cosmwasm_std::{Addr, Coin, Uint128, Uint64};
+use cosmwasm_std::{to_json_binary, to_json_string, Addr, Binary, Coin, Uint128, Uint64};
 use covenant_utils::{
-    neutron::RemoteChainInfo,
+    ica::INTERCHAIN_ACCOUNT_ID,
+    neutron::{OpenAckVersion, RemoteChainInfo},
     op_mode::{ContractOperationMode, ContractOperationModeConfig},
 };
-use cw_multi_test::{AppResponse, Executor};
+use cw_multi_test::{AppResponse, Executor, WasmSudo};
 use cw_storage_plus::KeyDeserialize;
+use neutron_sdk::{
+    bindings::msg::IbcFee,
+    interchain_txs::helpers::get_port_id,
+    sudo::msg::{RequestPacket, SudoMsg},
+};
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
@@ -39,6 +45,7 @@ impl IbcForwarderBuilder {
                 next_contract_addr.to_string(),
             ],
             initial_queue: vec![],
+            tick_intervals: vec![],
         };
 
         builder.contract_init2(
@@ -88,6 +95,17 @@ impl IbcForwarderBuilder {
         self
     }
 
+    pub fn with_milestone_callback(mut self, milestone_callback: String) -> Self {
+        self.instantiate_msg
+            .with_milestone_callback(milestone_callback);
+        self
+    }
+
+    pub fn with_ibc_fee_override(mut self, ibc_fee: IbcFee) -> Self {
+        self.instantiate_msg.with_ibc_fee_override(ibc_fee);
+        self
+    }
+
     pub fn with_ibc_transfer_timeout(mut self, ibc_transfer_timeout: Uint64) -> Self {
         self.instantiate_msg
             .with_ibc_transfer_timeout(ibc_transfer_timeout);
@@ -228,6 +246,16 @@ impl Suite {
             .unwrap()
     }
 
+    pub(crate) fn query_ibc_fee_override(&mut self) -> Option<IbcFee> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.ibc_forwarder.clone(),
+                &valence_ibc_forwarder::msg::QueryMsg::IbcFeeOverride {},
+            )
+            .unwrap()
+    }
+
     pub(crate) fn query_contract_state(&mut self) -> valence_ibc_forwarder::msg::ContractState {
         self.app
             .wrap()
@@ -245,6 +273,50 @@ impl Suite {
             .unwrap()
     }
 
+    pub(crate) fn sudo_forwarder(&mut self, msg: &SudoMsg) -> AppResponse {
+        self.app
+            .sudo(cw_multi_test::SudoMsg::Wasm(WasmSudo {
+                contract_addr: self.ibc_forwarder.clone(),
+                msg: to_json_binary(msg).unwrap(),
+            }))
+            .unwrap()
+    }
+
+    /// acknowledges the ICA registration with the given address
+    pub(crate) fn open_ack(&mut self, ica_address: &str) -> AppResponse {
+        self.sudo_forwarder(&SudoMsg::OpenAck {
+            port_id: get_port_id(self.ibc_forwarder.as_str(), INTERCHAIN_ACCOUNT_ID),
+            channel_id: "channel-1".to_string(),
+            counterparty_channel_id: "channel-1".to_string(),
+            counterparty_version: to_json_string(&OpenAckVersion {
+                version: "ica".to_string(),
+                controller_connection_id: "connection-todo".to_string(),
+                host_connection_id: "connection-todo".to_string(),
+                address: ica_address.to_string(),
+                encoding: "encoding".to_string(),
+                tx_type: "tx_type".to_string(),
+            })
+            .unwrap(),
+        })
+    }
+
+    /// acknowledges the ICA transaction submitted last
+    pub(crate) fn ack_response(&mut self) -> AppResponse {
+        self.sudo_forwarder(&SudoMsg::Response {
+            request: RequestPacket {
+                sequence: Some(1),
+                source_port: None,
+                source_channel: Some("some_channel".to_string()),
+                destination_port: None,
+                destination_channel: None,
+                data: None,
+                timeout_height: None,
+                timeout_timestamp: None,
+            },
+            data: Binary::default(),
+        })
+    }
+
     // temp fix until we add a query
     pub(crate) fn query_next_contract(&mut self) -> Addr {
         let resp = self
@@ -293,10 +365,31 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn decommission(&mut self, sender: Addr) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.ibc_forwarder.clone(),
+                &valence_ibc_forwarder::msg::ExecuteMsg::Decommission {},
+                &[],
+            )
+            .unwrap()
+    }
+
     pub fn distribute_fallback(&mut self, coins: Vec<Coin>, funds: Vec<Coin>) -> AppResponse {
+        let faucet = self.faucet.clone();
+        self.distribute_fallback_as(faucet, coins, funds)
+    }
+
+    pub fn distribute_fallback_as(
+        &mut self,
+        sender: Addr,
+        coins: Vec<Coin>,
+        funds: Vec<Coin>,
+    ) -> AppResponse {
         self.app
             .execute_contract(
-                self.faucet.clone(),
+                sender,
                 self.ibc_forwarder.clone(),
                 &valence_ibc_forwarder::msg::ExecuteMsg::DistributeFallback { coins },
                 &funds,
@@ -1,6 +1,7 @@
-use cosmwasm_std::{coin, coins, Addr, Uint128};
+use cosmwasm_std::{coin, coins, Addr, Event, Uint128};
 use covenant_utils::op_mode::{ContractOperationMode, ContractOperationModeConfig};
 use cw_multi_test::Executor;
+use neutron_sdk::bindings::msg::IbcFee;
 use valence_ibc_forwarder::msg::{ContractState, FallbackAddressUpdateConfig};
 
 use crate::setup::{
@@ -218,6 +219,61 @@ fn test_forward_funds_happy_permissionless() {
     );
 }
 
+#[test]
+fn test_ica_registration_emits_milestone() {
+    let mut suite = IbcForwarderBuilder::default().build();
+    let ica_addr = suite.app.api().addr_make("ica").to_string();
+
+    suite.open_ack(&ica_addr).assert_event(
+        &Event::new("wasm-ica_registered")
+            .add_attribute("address", ica_addr)
+            .add_attribute("channel", "channel-1"),
+    );
+}
+
+#[test]
+fn test_first_forward_emits_milestone_once() {
+    let mut suite = IbcForwarderBuilder::default().build();
+
+    let forwarder_addr = suite.ibc_forwarder.clone();
+    let next_contract = suite.query_next_contract();
+
+    // fund both contracts to register the ica
+    suite.fund_contract(&coins(2_000_000, DENOM_NTRN), forwarder_addr.clone());
+    suite.fund_contract(&coins(1_000_000, DENOM_NTRN), next_contract.clone());
+
+    // register ica
+    suite.tick_contract(forwarder_addr.clone());
+    suite.tick_contract(next_contract);
+
+    let forwarder_ica = suite.query_ica_address(forwarder_addr.clone());
+    suite.fund_contract(&coins(100_000, DENOM_ATOM_ON_NTRN), forwarder_ica);
+
+    // forward the funds and acknowledge the transfer
+    suite.tick_contract(forwarder_addr);
+    suite
+        .ack_response()
+        .assert_event(&Event::new("wasm-first_forward").add_attribute("amount", "100000"));
+
+    // later acknowledgements are not milestones
+    assert!(!suite
+        .ack_response()
+        .has_event(&Event::new("wasm-first_forward")));
+}
+
+#[test]
+fn test_milestone_callback_failure_is_ignored() {
+    let mut builder = IbcForwarderBuilder::default();
+    let milestone_callback = builder.builder.get_random_addr().to_string();
+    let mut suite = builder.with_milestone_callback(milestone_callback).build();
+    let ica_addr = suite.app.api().addr_make("ica").to_string();
+
+    suite
+        .open_ack(&ica_addr)
+        .assert_event(&Event::new("wasm").add_attribute("method", "milestone_callback_failed"));
+    assert_eq!(suite.query_contract_state(), ContractState::IcaCreated {});
+}
+
 #[test]
 #[should_panic(expected = "Missing fallback address")]
 fn test_distribute_fallback_errors_without_fallback_address() {
@@ -327,6 +383,52 @@ fn test_distribute_fallback_validates_insufficient_ibc_fee_coverage() {
     );
 }
 
+#[test]
+#[should_panic(expected = "ibc fee override must not include a recv fee")]
+fn test_instantiate_validates_ibc_fee_override() {
+    IbcForwarderBuilder::default()
+        .with_ibc_fee_override(IbcFee {
+            recv_fee: coins(1, DENOM_NTRN),
+            ack_fee: vec![],
+            timeout_fee: vec![],
+        })
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "insufficient fees")]
+fn test_distribute_fallback_validates_ibc_fee_override_coverage() {
+    let ibc_fee = IbcFee {
+        recv_fee: vec![],
+        ack_fee: coins(50_000, DENOM_NTRN),
+        timeout_fee: vec![],
+    };
+    let mut suite = IbcForwarderBuilder::default()
+        .with_ibc_fee_override(ibc_fee.clone())
+        .build();
+    assert_eq!(suite.query_ibc_fee_override(), Some(ibc_fee));
+
+    let forwarder_addr = suite.ibc_forwarder.clone();
+
+    // fund forwarder to register the ica
+    suite.fund_contract(&coins(2_000_000, DENOM_NTRN), forwarder_addr.clone());
+
+    // register ica
+    suite.tick_contract(forwarder_addr.clone());
+
+    let forwarder_ica = suite.query_ica_address(forwarder_addr.clone());
+    suite.fund_contract(
+        &coins(100_000, DENOM_FALLBACK_ON_HUB),
+        forwarder_ica.clone(),
+    );
+
+    // covers the chain minimum fee, but not the overridden ack fee
+    suite.distribute_fallback(
+        vec![coin(100_000, DENOM_FALLBACK_ON_HUB.to_string())],
+        coins(30_000, DENOM_NTRN),
+    );
+}
+
 #[test]
 #[should_panic(expected = "Attempt to distribute duplicate denoms via fallback distribution")]
 fn test_distribute_fallback_validates_duplicate_input_denoms() {
@@ -407,6 +509,63 @@ fn test_distribute_fallback_happy() {
     suite.assert_balance(fallback_addr, coin(100_000, DENOM_OSMO_ON_HUB_FROM_NTRN));
 }
 
+#[test]
+fn test_distribute_fallback_guard_is_kept_per_sender() {
+    let mut builder = IbcForwarderBuilder::default();
+    let other_sender = builder.builder.get_random_addr();
+    let mut suite = builder.build();
+    let forwarder_addr = suite.ibc_forwarder.clone();
+
+    suite.fund_contract(&coins(3_000_000, DENOM_NTRN), forwarder_addr.clone());
+    suite.fund_contract(&coins(2_000_000, DENOM_NTRN), other_sender.clone());
+    suite.tick_contract(forwarder_addr.clone());
+
+    let forwarder_ica = suite.query_ica_address(forwarder_addr.clone());
+    suite.fund_contract(
+        &coins(100_000, DENOM_FALLBACK_ON_HUB),
+        forwarder_ica.clone(),
+    );
+
+    // a fallback distribution of one sender does not lock
+    // out the others for the rest of the block
+    suite.distribute_fallback(
+        vec![coin(50_000, DENOM_FALLBACK_ON_HUB)],
+        vec![coin(2_000_000, DENOM_NTRN)],
+    );
+    suite.distribute_fallback_as(
+        other_sender,
+        vec![coin(50_000, DENOM_FALLBACK_ON_HUB)],
+        vec![coin(2_000_000, DENOM_NTRN)],
+    );
+
+    suite.assert_balance(&forwarder_ica, coin(0, DENOM_FALLBACK_ON_HUB));
+}
+
+#[test]
+#[should_panic(expected = "was already triggered at height")]
+fn test_distribute_fallback_guards_sender_retries() {
+    let mut suite = IbcForwarderBuilder::default().build();
+    let forwarder_addr = suite.ibc_forwarder.clone();
+
+    suite.fund_contract(&coins(3_000_000, DENOM_NTRN), forwarder_addr.clone());
+    suite.tick_contract(forwarder_addr.clone());
+
+    let forwarder_ica = suite.query_ica_address(forwarder_addr.clone());
+    suite.fund_contract(
+        &coins(100_000, DENOM_FALLBACK_ON_HUB),
+        forwarder_ica.clone(),
+    );
+
+    suite.distribute_fallback(
+        vec![coin(50_000, DENOM_FALLBACK_ON_HUB)],
+        vec![coin(2_000_000, DENOM_NTRN)],
+    );
+    suite.distribute_fallback(
+        vec![coin(50_000, DENOM_FALLBACK_ON_HUB)],
+        vec![coin(2_000_000, DENOM_NTRN)],
+    );
+}
+
 #[test]
 fn test_migrate_update_config() {
     let mut ibc_fwdr_builder = IbcForwarderBuilder::default();
@@ -477,3 +636,57 @@ fn test_migrate_update_config_remove_fallback() {
 
     assert!(suite.query_fallback_address().is_none());
 }
+
+#[test]
+fn test_decommission_allows_returning_target_denom() {
+    let mut suite = IbcForwarderBuilder::default().build();
+    let forwarder_addr = suite.ibc_forwarder.clone();
+
+    // fund forwarder to register the ica
+    suite.fund_contract(&coins(2_000_000, DENOM_NTRN), forwarder_addr.clone());
+    suite.tick_contract(forwarder_addr.clone());
+
+    let forwarder_ica = suite.query_ica_address(forwarder_addr.clone());
+    suite.fund_contract(&coins(100_000, DENOM_ATOM_ON_NTRN), forwarder_ica.clone());
+
+    // the forwarder admin is the covenant
+    suite.decommission(Addr::unchecked(ADMIN)).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_decommission")
+            .add_attribute("contract_state", "decommissioned"),
+    );
+    assert_eq!(
+        suite.query_contract_state(),
+        ContractState::Decommissioned {}
+    );
+
+    // decommissioned forwarder does not forward anymore
+    suite
+        .tick_contract(forwarder_addr)
+        .assert_event(&Event::new("wasm").add_attribute("contract_state", "decommissioned"));
+
+    // residual target denom can be returned to the fallback address
+    suite.distribute_fallback(
+        vec![coin(100_000, DENOM_ATOM_ON_NTRN.to_string())],
+        coins(1_000_000, DENOM_NTRN),
+    );
+    suite.assert_balance(&forwarder_ica, coin(0, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "only the covenant can decommission the contract")]
+fn test_decommission_validates_sender() {
+    let mut suite = IbcForwarderBuilder::default().build();
+    let sender = suite.faucet.clone();
+
+    suite.decommission(sender);
+}
+
+#[test]
+#[should_panic(expected = "contract is decommissioned")]
+fn test_decommission_validates_decommissioned() {
+    let mut suite = IbcForwarderBuilder::default().build();
+
+    suite.decommission(Addr::unchecked(ADMIN));
+    suite.decommission(Addr::unchecked(ADMIN));
+}
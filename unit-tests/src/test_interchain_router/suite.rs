@@ -1,11 +1,19 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
-use cosmwasm_std::Addr;
-use covenant_utils::DestinationConfig;
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, Uint128};
+use covenant_utils::{
+    distribution_receipt::PendingDistribution, router_halt::RouterHaltConfig, DestinationConfig,
+    PacketForwardMiddlewareConfig,
+};
+use cw_multi_test::{AppResponse, Executor, WasmSudo};
+use neutron_sdk::sudo::msg::{RequestPacket, SudoMsg};
+use valence_interchain_router::msg::{InFlightTransfer, RoutingStats};
 
 use crate::setup::{
-    base_suite::BaseSuiteMut, instantiates::interchain_router::InterchainRouterInstantiate,
-    suite_builder::SuiteBuilder, CustomApp, CLOCK_SALT, INTERCHAIN_ROUTER_SALT,
+    base_suite::{BaseSuite, BaseSuiteMut},
+    instantiates::interchain_router::InterchainRouterInstantiate,
+    suite_builder::SuiteBuilder,
+    CustomApp, CLOCK_SALT, INTERCHAIN_ROUTER_SALT, NTRN_HUB_CHANNEL,
 };
 
 pub struct InterchainRouterBuilder {
@@ -25,6 +33,7 @@ impl Default for InterchainRouterBuilder {
             tick_max_gas: None,
             whitelist: vec![interchain_router_addr.to_string()],
             initial_queue: vec![],
+            tick_intervals: vec![],
         };
         builder.contract_init2(
             builder.clock_code_id,
@@ -63,6 +72,38 @@ impl InterchainRouterBuilder {
         self
     }
 
+    pub fn with_denom_destination(
+        mut self,
+        denom: &str,
+        destination_config: DestinationConfig,
+    ) -> Self {
+        self.instantiate_msg
+            .with_denom_destination(denom, destination_config);
+        self
+    }
+
+    pub fn with_min_route_amount(mut self, denom: &str, amount: u128) -> Self {
+        self.instantiate_msg
+            .with_min_route_amount(denom, Uint128::new(amount));
+        self
+    }
+
+    pub fn with_halt_config(mut self, halt_config: Option<RouterHaltConfig>) -> Self {
+        self.instantiate_msg.with_halt_config(halt_config);
+        self
+    }
+
+    pub fn with_owner(mut self, addr: &str) -> Self {
+        self.instantiate_msg.with_owner(addr);
+        self
+    }
+
+    pub fn with_holder_address(mut self, addr: &str) -> Self {
+        self.instantiate_msg
+            .with_holder_address(Some(addr.to_string()));
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let interchain_router_address = self.builder.contract_init2(
             self.builder.interchain_router_code_id,
@@ -104,6 +145,7 @@ impl InterchainRouterBuilder {
         Suite {
             faucet: self.builder.faucet.clone(),
             admin: self.builder.admin.clone(),
+            router_addr: interchain_router_address,
             clock_addr,
             denoms,
             receiver_config,
@@ -119,11 +161,207 @@ pub struct Suite {
     pub faucet: Addr,
     pub admin: Addr,
 
+    pub router_addr: Addr,
     pub clock_addr: Addr,
     pub receiver_config: covenant_utils::DestinationConfig,
     pub denoms: BTreeSet<String>,
 }
 
+impl Suite {
+    pub fn query_routing_stats(&mut self) -> BTreeMap<String, RoutingStats> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::QueryMsg::RoutingStats {},
+            )
+            .unwrap()
+    }
+
+    pub fn halt(&mut self, sender: Addr) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::ExecuteMsg::Halt {},
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn update_pfm_config(
+        &mut self,
+        sender: Addr,
+        denom: &str,
+        pfm_config: Option<PacketForwardMiddlewareConfig>,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::ExecuteMsg::UpdatePfmConfig {
+                    denom: denom.to_string(),
+                    pfm_config,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_pfm_config(&self, denom: &str) -> Option<PacketForwardMiddlewareConfig> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::QueryMsg::PfmConfig {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_all_pfm_configs(&self) -> BTreeMap<String, PacketForwardMiddlewareConfig> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::QueryMsg::AllPfmConfigs {},
+            )
+            .unwrap()
+    }
+
+    pub fn distribute_fallback(&mut self, denoms: Vec<String>, funds: Vec<Coin>) -> AppResponse {
+        self.app
+            .execute_contract(
+                self.faucet.clone(),
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::ExecuteMsg::DistributeFallback { denoms },
+                &funds,
+            )
+            .unwrap()
+    }
+
+    pub fn sweep(&mut self, denoms: Vec<String>, funds: Vec<Coin>) -> AppResponse {
+        self.app
+            .execute_contract(
+                self.faucet.clone(),
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::ExecuteMsg::Sweep { denoms },
+                &funds,
+            )
+            .unwrap()
+    }
+
+    pub fn query_denom_destinations(&self) -> BTreeMap<String, DestinationConfig> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::QueryMsg::DenomDestinations {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_dust_balances(&self) -> Vec<Coin> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::QueryMsg::DustBalances {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_in_flight_transfers(&self) -> Vec<InFlightTransfer> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::QueryMsg::InFlightTransfers {},
+            )
+            .unwrap()
+    }
+
+    pub fn register_distribution(
+        &mut self,
+        sender: Addr,
+        id: u64,
+        coins: Vec<Coin>,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::ExecuteMsg::RegisterDistribution { id, coins },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_pending_distributions(&self) -> Vec<PendingDistribution> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::QueryMsg::PendingDistributions {},
+            )
+            .unwrap()
+    }
+
+    pub fn sudo_router(&mut self, msg: &SudoMsg) -> AppResponse {
+        self.app
+            .sudo(cw_multi_test::SudoMsg::Wasm(WasmSudo {
+                contract_addr: self.router_addr.clone(),
+                msg: to_json_binary(msg).unwrap(),
+            }))
+            .unwrap()
+    }
+
+    /// acknowledges the ibc transfer with the given sequence
+    pub fn ack_transfer(&mut self, sequence: u64) -> AppResponse {
+        self.sudo_router(&SudoMsg::Response {
+            request: get_transfer_request_packet(sequence),
+            data: Binary::default(),
+        })
+    }
+
+    /// times out the ibc transfer with the given sequence
+    pub fn timeout_transfer(&mut self, sequence: u64) -> AppResponse {
+        self.sudo_router(&SudoMsg::Timeout {
+            request: get_transfer_request_packet(sequence),
+        })
+    }
+
+    pub fn query_halted(&mut self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_interchain_router::msg::QueryMsg::Halted {},
+            )
+            .unwrap()
+    }
+}
+
+fn get_transfer_request_packet(sequence: u64) -> RequestPacket {
+    RequestPacket {
+        sequence: Some(sequence),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some(NTRN_HUB_CHANNEL.0.to_string()),
+        destination_port: None,
+        destination_channel: None,
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
+}
+
+impl BaseSuite for Suite {
+    fn get_app(&self) -> &CustomApp {
+        &self.app
+    }
+}
+
 impl BaseSuiteMut for Suite {
     fn get_app(&mut self) -> &mut CustomApp {
         &mut self.app
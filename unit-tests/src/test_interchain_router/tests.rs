@@ -1,3 +1,20 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use cosmwasm_std::{coin, coins, to_json_string, Addr, Binary, Event, Uint128};
+use covenant_utils::{
+    ibc_memo::WasmHookConfig, router_halt::RouterHaltConfig, HopConfig,
+    PacketForwardMiddlewareConfig,
+};
+use cw_multi_test::Executor;
+use valence_interchain_router::msg::{InFlightTransfer, RoutingStats};
+
+use crate::setup::{
+    adapter_contracts::{mock_holder_contract, MockHolderMsg},
+    base_suite::{BaseSuite, BaseSuiteMut},
+    ADMIN, DENOM_ATOM, DENOM_ATOM_ON_NTRN, DENOM_FALLBACK, DENOM_FALLBACK_ON_HUB, DENOM_NTRN,
+    NTRN_HUB_CHANNEL,
+};
+
 use super::suite::InterchainRouterBuilder;
 
 #[test]
@@ -19,3 +36,407 @@ fn test_instantiate_validates_destination_receiver_addr() {
         .destination_receiver_addr = "invalid_receiver".to_string();
     builder.build();
 }
+
+#[test]
+#[should_panic(expected = "ibc memo template must be between 1 and 256 characters long")]
+fn test_instantiate_validates_memo_template() {
+    let mut builder = InterchainRouterBuilder::default();
+    builder.instantiate_msg.msg.destination_config.memo_template = Some("".to_string());
+    builder.build();
+}
+
+#[test]
+#[should_panic(expected = "wasm hook contract must be the destination receiver")]
+fn test_instantiate_validates_wasm_hook() {
+    let mut builder = InterchainRouterBuilder::default();
+    builder.instantiate_msg.msg.destination_config.wasm_hook = Some(WasmHookConfig {
+        contract: "vault".to_string(),
+        msg: Binary::from(br#"{"deposit":{}}"#),
+    });
+    builder.build();
+}
+
+#[test]
+fn test_routing_stats() {
+    let mut suite = InterchainRouterBuilder::default().build();
+
+    // funds awaiting routing are received, but not yet transferred
+    suite.fund_contract(&coins(1_000, DENOM_ATOM_ON_NTRN), suite.router_addr.clone());
+    assert_eq!(
+        suite.query_routing_stats()[DENOM_ATOM_ON_NTRN],
+        RoutingStats {
+            received: Uint128::new(1_000),
+            ..Default::default()
+        }
+    );
+
+    suite.fund_contract(&coins(100_000, DENOM_NTRN), suite.router_addr.clone());
+    suite.tick_contract(suite.router_addr.clone());
+    suite.fund_contract(&coins(500, DENOM_ATOM_ON_NTRN), suite.router_addr.clone());
+
+    let routing_stats = suite.query_routing_stats();
+    assert_eq!(
+        routing_stats[DENOM_ATOM_ON_NTRN],
+        RoutingStats {
+            received: Uint128::new(1_500),
+            transferred: Uint128::new(1_000),
+            acknowledged: Uint128::zero(),
+        }
+    );
+    // fee denoms are not routed and thus not tracked
+    assert!(!routing_stats.contains_key(DENOM_NTRN));
+}
+
+#[test]
+fn test_acknowledged_transfers() {
+    let mut suite = InterchainRouterBuilder::default().build();
+    let router = suite.router_addr.clone();
+
+    suite.fund_contract(&coins(100_000, DENOM_NTRN), router.clone());
+    suite.fund_contract(&coins(1_000, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.tick_contract(router.clone());
+
+    let in_flight_transfers = suite.query_in_flight_transfers();
+    assert_eq!(in_flight_transfers.len(), 1);
+    assert_eq!(in_flight_transfers[0].channel_id, NTRN_HUB_CHANNEL.0);
+    assert_eq!(in_flight_transfers[0].coin, coin(1_000, DENOM_ATOM_ON_NTRN));
+
+    suite.ack_transfer(in_flight_transfers[0].sequence);
+
+    assert!(suite.query_in_flight_transfers().is_empty());
+    assert_eq!(
+        suite.query_routing_stats()[DENOM_ATOM_ON_NTRN],
+        RoutingStats {
+            received: Uint128::new(1_000),
+            transferred: Uint128::new(1_000),
+            acknowledged: Uint128::new(1_000),
+        }
+    );
+}
+
+#[test]
+fn test_distributions_are_acknowledged_with_their_transfers() {
+    let builder = InterchainRouterBuilder::default();
+    let holder = builder.builder.faucet.clone();
+    let mut suite = builder.with_holder_address(holder.as_str()).build();
+    let router = suite.router_addr.clone();
+
+    suite.fund_contract(&coins(100_000, DENOM_NTRN), router.clone());
+    suite.register_distribution(holder.clone(), 1, coins(1_000, DENOM_ATOM_ON_NTRN));
+
+    // ticks without balances to route acknowledge nothing
+    suite.tick_contract(router.clone());
+    assert_eq!(suite.query_pending_distributions().len(), 1);
+
+    // submitting the transfers is not enough either
+    suite.fund_contract(&coins(1_000, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.tick_contract(router.clone());
+    assert_eq!(suite.query_pending_distributions().len(), 1);
+
+    // acknowledgement to a non-contract holder fails without
+    // blocking the sudo handler
+    let in_flight_transfers = suite.query_in_flight_transfers();
+    suite
+        .ack_transfer(in_flight_transfers[0].sequence)
+        .assert_event(
+            &Event::new("wasm")
+                .add_attribute("method", "reply")
+                .add_attribute("acknowledgement", "failed"),
+        );
+    assert!(suite.query_pending_distributions().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "only the holder can register distributions")]
+fn test_register_distribution_validates_holder() {
+    let mut builder = InterchainRouterBuilder::default();
+    let holder = builder.builder.get_random_addr();
+    let mut suite = builder.with_holder_address(holder.as_str()).build();
+
+    suite.register_distribution(suite.faucet.clone(), 1, coins(1_000, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+fn test_timed_out_transfers_are_retried() {
+    let mut suite = InterchainRouterBuilder::default().build();
+    let router = suite.router_addr.clone();
+
+    suite.fund_contract(&coins(100_000, DENOM_NTRN), router.clone());
+    suite.fund_contract(&coins(500, DENOM_FALLBACK), router.clone());
+    suite.distribute_fallback(vec![DENOM_FALLBACK.to_string()], coins(20_000, DENOM_NTRN));
+
+    let in_flight_transfers = suite.query_in_flight_transfers();
+    assert_eq!(in_flight_transfers.len(), 1);
+    assert_eq!(in_flight_transfers[0].coin, coin(500, DENOM_FALLBACK));
+
+    suite.timeout_transfer(in_flight_transfers[0].sequence);
+    assert!(suite.query_in_flight_transfers().is_empty());
+
+    // timed out transfers are refunded to the router
+    suite.fund_contract(&coins(500, DENOM_FALLBACK), router.clone());
+    suite
+        .tick_contract(router.clone())
+        .assert_event(&Event::new("wasm").add_attribute("retried", "500ufallback"));
+
+    let in_flight_transfers = suite.query_in_flight_transfers();
+    assert_eq!(
+        in_flight_transfers,
+        vec![InFlightTransfer {
+            channel_id: NTRN_HUB_CHANNEL.0.to_string(),
+            sequence: in_flight_transfers[0].sequence,
+            coin: coin(500, DENOM_FALLBACK),
+        }]
+    );
+    suite.assert_balance(&router, coin(0, DENOM_FALLBACK));
+}
+
+#[test]
+fn test_denom_destination_overrides() {
+    let mut builder = InterchainRouterBuilder::default();
+    let treasury = builder.builder.get_random_addr();
+    let mut treasury_destination = builder.instantiate_msg.msg.destination_config.clone();
+    treasury_destination.destination_receiver_addr = treasury.to_string();
+    let mut suite = builder
+        .with_denoms(BTreeSet::from([
+            DENOM_ATOM_ON_NTRN.to_string(),
+            DENOM_FALLBACK.to_string(),
+        ]))
+        .with_denom_destination(DENOM_FALLBACK, treasury_destination.clone())
+        .build();
+    let router = suite.router_addr.clone();
+    let receiver = suite.receiver_config.destination_receiver_addr.to_string();
+
+    assert_eq!(
+        suite.query_denom_destinations(),
+        BTreeMap::from([(DENOM_FALLBACK.to_string(), treasury_destination)])
+    );
+
+    suite.fund_contract(&coins(100_000, DENOM_NTRN), router.clone());
+    suite.fund_contract(&coins(1_000, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.fund_contract(&coins(500, DENOM_FALLBACK), router.clone());
+    suite.tick_contract(router);
+
+    // overridden denoms are routed to their own destination
+    suite.assert_balance(&treasury, coin(500, DENOM_FALLBACK_ON_HUB));
+    suite.assert_balance(&treasury, coin(0, DENOM_ATOM));
+    suite.assert_balance(&receiver, coin(1_000, DENOM_ATOM));
+    suite.assert_balance(&receiver, coin(0, DENOM_FALLBACK_ON_HUB));
+}
+
+#[test]
+fn test_dust_balances_are_not_routed() {
+    let mut suite = InterchainRouterBuilder::default()
+        .with_min_route_amount(DENOM_ATOM_ON_NTRN, 1_000)
+        .build();
+    let router = suite.router_addr.clone();
+    let receiver = suite.receiver_config.destination_receiver_addr.to_string();
+
+    suite.fund_contract(&coins(100_000, DENOM_NTRN), router.clone());
+    suite.fund_contract(&coins(1, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.tick_contract(router.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("balances", "[]")
+            .add_attribute(
+                "skipped_dust",
+                to_json_string(&coins(1, DENOM_ATOM_ON_NTRN)).unwrap(),
+            ),
+    );
+
+    // no ibc fees are spent on dust
+    suite.assert_balance(&router, coin(100_000, DENOM_NTRN));
+    assert!(suite.query_in_flight_transfers().is_empty());
+    assert_eq!(suite.query_dust_balances(), coins(1, DENOM_ATOM_ON_NTRN));
+
+    suite.fund_contract(&coins(999, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.tick_contract(router.clone());
+
+    assert!(suite.query_dust_balances().is_empty());
+    suite.assert_balance(&router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&receiver, coin(1_000, DENOM_ATOM));
+}
+
+#[test]
+fn test_execute_halt_returns_balances_to_holder() {
+    let mut builder = InterchainRouterBuilder::default();
+    let holder = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_halt_config(Some(RouterHaltConfig {
+            holder_address: holder.to_string(),
+            emergency_committee: None,
+        }))
+        .build();
+    let router = suite.router_addr.clone();
+
+    suite.fund_contract(&coins(1_000, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.halt(Addr::unchecked(ADMIN));
+
+    assert!(suite.query_halted());
+    suite.assert_balance(&router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&holder, coin(1_000, DENOM_ATOM_ON_NTRN));
+
+    // balances received after halting are returned on tick
+    suite.fund_contract(&coins(500, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.tick_contract(router.clone());
+
+    suite.assert_balance(&router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&holder, coin(1_500, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "only the covenant or the emergency committee can halt the router")]
+fn test_execute_halt_validates_sender() {
+    let mut builder = InterchainRouterBuilder::default();
+    let holder = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_halt_config(Some(RouterHaltConfig {
+            holder_address: holder.to_string(),
+            emergency_committee: None,
+        }))
+        .build();
+    let faucet = suite.faucet.clone();
+
+    suite.halt(faucet);
+}
+
+#[test]
+fn test_execute_update_pfm_config() {
+    let mut builder = InterchainRouterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+    assert!(suite.query_all_pfm_configs().is_empty());
+
+    let pfm_config = PacketForwardMiddlewareConfig {
+        local_to_hop_chain_channel_id: "channel-10".to_string(),
+        hop_to_destination_chain_channel_id: "channel-20".to_string(),
+        hop_chain_receiver_address: "hop_receiver".to_string(),
+        next_hops: vec![HopConfig {
+            channel_id: "channel-30".to_string(),
+            receiver_address: "second_hop_receiver".to_string(),
+        }],
+    };
+    suite.update_pfm_config(owner.clone(), DENOM_ATOM_ON_NTRN, Some(pfm_config.clone()));
+    assert_eq!(
+        suite.query_pfm_config(DENOM_ATOM_ON_NTRN),
+        Some(pfm_config.clone())
+    );
+    assert_eq!(
+        suite.query_all_pfm_configs(),
+        BTreeMap::from([(DENOM_ATOM_ON_NTRN.to_string(), pfm_config)])
+    );
+    assert_eq!(suite.query_pfm_config(DENOM_NTRN), None);
+
+    // passing no config removes it
+    suite.update_pfm_config(owner, DENOM_ATOM_ON_NTRN, None);
+    assert_eq!(suite.query_pfm_config(DENOM_ATOM_ON_NTRN), None);
+    assert!(suite.query_all_pfm_configs().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the contract's current owner")]
+fn test_execute_update_pfm_config_validates_owner() {
+    let mut builder = InterchainRouterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+    let faucet = suite.faucet.clone();
+
+    suite.update_pfm_config(faucet, DENOM_ATOM_ON_NTRN, None);
+}
+
+#[test]
+#[should_panic(expected = "Contract ownership has been renounced")]
+fn test_execute_update_pfm_config_without_owner() {
+    let mut suite = InterchainRouterBuilder::default().build();
+
+    suite.update_pfm_config(Addr::unchecked(ADMIN), DENOM_ATOM_ON_NTRN, None);
+}
+
+#[test]
+fn test_execute_sweep_after_holder_complete() {
+    let mut builder = InterchainRouterBuilder::default();
+    let holder_code_id = builder.builder.app.store_code(mock_holder_contract());
+    let holder = builder.builder.contract_init(
+        holder_code_id,
+        "holder".to_string(),
+        &MockHolderMsg {
+            contract_state: "expired".to_string(),
+        },
+        &[],
+    );
+    let mut suite = builder.with_holder_address(holder.as_str()).build();
+    let router = suite.router_addr.clone();
+    let receiver = suite.receiver_config.destination_receiver_addr.to_string();
+
+    suite.fund_contract(&coins(100_000, DENOM_NTRN), router.clone());
+    suite.fund_contract(&coins(500, DENOM_FALLBACK), router.clone());
+
+    let sweep_msg = valence_interchain_router::msg::ExecuteMsg::Sweep {
+        denoms: vec![DENOM_FALLBACK.to_string()],
+    };
+    let err = suite
+        .app
+        .execute_contract(
+            suite.faucet.clone(),
+            router.clone(),
+            &sweep_msg,
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("router can only be swept once the holder is complete"));
+
+    suite
+        .app
+        .execute_contract(
+            suite.admin.clone(),
+            holder,
+            &MockHolderMsg {
+                contract_state: "complete".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    suite
+        .sweep(vec![DENOM_FALLBACK.to_string()], coins(20_000, DENOM_NTRN))
+        .assert_event(
+            &Event::new("wasm")
+                .add_attribute("method", "try_sweep")
+                .add_attribute(
+                    "swept",
+                    to_json_string(&coins(500, DENOM_FALLBACK)).unwrap(),
+                ),
+        );
+
+    suite.assert_balance(&router, coin(0, DENOM_FALLBACK));
+    suite.assert_balance(&receiver, coin(500, DENOM_FALLBACK_ON_HUB));
+    assert_eq!(suite.query_in_flight_transfers().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "must cover ibc fees to distribute fallback denoms")]
+fn test_execute_sweep_validates_ibc_fee_coverage() {
+    let mut builder = InterchainRouterBuilder::default();
+    let holder_code_id = builder.builder.app.store_code(mock_holder_contract());
+    let holder = builder.builder.contract_init(
+        holder_code_id,
+        "holder".to_string(),
+        &MockHolderMsg {
+            contract_state: "complete".to_string(),
+        },
+        &[],
+    );
+    let mut suite = builder.with_holder_address(holder.as_str()).build();
+    let router = suite.router_addr.clone();
+    suite.fund_contract(&coins(500, DENOM_FALLBACK), router);
+
+    suite.sweep(vec![DENOM_FALLBACK.to_string()], vec![]);
+}
+
+#[test]
+#[should_panic(expected = "router has no holder to be swept after")]
+fn test_execute_sweep_validates_holder() {
+    let mut suite = InterchainRouterBuilder::default().build();
+
+    suite.sweep(vec![DENOM_FALLBACK.to_string()], coins(20_000, DENOM_NTRN));
+}
@@ -0,0 +1,86 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Addr, Record};
+use cw_multi_test::{AppResponse, Executor};
+
+use crate::setup::CustomApp;
+
+/// code, admin, and raw storage of a contract at some point in time
+#[derive(Debug, PartialEq)]
+pub struct ContractSnapshot {
+    pub code_id: u64,
+    pub admin: Option<String>,
+    pub storage: Vec<Record>,
+}
+
+pub fn snapshot_contract(app: &CustomApp, contract: &Addr) -> ContractSnapshot {
+    let info = app.wrap().query_wasm_contract_info(contract).unwrap();
+    ContractSnapshot {
+        code_id: info.code_id,
+        admin: info.admin,
+        storage: app.dump_wasm_raw(contract),
+    }
+}
+
+/// migrates the contract to the code it is already running, as its admin
+pub fn migrate_in_place<M: Serialize>(
+    app: &mut CustomApp,
+    contract: &Addr,
+    msg: &M,
+) -> AppResponse {
+    let info = app.wrap().query_wasm_contract_info(contract).unwrap();
+    app.migrate_contract(
+        Addr::unchecked(info.admin.unwrap()),
+        contract.clone(),
+        msg,
+        info.code_id,
+    )
+    .unwrap()
+}
+
+/// migrates the contract in place and asserts that the migration
+/// left its code, admin, and storage untouched
+pub fn assert_migration_preserves_state<M: Serialize>(
+    app: &mut CustomApp,
+    contract: &Addr,
+    msg: &M,
+) {
+    let snapshot = snapshot_contract(app, contract);
+    migrate_in_place(app, contract, msg);
+    assert_eq!(snapshot, snapshot_contract(app, contract));
+}
+
+/// storage keys whose values differ between both snapshots
+pub fn get_updated_keys(before: &ContractSnapshot, after: &ContractSnapshot) -> BTreeSet<Vec<u8>> {
+    let before: BTreeMap<_, _> = before.storage.iter().cloned().collect();
+    let after: BTreeMap<_, _> = after.storage.iter().cloned().collect();
+    before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect()
+}
+
+/// migrates the contract in place and asserts that the migration
+/// left its code and admin untouched, and only wrote to the given keys
+pub fn assert_migration_updates_only<M: Serialize>(
+    app: &mut CustomApp,
+    contract: &Addr,
+    msg: &M,
+    keys: &[&str],
+) {
+    let snapshot = snapshot_contract(app, contract);
+    migrate_in_place(app, contract, msg);
+    let migrated = snapshot_contract(app, contract);
+
+    assert_eq!(
+        (snapshot.code_id, &snapshot.admin),
+        (migrated.code_id, &migrated.admin)
+    );
+    assert_eq!(
+        get_updated_keys(&snapshot, &migrated),
+        keys.iter().map(|key| key.as_bytes().to_vec()).collect()
+    );
+}
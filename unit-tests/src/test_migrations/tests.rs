@@ -0,0 +1,749 @@
+use cosmwasm_std::{coin, Uint64};
+use cw_utils::Expiration;
+use valence_covenant_invariant_checker::msg::Invariant;
+
+use crate::{
+    setup::{
+        base_suite::BaseSuiteMut, DENOM_ATOM, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN, DENOM_NTRN,
+    },
+    test_astroport_liquid_pooler::suite::AstroLiquidPoolerBuilder,
+    test_covenant_invariant_checker::suite::{
+        InvariantCheckerBuilder, Suite as InvariantCheckerSuite,
+    },
+    test_ibc_forwarder::suite::IbcForwarderBuilder,
+    test_interchain_router::suite::InterchainRouterBuilder,
+    test_native_router::suite::NativeRouterBuilder,
+    test_native_splitter::suite::NativeSplitterBuilder,
+    test_osmo_lp_outpost::suite::OsmoLpOutpostBuilder,
+    test_remote_chain_splitter::suite::RemoteChainSplitterBuilder,
+    test_single_party_covenant::suite::Suite as SinglePartyCovenantSuite,
+    test_single_party_holder::suite::SinglePartyHolderBuilder,
+    test_swap_covenant::suite::Suite as SwapCovenantSuite,
+    test_swap_holder::suite::SwapHolderBuilder,
+    test_two_party_pol_holder::suite::TwoPartyHolderBuilder,
+};
+
+use super::suite::{
+    assert_migration_preserves_state, assert_migration_updates_only, get_updated_keys,
+    migrate_in_place, snapshot_contract, ContractSnapshot,
+};
+
+#[test]
+fn test_two_party_holder_update_code_id_preserves_state() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.holder_addr,
+        &valence_two_party_pol_holder::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+}
+
+#[test]
+fn test_single_party_holder_update_code_id_preserves_state() {
+    let mut suite = SinglePartyHolderBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.holder_addr,
+        &valence_single_party_pol_holder::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+}
+
+#[test]
+fn test_swap_holder_update_code_id_preserves_state() {
+    let mut suite = SwapHolderBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.holder,
+        &valence_swap_holder::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+}
+
+#[test]
+fn test_astroport_liquid_pooler_update_code_id_preserves_state() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(500_000, DENOM_ATOM_ON_NTRN),
+            coin(500_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.liquid_pooler_addr.clone(),
+    );
+    suite.tick_contract(suite.liquid_pooler_addr.clone());
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.liquid_pooler_addr,
+        &valence_astroport_liquid_pooler::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+}
+
+#[test]
+fn test_native_splitter_update_code_id_preserves_state() {
+    let mut suite = NativeSplitterBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.splitter,
+        &valence_native_splitter::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+}
+
+#[test]
+fn test_remote_chain_splitter_update_code_id_preserves_state() {
+    let mut suite = RemoteChainSplitterBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.splitter,
+        &valence_remote_chain_splitter::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+}
+
+#[test]
+fn test_two_party_covenant_update_covenant_preserves_modules() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    let modules = vec![
+        suite.covenant.covenant_addr.clone(),
+        suite.covenant.clock_addr.clone(),
+        suite.covenant.holder_addr.clone(),
+        suite.covenant.query_liquid_pooler_address(),
+        suite.covenant.query_interchain_router_address("party_a"),
+        suite.covenant.query_interchain_router_address("party_b"),
+    ];
+    let snapshots: Vec<ContractSnapshot> = modules
+        .iter()
+        .map(|module| snapshot_contract(&suite.covenant.app, module))
+        .collect();
+
+    let router_migrate_msg = valence_covenant_two_party_pol::msg::RouterMigrateMsg::Native(
+        valence_native_router::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+    migrate_in_place(
+        &mut suite.covenant.app,
+        &modules[0],
+        &valence_covenant_two_party_pol::msg::MigrateMsg::UpdateCovenant {
+            codes: None,
+            clock: Some(valence_clock::msg::MigrateMsg::UpdateCodeId { data: None }),
+            holder: Some(
+                valence_two_party_pol_holder::msg::MigrateMsg::UpdateCodeId { data: None },
+            ),
+            liquid_pooler: Some(
+                valence_covenant_two_party_pol::msg::LiquidPoolerMigrateMsg::Astroport(
+                    valence_astroport_liquid_pooler::msg::MigrateMsg::UpdateCodeId { data: None },
+                ),
+            ),
+            party_a_router: Some(router_migrate_msg.clone()),
+            party_b_router: Some(router_migrate_msg),
+            party_a_forwarder: None,
+            party_b_forwarder: None,
+        },
+    );
+    migrate_in_place(
+        &mut suite.covenant.app,
+        &modules[0],
+        &valence_covenant_two_party_pol::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+
+    for (module, snapshot) in modules.iter().zip(snapshots) {
+        assert_eq!(snapshot, snapshot_contract(&suite.covenant.app, module));
+    }
+    assert!(suite.query_check_invariants().is_ok());
+}
+
+#[test]
+fn test_two_party_covenant_update_covenant_preserves_invariants() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    let covenant_addr = suite.covenant.covenant_addr.clone();
+
+    migrate_in_place(
+        &mut suite.covenant.app,
+        &covenant_addr,
+        &valence_covenant_two_party_pol::msg::MigrateMsg::UpdateCovenant {
+            codes: None,
+            clock: None,
+            holder: Some(
+                valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+                    clock_addr: None,
+                    next_contract: None,
+                    emergency_committee: None,
+                    lockup_config: Some(Expiration::AtHeight(300000)),
+                    deposit_deadline: None,
+                    ragequit_config: Box::new(None),
+                    covenant_config: Box::new(None),
+                    denom_splits: None,
+                    fallback_split: None,
+                },
+            ),
+            liquid_pooler: None,
+            party_a_router: None,
+            party_b_router: None,
+            party_a_forwarder: None,
+            party_b_forwarder: None,
+        },
+    );
+
+    let lockup_config: Expiration = suite
+        .covenant
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.covenant.holder_addr.clone(),
+            &valence_two_party_pol_holder::msg::QueryMsg::LockupConfig {},
+        )
+        .unwrap();
+    assert_eq!(lockup_config, Expiration::AtHeight(300000));
+    assert!(suite.query_check_invariants().is_ok());
+}
+
+#[test]
+fn test_two_party_covenant_update_covenant_reports_broken_invariants() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    let covenant_addr = suite.covenant.covenant_addr.clone();
+    let faucet = suite.covenant.faucet.clone();
+
+    // rewire the holder away from the liquid pooler, and move the
+    // lockup before the deposit deadline
+    migrate_in_place(
+        &mut suite.covenant.app,
+        &covenant_addr,
+        &valence_covenant_two_party_pol::msg::MigrateMsg::UpdateCovenant {
+            codes: None,
+            clock: None,
+            holder: Some(
+                valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+                    clock_addr: None,
+                    next_contract: Some(faucet.to_string()),
+                    emergency_committee: None,
+                    lockup_config: Some(Expiration::AtHeight(50000)),
+                    deposit_deadline: None,
+                    ragequit_config: Box::new(None),
+                    covenant_config: Box::new(None),
+                    denom_splits: None,
+                    fallback_split: None,
+                },
+            ),
+            liquid_pooler: None,
+            party_a_router: None,
+            party_b_router: None,
+            party_a_forwarder: None,
+            party_b_forwarder: None,
+        },
+    );
+
+    let invariants: Vec<Invariant> = suite
+        .query_check_invariants()
+        .violations
+        .into_iter()
+        .map(|violation| violation.invariant)
+        .collect();
+    assert_eq!(
+        invariants,
+        vec![Invariant::ModuleWiring, Invariant::DeadlineOrder]
+    );
+}
+
+#[test]
+fn test_two_party_covenant_update_covenant_pauses_clock() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    let covenant_addr = suite.covenant.covenant_addr.clone();
+    let clock_addr = suite.covenant.clock_addr.clone();
+    let snapshot = snapshot_contract(&suite.covenant.app, &clock_addr);
+
+    let migrate_clock = |suite: &mut InvariantCheckerSuite, msg: valence_clock::msg::MigrateMsg| {
+        migrate_in_place(
+            &mut suite.covenant.app,
+            &covenant_addr,
+            &valence_covenant_two_party_pol::msg::MigrateMsg::UpdateCovenant {
+                codes: None,
+                clock: Some(msg),
+                holder: None,
+                liquid_pooler: None,
+                party_a_router: None,
+                party_b_router: None,
+                party_a_forwarder: None,
+                party_b_forwarder: None,
+            },
+        );
+        suite
+            .covenant
+            .app
+            .wrap()
+            .query_wasm_smart::<bool>(clock_addr.clone(), &valence_clock::msg::QueryMsg::Paused {})
+            .unwrap()
+    };
+
+    assert!(migrate_clock(
+        &mut suite,
+        valence_clock::msg::MigrateMsg::Pause {}
+    ));
+    assert!(!migrate_clock(
+        &mut suite,
+        valence_clock::msg::MigrateMsg::Unpause {}
+    ));
+    assert_eq!(
+        snapshot,
+        snapshot_contract(&suite.covenant.app, &clock_addr)
+    );
+}
+
+#[test]
+fn test_single_party_covenant_migrate_contracts_preserves_modules() {
+    let mut suite = SinglePartyCovenantSuite::new_with_stable_pool();
+    let modules = vec![
+        suite.covenant_addr.clone(),
+        suite.clock_addr.clone(),
+        suite.holder_addr.clone(),
+        suite.ls_forwarder_addr.clone(),
+        suite.lp_forwarder_addr.clone(),
+        suite.splitter_addr.clone(),
+        suite.lper_addr.clone(),
+        suite.lser_addr.clone().unwrap(),
+        suite.router_addr.clone(),
+    ];
+    let snapshots: Vec<ContractSnapshot> = modules
+        .iter()
+        .map(|module| snapshot_contract(&suite.app, module))
+        .collect();
+    let contract_codes = suite.query_contract_codes();
+
+    let forwarder_migrate_msg = valence_ibc_forwarder::msg::MigrateMsg::UpdateCodeId { data: None };
+    migrate_in_place(
+        &mut suite.app,
+        &modules[0],
+        &valence_covenant_single_party_pol::msg::MigrateMsg::MigrateContracts {
+            codes: None,
+            clock: Some(valence_clock::msg::MigrateMsg::UpdateCodeId { data: None }),
+            holder: Some(
+                valence_single_party_pol_holder::msg::MigrateMsg::UpdateCodeId { data: None },
+            ),
+            ls_forwarder: Some(forwarder_migrate_msg.clone()),
+            lp_forwarder: Some(forwarder_migrate_msg),
+            splitter: Some(
+                valence_remote_chain_splitter::msg::MigrateMsg::UpdateCodeId { data: None },
+            ),
+            liquid_pooler: Some(
+                valence_covenant_single_party_pol::msg::LiquidPoolerMigrateMsg::Astroport(
+                    valence_astroport_liquid_pooler::msg::MigrateMsg::UpdateCodeId { data: None },
+                ),
+            ),
+            liquid_staker: Some(
+                valence_stride_liquid_staker::msg::MigrateMsg::UpdateCodeId { data: None },
+            ),
+            router: Some(valence_interchain_router::msg::MigrateMsg::UpdateCodeId { data: None }),
+        },
+    );
+    migrate_in_place(
+        &mut suite.app,
+        &modules[0],
+        &valence_covenant_single_party_pol::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+
+    for (module, snapshot) in modules.iter().zip(snapshots) {
+        assert_eq!(snapshot, snapshot_contract(&suite.app, module));
+    }
+    assert_eq!(contract_codes, suite.query_contract_codes());
+}
+
+#[test]
+fn test_swap_covenant_update_covenant_preserves_modules() {
+    let mut suite = SwapCovenantSuite::new_with_2_native_configs();
+    let modules = vec![
+        suite.covenant_addr.clone(),
+        suite.clock_addr.clone(),
+        suite.holder_addr.clone(),
+        suite.splitter_addr.clone(),
+        suite.router_a_addr.clone(),
+        suite.router_b_addr.clone(),
+    ];
+    let snapshots: Vec<ContractSnapshot> = modules
+        .iter()
+        .map(|module| snapshot_contract(&suite.app, module))
+        .collect();
+    let contract_codes = suite.query_contract_codes();
+
+    let router_migrate_msg = valence_covenant_swap::msg::RouterMigrateMsg::Native(
+        valence_native_router::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+    migrate_in_place(
+        &mut suite.app,
+        &modules[0],
+        &valence_covenant_swap::msg::MigrateMsg::UpdateCovenant {
+            codes: None,
+            clock: Some(valence_clock::msg::MigrateMsg::UpdateCodeId { data: None }),
+            holder: Some(valence_swap_holder::msg::MigrateMsg::UpdateCodeId { data: None }),
+            splitter: Some(valence_native_splitter::msg::MigrateMsg::UpdateCodeId { data: None }),
+            party_a_router: Some(router_migrate_msg.clone()),
+            party_b_router: Some(router_migrate_msg),
+            party_a_forwarder: Box::new(None),
+            party_b_forwarder: Box::new(None),
+        },
+    );
+    migrate_in_place(
+        &mut suite.app,
+        &modules[0],
+        &valence_covenant_swap::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+
+    for (module, snapshot) in modules.iter().zip(snapshots) {
+        assert_eq!(snapshot, snapshot_contract(&suite.app, module));
+    }
+    assert_eq!(contract_codes, suite.query_contract_codes());
+}
+
+#[test]
+fn test_two_party_holder_empty_update_config_preserves_state() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.holder_addr,
+        &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+            clock_addr: None,
+            next_contract: None,
+            emergency_committee: None,
+            lockup_config: None,
+            deposit_deadline: None,
+            ragequit_config: Box::new(None),
+            covenant_config: Box::new(None),
+            denom_splits: None,
+            fallback_split: None,
+        },
+    );
+}
+
+#[test]
+fn test_single_party_holder_empty_update_config_preserves_state() {
+    let mut suite = SinglePartyHolderBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.holder_addr,
+        &valence_single_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+            withdrawer: None,
+            withdraw_to: None,
+            emergency_committee: None,
+            pooler_address: None,
+            lockup_period: None,
+        },
+    );
+}
+
+#[test]
+fn test_swap_holder_update_config_only_updates_given_fields() {
+    let mut suite = SwapHolderBuilder::default().build();
+    let faucet = suite.faucet.clone();
+
+    assert_migration_updates_only(
+        &mut suite.app,
+        &suite.holder,
+        &valence_swap_holder::msg::MigrateMsg::UpdateConfig {
+            clock_addr: Some(faucet.to_string()),
+            next_contract: None,
+            lockup_config: None,
+            parites_config: Box::new(None),
+            covenant_terms: None,
+            refund_config: None,
+            direct_settlement: None,
+        },
+        &["clock_address"],
+    );
+}
+
+#[test]
+fn test_astroport_liquid_pooler_empty_update_config_preserves_state() {
+    let mut suite = AstroLiquidPoolerBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.liquid_pooler_addr,
+        &valence_astroport_liquid_pooler::msg::MigrateMsg::UpdateConfig {
+            op_mode: None,
+            holder_address: None,
+            lp_config: None,
+            emergency_committee: None,
+        },
+    );
+}
+
+#[test]
+fn test_osmo_lp_outpost_update_code_id_preserves_state() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.outpost,
+        &valence_outpost_osmo_liquid_pooler::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+}
+
+#[test]
+fn test_native_splitter_update_config_only_updates_given_fields() {
+    let mut suite = NativeSplitterBuilder::default().build();
+    let faucet = suite.faucet.clone();
+
+    assert_migration_updates_only(
+        &mut suite.app,
+        &suite.splitter,
+        &valence_native_splitter::msg::MigrateMsg::UpdateConfig {
+            clock_addr: Some(faucet.to_string()),
+            fallback_split: None,
+            splits: None,
+        },
+        &["clock_address"],
+    );
+}
+
+#[test]
+fn test_remote_chain_splitter_empty_update_config_preserves_state() {
+    let mut suite = RemoteChainSplitterBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.splitter,
+        &valence_remote_chain_splitter::msg::MigrateMsg::UpdateConfig {
+            op_mode: None,
+            remote_chain_info: None,
+            splits: None,
+            fallback_address: None,
+        },
+    );
+}
+
+#[test]
+fn test_native_router_migrations() {
+    let mut suite = NativeRouterBuilder::default().build();
+    let faucet = suite.faucet.clone();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.router_addr,
+        &valence_native_router::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+    assert_migration_updates_only(
+        &mut suite.app,
+        &suite.router_addr,
+        &valence_native_router::msg::MigrateMsg::UpdateConfig {
+            op_mode: None,
+            receiver_address: Some(faucet.to_string()),
+            target_denoms: None,
+            holder_address: None,
+        },
+        &["receiver_address"],
+    );
+}
+
+#[test]
+fn test_interchain_router_migrations() {
+    let mut suite = InterchainRouterBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.router_addr,
+        &valence_interchain_router::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.router_addr,
+        &valence_interchain_router::msg::MigrateMsg::UpdateConfig {
+            clock_addr: None,
+            destination_config: None,
+            target_denoms: None,
+            denom_to_destination_map: None,
+            holder_address: None,
+        },
+    );
+}
+
+#[test]
+fn test_ibc_forwarder_migrations() {
+    let mut suite = IbcForwarderBuilder::default().build();
+
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.ibc_forwarder,
+        &valence_ibc_forwarder::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+    assert_migration_preserves_state(
+        &mut suite.app,
+        &suite.ibc_forwarder,
+        &valence_ibc_forwarder::msg::MigrateMsg::UpdateConfig {
+            op_mode: None,
+            next_contract: None,
+            remote_chain_info: Box::new(None),
+            transfer_amount: None,
+            fallback_address: None,
+        },
+    );
+}
+
+#[test]
+fn test_invariant_checker_update_code_id_preserves_state() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    let invariant_checker = suite.invariant_checker.clone();
+
+    assert_migration_preserves_state(
+        &mut suite.covenant.app,
+        &invariant_checker,
+        &valence_covenant_invariant_checker::msg::MigrateMsg::UpdateCodeId { data: None },
+    );
+}
+
+#[test]
+fn test_two_party_covenant_update_covenant_updates_clock_config() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    let covenant_addr = suite.covenant.covenant_addr.clone();
+    let clock_addr = suite.covenant.clock_addr.clone();
+    let holder_addr = suite.covenant.holder_addr.clone();
+
+    let migrate_clock = |suite: &mut InvariantCheckerSuite, msg: valence_clock::msg::MigrateMsg| {
+        let snapshot = snapshot_contract(&suite.covenant.app, &clock_addr);
+        migrate_in_place(
+            &mut suite.covenant.app,
+            &covenant_addr,
+            &valence_covenant_two_party_pol::msg::MigrateMsg::UpdateCovenant {
+                codes: None,
+                clock: Some(msg),
+                holder: None,
+                liquid_pooler: None,
+                party_a_router: None,
+                party_b_router: None,
+                party_a_forwarder: None,
+                party_b_forwarder: None,
+            },
+        );
+        get_updated_keys(
+            &snapshot,
+            &snapshot_contract(&suite.covenant.app, &clock_addr),
+        )
+    };
+
+    let updated_keys = migrate_clock(
+        &mut suite,
+        valence_clock::msg::MigrateMsg::UpdateTickMaxGas {
+            new_value: Uint64::new(500_000),
+        },
+    );
+    assert_eq!(updated_keys, [b"tmg".to_vec()].into());
+
+    // (un)whitelisting is idempotent
+    let updated_keys = migrate_clock(
+        &mut suite,
+        valence_clock::msg::MigrateMsg::ManageWhitelist {
+            add: Some(vec![holder_addr.to_string()]),
+            remove: None,
+        },
+    );
+    assert!(updated_keys.is_empty());
+    let updated_keys = migrate_clock(
+        &mut suite,
+        valence_clock::msg::MigrateMsg::ManageWhitelist {
+            add: None,
+            remove: Some(vec![covenant_addr.to_string()]),
+        },
+    );
+    assert!(updated_keys.is_empty());
+    assert!(suite.query_check_invariants().is_ok());
+}
+
+#[test]
+fn test_two_party_covenant_halt_routers_preserves_other_modules() {
+    let mut suite = InvariantCheckerBuilder::default().build();
+    let modules = vec![
+        suite.covenant.covenant_addr.clone(),
+        suite.covenant.clock_addr.clone(),
+        suite.covenant.holder_addr.clone(),
+        suite.covenant.query_liquid_pooler_address(),
+    ];
+    let routers = vec![
+        suite.covenant.query_interchain_router_address("party_a"),
+        suite.covenant.query_interchain_router_address("party_b"),
+    ];
+    let snapshots: Vec<ContractSnapshot> = modules
+        .iter()
+        .map(|module| snapshot_contract(&suite.covenant.app, module))
+        .collect();
+
+    migrate_in_place(
+        &mut suite.covenant.app,
+        &modules[0],
+        &valence_covenant_two_party_pol::msg::MigrateMsg::HaltRouters {},
+    );
+
+    for (module, snapshot) in modules.iter().zip(snapshots) {
+        assert_eq!(snapshot, snapshot_contract(&suite.covenant.app, module));
+    }
+    for router in routers {
+        let halted: bool = suite
+            .covenant
+            .app
+            .wrap()
+            .query_wasm_smart(router, &valence_native_router::msg::QueryMsg::Halted {})
+            .unwrap();
+        assert!(halted);
+    }
+}
+
+#[test]
+fn test_swap_covenant_decommission_preserves_other_modules() {
+    let mut suite = SwapCovenantSuite::new_with_2_native_configs();
+    suite.get_and_fund_depositors(
+        coin(10_000_000_u128, DENOM_ATOM),
+        coin(10_000_000_u128, DENOM_NTRN),
+    );
+    loop {
+        let holder_state: valence_swap_holder::msg::ContractState = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.holder_addr.clone(),
+                &valence_swap_holder::msg::QueryMsg::ContractState {},
+            )
+            .unwrap();
+        if holder_state == valence_swap_holder::msg::ContractState::Complete {
+            break;
+        }
+        suite.tick("Wait for the holder to complete");
+    }
+
+    // the splitter may dequeue itself from the clock upon decommissioning
+    let modules = vec![
+        suite.covenant_addr.clone(),
+        suite.holder_addr.clone(),
+        suite.router_a_addr.clone(),
+        suite.router_b_addr.clone(),
+    ];
+    let snapshots: Vec<ContractSnapshot> = modules
+        .iter()
+        .map(|module| snapshot_contract(&suite.app, module))
+        .collect();
+
+    migrate_in_place(
+        &mut suite.app,
+        &modules[0],
+        &valence_covenant_swap::msg::MigrateMsg::Decommission {},
+    );
+
+    for (module, snapshot) in modules.iter().zip(snapshots) {
+        assert_eq!(snapshot, snapshot_contract(&suite.app, module));
+    }
+    let decommissioned: bool = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.splitter_addr.clone(),
+            &valence_native_splitter::msg::QueryMsg::Decommissioned {},
+        )
+        .unwrap();
+    assert!(decommissioned);
+}
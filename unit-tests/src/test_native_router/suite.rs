@@ -1,7 +1,11 @@
 use std::collections::BTreeSet;
 
-use cosmwasm_std::Addr;
-use covenant_utils::op_mode::{ContractOperationMode, ContractOperationModeConfig};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
+use covenant_utils::{
+    distribution_receipt::PendingDistribution,
+    op_mode::{ContractOperationMode, ContractOperationModeConfig},
+    router_halt::RouterHaltConfig,
+};
 use cw_multi_test::{AppResponse, Executor};
 
 use crate::setup::{
@@ -30,6 +34,7 @@ impl Default for NativeRouterBuilder {
             tick_max_gas: None,
             whitelist: vec![native_router_addr.to_string()],
             initial_queue: vec![],
+            tick_intervals: vec![],
         };
         builder.contract_init2(
             builder.clock_code_id,
@@ -65,12 +70,40 @@ impl NativeRouterBuilder {
         self
     }
 
+    pub fn with_receiver_msg(mut self, receiver_msg: Option<Binary>) -> Self {
+        self.instantiate_msg.with_receiver_msg(receiver_msg);
+        self
+    }
+
     pub fn with_denoms(mut self, denoms: Vec<String>) -> Self {
         let denom_set = BTreeSet::from_iter(denoms);
         self.instantiate_msg.with_denoms(denom_set);
         self
     }
 
+    pub fn with_halt_config(mut self, halt_config: Option<RouterHaltConfig>) -> Self {
+        self.instantiate_msg.with_halt_config(halt_config);
+        self
+    }
+
+    pub fn with_holder_address(mut self, addr: &str) -> Self {
+        self.instantiate_msg
+            .with_holder_address(Some(addr.to_string()));
+        self
+    }
+
+    pub fn with_cw20_tokens(mut self, cw20_tokens: Vec<String>) -> Self {
+        self.instantiate_msg
+            .with_cw20_tokens(BTreeSet::from_iter(cw20_tokens));
+        self
+    }
+
+    pub fn with_min_route_amount(mut self, denom: &str, amount: u128) -> Self {
+        self.instantiate_msg
+            .with_min_route_amount(denom, Uint128::new(amount));
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let native_router_address = self.builder.contract_init2(
             self.builder.native_router_code_id,
@@ -147,6 +180,16 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_receiver_msg(&mut self) -> Option<Binary> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_native_router::msg::QueryMsg::ReceiverMsg {},
+            )
+            .unwrap()
+    }
+
     pub(crate) fn query_op_mode(&mut self) -> ContractOperationMode {
         self.app
             .wrap()
@@ -177,6 +220,113 @@ impl Suite {
             )
             .unwrap()
     }
+
+    pub fn register_distribution(
+        &mut self,
+        sender: Addr,
+        id: u64,
+        coins: Vec<Coin>,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.router_addr.clone(),
+                &valence_native_router::msg::ExecuteMsg::RegisterDistribution { id, coins },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn send_cw20(&mut self, token: &Addr, amount: u128) -> AppResponse {
+        self.app
+            .execute_contract(
+                self.faucet.clone(),
+                token.clone(),
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: self.router_addr.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: Binary::default(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_cw20_balance(&self, token: &Addr, addr: &Addr) -> Uint128 {
+        let balance: cw20::BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: addr.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    }
+
+    pub fn sweep(&mut self, denoms: Vec<String>) -> AppResponse {
+        self.app
+            .execute_contract(
+                self.faucet.clone(),
+                self.router_addr.clone(),
+                &valence_native_router::msg::ExecuteMsg::Sweep { denoms },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn halt(&mut self, sender: Addr) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.router_addr.clone(),
+                &valence_native_router::msg::ExecuteMsg::Halt {},
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_halted(&mut self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_native_router::msg::QueryMsg::Halted {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_pending_distributions(&mut self) -> Vec<PendingDistribution> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_native_router::msg::QueryMsg::PendingDistributions {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_holder_address(&mut self) -> Option<Addr> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_native_router::msg::QueryMsg::HolderAddress {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_dust_balances(&mut self) -> Vec<Coin> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.router_addr.clone(),
+                &valence_native_router::msg::QueryMsg::DustBalances {},
+            )
+            .unwrap()
+    }
 }
 
 impl BaseSuite for Suite {
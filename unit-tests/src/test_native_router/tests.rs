@@ -1,15 +1,32 @@
-use cosmwasm_std::{coin, coins, Addr, Event};
-use covenant_utils::op_mode::{ContractOperationMode, ContractOperationModeConfig};
+use cosmwasm_std::{
+    coin, coins, to_json_binary, to_json_string, Addr, Binary, Empty, Event, Uint128,
+};
+use covenant_utils::{
+    contribution_adapter::ContributionAdapterMsgs,
+    op_mode::{ContractOperationMode, ContractOperationModeConfig},
+    router_halt::RouterHaltConfig,
+};
 use cw_multi_test::Executor;
 
 use crate::{
     setup::{
+        adapter_contracts::{
+            contribution_adapter_contract, mock_cw20_receiver_contract, mock_holder_contract,
+            ContributionAdapterInstantiateMsg, MockCw20ReceiverQueryMsg, MockHolderMsg,
+        },
         base_suite::{BaseSuite, BaseSuiteMut},
-        ADMIN, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN,
+        ADMIN, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN, DENOM_NTRN,
     },
     test_native_router::suite::NativeRouterBuilder,
 };
 
+fn get_halt_config(builder: &mut NativeRouterBuilder) -> RouterHaltConfig {
+    RouterHaltConfig {
+        holder_address: builder.builder.get_random_addr().to_string(),
+        emergency_committee: Some(builder.builder.get_random_addr().to_string()),
+    }
+}
+
 #[test]
 fn test_instantiate_with_valid_op_mode() {
     let _suite = NativeRouterBuilder::default().build();
@@ -76,6 +93,19 @@ fn test_execute_route_balances_with_no_balances() {
     );
 }
 
+#[test]
+fn test_execute_tick_increments_tick_nonce() {
+    let mut suite = NativeRouterBuilder::default().build();
+    let router = suite.router_addr.clone();
+
+    suite
+        .tick_contract(router.clone())
+        .assert_event(&Event::new("wasm").add_attribute("tick_nonce", "1"));
+    suite
+        .tick_contract(router)
+        .assert_event(&Event::new("wasm").add_attribute("tick_nonce", "2"));
+}
+
 #[test]
 fn test_execute_route_balances_with_one_balance() {
     let mut suite = NativeRouterBuilder::default().build();
@@ -121,6 +151,90 @@ fn test_execute_route_balances_with_multiple_balances() {
     suite.assert_balance(&suite.receiver_addr, coin(1000, DENOM_LS_ATOM_ON_NTRN));
 }
 
+#[test]
+fn test_execute_route_balances_skips_dust() {
+    let mut suite = NativeRouterBuilder::default()
+        .with_denoms(vec![
+            DENOM_ATOM_ON_NTRN.to_string(),
+            DENOM_LS_ATOM_ON_NTRN.to_string(),
+        ])
+        .with_min_route_amount(DENOM_ATOM_ON_NTRN, 1000)
+        .build();
+    let router = suite.router_addr.clone();
+
+    suite.fund_contract(&coins(999, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.fund_contract(&coins(1, DENOM_LS_ATOM_ON_NTRN), router.clone());
+
+    suite.tick_contract(router.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_route_balances")
+            .add_attribute(DENOM_LS_ATOM_ON_NTRN.to_string(), "1")
+            .add_attribute(
+                "skipped_dust",
+                to_json_string(&coins(999, DENOM_ATOM_ON_NTRN)).unwrap(),
+            ),
+    );
+    suite.assert_balance(&router, coin(999, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_addr, coin(1, DENOM_LS_ATOM_ON_NTRN));
+    assert_eq!(suite.query_dust_balances(), coins(999, DENOM_ATOM_ON_NTRN));
+
+    // once the balance accumulates to the minimum, it gets routed
+    suite.fund_contract(&coins(1, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.tick_contract(router.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_route_balances")
+            .add_attribute(DENOM_ATOM_ON_NTRN.to_string(), "1000"),
+    );
+    suite.assert_balance(&router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_addr, coin(1000, DENOM_ATOM_ON_NTRN));
+    assert!(suite.query_dust_balances().is_empty());
+}
+
+#[test]
+fn test_execute_route_balances_acknowledges_registered_distributions() {
+    let builder = NativeRouterBuilder::default();
+    let holder = builder.builder.faucet.clone();
+    let mut suite = builder.with_holder_address(holder.as_str()).build();
+    let router = suite.router_addr.clone();
+
+    suite.fund_contract(&coins(5000, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.register_distribution(holder.clone(), 1, coins(5000, DENOM_ATOM_ON_NTRN));
+
+    let pending = suite.query_pending_distributions();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].holder, holder);
+    assert_eq!(pending[0].id, 1);
+
+    // acknowledgement to a non-contract holder fails without
+    // blocking the routing
+    suite.tick_contract(router.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "reply")
+            .add_attribute("acknowledgement", "failed"),
+    );
+
+    suite.assert_balance(&suite.receiver_addr, coin(5000, DENOM_ATOM_ON_NTRN));
+    assert!(suite.query_pending_distributions().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "only the holder can register distributions")]
+fn test_execute_register_distribution_validates_holder() {
+    let mut builder = NativeRouterBuilder::default();
+    let holder = builder.builder.get_random_addr();
+    let mut suite = builder.with_holder_address(holder.as_str()).build();
+
+    suite.register_distribution(suite.faucet.clone(), 1, coins(5000, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "only the holder can register distributions")]
+fn test_execute_register_distribution_without_holder() {
+    let mut suite = NativeRouterBuilder::default().build();
+
+    suite.register_distribution(suite.faucet.clone(), 1, coins(5000, DENOM_ATOM_ON_NTRN));
+}
+
 #[test]
 #[should_panic(expected = "unauthorized denom distribution")]
 fn test_execute_distribute_fallback_validates_explicit_denoms() {
@@ -157,6 +271,162 @@ fn test_execute_distribute_fallback_happy() {
     suite.assert_balance(&suite.receiver_addr, coin(1000, DENOM_LS_ATOM_ON_NTRN));
 }
 
+/// routes to a contribution adapter converting the funds 1:1 into
+/// the same denom for the returned final receiver
+fn get_contract_receiver_builder(denom: &str) -> (NativeRouterBuilder, Addr) {
+    let mut builder = NativeRouterBuilder::default();
+    let adapter_code_id = builder
+        .builder
+        .app
+        .store_code(contribution_adapter_contract());
+    let adapter = builder.builder.contract_init(
+        adapter_code_id,
+        "contribution_adapter".to_string(),
+        &ContributionAdapterInstantiateMsg {
+            target_denom: denom.to_string(),
+        },
+        &[],
+    );
+    let final_receiver = builder.builder.get_random_addr();
+    let receiver_msg = to_json_binary(&ContributionAdapterMsgs::Convert {
+        receiver: final_receiver.to_string(),
+    })
+    .unwrap();
+
+    let builder = builder
+        .with_receiver_address(adapter.as_str())
+        .with_receiver_msg(Some(receiver_msg));
+    (builder, final_receiver)
+}
+
+#[test]
+fn test_execute_route_balances_to_contract_receiver() {
+    let (builder, final_receiver) = get_contract_receiver_builder(DENOM_ATOM_ON_NTRN);
+    let mut suite = builder.build();
+    let router = suite.router_addr.clone();
+    assert!(suite.query_receiver_msg().is_some());
+
+    suite.fund_contract(&coins(5000, DENOM_ATOM_ON_NTRN), router.clone());
+
+    suite.tick_contract(router.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_route_balances")
+            .add_attribute(DENOM_ATOM_ON_NTRN.to_string(), "5000"),
+    );
+
+    suite.assert_balance(&router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_addr, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&final_receiver, coin(5000, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+fn test_execute_distribute_fallback_to_contract_receiver() {
+    let (builder, final_receiver) = get_contract_receiver_builder(DENOM_LS_ATOM_ON_NTRN);
+    let mut suite = builder.build();
+    let router = suite.router_addr.clone();
+
+    suite.fund_contract(&coins(1000, DENOM_LS_ATOM_ON_NTRN), router.clone());
+
+    suite
+        .distribute_fallback(vec![DENOM_LS_ATOM_ON_NTRN.to_string()])
+        .assert_event(&Event::new("wasm").add_attribute("method", "try_distribute_fallback"));
+
+    suite.assert_balance(&router, coin(0, DENOM_LS_ATOM_ON_NTRN));
+    suite.assert_balance(&final_receiver, coin(1000, DENOM_LS_ATOM_ON_NTRN));
+}
+
+#[test]
+fn test_execute_halt_returns_balances_to_holder() {
+    let mut builder = NativeRouterBuilder::default();
+    let halt_config = get_halt_config(&mut builder);
+    let mut suite = builder.with_halt_config(Some(halt_config.clone())).build();
+    let router = suite.router_addr.clone();
+    let holder = Addr::unchecked(halt_config.holder_address);
+
+    suite.fund_contract(&coins(5000, DENOM_ATOM_ON_NTRN), router.clone());
+    // without a holder address, the holder of the halt config registers distributions
+    assert_eq!(suite.query_holder_address(), Some(holder.clone()));
+    suite.register_distribution(holder.clone(), 1, coins(5000, DENOM_ATOM_ON_NTRN));
+
+    // the router admin is the covenant
+    suite.halt(Addr::unchecked(ADMIN)).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_halt")
+            .add_attribute("holder", holder.to_string()),
+    );
+
+    assert!(suite.query_halted());
+    assert!(suite.query_pending_distributions().is_empty());
+    suite.assert_balance(&router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&holder, coin(5000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_addr, coin(0, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+fn test_execute_halt_by_emergency_committee() {
+    let mut builder = NativeRouterBuilder::default();
+    let halt_config = get_halt_config(&mut builder);
+    let mut suite = builder.with_halt_config(Some(halt_config.clone())).build();
+
+    suite.halt(Addr::unchecked(halt_config.emergency_committee.unwrap()));
+
+    assert!(suite.query_halted());
+}
+
+#[test]
+#[should_panic(expected = "only the covenant or the emergency committee can halt the router")]
+fn test_execute_halt_validates_sender() {
+    let mut builder = NativeRouterBuilder::default();
+    let halt_config = get_halt_config(&mut builder);
+    let mut suite = builder.with_halt_config(Some(halt_config)).build();
+    let receiver = suite.receiver_addr.clone();
+
+    suite.halt(receiver);
+}
+
+#[test]
+#[should_panic(expected = "router halting is not configured")]
+fn test_execute_halt_validates_halt_config() {
+    let mut suite = NativeRouterBuilder::default().build();
+
+    suite.halt(Addr::unchecked(ADMIN));
+}
+
+#[test]
+fn test_execute_tick_after_halt_returns_balances_to_holder() {
+    let mut builder = NativeRouterBuilder::default();
+    let halt_config = get_halt_config(&mut builder);
+    let mut suite = builder.with_halt_config(Some(halt_config.clone())).build();
+    let router = suite.router_addr.clone();
+    let holder = Addr::unchecked(halt_config.holder_address);
+
+    suite.halt(Addr::unchecked(ADMIN));
+    suite.fund_contract(&coins(5000, DENOM_ATOM_ON_NTRN), router.clone());
+
+    suite.tick_contract(router.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_return_balances")
+            .add_attribute("holder", holder.to_string()),
+    );
+
+    suite.assert_balance(&holder, coin(5000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_addr, coin(0, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "router is halted")]
+fn test_execute_distribute_fallback_after_halt() {
+    let mut builder = NativeRouterBuilder::default();
+    let halt_config = get_halt_config(&mut builder);
+    let mut suite = builder.with_halt_config(Some(halt_config)).build();
+    let router = suite.router_addr.clone();
+
+    suite.halt(Addr::unchecked(ADMIN));
+    suite.fund_contract(&coins(1000, DENOM_LS_ATOM_ON_NTRN), router);
+
+    suite.distribute_fallback(vec![DENOM_LS_ATOM_ON_NTRN.to_string()]);
+}
+
 #[test]
 fn test_migrate_update_config() {
     let mut suite = NativeRouterBuilder::default().build();
@@ -165,6 +435,7 @@ fn test_migrate_update_config() {
     let clock_addr = suite.clock_addr.clone();
     let mut target_denoms = suite.query_target_denoms();
     let receiver_addr = suite.receiver_addr.clone();
+    let faucet = suite.faucet.clone();
     target_denoms.insert("new_denom".to_string());
 
     suite
@@ -177,6 +448,7 @@ fn test_migrate_update_config() {
                     .into(),
                 receiver_address: Some(clock_addr.to_string()),
                 target_denoms: Some(target_denoms.clone().into_iter().collect()),
+                holder_address: Some(faucet.to_string()),
             },
             9,
         )
@@ -188,4 +460,225 @@ fn test_migrate_update_config() {
     );
     assert_eq!(suite.query_target_denoms(), target_denoms);
     assert_eq!(suite.query_receiver_config(), clock_addr);
+    assert_eq!(suite.query_holder_address(), Some(faucet));
+}
+
+/// configures a mock holder reporting the given contract state
+fn get_sweep_builder(contract_state: &str) -> (NativeRouterBuilder, Addr) {
+    let mut builder = NativeRouterBuilder::default();
+    let holder_code_id = builder.builder.app.store_code(mock_holder_contract());
+    let holder = builder.builder.contract_init(
+        holder_code_id,
+        "holder".to_string(),
+        &MockHolderMsg {
+            contract_state: contract_state.to_string(),
+        },
+        &[],
+    );
+    let builder = builder.with_holder_address(holder.as_str());
+    (builder, holder)
+}
+
+#[test]
+fn test_execute_sweep_after_holder_complete() {
+    let (builder, holder) = get_sweep_builder("active");
+    let mut suite = builder.build();
+    let router = suite.router_addr.clone();
+
+    suite.fund_contract(&coins(5000, DENOM_ATOM_ON_NTRN), router.clone());
+    suite.fund_contract(&coins(1000, DENOM_NTRN), router.clone());
+
+    let sweep_msg = valence_native_router::msg::ExecuteMsg::Sweep {
+        denoms: vec![DENOM_ATOM_ON_NTRN.to_string(), DENOM_NTRN.to_string()],
+    };
+    let err = suite
+        .app
+        .execute_contract(suite.faucet.clone(), router.clone(), &sweep_msg, &[])
+        .unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("router can only be swept once the holder is complete"));
+
+    suite
+        .app
+        .execute_contract(
+            suite.admin.clone(),
+            holder,
+            &MockHolderMsg {
+                contract_state: "complete".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    suite
+        .sweep(vec![DENOM_ATOM_ON_NTRN.to_string(), DENOM_NTRN.to_string()])
+        .assert_event(&Event::new("wasm").add_attribute("method", "try_sweep"));
+
+    suite.assert_balance(&router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&router, coin(0, DENOM_NTRN));
+    suite.assert_balance(&suite.receiver_addr, coin(5000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_addr, coin(1000, DENOM_NTRN));
+}
+
+#[test]
+fn test_execute_sweep_with_halt_config_holder() {
+    let mut builder = NativeRouterBuilder::default();
+    let holder_code_id = builder.builder.app.store_code(mock_holder_contract());
+    let holder = builder.builder.contract_init(
+        holder_code_id,
+        "holder".to_string(),
+        &MockHolderMsg {
+            contract_state: "complete".to_string(),
+        },
+        &[],
+    );
+    let mut suite = builder
+        .with_halt_config(Some(RouterHaltConfig {
+            holder_address: holder.to_string(),
+            emergency_committee: None,
+        }))
+        .build();
+    let router = suite.router_addr.clone();
+    suite.fund_contract(&coins(5000, DENOM_ATOM_ON_NTRN), router.clone());
+
+    suite.sweep(vec![DENOM_ATOM_ON_NTRN.to_string()]);
+    suite.assert_balance(&router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_addr, coin(5000, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "router has no holder to be swept after")]
+fn test_execute_sweep_validates_holder() {
+    let mut suite = NativeRouterBuilder::default().build();
+
+    suite.sweep(vec![DENOM_ATOM_ON_NTRN.to_string()]);
+}
+
+#[test]
+#[should_panic(expected = "unknown variant")]
+fn test_execute_sweep_validates_holder_state() {
+    let (builder, _) = get_sweep_builder("withdrawn");
+    let mut suite = builder.build();
+
+    suite.sweep(vec![DENOM_ATOM_ON_NTRN.to_string()]);
+}
+
+#[test]
+fn test_execute_route_cw20_tokens() {
+    let mut builder = NativeRouterBuilder::default();
+    let token = builder
+        .builder
+        .init_cw20_token("CWATOM", Uint128::new(1_000_000));
+    let mut suite = builder.with_cw20_tokens(vec![token.to_string()]).build();
+    let router = suite.router_addr.clone();
+    let receiver = suite.receiver_addr.clone();
+
+    // tokens sent to the router are forwarded right away
+    suite.send_cw20(&token, 1_000).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_receive_cw20")
+            .add_attribute(token.to_string(), "1000"),
+    );
+    assert_eq!(suite.query_cw20_balance(&token, &router), Uint128::zero());
+    assert_eq!(
+        suite.query_cw20_balance(&token, &receiver),
+        Uint128::new(1_000)
+    );
+
+    // while plain transfers get routed on tick
+    let faucet = suite.faucet.clone();
+    suite
+        .app
+        .execute_contract(
+            faucet,
+            token.clone(),
+            &cw20::Cw20ExecuteMsg::Transfer {
+                recipient: router.to_string(),
+                amount: Uint128::new(500),
+            },
+            &[],
+        )
+        .unwrap();
+    suite.tick_contract(router.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_route_balances")
+            .add_attribute(token.to_string(), "500"),
+    );
+    assert_eq!(suite.query_cw20_balance(&token, &router), Uint128::zero());
+    assert_eq!(
+        suite.query_cw20_balance(&token, &receiver),
+        Uint128::new(1_500)
+    );
+}
+
+#[test]
+fn test_execute_route_cw20_tokens_with_receiver_msg() {
+    let mut builder = NativeRouterBuilder::default();
+    let token = builder
+        .builder
+        .init_cw20_token("CWATOM", Uint128::new(1_000_000));
+    let receiver_code_id = builder
+        .builder
+        .app
+        .store_code(mock_cw20_receiver_contract());
+    let receiver = builder.builder.contract_init(
+        receiver_code_id,
+        "cw20_receiver".to_string(),
+        &Empty {},
+        &[],
+    );
+    let receiver_msg = to_json_binary(&"deposit").unwrap();
+    let mut suite = builder
+        .with_cw20_tokens(vec![token.to_string()])
+        .with_receiver_address(receiver.as_str())
+        .with_receiver_msg(Some(receiver_msg.clone()))
+        .build();
+    let router = suite.router_addr.clone();
+
+    // tokens sent to the router are sent along with the receiver msg
+    suite.send_cw20(&token, 1_000);
+    assert_eq!(
+        suite.query_cw20_balance(&token, &receiver),
+        Uint128::new(1_000)
+    );
+    let received_msg: Binary = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&receiver, &MockCw20ReceiverQueryMsg::ReceivedMsg {})
+        .unwrap();
+    assert_eq!(received_msg, receiver_msg);
+
+    // and so are the balances routed on tick
+    let faucet = suite.faucet.clone();
+    suite
+        .app
+        .execute_contract(
+            faucet,
+            token.clone(),
+            &cw20::Cw20ExecuteMsg::Transfer {
+                recipient: router.to_string(),
+                amount: Uint128::new(500),
+            },
+            &[],
+        )
+        .unwrap();
+    suite.tick_contract(router.clone());
+    assert_eq!(suite.query_cw20_balance(&token, &router), Uint128::zero());
+    assert_eq!(
+        suite.query_cw20_balance(&token, &receiver),
+        Uint128::new(1_500)
+    );
+}
+
+#[test]
+#[should_panic(expected = "is not routed")]
+fn test_execute_receive_cw20_validates_allowlist() {
+    let mut builder = NativeRouterBuilder::default();
+    let token = builder
+        .builder
+        .init_cw20_token("CWATOM", Uint128::new(1_000_000));
+    let mut suite = builder.build();
+
+    suite.send_cw20(&token, 1_000);
 }
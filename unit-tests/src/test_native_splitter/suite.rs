@@ -1,8 +1,9 @@
 use std::collections::BTreeMap;
 
-use cosmwasm_std::Addr;
-use covenant_utils::split::SplitConfig;
+use cosmwasm_std::{Addr, Coin, Uint128};
+use covenant_utils::{split::SplitConfig, split_receiver_fix::SplitReceiverFix};
 use cw_multi_test::{AppResponse, Executor};
+use valence_native_splitter::msg::{CompletionConfig, IbcReceiverConfig, ReceiverType};
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
@@ -28,6 +29,7 @@ impl Default for NativeSplitterBuilder {
             tick_max_gas: None,
             whitelist: vec![native_splitter_addr.to_string()],
             initial_queue: vec![],
+            tick_intervals: vec![],
         };
         builder.contract_init2(
             builder.clock_code_id,
@@ -69,6 +71,33 @@ impl NativeSplitterBuilder {
         self
     }
 
+    pub fn with_emergency_committee(mut self, addr: &str) -> Self {
+        self.instantiate_msg.with_emergency_committee(addr);
+        self
+    }
+
+    pub fn with_owner(mut self, addr: &str) -> Self {
+        self.instantiate_msg.with_owner(addr);
+        self
+    }
+
+    pub fn with_op_admin(mut self, addr: &str) -> Self {
+        self.instantiate_msg.with_op_admin(addr);
+        self
+    }
+
+    pub fn with_expected_amounts(mut self, expected_amounts: Vec<Coin>) -> Self {
+        self.instantiate_msg
+            .with_completion_config(Some(CompletionConfig { expected_amounts }));
+        self
+    }
+
+    pub fn with_receiver_type(mut self, receiver: &str, receiver_type: ReceiverType) -> Self {
+        self.instantiate_msg
+            .with_receiver_type(receiver, receiver_type);
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let native_splitter_address = self.builder.contract_init2(
             self.builder.native_splitter_code_id,
@@ -193,6 +222,182 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn update_splits(
+        &mut self,
+        sender: Addr,
+        splits: BTreeMap<String, SplitConfig>,
+        fallback: Option<SplitConfig>,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.splitter.clone(),
+                &valence_native_splitter::msg::ExecuteMsg::UpdateSplits { splits, fallback },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_op_admin(&self) -> Option<Addr> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_native_splitter::msg::QueryMsg::OpAdmin {},
+            )
+            .unwrap()
+    }
+
+    pub fn fix_split_receiver(
+        &mut self,
+        sender: Addr,
+        denom: &str,
+        old: &str,
+        new: &str,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.splitter.clone(),
+                &valence_native_splitter::msg::ExecuteMsg::FixSplitReceiver {
+                    denom: denom.to_string(),
+                    old: old.to_string(),
+                    new: new.to_string(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_pending_split_receiver_fix(&mut self, denom: &str) -> Option<SplitReceiverFix> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_native_splitter::msg::QueryMsg::PendingSplitReceiverFix {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_ibc_receivers(&self) -> BTreeMap<String, IbcReceiverConfig> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_native_splitter::msg::QueryMsg::IbcReceivers {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_accrued_distributions(&mut self, denom: &str) -> BTreeMap<String, Uint128> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_native_splitter::msg::QueryMsg::AccruedDistributions {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn decommission(&mut self, sender: Addr) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.splitter.clone(),
+                &valence_native_splitter::msg::ExecuteMsg::Decommission {},
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_completed(&mut self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_native_splitter::msg::QueryMsg::Completed {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_received_amounts(&mut self) -> BTreeMap<String, Uint128> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_native_splitter::msg::QueryMsg::ReceivedAmounts {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_clock_queued(&mut self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.clock_addr.clone(),
+                &valence_clock::msg::QueryMsg::IsQueued {
+                    address: self.splitter.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_decommissioned(&mut self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_native_splitter::msg::QueryMsg::Decommissioned {},
+            )
+            .unwrap()
+    }
+
+    pub fn update_config(
+        &mut self,
+        sender: Addr,
+        clock_addr: Option<String>,
+        fallback_split: Option<SplitConfig>,
+        splits: Option<BTreeMap<String, SplitConfig>>,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.splitter.clone(),
+                &valence_native_splitter::msg::ExecuteMsg::UpdateConfig {
+                    clock_addr,
+                    fallback_split,
+                    splits,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn update_ownership(&mut self, sender: Addr, action: cw_ownable::Action) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.splitter.clone(),
+                &valence_native_splitter::msg::ExecuteMsg::UpdateOwnership(action),
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_ownership(&self) -> cw_ownable::Ownership<Addr> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_native_splitter::msg::QueryMsg::Ownership {},
+            )
+            .unwrap()
+    }
+
     pub fn distribute_fallback(&mut self, denoms: Vec<String>) -> AppResponse {
         self.app
             .execute_contract(
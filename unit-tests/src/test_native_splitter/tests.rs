@@ -1,12 +1,13 @@
 use std::collections::BTreeMap;
 
-use cosmwasm_std::{coin, coins, Addr, Decimal};
-use covenant_utils::split::SplitConfig;
+use cosmwasm_std::{coin, coins, Addr, Decimal, Event, Uint128, Uint64};
+use covenant_utils::{split::SplitConfig, split_receiver_fix::SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS};
 use cw_multi_test::Executor;
+use valence_native_splitter::msg::{IbcReceiverConfig, ReceiverType};
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
-    ADMIN, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN, DENOM_NTRN,
+    ADMIN, DENOM_ATOM, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN, DENOM_NTRN, NTRN_HUB_CHANNEL,
 };
 
 use super::suite::NativeSplitterBuilder;
@@ -25,6 +26,8 @@ fn test_instantiate_validates_explicit_split_shares() {
         denom,
         SplitConfig {
             receivers: invalid_split_config,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
         },
     );
     builder.build();
@@ -44,11 +47,44 @@ fn test_instantiate_validates_explicit_split_receiver_addresses() {
         denom,
         SplitConfig {
             receivers: invalid_split_config,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
         },
     );
     builder.build();
 }
 
+#[test]
+#[should_panic(expected = "denom ibc/invalid does not exist on this chain")]
+fn test_instantiate_validates_explicit_split_denoms() {
+    let mut builder = NativeSplitterBuilder::default();
+    builder.instantiate_msg.msg.validate_denoms = true;
+    let (_, split_config) = builder.instantiate_msg.msg.splits.pop_first().unwrap();
+    builder
+        .instantiate_msg
+        .msg
+        .splits
+        .insert("ibc/invalid".to_string(), split_config);
+    builder.build();
+}
+
+#[test]
+fn test_instantiate_allows_unbridged_split_denoms() {
+    let mut builder = NativeSplitterBuilder::default();
+    let (_, split_config) = builder.instantiate_msg.msg.splits.pop_first().unwrap();
+    builder
+        .instantiate_msg
+        .msg
+        .splits
+        .insert("ibc/unbridged".to_string(), split_config.clone());
+    let mut suite = builder.build();
+
+    assert_eq!(
+        suite.query_denom_split("ibc/unbridged".to_string()),
+        split_config
+    );
+}
+
 #[test]
 #[should_panic]
 fn test_instantiate_validates_clock_address() {
@@ -65,6 +101,8 @@ fn test_instantiate_validates_fallback_split_receiver_addresses() {
     NativeSplitterBuilder::default()
         .with_fallback_split(Some(SplitConfig {
             receivers: invalid_split_config,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
         }))
         .build();
 }
@@ -81,6 +119,8 @@ fn test_instantiate_validates_fallback_split_shares() {
     builder
         .with_fallback_split(Some(SplitConfig {
             receivers: invalid_split_config,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
         }))
         .build();
 }
@@ -113,6 +153,23 @@ fn test_execute_distribute_single_denom() {
     suite.assert_balance(&suite.receiver_2, coin(50000, DENOM_ATOM_ON_NTRN));
 }
 
+#[test]
+fn test_execute_distribute_leaves_no_dust() {
+    let mut suite = NativeSplitterBuilder::default().build();
+
+    suite.fund_contract(&coins(100001, DENOM_ATOM_ON_NTRN), suite.splitter.clone());
+
+    suite.tick_contract(suite.splitter.clone());
+    suite.assert_balance(&suite.splitter, coin(0, DENOM_ATOM_ON_NTRN));
+
+    let receiver_1_bal = suite.query_balance(&suite.receiver_1, DENOM_ATOM_ON_NTRN);
+    let receiver_2_bal = suite.query_balance(&suite.receiver_2, DENOM_ATOM_ON_NTRN);
+    assert_eq!(
+        receiver_1_bal.amount + receiver_2_bal.amount,
+        Uint128::new(100001)
+    );
+}
+
 #[test]
 fn test_execute_distribute_multiple_denoms() {
     let mut suite = NativeSplitterBuilder::default().build();
@@ -134,6 +191,111 @@ fn test_execute_distribute_multiple_denoms() {
     suite.assert_balance(&suite.receiver_2, coin(50000, DENOM_LS_ATOM_ON_NTRN));
 }
 
+#[test]
+fn test_execute_distribute_to_ibc_receiver() {
+    let builder = NativeSplitterBuilder::default();
+    let ibc_receiver = builder.instantiate_msg.msg.splits[DENOM_ATOM_ON_NTRN]
+        .receivers
+        .keys()
+        .next()
+        .unwrap()
+        .to_string();
+    let ibc_receiver_config = IbcReceiverConfig {
+        channel_id: NTRN_HUB_CHANNEL.0.to_string(),
+        ibc_transfer_timeout: Uint64::new(100),
+        denom_to_pfm_map: BTreeMap::new(),
+    };
+    let mut suite = builder
+        .with_receiver_type(
+            &ibc_receiver,
+            ReceiverType::Ibc(ibc_receiver_config.clone()),
+        )
+        .build();
+    let native_receiver = suite.receiver_2.clone();
+    assert_eq!(suite.receiver_1.as_str(), ibc_receiver);
+    assert_eq!(
+        suite.query_ibc_receivers(),
+        BTreeMap::from([(ibc_receiver.to_string(), ibc_receiver_config)])
+    );
+
+    // the splitter pays the ibc fees
+    suite.fund_contract(&coins(100_000, DENOM_NTRN), suite.splitter.clone());
+    suite.fund_contract(&coins(100_000, DENOM_ATOM_ON_NTRN), suite.splitter.clone());
+
+    suite.tick_contract(suite.splitter.clone());
+    suite.assert_balance(&suite.splitter, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&ibc_receiver, coin(50_000, DENOM_ATOM));
+    suite.assert_balance(&native_receiver, coin(50_000, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "soft_addr_validation")]
+fn test_instantiate_validates_ibc_receiver_addresses() {
+    NativeSplitterBuilder::default()
+        .with_receiver_type(
+            "invalid_receiver",
+            ReceiverType::Ibc(IbcReceiverConfig {
+                channel_id: NTRN_HUB_CHANNEL.0.to_string(),
+                ibc_transfer_timeout: Uint64::new(100),
+                denom_to_pfm_map: BTreeMap::new(),
+            }),
+        )
+        .build();
+}
+
+#[test]
+fn test_execute_distribute_accrues_below_min_amount() {
+    let mut builder = NativeSplitterBuilder::default();
+    let split = builder
+        .instantiate_msg
+        .msg
+        .splits
+        .get_mut(DENOM_ATOM_ON_NTRN)
+        .unwrap();
+    let receiver = split.receivers.keys().next().unwrap().to_string();
+    split
+        .min_amounts
+        .insert(receiver.to_string(), Uint128::new(60000));
+    let mut suite = builder.build();
+
+    // receiver_1 share is below its min amount and accrues
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.splitter.clone());
+    suite.tick_contract(suite.splitter.clone());
+    suite.assert_balance(&suite.splitter, coin(50000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_1, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_2, coin(50000, DENOM_ATOM_ON_NTRN));
+    assert_eq!(
+        suite.query_accrued_distributions(DENOM_ATOM_ON_NTRN),
+        BTreeMap::from([(receiver, Uint128::new(50000))])
+    );
+
+    // accrued amount crosses the min amount and gets flushed
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.splitter.clone());
+    suite.tick_contract(suite.splitter.clone());
+    suite.assert_balance(&suite.splitter, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_1, coin(100000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_2, coin(100000, DENOM_ATOM_ON_NTRN));
+    assert!(suite
+        .query_accrued_distributions(DENOM_ATOM_ON_NTRN)
+        .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "min amount set for unknown receiver")]
+fn test_instantiate_validates_min_amount_receivers() {
+    let mut builder = NativeSplitterBuilder::default();
+    let unknown = builder.builder.get_random_addr();
+    builder
+        .instantiate_msg
+        .msg
+        .splits
+        .get_mut(DENOM_ATOM_ON_NTRN)
+        .unwrap()
+        .min_amounts
+        .insert(unknown.to_string(), Uint128::new(1));
+    builder.build();
+}
+
 #[test]
 #[should_panic(expected = "unauthorized denom distribution")]
 fn test_execute_distribute_fallback_validates_explicit_denoms() {
@@ -176,6 +338,239 @@ fn test_execute_distribute_fallback_happy() {
     suite.assert_balance(&suite.receiver_2, coin(50000, DENOM_NTRN));
 }
 
+#[test]
+fn test_execute_fix_split_receiver() {
+    let mut builder = NativeSplitterBuilder::default();
+    let committee = builder.builder.get_random_addr();
+    let mut suite = builder.with_emergency_committee(committee.as_str()).build();
+    let old_receiver = suite.receiver_1.to_string();
+    let new_receiver = suite.app.api().addr_make("new_receiver");
+
+    suite.fix_split_receiver(
+        committee.clone(),
+        DENOM_ATOM_ON_NTRN,
+        &old_receiver,
+        new_receiver.as_str(),
+    );
+    assert!(suite
+        .query_pending_split_receiver_fix(DENOM_ATOM_ON_NTRN)
+        .is_some());
+
+    suite
+        .app
+        .update_block(|b| b.time = b.time.plus_seconds(SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS));
+    let resp = suite.fix_split_receiver(
+        committee,
+        DENOM_ATOM_ON_NTRN,
+        &old_receiver,
+        new_receiver.as_str(),
+    );
+    assert!(resp.has_event(
+        &Event::new("wasm-split_receiver_fix_applied")
+            .add_attribute("denom", DENOM_ATOM_ON_NTRN)
+            .add_attribute("old", &old_receiver)
+            .add_attribute("new", new_receiver.as_str())
+    ));
+
+    // distributions follow the fixed split
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.splitter.clone());
+    suite.tick_contract(suite.splitter.clone());
+    suite.assert_balance(&new_receiver, coin(50000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_1, coin(0, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "unauthorized caller")]
+fn test_execute_fix_split_receiver_validates_committee() {
+    let mut suite = NativeSplitterBuilder::default().build();
+    let old_receiver = suite.receiver_1.to_string();
+    let new_receiver = suite.app.api().addr_make("new_receiver");
+
+    suite.fix_split_receiver(
+        suite.faucet.clone(),
+        DENOM_ATOM_ON_NTRN,
+        &old_receiver,
+        new_receiver.as_str(),
+    );
+}
+
+#[test]
+fn test_execute_decommission_flushes_accrued_distributions() {
+    let mut builder = NativeSplitterBuilder::default();
+    let split = builder
+        .instantiate_msg
+        .msg
+        .splits
+        .get_mut(DENOM_ATOM_ON_NTRN)
+        .unwrap();
+    let receiver = split.receivers.keys().next().unwrap().to_string();
+    split.min_amounts.insert(receiver, Uint128::new(60000));
+    let mut suite = builder.build();
+
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.splitter.clone());
+    suite.tick_contract(suite.splitter.clone());
+    suite.assert_balance(&suite.splitter, coin(50000, DENOM_ATOM_ON_NTRN));
+
+    // the splitter admin is the covenant
+    suite
+        .decommission(Addr::unchecked(ADMIN))
+        .assert_event(&Event::new("wasm").add_attribute("method", "try_decommission"));
+
+    assert!(suite.query_decommissioned());
+    assert!(suite
+        .query_accrued_distributions(DENOM_ATOM_ON_NTRN)
+        .is_empty());
+    suite.assert_balance(&suite.splitter, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_1, coin(50000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_2, coin(50000, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "only the covenant can decommission the contract")]
+fn test_execute_decommission_validates_sender() {
+    let mut suite = NativeSplitterBuilder::default().build();
+    let sender = suite.faucet.clone();
+
+    suite.decommission(sender);
+}
+
+#[test]
+fn test_execute_distribute_completes_once_expected_amounts_received() {
+    let mut builder = NativeSplitterBuilder::default().with_expected_amounts(vec![
+        coin(100000, DENOM_ATOM_ON_NTRN),
+        coin(50000, DENOM_LS_ATOM_ON_NTRN),
+    ]);
+    let split = builder
+        .instantiate_msg
+        .msg
+        .splits
+        .get_mut(DENOM_ATOM_ON_NTRN)
+        .unwrap();
+    let receiver = split.receivers.keys().next().unwrap().to_string();
+    split.min_amounts.insert(receiver, Uint128::new(60000));
+    let mut suite = builder.build();
+    assert!(suite.query_clock_queued());
+
+    // first part of the expected amounts accrues below the min amount
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.splitter.clone());
+    suite.tick_contract(suite.splitter.clone());
+    assert!(!suite.query_completed());
+    suite.assert_balance(&suite.splitter, coin(50000, DENOM_ATOM_ON_NTRN));
+
+    // accrued amounts are not recorded twice
+    suite.fund_contract(&coins(20000, DENOM_LS_ATOM_ON_NTRN), suite.splitter.clone());
+    suite.tick_contract(suite.splitter.clone());
+    assert!(!suite.query_completed());
+    assert_eq!(
+        suite.query_received_amounts(),
+        BTreeMap::from([
+            (DENOM_ATOM_ON_NTRN.to_string(), Uint128::new(100000)),
+            (DENOM_LS_ATOM_ON_NTRN.to_string(), Uint128::new(20000)),
+        ])
+    );
+
+    suite.fund_contract(&coins(30000, DENOM_LS_ATOM_ON_NTRN), suite.splitter.clone());
+    suite
+        .tick_contract(suite.splitter.clone())
+        .assert_event(&Event::new("wasm").add_attribute("status", "completed"));
+
+    assert!(suite.query_completed());
+    assert!(!suite.query_clock_queued());
+    // accrued amounts got flushed on completion
+    suite.assert_balance(&suite.splitter, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.splitter, coin(0, DENOM_LS_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_1, coin(50000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_2, coin(50000, DENOM_ATOM_ON_NTRN));
+
+    // a completed splitter can still be decommissioned
+    suite.decommission(Addr::unchecked(ADMIN));
+    assert!(suite.query_decommissioned());
+}
+
+#[test]
+#[should_panic(expected = "expected amount of untrn has to be non-zero and covered by a split")]
+fn test_instantiate_validates_completion_config() {
+    NativeSplitterBuilder::default()
+        .with_expected_amounts(vec![coin(100, DENOM_NTRN)])
+        .build();
+}
+
+#[test]
+fn test_execute_update_splits() {
+    let mut builder = NativeSplitterBuilder::default();
+    let op_admin = builder.builder.get_random_addr();
+    let mut suite = builder.with_op_admin(op_admin.as_str()).build();
+    assert_eq!(suite.query_op_admin(), Some(op_admin.clone()));
+
+    let split = SplitConfig {
+        receivers: BTreeMap::from([
+            (suite.receiver_1.to_string(), Decimal::percent(10)),
+            (suite.receiver_2.to_string(), Decimal::percent(90)),
+        ]),
+        rounding: Default::default(),
+        min_amounts: Default::default(),
+    };
+    let splits = BTreeMap::from([(DENOM_ATOM_ON_NTRN.to_string(), split.clone())]);
+
+    suite
+        .update_splits(op_admin.clone(), splits.clone(), Some(split.clone()))
+        .assert_event(
+            &Event::new("wasm-splits_updated")
+                .add_attribute("op_admin", op_admin)
+                .add_attributes(vec![split.get_response_attribute("fallback".to_string())]),
+        );
+    assert_eq!(suite.query_all_splits(), splits);
+    assert_eq!(suite.query_fallback_split(), Some(split));
+
+    // the updated splits apply to the next distribution
+    suite.fund_contract(&coins(100_000, DENOM_ATOM_ON_NTRN), suite.splitter.clone());
+    suite.tick_contract(suite.splitter.clone());
+    suite.assert_balance(&suite.receiver_1, coin(10_000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.receiver_2, coin(90_000, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "unauthorized caller")]
+fn test_execute_update_splits_validates_op_admin() {
+    let mut builder = NativeSplitterBuilder::default();
+    let op_admin = builder.builder.get_random_addr();
+    let mut suite = builder.with_op_admin(op_admin.as_str()).build();
+    let splits = suite.query_all_splits();
+
+    suite.update_splits(suite.faucet.clone(), splits, None);
+}
+
+#[test]
+#[should_panic(expected = "shares must add up to 1.0")]
+fn test_execute_update_splits_validates_shares() {
+    let mut builder = NativeSplitterBuilder::default();
+    let op_admin = builder.builder.get_random_addr();
+    let mut suite = builder.with_op_admin(op_admin.as_str()).build();
+    let mut splits = suite.query_all_splits();
+    splits
+        .get_mut(DENOM_ATOM_ON_NTRN)
+        .unwrap()
+        .receivers
+        .insert(suite.receiver_1.to_string(), Decimal::percent(10));
+
+    suite.update_splits(op_admin, splits, None);
+}
+
+#[test]
+#[should_panic(expected = "expected amount of")]
+fn test_execute_update_splits_validates_completion_config() {
+    let mut builder = NativeSplitterBuilder::default();
+    let op_admin = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_op_admin(op_admin.as_str())
+        .with_expected_amounts(coins(100_000, DENOM_LS_ATOM_ON_NTRN))
+        .build();
+    let mut splits = suite.query_all_splits();
+    splits.remove(DENOM_LS_ATOM_ON_NTRN);
+
+    suite.update_splits(op_admin, splits, None);
+}
+
 #[test]
 fn test_migrate_update_config() {
     let mut suite = NativeSplitterBuilder::default()
@@ -213,3 +608,137 @@ fn test_migrate_update_config() {
     assert_eq!(clock_address, suite.faucet);
     assert_eq!(splits.get(DENOM_LS_ATOM_ON_NTRN).unwrap(), &ls_atom_split);
 }
+
+#[test]
+fn test_execute_update_config() {
+    let mut builder = NativeSplitterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+
+    assert_eq!(suite.query_ownership().owner, Some(owner.clone()));
+
+    let mut splits = suite.query_all_splits();
+    splits.remove(DENOM_ATOM_ON_NTRN);
+
+    suite
+        .update_config(
+            owner,
+            Some(suite.faucet.to_string()),
+            None,
+            Some(splits.clone()),
+        )
+        .assert_event(
+            &Event::new("wasm")
+                .add_attribute("method", "update_config")
+                .add_attribute("clock_addr", suite.faucet.to_string()),
+        );
+
+    assert_eq!(suite.query_clock_address(), suite.faucet);
+    assert_eq!(
+        suite.query_denom_split(DENOM_LS_ATOM_ON_NTRN.to_string()),
+        splits.get(DENOM_LS_ATOM_ON_NTRN).unwrap().clone()
+    );
+}
+
+#[test]
+fn test_execute_update_config_clears_pending_split_receiver_fixes() {
+    let mut builder = NativeSplitterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let committee = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_owner(owner.as_str())
+        .with_emergency_committee(committee.as_str())
+        .build();
+    let old_receiver = suite.receiver_1.to_string();
+    let new_receiver = suite.app.api().addr_make("new_receiver");
+
+    suite.fix_split_receiver(
+        committee,
+        DENOM_ATOM_ON_NTRN,
+        &old_receiver,
+        new_receiver.as_str(),
+    );
+    assert!(suite
+        .query_pending_split_receiver_fix(DENOM_ATOM_ON_NTRN)
+        .is_some());
+
+    let splits = suite.query_all_splits();
+    suite.update_config(owner, None, None, Some(splits));
+
+    assert!(suite
+        .query_pending_split_receiver_fix(DENOM_ATOM_ON_NTRN)
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "expected amount of")]
+fn test_execute_update_config_validates_completion_config() {
+    let mut builder = NativeSplitterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_owner(owner.as_str())
+        .with_expected_amounts(coins(100_000, DENOM_LS_ATOM_ON_NTRN))
+        .build();
+    let mut splits = suite.query_all_splits();
+    splits.remove(DENOM_LS_ATOM_ON_NTRN);
+
+    suite.update_config(owner, None, None, Some(splits));
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the contract's current owner")]
+fn test_execute_update_config_validates_owner() {
+    let mut builder = NativeSplitterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+
+    suite.update_config(
+        suite.faucet.clone(),
+        Some(suite.faucet.to_string()),
+        None,
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Contract ownership has been renounced")]
+fn test_execute_update_config_without_owner() {
+    let mut suite = NativeSplitterBuilder::default().build();
+
+    // the admin can still migrate, but has no say over the config otherwise
+    suite.update_config(
+        Addr::unchecked(ADMIN),
+        Some(suite.faucet.to_string()),
+        None,
+        None,
+    );
+}
+
+#[test]
+fn test_execute_update_ownership() {
+    let mut builder = NativeSplitterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+    let new_owner = suite.faucet.clone();
+
+    suite.update_ownership(
+        owner,
+        cw_ownable::Action::TransferOwnership {
+            new_owner: new_owner.to_string(),
+            expiry: None,
+        },
+    );
+    assert_eq!(
+        suite.query_ownership().pending_owner,
+        Some(new_owner.clone())
+    );
+
+    suite.update_ownership(new_owner.clone(), cw_ownable::Action::AcceptOwnership);
+    let ownership = suite.query_ownership();
+    assert_eq!(ownership.owner, Some(new_owner.clone()));
+    assert_eq!(ownership.pending_owner, None);
+
+    // the new owner is authorized to update the config
+    suite.update_config(new_owner, Some(suite.faucet.to_string()), None, None);
+    assert_eq!(suite.query_clock_address(), suite.faucet);
+}
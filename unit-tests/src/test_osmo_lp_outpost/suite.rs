@@ -1,9 +1,11 @@
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Coin, Decimal, StdResult, Uint128, Uint64};
 use cw_multi_test::{AppResponse, Executor};
 
 use crate::setup::{
-    base_suite::BaseSuiteMut, instantiates::osmo_lp_outpost::OsmoLpOutpostInstantiate,
-    suite_builder::SuiteBuilder, CustomApp,
+    base_suite::{BaseSuite, BaseSuiteMut},
+    instantiates::osmo_lp_outpost::OsmoLpOutpostInstantiate,
+    suite_builder::SuiteBuilder,
+    CustomApp,
 };
 
 pub struct OsmoLpOutpostBuilder {
@@ -79,6 +81,67 @@ impl Suite {
             )
             .unwrap()
     }
+
+    pub fn swap(
+        &mut self,
+        funds: Vec<Coin>,
+        sender: Addr,
+        pool_id: u64,
+        token_out_denom: &str,
+        min_out: u128,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.outpost.clone(),
+                &valence_outpost_osmo_liquid_pooler::msg::ExecuteMsg::Swap {
+                    pool_id: Uint64::new(pool_id),
+                    token_out_denom: token_out_denom.to_string(),
+                    min_out: Uint128::new(min_out),
+                },
+                &funds,
+            )
+            .unwrap()
+    }
+}
+
+impl Suite {
+    pub fn query_pool_info(
+        &self,
+        pool_id: u64,
+    ) -> valence_outpost_osmo_liquid_pooler::msg::PoolInfoResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.outpost.clone(),
+                &valence_outpost_osmo_liquid_pooler::msg::QueryMsg::PoolInfo {
+                    pool_id: Uint64::new(pool_id),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_expected_shares(&self, pool_id: u64, tokens_in: Vec<Coin>) -> StdResult<Coin> {
+        self.app.wrap().query_wasm_smart(
+            self.outpost.clone(),
+            &valence_outpost_osmo_liquid_pooler::msg::QueryMsg::ExpectedShares {
+                pool_id: Uint64::new(pool_id),
+                tokens_in,
+            },
+        )
+    }
+
+    pub fn query_spot_price(&self, pool_id: u64) -> Decimal {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.outpost.clone(),
+                &valence_outpost_osmo_liquid_pooler::msg::QueryMsg::SpotPrice {
+                    pool_id: Uint64::new(pool_id),
+                },
+            )
+            .unwrap()
+    }
 }
 
 impl BaseSuiteMut for Suite {
@@ -95,3 +158,9 @@ impl BaseSuiteMut for Suite {
         self.faucet.clone()
     }
 }
+
+impl BaseSuite for Suite {
+    fn get_app(&self) -> &CustomApp {
+        &self.app
+    }
+}
@@ -1,12 +1,17 @@
 use std::str::FromStr;
 
-use cosmwasm_std::{coin, coins, Decimal, Uint128, Uint64};
+use cosmwasm_std::{coin, coins, to_json_string, Decimal, Event, Uint128, Uint64};
 use valence_outpost_osmo_liquid_pooler::msg::{
-    OutpostProvideLiquidityConfig, OutpostWithdrawLiquidityConfig,
+    JoinMode, OsmosisPoolKind, OutpostExitPriceGuard, OutpostProvideLiquidityConfig,
+    OutpostWithdrawLiquidityConfig, TickRange,
 };
 
 use crate::{
-    setup::{base_suite::BaseSuiteMut, DENOM_ATOM, DENOM_FALLBACK, DENOM_LS_ATOM_ON_NTRN},
+    setup::{
+        base_suite::{BaseSuite, BaseSuiteMut},
+        custom_keepers::{FAILING_JOIN_POOL_ID, MULTI_ASSET_POOL_ID, WEIGHTED_POOL_ID},
+        DENOM_ATOM, DENOM_FALLBACK, DENOM_LS_ATOM_ON_NTRN,
+    },
     test_osmo_lp_outpost::suite::OsmoLpOutpostBuilder,
 };
 
@@ -23,6 +28,61 @@ fn test_withdraw_liquidity() {
         suite.faucet.clone(),
         OutpostWithdrawLiquidityConfig {
             pool_id: Uint64::new(1),
+            exit_price_guard: None,
+            share_amount: None,
+            min_tokens_out: None,
+            position_id: None,
+            position_share: None,
+        },
+    );
+}
+
+#[test]
+fn test_withdraw_liquidity_within_exit_price_guard() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.fund_contract(&coins(1, DENOM_ATOM), suite.outpost.clone());
+    suite.fund_contract(&coins(1, DENOM_LS_ATOM_ON_NTRN), suite.outpost.clone());
+
+    suite.withdraw_liquidity(
+        coins(1, DENOM_FALLBACK),
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(1),
+            exit_price_guard: Some(OutpostExitPriceGuard {
+                expected_spot_price: Decimal::from_str("1.0").unwrap(),
+                acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            }),
+            share_amount: None,
+            min_tokens_out: None,
+            position_id: None,
+            position_share: None,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Price range error")]
+fn test_withdraw_liquidity_outside_exit_price_guard() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.fund_contract(&coins(1, DENOM_ATOM), suite.outpost.clone());
+    suite.fund_contract(&coins(1, DENOM_LS_ATOM_ON_NTRN), suite.outpost.clone());
+
+    // pool is priced at 1.0, so we expect the exit to revert
+    suite.withdraw_liquidity(
+        coins(1, DENOM_FALLBACK),
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(1),
+            exit_price_guard: Some(OutpostExitPriceGuard {
+                expected_spot_price: Decimal::from_str("1.5").unwrap(),
+                acceptable_price_spread: Decimal::from_str("0.1").unwrap(),
+            }),
+            share_amount: None,
+            min_tokens_out: None,
+            position_id: None,
+            position_share: None,
         },
     );
 }
@@ -44,8 +104,43 @@ fn test_provide_liquidity_double_sided() {
             slippage_tolerance: Decimal::from_str("0.01").unwrap(),
             asset_1_single_side_lp_limit: Uint128::new(100000),
             asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+}
+
+#[test]
+fn test_provide_liquidity_double_sided_exact_amounts_in() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.fund_contract(&coins(1, DENOM_ATOM), suite.outpost.clone());
+    suite.fund_contract(&coins(1, DENOM_LS_ATOM_ON_NTRN), suite.outpost.clone());
+
+    // join messages are no-ops in the test environment, so the
+    // whole double sided provision is left over and joined again
+    let resp = suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(1),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::ExactAmountsIn,
+            tick_range: None,
+            position_id: None,
         },
     );
+
+    assert!(resp.has_event(&Event::new("wasm").add_attribute("method", "try_join_remainder")));
 }
 
 #[test]
@@ -65,6 +160,11 @@ fn test_provide_liquidity_single_sided_asset_a() {
             slippage_tolerance: Decimal::from_str("0.01").unwrap(),
             asset_1_single_side_lp_limit: Uint128::new(100000),
             asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
         },
     );
 }
@@ -86,6 +186,603 @@ fn test_provide_liquidity_single_sided_asset_b() {
             slippage_tolerance: Decimal::from_str("0.01").unwrap(),
             asset_1_single_side_lp_limit: Uint128::new(100000),
             asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "max pool ownership must be within (0, 1]")]
+fn test_provide_liquidity_validates_max_pool_ownership() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(1),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: Some(Decimal::zero()),
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+}
+
+#[test]
+fn test_provide_concentrated_liquidity_creates_position() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    // position creation is mocked, so all of the funds are refunded
+    // along with the transferred position
+    let resp = suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(2),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: Some(TickRange {
+                lower_tick: -100,
+                upper_tick: 100,
+            }),
+            position_id: None,
+        },
+    );
+
+    resp.assert_event(&Event::new("wasm").add_attribute("position_id", "1"));
+}
+
+#[test]
+fn test_provide_concentrated_liquidity_adds_to_position() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    let resp = suite.provide_liquidity(
+        coins(1, DENOM_ATOM),
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(2),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: Some(Uint64::new(1)),
+        },
+    );
+
+    resp.assert_event(&Event::new("wasm").add_attribute("position_id", "2"));
+}
+
+#[test]
+#[should_panic(expected = "concentrated liquidity pools require a tick range")]
+fn test_provide_concentrated_liquidity_requires_tick_range() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(2),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "lower tick must be below the upper tick")]
+fn test_provide_concentrated_liquidity_validates_tick_range() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(2),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: Some(TickRange {
+                lower_tick: 100,
+                upper_tick: 100,
+            }),
+            position_id: None,
+        },
+    );
+}
+
+#[test]
+fn test_provide_liquidity_stableswap_double_sided() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    let resp = suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(3),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "try_join_pool"));
+}
+
+#[test]
+fn test_provide_liquidity_stableswap_validates_spot_price() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    // stableswap pool is priced at 1.0, so the funds are refunded
+    let resp = suite.provide_liquidity(
+        coins(1, DENOM_ATOM),
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(3),
+            expected_spot_price: Decimal::from_str("1.5").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.1").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+
+    resp.assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "refund_out_of_range")
+            .add_attribute("pool_spot_price", "1"),
+    );
+}
+
+#[test]
+fn test_provide_liquidity_weighted_pool_double_sided() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    // 80:20 pool with equal reserves is priced at 0.25
+    let resp = suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(WEIGHTED_POOL_ID),
+            expected_spot_price: Decimal::from_str("0.25").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "try_join_pool"));
+}
+
+#[test]
+fn test_provide_liquidity_weighted_pool_validates_spot_price() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    // reserve ratio alone would price the pool at 1.0
+    let resp = suite.provide_liquidity(
+        coins(1, DENOM_ATOM),
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(WEIGHTED_POOL_ID),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.1").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+
+    resp.assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "refund_out_of_range")
+            .add_attribute("pool_spot_price", "0.25"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "pools of three or more assets are not supported")]
+fn test_provide_liquidity_rejects_multi_asset_pool() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        suite.faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(MULTI_ASSET_POOL_ID),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.1").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+}
+
+#[test]
+fn test_withdraw_liquidity_stableswap() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.withdraw_liquidity(
+        coins(1, DENOM_FALLBACK),
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(3),
+            exit_price_guard: Some(OutpostExitPriceGuard {
+                expected_spot_price: Decimal::from_str("1.0").unwrap(),
+                acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            }),
+            share_amount: None,
+            min_tokens_out: None,
+            position_id: None,
+            position_share: None,
+        },
+    );
+}
+
+#[test]
+fn test_withdraw_liquidity_share_amount() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    // only part of the paid shares is redeemed, the rest is refunded
+    suite.withdraw_liquidity(
+        coins(2, DENOM_FALLBACK),
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(1),
+            exit_price_guard: None,
+            share_amount: Some(Uint128::one()),
+            min_tokens_out: Some(vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)]),
+            position_id: None,
+            position_share: None,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "share amount must be within (0, 1], got 2")]
+fn test_withdraw_liquidity_validates_share_amount() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.withdraw_liquidity(
+        coins(1, DENOM_FALLBACK),
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(1),
+            exit_price_guard: None,
+            share_amount: Some(Uint128::new(2)),
+            min_tokens_out: None,
+            position_id: None,
+            position_share: None,
+        },
+    );
+}
+
+#[test]
+fn test_withdraw_concentrated_liquidity() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    // the position is withdrawn in full, so it is not transferred back
+    let resp = suite.withdraw_liquidity(
+        vec![],
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(2),
+            exit_price_guard: Some(OutpostExitPriceGuard {
+                expected_spot_price: Decimal::from_str("1.0").unwrap(),
+                acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            }),
+            share_amount: None,
+            min_tokens_out: None,
+            position_id: Some(Uint64::new(1)),
+            position_share: None,
+        },
+    );
+
+    resp.assert_event(&Event::new("wasm").add_attribute(
+        "refund_tokens",
+        to_json_string(&vec![coin(10, DENOM_ATOM), coin(10, DENOM_LS_ATOM_ON_NTRN)]).unwrap(),
+    ));
+    assert!(!resp
+        .events
+        .iter()
+        .any(|e| e.attributes.iter().any(|a| a.key == "position_id")));
+}
+
+#[test]
+fn test_withdraw_concentrated_liquidity_partially() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    // the remaining position is transferred back to the sender
+    let resp = suite.withdraw_liquidity(
+        vec![],
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(2),
+            exit_price_guard: None,
+            share_amount: None,
+            min_tokens_out: None,
+            position_id: Some(Uint64::new(1)),
+            position_share: Some(Decimal::percent(50)),
+        },
+    );
+
+    resp.assert_event(&Event::new("wasm").add_attribute("position_id", "1"));
+}
+
+#[test]
+#[should_panic(expected = "Price range error")]
+fn test_withdraw_concentrated_liquidity_outside_exit_price_guard() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.withdraw_liquidity(
+        vec![],
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(2),
+            exit_price_guard: Some(OutpostExitPriceGuard {
+                expected_spot_price: Decimal::from_str("1.5").unwrap(),
+                acceptable_price_spread: Decimal::from_str("0.1").unwrap(),
+            }),
+            share_amount: None,
+            min_tokens_out: None,
+            position_id: Some(Uint64::new(1)),
+            position_share: None,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "concentrated liquidity pools require a position id")]
+fn test_withdraw_concentrated_liquidity_requires_position_id() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.withdraw_liquidity(
+        vec![],
+        suite.faucet.clone(),
+        OutpostWithdrawLiquidityConfig {
+            pool_id: Uint64::new(2),
+            exit_price_guard: None,
+            share_amount: None,
+            min_tokens_out: None,
+            position_id: None,
+            position_share: None,
+        },
+    );
+}
+
+#[test]
+fn test_query_pool_info() {
+    let suite = OsmoLpOutpostBuilder::default().build();
+
+    let pool_info = suite.query_pool_info(1);
+    assert_eq!(pool_info.pool_type, OsmosisPoolKind::Balancer);
+    assert_eq!(
+        pool_info.assets,
+        vec![coin(100, DENOM_ATOM), coin(100, DENOM_LS_ATOM_ON_NTRN)]
+    );
+    assert_eq!(pool_info.total_shares, Some(coin(101010, DENOM_FALLBACK)));
+    assert_eq!(pool_info.spot_price, Decimal::one());
+
+    let pool_info = suite.query_pool_info(2);
+    assert_eq!(pool_info.pool_type, OsmosisPoolKind::Concentrated);
+    assert_eq!(
+        pool_info.assets,
+        vec![coin(100, DENOM_ATOM), coin(100, DENOM_LS_ATOM_ON_NTRN)]
+    );
+    assert_eq!(pool_info.total_shares, None);
+
+    let pool_info = suite.query_pool_info(3);
+    assert_eq!(pool_info.pool_type, OsmosisPoolKind::Stableswap);
+}
+
+#[test]
+fn test_query_spot_price() {
+    let suite = OsmoLpOutpostBuilder::default().build();
+
+    assert_eq!(suite.query_spot_price(1), Decimal::one());
+    assert_eq!(suite.query_spot_price(2), Decimal::one());
+    assert_eq!(suite.query_spot_price(3), Decimal::one());
+    assert_eq!(
+        suite.query_spot_price(WEIGHTED_POOL_ID),
+        Decimal::from_str("0.25").unwrap()
+    );
+}
+
+#[test]
+fn test_query_expected_shares() {
+    let suite = OsmoLpOutpostBuilder::default().build();
+
+    let expected_shares = suite
+        .query_expected_shares(1, vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)])
+        .unwrap();
+    assert_eq!(expected_shares, coin(1, DENOM_FALLBACK));
+
+    let expected_shares = suite
+        .query_expected_shares(1, vec![coin(1, DENOM_ATOM)])
+        .unwrap();
+    assert_eq!(expected_shares, coin(1, DENOM_FALLBACK));
+
+    // concentrated liquidity pools do not issue shares
+    let err = suite
+        .query_expected_shares(2, vec![coin(1, DENOM_ATOM)])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("concentrated liquidity pools do not issue shares"));
+}
+
+#[test]
+fn test_provide_liquidity_out_of_range_refunds_funds() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+    let faucet = suite.faucet.clone();
+    let atom_balance = suite.query_balance(&faucet, DENOM_ATOM);
+
+    // pool is priced at 1.0, so the funds are returned to the sender
+    let resp = suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(1),
+            expected_spot_price: Decimal::from_str("1.5").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.1").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
+        },
+    );
+
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "refund_out_of_range"));
+    assert_eq!(suite.query_balance(&faucet, DENOM_ATOM), atom_balance);
+}
+
+#[test]
+fn test_provide_liquidity_failure_refunds_funds() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+    let faucet = suite.faucet.clone();
+    let atom_balance = suite.query_balance(&faucet, DENOM_ATOM);
+
+    let resp = suite.provide_liquidity(
+        vec![coin(1, DENOM_ATOM), coin(1, DENOM_LS_ATOM_ON_NTRN)],
+        faucet.clone(),
+        OutpostProvideLiquidityConfig {
+            pool_id: Uint64::new(FAILING_JOIN_POOL_ID),
+            expected_spot_price: Decimal::from_str("1.0").unwrap(),
+            acceptable_price_spread: Decimal::from_str("0.01").unwrap(),
+            slippage_tolerance: Decimal::from_str("0.01").unwrap(),
+            asset_1_single_side_lp_limit: Uint128::new(100000),
+            asset_2_single_side_lp_limit: Uint128::new(100000),
+            single_side_lp_pool_reserve_share: None,
+            max_pool_ownership: None,
+            join_mode: JoinMode::MinRatio,
+            tick_range: None,
+            position_id: None,
         },
     );
+
+    resp.assert_event(&Event::new("wasm-refund").add_attribute("recipient", faucet.to_string()));
+    assert_eq!(suite.query_balance(&faucet, DENOM_ATOM), atom_balance);
+}
+
+#[test]
+fn test_swap() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+    let faucet = suite.faucet.clone();
+    let ls_balance_before = suite.query_balance(&faucet, DENOM_LS_ATOM_ON_NTRN).amount;
+
+    let resp = suite.swap(
+        coins(10, DENOM_ATOM),
+        faucet.clone(),
+        1,
+        DENOM_LS_ATOM_ON_NTRN,
+        10,
+    );
+
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "try_swap"));
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "handle_swap_reply"));
+    let ls_balance_after = suite.query_balance(&faucet, DENOM_LS_ATOM_ON_NTRN).amount;
+    assert_eq!(ls_balance_after - ls_balance_before, Uint128::new(10));
+    assert!(suite.query_all_balances(&suite.outpost).is_empty());
+}
+
+#[test]
+fn test_swap_failure_refunds_funds() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+    let faucet = suite.faucet.clone();
+    let atom_balance_before = suite.query_balance(&faucet, DENOM_ATOM).amount;
+
+    // the minimum out cannot be met, so the swap fails
+    let resp = suite.swap(
+        coins(10, DENOM_ATOM),
+        faucet.clone(),
+        1,
+        DENOM_LS_ATOM_ON_NTRN,
+        11,
+    );
+
+    resp.assert_event(&Event::new("wasm-refund").add_attribute("recipient", faucet.to_string()));
+    assert_eq!(
+        suite.query_balance(&faucet, DENOM_ATOM).amount,
+        atom_balance_before
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot swap uatom for itself")]
+fn test_swap_validates_token_out_denom() {
+    let mut suite = OsmoLpOutpostBuilder::default().build();
+
+    suite.swap(
+        coins(10, DENOM_ATOM),
+        suite.faucet.clone(),
+        1,
+        DENOM_ATOM,
+        10,
+    );
 }
@@ -7,6 +7,7 @@ use covenant_utils::{
     split::SplitConfig,
 };
 use cw_multi_test::{AppResponse, Executor};
+use valence_remote_chain_splitter::msg::FallbackAddressUpdateConfig;
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
@@ -41,6 +42,7 @@ impl Default for RemoteChainSplitterBuilder {
                 forwarder_a_addr.to_string(),
                 forwarder_b_addr.to_string(),
             ],
+            tick_intervals: vec![],
         };
         builder.contract_init2(
             builder.clock_code_id,
@@ -59,6 +61,8 @@ impl Default for RemoteChainSplitterBuilder {
             ibc_transfer_timeout: Uint64::new(100),
             ica_timeout: Uint64::new(100),
             fallback_address: None,
+            milestone_callback: None,
+            ibc_fee_override: None,
         };
 
         builder.contract_init2(
@@ -131,6 +135,21 @@ impl RemoteChainSplitterBuilder {
         self
     }
 
+    pub fn with_emergency_committee(mut self, addr: &str) -> Self {
+        self.instantiate_msg.with_emergency_committee(addr);
+        self
+    }
+
+    pub fn with_owner(mut self, addr: &str) -> Self {
+        self.instantiate_msg.with_owner(addr);
+        self
+    }
+
+    pub fn with_op_admin(mut self, addr: &str) -> Self {
+        self.instantiate_msg.with_op_admin(addr);
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let remote_chain_splitter_address = self.builder.contract_init2(
             self.builder.remote_splitter_code_id,
@@ -301,6 +320,53 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn update_splits(
+        &mut self,
+        sender: Addr,
+        splits: BTreeMap<String, SplitConfig>,
+        fallback: Option<FallbackAddressUpdateConfig>,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.splitter.clone(),
+                &valence_remote_chain_splitter::msg::ExecuteMsg::UpdateSplits { splits, fallback },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_op_admin(&self) -> Option<Addr> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter.clone(),
+                &valence_remote_chain_splitter::msg::QueryMsg::OpAdmin {},
+            )
+            .unwrap()
+    }
+
+    pub fn fix_split_receiver(
+        &mut self,
+        sender: Addr,
+        denom: &str,
+        old: &str,
+        new: &str,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.splitter.clone(),
+                &valence_remote_chain_splitter::msg::ExecuteMsg::FixSplitReceiver {
+                    denom: denom.to_string(),
+                    old: old.to_string(),
+                    new: new.to_string(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
     pub fn query_ica_address(&mut self, addr: Addr) -> Addr {
         self.app
             .wrap()
@@ -1,9 +1,10 @@
 use std::{collections::BTreeMap, str::FromStr};
 
-use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+use cosmwasm_std::{coin, coins, Addr, Decimal, Event, Uint128};
 use covenant_utils::{
     op_mode::{ContractOperationMode, ContractOperationModeConfig},
     split::SplitConfig,
+    split_receiver_fix::SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS,
 };
 use cw_multi_test::Executor;
 use valence_remote_chain_splitter::msg::FallbackAddressUpdateConfig;
@@ -60,6 +61,8 @@ fn test_instantiate_validates_explicit_split_shares() {
         denom,
         SplitConfig {
             receivers: invalid_split_config,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
         },
     );
     builder.build();
@@ -76,6 +79,8 @@ fn test_instantiate_validates_explicit_split_receiver_addresses() {
         DENOM_ATOM_ON_NTRN.to_string(),
         SplitConfig {
             receivers: split_config,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
         },
     );
 
@@ -240,6 +245,113 @@ fn test_execute_tick_splits_with_no_leftover() {
     suite.assert_balance(&splitter_ica, zero_bal.clone());
 }
 
+#[test]
+fn test_execute_fix_split_receiver() {
+    let mut builder = RemoteChainSplitterBuilder::default();
+    let committee = builder.builder.get_random_addr();
+    let mut suite = builder.with_emergency_committee(committee.as_str()).build();
+    let old_receiver = suite.receiver_1.to_string();
+    let new_receiver = suite.app.api().addr_make("new_receiver").to_string();
+
+    suite.fix_split_receiver(
+        committee.clone(),
+        DENOM_ATOM_ON_NTRN,
+        &old_receiver,
+        &new_receiver,
+    );
+    // the split remains unchanged until the timelock expires
+    assert!(suite.query_split_config()[DENOM_ATOM_ON_NTRN]
+        .receivers
+        .contains_key(&old_receiver));
+
+    suite
+        .app
+        .update_block(|b| b.time = b.time.plus_seconds(SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS));
+    suite.fix_split_receiver(committee, DENOM_ATOM_ON_NTRN, &old_receiver, &new_receiver);
+
+    let receivers = &suite.query_split_config()[DENOM_ATOM_ON_NTRN].receivers;
+    assert!(receivers.contains_key(&new_receiver));
+    assert!(!receivers.contains_key(&old_receiver));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized caller")]
+fn test_execute_fix_split_receiver_validates_committee() {
+    let mut suite = RemoteChainSplitterBuilder::default().build();
+    let old_receiver = suite.receiver_1.to_string();
+    let new_receiver = suite.app.api().addr_make("new_receiver").to_string();
+
+    suite.fix_split_receiver(
+        suite.faucet.clone(),
+        DENOM_ATOM_ON_NTRN,
+        &old_receiver,
+        &new_receiver,
+    );
+}
+
+#[test]
+fn test_execute_update_splits() {
+    let mut builder = RemoteChainSplitterBuilder::default();
+    let op_admin = builder.builder.get_random_addr();
+    let mut suite = builder.with_op_admin(op_admin.as_str()).build();
+    assert_eq!(suite.query_op_admin(), Some(op_admin.clone()));
+
+    let mut splits = suite.query_split_config();
+    let split = splits.get_mut(DENOM_ATOM_ON_NTRN).unwrap();
+    split.receivers.insert(
+        suite.receiver_1.to_string(),
+        Decimal::from_str("0.1").unwrap(),
+    );
+    split.receivers.insert(
+        suite.receiver_2.to_string(),
+        Decimal::from_str("0.9").unwrap(),
+    );
+
+    suite
+        .update_splits(
+            op_admin.clone(),
+            splits.clone(),
+            Some(FallbackAddressUpdateConfig::ExplicitAddress(
+                suite.faucet.to_string(),
+            )),
+        )
+        .assert_event(
+            &Event::new("wasm-splits_updated")
+                .add_attribute("op_admin", op_admin)
+                .add_attribute("fallback_address", suite.faucet.to_string()),
+        );
+
+    assert_eq!(suite.query_split_config(), splits);
+    assert_eq!(
+        suite.query_fallback_address(),
+        Some(suite.faucet.to_string())
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized caller")]
+fn test_execute_update_splits_validates_op_admin() {
+    let mut builder = RemoteChainSplitterBuilder::default();
+    let op_admin = builder.builder.get_random_addr();
+    let mut suite = builder.with_op_admin(op_admin.as_str()).build();
+    let splits = suite.query_split_config();
+
+    suite.update_splits(suite.faucet.clone(), splits, None);
+}
+
+#[test]
+#[should_panic(expected = "missing split configuration for denom")]
+fn test_execute_update_splits_validates_transfer_denom() {
+    let mut builder = RemoteChainSplitterBuilder::default();
+    let op_admin = builder.builder.get_random_addr();
+    let mut suite = builder.with_op_admin(op_admin.as_str()).build();
+    let mut splits = suite.query_split_config();
+    let split = splits.remove(DENOM_ATOM_ON_NTRN).unwrap();
+    splits.insert(DENOM_LS_ATOM_ON_NTRN.to_string(), split);
+
+    suite.update_splits(op_admin, splits, None);
+}
+
 #[test]
 fn test_migrate_update_config() {
     let mut suite = RemoteChainSplitterBuilder::default().build();
@@ -556,3 +668,52 @@ fn test_distribute_fallback_happy() {
         coin(100_000, DENOM_OSMO_ON_HUB_FROM_NTRN),
     );
 }
+
+#[test]
+fn test_execute_update_config() {
+    let mut builder = RemoteChainSplitterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+
+    suite
+        .app
+        .execute_contract(
+            owner,
+            suite.splitter.clone(),
+            &valence_remote_chain_splitter::msg::ExecuteMsg::UpdateConfig {
+                op_mode: None,
+                remote_chain_info: None,
+                splits: None,
+                fallback_address: Some(FallbackAddressUpdateConfig::ExplicitAddress(
+                    suite.faucet.to_string(),
+                )),
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_fallback_address().unwrap(), suite.faucet);
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the contract's current owner")]
+fn test_execute_update_config_validates_owner() {
+    let mut builder = RemoteChainSplitterBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+
+    suite
+        .app
+        .execute_contract(
+            suite.faucet.clone(),
+            suite.splitter.clone(),
+            &valence_remote_chain_splitter::msg::ExecuteMsg::UpdateConfig {
+                op_mode: None,
+                remote_chain_info: None,
+                splits: None,
+                fallback_address: Some(FallbackAddressUpdateConfig::Disable {}),
+            },
+            &[],
+        )
+        .unwrap();
+}
@@ -13,7 +13,7 @@ use crate::setup::{
 };
 
 #[allow(dead_code)]
-pub(super) struct Suite {
+pub(crate) struct Suite {
     pub fuacet: Addr,
     pub admin: Addr,
 
@@ -23,7 +23,8 @@ pub(super) struct Suite {
     pub clock_addr: Addr,
     pub holder_addr: Addr,
     pub splitter_addr: Addr,
-    pub lser_addr: Addr,
+    /// not instantiated for pre-staked contributions
+    pub lser_addr: Option<Addr>,
     pub lper_addr: Addr,
     pub ls_forwarder_addr: Addr,
     pub lp_forwarder_addr: Addr,
@@ -110,12 +111,14 @@ impl Suite {
         let lser_addr = builder
             .app
             .wrap()
-            .query_wasm_smart::<Addr>(
+            .query_wasm_smart::<Option<Addr>>(
                 covenant_addr.clone(),
                 &valence_covenant_single_party_pol::msg::QueryMsg::LiquidStakerAddress {},
             )
             .unwrap();
-        builder.fund_with_ntrn(&lser_addr, 2_000_000_u128);
+        if let Some(lser_addr) = &lser_addr {
+            builder.fund_with_ntrn(lser_addr, 2_000_000_u128);
+        }
 
         let ls_forwarder_addr = builder
             .app
@@ -178,6 +181,16 @@ impl Suite {
 
 impl Suite {
     pub fn new_with_stable_pool() -> Self {
+        Self::new_with_stable_pool_contribution(false)
+    }
+
+    /// the party contributes the ls denom it already holds
+    /// instead of having it liquid staked by the covenant
+    pub fn new_with_pre_staked_contribution() -> Self {
+        Self::new_with_stable_pool_contribution(true)
+    }
+
+    fn new_with_stable_pool_contribution(pre_staked_contribution: bool) -> Self {
         let mut builder = SuiteBuilder::new();
 
         let covenant_addr = builder.get_contract_addr(
@@ -204,6 +217,7 @@ impl Suite {
                 local_to_hop_chain_channel_id: NTRN_STRIDE_CHANNEL.0.to_string(),
                 hop_to_destination_chain_channel_id: HUB_STRIDE_CHANNEL.1.to_string(),
                 hop_chain_receiver_address: ls_receiver.to_string(),
+                next_hops: vec![],
             },
         );
 
@@ -226,17 +240,31 @@ impl Suite {
             covenant_utils::SingleSideLpLimits {
                 asset_a_limit: 500_000_000_u128.into(),
                 asset_b_limit: 500_000_000_u128.into(),
+                pool_reserve_share: None,
             },
         );
-        let ls_forwarder_config = SinglePartyCovenantInstantiate::get_forwarder_config_interchain(
-            &ls_receiver,
-            &ls_receiver_on_ntrn,
-            DENOM_ATOM,
-            DENOM_LS_ATOM_ON_STRIDE,
-            HUB_STRIDE_CHANNEL.1,
-            HUB_STRIDE_CHANNEL.0,
-            500_000_000_000_u128,
-        );
+        let ls_forwarder_config = if pre_staked_contribution {
+            // the ls denom held on stride is forwarded to the liquid pooler
+            SinglePartyCovenantInstantiate::get_forwarder_config_interchain(
+                &ls_receiver,
+                &ls_receiver_on_ntrn,
+                DENOM_LS_ATOM_ON_STRIDE,
+                DENOM_LS_ATOM_ON_NTRN,
+                NTRN_STRIDE_CHANNEL.0,
+                NTRN_STRIDE_CHANNEL.1,
+                500_000_000_000_u128,
+            )
+        } else {
+            SinglePartyCovenantInstantiate::get_forwarder_config_interchain(
+                &ls_receiver,
+                &ls_receiver_on_ntrn,
+                DENOM_ATOM,
+                DENOM_LS_ATOM_ON_STRIDE,
+                HUB_STRIDE_CHANNEL.1,
+                HUB_STRIDE_CHANNEL.0,
+                500_000_000_000_u128,
+            )
+        };
         let lp_forwarder_config = SinglePartyCovenantInstantiate::get_forwarder_config_interchain(
             &Addr::unchecked("not_used"),
             &Addr::unchecked("not_used"),
@@ -249,7 +277,11 @@ impl Suite {
         let remote_splitter = SinglePartyCovenantInstantiate::get_remote_splitter_config(
             NTRN_HUB_CHANNEL.0,
             DENOM_ATOM,
-            1_000_000_000_000_u128,
+            if pre_staked_contribution {
+                500_000_000_000_u128
+            } else {
+                1_000_000_000_000_u128
+            },
             Decimal::bps(5000),
             Decimal::bps(5000),
         );
@@ -257,7 +289,7 @@ impl Suite {
             Decimal::from_str("1").unwrap(),
             Decimal::bps(5000),
         );
-        let init_msg = SinglePartyCovenantInstantiate::default(
+        let mut init_msg = SinglePartyCovenantInstantiate::default(
             &builder,
             ls_forwarder_config,
             lp_forwarder_config,
@@ -267,6 +299,8 @@ impl Suite {
             pool_price_config,
         );
 
+        init_msg.with_pre_staked_contribution(pre_staked_contribution);
+
         builder.contract_init2(
             builder.single_party_covenant_code_id,
             SINGLE_PARTY_COVENANT_SALT,
@@ -312,6 +346,7 @@ impl Suite {
                 local_to_hop_chain_channel_id: NTRN_STRIDE_CHANNEL.0.to_string(),
                 hop_to_destination_chain_channel_id: HUB_STRIDE_CHANNEL.1.to_string(),
                 hop_chain_receiver_address: ls_receiver.to_string(),
+                next_hops: vec![],
             },
         );
 
@@ -334,6 +369,7 @@ impl Suite {
             covenant_utils::SingleSideLpLimits {
                 asset_a_limit: 10_000_000_u128.into(),
                 asset_b_limit: 10_000_000_u128.into(),
+                pool_reserve_share: None,
             },
         );
         let ls_forwarder_config = SinglePartyCovenantInstantiate::get_forwarder_config_interchain(
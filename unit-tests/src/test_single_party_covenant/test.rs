@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, to_json_binary, Addr, Event, Uint128, Uint64};
+use cosmwasm_std::{coin, to_json_binary, Addr, Decimal, Event, Uint128, Uint64};
 use covenant_utils::{neutron::RemoteChainInfo, op_mode::ContractOperationModeConfig};
 use cw_multi_test::{AppResponse, Executor};
 
@@ -16,7 +16,7 @@ fn test_covenant() {
         .app
         .execute_contract(
             suite.admin.clone(),
-            suite.lser_addr.clone(),
+            suite.lser_addr.clone().unwrap(),
             &valence_stride_liquid_staker::msg::ExecuteMsg::Transfer {
                 amount: 500_000_000_000_u128.into(),
             },
@@ -71,7 +71,7 @@ fn test_covenant() {
     assert_eq!(ls_forwarder_ica_balance.amount.u128(), 500_000_000_000_u128);
 
     // Wait for forwarders to forward the funds to the correct addrs
-    let lser_ica = suite.get_ica(suite.lser_addr.clone());
+    let lser_ica = suite.get_ica(suite.lser_addr.clone().unwrap());
 
     // lser_ica should get his half on stride (lsAtom on stride)
     while suite
@@ -116,7 +116,7 @@ fn test_covenant() {
         .app
         .execute_contract(
             suite.admin.clone(),
-            suite.lser_addr.clone(),
+            suite.lser_addr.clone().unwrap(),
             &valence_stride_liquid_staker::msg::ExecuteMsg::Transfer {
                 amount: 500_000_000_000_u128.into(),
             },
@@ -265,7 +265,7 @@ fn test_covenant_with_xyk_pool() {
     assert_eq!(ls_forwarder_ica_balance.amount.u128(), 500_000_000_000_u128);
 
     // Wait for forwarders to forward the funds to the correct addrs
-    let lser_ica = suite.get_ica(suite.lser_addr.clone());
+    let lser_ica = suite.get_ica(suite.lser_addr.clone().unwrap());
 
     // lser_ica should get his half on stride (lsAtom on stride)
     while suite
@@ -310,7 +310,7 @@ fn test_covenant_with_xyk_pool() {
         .app
         .execute_contract(
             suite.admin.clone(),
-            suite.lser_addr.clone(),
+            suite.lser_addr.clone().unwrap(),
             &valence_stride_liquid_staker::msg::ExecuteMsg::Transfer {
                 amount: 500_000_000_000_u128.into(),
             },
@@ -461,7 +461,7 @@ fn test_covenant_with_uneven_pool() {
     assert_eq!(ls_forwarder_ica_balance.amount.u128(), 500_000_000_000_u128);
 
     // Wait for forwarders to forward the funds to the correct addrs
-    let lser_ica = suite.get_ica(suite.lser_addr.clone());
+    let lser_ica = suite.get_ica(suite.lser_addr.clone().unwrap());
 
     // lser_ica should get his half on stride (lsAtom on stride)
     while suite
@@ -506,7 +506,7 @@ fn test_covenant_with_uneven_pool() {
         .app
         .execute_contract(
             suite.admin.clone(),
-            suite.lser_addr.clone(),
+            suite.lser_addr.clone().unwrap(),
             &valence_stride_liquid_staker::msg::ExecuteMsg::Transfer {
                 amount: 500_000_000_000_u128.into(),
             },
@@ -657,7 +657,7 @@ fn test_covenant_with_uneven_pool_stable() {
     assert_eq!(ls_forwarder_ica_balance.amount.u128(), 500_000_000_000_u128);
 
     // Wait for forwarders to forward the funds to the correct addrs
-    let lser_ica = suite.get_ica(suite.lser_addr.clone());
+    let lser_ica = suite.get_ica(suite.lser_addr.clone().unwrap());
 
     // lser_ica should get his half on stride (lsAtom on stride)
     while suite
@@ -702,7 +702,7 @@ fn test_covenant_with_uneven_pool_stable() {
         .app
         .execute_contract(
             suite.admin.clone(),
-            suite.lser_addr.clone(),
+            suite.lser_addr.clone().unwrap(),
             &valence_stride_liquid_staker::msg::ExecuteMsg::Transfer {
                 amount: 500_000_000_000_u128.into(),
             },
@@ -861,7 +861,7 @@ fn test_covenant_with_single_sided() {
     assert_eq!(ls_forwarder_ica_balance.amount.u128(), 500_000_000_000_u128);
 
     // Wait for forwarders to forward the funds to the correct addrs
-    let lser_ica = suite.get_ica(suite.lser_addr.clone());
+    let lser_ica = suite.get_ica(suite.lser_addr.clone().unwrap());
 
     // lser_ica should get his half on stride (lsAtom on stride)
     while suite
@@ -906,7 +906,7 @@ fn test_covenant_with_single_sided() {
         .app
         .execute_contract(
             suite.admin.clone(),
-            suite.lser_addr.clone(),
+            suite.lser_addr.clone().unwrap(),
             &valence_stride_liquid_staker::msg::ExecuteMsg::Transfer {
                 amount: 500_000_000_000_u128.into(),
             },
@@ -1175,6 +1175,8 @@ fn test_migrate_update_config_no_codes() {
         clock_addr: Some(covenant_addr.to_string()),
         destination_config: None,
         target_denoms: None,
+        denom_to_destination_map: None,
+        holder_address: None,
     };
     let resp = suite
         .app
@@ -1208,3 +1210,44 @@ fn test_migrate_update_config_no_codes() {
             ),
     );
 }
+
+#[test]
+fn test_covenant_with_pre_staked_contribution() {
+    let mut suite = Suite::new_with_pre_staked_contribution();
+    assert!(suite.lser_addr.is_none());
+
+    // the whole native contribution is split to the lp forwarder
+    let split_config: Vec<(String, covenant_utils::split::SplitConfig)> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.splitter_addr.clone(),
+            &valence_remote_chain_splitter::msg::QueryMsg::SplitConfig {},
+        )
+        .unwrap();
+    assert_eq!(split_config.len(), 1);
+    assert_eq!(
+        split_config[0].1.receivers,
+        [(suite.lp_forwarder_addr.to_string(), Decimal::one())].into()
+    );
+
+    suite.get_and_fund_depositors(coin(500_000_000_000_u128, DENOM_ATOM));
+
+    let lp_forwarder_ica = suite.get_ica(suite.lp_forwarder_addr.clone());
+    while suite
+        .app
+        .wrap()
+        .query_all_balances(lp_forwarder_ica.clone())
+        .unwrap()
+        .is_empty()
+    {
+        suite.tick("Wait for lp_forwarder ICA to get its split");
+    }
+
+    let lp_forwarder_ica_balance = suite
+        .app
+        .wrap()
+        .query_balance(lp_forwarder_ica, DENOM_ATOM)
+        .unwrap();
+    assert_eq!(lp_forwarder_ica_balance.amount.u128(), 500_000_000_000_u128);
+}
@@ -3,6 +3,7 @@ use cosmwasm_std::{coin, Addr, Coin, Decimal, Uint128};
 use covenant_utils::{op_mode::ContractOperationModeConfig, PoolPriceConfig, SingleSideLpLimits};
 use cw_multi_test::{AppResponse, Executor};
 use cw_utils::Expiration;
+use valence_single_party_pol_holder::msg::DashboardResponse;
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
@@ -41,6 +42,7 @@ impl Default for SinglePartyHolderBuilder {
             tick_max_gas: None,
             whitelist: vec![liquid_pooler_addr.to_string()],
             initial_queue: vec![],
+            tick_intervals: vec![],
         };
         builder.contract_init2(
             builder.clock_code_id,
@@ -56,10 +58,12 @@ impl Default for SinglePartyHolderBuilder {
             assets: valence_astroport_liquid_pooler::msg::AssetData {
                 asset_a_denom: DENOM_ATOM_ON_NTRN.to_string(),
                 asset_b_denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+                additional_assets: vec![],
             },
             single_side_lp_limits: SingleSideLpLimits {
                 asset_a_limit: Uint128::new(100000),
                 asset_b_limit: Uint128::new(100000),
+                pool_reserve_share: None,
             },
             pool_price_config: PoolPriceConfig {
                 expected_spot_price: Decimal::one(),
@@ -67,6 +71,16 @@ impl Default for SinglePartyHolderBuilder {
             },
             pair_type: PairType::Stable {},
             holder_address: holder_addr.to_string(),
+            pair_type_migration_policy: None,
+            emergency_committee: None,
+            oracle_price_guard: None,
+            max_pool_ownership: None,
+            generator_address: None,
+            twap_window: None,
+            single_side_rebalance: None,
+            min_lp_out_tolerance: None,
+            max_provision_per_tick: None,
+            funding_deadline: None,
         };
 
         builder.contract_init2(
@@ -313,6 +327,28 @@ impl Suite {
             )
             .unwrap()
     }
+
+    pub fn query_dashboard(&mut self) -> DashboardResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_single_party_pol_holder::msg::QueryMsg::Dashboard {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_claim_preview(&mut self, party: &Addr) -> Vec<Coin> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_single_party_pol_holder::msg::QueryMsg::ClaimPreview {
+                    party: party.to_string(),
+                },
+            )
+            .unwrap()
+    }
 }
 
 impl BaseSuiteMut for Suite {
@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, Addr, Event, Storage};
+use cosmwasm_std::{coin, Addr, Event, Storage, Uint128};
 use cw_multi_test::Executor;
 use cw_utils::Expiration;
 
@@ -257,3 +257,46 @@ fn test_migrate_update_config() {
     assert_eq!(clock, pooler_address);
     assert_eq!(Expiration::AtHeight(192837465), lockup_period);
 }
+
+#[test]
+fn test_query_dashboard() {
+    let mut suite = SinglePartyHolderBuilder::default().build();
+
+    let dashboard = suite.query_dashboard();
+    assert_eq!(dashboard.pooler_address, suite.liquid_pooler_address);
+    assert!(!dashboard.withdraw_in_progress);
+    assert!(!dashboard.lockup.expired);
+    assert_eq!(dashboard.pool_position.unwrap().lp_balance, Uint128::zero());
+    assert!(dashboard.claimable.is_empty());
+
+    suite.enter_pool();
+
+    let dashboard = suite.query_dashboard();
+    assert!(!dashboard.pool_position.unwrap().lp_balance.is_zero());
+    assert_eq!(dashboard.claimable.len(), 2);
+    for coin in dashboard.claimable {
+        assert!(!coin.amount.is_zero());
+        assert!(coin.amount <= Uint128::new(1_000_000));
+    }
+}
+
+#[test]
+fn test_query_claim_preview() {
+    let mut suite = SinglePartyHolderBuilder::default().build();
+    let withdrawer = suite.liquid_pooler_address.clone();
+
+    suite.enter_pool();
+    assert!(suite.query_claim_preview(&withdrawer).is_empty());
+
+    suite.expire_lockup();
+    assert!(suite.query_claim_preview(&suite.faucet.clone()).is_empty());
+
+    let preview = suite.query_claim_preview(&withdrawer);
+    assert_eq!(preview.len(), 2);
+
+    suite.execute_claim(withdrawer.clone());
+    for coin in preview {
+        assert!(!coin.amount.is_zero());
+        suite.assert_balance(&withdrawer, coin);
+    }
+}
@@ -1,7 +1,8 @@
 use std::vec;
 
 use cosmwasm_std::{coins, Addr, Coin, Decimal, StdResult};
-use cw_multi_test::Executor;
+use covenant_utils::plan::ShadowDeployment;
+use cw_multi_test::{AppResponse, Executor};
 use valence_covenant_swap::msg::CovenantContractCodes;
 
 use crate::setup::{
@@ -12,7 +13,7 @@ use crate::setup::{
     NTRN_HUB_CHANNEL, NTRN_OSMO_CHANNEL, SWAP_COVENANT_SALT,
 };
 
-pub(super) struct Suite {
+pub(crate) struct Suite {
     pub fuacet: Addr,
     pub admin: Addr,
 
@@ -366,6 +367,26 @@ impl Suite {
     }
 
     pub fn new_with_100_percent_split() -> Self {
+        Self::new_with_swap_split(Decimal::one(), false, false)
+    }
+
+    /// Init covenant where the holder settles directly with the parties
+    pub fn new_with_direct_settlement(counterparty_share: Decimal) -> Self {
+        Self::new_with_swap_split(counterparty_share, true, false)
+    }
+
+    /// Init covenant where the splitter completes once it distributed the swap
+    pub fn new_with_complete_on_settlement(direct_settlement: bool) -> Self {
+        Self::new_with_swap_split(Decimal::one(), direct_settlement, true)
+    }
+
+    /// Init covenant with 2 interchain parties, where each party contribution
+    /// goes to its counterparty with the given share
+    fn new_with_swap_split(
+        counterparty_share: Decimal,
+        direct_settlement: bool,
+        complete_on_settlement: bool,
+    ) -> Self {
         let mut builder = SuiteBuilder::new();
 
         let covenant_addr =
@@ -377,12 +398,12 @@ impl Suite {
         let party_b_on_ntrn = builder.get_random_addr();
 
         let recievers_1 = vec![
-            (&party_a_receiver, Decimal::one()),
-            (&party_b_receiver, Decimal::zero()),
+            (&party_a_receiver, counterparty_share),
+            (&party_b_receiver, Decimal::one() - counterparty_share),
         ];
         let recievers_2 = vec![
-            (&party_b_receiver, Decimal::one()),
-            (&party_a_receiver, Decimal::zero()),
+            (&party_b_receiver, counterparty_share),
+            (&party_a_receiver, Decimal::one() - counterparty_share),
         ];
         let splits = SwapCovenantInstantiate::get_split_custom(vec![
             (DENOM_ATOM_ON_NTRN, &recievers_2),
@@ -407,7 +428,11 @@ impl Suite {
             10_000_000_u128,
         );
         let init_msg =
-            SwapCovenantInstantiate::default(&builder, party_a_config, party_b_config, splits).msg;
+            SwapCovenantInstantiate::default(&builder, party_a_config, party_b_config, splits)
+                .with_direct_settlement(direct_settlement)
+                .with_complete_on_settlement(complete_on_settlement)
+                .msg
+                .clone();
 
         builder.contract_init2(
             builder.swap_covenant_code_id,
@@ -535,6 +560,17 @@ impl Suite {
 
         (depositor_a, depositor_b)
     }
+
+    pub fn shadow_deploy(&mut self, sender: Addr, codes: CovenantContractCodes) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.covenant_addr.clone(),
+                &valence_covenant_swap::msg::ExecuteMsg::ShadowDeploy { codes },
+                &[],
+            )
+            .unwrap()
+    }
 }
 // queries
 impl Suite {
@@ -556,4 +592,36 @@ impl Suite {
             )
             .unwrap()
     }
+
+    pub fn query_splitter_completed(&self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.splitter_addr.clone(),
+                &valence_native_splitter::msg::QueryMsg::Completed {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_splitter_queued(&self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.clock_addr.clone(),
+                &valence_clock::msg::QueryMsg::IsQueued {
+                    address: self.splitter_addr.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_shadow_deployment(&self) -> Option<ShadowDeployment> {
+        self.app
+            .wrap()
+            .query_wasm_smart::<Option<ShadowDeployment>>(
+                self.covenant_addr.clone(),
+                &valence_covenant_swap::msg::QueryMsg::ShadowDeployment {},
+            )
+            .unwrap()
+    }
 }
@@ -1,5 +1,5 @@
-use cosmwasm_std::{coin, coins, to_json_binary, Addr, Event, Uint128, Uint64};
-use covenant_utils::op_mode::ContractOperationModeConfig;
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Decimal, Event, Uint128, Uint64};
+use covenant_utils::{op_mode::ContractOperationModeConfig, plan::ShadowStepStatus};
 use cw_multi_test::Executor;
 
 use crate::setup::{
@@ -208,6 +208,68 @@ fn test_covenant_100_percent_split() {
     assert!(receiver_b_balance_osmo.amount > Uint128::zero());
 }
 
+#[test]
+#[should_panic(expected = "direct settlement requires channel-1/uatom to be split entirely")]
+fn test_instantiate_validates_direct_settlement_splits() {
+    Suite::new_with_direct_settlement(Decimal::bps(5000));
+}
+
+#[test]
+fn test_covenant_direct_settlement() {
+    let mut suite = Suite::new_with_direct_settlement(Decimal::one());
+
+    // holder pays the ibc fees of delivering both sides
+    suite.fund_contract(&coins(1_000_000, DENOM_NTRN), suite.holder_addr.clone());
+
+    suite.get_and_fund_depositors(
+        coin(10_000_000_u128, DENOM_ATOM),
+        coin(10_000_000_u128, DENOM_OSMO),
+    );
+
+    while suite.query_all_balances(&suite.party_a_receiver).is_empty()
+        || suite.query_all_balances(&suite.party_b_receiver).is_empty()
+    {
+        suite.tick("Wait for receivers to get the swapped denoms");
+    }
+
+    // both sides are delivered in full without passing through the splitter
+    suite.assert_balance(
+        &suite.party_a_receiver,
+        coin(10_000_000, DENOM_OSMO_ON_HUB_FROM_NTRN),
+    );
+    suite.assert_balance(
+        &suite.party_b_receiver,
+        coin(10_000_000, DENOM_HUB_ON_OSMO_FROM_NTRN),
+    );
+    assert!(suite.query_all_balances(&suite.splitter_addr).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "splitter can not complete on settlement with direct settlement enabled")]
+fn test_instantiate_validates_complete_on_settlement() {
+    Suite::new_with_complete_on_settlement(true);
+}
+
+#[test]
+fn test_covenant_complete_on_settlement() {
+    let mut suite = Suite::new_with_complete_on_settlement(false);
+    assert!(suite.query_splitter_queued());
+
+    suite.get_and_fund_depositors(
+        coin(10_000_000_u128, DENOM_ATOM),
+        coin(10_000_000_u128, DENOM_OSMO),
+    );
+
+    while !suite.query_splitter_completed() {
+        suite.tick("Wait for the splitter to distribute the swap");
+    }
+
+    // settled splitter stops consuming ticks
+    assert!(!suite.query_splitter_queued());
+    assert!(suite.query_all_balances(&suite.splitter_addr).is_empty());
+    assert!(suite.query_all_balances(&suite.holder_addr).is_empty());
+}
+
 #[test]
 fn test_covenant_fallback_split() {
     let mut suite = Suite::new_with_fallback();
@@ -467,6 +529,7 @@ fn test_migrate_update_with_codes() {
     let native_router_migrate_msg = valence_native_router::msg::MigrateMsg::UpdateConfig {
         op_mode: ContractOperationModeConfig::Permissioned(vec![covenant_addr.to_string()]).into(),
         target_denoms: None,
+        holder_address: None,
         receiver_address: None,
     };
 
@@ -477,6 +540,7 @@ fn test_migrate_update_with_codes() {
         parites_config: Box::new(None),
         covenant_terms: None,
         refund_config: None,
+        direct_settlement: None,
     };
 
     let splitter_migrate_msg = valence_native_splitter::msg::MigrateMsg::UpdateConfig {
@@ -548,6 +612,8 @@ fn test_migrate_update_without_codes() {
     let interchain_router_migrate_msg = valence_interchain_router::msg::MigrateMsg::UpdateConfig {
         clock_addr: Some(covenant_addr.to_string()),
         target_denoms: None,
+        denom_to_destination_map: None,
+        holder_address: None,
         destination_config: None,
     };
 
@@ -621,6 +687,67 @@ fn test_migrate_update_without_codes() {
     );
 }
 
+#[test]
+fn test_shadow_deploy_with_updated_codes() {
+    let mut suite = Suite::new_with_2_native_configs();
+    let covenant_addr = suite.covenant_addr.clone();
+    let contract_codes = suite.query_contract_codes();
+
+    suite.shadow_deploy(Addr::unchecked(ADMIN), contract_codes.clone());
+    let shadow_deployment = suite.query_shadow_deployment().unwrap();
+    assert_eq!(shadow_deployment.nonce, 1);
+    assert!(shadow_deployment.compatible);
+
+    // candidate codes that do not exist fail to migrate
+    let mut candidate_codes = contract_codes.clone();
+    candidate_codes.splitter = 9999;
+    suite.shadow_deploy(Addr::unchecked(ADMIN), candidate_codes);
+    let shadow_deployment = suite.query_shadow_deployment().unwrap();
+    assert_eq!(shadow_deployment.nonce, 2);
+    assert!(!shadow_deployment.compatible);
+    let splitter_step = shadow_deployment
+        .steps
+        .iter()
+        .find(|step| step.candidate_code_id == 9999)
+        .unwrap();
+    assert!(matches!(
+        splitter_step.status,
+        ShadowStepStatus::MigrationFailed { .. }
+    ));
+
+    // point the clock to a code that can not be instantiated with the clock msg
+    let mut contract_codes = suite.query_contract_codes();
+    contract_codes.clock = contract_codes.holder;
+    suite
+        .app
+        .migrate_contract(
+            Addr::unchecked(ADMIN),
+            covenant_addr,
+            &valence_covenant_swap::msg::MigrateMsg::UpdateCovenant {
+                codes: Some(contract_codes.clone()),
+                clock: None,
+                holder: None,
+                splitter: None,
+                party_a_router: None,
+                party_b_router: None,
+                party_a_forwarder: Box::new(None),
+                party_b_forwarder: Box::new(None),
+            },
+            1,
+        )
+        .unwrap();
+
+    suite.shadow_deploy(Addr::unchecked(ADMIN), contract_codes.clone());
+    let shadow_deployment = suite.query_shadow_deployment().unwrap();
+    assert_eq!(shadow_deployment.nonce, 3);
+    assert!(!shadow_deployment.compatible);
+    assert_eq!(shadow_deployment.steps[0].code_id, contract_codes.holder);
+    assert!(matches!(
+        shadow_deployment.steps[0].status,
+        ShadowStepStatus::Failed { .. }
+    ));
+}
+
 // TODO: swap holder is using IBC transfer method isntead of the neutron msg, so this test is not working
 // #[test]
 // fn test_covenant_interchain_refund() {
@@ -683,3 +810,68 @@ fn test_migrate_update_without_codes() {
 //     let receiver_b_balance_osmo = suite.query_balance(&suite.party_b_receiver, DENOM_OSMO);
 //     assert_eq!(receiver_b_balance_osmo.amount.u128(), 10_000_000_u128);
 // }
+
+#[test]
+fn test_migrate_decommission() {
+    let mut suite = Suite::new_with_2_native_configs();
+    let covenant_addr = suite.covenant_addr.clone();
+
+    suite.get_and_fund_depositors(
+        coin(10_000_000_u128, DENOM_ATOM),
+        coin(10_000_000_u128, DENOM_NTRN),
+    );
+
+    // tick until the holder completes
+    loop {
+        let holder_state: valence_swap_holder::msg::ContractState = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.holder_addr.clone(),
+                &valence_swap_holder::msg::QueryMsg::ContractState {},
+            )
+            .unwrap();
+        if holder_state == valence_swap_holder::msg::ContractState::Complete {
+            break;
+        }
+        suite.tick("Wait for the holder to complete");
+    }
+
+    suite
+        .app
+        .migrate_contract(
+            Addr::unchecked(ADMIN),
+            covenant_addr,
+            &valence_covenant_swap::msg::MigrateMsg::Decommission {},
+            1,
+        )
+        .unwrap()
+        .assert_event(&Event::new("wasm").add_attribute("method", "decommission"));
+
+    let decommissioned: bool = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.splitter_addr.clone(),
+            &valence_native_splitter::msg::QueryMsg::Decommissioned {},
+        )
+        .unwrap();
+    assert!(decommissioned);
+}
+
+#[test]
+#[should_panic(expected = "covenant is not complete")]
+fn test_migrate_decommission_validates_holder_completion() {
+    let mut suite = Suite::new_with_2_native_configs();
+    let covenant_addr = suite.covenant_addr.clone();
+
+    suite
+        .app
+        .migrate_contract(
+            Addr::unchecked(ADMIN),
+            covenant_addr,
+            &valence_covenant_swap::msg::MigrateMsg::Decommission {},
+            1,
+        )
+        .unwrap();
+}
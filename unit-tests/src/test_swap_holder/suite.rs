@@ -8,14 +8,17 @@ use crate::setup::{
     instantiates::swap_holder::SwapHolderInstantiate,
     suite_builder::SuiteBuilder,
     CustomApp, CLOCK_SALT, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN, NATIVE_SPLITTER_SALT,
-    SWAP_HOLDER_SALT,
+    NTRN_HUB_CHANNEL, SWAP_HOLDER_SALT,
 };
-use cosmwasm_std::{Addr, Decimal};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Decimal, Uint64};
 use covenant_utils::{
-    op_mode::ContractOperationModeConfig, split::SplitConfig, CovenantPartiesConfig, CovenantTerms,
+    deadline::TimeToExpiry, op_mode::ContractOperationModeConfig, split::SplitConfig,
+    CovenantPartiesConfig, CovenantTerms, DestinationConfig, ReceiverConfig,
 };
+use cw_multi_test::{AppResponse, WasmSudo};
 use cw_utils::Expiration;
-use valence_swap_holder::msg::RefundConfig;
+use neutron_sdk::sudo::msg::{RequestPacket, SudoMsg};
+use valence_swap_holder::msg::{InFlightSettlement, RefundConfig};
 
 pub struct SwapHolderBuilder {
     pub builder: SuiteBuilder,
@@ -48,6 +51,7 @@ impl Default for SwapHolderBuilder {
                 party_b_router_addr.to_string(),
             ],
             initial_queue: vec![],
+            tick_intervals: vec![],
         };
         builder.contract_init2(
             builder.clock_code_id,
@@ -67,7 +71,12 @@ impl Default for SwapHolderBuilder {
                     vec![clock_addr.to_string()],
                 ),
                 receiver_address: party_a_controller_addr.to_string(),
+                receiver_msg: None,
                 denoms: denom_set.clone(),
+                halt_config: None,
+                holder_address: None,
+                min_route_amounts: BTreeMap::new(),
+                cw20_tokens: BTreeSet::new(),
             },
             &[],
         );
@@ -79,7 +88,12 @@ impl Default for SwapHolderBuilder {
                     vec![clock_addr.to_string()],
                 ),
                 receiver_address: party_b_controller_addr.to_string(),
+                receiver_msg: None,
                 denoms: denom_set.clone(),
+                halt_config: None,
+                holder_address: None,
+                min_route_amounts: BTreeMap::new(),
+                cw20_tokens: BTreeSet::new(),
             },
             &[],
         );
@@ -94,7 +108,11 @@ impl Default for SwapHolderBuilder {
             Decimal::from_str("0.5").unwrap(),
         );
 
-        let split_config = SplitConfig { receivers: splits };
+        let split_config = SplitConfig {
+            receivers: splits,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        };
         let mut denom_to_split_config_map = BTreeMap::new();
         denom_to_split_config_map.insert(DENOM_ATOM_ON_NTRN.to_string(), split_config.clone());
         denom_to_split_config_map.insert(DENOM_LS_ATOM_ON_NTRN.to_string(), split_config.clone());
@@ -103,6 +121,12 @@ impl Default for SwapHolderBuilder {
             clock_address: clock_addr.to_string(),
             splits: denom_to_split_config_map,
             fallback_split: None,
+            emergency_committee: None,
+            owner: None,
+            completion_config: None,
+            receiver_types: BTreeMap::new(),
+            op_admin: None,
+            validate_denoms: false,
         };
 
         builder.contract_init2(
@@ -155,6 +179,35 @@ impl SwapHolderBuilder {
         self
     }
 
+    pub fn with_owner(mut self, addr: &str) -> Self {
+        self.instantiate_msg.with_owner(addr);
+        self
+    }
+
+    pub fn with_direct_settlement(mut self, direct_settlement: bool) -> Self {
+        self.instantiate_msg
+            .with_direct_settlement(direct_settlement);
+        self
+    }
+
+    /// delivers the contribution of party a to party b over ibc
+    pub fn with_party_b_ibc_receiver(mut self) -> Self {
+        let receiver = self.builder.get_random_addr();
+        self.instantiate_msg
+            .msg
+            .parties_config
+            .party_b
+            .receiver_config = ReceiverConfig::Ibc(DestinationConfig {
+            local_to_destination_chain_channel_id: NTRN_HUB_CHANNEL.0.to_string(),
+            destination_receiver_addr: receiver.to_string(),
+            ibc_transfer_timeout: Uint64::new(100),
+            denom_to_pfm_map: BTreeMap::new(),
+            memo_template: None,
+            wasm_hook: None,
+        });
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let holder_addr = self.builder.contract_init2(
             self.builder.swap_holder_code_id,
@@ -259,6 +312,16 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_time_to_expiry(&self) -> TimeToExpiry {
+        self.get_app()
+            .wrap()
+            .query_wasm_smart(
+                self.holder.clone(),
+                &valence_swap_holder::msg::QueryMsg::TimeToExpiry {},
+            )
+            .unwrap()
+    }
+
     pub fn query_covenant_parties_config(&self) -> CovenantPartiesConfig {
         self.get_app()
             .wrap()
@@ -318,6 +381,71 @@ impl Suite {
             )
             .unwrap()
     }
+
+    pub fn query_in_flight_settlements(&self) -> Vec<InFlightSettlement> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder.clone(),
+                &valence_swap_holder::msg::QueryMsg::InFlightSettlements {},
+            )
+            .unwrap()
+    }
+
+    pub fn sudo_holder(&mut self, msg: &SudoMsg) -> AppResponse {
+        self.app
+            .sudo(cw_multi_test::SudoMsg::Wasm(WasmSudo {
+                contract_addr: self.holder.clone(),
+                msg: to_json_binary(msg).unwrap(),
+            }))
+            .unwrap()
+    }
+
+    /// acknowledges the settlement transfer with the given sequence
+    pub fn ack_settlement(&mut self, sequence: u64) -> AppResponse {
+        self.sudo_holder(&SudoMsg::Response {
+            request: get_transfer_request_packet(sequence),
+            data: Binary::default(),
+        })
+    }
+
+    /// times out the settlement transfer with the given sequence
+    pub fn timeout_settlement(&mut self, sequence: u64) -> AppResponse {
+        self.sudo_holder(&SudoMsg::Timeout {
+            request: get_transfer_request_packet(sequence),
+        })
+    }
+
+    /// fails the settlement transfer with the given sequence
+    pub fn fail_settlement(&mut self, sequence: u64) -> AppResponse {
+        self.sudo_holder(&SudoMsg::Error {
+            request: get_transfer_request_packet(sequence),
+            details: "receiver rejected the transfer".to_string(),
+        })
+    }
+
+    pub fn query_direct_settlement(&self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder.clone(),
+                &valence_swap_holder::msg::QueryMsg::DirectSettlement {},
+            )
+            .unwrap()
+    }
+}
+
+fn get_transfer_request_packet(sequence: u64) -> RequestPacket {
+    RequestPacket {
+        sequence: Some(sequence),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some(NTRN_HUB_CHANNEL.0.to_string()),
+        destination_port: None,
+        destination_channel: None,
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
 }
 
 #[allow(dead_code)]
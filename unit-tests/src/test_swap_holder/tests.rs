@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, coins, Addr, Event, Uint128};
+use cosmwasm_std::{coin, coins, Addr, Event, Timestamp, Uint128};
 use covenant_utils::{CovenantTerms, SwapCovenantTerms};
 use cw_multi_test::Executor;
 use cw_utils::Expiration;
@@ -6,7 +6,7 @@ use valence_swap_holder::msg::{ContractState, RefundConfig};
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
-    ADMIN, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN,
+    ADMIN, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN, DENOM_NTRN,
 };
 
 use super::suite::SwapHolderBuilder;
@@ -35,6 +35,20 @@ fn test_instantiate_validates_lockup_config() {
         .build();
 }
 
+#[test]
+#[should_panic(expected = "denom ibc/invalid does not exist on this chain")]
+fn test_instantiate_validates_party_denoms() {
+    let mut builder = SwapHolderBuilder::default();
+    builder.instantiate_msg.msg.validate_denoms = true;
+    builder
+        .instantiate_msg
+        .msg
+        .parties_config
+        .party_b
+        .native_denom = "ibc/invalid".to_string();
+    builder.build();
+}
+
 #[test]
 #[should_panic]
 fn test_instantiate_validates_party_a_refund_addr() {
@@ -86,6 +100,26 @@ fn test_execute_tick_instantiated_expires() {
     assert!(matches!(contract_state, ContractState::Expired {}));
 }
 
+#[test]
+fn test_query_time_to_expiry() {
+    let lockup_config = Expiration::AtTime(Timestamp::from_seconds(10000999990));
+    let mut suite = SwapHolderBuilder::default()
+        .with_lockup_config(lockup_config)
+        .build();
+
+    let time_to_expiry = suite.query_time_to_expiry();
+    assert!(!time_to_expiry.expired);
+    assert_eq!(time_to_expiry.remaining_blocks, None);
+    assert_eq!(time_to_expiry.expiration, lockup_config);
+    assert!(time_to_expiry.remaining_seconds.is_some());
+
+    suite.expire_lockup_config();
+
+    let time_to_expiry = suite.query_time_to_expiry();
+    assert!(time_to_expiry.expired);
+    assert_eq!(time_to_expiry.remaining_seconds, Some(0));
+}
+
 #[test]
 #[should_panic(expected = "Insufficient funds to forward")]
 fn test_execute_tick_instantiated_validates_sufficient_funds() {
@@ -107,6 +141,143 @@ fn test_execute_tick_instantiated_forwards_and_completes() {
     assert!(matches!(contract_state, ContractState::Complete {}));
 }
 
+#[test]
+fn test_execute_tick_instantiated_direct_settlement() {
+    let mut suite = SwapHolderBuilder::default()
+        .with_direct_settlement(true)
+        .build();
+    let party_a = suite.covenant_parties_config.party_a.addr.to_string();
+    let party_b = suite.covenant_parties_config.party_b.addr.to_string();
+
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fund_contract(&coins(100000, DENOM_LS_ATOM_ON_NTRN), suite.holder.clone());
+
+    suite.tick_contract(suite.holder.clone());
+
+    // each party receives the counterparty contribution directly
+    suite.assert_balance(party_a, coin(100000, DENOM_LS_ATOM_ON_NTRN));
+    suite.assert_balance(party_b, coin(100000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(suite.next_contract.clone(), coin(0, DENOM_ATOM_ON_NTRN));
+
+    let contract_state = suite.query_contract_state();
+    assert!(matches!(contract_state, ContractState::Complete {}));
+}
+
+#[test]
+fn test_execute_tick_direct_settlement_completes_on_acknowledgement() {
+    let mut suite = SwapHolderBuilder::default()
+        .with_direct_settlement(true)
+        .with_party_b_ibc_receiver()
+        .build();
+    let party_a = suite.covenant_parties_config.party_a.addr.to_string();
+
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fund_contract(&coins(100000, DENOM_LS_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fund_contract(&coins(100000, DENOM_NTRN), suite.holder.clone());
+
+    suite.tick_contract(suite.holder.clone());
+
+    // the native side is settled right away, while the
+    // holder awaits the acknowledgement of the ibc side
+    suite.assert_balance(party_a, coin(100000, DENOM_LS_ATOM_ON_NTRN));
+    assert!(matches!(
+        suite.query_contract_state(),
+        ContractState::Settling {}
+    ));
+    let in_flight_settlements = suite.query_in_flight_settlements();
+    assert_eq!(in_flight_settlements.len(), 1);
+    assert_eq!(
+        in_flight_settlements[0].transfer.coin,
+        coin(100000, DENOM_ATOM_ON_NTRN)
+    );
+
+    suite.ack_settlement(in_flight_settlements[0].sequence);
+
+    assert!(suite.query_in_flight_settlements().is_empty());
+    assert!(matches!(
+        suite.query_contract_state(),
+        ContractState::Complete {}
+    ));
+}
+
+#[test]
+fn test_execute_tick_direct_settlement_retries_timed_out_transfers() {
+    let mut suite = SwapHolderBuilder::default()
+        .with_direct_settlement(true)
+        .with_party_b_ibc_receiver()
+        .build();
+
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fund_contract(&coins(100000, DENOM_LS_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fund_contract(&coins(100000, DENOM_NTRN), suite.holder.clone());
+
+    suite.tick_contract(suite.holder.clone());
+    let sequence = suite.query_in_flight_settlements()[0].sequence;
+
+    // timed out transfers get refunded to the holder
+    suite.timeout_settlement(sequence);
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.holder.clone());
+    assert!(matches!(
+        suite.query_contract_state(),
+        ContractState::Settling {}
+    ));
+
+    let resp = suite.tick_contract(suite.holder.clone());
+    resp.assert_event(
+        &Event::new("wasm").add_attribute("retried", coin(100000, DENOM_ATOM_ON_NTRN).to_string()),
+    );
+
+    let in_flight_settlements = suite.query_in_flight_settlements();
+    assert_eq!(in_flight_settlements.len(), 1);
+    suite.ack_settlement(in_flight_settlements[0].sequence);
+
+    assert!(matches!(
+        suite.query_contract_state(),
+        ContractState::Complete {}
+    ));
+}
+
+#[test]
+fn test_execute_tick_direct_settlement_refunds_failed_transfers() {
+    let mut suite = SwapHolderBuilder::default()
+        .with_direct_settlement(true)
+        .with_party_b_ibc_receiver()
+        .build();
+    let party_a_refund_address = suite.query_refund_config().party_a_refund_address;
+
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fund_contract(&coins(100000, DENOM_LS_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fund_contract(&coins(100000, DENOM_NTRN), suite.holder.clone());
+
+    suite.tick_contract(suite.holder.clone());
+    let sequence = suite.query_in_flight_settlements()[0].sequence;
+
+    // failed transfers get refunded to the holder, which
+    // returns them to the contributing party
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fail_settlement(sequence);
+
+    suite.assert_balance(party_a_refund_address, coin(100000, DENOM_ATOM_ON_NTRN));
+    assert!(matches!(
+        suite.query_contract_state(),
+        ContractState::Complete {}
+    ));
+}
+
+#[test]
+#[should_panic(expected = "insufficient untrn balance to cover the ibc fees")]
+fn test_execute_tick_direct_settlement_validates_ibc_fee_coverage() {
+    let mut suite = SwapHolderBuilder::default()
+        .with_direct_settlement(true)
+        .with_party_b_ibc_receiver()
+        .build();
+
+    suite.fund_contract(&coins(100000, DENOM_ATOM_ON_NTRN), suite.holder.clone());
+    suite.fund_contract(&coins(100000, DENOM_LS_ATOM_ON_NTRN), suite.holder.clone());
+
+    suite.tick_contract(suite.holder.clone());
+}
+
 #[test]
 fn test_execute_expired_refund_both_parties() {
     let mut suite = SwapHolderBuilder::default().build();
@@ -239,6 +410,7 @@ fn test_migrate_update_config() {
                 parites_config: Box::new(Some(parties_config.clone())),
                 covenant_terms: Some(new_covenant_terms.clone()),
                 refund_config: Some(new_refund_config.clone()),
+                direct_settlement: Some(true),
             },
             4,
         )
@@ -251,7 +423,8 @@ fn test_migrate_update_config() {
             .add_attribute("lockup_config", new_expiration.to_string())
             .add_attribute("parites_config", format!("{parties_config:?}"))
             .add_attribute("covenant_terms", format!("{new_covenant_terms:?}"))
-            .add_attribute("refund_config", format!("{new_refund_config:?}")),
+            .add_attribute("refund_config", format!("{new_refund_config:?}"))
+            .add_attribute("direct_settlement", "true"),
     );
 
     assert_eq!(suite.query_clock_address(), next_contract);
@@ -263,6 +436,7 @@ fn test_migrate_update_config() {
     );
     assert_eq!(suite.query_covenant_terms(), new_covenant_terms);
     assert_eq!(suite.query_refund_config(), new_refund_config);
+    assert!(suite.query_direct_settlement());
 }
 
 #[test]
@@ -281,8 +455,70 @@ fn test_migrate_update_config_validates_lockup_config_expiration() {
                 parites_config: Box::new(None),
                 covenant_terms: None,
                 refund_config: None,
+                direct_settlement: None,
             },
             4,
         )
         .unwrap();
 }
+
+#[test]
+fn test_execute_update_config() {
+    let mut builder = SwapHolderBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+    let new_expiration = Expiration::AtHeight(192837465);
+
+    suite
+        .app
+        .execute_contract(
+            owner,
+            suite.holder.clone(),
+            &valence_swap_holder::msg::ExecuteMsg::UpdateConfig {
+                clock_addr: None,
+                next_contract: None,
+                lockup_config: Some(new_expiration),
+                parites_config: Box::new(None),
+                covenant_terms: None,
+                refund_config: None,
+                direct_settlement: Some(true),
+            },
+            &[],
+        )
+        .unwrap()
+        .assert_event(
+            &Event::new("wasm")
+                .add_attribute("method", "update_config")
+                .add_attribute("lockup_config", new_expiration.to_string())
+                .add_attribute("direct_settlement", "true"),
+        );
+
+    assert_eq!(suite.query_lockup_config(), new_expiration);
+    assert!(suite.query_direct_settlement());
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the contract's current owner")]
+fn test_execute_update_config_validates_owner() {
+    let mut builder = SwapHolderBuilder::default();
+    let owner = builder.builder.get_random_addr();
+    let mut suite = builder.with_owner(owner.as_str()).build();
+
+    suite
+        .app
+        .execute_contract(
+            suite.faucet.clone(),
+            suite.holder.clone(),
+            &valence_swap_holder::msg::ExecuteMsg::UpdateConfig {
+                clock_addr: None,
+                next_contract: None,
+                lockup_config: None,
+                parites_config: Box::new(None),
+                covenant_terms: None,
+                refund_config: None,
+                direct_settlement: Some(true),
+            },
+            &[],
+        )
+        .unwrap();
+}
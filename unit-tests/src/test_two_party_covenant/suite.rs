@@ -1,10 +1,13 @@
 use std::collections::BTreeMap;
 
 use cosmwasm_std::{coin, Addr, Decimal, Uint64};
-use covenant_utils::split::SplitConfig;
+use covenant_utils::{
+    plan::{DeploymentPlan, DeploymentProgress, ShadowDeployment},
+    split::SplitConfig,
+};
 use cw_multi_test::{AppResponse, Executor};
 use cw_utils::Expiration;
-use valence_covenant_two_party_pol::msg::{CovenantContractCodes, Timeouts};
+use valence_covenant_two_party_pol::msg::{CovenantContractCodes, PartyDepositStatus, Timeouts};
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
@@ -67,6 +70,12 @@ impl TwoPartyCovenantBuilder {
         self
     }
 
+    pub fn with_router_tick_interval(mut self, router_tick_interval: Option<Uint64>) -> Self {
+        self.instantiate_msg
+            .with_router_tick_interval(router_tick_interval);
+        self
+    }
+
     pub fn with_lockup_config(mut self, lockup_config: Expiration) -> Self {
         self.instantiate_msg.with_lockup_config(lockup_config);
         self
@@ -133,6 +142,11 @@ impl TwoPartyCovenantBuilder {
         self
     }
 
+    pub fn with_reward_denoms(mut self, reward_denoms: Option<Vec<String>>) -> Self {
+        self.instantiate_msg.with_reward_denoms(reward_denoms);
+        self
+    }
+
     pub fn with_fallback_split(mut self, fallback_split: Option<SplitConfig>) -> Self {
         self.instantiate_msg.with_fallback_split(fallback_split);
         self
@@ -144,6 +158,11 @@ impl TwoPartyCovenantBuilder {
         self
     }
 
+    pub fn with_external_holder_address(mut self, addr: Option<String>) -> Self {
+        self.instantiate_msg.with_external_holder_address(addr);
+        self
+    }
+
     pub fn with_liquid_pooler_config(
         mut self,
         liquid_pooler_config: valence_covenant_two_party_pol::msg::LiquidPoolerConfig,
@@ -153,6 +172,11 @@ impl TwoPartyCovenantBuilder {
         self
     }
 
+    pub fn with_deployment_batch_size(mut self, batch_size: Option<u64>) -> Self {
+        self.instantiate_msg.with_deployment_batch_size(batch_size);
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let covenant_addr = self.builder.contract_init2(
             self.builder.two_party_covenant_code_id,
@@ -273,6 +297,18 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_party_deposit_status(&self, party: &str) -> PartyDepositStatus {
+        self.app
+            .wrap()
+            .query_wasm_smart::<PartyDepositStatus>(
+                self.covenant_addr.clone(),
+                &valence_covenant_two_party_pol::msg::QueryMsg::PartyDepositStatus {
+                    party: party.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_contract_codes(&self) -> CovenantContractCodes {
         self.app
             .wrap()
@@ -282,6 +318,58 @@ impl Suite {
             )
             .unwrap()
     }
+
+    pub fn query_deployment_plan(&self) -> DeploymentPlan {
+        self.app
+            .wrap()
+            .query_wasm_smart::<DeploymentPlan>(
+                self.covenant_addr.clone(),
+                &valence_covenant_two_party_pol::msg::QueryMsg::DeploymentPlan {},
+            )
+            .unwrap()
+    }
+
+    pub fn shadow_deploy(&mut self, sender: Addr, codes: CovenantContractCodes) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.covenant_addr.clone(),
+                &valence_covenant_two_party_pol::msg::ExecuteMsg::ShadowDeploy { codes },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn deploy_next(&mut self, sender: Addr) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender,
+                self.covenant_addr.clone(),
+                &valence_covenant_two_party_pol::msg::ExecuteMsg::DeployNext {},
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_deployment_progress(&self) -> DeploymentProgress {
+        self.app
+            .wrap()
+            .query_wasm_smart::<DeploymentProgress>(
+                self.covenant_addr.clone(),
+                &valence_covenant_two_party_pol::msg::QueryMsg::DeploymentProgress {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_shadow_deployment(&self) -> Option<ShadowDeployment> {
+        self.app
+            .wrap()
+            .query_wasm_smart::<Option<ShadowDeployment>>(
+                self.covenant_addr.clone(),
+                &valence_covenant_two_party_pol::msg::QueryMsg::ShadowDeployment {},
+            )
+            .unwrap()
+    }
 }
 
 impl BaseSuiteMut for Suite {
@@ -1,9 +1,18 @@
 use std::collections::BTreeMap;
 
-use cosmwasm_std::{coin, to_json_binary, Addr, Event, Uint64};
-use covenant_utils::op_mode::{ContractOperationMode, ContractOperationModeConfig};
-
-use crate::setup::{base_suite::BaseSuiteMut, DENOM_ATOM, DENOM_ATOM_ON_NTRN, NTRN_HUB_CHANNEL};
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Decimal, Event, Uint128, Uint64};
+use covenant_utils::{
+    forwarder_milestone::{ForwarderMilestone, ForwarderMilestones},
+    op_mode::{ContractOperationMode, ContractOperationModeConfig},
+    plan::ShadowStepStatus,
+    router_halt::RouterHaltConfig,
+};
+use cw_multi_test::Executor;
+
+use crate::setup::{
+    base_suite::BaseSuiteMut, ADMIN, DENOM_ATOM, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN,
+    DENOM_NTRN, NTRN_HUB_CHANNEL,
+};
 
 use super::suite::TwoPartyCovenantBuilder;
 
@@ -12,6 +21,28 @@ fn test_instantiate_both_native_parties_astroport() {
     let _suite = TwoPartyCovenantBuilder::default().build();
 }
 
+#[test]
+fn test_instantiate_router_tick_interval() {
+    let suite = TwoPartyCovenantBuilder::default()
+        .with_router_tick_interval(Some(Uint64::new(600)))
+        .build();
+
+    for party in ["party_a", "party_b"] {
+        let router = suite.query_interchain_router_address(party);
+        let tick_interval: Uint64 = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.clock_addr.clone(),
+                &valence_clock::msg::QueryMsg::TickInterval {
+                    address: router.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(tick_interval, Uint64::new(600));
+    }
+}
+
 #[test]
 fn test_instantiate_party_a_interchain() {
     let builder = TwoPartyCovenantBuilder::default();
@@ -83,6 +114,138 @@ fn test_instantiate_with_fallback_split() {
     builder.with_fallback_split(Some(fallback_split)).build();
 }
 
+#[test]
+fn test_instantiate_with_reward_denom_split() {
+    let builder = TwoPartyCovenantBuilder::default();
+    let mut splits = builder.instantiate_msg.msg.splits.clone();
+    let split = splits.get(DENOM_ATOM_ON_NTRN).unwrap().clone();
+    splits.insert(DENOM_NTRN.to_string(), split);
+
+    builder
+        .with_splits(splits)
+        .with_reward_denoms(Some(vec![DENOM_NTRN.to_string()]))
+        .build();
+}
+
+#[test]
+fn test_instantiate_with_allocation_splits() {
+    let suite = TwoPartyCovenantBuilder::default()
+        .with_splits(BTreeMap::new())
+        .with_party_a_share(Decimal::percent(60))
+        .with_party_b_share(Decimal::percent(40))
+        .build();
+
+    let denom_splits: valence_two_party_pol_holder::msg::DenomSplits = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.query_holder_address(),
+            &valence_two_party_pol_holder::msg::QueryMsg::DenomSplits {},
+        )
+        .unwrap();
+    let router_a = suite.query_interchain_router_address("party_a");
+    let router_b = suite.query_interchain_router_address("party_b");
+
+    assert_eq!(denom_splits.explicit_splits.len(), 2);
+    for denom in [DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN] {
+        assert_eq!(
+            denom_splits.get_receiver_share(denom, router_a.as_str()),
+            Decimal::percent(60)
+        );
+        assert_eq!(
+            denom_splits.get_receiver_share(denom, router_b.as_str()),
+            Decimal::percent(40)
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "is neither contributed nor a reward denom")]
+fn test_instantiate_validates_split_denoms() {
+    let builder = TwoPartyCovenantBuilder::default();
+    let mut splits = builder.instantiate_msg.msg.splits.clone();
+    let split = splits.get(DENOM_ATOM_ON_NTRN).unwrap().clone();
+    splits.insert(DENOM_NTRN.to_string(), split);
+
+    builder.with_splits(splits).build();
+}
+
+#[test]
+#[should_panic(expected = "missing split configuration for denom")]
+fn test_instantiate_validates_contribution_denom_splits() {
+    let builder = TwoPartyCovenantBuilder::default();
+    let mut splits = builder.instantiate_msg.msg.splits.clone();
+    splits.remove(DENOM_LS_ATOM_ON_NTRN);
+
+    builder.with_splits(splits).build();
+}
+
+#[test]
+#[should_panic(expected = "must be between the party receivers")]
+fn test_instantiate_validates_split_receivers() {
+    let mut builder = TwoPartyCovenantBuilder::default();
+    let random_receiver = builder.builder.get_random_addr().to_string();
+    let mut splits = builder.instantiate_msg.msg.splits.clone();
+    let split = splits.get_mut(DENOM_ATOM_ON_NTRN).unwrap();
+    let share = split.receivers.pop_first().unwrap().1;
+    split.receivers.insert(random_receiver, share);
+
+    builder.with_splits(splits).build();
+}
+
+#[test]
+fn test_instantiate_with_external_holder() {
+    let mut builder = TwoPartyCovenantBuilder::default();
+    let external_holder = builder.builder.get_random_addr();
+    let suite = builder
+        .with_external_holder_address(Some(external_holder.to_string()))
+        .build();
+    let plan = suite.query_deployment_plan();
+
+    assert_eq!(suite.query_holder_address(), external_holder);
+    // clock, two routers, and the liquid pooler
+    assert_eq!(plan.steps.len(), 4);
+    assert!(plan
+        .steps
+        .iter()
+        .all(|step| step.address != external_holder));
+
+    let liquid_pooler_holder: Addr = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.query_liquid_pooler_address(),
+            &valence_astroport_liquid_pooler::msg::QueryMsg::HolderAddress {},
+        )
+        .unwrap();
+    assert_eq!(liquid_pooler_holder, external_holder);
+}
+
+#[test]
+#[should_panic]
+fn test_instantiate_validates_external_holder() {
+    TwoPartyCovenantBuilder::default()
+        .with_external_holder_address(Some("not a holder".to_string()))
+        .build();
+}
+
+#[test]
+fn test_query_deployment_plan() {
+    let suite = TwoPartyCovenantBuilder::default().build();
+    let plan = suite.query_deployment_plan();
+
+    // clock, holder, two routers, and the liquid pooler
+    assert_eq!(plan.steps.len(), 5);
+    assert_eq!(plan.steps[0].address, suite.query_clock_address());
+    assert_eq!(plan.steps[1].address, suite.query_holder_address());
+    assert_eq!(plan.steps[4].address, suite.query_liquid_pooler_address());
+    assert!(plan.steps.iter().all(|step| step.msg_size > 0));
+    assert_eq!(
+        plan.total_msg_size(),
+        plan.steps.iter().map(|step| step.msg_size).sum::<u64>()
+    );
+}
+
 #[test]
 fn test_migrate_update_config_party_a_interchain() {
     let builder = TwoPartyCovenantBuilder::default();
@@ -141,6 +304,8 @@ fn test_migrate_update_config_party_a_interchain() {
             clock_addr: Some(random_address.to_string()),
             destination_config: None,
             target_denoms: None,
+            denom_to_destination_map: None,
+            holder_address: None,
         };
     let party_a_router_migrate_msg =
         valence_covenant_two_party_pol::msg::RouterMigrateMsg::Interchain(
@@ -152,6 +317,7 @@ fn test_migrate_update_config_party_a_interchain() {
         ])),
         receiver_address: None,
         target_denoms: None,
+        holder_address: None,
     };
     let party_b_router_migrate_msg = valence_covenant_two_party_pol::msg::RouterMigrateMsg::Native(
         party_b_native_router_migrate_msg.clone(),
@@ -346,6 +512,8 @@ fn test_migrate_update_config_party_b_interchain() {
             clock_addr: Some(random_address.to_string()),
             destination_config: None,
             target_denoms: None,
+            denom_to_destination_map: None,
+            holder_address: None,
         };
     let party_b_router_migrate_msg =
         valence_covenant_two_party_pol::msg::RouterMigrateMsg::Interchain(
@@ -357,6 +525,7 @@ fn test_migrate_update_config_party_b_interchain() {
         ])),
         receiver_address: None,
         target_denoms: None,
+        holder_address: None,
     };
     let party_a_router_migrate_msg = valence_covenant_two_party_pol::msg::RouterMigrateMsg::Native(
         party_a_native_router_migrate_msg.clone(),
@@ -499,3 +668,298 @@ fn test_migrate_update_config_party_b_interchain() {
     );
     assert_eq!(new_contract_codes, contract_codes);
 }
+
+#[test]
+fn test_migrate_halt_routers() {
+    let mut suite = TwoPartyCovenantBuilder::default().build();
+    let holder_addr = suite.holder_addr.clone();
+
+    let resp = suite.migrate_update(
+        22,
+        valence_covenant_two_party_pol::msg::MigrateMsg::HaltRouters {},
+    );
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "halt_routers"));
+
+    for party in ["party_a", "party_b"] {
+        let router = suite.query_interchain_router_address(party);
+        let halt_config: Option<RouterHaltConfig> = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                router.clone(),
+                &valence_native_router::msg::QueryMsg::HaltConfig {},
+            )
+            .unwrap();
+        let halted: bool = suite
+            .app
+            .wrap()
+            .query_wasm_smart(router, &valence_native_router::msg::QueryMsg::Halted {})
+            .unwrap();
+
+        assert_eq!(halt_config.unwrap().holder_address, holder_addr.to_string());
+        assert!(halted);
+    }
+}
+
+#[test]
+fn test_query_party_deposit_status_native_party() {
+    let suite = TwoPartyCovenantBuilder::default().build();
+
+    let status = suite.query_party_deposit_status("party_a");
+    assert_eq!(status.deposit_address, Some(suite.holder_addr.to_string()));
+    assert!(status.ready);
+    assert_eq!(status.forwarder_milestones, None);
+}
+
+#[test]
+fn test_forwarder_milestones_mark_party_deposit_ready() {
+    let builder = TwoPartyCovenantBuilder::default();
+    let party_address = builder
+        .instantiate_msg
+        .msg
+        .party_a_config
+        .get_final_receiver_address();
+    let mut suite = builder
+        .with_party_a_config(
+            valence_covenant_two_party_pol::msg::CovenantPartyConfig::Interchain(
+                covenant_utils::InterchainCovenantParty {
+                    party_receiver_addr: party_address.to_string(),
+                    party_chain_connection_id: "connection-0".to_string(),
+                    ibc_transfer_timeout: Uint64::new(100),
+                    party_to_host_chain_channel_id: NTRN_HUB_CHANNEL.0.to_string(),
+                    host_to_party_chain_channel_id: NTRN_HUB_CHANNEL.1.to_string(),
+                    remote_chain_denom: DENOM_ATOM.to_string(),
+                    addr: party_address.to_string(),
+                    native_denom: DENOM_ATOM_ON_NTRN.to_string(),
+                    contribution: coin(10_000, DENOM_ATOM_ON_NTRN),
+                    denom_to_pfm_map: BTreeMap::new(),
+                    fallback_address: None,
+                },
+            ),
+        )
+        .build();
+    let forwarder = suite.query_ibc_forwarder_address("party_a");
+
+    let status = suite.query_party_deposit_status("party_a");
+    assert_eq!(status.deposit_address, None);
+    assert!(!status.ready);
+    assert_eq!(
+        status.forwarder_milestones,
+        Some(ForwarderMilestones::default())
+    );
+
+    // registering the forwarder ICA reports the milestone to the covenant
+    suite.fund_contract(&coins(1_000_000, DENOM_NTRN), forwarder.clone());
+    suite.tick_contract(forwarder.clone());
+
+    let ica: Addr = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            forwarder,
+            &valence_ibc_forwarder::msg::QueryMsg::IcaAddress {},
+        )
+        .unwrap();
+    let status = suite.query_party_deposit_status("party_a");
+    assert_eq!(status.deposit_address, Some(ica.to_string()));
+    assert!(status.ready);
+    assert_eq!(
+        status.forwarder_milestones.unwrap().ica_channel,
+        Some("channel-1".to_string())
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_execute_forwarder_milestone_validates_sender() {
+    let mut suite = TwoPartyCovenantBuilder::default().build();
+    let covenant_addr = suite.covenant_addr.clone();
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADMIN),
+            covenant_addr,
+            &valence_covenant_two_party_pol::msg::ExecuteMsg::ForwarderMilestone {
+                milestone: ForwarderMilestone::FirstForward {
+                    amount: Uint128::new(10_000),
+                },
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_execute_shadow_deploy() {
+    let mut suite = TwoPartyCovenantBuilder::default().build();
+    let plan = suite.query_deployment_plan();
+    let contract_codes = suite.query_contract_codes();
+    assert!(suite.query_shadow_deployment().is_none());
+
+    // the liquid pooler code stands in for an upgraded holder code
+    let mut candidate_codes = contract_codes.clone();
+    candidate_codes.holder = contract_codes.liquid_pooler;
+    suite.shadow_deploy(Addr::unchecked(ADMIN), candidate_codes.clone());
+
+    let shadow_deployment = suite.query_shadow_deployment().unwrap();
+    assert_eq!(shadow_deployment.nonce, 1);
+    assert!(shadow_deployment.compatible);
+    assert_eq!(shadow_deployment.steps.len(), plan.steps.len());
+    for (shadow_step, step) in shadow_deployment.steps.iter().zip(plan.steps.iter()) {
+        assert_eq!(shadow_step.status, ShadowStepStatus::Compatible);
+        assert_eq!(shadow_step.code_id, step.code_id);
+        assert_ne!(shadow_step.address, step.address);
+
+        // shadow contracts run their candidate code and
+        // are handed over to the covenant admin
+        let contract_info = suite
+            .app
+            .wrap()
+            .query_wasm_contract_info(shadow_step.address.to_string())
+            .unwrap();
+        assert_eq!(contract_info.code_id, shadow_step.candidate_code_id);
+        assert_eq!(contract_info.admin, Some(ADMIN.to_string()));
+    }
+    assert!(shadow_deployment
+        .steps
+        .iter()
+        .any(|step| step.candidate_code_id == candidate_codes.holder
+            && step.code_id == contract_codes.holder));
+
+    // the live deployment is left untouched
+    assert_eq!(suite.query_clock_address(), plan.steps[0].address);
+    let clock_info = suite
+        .app
+        .wrap()
+        .query_wasm_contract_info(plan.steps[0].address.to_string())
+        .unwrap();
+    assert_eq!(clock_info.code_id, contract_codes.clock);
+
+    // consecutive shadow deployments get distinct addresses
+    suite.shadow_deploy(Addr::unchecked(ADMIN), contract_codes);
+    let next_shadow_deployment = suite.query_shadow_deployment().unwrap();
+    assert_eq!(next_shadow_deployment.nonce, 2);
+    assert!(next_shadow_deployment.compatible);
+    assert_ne!(
+        next_shadow_deployment.steps[0].address,
+        shadow_deployment.steps[0].address
+    );
+}
+
+#[test]
+fn test_execute_shadow_deploy_reports_failed_migrations() {
+    let mut suite = TwoPartyCovenantBuilder::default().build();
+    let contract_codes = suite.query_contract_codes();
+
+    // the clock candidate code does not exist
+    let mut candidate_codes = contract_codes.clone();
+    candidate_codes.clock = 9999;
+    suite.shadow_deploy(Addr::unchecked(ADMIN), candidate_codes);
+
+    let shadow_deployment = suite.query_shadow_deployment().unwrap();
+    assert!(!shadow_deployment.compatible);
+    assert!(matches!(
+        shadow_deployment.steps[0].status,
+        ShadowStepStatus::MigrationFailed { .. }
+    ));
+    assert!(shadow_deployment.steps[1..]
+        .iter()
+        .all(|step| step.status == ShadowStepStatus::Compatible));
+
+    // the shadow clock is still handed over, running its original code
+    let contract_info = suite
+        .app
+        .wrap()
+        .query_wasm_contract_info(shadow_deployment.steps[0].address.to_string())
+        .unwrap();
+    assert_eq!(contract_info.code_id, contract_codes.clock);
+    assert_eq!(contract_info.admin, Some(ADMIN.to_string()));
+}
+
+#[test]
+#[should_panic(expected = "only the covenant admin can shadow deploy")]
+fn test_execute_shadow_deploy_validates_sender() {
+    let mut suite = TwoPartyCovenantBuilder::default().build();
+    let faucet = suite.faucet.clone();
+    let contract_codes = suite.query_contract_codes();
+
+    suite.shadow_deploy(faucet, contract_codes);
+}
+
+#[test]
+fn test_deployment_progress_without_stages() {
+    let suite = TwoPartyCovenantBuilder::default().build();
+    let plan = suite.query_deployment_plan();
+
+    let progress = suite.query_deployment_progress();
+    assert_eq!(progress.deployed.len(), plan.steps.len());
+    assert!(progress.pending.is_empty());
+}
+
+#[test]
+fn test_execute_deploy_next() {
+    let mut suite = TwoPartyCovenantBuilder::default()
+        .with_deployment_batch_size(Some(2))
+        .build();
+    let plan = suite.query_deployment_plan();
+    let faucet = suite.faucet.clone();
+
+    // nothing is instantiated along with the covenant
+    let progress = suite.query_deployment_progress();
+    assert!(progress.deployed.is_empty());
+    assert_eq!(progress.pending.len(), plan.steps.len());
+    assert!(suite
+        .app
+        .wrap()
+        .query_wasm_contract_info(plan.steps[0].address.to_string())
+        .is_err());
+
+    suite.deploy_next(faucet.clone());
+    let progress = suite.query_deployment_progress();
+    assert_eq!(
+        progress.deployed,
+        vec![
+            plan.steps[0].label.to_string(),
+            plan.steps[1].label.to_string()
+        ]
+    );
+    assert_eq!(progress.pending.len(), plan.steps.len() - 2);
+    suite
+        .app
+        .wrap()
+        .query_wasm_contract_info(plan.steps[1].address.to_string())
+        .unwrap();
+
+    while !suite.query_deployment_progress().pending.is_empty() {
+        suite.deploy_next(faucet.clone());
+    }
+    for step in plan.steps.iter() {
+        suite
+            .app
+            .wrap()
+            .query_wasm_contract_info(step.address.to_string())
+            .unwrap();
+    }
+}
+
+#[test]
+#[should_panic(expected = "deployment is already complete")]
+fn test_execute_deploy_next_validates_pending_steps() {
+    let mut suite = TwoPartyCovenantBuilder::default()
+        .with_deployment_batch_size(Some(10))
+        .build();
+    let faucet = suite.faucet.clone();
+
+    suite.deploy_next(faucet.clone());
+    suite.deploy_next(faucet);
+}
+
+#[test]
+#[should_panic(expected = "covenant is not deployed in stages")]
+fn test_execute_deploy_next_validates_staged_deployment() {
+    let mut suite = TwoPartyCovenantBuilder::default().build();
+    let faucet = suite.faucet.clone();
+
+    suite.deploy_next(faucet);
+}
@@ -1,2 +1,2 @@
-mod suite;
+pub(crate) mod suite;
 mod tests;
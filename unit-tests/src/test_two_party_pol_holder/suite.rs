@@ -1,17 +1,26 @@
 use std::collections::BTreeMap;
 
 use astroport::factory::PairType;
-use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cosmwasm_std::{coin, Addr, Coin, Decimal, HexBinary, Uint128};
 use covenant_utils::{
-    op_mode::ContractOperationModeConfig, split::SplitConfig, PoolPriceConfig, SingleSideLpLimits,
+    deadline::TimeToExpiry, distribution_receipt::DistributionReceipt,
+    op_mode::ContractOperationModeConfig, split::SplitConfig, split_receiver_fix::SplitReceiverFix,
+    PoolPriceConfig, SingleSideLpLimits,
 };
 use cw_multi_test::{AppResponse, Executor};
 use cw_utils::Expiration;
-use valence_two_party_pol_holder::msg::{ContractState, DenomSplits, RagequitConfig};
+use valence_two_party_pol_holder::msg::{
+    AmendmentProposal, AmendmentRecord, ContractState, DashboardResponse, DenomSplits, Depeg,
+    DepositRecord, LockupExtensionProposal, PartyRebinding, RagequitConfig, ReinvestTarget,
+    ResidualLpDonationConfig, SettlementReport, TermsAmendment,
+};
 
 use crate::setup::{
     base_suite::{BaseSuite, BaseSuiteMut},
-    instantiates::two_party_pol_holder::TwoPartyHolderInstantiate,
+    instantiates::{
+        single_party_holder::SinglePartyHolderInstantiate,
+        two_party_pol_holder::TwoPartyHolderInstantiate,
+    },
     suite_builder::SuiteBuilder,
     CustomApp, ASTRO_LIQUID_POOLER_SALT, CLOCK_SALT, DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN,
     TWO_PARTY_HOLDER_SALT,
@@ -20,6 +29,8 @@ use crate::setup::{
 pub struct TwoPartyHolderBuilder {
     pub builder: SuiteBuilder,
     pub instantiate_msg: TwoPartyHolderInstantiate,
+    pub pool_addr: Addr,
+    pub lp_token_addr: Addr,
 }
 
 impl Default for TwoPartyHolderBuilder {
@@ -33,7 +44,7 @@ impl Default for TwoPartyHolderBuilder {
             builder.get_contract_addr(builder.astro_pooler_code_id, ASTRO_LIQUID_POOLER_SALT);
 
         // init astro pools
-        let (pool_addr, _lp_token_addr) = builder.init_astro_pool(
+        let (pool_addr, lp_token_addr) = builder.init_astro_pool(
             astroport::factory::PairType::Stable {},
             coin(10_000_000_000_000, DENOM_ATOM_ON_NTRN),
             coin(10_000_000_000_000, DENOM_LS_ATOM_ON_NTRN),
@@ -43,6 +54,7 @@ impl Default for TwoPartyHolderBuilder {
             tick_max_gas: None,
             whitelist: vec![holder_addr.to_string(), liquid_pooler_addr.to_string()],
             initial_queue: vec![],
+            tick_intervals: vec![],
         };
         builder.contract_init2(
             builder.clock_code_id,
@@ -58,10 +70,12 @@ impl Default for TwoPartyHolderBuilder {
             assets: valence_astroport_liquid_pooler::msg::AssetData {
                 asset_a_denom: DENOM_ATOM_ON_NTRN.to_string(),
                 asset_b_denom: DENOM_LS_ATOM_ON_NTRN.to_string(),
+                additional_assets: vec![],
             },
             single_side_lp_limits: SingleSideLpLimits {
                 asset_a_limit: Uint128::new(100000),
                 asset_b_limit: Uint128::new(100000),
+                pool_reserve_share: None,
             },
             pool_price_config: PoolPriceConfig {
                 expected_spot_price: Decimal::one(),
@@ -69,6 +83,16 @@ impl Default for TwoPartyHolderBuilder {
             },
             pair_type: PairType::Stable {},
             holder_address: holder_addr.to_string(),
+            pair_type_migration_policy: None,
+            emergency_committee: None,
+            oracle_price_guard: None,
+            max_pool_ownership: None,
+            generator_address: None,
+            twap_window: None,
+            single_side_rebalance: None,
+            min_lp_out_tolerance: None,
+            max_provision_per_tick: None,
+            funding_deadline: None,
         };
 
         builder.contract_init2(
@@ -91,6 +115,8 @@ impl Default for TwoPartyHolderBuilder {
         Self {
             builder,
             instantiate_msg: holder_instantiate_msg,
+            pool_addr,
+            lp_token_addr,
         }
     }
 }
@@ -148,6 +174,116 @@ impl TwoPartyHolderBuilder {
         self
     }
 
+    pub fn with_lockup_extension_window(mut self, window: u64) -> Self {
+        self.instantiate_msg.with_lockup_extension_window(window);
+        self
+    }
+
+    pub fn with_auto_distribute_on_expiry(mut self, enabled: bool) -> Self {
+        self.instantiate_msg.with_auto_distribute_on_expiry(enabled);
+        self
+    }
+
+    pub fn with_fee_refund_config(
+        mut self,
+        config: valence_two_party_pol_holder::msg::FeeRefundConfig,
+    ) -> Self {
+        self.instantiate_msg.with_fee_refund_config(config);
+        self
+    }
+
+    pub fn with_generator_staking(
+        mut self,
+        config: valence_two_party_pol_holder::msg::GeneratorStakingConfig,
+    ) -> Self {
+        self.instantiate_msg.with_generator_staking(config);
+        self
+    }
+
+    pub fn with_provisioning_stall(
+        mut self,
+        config: valence_two_party_pol_holder::msg::ProvisioningStallConfig,
+    ) -> Self {
+        self.instantiate_msg.with_provisioning_stall(config);
+        self
+    }
+
+    pub fn with_depositor_allowlist(
+        mut self,
+        allowlist: valence_two_party_pol_holder::msg::DepositorAllowlist,
+    ) -> Self {
+        self.instantiate_msg.with_depositor_allowlist(allowlist);
+        self
+    }
+
+    pub fn with_vesting_config(
+        mut self,
+        config: valence_two_party_pol_holder::msg::VestingConfig,
+    ) -> Self {
+        self.instantiate_msg.with_vesting_config(config);
+        self
+    }
+
+    pub fn with_depeg_guard(
+        mut self,
+        config: valence_two_party_pol_holder::msg::DepegGuardConfig,
+    ) -> Self {
+        self.instantiate_msg.with_depeg_guard(config);
+        self
+    }
+
+    /// inits an xyk pool of the covenant denoms holding the given reserves
+    pub fn init_price_pool(&mut self, atom_reserve: u128, ls_atom_reserve: u128) -> Addr {
+        let (pool_addr, _) = self.builder.init_astro_pool(
+            PairType::Xyk {},
+            coin(atom_reserve, DENOM_ATOM_ON_NTRN),
+            coin(ls_atom_reserve, DENOM_LS_ATOM_ON_NTRN),
+        );
+        pool_addr
+    }
+
+    /// swaps through the given pools in two consecutive blocks,
+    /// so that their oracles hold a price observation
+    pub fn observe_pool_prices(&mut self, pools: &[Addr]) {
+        for _ in 0..2 {
+            for pool in pools {
+                let offer = coin(1_000, DENOM_ATOM_ON_NTRN);
+                self.builder
+                    .app
+                    .execute_contract(
+                        self.builder.faucet.clone(),
+                        pool.clone(),
+                        &astroport::pair::ExecuteMsg::Swap {
+                            offer_asset: astroport::asset::Asset {
+                                info: astroport::asset::AssetInfo::NativeToken {
+                                    denom: offer.denom.clone(),
+                                },
+                                amount: offer.amount,
+                            },
+                            ask_asset_info: None,
+                            belief_price: None,
+                            max_spread: Some(Decimal::bps(5000)),
+                            to: None,
+                        },
+                        &[offer],
+                    )
+                    .unwrap();
+            }
+            self.builder.app.update_block(|b| {
+                b.height += 1;
+                b.time = b.time.plus_seconds(5);
+            });
+        }
+    }
+
+    pub fn with_residual_lp_donation(
+        mut self,
+        config: valence_two_party_pol_holder::msg::ResidualLpDonationConfig,
+    ) -> Self {
+        self.instantiate_msg.with_residual_lp_donation(config);
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let holder_addr = self.builder.contract_init2(
             self.builder.two_party_holder_code_id,
@@ -239,6 +375,7 @@ impl TwoPartyHolderBuilder {
             splits: denom_splits.clone().explicit_splits,
             fallback_split: denom_splits.clone().fallback_split,
             emergency_committee_addr: None, // todo after adding emergency committee query to holder contract
+            single_party_holder_code_id: self.builder.single_party_holder_code_id,
             app: self.builder.build(),
         }
     }
@@ -262,6 +399,7 @@ pub struct Suite {
     pub splits: BTreeMap<String, SplitConfig>,
     pub fallback_split: Option<SplitConfig>,
     pub emergency_committee_addr: Option<String>,
+    pub single_party_holder_code_id: u64,
 }
 
 impl Suite {
@@ -294,13 +432,109 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn deposit(&mut self, sender: &Addr, funds: &[Coin]) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender.clone(),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::Deposit {},
+                funds,
+            )
+            .unwrap()
+    }
+
+    pub fn notify_deposit(&mut self, sender: &Addr) -> AppResponse {
+        self.app
+            .execute_contract(
+                sender.clone(),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::NotifyDeposit {},
+                &[],
+            )
+            .unwrap()
+    }
+
     pub fn claim(&mut self, sender: &str) -> AppResponse {
         self.app
             .execute_contract(
                 Addr::unchecked(sender),
                 self.holder_addr.clone(),
-                &valence_two_party_pol_holder::msg::ExecuteMsg::Claim {},
+                &valence_two_party_pol_holder::msg::ExecuteMsg::Claim {
+                    reinvest: None,
+                    to: None,
+                    denoms: None,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn claim_to(&mut self, sender: &str, to: &str) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::Claim {
+                    reinvest: None,
+                    to: Some(to.to_string()),
+                    denoms: None,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn claim_and_reinvest(&mut self, sender: &str, holder_address: &str) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::Claim {
+                    reinvest: Some(ReinvestTarget {
+                        holder_address: holder_address.to_string(),
+                    }),
+                    to: None,
+                    denoms: None,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn claim_denoms(&mut self, sender: &str, denoms: &[&str]) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::Claim {
+                    reinvest: None,
+                    to: None,
+                    denoms: Some(denoms.iter().map(|denom| denom.to_string()).collect()),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    /// instantiates a single party holder to reinvest claims into
+    pub fn init_single_party_holder(&mut self, withdrawer: &str, pooler_address: &Addr) -> Addr {
+        let code_id = self.single_party_holder_code_id;
+        let admin = self.admin.clone();
+        let instantiate_msg = SinglePartyHolderInstantiate::new(
+            withdrawer.to_string(),
+            withdrawer.to_string(),
+            None,
+            pooler_address.to_string(),
+            Expiration::Never {},
+        );
+        self.app
+            .instantiate_contract(
+                code_id,
+                admin,
+                &valence_single_party_pol_holder::msg::InstantiateMsg::from(instantiate_msg),
                 &[],
+                "single_party_holder",
+                None,
             )
             .unwrap()
     }
@@ -421,6 +655,16 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_time_to_expiry(&mut self) -> TimeToExpiry {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::TimeToExpiry {},
+            )
+            .unwrap()
+    }
+
     pub fn query_denom_splits(&mut self) -> DenomSplits {
         self.app
             .wrap()
@@ -440,6 +684,402 @@ impl Suite {
             )
             .unwrap()
     }
+
+    pub fn query_distributions_by_party(&mut self, party: &str) -> Vec<Coin> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::DistributionsByParty {
+                    party: party.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_deposit_record(&mut self, party: &str) -> DepositRecord {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::DepositRecord {
+                    party: party.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_depeg(&mut self) -> Option<Depeg> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::Depeg {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_provisioning_paused(&mut self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.next_contract.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::ProvisioningPaused {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_settlement_report(&mut self) -> Option<SettlementReport> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::SettlementReport {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_dashboard(&mut self) -> DashboardResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::Dashboard {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_claim_preview(&mut self, party: &str) -> Vec<Coin> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::ClaimPreview {
+                    party: party.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_vesting_claimed_share(&mut self, party: &str) -> Decimal {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::VestingClaimedShare {
+                    party: party.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_retained_claim_funds(&mut self, party: &str) -> Vec<Coin> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::RetainedClaimFunds {
+                    party: party.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_provisioning_stall(
+        &mut self,
+    ) -> Option<valence_two_party_pol_holder::msg::ProvisioningStall> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::ProvisioningStall {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_claim_receipts(&mut self, id: u64) -> Vec<DistributionReceipt> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::ClaimReceipts { id },
+            )
+            .unwrap()
+    }
+
+    pub fn request_party_rebinding(
+        &mut self,
+        sender: &str,
+        rebinding: PartyRebinding,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::RequestPartyRebinding { rebinding },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn approve_party_rebinding(&mut self, sender: &str, party: &str) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::ApprovePartyRebinding {
+                    party: party.to_string(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn fix_split_receiver(
+        &mut self,
+        sender: &str,
+        denom: &str,
+        old: &str,
+        new: &str,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::FixSplitReceiver {
+                    denom: denom.to_string(),
+                    old: old.to_string(),
+                    new: new.to_string(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_accrued_distributions(&mut self, denom: &str) -> BTreeMap<String, Uint128> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::AccruedDistributions {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_pending_split_receiver_fix(&mut self, denom: &str) -> Option<SplitReceiverFix> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::PendingSplitReceiverFix {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_residual_lp_donation(&mut self) -> Option<ResidualLpDonationConfig> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::ResidualLpDonation {},
+            )
+            .unwrap()
+    }
+
+    /// queries the balance of the LP token of the pool the liquid pooler provides to
+    pub fn query_lp_token_balance(&mut self, addr: &Addr) -> Uint128 {
+        let lp_config: valence_astroport_liquid_pooler::msg::LpConfig = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                self.next_contract.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::LpConfig {},
+            )
+            .unwrap();
+        let pair_info: astroport::asset::PairInfo = self
+            .app
+            .wrap()
+            .query_wasm_smart(lp_config.pool_address, &astroport::pair::QueryMsg::Pair {})
+            .unwrap();
+        let balance: cw20::BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                pair_info.liquidity_token,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: addr.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    }
+
+    /// queries the LP tokens staked in the generator on behalf of the user
+    pub fn query_generator_deposit(&mut self, generator: &Addr, user: &Addr) -> Uint128 {
+        let lp_config: valence_astroport_liquid_pooler::msg::LpConfig = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                self.next_contract.clone(),
+                &valence_astroport_liquid_pooler::msg::QueryMsg::LpConfig {},
+            )
+            .unwrap();
+        let pair_info: astroport::asset::PairInfo = self
+            .app
+            .wrap()
+            .query_wasm_smart(lp_config.pool_address, &astroport::pair::QueryMsg::Pair {})
+            .unwrap();
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                generator,
+                &astroport::generator::QueryMsg::Deposit {
+                    lp_token: pair_info.liquidity_token.to_string(),
+                    user: user.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn propose_lockup_extension(
+        &mut self,
+        sender: &str,
+        new_expiry: Expiration,
+    ) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::ProposeLockupExtension {
+                    new_expiry,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn approve_lockup_extension(&mut self, sender: &str) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::ApproveLockupExtension {},
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_pending_lockup_extension(&mut self) -> Option<LockupExtensionProposal> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::PendingLockupExtension {},
+            )
+            .unwrap()
+    }
+
+    pub fn propose_amendment(&mut self, sender: &str, amendment: TermsAmendment) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::ProposeAmendment { amendment },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn approve_amendment(&mut self, sender: &str, hash: HexBinary) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::ApproveAmendment { hash },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_pending_amendment(&mut self) -> Option<AmendmentProposal> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::PendingAmendment {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_amendment(&mut self, index: u64) -> AmendmentRecord {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::Amendment { index },
+            )
+            .unwrap()
+    }
+
+    pub fn query_amendment_count(&mut self) -> u64 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::AmendmentCount {},
+            )
+            .unwrap()
+    }
+
+    pub fn extend_deposit_deadline(&mut self, sender: &str, expiry: Expiration) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::ExtendDepositDeadline { expiry },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_pending_deposit_deadline_extension(
+        &mut self,
+    ) -> Option<valence_two_party_pol_holder::msg::DepositDeadlineExtension> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::PendingDepositDeadlineExtension {},
+            )
+            .unwrap()
+    }
+
+    pub fn query_pending_party_rebinding(&mut self, party: &str) -> Option<PartyRebinding> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::QueryMsg::PendingPartyRebinding {
+                    party: party.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn acknowledge_distribution(&mut self, sender: &str, id: u64) -> AppResponse {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.holder_addr.clone(),
+                &valence_two_party_pol_holder::msg::ExecuteMsg::AcknowledgeDistribution { id },
+                &[],
+            )
+            .unwrap()
+    }
 }
 
 impl BaseSuiteMut for Suite {
@@ -1,14 +1,26 @@
 use std::{collections::BTreeMap, str::FromStr};
 
 use cosmwasm_std::{coin, coins, Addr, Decimal, Event, Timestamp, Uint128};
-use covenant_utils::split::SplitConfig;
+use covenant_oracle::{PriceGuard, PriceQuery, PriceSource};
+use covenant_utils::{
+    contribution_adapter::ContributionAdapter, distribution_receipt::DeliveryStatus,
+    split::SplitConfig, split_receiver_fix::SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS,
+};
 use cw_multi_test::Executor;
-use cw_utils::Expiration;
-use valence_two_party_pol_holder::msg::{ContractState, RagequitConfig, RagequitTerms};
+use cw_utils::{Duration, Expiration};
+use valence_two_party_pol_holder::msg::{
+    ContractState, CovenantType, DepegAction, DepegGuardConfig, DepositDeadlineExtension,
+    DepositRecord, DepositorAllowlist, FeeRefundConfig, GeneratorStakingConfig,
+    LockupExtensionProposal, PartyRebinding, ProvisioningStallConfig, RagequitConfig,
+    RagequitPenalty, RagequitTerms, ResidualLpDonationConfig, TermsAmendment, VestingConfig,
+    DEFAULT_LOCKUP_EXTENSION_WINDOW,
+};
 
 use crate::setup::{
+    adapter_contracts::{contribution_adapter_contract, ContributionAdapterInstantiateMsg},
     base_suite::{BaseSuite, BaseSuiteMut},
-    ADMIN, DENOM_ATOM_ON_NTRN, DENOM_FALLBACK, DENOM_LS_ATOM_ON_NTRN,
+    generator_contracts::{generator_contract, GeneratorInstantiateMsg},
+    ADMIN, DENOM_ATOM_ON_NTRN, DENOM_FALLBACK, DENOM_LS_ATOM_ON_NTRN, DENOM_NTRN,
 };
 
 use super::suite::TwoPartyHolderBuilder;
@@ -67,6 +79,35 @@ fn test_instantiate_validates_party_a_contribution_amount() {
     builder.build();
 }
 
+#[test]
+#[should_panic(expected = "denom ibc/invalid does not exist on this chain")]
+fn test_instantiate_validates_party_contribution_denom() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    builder.instantiate_msg.msg.validate_denoms = true;
+    builder
+        .instantiate_msg
+        .msg
+        .covenant_config
+        .party_a
+        .contribution
+        .denom = "ibc/invalid".to_string();
+    builder.build();
+}
+
+#[test]
+#[should_panic(expected = "denom ibc/invalid does not exist on this chain")]
+fn test_instantiate_validates_explicit_split_denoms() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    builder.instantiate_msg.msg.validate_denoms = true;
+    let (_, split) = builder.instantiate_msg.msg.splits.pop_first().unwrap();
+    builder
+        .instantiate_msg
+        .msg
+        .splits
+        .insert("ibc/invalid".to_string(), split);
+    builder.build();
+}
+
 #[test]
 #[should_panic(expected = "Party contribution cannot be zero")]
 fn test_instantiate_validates_party_b_contribution_amount() {
@@ -170,7 +211,7 @@ fn test_instantiate_validates_ragequit_config_range() {
     TwoPartyHolderBuilder::default()
         .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
             RagequitTerms {
-                penalty: Decimal::from_str("1.1").unwrap(),
+                penalty: Decimal::from_str("1.1").unwrap().into(),
                 state: None,
             },
         ))
@@ -183,13 +224,116 @@ fn test_instantiate_validates_ragequit_config_party_allocations() {
     TwoPartyHolderBuilder::default()
         .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
             RagequitTerms {
-                penalty: Decimal::from_str("0.6").unwrap(),
+                penalty: Decimal::from_str("0.6").unwrap().into(),
+                state: None,
+            },
+        ))
+        .build();
+}
+
+#[test]
+#[should_panic(
+    expected = "Per denom ragequit penalties are only supported by side based covenants"
+)]
+fn test_instantiate_validates_per_denom_ragequit_penalty_covenant_type() {
+    TwoPartyHolderBuilder::default()
+        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
+            RagequitTerms {
+                penalty: RagequitPenalty::PerDenom(BTreeMap::from([(
+                    DENOM_ATOM_ON_NTRN.to_string(),
+                    Decimal::from_str("0.05").unwrap(),
+                )])),
                 state: None,
             },
         ))
         .build();
 }
 
+#[test]
+#[should_panic(expected = "Ragequit penalty exceeds party allocation")]
+fn test_instantiate_validates_per_denom_ragequit_penalties() {
+    let mut builder = TwoPartyHolderBuilder::default().with_ragequit_config(
+        valence_two_party_pol_holder::msg::RagequitConfig::Enabled(RagequitTerms {
+            penalty: RagequitPenalty::PerDenom(BTreeMap::from([
+                (
+                    DENOM_ATOM_ON_NTRN.to_string(),
+                    Decimal::from_str("0.05").unwrap(),
+                ),
+                (
+                    DENOM_LS_ATOM_ON_NTRN.to_string(),
+                    Decimal::from_str("0.6").unwrap(),
+                ),
+            ])),
+            state: None,
+        }),
+    );
+    builder.instantiate_msg.msg.covenant_config.covenant_type = CovenantType::Side;
+    builder.build();
+}
+
+#[test]
+fn test_apply_per_denom_ragequit_penalty() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.clone();
+    let party_b = suite.covenant_config.party_b.clone();
+
+    let penalty = RagequitPenalty::PerDenom(BTreeMap::from([
+        (
+            DENOM_ATOM_ON_NTRN.to_string(),
+            Decimal::from_str("0.05").unwrap(),
+        ),
+        (
+            DENOM_LS_ATOM_ON_NTRN.to_string(),
+            Decimal::from_str("0.2").unwrap(),
+        ),
+    ]));
+
+    let denom_splits = suite
+        .query_denom_splits()
+        .apply_penalty(&penalty, &party_a, &party_b)
+        .unwrap();
+
+    let atom_split = &denom_splits.explicit_splits[DENOM_ATOM_ON_NTRN].receivers;
+    assert_eq!(
+        atom_split[&party_a.router],
+        Decimal::from_str("0.45").unwrap()
+    );
+    assert_eq!(
+        atom_split[&party_b.router],
+        Decimal::from_str("0.55").unwrap()
+    );
+
+    let ls_atom_split = &denom_splits.explicit_splits[DENOM_LS_ATOM_ON_NTRN].receivers;
+    assert_eq!(
+        ls_atom_split[&party_a.router],
+        Decimal::from_str("0.3").unwrap()
+    );
+    assert_eq!(
+        ls_atom_split[&party_b.router],
+        Decimal::from_str("0.7").unwrap()
+    );
+
+    // denoms without a configured penalty are left untouched
+    let penalty = RagequitPenalty::PerDenom(BTreeMap::from([(
+        DENOM_ATOM_ON_NTRN.to_string(),
+        Decimal::from_str("0.05").unwrap(),
+    )]));
+    let denom_splits = suite
+        .query_denom_splits()
+        .apply_penalty(&penalty, &party_a, &party_b)
+        .unwrap();
+
+    let ls_atom_split = &denom_splits.explicit_splits[DENOM_LS_ATOM_ON_NTRN].receivers;
+    assert_eq!(
+        ls_atom_split[&party_a.router],
+        Decimal::from_str("0.5").unwrap()
+    );
+    assert_eq!(
+        ls_atom_split[&party_b.router],
+        Decimal::from_str("0.5").unwrap()
+    );
+}
+
 #[test]
 // #[should_panic] TODO: enable
 fn test_instantiate_validates_explicit_splits() {
@@ -226,6 +370,8 @@ fn test_instantiate_validates_fallback_split() {
             .1
             .receivers
             .clone(),
+        rounding: Default::default(),
+        min_amounts: Default::default(),
     };
     fallback_split
         .receivers
@@ -234,6 +380,22 @@ fn test_instantiate_validates_fallback_split() {
     default_builder.build();
 }
 
+#[test]
+#[should_panic(
+    expected = "residual lp donation claim window must be of the same unit as the lockup config"
+)]
+fn test_instantiate_validates_residual_lp_donation_claim_window() {
+    let builder = TwoPartyHolderBuilder::default();
+    let receiver = builder.builder.app.api().addr_make("protocol_owned");
+    builder
+        .with_lockup_config(Expiration::AtHeight(200_000))
+        .with_residual_lp_donation(ResidualLpDonationConfig {
+            receiver: receiver.to_string(),
+            claim_window: Duration::Time(1_000),
+        })
+        .build();
+}
+
 #[test]
 #[should_panic(expected = "Caller is not the clock, only clock can tick contracts")]
 fn test_execute_tick_validates_clock() {
@@ -296,6 +458,79 @@ fn test_execute_tick_expired_deposit_refunds_both_parties() {
     ));
 }
 
+#[test]
+fn test_query_distributions_by_party_tracks_refunds() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.expire_deposit_deadline();
+
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.holder_addr.clone());
+
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+    assert_eq!(
+        suite.query_distributions_by_party(&party_a),
+        vec![coin(10_000, DENOM_ATOM_ON_NTRN)]
+    );
+    assert_eq!(
+        suite.query_distributions_by_party(&party_b),
+        vec![coin(10_000, DENOM_LS_ATOM_ON_NTRN)]
+    );
+}
+
+#[test]
+fn test_query_settlement_report() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(5_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_settlement_report(), None);
+
+    suite.expire_deposit_deadline();
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.holder_addr.clone());
+
+    let report = suite.query_settlement_report().unwrap();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+    assert_eq!(report.party_a.host_addr, party_a);
+    assert_eq!(report.party_a.contributed, coin(10_000, DENOM_ATOM_ON_NTRN));
+    assert_eq!(
+        report.party_a.distributed,
+        vec![coin(10_000, DENOM_ATOM_ON_NTRN)]
+    );
+    assert_eq!(report.party_b.host_addr, party_b);
+    assert_eq!(
+        report.party_b.contributed,
+        coin(5_000, DENOM_LS_ATOM_ON_NTRN)
+    );
+    assert_eq!(
+        report.party_b.distributed,
+        vec![coin(5_000, DENOM_LS_ATOM_ON_NTRN)]
+    );
+    assert_eq!(report.ragequit, None);
+    assert_eq!(report.fee_refunds, coin(0, DENOM_NTRN));
+
+    let instantiated_at = report.instantiated_at.unwrap();
+    assert!(report.completed_at >= instantiated_at);
+    assert_eq!(
+        report.duration,
+        Some(report.completed_at.seconds() - instantiated_at.seconds())
+    );
+}
+
 #[test]
 fn test_execute_tick_expired_deposit_refunds_party_a() {
     let mut suite = TwoPartyHolderBuilder::default().build();
@@ -397,7 +632,6 @@ fn test_execute_tick_expired_deposit_completes() {
 }
 
 #[test]
-#[should_panic(expected = "both parties have not deposited")]
 fn test_execute_tick_deposit_validates_insufficient_deposits() {
     let mut suite = TwoPartyHolderBuilder::default().build();
 
@@ -408,73 +642,96 @@ fn test_execute_tick_deposit_validates_insufficient_deposits() {
         ],
         suite.holder_addr.clone(),
     );
-    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.holder_addr.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "try_deposit")
+            .add_attribute("party_a_deposited", "10000")
+            .add_attribute("party_b_deposited", "5000")
+            .add_attribute("contract_state", "instantiated"),
+    );
+    assert_eq!(suite.query_contract_state(), ContractState::Instantiated);
 }
 
 #[test]
-fn test_execute_tick_expired_noop() {
+fn test_execute_tick_deposit_in_installments() {
     let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+
+    suite.fund_contract(&coins(4_000, DENOM_ATOM_ON_NTRN), suite.holder_addr.clone());
+    suite.tick_contract(suite.holder_addr.clone());
     suite.fund_contract(
         &[
-            coin(10_000, DENOM_ATOM_ON_NTRN),
-            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+            coin(6_000, DENOM_ATOM_ON_NTRN),
+            coin(5_000, DENOM_LS_ATOM_ON_NTRN),
         ],
         suite.holder_addr.clone(),
     );
     suite.tick_contract(suite.holder_addr.clone());
 
-    assert_eq!(suite.query_contract_state(), ContractState::Active {});
-
-    suite.expire_lockup_config();
+    assert_eq!(
+        suite.query_deposit_record(&party_a),
+        DepositRecord {
+            amount: Uint128::new(10_000),
+            installments: 2,
+        }
+    );
+    assert_eq!(
+        suite.query_deposit_record(&party_b),
+        DepositRecord {
+            amount: Uint128::new(5_000),
+            installments: 1,
+        }
+    );
+    assert_eq!(suite.query_contract_state(), ContractState::Instantiated);
 
+    // the last installment of party b fulfills both contributions
+    suite.fund_contract(
+        &coins(5_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.holder_addr.clone(),
+    );
     suite.tick_contract(suite.holder_addr.clone());
-    assert_eq!(suite.query_contract_state(), ContractState::Expired {});
 
-    suite.tick_contract(suite.holder_addr.clone()).assert_event(
-        &Event::new("wasm")
-            .add_attribute("method", "tick")
-            .add_attribute("contract_state", "expired"),
+    assert_eq!(
+        suite.query_deposit_record(&party_b),
+        DepositRecord {
+            amount: Uint128::new(10_000),
+            installments: 2,
+        }
     );
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
 }
 
 #[test]
-fn test_execute_tick_ragequit_noop() {
-    let mut suite = TwoPartyHolderBuilder::default()
-        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
-            RagequitTerms {
-                penalty: Decimal::from_str("0.05").unwrap(),
-                state: None,
-            },
-        ))
-        .build();
+fn test_execute_notify_deposit() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let keeper = suite.faucet.clone();
+
     suite.fund_contract(
-        &[
-            coin(10_000, DENOM_ATOM_ON_NTRN),
-            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
-        ],
+        &coins(10_000, DENOM_ATOM_ON_NTRN),
         suite.holder_addr.clone(),
     );
-    suite.tick_contract(suite.holder_addr.clone());
-    suite.tick_contract(suite.next_contract.clone());
-
-    assert_eq!(suite.query_contract_state(), ContractState::Active {});
-
-    suite.expire_deposit_deadline();
-    suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
-
-    assert_eq!(suite.query_contract_state(), ContractState::Ragequit {});
+    suite.notify_deposit(&keeper);
+    assert_eq!(suite.query_contract_state(), ContractState::Instantiated);
 
-    suite.tick_contract(suite.holder_addr.clone()).assert_event(
-        &Event::new("wasm")
-            .add_attribute("method", "tick")
-            .add_attribute("contract_state", "ragequit"),
+    // deposits are forwarded to the liquid pooler without waiting for a tick
+    suite.fund_contract(
+        &coins(10_000, DENOM_LS_ATOM_ON_NTRN),
+        suite.holder_addr.clone(),
+    );
+    suite.notify_deposit(&keeper);
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+    suite.assert_balance(
+        suite.next_contract.to_string(),
+        coin(10_000, DENOM_LS_ATOM_ON_NTRN),
     );
 }
 
 #[test]
-#[should_panic(expected = "ragequit is disabled")]
-fn test_execute_ragequit_validates_ragequit_config() {
+#[should_panic(expected = "deposits are only accepted before the deposit deadline")]
+fn test_execute_notify_deposit_validates_state() {
     let mut suite = TwoPartyHolderBuilder::default().build();
+    let keeper = suite.faucet.clone();
     suite.fund_contract(
         &[
             coin(10_000, DENOM_ATOM_ON_NTRN),
@@ -482,46 +739,65 @@ fn test_execute_ragequit_validates_ragequit_config() {
         ],
         suite.holder_addr.clone(),
     );
-    suite.tick_contract(suite.holder_addr.clone());
-    suite.tick_contract(suite.next_contract.clone());
+    suite.notify_deposit(&keeper);
 
-    assert_eq!(suite.query_contract_state(), ContractState::Active {});
+    suite.notify_deposit(&keeper);
+}
 
-    suite.expire_deposit_deadline();
-    suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
+#[test]
+fn test_execute_deposit_activates_covenant() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let faucet = suite.faucet.clone();
+
+    suite.deposit(
+        &faucet,
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+    );
+
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+    suite.assert_balance(
+        suite.next_contract.to_string(),
+        coin(10_000, DENOM_ATOM_ON_NTRN),
+    );
 }
 
 #[test]
-#[should_panic(expected = "covenant is not in active state")]
-fn test_execute_ragequit_validates_active_state() {
+#[should_panic(expected = "depositor is not allowed to deposit")]
+fn test_execute_deposit_validates_depositor() {
     let mut suite = TwoPartyHolderBuilder::default()
-        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
-            RagequitTerms {
-                penalty: Decimal::from_str("0.05").unwrap(),
-                state: None,
-            },
-        ))
+        .with_depositor_allowlist(DepositorAllowlist {
+            allowed_depositors: vec![ADMIN.to_string()],
+            legacy_balance_deposits: false,
+        })
         .build();
-
-    suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
+    let faucet = suite.faucet.clone();
+    suite.deposit(&faucet, &coins(10_000, DENOM_ATOM_ON_NTRN));
 }
 
 #[test]
-fn test_execute_ragequit_validates_withdraw_started() {
-    // todo
+#[should_panic(expected = "deposits must attach the contribution denoms of the parties")]
+fn test_execute_deposit_validates_denoms() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let faucet = suite.faucet.clone();
+    suite.deposit(&faucet, &coins(10_000, DENOM_NTRN));
 }
 
 #[test]
-#[should_panic(expected = "covenant is active but expired; tick to proceed")]
-fn test_execute_ragequit_validates_lockup_config_expiration() {
-    let mut suite = TwoPartyHolderBuilder::default()
-        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
-            RagequitTerms {
-                penalty: Decimal::from_str("0.05").unwrap(),
-                state: None,
-            },
-        ))
+fn test_execute_deposit_ignores_third_party_transfers() {
+    let builder = TwoPartyHolderBuilder::default();
+    let depositor = builder.builder.faucet.clone();
+    let mut suite = builder
+        .with_depositor_allowlist(DepositorAllowlist {
+            allowed_depositors: vec![depositor.to_string()],
+            legacy_balance_deposits: false,
+        })
         .build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    // funds transferred to the holder do not count as deposits
     suite.fund_contract(
         &[
             coin(10_000, DENOM_ATOM_ON_NTRN),
@@ -530,24 +806,51 @@ fn test_execute_ragequit_validates_lockup_config_expiration() {
         suite.holder_addr.clone(),
     );
     suite.tick_contract(suite.holder_addr.clone());
-    suite.tick_contract(suite.next_contract.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Instantiated);
+    assert_eq!(
+        suite.query_deposit_record(&party_a),
+        DepositRecord::default()
+    );
 
-    assert_eq!(suite.query_contract_state(), ContractState::Active {});
+    suite.deposit(
+        &depositor,
+        &[
+            coin(4_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+    );
+    suite.deposit(&depositor, &coins(6_000, DENOM_ATOM_ON_NTRN));
+    assert_eq!(
+        suite.query_deposit_record(&party_a),
+        DepositRecord {
+            amount: Uint128::new(10_000),
+            installments: 2,
+        }
+    );
 
-    suite.expire_lockup_config();
-    suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
+    // only the deposited funds are provided to the liquid pooler
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+    suite.assert_balance(
+        suite.next_contract.to_string(),
+        coin(10_000, DENOM_ATOM_ON_NTRN),
+    );
+    suite.assert_balance(
+        suite.next_contract.to_string(),
+        coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+    );
+    suite.assert_balance(
+        suite.holder_addr.to_string(),
+        coin(10_000, DENOM_ATOM_ON_NTRN),
+    );
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
-fn test_execute_ragequit_validates_sender() {
+fn test_execute_tick_deposit_with_legacy_balance_deposits() {
     let mut suite = TwoPartyHolderBuilder::default()
-        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
-            RagequitTerms {
-                penalty: Decimal::from_str("0.05").unwrap(),
-                state: None,
-            },
-        ))
+        .with_depositor_allowlist(DepositorAllowlist {
+            allowed_depositors: vec![],
+            legacy_balance_deposits: true,
+        })
         .build();
     suite.fund_contract(
         &[
@@ -557,23 +860,302 @@ fn test_execute_ragequit_validates_sender() {
         suite.holder_addr.clone(),
     );
     suite.tick_contract(suite.holder_addr.clone());
-    suite.tick_contract(suite.next_contract.clone());
-
-    assert_eq!(suite.query_contract_state(), ContractState::Active {});
-
-    suite.ragequit(suite.faucet.clone().as_ref());
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
-fn test_execute_claim_unauthorized() {
-    let mut suite = TwoPartyHolderBuilder::default()
-        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
-            RagequitTerms {
-                penalty: Decimal::from_str("0.05").unwrap(),
-                state: None,
-            },
-        ))
+fn test_execute_tick_deposit_converts_adapted_contribution() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let adapter_code_id = builder
+        .builder
+        .app
+        .store_code(contribution_adapter_contract());
+    let adapter = builder.builder.contract_init(
+        adapter_code_id,
+        "contribution_adapter".to_string(),
+        &ContributionAdapterInstantiateMsg {
+            target_denom: DENOM_ATOM_ON_NTRN.to_string(),
+        },
+        &[],
+    );
+    let faucet = builder.builder.faucet.clone();
+    builder
+        .builder
+        .app
+        .send_tokens(faucet, adapter.clone(), &[coin(10_000, DENOM_ATOM_ON_NTRN)])
+        .unwrap();
+
+    // party a contributes a derivative of the pooled denom
+    let mut covenant_config = builder.instantiate_msg.msg.covenant_config.clone();
+    covenant_config.party_a.contribution = coin(10_000, DENOM_FALLBACK);
+    covenant_config.party_a.contribution_adapter = Some(ContributionAdapter {
+        address: adapter.to_string(),
+        target_denom: DENOM_ATOM_ON_NTRN.to_string(),
+    });
+    let mut suite = builder.with_covenant_config(covenant_config).build();
+
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_FALLBACK),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "deposit_to_next_contract")
+            .add_attribute(
+                "converted_contribution",
+                coin(10_000, DENOM_FALLBACK).to_string(),
+            ),
+    );
+
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+    suite.assert_balance(&adapter, coin(10_000, DENOM_FALLBACK));
+    suite.assert_balance(&adapter, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.next_contract, coin(10_000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&suite.next_contract, coin(10_000, DENOM_LS_ATOM_ON_NTRN));
+}
+
+#[test]
+#[should_panic(expected = "contribution adapter must convert")]
+fn test_instantiate_validates_contribution_adapter() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let mut covenant_config = builder.instantiate_msg.msg.covenant_config.clone();
+    covenant_config.party_a.contribution_adapter = Some(ContributionAdapter {
+        address: builder.builder.get_random_addr().to_string(),
+        target_denom: DENOM_ATOM_ON_NTRN.to_string(),
+    });
+    builder.with_covenant_config(covenant_config).build();
+}
+
+#[test]
+fn test_execute_tick_expired_noop() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    suite.expire_lockup_config();
+
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Expired {});
+
+    suite.tick_contract(suite.holder_addr.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "tick")
+            .add_attribute("contract_state", "expired"),
+    );
+}
+
+#[test]
+fn test_query_time_to_expiry() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let height = suite.app.block_info().height;
+
+    let time_to_expiry = suite.query_time_to_expiry();
+    assert_eq!(time_to_expiry.expiration, suite.lockup_config);
+    assert!(!time_to_expiry.expired);
+    assert_eq!(time_to_expiry.remaining_blocks, Some(200_000 - height));
+    assert_eq!(time_to_expiry.remaining_seconds, None);
+
+    suite.expire_lockup_config();
+
+    let time_to_expiry = suite.query_time_to_expiry();
+    assert!(time_to_expiry.expired);
+    assert_eq!(time_to_expiry.remaining_blocks, Some(0));
+}
+
+#[test]
+fn test_execute_tick_ragequit_noop() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
+            RagequitTerms {
+                penalty: Decimal::from_str("0.05").unwrap().into(),
+                state: None,
+            },
+        ))
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    suite.expire_deposit_deadline();
+    suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
+
+    assert_eq!(suite.query_contract_state(), ContractState::Ragequit {});
+
+    suite.tick_contract(suite.holder_addr.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "tick")
+            .add_attribute("contract_state", "ragequit"),
+    );
+}
+
+#[test]
+fn test_execute_tick_donates_residual_lp_position() {
+    let builder = TwoPartyHolderBuilder::default();
+    let receiver = builder.builder.app.api().addr_make("protocol_owned");
+    let mut suite = builder
+        .with_residual_lp_donation(ResidualLpDonationConfig {
+            receiver: receiver.to_string(),
+            claim_window: Duration::Height(1_000),
+        })
+        .build();
+
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    let lp_position = suite.query_lp_token_balance(&suite.next_contract.clone());
+    assert!(!lp_position.is_zero());
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Expired {});
+
+    // parties are still within the claim window
+    suite.get_app().update_block(|b| b.height += 999);
+    suite.tick_contract(suite.holder_addr.clone()).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "tick")
+            .add_attribute("contract_state", "expired"),
+    );
+
+    suite.get_app().update_block(|b| b.height += 1);
+    suite.tick_contract(suite.holder_addr.clone());
+
+    assert_eq!(suite.query_contract_state(), ContractState::Complete {});
+    assert!(suite
+        .query_lp_token_balance(&suite.next_contract.clone())
+        .is_zero());
+    assert_eq!(suite.query_lp_token_balance(&receiver), lp_position);
+}
+
+#[test]
+#[should_panic(expected = "ragequit is disabled")]
+fn test_execute_ragequit_validates_ragequit_config() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    suite.expire_deposit_deadline();
+    suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
+}
+
+#[test]
+#[should_panic(expected = "covenant is not in active state")]
+fn test_execute_ragequit_validates_active_state() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
+            RagequitTerms {
+                penalty: Decimal::from_str("0.05").unwrap().into(),
+                state: None,
+            },
+        ))
+        .build();
+
+    suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
+}
+
+#[test]
+fn test_execute_ragequit_validates_withdraw_started() {
+    // todo
+}
+
+#[test]
+#[should_panic(expected = "covenant is active but expired; tick to proceed")]
+fn test_execute_ragequit_validates_lockup_config_expiration() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
+            RagequitTerms {
+                penalty: Decimal::from_str("0.05").unwrap().into(),
+                state: None,
+            },
+        ))
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    suite.expire_lockup_config();
+    suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_ragequit_validates_sender() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
+            RagequitTerms {
+                penalty: Decimal::from_str("0.05").unwrap().into(),
+                state: None,
+            },
+        ))
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    suite.ragequit(suite.faucet.clone().as_ref());
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_claim_unauthorized() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
+            RagequitTerms {
+                penalty: Decimal::from_str("0.05").unwrap().into(),
+                state: None,
+            },
+        ))
         .build();
     let clock = suite.clock_addr.clone();
 
@@ -586,7 +1168,7 @@ fn test_execute_claim_with_null_allocation() {
     let mut suite = TwoPartyHolderBuilder::default()
         .with_ragequit_config(valence_two_party_pol_holder::msg::RagequitConfig::Enabled(
             RagequitTerms {
-                penalty: Decimal::from_str("0.05").unwrap(),
+                penalty: Decimal::from_str("0.05").unwrap().into(),
                 state: None,
             },
         ))
@@ -601,7 +1183,7 @@ fn test_execute_claim_with_null_allocation() {
     suite.tick_contract(suite.holder_addr.clone());
     suite.tick_contract(suite.next_contract.clone());
 
-    assert_eq!(suite.query_contract_state(), ContractState::Active {});
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
 
     suite.expire_deposit_deadline();
     suite.ragequit(&suite.covenant_config.party_a.host_addr.clone());
@@ -657,15 +1239,21 @@ fn test_execute_claim_happy() {
         suite.query_covenant_config().party_a.allocation,
         Decimal::zero()
     );
+
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let mut distributions = suite.query_distributions_by_party(&party_a);
+    distributions.sort_by(|a, b| a.denom.cmp(&b.denom));
+    let mut expected = vec![atom_bal, ls_atom_bal];
+    expected.sort_by(|a, b| a.denom.cmp(&b.denom));
+    assert_eq!(distributions, expected);
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+    assert!(suite.query_distributions_by_party(&party_b).is_empty());
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
-fn test_execute_emergency_withdraw_validates_committee_address() {
-    let builder = TwoPartyHolderBuilder::default();
-    let clock = builder.instantiate_msg.msg.clock_address.clone();
-    let mut suite = builder.with_emergency_committee(clock.as_str()).build();
-
+#[should_panic(expected = "was already triggered at height")]
+fn test_execute_claim_rejects_duplicate_claim_in_block() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
     suite.fund_contract(
         &[
             coin(10_001, DENOM_ATOM_ON_NTRN),
@@ -676,17 +1264,17 @@ fn test_execute_emergency_withdraw_validates_committee_address() {
     suite.tick_contract(suite.holder_addr.clone());
     suite.tick_contract(suite.next_contract.clone());
 
-    let sender = suite.faucet.clone();
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
 
-    suite.emergency_withdraw(sender.as_str());
+    let party_a = suite.covenant_config.party_a.host_addr.clone();
+    suite.claim(&party_a);
+    suite.claim(&party_a);
 }
 
 #[test]
-fn test_execute_emergency_withdraw_happy() {
-    let builder = TwoPartyHolderBuilder::default();
-    let clock = builder.instantiate_msg.msg.clock_address.clone();
-    let mut suite = builder.with_emergency_committee(clock.as_str()).build();
-
+fn test_execute_claim_with_reinvest() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
     suite.fund_contract(
         &[
             coin(10_001, DENOM_ATOM_ON_NTRN),
@@ -697,220 +1285,847 @@ fn test_execute_emergency_withdraw_happy() {
     suite.tick_contract(suite.holder_addr.clone());
     suite.tick_contract(suite.next_contract.clone());
 
-    suite.emergency_withdraw(clock.as_str());
-
-    let party_a = Addr::unchecked(suite.covenant_config.party_a.router.to_string());
-    let party_b = Addr::unchecked(suite.covenant_config.party_b.router.to_string());
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
 
-    let party_a_atom_bal = suite.query_balance(&party_a, DENOM_ATOM_ON_NTRN).amount;
-    let party_b_atom_bal = suite.query_balance(&party_b, DENOM_ATOM_ON_NTRN).amount;
-    let party_a_ls_atom_bal = suite.query_balance(&party_a, DENOM_LS_ATOM_ON_NTRN).amount;
-    let party_b_ls_atom_bal = suite.query_balance(&party_b, DENOM_LS_ATOM_ON_NTRN).amount;
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let reinvest_pooler = suite.app.api().addr_make("reinvest_pooler");
+    let reinvest_holder = suite.init_single_party_holder(&party_a, &reinvest_pooler);
 
-    assert_eq!(5000, party_a_atom_bal.u128());
-    assert_eq!(5000, party_b_atom_bal.u128());
-    assert_eq!(5000, party_a_ls_atom_bal.u128());
-    assert_eq!(5000, party_b_ls_atom_bal.u128());
-    assert!(matches!(
-        suite.query_contract_state(),
-        ContractState::Complete {}
-    ));
-}
+    suite.claim_and_reinvest(&party_a, reinvest_holder.as_str());
 
-#[test]
-#[should_panic(expected = "unauthorized to distribute explicitly defined denom")]
-fn test_distribute_fallback_validates_denoms() {
-    let mut suite = TwoPartyHolderBuilder::default().build();
-    let sender = suite.clock_addr.to_string();
-    suite.distribute_fallback_split(&sender, vec![DENOM_ATOM_ON_NTRN.to_string()]);
+    // entitlement is deposited into the single party covenant pooler
+    suite.assert_balance(&reinvest_pooler, coin(5_000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&reinvest_pooler, coin(5_000, DENOM_LS_ATOM_ON_NTRN));
+    suite.assert_balance(&party_a, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&party_a, coin(0, DENOM_LS_ATOM_ON_NTRN));
+    assert_eq!(
+        suite.query_covenant_config().party_a.allocation,
+        Decimal::zero()
+    );
+
+    // reinvested funds still count towards the party distributions
+    let mut distributions = suite.query_distributions_by_party(&party_a);
+    distributions.sort_by(|a, b| a.denom.cmp(&b.denom));
+    assert_eq!(
+        distributions,
+        vec![
+            coin(5_000, DENOM_ATOM_ON_NTRN),
+            coin(5_000, DENOM_LS_ATOM_ON_NTRN)
+        ]
+    );
+    assert!(suite.query_claim_receipts(0).is_empty());
 }
 
 #[test]
-fn test_distribute_fallback_with_no_fallback_split_noop_happy() {
+#[should_panic(
+    expected = "reinvest target must be a single party holder withdrawn by the claiming party"
+)]
+fn test_execute_claim_with_reinvest_validates_withdrawer() {
     let mut suite = TwoPartyHolderBuilder::default().build();
-    let sender = suite.clock_addr.to_string();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
 
-    suite.fund_contract(&coins(1_000_000, DENOM_FALLBACK), suite.holder_addr.clone());
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
 
-    suite.distribute_fallback_split(&sender, vec![DENOM_FALLBACK.to_string()]);
+    // single party holder is owned by the counterparty
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+    let reinvest_pooler = suite.app.api().addr_make("reinvest_pooler");
+    let reinvest_holder = suite.init_single_party_holder(&party_b, &reinvest_pooler);
 
-    suite.assert_balance(
-        suite.holder_addr.to_string(),
-        coin(1_000_000, DENOM_FALLBACK),
-    );
+    suite.claim_and_reinvest(&party_a, reinvest_holder.as_str());
 }
 
 #[test]
-fn test_distribute_fallback_happy() {
-    let mut builder = TwoPartyHolderBuilder::default();
-    let router_a_addr = builder
-        .instantiate_msg
-        .msg
-        .covenant_config
-        .party_a
-        .router
-        .to_string();
-    let router_b_addr = builder
-        .instantiate_msg
-        .msg
-        .covenant_config
-        .party_b
-        .router
-        .to_string();
-    builder.instantiate_msg.msg.fallback_split = Some(SplitConfig {
-        receivers: vec![
-            (router_a_addr.to_string(), Decimal::percent(50)),
-            (router_b_addr.to_string(), Decimal::percent(50)),
-        ]
-        .into_iter()
-        .collect(),
-    });
+fn test_execute_claim_to_alternate_receiver() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
 
-    let mut suite = builder.build();
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
 
-    let sender = suite.clock_addr.to_string();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_a_router = suite.covenant_config.party_a.router.to_string();
+    let new_treasury = suite.app.api().addr_make("new_treasury");
 
-    suite.fund_contract(&coins(1_000_000, DENOM_FALLBACK), suite.holder_addr.clone());
+    let resp = suite.claim_to(&party_a, new_treasury.as_str());
+    resp.assert_event(
+        &Event::new("wasm").add_attribute("claim_receiver", new_treasury.to_string()),
+    );
 
-    suite.distribute_fallback_split(&sender, vec![DENOM_FALLBACK.to_string()]);
+    // entitlement is sent to the alternate receiver instead of the router
+    suite.assert_balance(&new_treasury, coin(5_000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&new_treasury, coin(5_000, DENOM_LS_ATOM_ON_NTRN));
+    suite.assert_balance(&party_a_router, coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&party_a_router, coin(0, DENOM_LS_ATOM_ON_NTRN));
+    assert_eq!(
+        suite.query_covenant_config().party_a.allocation,
+        Decimal::zero()
+    );
 
-    suite.assert_balance(suite.holder_addr.to_string(), coin(0, DENOM_FALLBACK));
-    suite.assert_balance(router_a_addr, coin(500_000, DENOM_FALLBACK));
-    suite.assert_balance(router_b_addr, coin(500_000, DENOM_FALLBACK));
+    // redirected funds still count towards the party distributions
+    let mut distributions = suite.query_distributions_by_party(&party_a);
+    distributions.sort_by(|a, b| a.denom.cmp(&b.denom));
+    assert_eq!(
+        distributions,
+        vec![
+            coin(5_000, DENOM_ATOM_ON_NTRN),
+            coin(5_000, DENOM_LS_ATOM_ON_NTRN)
+        ]
+    );
 }
 
 #[test]
-fn test_migrate_update_config() {
+#[should_panic(
+    expected = "claim receiver can not belong to the counterparty or be combined with a reinvest target"
+)]
+fn test_execute_claim_to_counterparty_router() {
     let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
 
-    let clock = suite.query_clock_addr();
-    let next_contract = suite.query_next_contract();
-    let mut covenant_config = suite.query_covenant_config();
-    let denom_splits = suite.query_denom_splits();
-    covenant_config.party_a.contribution.amount = Uint128::one();
-    let random_split = denom_splits
-        .explicit_splits
-        .get(DENOM_ATOM_ON_NTRN)
-        .unwrap();
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
 
-    suite
-        .app
-        .migrate_contract(
-            Addr::unchecked(ADMIN),
-            suite.holder_addr.clone(),
-            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
-                clock_addr: Some(next_contract.to_string()),
-                next_contract: Some(clock.to_string()),
-                emergency_committee: Some(clock.to_string()),
-                lockup_config: Some(Expiration::AtHeight(543210)),
-                deposit_deadline: Some(Expiration::AtHeight(543210)),
-                ragequit_config: Box::new(Some(RagequitConfig::Enabled(RagequitTerms {
-                    penalty: Decimal::from_str("0.123").unwrap(),
-                    state: None,
-                }))),
-                covenant_config: Box::new(Some(covenant_config)),
-                denom_splits: Some(denom_splits.explicit_splits.clone()),
-                fallback_split: Some(random_split.clone()),
-            },
-            13,
-        )
-        .unwrap();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b_router = suite.covenant_config.party_b.router.to_string();
+    suite.claim_to(&party_a, &party_b_router);
+}
 
-    let new_clock = suite.query_clock_addr();
-    let new_next_contract = suite.query_next_contract();
-    let ragequit_config = suite.query_ragequit_config();
-    let lockup_config = suite.query_lockup_config();
-    let deposit_deadline = suite.query_deposit_deadline();
-    let covenant_config = suite.query_covenant_config();
-    let denom_splits = suite.query_denom_splits();
-    let emergency_committee = suite.query_emergency_committee();
+#[test]
+fn test_execute_claim_selected_denoms() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
 
-    assert_eq!(random_split, &denom_splits.fallback_split.unwrap());
-    assert_eq!(Uint128::one(), covenant_config.party_a.contribution.amount);
-    assert_eq!(Expiration::AtHeight(543210), deposit_deadline);
-    assert_eq!(Expiration::AtHeight(543210), lockup_config);
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    suite.claim_denoms(&party_a, &[DENOM_ATOM_ON_NTRN]);
+
+    // only the selected denom is routed to the party
+    suite.assert_balance(&party_a, coin(5_000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&party_a, coin(0, DENOM_LS_ATOM_ON_NTRN));
     assert_eq!(
-        RagequitConfig::Enabled(RagequitTerms {
-            penalty: Decimal::from_str("0.123").unwrap(),
-            state: None,
-        }),
-        ragequit_config
+        suite.query_covenant_config().party_a.allocation,
+        Decimal::zero()
+    );
+    assert_eq!(
+        suite.query_retained_claim_funds(&party_a),
+        vec![coin(5_000, DENOM_LS_ATOM_ON_NTRN)]
+    );
+    assert_eq!(
+        suite.query_claim_preview(&party_a),
+        vec![coin(5_000, DENOM_LS_ATOM_ON_NTRN)]
+    );
+    assert_eq!(
+        suite.query_distributions_by_party(&party_a),
+        vec![coin(5_000, DENOM_ATOM_ON_NTRN)]
+    );
+
+    // retained funds are claimed without another withdrawal
+    suite.get_app().update_block(|b| b.height += 1);
+    suite.claim(&party_a);
+    suite.assert_balance(&party_a, coin(5_000, DENOM_LS_ATOM_ON_NTRN));
+    assert!(suite.query_retained_claim_funds(&party_a).is_empty());
+
+    let mut distributions = suite.query_distributions_by_party(&party_a);
+    distributions.sort_by(|a, b| a.denom.cmp(&b.denom));
+    assert_eq!(
+        distributions,
+        vec![
+            coin(5_000, DENOM_ATOM_ON_NTRN),
+            coin(5_000, DENOM_LS_ATOM_ON_NTRN)
+        ]
     );
-    assert_eq!(next_contract, new_clock);
-    assert_eq!(clock, new_next_contract);
-    assert_eq!(clock, emergency_committee);
 }
 
 #[test]
-#[should_panic]
-fn test_migrate_update_config_invalid_fallback_split() {
+#[should_panic(expected = "selective claims must specify at least one denom")]
+fn test_execute_claim_selected_denoms_validates_denoms() {
     let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
 
-    let denom_splits = suite.query_denom_splits();
-    let mut receivers = denom_splits
-        .explicit_splits
-        .get(DENOM_ATOM_ON_NTRN)
-        .unwrap()
-        .clone()
-        .receivers;
-    let mut receiver = receivers.pop_first().unwrap();
-    receiver.1 = Decimal::zero();
-    receivers.insert(receiver.0, receiver.1);
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
 
-    suite
-        .app
-        .migrate_contract(
-            Addr::unchecked(ADMIN),
-            suite.holder_addr.clone(),
-            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
-                clock_addr: None,
-                next_contract: None,
-                emergency_committee: None,
-                lockup_config: None,
-                deposit_deadline: None,
-                ragequit_config: Box::new(None),
-                covenant_config: Box::new(None),
-                denom_splits: None,
-                fallback_split: Some(SplitConfig { receivers }),
-            },
-            13,
-        )
-        .unwrap();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    suite.claim_denoms(&party_a, &[]);
 }
 
 #[test]
-#[should_panic]
-fn test_migrate_update_config_invalid_explicit_splits() {
+fn test_execute_claim_issues_receipts() {
     let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
 
-    let mut explicit_splits = suite.query_denom_splits().explicit_splits.clone();
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
 
-    let mut receivers = explicit_splits
-        .get(DENOM_ATOM_ON_NTRN)
-        .unwrap()
-        .clone()
-        .receivers;
+    suite.claim(&suite.covenant_config.party_a.host_addr.clone());
 
-    let mut receiver = receivers.pop_first().unwrap();
-    receiver.1 = Decimal::zero();
-    receivers.insert(receiver.0, receiver.1);
+    // routers that are plain accounts are considered delivered
+    let receipts = suite.query_claim_receipts(0);
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(
+        receipts[0].router,
+        Addr::unchecked(suite.covenant_config.party_a.router.to_string())
+    );
+    assert_eq!(receipts[0].status, DeliveryStatus::Delivered);
+    assert!(suite.query_claim_receipts(1).is_empty());
+}
 
-    explicit_splits.insert(DENOM_ATOM_ON_NTRN.to_string(), SplitConfig { receivers });
+#[test]
+#[should_panic(expected = "unknown claim id: 0")]
+fn test_execute_acknowledge_distribution_validates_claim_id() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let router = suite.covenant_config.party_a.router.to_string();
+    suite.acknowledge_distribution(&router, 0);
+}
 
-    suite
-        .app
-        .migrate_contract(
-            Addr::unchecked(ADMIN),
-            suite.holder_addr.clone(),
-            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
-                clock_addr: None,
-                next_contract: None,
-                emergency_committee: None,
-                lockup_config: None,
-                deposit_deadline: None,
-                ragequit_config: Box::new(None),
-                covenant_config: Box::new(None),
-                denom_splits: Some(explicit_splits),
+#[test]
+#[should_panic(expected = "distribution receipt is already settled")]
+fn test_execute_acknowledge_distribution_validates_status() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    suite.claim(&suite.covenant_config.party_a.host_addr.clone());
+
+    let router = suite.covenant_config.party_a.router.to_string();
+    suite.acknowledge_distribution(&router, 0);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_acknowledge_distribution_validates_router() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    suite.claim(&suite.covenant_config.party_a.host_addr.clone());
+
+    let not_the_router = suite.covenant_config.party_b.router.to_string();
+    suite.acknowledge_distribution(&not_the_router, 0);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_emergency_withdraw_validates_committee_address() {
+    let builder = TwoPartyHolderBuilder::default();
+    let clock = builder.instantiate_msg.msg.clock_address.clone();
+    let mut suite = builder.with_emergency_committee(clock.as_str()).build();
+
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    let sender = suite.faucet.clone();
+
+    suite.emergency_withdraw(sender.as_str());
+}
+
+#[test]
+fn test_execute_emergency_withdraw_happy() {
+    let builder = TwoPartyHolderBuilder::default();
+    let clock = builder.instantiate_msg.msg.clock_address.clone();
+    let mut suite = builder.with_emergency_committee(clock.as_str()).build();
+
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    suite.emergency_withdraw(clock.as_str());
+
+    let party_a = Addr::unchecked(suite.covenant_config.party_a.router.to_string());
+    let party_b = Addr::unchecked(suite.covenant_config.party_b.router.to_string());
+
+    let party_a_atom_bal = suite.query_balance(&party_a, DENOM_ATOM_ON_NTRN).amount;
+    let party_b_atom_bal = suite.query_balance(&party_b, DENOM_ATOM_ON_NTRN).amount;
+    let party_a_ls_atom_bal = suite.query_balance(&party_a, DENOM_LS_ATOM_ON_NTRN).amount;
+    let party_b_ls_atom_bal = suite.query_balance(&party_b, DENOM_LS_ATOM_ON_NTRN).amount;
+
+    assert_eq!(5000, party_a_atom_bal.u128());
+    assert_eq!(5000, party_b_atom_bal.u128());
+    assert_eq!(5000, party_a_ls_atom_bal.u128());
+    assert_eq!(5000, party_b_ls_atom_bal.u128());
+    assert!(matches!(
+        suite.query_contract_state(),
+        ContractState::Complete {}
+    ));
+}
+
+#[test]
+fn test_execute_party_rebinding_happy() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.clone();
+    let party_b = suite.covenant_config.party_b.clone();
+    let new_host_addr = suite.app.api().addr_make("new_host").to_string();
+    let new_router = suite.app.api().addr_make("new_router").to_string();
+
+    let rebinding = PartyRebinding {
+        host_addr: Some(new_host_addr.to_string()),
+        controller_addr: None,
+        router: Some(new_router.to_string()),
+    };
+    suite.request_party_rebinding(&party_a.host_addr, rebinding.clone());
+    assert_eq!(
+        suite.query_pending_party_rebinding(&party_a.host_addr),
+        Some(rebinding)
+    );
+
+    suite.approve_party_rebinding(&party_b.host_addr, &party_a.host_addr);
+
+    let covenant_config = suite.query_covenant_config();
+    assert_eq!(covenant_config.party_a.host_addr, new_host_addr);
+    assert_eq!(covenant_config.party_a.router, new_router);
+    assert_eq!(
+        covenant_config.party_a.controller_addr,
+        party_a.controller_addr
+    );
+    assert_eq!(covenant_config.party_b, party_b);
+    assert_eq!(
+        suite.query_pending_party_rebinding(&party_a.host_addr),
+        None
+    );
+
+    // split receivers follow the router of the party
+    for split in suite.query_denom_splits().explicit_splits.values() {
+        assert_eq!(
+            split.receivers.get(&new_router),
+            Some(&Decimal::from_str("0.5").unwrap())
+        );
+        assert!(!split.receivers.contains_key(&party_a.router));
+    }
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_party_rebinding_requires_counterparty_approval() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let new_host_addr = suite.app.api().addr_make("new_host").to_string();
+
+    suite.request_party_rebinding(
+        &party_a,
+        PartyRebinding {
+            host_addr: Some(new_host_addr),
+            controller_addr: None,
+            router: None,
+        },
+    );
+    suite.approve_party_rebinding(&party_a, &party_a);
+}
+
+#[test]
+#[should_panic(expected = "no pending rebinding for party")]
+fn test_execute_party_rebinding_validates_pending_request() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+
+    suite.approve_party_rebinding(&party_b, &party_a);
+}
+
+#[test]
+#[should_panic(expected = "party rebinding must replace at least one address")]
+fn test_execute_party_rebinding_validates_empty_request() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    suite.request_party_rebinding(
+        &party_a,
+        PartyRebinding {
+            host_addr: None,
+            controller_addr: None,
+            router: None,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "party rebinding conflicts with the counterparty")]
+fn test_execute_party_rebinding_validates_counterparty_addresses() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.clone();
+
+    suite.request_party_rebinding(
+        &party_a,
+        PartyRebinding {
+            host_addr: None,
+            controller_addr: None,
+            router: Some(party_b.router),
+        },
+    );
+}
+
+#[test]
+fn test_execute_fix_split_receiver() {
+    let builder = TwoPartyHolderBuilder::default();
+    let committee = builder.instantiate_msg.msg.clock_address.clone();
+    let mut suite = builder.with_emergency_committee(committee.as_str()).build();
+    let old_router = suite.covenant_config.party_a.router.to_string();
+    let new_router = suite.app.api().addr_make("new_router").to_string();
+
+    let resp = suite.fix_split_receiver(&committee, DENOM_ATOM_ON_NTRN, &old_router, &new_router);
+    assert!(resp.has_event(
+        &Event::new("wasm-split_receiver_fix_scheduled")
+            .add_attribute("denom", DENOM_ATOM_ON_NTRN)
+            .add_attribute("old", &old_router)
+            .add_attribute("new", &new_router)
+    ));
+    assert!(suite
+        .query_pending_split_receiver_fix(DENOM_ATOM_ON_NTRN)
+        .is_some());
+
+    // the split remains unchanged until the timelock expires
+    let denom_splits = suite.query_denom_splits();
+    assert!(denom_splits.explicit_splits[DENOM_ATOM_ON_NTRN]
+        .receivers
+        .contains_key(&old_router));
+
+    suite
+        .app
+        .update_block(|b| b.time = b.time.plus_seconds(SPLIT_RECEIVER_FIX_TIMELOCK_SECONDS));
+    let resp = suite.fix_split_receiver(&committee, DENOM_ATOM_ON_NTRN, &old_router, &new_router);
+    assert!(resp.has_event(&Event::new("wasm-split_receiver_fix_applied")));
+    assert!(suite
+        .query_pending_split_receiver_fix(DENOM_ATOM_ON_NTRN)
+        .is_none());
+
+    // only the split of the fixed denom is updated
+    let denom_splits = suite.query_denom_splits();
+    let atom_split = &denom_splits.explicit_splits[DENOM_ATOM_ON_NTRN].receivers;
+    assert_eq!(
+        atom_split.get(&new_router),
+        Some(&Decimal::from_str("0.5").unwrap())
+    );
+    assert!(!atom_split.contains_key(&old_router));
+    assert!(denom_splits.explicit_splits[DENOM_LS_ATOM_ON_NTRN]
+        .receivers
+        .contains_key(&old_router));
+}
+
+#[test]
+#[should_panic(expected = "split receiver fix is timelocked until")]
+fn test_execute_fix_split_receiver_validates_timelock() {
+    let builder = TwoPartyHolderBuilder::default();
+    let committee = builder.instantiate_msg.msg.clock_address.clone();
+    let mut suite = builder.with_emergency_committee(committee.as_str()).build();
+    let old_router = suite.covenant_config.party_a.router.to_string();
+    let new_router = suite.app.api().addr_make("new_router").to_string();
+
+    suite.fix_split_receiver(&committee, DENOM_ATOM_ON_NTRN, &old_router, &new_router);
+    suite.fix_split_receiver(&committee, DENOM_ATOM_ON_NTRN, &old_router, &new_router);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_fix_split_receiver_validates_committee() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.clone();
+    let new_router = suite.app.api().addr_make("new_router").to_string();
+
+    suite.fix_split_receiver(
+        &party_a.host_addr,
+        DENOM_ATOM_ON_NTRN,
+        &party_a.router,
+        &new_router,
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized to distribute explicitly defined denom")]
+fn test_distribute_fallback_validates_denoms() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let sender = suite.clock_addr.to_string();
+    suite.distribute_fallback_split(&sender, vec![DENOM_ATOM_ON_NTRN.to_string()]);
+}
+
+#[test]
+fn test_distribute_fallback_with_no_fallback_split_noop_happy() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let sender = suite.clock_addr.to_string();
+
+    suite.fund_contract(&coins(1_000_000, DENOM_FALLBACK), suite.holder_addr.clone());
+
+    suite.distribute_fallback_split(&sender, vec![DENOM_FALLBACK.to_string()]);
+
+    suite.assert_balance(
+        suite.holder_addr.to_string(),
+        coin(1_000_000, DENOM_FALLBACK),
+    );
+}
+
+#[test]
+fn test_distribute_fallback_happy() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let router_a_addr = builder
+        .instantiate_msg
+        .msg
+        .covenant_config
+        .party_a
+        .router
+        .to_string();
+    let router_b_addr = builder
+        .instantiate_msg
+        .msg
+        .covenant_config
+        .party_b
+        .router
+        .to_string();
+    builder.instantiate_msg.msg.fallback_split = Some(SplitConfig {
+        receivers: vec![
+            (router_a_addr.to_string(), Decimal::percent(50)),
+            (router_b_addr.to_string(), Decimal::percent(50)),
+        ]
+        .into_iter()
+        .collect(),
+        rounding: Default::default(),
+        min_amounts: Default::default(),
+    });
+
+    let mut suite = builder.build();
+
+    let sender = suite.clock_addr.to_string();
+
+    suite.fund_contract(&coins(1_000_000, DENOM_FALLBACK), suite.holder_addr.clone());
+
+    suite.distribute_fallback_split(&sender, vec![DENOM_FALLBACK.to_string()]);
+
+    suite.assert_balance(suite.holder_addr.to_string(), coin(0, DENOM_FALLBACK));
+    suite.assert_balance(router_a_addr, coin(500_000, DENOM_FALLBACK));
+    suite.assert_balance(router_b_addr, coin(500_000, DENOM_FALLBACK));
+}
+
+#[test]
+fn test_distribute_fallback_accrues_below_min_amount() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let router_a_addr = builder
+        .instantiate_msg
+        .msg
+        .covenant_config
+        .party_a
+        .router
+        .to_string();
+    let router_b_addr = builder
+        .instantiate_msg
+        .msg
+        .covenant_config
+        .party_b
+        .router
+        .to_string();
+    builder.instantiate_msg.msg.fallback_split = Some(SplitConfig {
+        receivers: vec![
+            (router_a_addr.to_string(), Decimal::percent(50)),
+            (router_b_addr.to_string(), Decimal::percent(50)),
+        ]
+        .into_iter()
+        .collect(),
+        rounding: Default::default(),
+        min_amounts: BTreeMap::from([(router_a_addr.to_string(), Uint128::new(600_000))]),
+    });
+
+    let mut suite = builder.build();
+    let sender = suite.clock_addr.to_string();
+
+    // router a share is below its min amount and accrues
+    suite.fund_contract(&coins(1_000_000, DENOM_FALLBACK), suite.holder_addr.clone());
+    suite.distribute_fallback_split(&sender, vec![DENOM_FALLBACK.to_string()]);
+
+    suite.assert_balance(suite.holder_addr.to_string(), coin(500_000, DENOM_FALLBACK));
+    suite.assert_balance(&router_a_addr, coin(0, DENOM_FALLBACK));
+    suite.assert_balance(&router_b_addr, coin(500_000, DENOM_FALLBACK));
+    assert_eq!(
+        suite.query_accrued_distributions(DENOM_FALLBACK),
+        BTreeMap::from([(router_a_addr.to_string(), Uint128::new(500_000))])
+    );
+
+    // accrued amounts are flushed once the covenant is complete
+    suite.app.update_block(|b| b.height = 200000);
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Complete {});
+    suite.distribute_fallback_split(&sender, vec![DENOM_FALLBACK.to_string()]);
+
+    suite.assert_balance(suite.holder_addr.to_string(), coin(0, DENOM_FALLBACK));
+    suite.assert_balance(&router_a_addr, coin(500_000, DENOM_FALLBACK));
+    suite.assert_balance(&router_b_addr, coin(500_000, DENOM_FALLBACK));
+    assert!(suite.query_accrued_distributions(DENOM_FALLBACK).is_empty());
+}
+
+#[test]
+fn test_migrate_update_config() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+
+    let clock = suite.query_clock_addr();
+    let next_contract = suite.query_next_contract();
+    let mut covenant_config = suite.query_covenant_config();
+    let denom_splits = suite.query_denom_splits();
+    covenant_config.party_a.contribution.amount = Uint128::one();
+    let random_split = denom_splits
+        .explicit_splits
+        .get(DENOM_ATOM_ON_NTRN)
+        .unwrap();
+
+    suite
+        .app
+        .migrate_contract(
+            Addr::unchecked(ADMIN),
+            suite.holder_addr.clone(),
+            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+                clock_addr: Some(next_contract.to_string()),
+                next_contract: Some(clock.to_string()),
+                emergency_committee: Some(clock.to_string()),
+                lockup_config: Some(Expiration::AtHeight(543210)),
+                deposit_deadline: Some(Expiration::AtHeight(543210)),
+                ragequit_config: Box::new(Some(RagequitConfig::Enabled(RagequitTerms {
+                    penalty: Decimal::from_str("0.123").unwrap().into(),
+                    state: None,
+                }))),
+                covenant_config: Box::new(Some(covenant_config)),
+                denom_splits: Some(denom_splits.explicit_splits.clone()),
+                fallback_split: Some(random_split.clone()),
+            },
+            13,
+        )
+        .unwrap();
+
+    let new_clock = suite.query_clock_addr();
+    let new_next_contract = suite.query_next_contract();
+    let ragequit_config = suite.query_ragequit_config();
+    let lockup_config = suite.query_lockup_config();
+    let deposit_deadline = suite.query_deposit_deadline();
+    let covenant_config = suite.query_covenant_config();
+    let denom_splits = suite.query_denom_splits();
+    let emergency_committee = suite.query_emergency_committee();
+
+    assert_eq!(random_split, &denom_splits.fallback_split.unwrap());
+    assert_eq!(Uint128::one(), covenant_config.party_a.contribution.amount);
+    assert_eq!(Expiration::AtHeight(543210), deposit_deadline);
+    assert_eq!(Expiration::AtHeight(543210), lockup_config);
+    assert_eq!(
+        RagequitConfig::Enabled(RagequitTerms {
+            penalty: Decimal::from_str("0.123").unwrap().into(),
+            state: None,
+        }),
+        ragequit_config
+    );
+    assert_eq!(next_contract, new_clock);
+    assert_eq!(clock, new_next_contract);
+    assert_eq!(clock, emergency_committee);
+}
+
+#[test]
+#[should_panic]
+fn test_migrate_update_config_invalid_fallback_split() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+
+    let denom_splits = suite.query_denom_splits();
+    let mut receivers = denom_splits
+        .explicit_splits
+        .get(DENOM_ATOM_ON_NTRN)
+        .unwrap()
+        .clone()
+        .receivers;
+    let mut receiver = receivers.pop_first().unwrap();
+    receiver.1 = Decimal::zero();
+    receivers.insert(receiver.0, receiver.1);
+
+    suite
+        .app
+        .migrate_contract(
+            Addr::unchecked(ADMIN),
+            suite.holder_addr.clone(),
+            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+                clock_addr: None,
+                next_contract: None,
+                emergency_committee: None,
+                lockup_config: None,
+                deposit_deadline: None,
+                ragequit_config: Box::new(None),
+                covenant_config: Box::new(None),
+                denom_splits: None,
+                fallback_split: Some(SplitConfig {
+                    receivers,
+                    rounding: Default::default(),
+                    min_amounts: Default::default(),
+                }),
+            },
+            13,
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic]
+fn test_migrate_update_config_invalid_explicit_splits() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+
+    let mut explicit_splits = suite.query_denom_splits().explicit_splits.clone();
+
+    let mut receivers = explicit_splits
+        .get(DENOM_ATOM_ON_NTRN)
+        .unwrap()
+        .clone()
+        .receivers;
+
+    let mut receiver = receivers.pop_first().unwrap();
+    receiver.1 = Decimal::zero();
+    receivers.insert(receiver.0, receiver.1);
+
+    explicit_splits.insert(
+        DENOM_ATOM_ON_NTRN.to_string(),
+        SplitConfig {
+            receivers,
+            rounding: Default::default(),
+            min_amounts: Default::default(),
+        },
+    );
+
+    suite
+        .app
+        .migrate_contract(
+            Addr::unchecked(ADMIN),
+            suite.holder_addr.clone(),
+            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+                clock_addr: None,
+                next_contract: None,
+                emergency_committee: None,
+                lockup_config: None,
+                deposit_deadline: None,
+                ragequit_config: Box::new(None),
+                covenant_config: Box::new(None),
+                denom_splits: Some(explicit_splits),
+                fallback_split: None,
+            },
+            13,
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "lockup config is already past")]
+fn test_migrate_update_config_validates_lockup_config_expiration() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite
+        .app
+        .migrate_contract(
+            Addr::unchecked(ADMIN),
+            suite.holder_addr.clone(),
+            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+                clock_addr: None,
+                next_contract: None,
+                emergency_committee: None,
+                lockup_config: Some(Expiration::AtHeight(1)),
+                deposit_deadline: None,
+                ragequit_config: Box::new(None),
+                covenant_config: Box::new(None),
+                denom_splits: None,
+                fallback_split: None,
+            },
+            13,
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "deposit deadline is already past")]
+fn test_migrate_update_config_validates_deposit_deadline_expiration() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite
+        .app
+        .migrate_contract(
+            Addr::unchecked(ADMIN),
+            suite.holder_addr.clone(),
+            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
+                clock_addr: None,
+                next_contract: None,
+                emergency_committee: None,
+                lockup_config: None,
+                deposit_deadline: Some(Expiration::AtHeight(1)),
+                ragequit_config: Box::new(None),
+                covenant_config: Box::new(None),
+                denom_splits: None,
                 fallback_split: None,
             },
             13,
@@ -919,51 +2134,980 @@ fn test_migrate_update_config_invalid_explicit_splits() {
 }
 
 #[test]
-#[should_panic(expected = "lockup config is already past")]
-fn test_migrate_update_config_validates_lockup_config_expiration() {
-    let mut suite = TwoPartyHolderBuilder::default().build();
+fn test_query_dashboard() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+
+    let dashboard = suite.query_dashboard();
+    assert_eq!(dashboard.contract_state, ContractState::Instantiated);
+    assert_eq!(dashboard.liquid_pooler_address, suite.next_contract);
+    assert_eq!(dashboard.covenant_config, suite.covenant_config);
+    assert!(!dashboard.lockup.expired);
+    assert!(dashboard.party_a_claimable.is_empty());
+    assert!(dashboard.party_b_claimable.is_empty());
+
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    let dashboard = suite.query_dashboard();
+    assert_eq!(dashboard.contract_state, ContractState::Active);
+    assert!(!dashboard.pool_position.unwrap().lp_balance.is_zero());
+
+    // each party is entitled to half of the position
+    for claimable in [dashboard.party_a_claimable, dashboard.party_b_claimable] {
+        assert_eq!(claimable.len(), 2);
+        for coin in claimable {
+            assert!(!coin.amount.is_zero());
+            assert!(coin.amount <= Uint128::new(5_001));
+        }
+    }
+}
+
+#[test]
+fn test_query_claim_preview() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    // claims are not possible while the covenant is active
+    assert!(suite.query_claim_preview(&party_a).is_empty());
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    let mut expected = vec![
+        coin(5_000, DENOM_ATOM_ON_NTRN),
+        coin(5_000, DENOM_LS_ATOM_ON_NTRN),
+    ];
+    expected.sort_by(|a, b| a.denom.cmp(&b.denom));
+    assert_eq!(suite.query_claim_preview(&party_a), expected);
+
+    suite.claim(&party_a);
+    for coin in expected {
+        suite.assert_balance(party_a.to_string(), coin);
+    }
+    assert!(suite.query_claim_preview(&party_a).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "is not a covenant party")]
+fn test_query_claim_preview_validates_party() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let faucet = suite.faucet.to_string();
+    suite.query_claim_preview(&faucet);
+}
+
+#[test]
+fn test_claim_distributes_unknown_denoms_via_fallback_split() {
+    let builder = TwoPartyHolderBuilder::default();
+    let mut splits = builder.instantiate_msg.msg.splits.clone();
+    let ls_atom_split = splits.remove(DENOM_LS_ATOM_ON_NTRN).unwrap();
+    let mut suite = builder
+        .with_splits(splits)
+        .with_fallback_split(ls_atom_split)
+        .build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    let mut expected = vec![
+        coin(5_000, DENOM_ATOM_ON_NTRN),
+        coin(5_000, DENOM_LS_ATOM_ON_NTRN),
+    ];
+    expected.sort_by(|a, b| a.denom.cmp(&b.denom));
+    assert_eq!(suite.query_claim_preview(&party_a), expected);
+
+    suite.claim(&party_a);
+    for coin in expected {
+        suite.assert_balance(party_a.to_string(), coin);
+    }
+}
+
+#[test]
+fn test_claim_reports_undistributed_denoms() {
+    let builder = TwoPartyHolderBuilder::default();
+    let mut splits = builder.instantiate_msg.msg.splits.clone();
+    splits.remove(DENOM_LS_ATOM_ON_NTRN);
+    let mut suite = builder.with_splits(splits).build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    suite.claim(&party_a).assert_event(
+        &Event::new("wasm")
+            .add_attribute("method", "claim_share_based")
+            .add_attribute("undistributed_denoms", DENOM_LS_ATOM_ON_NTRN),
+    );
+    suite.assert_balance(party_a.to_string(), coin(5_000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(party_a.to_string(), coin(0, DENOM_LS_ATOM_ON_NTRN));
+}
+
+fn build_vesting_suite() -> super::suite::Suite {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_vesting_config(VestingConfig {
+            start: Expiration::AtHeight(150_000),
+            end: Expiration::AtHeight(190_000),
+        })
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+    suite
+}
+
+#[test]
+#[should_panic(expected = "vesting is only supported by share based covenants")]
+fn test_instantiate_validates_vesting_covenant_type() {
+    let mut builder = TwoPartyHolderBuilder::default().with_vesting_config(VestingConfig {
+        start: Expiration::AtHeight(150_000),
+        end: Expiration::AtHeight(190_000),
+    });
+    builder.instantiate_msg.msg.covenant_config.covenant_type = CovenantType::Side;
+    builder.build();
+}
+
+#[test]
+#[should_panic(
+    expected = "vesting must end after it starts, with both expirations of the same unit"
+)]
+fn test_instantiate_validates_vesting_schedule() {
+    TwoPartyHolderBuilder::default()
+        .with_vesting_config(VestingConfig {
+            start: Expiration::AtHeight(150_000),
+            end: Expiration::AtTime(Timestamp::from_seconds(190_000)),
+        })
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "no allocation vested since the previous claim")]
+fn test_claim_before_vesting_start() {
+    let mut suite = build_vesting_suite();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    assert!(suite.query_claim_preview(&party_a).is_empty());
+    suite.claim(&party_a);
+}
+
+#[test]
+fn test_claim_vested_allocation() {
+    let mut suite = build_vesting_suite();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    // a quarter of the allocations vested
+    suite.app.update_block(|b| b.height = 160_000);
+    assert!(!suite.query_claim_preview(&party_a).is_empty());
+    suite.claim(&party_a);
+    assert!(!suite
+        .query_all_balances(&Addr::unchecked(&party_a))
+        .is_empty());
+    assert_eq!(
+        suite.query_vesting_claimed_share(&party_a),
+        Decimal::from_str("0.25").unwrap()
+    );
+
+    // the remaining allocations are rescaled to the remaining position
+    let covenant_config = suite.query_covenant_config();
+    assert_eq!(
+        covenant_config.party_a.allocation,
+        Decimal::from_str("0.428571428571428571").unwrap()
+    );
+    assert_eq!(
+        covenant_config.party_b.allocation,
+        Decimal::from_str("0.571428571428571428").unwrap()
+    );
+
+    // the whole allocation vests before the lockup expires
+    suite.app.update_block(|b| b.height = 190_000);
+    suite.claim(&party_a);
+    let covenant_config = suite.query_covenant_config();
+    assert_eq!(covenant_config.party_a.allocation, Decimal::zero());
+    assert_eq!(covenant_config.party_b.allocation, Decimal::one());
+    assert!(matches!(
+        suite.query_contract_state(),
+        ContractState::Active {}
+    ));
+}
+
+#[test]
+fn test_execute_lockup_extension_happy() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+    let block_time = suite.app.block_info().time;
+
+    suite.propose_lockup_extension(&party_a, Expiration::AtHeight(300000));
+    assert_eq!(
+        suite.query_pending_lockup_extension(),
+        Some(LockupExtensionProposal {
+            proposer: party_a,
+            new_expiry: Expiration::AtHeight(300000),
+            expires_at: block_time.plus_seconds(DEFAULT_LOCKUP_EXTENSION_WINDOW),
+        })
+    );
+
+    suite.approve_lockup_extension(&party_b);
+
+    assert_eq!(suite.query_lockup_config(), Expiration::AtHeight(300000));
+    assert_eq!(suite.query_pending_lockup_extension(), None);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_lockup_extension_requires_counterparty_approval() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    suite.propose_lockup_extension(&party_a, Expiration::AtHeight(300000));
+    suite.approve_lockup_extension(&party_a);
+}
+
+#[test]
+#[should_panic(expected = "no pending lockup extension")]
+fn test_execute_lockup_extension_validates_pending_proposal() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+
+    suite.approve_lockup_extension(&party_b);
+}
+
+#[test]
+#[should_panic(expected = "lockup extension must expire after the current lockup")]
+fn test_execute_lockup_extension_validates_new_expiry() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    suite.propose_lockup_extension(&party_a, Expiration::AtHeight(150000));
+}
+
+#[test]
+#[should_panic(expected = "lockup extension must expire after the current lockup")]
+fn test_execute_lockup_extension_validates_new_expiry_unit() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let block_time = suite.app.block_info().time;
+
+    suite.propose_lockup_extension(&party_a, Expiration::AtTime(block_time.plus_days(365)));
+}
+
+#[test]
+#[should_panic(expected = "lockup extension proposal expired")]
+fn test_execute_lockup_extension_validates_proposal_window() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_lockup_extension_window(100)
+        .build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+
+    suite.propose_lockup_extension(&party_a, Expiration::AtHeight(300000));
+    suite
+        .app
+        .update_block(|b| b.time = b.time.plus_seconds(100));
+    suite.approve_lockup_extension(&party_b);
+}
+
+#[test]
+fn test_execute_amendment_happy() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+    let amendment = TermsAmendment {
+        lockup_config: Some(Expiration::AtHeight(300000)),
+        ragequit_config: Some(RagequitConfig::Disabled),
+        ..Default::default()
+    };
+    let hash = amendment.get_hash().unwrap();
+
+    suite.propose_amendment(&party_a, amendment.clone());
+    let proposal = suite.query_pending_amendment().unwrap();
+    assert_eq!(proposal.proposer, party_a);
+    assert_eq!(proposal.amendment, amendment);
+    assert_eq!(proposal.hash, hash);
+    assert_eq!(suite.query_amendment_count(), 0);
+
+    suite.approve_amendment(&party_b, hash.clone());
+
+    assert_eq!(suite.query_lockup_config(), Expiration::AtHeight(300000));
+    assert_eq!(suite.query_ragequit_config(), RagequitConfig::Disabled);
+    assert_eq!(suite.query_pending_amendment(), None);
+    assert_eq!(suite.query_amendment_count(), 1);
+    let record = suite.query_amendment(0);
+    assert_eq!(record.index, 0);
+    assert_eq!(record.proposer, party_a);
+    assert_eq!(record.approver, party_b);
+    assert_eq!(record.amendment, amendment);
+    assert_eq!(record.hash, hash);
+    assert_eq!(record.height, suite.app.block_info().height);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_amendment_requires_counterparty_approval() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let amendment = TermsAmendment {
+        lockup_config: Some(Expiration::AtHeight(300000)),
+        ..Default::default()
+    };
+    let hash = amendment.get_hash().unwrap();
+
+    suite.propose_amendment(&party_a, amendment);
+    suite.approve_amendment(&party_a, hash);
+}
+
+#[test]
+#[should_panic(expected = "amendment hash does not match the pending amendment")]
+fn test_execute_amendment_validates_hash() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+    let inspected = TermsAmendment {
+        lockup_config: Some(Expiration::AtHeight(300000)),
+        ..Default::default()
+    };
+
+    // the proposal gets replaced before the counterparty approves it
+    suite.propose_amendment(&party_a, inspected.clone());
+    suite.propose_amendment(
+        &party_a,
+        TermsAmendment {
+            lockup_config: Some(Expiration::AtHeight(900000)),
+            ..Default::default()
+        },
+    );
+    suite.approve_amendment(&party_b, inspected.get_hash().unwrap());
+}
+
+#[test]
+#[should_panic(expected = "amendment must change at least one term")]
+fn test_execute_amendment_validates_empty_amendment() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    suite.propose_amendment(&party_a, TermsAmendment::default());
+}
+
+#[test]
+#[should_panic(expected = "lockup can only be extended before it expires")]
+fn test_execute_lockup_extension_validates_lockup_expiry() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    suite.expire_lockup_config();
+    suite.propose_lockup_extension(&party_a, Expiration::AtHeight(300000));
+}
+
+#[test]
+fn test_execute_extend_deposit_deadline_happy() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+
+    suite.extend_deposit_deadline(&party_a, Expiration::AtHeight(150000));
+    assert_eq!(
+        suite.query_pending_deposit_deadline_extension(),
+        Some(DepositDeadlineExtension {
+            proposer: party_a,
+            expiry: Expiration::AtHeight(150000),
+        })
+    );
+    assert_eq!(suite.query_deposit_deadline(), Expiration::AtHeight(100000));
+
+    suite.extend_deposit_deadline(&party_b, Expiration::AtHeight(150000));
+    assert_eq!(suite.query_deposit_deadline(), Expiration::AtHeight(150000));
+    assert_eq!(suite.query_pending_deposit_deadline_extension(), None);
+
+    // deposits landing after the original deadline are still accepted
+    suite.app.update_block(|b| b.height = 120000);
+    suite.fund_contract(
+        &[
+            coin(10_000, DENOM_ATOM_ON_NTRN),
+            coin(10_000, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+}
+
+#[test]
+fn test_execute_extend_deposit_deadline_requires_matching_approvals() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+    let party_b = suite.covenant_config.party_b.host_addr.to_string();
+
+    // resubmitting or submitting a different expiry replaces the pending extension
+    suite.extend_deposit_deadline(&party_a, Expiration::AtHeight(150000));
+    suite.extend_deposit_deadline(&party_a, Expiration::AtHeight(150000));
+    suite.extend_deposit_deadline(&party_b, Expiration::AtHeight(160000));
+    assert_eq!(suite.query_deposit_deadline(), Expiration::AtHeight(100000));
+    assert_eq!(
+        suite.query_pending_deposit_deadline_extension(),
+        Some(DepositDeadlineExtension {
+            proposer: party_b,
+            expiry: Expiration::AtHeight(160000),
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_execute_extend_deposit_deadline_validates_sender() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    suite.extend_deposit_deadline(ADMIN, Expiration::AtHeight(150000));
+}
+
+#[test]
+#[should_panic(
+    expected = "deposit deadline extension must expire after the current deadline and before the lockup"
+)]
+fn test_execute_extend_deposit_deadline_validates_expiry() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    suite.extend_deposit_deadline(&party_a, Expiration::AtHeight(200000));
+}
+
+#[test]
+#[should_panic(expected = "deposit deadline can only be extended before it expires")]
+fn test_execute_extend_deposit_deadline_validates_deadline_expiry() {
+    let mut suite = TwoPartyHolderBuilder::default().build();
+    let party_a = suite.covenant_config.party_a.host_addr.to_string();
+
+    suite.app.update_block(|b| b.height = 100000);
+    suite.extend_deposit_deadline(&party_a, Expiration::AtHeight(150000));
+}
+
+#[test]
+fn test_execute_tick_auto_distributes_on_expiry() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_auto_distribute_on_expiry(true)
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Expired {});
+
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "try_auto_distribute"));
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "auto_distribute_share_based"));
+
+    assert_eq!(suite.query_contract_state(), ContractState::Complete {});
+    let covenant_config = suite.query_covenant_config();
+    assert!(covenant_config.party_a.allocation.is_zero());
+    assert!(covenant_config.party_b.allocation.is_zero());
+    assert!(suite
+        .query_lp_token_balance(&suite.next_contract.clone())
+        .is_zero());
+
+    // both parties receive an equal share without claiming
+    let party_a = Addr::unchecked(suite.covenant_config.party_a.router.to_string());
+    let party_b = Addr::unchecked(suite.covenant_config.party_b.router.to_string());
+    for denom in [DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN] {
+        let party_a_bal = suite.query_balance(&party_a, denom).amount;
+        let party_b_bal = suite.query_balance(&party_b, denom).amount;
+        assert!(!party_a_bal.is_zero());
+        assert!(party_a_bal.abs_diff(party_b_bal) <= Uint128::one());
+    }
+}
+
+#[test]
+fn test_execute_tick_auto_distributes_remaining_position() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_auto_distribute_on_expiry(true)
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    // party a claims before the auto distribution kicks in
+    suite.claim(&suite.covenant_config.party_a.host_addr.clone());
+    let party_a = Addr::unchecked(suite.covenant_config.party_a.router.to_string());
+    let party_b = Addr::unchecked(suite.covenant_config.party_b.router.to_string());
+    assert_eq!(
+        suite.query_balance(&party_a, DENOM_ATOM_ON_NTRN),
+        coin(5_000, DENOM_ATOM_ON_NTRN)
+    );
+
+    suite.tick_contract(suite.holder_addr.clone());
+
+    // the rest of the position goes to party b
+    assert_eq!(suite.query_contract_state(), ContractState::Complete {});
+    assert_eq!(
+        suite.query_balance(&party_a, DENOM_ATOM_ON_NTRN),
+        coin(5_000, DENOM_ATOM_ON_NTRN)
+    );
+    assert!(!suite
+        .query_balance(&party_b, DENOM_ATOM_ON_NTRN)
+        .amount
+        .is_zero());
+    assert!(!suite
+        .query_balance(&party_b, DENOM_LS_ATOM_ON_NTRN)
+        .amount
+        .is_zero());
+}
+
+#[test]
+fn test_execute_claim_refunds_fees_to_fee_payer() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let fee_payer = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_fee_refund_config(FeeRefundConfig {
+            fee_payer: fee_payer.to_string(),
+            reserve: Uint128::new(1_000),
+        })
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+
+    // ack fee refunds accumulated by the holder
+    suite.fund_contract(&coins(5_000, DENOM_NTRN), suite.holder_addr.clone());
+
+    suite.claim(&suite.covenant_config.party_a.host_addr.clone());
+
+    // untrn beyond the reserve goes back to the fee payer, not to the parties
+    suite.assert_balance(&fee_payer, coin(4_000, DENOM_NTRN));
+    suite.assert_balance(&suite.holder_addr, coin(1_000, DENOM_NTRN));
+    let party_a = Addr::unchecked(suite.covenant_config.party_a.router.to_string());
+    suite.assert_balance(&party_a, coin(0, DENOM_NTRN));
+    suite.assert_balance(&party_a, coin(5_000, DENOM_ATOM_ON_NTRN));
+}
+
+#[test]
+fn test_distribute_fallback_refunds_fees_to_fee_payer() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let fee_payer = builder.builder.get_random_addr();
+    let mut suite = builder
+        .with_fee_refund_config(FeeRefundConfig {
+            fee_payer: fee_payer.to_string(),
+            reserve: Uint128::new(1_000),
+        })
+        .build();
+    let sender = suite.clock_addr.to_string();
+
+    suite.fund_contract(&coins(10_000, DENOM_NTRN), suite.holder_addr.clone());
+    suite.distribute_fallback_split(&sender, vec![DENOM_NTRN.to_string()]);
+
+    suite.assert_balance(&fee_payer, coin(9_000, DENOM_NTRN));
+    suite.assert_balance(&suite.holder_addr, coin(1_000, DENOM_NTRN));
+}
+
+/// builds a holder staking its LP position into a mock generator
+/// paying out 1_000untrn on every claim
+fn build_generator_staking_suite() -> (super::suite::Suite, Addr) {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let generator_code_id = builder.builder.app.store_code(generator_contract());
+    let generator = builder.builder.contract_init(
+        generator_code_id,
+        "generator".to_string(),
+        &GeneratorInstantiateMsg {
+            reward: coin(1_000, DENOM_NTRN),
+        },
+        &[],
+    );
+    let faucet = builder.builder.faucet.clone();
+    builder
+        .builder
+        .app
+        .send_tokens(faucet, generator.clone(), &[coin(10_000, DENOM_NTRN)])
+        .unwrap();
+
+    let mut splits = builder.instantiate_msg.msg.splits.clone();
+    let atom_split = splits[DENOM_ATOM_ON_NTRN].clone();
+    splits.insert(DENOM_NTRN.to_string(), atom_split);
+    let lp_token = builder.lp_token_addr.to_string();
+    let suite = builder
+        .with_splits(splits)
+        .with_generator_staking(GeneratorStakingConfig {
+            generator_address: generator.to_string(),
+            lp_token,
+            reward_denoms: vec![DENOM_NTRN.to_string()],
+        })
+        .build();
+
+    (suite, generator)
+}
+
+#[test]
+fn test_execute_tick_stakes_lp_and_distributes_rewards() {
+    let (mut suite, generator) = build_generator_staking_suite();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    // liquidity provided by the pooler gets staked on behalf of the holder
+    let lp_position = suite.query_lp_token_balance(&suite.next_contract.clone());
+    assert!(!lp_position.is_zero());
+    suite.tick_contract(suite.holder_addr.clone());
+    assert!(suite
+        .query_lp_token_balance(&suite.next_contract.clone())
+        .is_zero());
+    assert_eq!(
+        suite.query_generator_deposit(&generator, &suite.holder_addr.clone()),
+        lp_position
+    );
+
+    // rewards are claimed on tick and distributed on the following one
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.assert_balance(&suite.holder_addr.clone(), coin(1_000, DENOM_NTRN));
+    suite.tick_contract(suite.holder_addr.clone());
+
+    let party_a = Addr::unchecked(suite.covenant_config.party_a.router.to_string());
+    let party_b = Addr::unchecked(suite.covenant_config.party_b.router.to_string());
+    suite.assert_balance(&party_a, coin(500, DENOM_NTRN));
+    suite.assert_balance(&party_b, coin(500, DENOM_NTRN));
+    suite.assert_balance(&suite.holder_addr.clone(), coin(1_000, DENOM_NTRN));
+    assert_eq!(
+        suite.query_distributions_by_party(&suite.covenant_config.party_a.host_addr.clone()),
+        vec![coin(500, DENOM_NTRN)]
+    );
+}
+
+#[test]
+fn test_execute_claim_unstakes_lp_position() {
+    let (mut suite, generator) = build_generator_staking_suite();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+    suite.tick_contract(suite.holder_addr.clone());
+    assert!(!suite
+        .query_generator_deposit(&generator, &suite.holder_addr.clone())
+        .is_zero());
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Expired {});
+
+    suite.claim(&suite.covenant_config.party_a.host_addr.clone());
+
+    // the whole position is unstaked, only the claimed share withdrawn
+    assert!(suite
+        .query_generator_deposit(&generator, &suite.holder_addr.clone())
+        .is_zero());
+    assert!(!suite
+        .query_lp_token_balance(&suite.next_contract.clone())
+        .is_zero());
+    let party_a = Addr::unchecked(suite.covenant_config.party_a.host_addr.to_string());
+    suite.assert_balance(&party_a, coin(5_000, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(&party_a, coin(5_000, DENOM_LS_ATOM_ON_NTRN));
+}
+
+#[test]
+fn test_execute_tick_reports_provisioning_stall() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_provisioning_stall(ProvisioningStallConfig {
+            max_stalled_ticks: 1,
+            refund_timeout: None,
+        })
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+
+    // the liquid pooler holds the deposits without providing liquidity
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    assert!(!resp.has_event(&Event::new("wasm-provisioning_stalled")));
+    let since = suite.app.block_info().time;
+
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    resp.assert_event(
+        &Event::new("wasm-provisioning_stalled")
+            .add_attribute("stalled_ticks", "2")
+            .add_attribute("stalled_since", since.to_string()),
+    );
+    assert_eq!(suite.query_provisioning_stall().unwrap().stalled_ticks, 2);
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    // the stall is cleared once the liquidity gets provided
+    suite.tick_contract(suite.next_contract.clone());
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    assert!(!resp.has_event(&Event::new("wasm-provisioning_stalled")));
+    assert!(suite.query_provisioning_stall().is_none());
+}
+
+/// depeg guard pricing the covenant pool against an xyk pool pricing
+/// ls atom at 2 atom, so that the balanced covenant pool is depegged
+fn get_depeg_guard_config(
+    builder: &mut TwoPartyHolderBuilder,
+    action: DepegAction,
+) -> DepegGuardConfig {
+    let price_pool = builder.init_price_pool(2_000_000_000, 1_000_000_000);
+    let pool_addr = builder.pool_addr.clone();
+    builder.observe_pool_prices(&[pool_addr.clone(), price_pool.clone()]);
+
+    DepegGuardConfig {
+        pool_price: PriceQuery {
+            source: PriceSource::AstroportTwap {
+                pool_address: pool_addr.to_string(),
+                seconds_ago: 0,
+            },
+            base: DENOM_LS_ATOM_ON_NTRN.to_string(),
+            quote: DENOM_ATOM_ON_NTRN.to_string(),
+        },
+        fair_price: PriceGuard {
+            source: PriceSource::AstroportTwap {
+                pool_address: price_pool.to_string(),
+                seconds_ago: 0,
+            },
+            base: DENOM_LS_ATOM_ON_NTRN.to_string(),
+            quote: DENOM_ATOM_ON_NTRN.to_string(),
+            max_deviation: Decimal::percent(10),
+        },
+        max_duration: 100,
+        action,
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "depeg guard expects the pool price of a pool twap and a max deviation in the range of (0.00, 1.00)"
+)]
+fn test_instantiate_validates_depeg_guard() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let mut config = get_depeg_guard_config(&mut builder, DepegAction::PauseProvisioning);
+    config.pool_price.source = PriceSource::Slinky {};
+    builder.with_depeg_guard(config).build();
+}
+
+#[test]
+fn test_execute_tick_depeg_guard_pauses_provisioning() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let config = get_depeg_guard_config(&mut builder, DepegAction::PauseProvisioning);
+    let mut suite = builder.with_depeg_guard(config).build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    assert!(!resp.has_event(&Event::new("wasm-depeg_guard")));
+    let since = suite.app.block_info().time;
+    assert_eq!(suite.query_depeg().unwrap().since, since);
+    assert!(!suite.query_provisioning_paused());
+
     suite
         .app
-        .migrate_contract(
-            Addr::unchecked(ADMIN),
-            suite.holder_addr.clone(),
-            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
-                clock_addr: None,
-                next_contract: None,
-                emergency_committee: None,
-                lockup_config: Some(Expiration::AtHeight(1)),
-                deposit_deadline: None,
-                ragequit_config: Box::new(None),
-                covenant_config: Box::new(None),
-                denom_splits: None,
-                fallback_split: None,
-            },
-            13,
-        )
-        .unwrap();
+        .update_block(|b| b.time = b.time.plus_seconds(100));
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    resp.assert_event(
+        &Event::new("wasm-depeg_guard")
+            .add_attribute("action", "pause_provisioning")
+            .add_attribute("depegged_since", since.to_string()),
+    );
+    assert!(suite.query_depeg().unwrap().acted);
+    assert!(suite.query_provisioning_paused());
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    // the action is only taken once
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    assert!(!resp.has_event(&Event::new("wasm-depeg_guard")));
 }
 
 #[test]
-#[should_panic(expected = "deposit deadline is already past")]
-fn test_migrate_update_config_validates_deposit_deadline_expiration() {
-    let mut suite = TwoPartyHolderBuilder::default().build();
+fn test_execute_tick_depeg_guard_ragequits_both_parties() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let config = get_depeg_guard_config(&mut builder, DepegAction::Ragequit);
+    let mut suite = builder.with_depeg_guard(config).build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+    suite.tick_contract(suite.holder_addr.clone());
+
     suite
         .app
-        .migrate_contract(
-            Addr::unchecked(ADMIN),
-            suite.holder_addr.clone(),
-            &valence_two_party_pol_holder::msg::MigrateMsg::UpdateConfig {
-                clock_addr: None,
-                next_contract: None,
-                emergency_committee: None,
-                lockup_config: None,
-                deposit_deadline: Some(Expiration::AtHeight(1)),
-                ragequit_config: Box::new(None),
-                covenant_config: Box::new(None),
-                denom_splits: None,
-                fallback_split: None,
-            },
-            13,
-        )
-        .unwrap();
+        .update_block(|b| b.time = b.time.plus_seconds(100));
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    resp.assert_event(&Event::new("wasm-depeg_guard").add_attribute("action", "ragequit"));
+    resp.assert_event(&Event::new("wasm").add_attribute("method", "auto_distribute_share_based"));
+
+    assert_eq!(suite.query_contract_state(), ContractState::Complete {});
+    assert!(suite
+        .query_lp_token_balance(&suite.next_contract.clone())
+        .is_zero());
+
+    // both parties receive their allocations without any penalty
+    let party_a = Addr::unchecked(suite.covenant_config.party_a.router.to_string());
+    let party_b = Addr::unchecked(suite.covenant_config.party_b.router.to_string());
+    for denom in [DENOM_ATOM_ON_NTRN, DENOM_LS_ATOM_ON_NTRN] {
+        let party_a_bal = suite.query_balance(&party_a, denom).amount;
+        let party_b_bal = suite.query_balance(&party_b, denom).amount;
+        assert!(!party_a_bal.is_zero());
+        assert!(party_a_bal.abs_diff(party_b_bal) <= Uint128::one());
+    }
+}
+
+#[test]
+fn test_execute_tick_depeg_guard_price_query_failure_expires() {
+    let mut builder = TwoPartyHolderBuilder::default();
+    let mut config = get_depeg_guard_config(&mut builder, DepegAction::Ragequit);
+    // fair price source that is not a pool, failing every price query
+    config.fair_price.source = PriceSource::AstroportTwap {
+        pool_address: builder.builder.get_random_addr().to_string(),
+        seconds_ago: 0,
+    };
+    let mut suite = builder.with_depeg_guard(config).build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.next_contract.clone());
+
+    // the failure is reported without blocking the tick
+    let resp = suite.tick_contract(suite.holder_addr.clone());
+    assert!(resp
+        .events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .any(|a| a.key == "depeg_guard_error"));
+    assert!(!resp.has_event(&Event::new("wasm-depeg_guard")));
+    assert!(suite.query_depeg().is_none());
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    suite.expire_lockup_config();
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Expired {});
+}
+
+#[test]
+fn test_execute_tick_refunds_stalled_provision() {
+    let mut suite = TwoPartyHolderBuilder::default()
+        .with_provisioning_stall(ProvisioningStallConfig {
+            max_stalled_ticks: 1,
+            refund_timeout: Some(100),
+        })
+        .build();
+    suite.fund_contract(
+        &[
+            coin(10_001, DENOM_ATOM_ON_NTRN),
+            coin(10_001, DENOM_LS_ATOM_ON_NTRN),
+        ],
+        suite.holder_addr.clone(),
+    );
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.holder_addr.clone());
+    suite.tick_contract(suite.holder_addr.clone());
+    assert_eq!(suite.query_contract_state(), ContractState::Active);
+
+    suite
+        .app
+        .update_block(|b| b.time = b.time.plus_seconds(100));
+    suite.tick_contract(suite.holder_addr.clone());
+
+    assert_eq!(suite.query_contract_state(), ContractState::Complete);
+    assert!(suite.query_provisioning_stall().is_none());
+    for party in [
+        suite.covenant_config.party_a.clone(),
+        suite.covenant_config.party_b.clone(),
+    ] {
+        suite.assert_balance(
+            party.router.to_string(),
+            coin(10_001, party.contribution.denom.to_string()),
+        );
+        assert_eq!(
+            suite.query_distributions_by_party(&party.host_addr),
+            vec![coin(10_001, party.contribution.denom)]
+        );
+    }
+    suite.assert_balance(suite.next_contract.to_string(), coin(0, DENOM_ATOM_ON_NTRN));
+    suite.assert_balance(
+        suite.next_contract.to_string(),
+        coin(0, DENOM_LS_ATOM_ON_NTRN),
+    );
 }